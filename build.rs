@@ -100,6 +100,9 @@ fn build_pocketpy(_target_os: &str, target_env: &str) {
     // Remove PK_ENABLE_THREADS since PixelScript is single threaded (in theory at least)
     build.define("PK_ENABLE_THREADS", "0");
 
+    // Used by `pxs_set_exec_limit`.
+    build.define("PK_ENABLE_WATCHDOG", "1");
+
     // Now we can compile pocketpy.
     build.compile("pocketpy");
 }
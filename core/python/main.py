@@ -11,3 +11,18 @@ def _pxs_new_register(obj):
     _pxs_register_next_id += 1
     return id
 
+
+def _pxs_overrides(obj, name):
+    # Checks the object, then its classes until a bridge class of the host type.
+    d = obj.__dict__
+    if d is not None and name in d:
+        return True
+    cls = type(obj)
+    while cls is not object:
+        d = cls.__dict__
+        if '_pxs_bridge' in d:
+            return False
+        if name in d:
+            return True
+        cls = cls.__base__
+    return False
@@ -27,4 +27,27 @@ int pxslua_callback(lua_State* L) {
 
     // Num returned (1)
     return result;
-}
\ No newline at end of file
+}
+
+// Count hook used under a execution limit.
+// Raises a error once the Rust side says the limit is exceeded, yields when the host suspends the script.
+void pxslua_limithook(lua_State* L, lua_Debug* ar) {
+    (void)ar;
+    char* err_buf = NULL;
+
+    int result = pxslua_rustlimit(L, lua_isyieldable(L), &err_buf);
+    if (result == 2) {
+        // Hooks can only yield without values.
+        lua_yield(L, 0);
+        return;
+    }
+    if (result) {
+        if (err_buf == NULL) {
+            lua_pushstring(L, "Execution limit exceeded.");
+        } else {
+            lua_pushstring(L, err_buf);
+            pxsutils_freestring(err_buf);
+        }
+        lua_error(L);
+    }
+}
@@ -13,4 +13,13 @@ int pxslua_rustbridge(lua_State* L, char** err_buf);
 // It's up to the bridge to know what function to call. Use upvalues for that.
 int pxslua_callback(lua_State* L);
 
+// Function signature in Rust.
+// Counts instructions under a execution limit. Returns 1 and sets err_buf when the script should stop.
+// Returns 2 when the script should yield, can_yield tells if it can.
+int pxslua_rustlimit(lua_State* L, int can_yield, char** err_buf);
+
+// Count hook used under a execution limit.
+// Raises a error once the Rust side says the limit is exceeded, yields when the host suspends the script.
+void pxslua_limithook(lua_State* L, lua_Debug* ar);
+
 #endif
\ No newline at end of file
@@ -8,6 +8,11 @@
 #include <stdint.h>
 #include <stdlib.h>
 
+/**
+ * Runtime id of the first backend registered with `pxs_register_backend`. Later backends get the next ids.
+ */
+#define PXS_CUSTOM_RUNTIME 16
+
 /**
  * This represents the variable type that is being read or created.
  */
@@ -55,8 +60,41 @@ typedef enum pxs_VarType {
    * Holds 1 byte of memory (u8).
    */
   pxs_Byte,
+  /**
+   * A buffer of raw bytes.
+   * Lua (string), Python (bytes), JS/easyjs (Uint8Array)
+   */
+  pxs_Bytes,
+  /**
+   * Compact numbers for hosts streaming 32 bit data. Scripts get a regular number and always return the 64 bit
+   * types.
+   */
+  pxs_Int32,
+  pxs_UInt32,
+  pxs_Float32,
+  /**
+   * A contiguous buffer of f32, f64, i32 or u8 numbers the host can read and write in place.
+   * Lua (table), Python (list), JS/easyjs (Float32Array, Float64Array, Int32Array, Uint8Array)
+   */
+  pxs_TypedArray,
 } pxs_VarType;
 
+/**
+ * Kind of a exception made by the host with `pxs_newerror`, decides what scripts see: a `TypeError`, `ValueError`,
+ * ... in Python and JavaScript. Lua only gets the message.
+ */
+typedef enum pxs_ErrorKind {
+  /**
+   * `BaseException` in Python, a error named `Exception` in JavaScript.
+   */
+  pxs_GenericError = 0,
+  pxs_TypeError = 1,
+  pxs_ValueError = 2,
+  pxs_KeyError = 3,
+  pxs_IndexError = 4,
+  pxs_NotImplementedError = 5,
+} pxs_ErrorKind;
+
 /**
  * Public enum for supported runtimes.
  */
@@ -76,6 +114,310 @@ typedef enum pxs_Runtime {
   pxs_Wren = 3,
 } pxs_Runtime;
 
+/**
+ * Kind of error returned by `pxs_exec_ex`.
+ */
+typedef enum pxs_ErrorCode {
+  /**
+   * Error while running the script.
+   */
+  pxs_RuntimeError = 1,
+  /**
+   * The script could not be parsed.
+   */
+  pxs_SyntaxError = 2,
+  /**
+   * Invalid arguments were passed to pixelscript.
+   */
+  pxs_InvalidArgument = 3,
+  /**
+   * The script was stopped by `pxs_set_exec_limit`.
+   */
+  pxs_TimeoutError = 4,
+  /**
+   * The script was suspended by the callback of `pxs_set_tick_callback` and could not yield.
+   */
+  pxs_SuspendedError = 5,
+  /**
+   * Called before `pxs_initialize` or after `pxs_finalize`.
+   */
+  pxs_NotInitialized = 6,
+} pxs_ErrorCode;
+
+/**
+ * Operators a host object can overload with `pxs_object_addoperator`.
+ *
+ * Lua uses metamethods (`__add`, `__tostring`, ...), Python dunder methods (`__add__`, `__str__`, ...).
+ * JavaScript has no operator overloading, only `pxs_Operator_ToString` is used (as `toString`).
+ */
+typedef enum pxs_Operator {
+  /**
+   * `a + b`
+   */
+  pxs_Operator_Add = 0,
+  /**
+   * `a - b`
+   */
+  pxs_Operator_Sub = 1,
+  /**
+   * `a * b`
+   */
+  pxs_Operator_Mul = 2,
+  /**
+   * `a == b`
+   */
+  pxs_Operator_Eq = 3,
+  /**
+   * `a < b`
+   */
+  pxs_Operator_Lt = 4,
+  /**
+   * `tostring(a)`/`str(a)`
+   */
+  pxs_Operator_ToString = 5,
+  /**
+   * `#a`/`len(a)`
+   */
+  pxs_Operator_Len = 6,
+  /**
+   * `a[key]`. In Lua only called for keys that are not a method or property.
+   */
+  pxs_Operator_Index = 7,
+} pxs_Operator;
+
+/**
+ * Result of a checked accessor like `pxs_getint_checked`.
+ */
+typedef enum pxs_Status {
+  /**
+   * The value was converted.
+   */
+  pxs_StatusOk = 0,
+  /**
+   * The var or out pointer is null.
+   */
+  pxs_StatusNull = 1,
+  /**
+   * The var is not a number (or bool).
+   */
+  pxs_StatusWrongType = 2,
+  /**
+   * The value does not fit in the target type. Also used for NaN and infinity.
+   */
+  pxs_StatusOverflow = 3,
+  /**
+   * The value would lose precision and the policy is `pxs_ConvertError`.
+   */
+  pxs_StatusInexact = 4,
+  /**
+   * Called before `pxs_initialize` or after `pxs_finalize`.
+   */
+  pxs_StatusNotInitialized = 5,
+} pxs_Status;
+
+/**
+ * What a checked accessor does with values that can not be represented exactly (i.e. `2.5` as a int).
+ */
+typedef enum pxs_Convert {
+  /**
+   * Drop the fraction, like a C cast.
+   */
+  pxs_ConvertTruncate = 0,
+  /**
+   * Round to the nearest, halfway away from zero.
+   */
+  pxs_ConvertRound = 1,
+  /**
+   * Fail with `pxs_StatusInexact`.
+   */
+  pxs_ConvertError = 2,
+} pxs_Convert;
+
+/**
+ * What happens to invalid input when converting between UTF-16 and the UTF-8 of a `pxs_String`.
+ *
+ * Unpaired surrogates (UTF-16), invalid bytes (UTF-8) and null characters, which a `pxs_String` can not hold, are
+ * invalid.
+ */
+typedef enum pxs_Utf16Policy {
+  /**
+   * Replace invalid input with U+FFFD. The default.
+   */
+  pxs_Utf16Replace = 0,
+  /**
+   * Drop invalid input.
+   */
+  pxs_Utf16Skip = 1,
+  /**
+   * Fail the conversion.
+   */
+  pxs_Utf16Error = 2,
+} pxs_Utf16Policy;
+
+/**
+ * What happens when a value crossing between a runtime and the host is over the clone budget.
+ */
+typedef enum pxs_BudgetMode {
+  /**
+   * No checks. The default.
+   */
+  pxs_BudgetOff = 0,
+  /**
+   * Print a warning and let the value through.
+   */
+  pxs_BudgetWarn = 1,
+  /**
+   * Replace the value with a `pxs_Exception`.
+   */
+  pxs_BudgetError = 2,
+} pxs_BudgetMode;
+
+/**
+ * Lifetime events of a host object. Sent to the `pxs_set_object_observer` callback.
+ */
+typedef enum pxs_ObjectEvent {
+  /**
+   * The object got its language representation, i.e. it was passed into a runtime.
+   */
+  pxs_ObjectWrapped = 0,
+  /**
+   * The last reference was dropped (i.e. by the script GC). The object is about to be finalized.
+   */
+  pxs_ObjectReleased = 1,
+  /**
+   * The object is being freed. Its `free_method` runs right after this.
+   */
+  pxs_ObjectFinalized = 2,
+} pxs_ObjectEvent;
+
+/**
+ * State of a `pxs_Coroutine`. Returned by `pxs_coroutine_status`.
+ */
+typedef enum pxs_CoroutineStatus {
+  /**
+   * Not started yet, or waiting at a `yield`.
+   */
+  pxs_CoroutineSuspended = 0,
+  /**
+   * Inside `pxs_coroutine_resume`.
+   */
+  pxs_CoroutineRunning = 1,
+  /**
+   * Returned or failed. Can not be resumed.
+   */
+  pxs_CoroutineDead = 2,
+} pxs_CoroutineStatus;
+
+/**
+ * How low the host is on memory. Passed to `pxs_notify_memory_pressure`.
+ */
+typedef enum pxs_MemoryPressure {
+  /**
+   * Run a full garbage collection in every runtime.
+   */
+  pxs_MemoryModerate = 0,
+  /**
+   * Also drop cached object bridges (Lua metatables, pocketpy classes, QuickJS prototypes) and the caches
+   * pixelscript owns, see `trim_caches`. They are made again when needed.
+   */
+  pxs_MemoryCritical = 1,
+} pxs_MemoryPressure;
+
+/**
+ * Element type of a `pxs_TypedArray`.
+ */
+typedef enum pxs_ArrayKind {
+  pxs_ArrayF32 = 0,
+  pxs_ArrayF64 = 1,
+  pxs_ArrayI32 = 2,
+  pxs_ArrayU8 = 3,
+} pxs_ArrayKind;
+
+/**
+ * Level of a `pxs_log` message.
+ */
+typedef enum pxs_LogLevel {
+  pxs_LogDebug = 0,
+  pxs_LogInfo = 1,
+  pxs_LogWarn = 2,
+  pxs_LogError = 3,
+} pxs_LogLevel;
+
+/**
+ * Value type of a record in a sync buffer.
+ *
+ * Every value is little endian.
+ */
+typedef enum pxs_SyncType {
+  /**
+   * No payload.
+   */
+  pxs_SyncNull = 0,
+  /**
+   * 8 byte signed integer.
+   */
+  pxs_SyncInt64 = 1,
+  /**
+   * 8 byte float.
+   */
+  pxs_SyncFloat64 = 2,
+  /**
+   * 4 byte signed integer.
+   */
+  pxs_SyncInt32 = 3,
+  /**
+   * 4 byte float.
+   */
+  pxs_SyncFloat32 = 4,
+  /**
+   * 1 byte, 0 is false.
+   */
+  pxs_SyncBool = 5,
+  /**
+   * 4 byte length followed by UTF-8 bytes.
+   */
+  pxs_SyncString = 6,
+} pxs_SyncType;
+
+typedef struct Option_pxs_ClockTimeFn Option_pxs_ClockTimeFn;
+
+typedef struct Option_pxs_DeleterFn Option_pxs_DeleterFn;
+
+typedef struct Option_pxs_HttpHandlerFn Option_pxs_HttpHandlerFn;
+
+typedef struct Option_pxs_LoadFileFn Option_pxs_LoadFileFn;
+
+typedef struct Option_pxs_LogSinkFn Option_pxs_LogSinkFn;
+
+typedef struct Option_pxs_ReadDirFn Option_pxs_ReadDirFn;
+
+typedef struct Option_pxs_TickFn Option_pxs_TickFn;
+
+/**
+ * Scripts by path. Created by `pxs_bundle_new`.
+ */
+typedef struct pxs_Bundle pxs_Bundle;
+
+/**
+ * A class of a module. Holds static methods, class constants and the constructor, which is called when the class
+ * itself is called (`Vec3(1, 2, 3)`).
+ *
+ * In Lua this is a table with a `__call` metamethod, in Python a class and in JavaScript the constructor function
+ * with the statics and constants as properties.
+ */
+typedef struct pxs_Class pxs_Class;
+
+/**
+ * A isolated set of runtimes. Created by `pxs_context_new` and freed by `pxs_context_free`.
+ */
+typedef struct pxs_Context pxs_Context;
+
+/**
+ * A script function driven by the host across frames. Created by `pxs_coroutine_create` and freed by
+ * `pxs_coroutine_free`.
+ */
+typedef struct pxs_Coroutine pxs_Coroutine;
+
 /**
  * A Factory variable data holder.
  *
@@ -84,6 +426,11 @@ typedef enum pxs_Runtime {
  */
 typedef struct pxs_FactoryHolder pxs_FactoryHolder;
 
+/**
+ * A lazy iterator over a script collection. Created by `pxs_iter_new` and freed by `pxs_iter_free`.
+ */
+typedef struct pxs_Iterator pxs_Iterator;
+
 /**
  * A Module is a C representation of data that needs to be (imported,required, etc)
  *
@@ -184,6 +531,27 @@ typedef struct pxs_PixelArena pxs_PixelArena;
  */
 typedef struct pxs_PixelObject pxs_PixelObject;
 
+/**
+ * A script thread. Created by `pxs_thread_spawn` and freed by `pxs_thread_join`.
+ */
+typedef struct pxs_Thread pxs_Thread;
+
+/**
+ * Holds data for a pxs_Var of bytes.
+ *
+ * Unlike a `pxs_List` of `pxs_Byte`s the data is stored contiguously and goes into the scripting language
+ * as its native binary type.
+ *
+ * When creating call:
+ *
+ * `pxs_newbytebuf(ptr, len)`.
+ *
+ * To read the data
+ *
+ * `pxs_getbytes(bytes_ptr, &len)`
+ */
+typedef struct pxs_VarBytes pxs_VarBytes;
+
 /**
  * Holds data for a pxs_Var of list.
  *
@@ -230,6 +598,22 @@ typedef struct pxs_VarMap pxs_VarMap;
  */
 typedef struct pxs_VarObject pxs_VarObject;
 
+/**
+ * Holds data for a pxs_Var of a typed array.
+ *
+ * Like `pxs_VarBytes` the data is stored contiguously, but as numbers of one `pxs_ArrayKind`. Scripts get their
+ * native typed array where there is one (JS) and a list of numbers otherwise.
+ *
+ * When creating call:
+ *
+ * `pxs_newtypedarray(kind, ptr, len)`.
+ *
+ * To read or write the data in place
+ *
+ * `pxs_gettypedarray(array_ptr, &kind, &len)`
+ */
+typedef struct pxs_VarTypedArray pxs_VarTypedArray;
+
 /**
  * The Variables actual value union.
  */
@@ -247,6 +631,11 @@ typedef union pxs_VarValue {
   struct pxs_FactoryHolder *factory_val;
   struct pxs_VarMap *map_val;
   uint8_t byte_val;
+  struct pxs_VarBytes *bytes_val;
+  struct pxs_VarTypedArray *typed_array_val;
+  int32_t i32_val;
+  uint32_t u32_val;
+  float f32_val;
 } pxs_VarValue;
 
 /**
@@ -273,6 +662,7 @@ typedef void (*pxs_DeleterFn)(void*);
  * - Factory (a function that is run on the fly and its result is treated as a variable.)
  * - Exception
  * - byte (u8)
+ * - Bytes (buffer of u8)
  *
  * When working with objects you must use the C-api:
  * ```c
@@ -299,6 +689,19 @@ typedef struct pxs_Var {
    * Optional delete method. This is used for Pointers in Objects, and Functions.
    */
   pxs_DeleterFn deleter;
+  /**
+   * The runtime (`pxs_Runtime`) that produced this var, -1 for vars made by the host. Set by the language
+   * converters for Objects and Functions.
+   */
+  int32_t origin;
+  /**
+   * The VM (thread) that produced this var, -1 for vars made by the host. Set together with `origin`.
+   */
+  int32_t vm;
+  /**
+   * Kind of a `pxs_Exception`, see `pxs_newerror`. `pxs_GenericError` for every other var.
+   */
+  enum pxs_ErrorKind error_kind;
 } pxs_Var;
 
 /**
@@ -307,6 +710,26 @@ typedef struct pxs_Var {
  */
 typedef struct pxs_Var *pxs_VarT;
 
+/**
+ * A structured script error. Free with `pxs_error_free`.
+ *
+ * `file` and `traceback` can be null. `line` and `column` are 0 when unknown.
+ */
+typedef struct pxs_Error {
+  enum pxs_ErrorCode code;
+  /**
+   * The error message without location info.
+   */
+  char *message;
+  char *file;
+  int32_t line;
+  int32_t column;
+  /**
+   * The full error as reported by the runtime.
+   */
+  char *traceback;
+} pxs_Error;
+
 /**
  * Function reference used in C.
  *
@@ -331,55 +754,432 @@ typedef pxs_VarT (*pxs_LoadFileFn)(const char *file_path);
  */
 typedef pxs_VarT (*pxs_ReadDirFn)(const char *dir_path);
 
-#ifdef __cplusplus
-extern "C" {
-#endif // __cplusplus
-
 /**
- * Current pixelscript version.
+ * A module found by a `pxs_ModuleResolverFn`.
  */
-uint32_t pxs_version(void);
+typedef struct pxs_ResolvedModule {
+  /**
+   * Source of the module, a `pxs_String`. Null if the resolver does not have the module. TRANSFER (NULLABLE)
+   */
+  pxs_VarT source;
+  /**
+   * Canonical path of the module, a `pxs_String`. Null to keep the requested name. TRANSFER (NULLABLE)
+   */
+  pxs_VarT path;
+} pxs_ResolvedModule;
 
 /**
- * Initialize the PixelScript runtime.
+ * Function Type for resolving a imported module. Used by `pxs_add_module_resolver`.
+ *
+ * name:BORROW
  */
-void pxs_initialize(void);
+typedef struct pxs_ResolvedModule (*pxs_ModuleResolverFn)(const char *name, enum pxs_Runtime runtime);
 
 /**
- * Finalize the PixelScript runtime.
+ * Function Type for writing a file. Used by `pxs_fs`. Should return false if the file could not be written.
+ *
+ * file_path:BORROW
+ * contents:BORROW
  */
-void pxs_finalize(void);
+typedef bool (*pxs_WriteFileFn)(const char *file_path, const char *contents);
 
 /**
- * Execute code in a runtime. Will return a pxs_VarT. Null means no error, otherwise error.
+ * Function Type for localizing pixelscript error messages.
  *
- * return:OWNED
+ * Return a `pxs_String` template using `{0}`, `{1}`, ... for the arguments, or null to keep `default`.
+ *
+ * key:BORROW
+ * default:BORROW
+ * return:TRANSFER
  */
-pxs_VarT pxs_exec(enum pxs_Runtime runtime, const char *code, const char *file_name);
+typedef pxs_VarT (*pxs_MessageCatalogFn)(const char *key, const char *default);
 
 /**
- * Free the string created by the pixelscript library
+ * A language implementation supplied by the host. Passed to `pxs_register_backend`.
  *
- * string:TRANSFER
+ * Every function gets `opaque` as its first argument. Any function can be NULL, calling it then returns a
+ * `pxs_Exception` (or does nothing for `start`, `stop`, `add_module` and `remove_module`).
+ *
+ * Vars passed to the functions are borrowed, returned vars are owned by pixelscript. Wrap language functions and
+ * objects with `pxs_backend_newvar` so `pxs_varcall`, `pxs_objectcall`, `pxs_objectget` and `pxs_objectset` are
+ * routed back to the backend.
  */
-void pxs_freestr(char *string);
+typedef struct pxs_BackendVTable {
+  /**
+   * Name of the language, used in errors. Copied on registration.
+   */
+  const char *name;
+  /**
+   * Host data passed to every function.
+   */
+  pxs_Opaque opaque;
+  /**
+   * Called on registration with the runtime id of the backend.
+   */
+  void (*start)(pxs_Opaque opaque, int32_t runtime);
+  /**
+   * Called on `pxs_finalize`.
+   */
+  void (*stop)(pxs_Opaque opaque);
+  /**
+   * A module was added. `module` is a Map of
+   * `{name, functions: [{name, handle}], objects: [{name, handle}], variables: [{name, value}], classes: [{name,
+   * constructor, functions, variables}], modules: [...]}`. A class without constructor has a `constructor` of -1.
+   * Call functions with `pxs_call_handle`.
+   */
+  void (*add_module)(pxs_Opaque opaque, pxs_VarT module);
+  /**
+   * A module was removed with `pxs_removemod` or `pxs_reloadmod`.
+   */
+  void (*remove_module)(pxs_Opaque opaque, const char *name);
+  /**
+   * Execute code. Return NULL on success or a `pxs_Exception`.
+   */
+  pxs_VarT (*execute)(pxs_Opaque opaque, const char *code, const char *file_name);
+  /**
+   * Evaluate code and return the result.
+   */
+  pxs_VarT (*eval)(pxs_Opaque opaque, const char *code, const char *name);
+  /**
+   * Compile code for `exec_object`. Return a List, pixelscript inserts the runtime id at index 0.
+   */
+  pxs_VarT (*compile)(pxs_Opaque opaque, const char *code, pxs_VarT global_scope);
+  /**
+   * Execute a List returned by `compile` (with the runtime id at index 0).
+   */
+  pxs_VarT (*exec_object)(pxs_Opaque opaque, pxs_VarT code, pxs_VarT local_scope);
+  /**
+   * Call a function var with a List of args.
+   */
+  pxs_VarT (*call)(pxs_Opaque opaque, pxs_VarT func, pxs_VarT args);
+  /**
+   * Call a method of a object var with a List of args.
+   */
+  pxs_VarT (*object_call)(pxs_Opaque opaque, pxs_VarT obj, const char *method, pxs_VarT args);
+  /**
+   * Get a property of a object var.
+   */
+  pxs_VarT (*object_get)(pxs_Opaque opaque, pxs_VarT obj, const char *key);
+  /**
+   * Set a property of a object var. Return false on failure.
+   */
+  bool (*object_set)(pxs_Opaque opaque, pxs_VarT obj, const char *key, pxs_VarT value);
+} pxs_BackendVTable;
 
 /**
- * Create a new pixelscript Module.
+ * Function Type for observing host object lifetimes.
  *
- * return:OWNED
+ * type_name:BORROW
  */
-struct pxs_Module *pxs_newmod(const char *name);
+typedef void (*pxs_ObjectObserverFn)(enum pxs_ObjectEvent event, const char *type_name, int32_t idx);
 
 /**
- * Add a callback to a module.
+ * Function Type called when a script GC collects the language object of a host object. Used by
+ * `pxs_set_object_destructor_hook`.
  *
- * Pass in the modules pointer and callback paramaters.
+ * type_name:BORROW
+ * ptr:BORROW
+ */
+typedef void (*pxs_ObjectDestructorFn)(const char *type_name, void *ptr);
+
+/**
+ * Result of `pxs_call_try`, `pxs_objectcall_try` and `pxs_varcall_try`.
+ *
+ * On success `value` is the returned var (free with `pxs_free_var`) and `error` is null. On failure `value` is null
+ * and `error` has the message and traceback (free with `pxs_error_free`).
+ */
+typedef struct pxs_CallResult {
+  bool ok;
+  struct pxs_Var *value;
+  struct pxs_Error *error;
+} pxs_CallResult;
+
+/**
+ * Function Type for being notified when a mod gets quarantined.
+ *
+ * mod_name:BORROW
+ * error:BORROW
+ */
+typedef void (*pxs_QuarantineFn)(const char *mod_name, const char *error);
+
+/**
+ * Function Type for getting the cost of entering a grid cell. Used by `pxs_path`.
+ * Return a negative number for blocked cells.
+ */
+typedef double (*pxs_PathCostFn)(int32_t x, int32_t y);
+
+/**
+ * Function Type for playing a audio event. Used by `pxs_audio`. Should return a host voice id.
+ *
+ * event:BORROW
+ * params:BORROW
+ */
+typedef int64_t (*pxs_AudioPlayFn)(const char *event, pxs_VarT params);
+
+/**
+ * Function Type for setting a parameter on a playing voice. Used by `pxs_audio`.
+ *
+ * name:BORROW
+ * value:BORROW
+ */
+typedef void (*pxs_AudioParamFn)(int64_t voice, const char *name, pxs_VarT value);
+
+/**
+ * Function Type for stopping a voice. Used by `pxs_audio`.
+ */
+typedef void (*pxs_AudioStopFn)(int64_t voice);
+
+/**
+ * Function Type for receiving bound UI value changes. Used by `pxs_ui`.
+ *
+ * widget_id:BORROW
+ * value:BORROW
+ */
+typedef void (*pxs_UiChangeFn)(const char *widget_id, pxs_VarT value);
+
+/**
+ * Function Type for reading a time in seconds. Used by `pxs_clock` and `pxs_time`.
+ */
+typedef double (*pxs_ClockTimeFn)(void);
+
+/**
+ * Function Type for reading the current frame index. Used by `pxs_clock`.
+ */
+typedef int64_t (*pxs_ClockFrameFn)(void);
+
+/**
+ * Function Type for running a job submitted by a script. Used by `pxs_jobs`.
+ *
+ * The host reports completion with `pxs_job_complete(id, result)`, now or later.
+ *
+ * kind:BORROW
+ * payload:BORROW
+ */
+typedef void (*pxs_JobSubmitFn)(int64_t id, const char *kind, pxs_VarT payload);
+
+/**
+ * Function Type for receiving script log messages. Used by `pxs_log`.
+ *
+ * `file` is empty and `line` is 0 when the location is unknown.
+ *
+ * message:BORROW
+ * file:BORROW
+ */
+typedef void (*pxs_LogSinkFn)(enum pxs_LogLevel level,
+                              const char *message,
+                              const char *file,
+                              int32_t line);
+
+/**
+ * The response to a `pxs_HttpRequest`, filled by the host.
+ */
+typedef struct pxs_HttpResponse {
+  /**
+   * HTTP status code.
+   */
+  int32_t status;
+  /**
+   * `pxs_Map` of header names to string values. TRANSFER (NULLABLE)
+   */
+  pxs_VarT headers;
+  /**
+   * A string or bytes var. TRANSFER (NULLABLE)
+   */
+  pxs_VarT body;
+  /**
+   * Message of a request that got no response (i.e. a unreachable host), scripts get it as a exception.
+   * TRANSFER (NULLABLE)
+   */
+  pxs_VarT error;
+} pxs_HttpResponse;
+
+/**
+ * A HTTP request made by a script with `pxs_http`. Only valid during the `pxs_HttpHandlerFn` call.
+ */
+typedef struct pxs_HttpRequest {
+  /**
+   * `GET` or `POST`. BORROW
+   */
+  const char *method;
+  /**
+   * BORROW
+   */
+  const char *url;
+  /**
+   * `pxs_Map` of header names to string values. BORROW
+   */
+  pxs_VarT headers;
+  /**
+   * A string or bytes var, null for no body. BORROW (NULLABLE)
+   */
+  pxs_VarT body;
+  /**
+   * Timeout in seconds, 0 for the host default.
+   */
+  double timeout;
+} pxs_HttpRequest;
+
+/**
+ * Function Type for doing the network IO of `pxs_http`. May block, the calling script waits for the response.
+ *
+ * request:BORROW
+ */
+typedef struct pxs_HttpResponse (*pxs_HttpHandlerFn)(const struct pxs_HttpRequest *request);
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Current pixelscript version.
+ */
+uint32_t pxs_version(void);
+
+/**
+ * Initialize the PixelScript runtime. Can be called again after `pxs_finalize` to restart scripting with fresh
+ * runtimes.
+ *
+ * Safe to call from any thread and more than once. The first call starts the runtimes on its thread, calls racing it
+ * wait until it is done. Other threads still call `pxs_startthread` for runtimes of their own.
+ */
+void pxs_initialize(void);
+
+/**
+ * Finalize the PixelScript runtime.
+ *
+ * Teardown happens in this order:
+ * 1. Scripts global `on_unload` functions are called.
+ * 2. Vars pinned by core modules (i.e. `pxs_ui` bindings, `pxs_save` migrations) are dropped.
+ * 3. Host objects are dropped, calling their `FreeMethod`.
+ * 4. Functions are dropped.
+ * 5. Runtimes (and backends from `pxs_register_backend`) are stopped.
+ *
+ * Afterwards `pxs_initialize` starts fresh runtimes. Host settings (file readers, callbacks, limits) are kept.
+ *
+ * Only one thread finalizes, `pxs_initialize` calls on other threads wait until it is done.
+ */
+void pxs_finalize(void);
+
+/**
+ * Execute code in a runtime. Will return a pxs_VarT. Null means no error, otherwise error.
+ *
+ * return:OWNED
+ */
+pxs_VarT pxs_exec(enum pxs_Runtime runtime, const char *code, const char *file_name);
+
+/**
+ * Execute code in a runtime and return its result: the value of a top level `return` in Lua, of the last line if it
+ * is a expression in Python and the completion value in JavaScript (run as a global script, not a module). Returns a
+ * `pxs_Null` if there is none.
+ *
+ * If the script fails null is returned and the exception is written to `out_err` (if not null).
+ *
+ * out_err:OWNED
+ * return:OWNED
+ */
+pxs_VarT pxs_exec_with_result(enum pxs_Runtime runtime,
+                              const char *code,
+                              const char *file_name,
+                              pxs_VarT *out_err);
+
+/**
+ * Limit how long a single `pxs_exec` (or `pxs_exec_ex`) call can run in a runtime, to protect against scripts like
+ * `while true do end`. `0` is unlimited, both `0` removes the limit. Limits are per thread.
+ *
+ * Instructions are counted approximately: Lua counts VM instructions in steps of 1000, pocketpy counts executed
+ * lines and QuickJS counts in steps of its interrupt counter. `max_millis` is wall time checked at the same points, so
+ * time spent waiting in a host call counts but the script is only stopped once it runs again. A stopped script
+ * returns a exception (or a `pxs_TimeoutError` from `pxs_exec_ex`) with the `exec_limit` message.
+ */
+void pxs_set_exec_limit(enum pxs_Runtime runtime,
+                        int64_t max_instructions,
+                        int64_t max_millis);
+
+/**
+ * Call `func` about every `every_n_instructions` instructions while a `pxs_exec` (or `pxs_coroutine_resume`) runs,
+ * for frame budgeted execution of long scripts. Instructions are counted like `pxs_set_exec_limit`. Null removes the
+ * callback. Per thread.
+ *
+ * Returning true from `func` suspends the script. A Lua coroutine yields, the next `pxs_coroutine_resume` continues
+ * where it was. Anything else is stopped with a exception (or a `pxs_SuspendedError` from `pxs_exec_ex`) with the
+ * `exec_suspended` message.
+ */
+void pxs_set_tick_callback(struct Option_pxs_TickFn func,
+                           int64_t every_n_instructions);
+
+/**
+ * Execute code in a runtime. Same as `pxs_exec` but returns a structured `pxs_Error` with the message, file, line,
+ * column and traceback. Returns null on success.
+ *
+ * Free the error with `pxs_error_free`.
+ *
+ * return:OWNED
+ */
+struct pxs_Error *pxs_exec_ex(enum pxs_Runtime runtime,
+                              const char *code,
+                              const char *file_name);
+
+/**
+ * Free a `pxs_Error` returned by `pxs_exec_ex`.
+ *
+ * error:TRANSFER
+ */
+void pxs_error_free(struct pxs_Error *error);
+
+/**
+ * Take the last error of the calling thread, i.e. a call made before `pxs_initialize`. Returns null if there was
+ * none. Free it with `pxs_freestr`.
+ *
+ * return:OWNED
+ */
+char *pxs_last_error(void);
+
+/**
+ * Is the library initialized? False before `pxs_initialize` and after `pxs_finalize`.
+ */
+bool pxs_is_initialized(void);
+
+/**
+ * Free the string created by the pixelscript library
+ *
+ * string:TRANSFER
+ */
+void pxs_freestr(char *string);
+
+/**
+ * Create a new pixelscript Module.
+ *
+ * return:OWNED
+ */
+struct pxs_Module *pxs_newmod(const char *name);
+
+/**
+ * Add a callback to a module.
+ *
+ * Pass in the modules pointer and callback paramaters.
  *
  * module_ptr:BORROW
  */
 void pxs_addfunc(struct pxs_Module *module_ptr, const char *name, pxs_Func func);
 
+/**
+ * Add a callback with declared arguments to a module.
+ *
+ * `signature` is a map with `params`, a list of maps with a `name`, a optional `type` (a type name or list of type
+ * names like `pxs_validate`) and a optional `default`, and optional `varargs`, `true` or the type name(s) of extra
+ * arguments. Lua and Python check calls before `func` is called: missing arguments get their default, ints are
+ * converted for `float` params and whole floats for `int` params, anything else raises a script error (a
+ * `TypeError` in Python). `opaque` is returned by `pxs_funcopaque` during calls.
+ *
+ * module_ptr:BORROW
+ * signature:TRANSFER
+ */
+void pxs_addfunc_ex(struct pxs_Module *module_ptr,
+                    const char *name,
+                    pxs_Func func,
+                    pxs_Opaque opaque,
+                    pxs_VarT signature);
+
 /**
  * Add the same function under different names.
  *
@@ -419,6 +1219,41 @@ void pxs_add_submod(struct pxs_Module *parent_ptr, struct pxs_Module *child_ptr)
  */
 void pxs_addmod(struct pxs_Module *module_ptr);
 
+/**
+ * Share a script module with the other runtimes, i.e. so Lua can `require` a module written in Python.
+ *
+ * `name` is imported in `runtime` with its own loader (`require`, `import`) and a module of the same name is added to
+ * every other runtime. Its functions call the functions of the source module, other exports are copied once.
+ * Arguments and results are copied between the runtimes: tables, dicts and objects become maps and functions can not
+ * be passed. Like `pxs_addmod` this needs to be called in each thread that uses the module.
+ *
+ * return:OWNED (null on success, otherwise a exception)
+ */
+pxs_VarT pxs_share_module(enum pxs_Runtime runtime,
+                          const char *name);
+
+/**
+ * Replace a module added with `pxs_addmod` by a new version with the same name, so host APIs can be iterated on
+ * without restarting the process. If no module with this name was added it is just added.
+ *
+ * The old functions are released, Lua's `package.loaded` entry is invalidated so the next `require` gets the new
+ * module, and the pocketpy module is refilled in place. Returns true if a module was replaced.
+ *
+ * module_ptr:TRANSFER
+ */
+bool pxs_reloadmod(struct pxs_Module *module_ptr);
+
+/**
+ * Remove a module added with `pxs_addmod`, i.e. when unloading a plugin. Child modules are removed too.
+ *
+ * The module is removed from Lua `package.preload` and `package.loaded`, emptied in pocketpy and no longer resolved
+ * by the JS module loader. Its functions are released from the function lookup, so script values still referencing
+ * them return null when called. Returns false if no module with this name was added.
+ *
+ * name:BORROW
+ */
+bool pxs_removemod(const char *name);
+
 /**
  * Optionally free a module if you changed your mind.
  *
@@ -483,6 +1318,21 @@ void pxs_object_addprop(struct pxs_PixelObject *ptr,
                         const char *name,
                         pxs_Func callback);
 
+/**
+ * Overload a operator of a object, i.e. `+` for a `Vec3`. Adding the same operator again replaces it.
+ *
+ * `callback` receives the runtime, the object (`_pxs_ptr`) and the other operand if there is one, same as
+ * `pxs_object_addfunc`. The object is always the left operand, `v * 2` works but `2 * v` does not.
+ * Use `pxs_funcopaque` inside the callback to get `opaque`.
+ *
+ * object_ptr:BORROW
+ * opaque:BORROW
+ */
+void pxs_object_addoperator(struct pxs_PixelObject *object_ptr,
+                            enum pxs_Operator operator_,
+                            pxs_Func callback,
+                            pxs_Opaque opaque);
+
 /**
  * Add a object constructor to a module. This is the same as calling `pxs_addfunc`. Only named differently to distinguish
  * when a function should be treated as a Object or a Function in your code.
@@ -495,6 +1345,58 @@ void pxs_addobject(struct pxs_Module *module_ptr,
                    const char *name,
                    pxs_Func object_constructor);
 
+/**
+ * Add a class to a module, i.e. `Vec3` with `Vec3.ZERO`, `Vec3.dot(a, b)` and `Vec3(1, 2, 3)`.
+ *
+ * Returns a handle for adding static methods (`pxs_class_addfunc`), constants (`pxs_class_addvar`) and the
+ * constructor (`pxs_class_setconstructor`). The handle belongs to the module and is valid until the module is added.
+ *
+ * module_ptr:BORROW
+ * return:BORROW
+ */
+struct pxs_Class *pxs_addclass(struct pxs_Module *module_ptr,
+                               const char *type_name);
+
+/**
+ * Add a static method to a class. It is called without a object, `callback` receives the runtime followed by the
+ * script args like a module function.
+ *
+ * class_ptr:BORROW
+ */
+void pxs_class_addfunc(struct pxs_Class *class_ptr,
+                       const char *name,
+                       pxs_Func callback);
+
+/**
+ * Add a constant to a class.
+ *
+ * class_ptr:BORROW
+ * variable:TRANSFER
+ */
+void pxs_class_addvar(struct pxs_Class *class_ptr, const char *name, pxs_VarT variable);
+
+/**
+ * Set the constructor of a class, called when scripts call the class. `callback` receives the runtime followed by
+ * the script args (not the class) and should return a `pxs_HostObject`. Setting it again replaces it.
+ *
+ * class_ptr:BORROW
+ */
+void pxs_class_setconstructor(struct pxs_Class *class_ptr,
+                              pxs_Func callback);
+
+/**
+ * Make objects of `child_type` inherit the methods, properties and operators of `parent_type`, i.e. `Enemy` from
+ * `Entity`. Objects of the child type only need their own callbacks.
+ *
+ * Lua chains the metatables, Python uses the parent bridge class as base class and JavaScript chains the prototypes.
+ * The parent bridge is made from the callbacks of the last `parent_type` object, so create one before a child
+ * object reaches a script. Call this before objects of the child type are passed to a script.
+ *
+ * Returns false if a name is null or `parent_type` already inherits from `child_type`.
+ */
+bool pxs_class_setparent(const char *child_type,
+                         const char *parent_type);
+
 /**
  * Make a new Var string.
  *
@@ -512,13 +1414,32 @@ pxs_VarT pxs_newnull(void);
 /**
  * Make a new HostObject var.
  *
- * Transfers ownership
+ * Transfers ownership. Returns a `pxs_Exception` if the object lookup is full.
  *
  * pixel_object:TRANSFER
  * return:OWNED
  */
 pxs_VarT pxs_newhost(struct pxs_PixelObject *pixel_object);
 
+/**
+ * Keep the host object `idx` alive after scripts stop referencing it. Every retain needs a `pxs_object_release`.
+ *
+ * Returns false if there is no such object.
+ */
+bool pxs_object_retain(int32_t idx);
+
+/**
+ * Drop a host reference of the host object `idx`, from `pxs_object_retain` or the one it was created with if it never
+ * reached a script. The object is freed once neither the host nor a script references it.
+ *
+ * Host objects are owned by their language object (the Lua table or Python instance) once they are passed to a script,
+ * and freed when the script GC collects it. JavaScript keeps its objects until they are released here or with
+ * `pxs_mem.memdel`.
+ *
+ * Returns false if there is no such object.
+ */
+bool pxs_object_release(int32_t idx);
+
 /**
  * Create a new variable int. (i64)
  *
@@ -547,6 +1468,27 @@ pxs_VarT pxs_newbool(bool val);
  */
 pxs_VarT pxs_newfloat(double val);
 
+/**
+ * Create a new variable int32. Scripts get a regular int.
+ *
+ * return:OWNED
+ */
+pxs_VarT pxs_newint32(int32_t val);
+
+/**
+ * Create a new variable uint32. Scripts get a regular int.
+ *
+ * return:OWNED
+ */
+pxs_VarT pxs_newuint32(uint32_t val);
+
+/**
+ * Create a new variable float32. Scripts get a regular float.
+ *
+ * return:OWNED
+ */
+pxs_VarT pxs_newfloat32(float val);
+
 /**
  * Call a function on a object, and use a Enum for runtime rather than a var.
  *
@@ -566,7 +1508,11 @@ pxs_VarT pxs_object_callrt(enum pxs_Runtime runtime,
  *
  * All memory is borrowed except for args. But the var returned need to be freed on host side if not returned by a function.
  *
- * You can get the runtime from the first Var in any callback.
+ * You can get the runtime from the first Var in any callback. If runtime is null the runtime that produced `var` is
+ * used, passing a different runtime is a `runtime_mismatch` exception.
+ *
+ * `var` can also be a `pxs_HostObject`, then its language object is called. Methods overridden by a script (see
+ * `pxs_object_is_overridden`) are called instead of the host methods.
  *
  * Example
  * ```C
@@ -585,6 +1531,21 @@ pxs_VarT pxs_objectcall(struct pxs_Var *runtime,
                         const char *method,
                         struct pxs_Var *args);
 
+/**
+ * Check if the script overrides `method` of a object: a script subclass defines it (`class Boss(Enemy)` in Python)
+ * or a function was assigned to the object (`function boss:attack() end` in Lua). Use it to only call script hooks
+ * with `pxs_objectcall` when a script has one.
+ *
+ * `var` can be a `pxs_HostObject` or its language object. If runtime is null the runtime that produced `var` is used.
+ * Returns false for errors, methods of the host type and vars of custom backends.
+ *
+ * runtime:BORROW
+ * var:BORROW
+ */
+bool pxs_object_is_overridden(pxs_VarT runtime,
+                              pxs_VarT var,
+                              const char *method);
+
 /**
  * Get a int (i64) from a var.
  *
@@ -607,390 +1568,1339 @@ uint64_t pxs_getuint(struct pxs_Var *var);
 double pxs_getfloat(struct pxs_Var *var);
 
 /**
- * Get a Bool
+ * Get a int32 from a var. Any number is cast like `pxs_getint`, use `pxs_getint_checked` to catch overflows.
  *
- * CAN_CRASH
  * var:BORROW
  */
-bool pxs_getbool(struct pxs_Var *var);
+int32_t pxs_getint32(pxs_VarT var);
 
 /**
- * Get a String
- *
- * CAN_CRASH, CALLER
- *
- * You have to free this memory by calling `pxs_free_str`
+ * Get a uint32 from a var. Any number is cast like `pxs_getuint`, use `pxs_getuint_checked` to catch overflows.
  *
  * var:BORROW
- * return:OWNED
  */
-char *pxs_getstring(struct pxs_Var *var);
+uint32_t pxs_getuint32(pxs_VarT var);
 
 /**
- * Check if a variable is of a type.
+ * Get a float32 from a var. Any number is cast like `pxs_getfloat`.
  *
  * var:BORROW
  */
-bool pxs_varis(struct pxs_Var *var, enum pxs_VarType var_type);
+float pxs_getfloat32(pxs_VarT var);
 
 /**
- * Set a function for reading a file.
+ * Get a Bool
  *
- * This is used to load files via import, require, etc
+ * CAN_CRASH
+ * var:BORROW
  */
-void pxs_set_filereader(pxs_LoadFileFn func);
+bool pxs_getbool(struct pxs_Var *var);
 
 /**
- * Set a function for reading a directory.
+ * Get a int (i64) from a var without silently losing data. Writes it into `out_value` on `pxs_StatusOk`, otherwise
+ * `out_value` is left alone.
  *
- * This is used to read a dir.
+ * Floats with a fraction are handled by `policy`. Values out of range, NaN and infinity are `pxs_StatusOverflow`.
+ *
+ * var:BORROW
+ * out_value:BORROW
  */
-void pxs_set_dirreader(pxs_ReadDirFn func);
+enum pxs_Status pxs_getint_checked(pxs_VarT var,
+                                   int64_t *out_value,
+                                   enum pxs_Convert policy);
 
 /**
- * Free a PixelScript var.
+ * Get a uint (u64) from a var without silently losing data. Writes it into `out_value` on `pxs_StatusOk`, otherwise
+ * `out_value` is left alone.
  *
- * You should only free results from `pxs_object_call`
+ * Floats with a fraction are handled by `policy`. Negative values, values out of range, NaN and infinity are
+ * `pxs_StatusOverflow`.
  *
- * var:TRANSFER
- */
-void pxs_freevar(struct pxs_Var *var);
-
-/**
- * Tells PixelScript that we are in a new thread.
+ * var:BORROW
+ * out_value:BORROW
  */
-void pxs_startthread(void);
+enum pxs_Status pxs_getuint_checked(pxs_VarT var,
+                                    uint64_t *out_value,
+                                    enum pxs_Convert policy);
 
 /**
- * Tells PixelScript that we just stopped the most recent thread.
+ * Get a float (f64) from a var. Writes it into `out_value` on `pxs_StatusOk`, otherwise `out_value` is left alone.
+ *
+ * Integers above 2^53 can not all be represented, with `pxs_ConvertError` those are `pxs_StatusInexact`. Otherwise
+ * they are rounded to the nearest float.
+ *
+ * var:BORROW
+ * out_value:BORROW
  */
-void pxs_stopthread(void);
+enum pxs_Status pxs_getfloat_checked(pxs_VarT var,
+                                     double *out_value,
+                                     enum pxs_Convert policy);
 
 /**
- * Clear the current threads state for all languages.
+ * Get a bool from a var. Writes it into `out_value` on `pxs_StatusOk`, otherwise `out_value` is left alone.
  *
- * Optionally, if you want to run the garbage collector.
+ * The integers 0 and 1 are also bools, other integers are `pxs_StatusOverflow`. Unlike `pxs_getbool` this does not
+ * crash on other types.
+ *
+ * var:BORROW
+ * out_value:BORROW
  */
-void pxs_clear(void);
+enum pxs_Status pxs_getbool_checked(pxs_VarT var,
+                                    bool *out_value);
 
 /**
- * Call a method within a specifed runtime.
+ * Get a String
  *
- * Runtime is a `pxs_Var`.
+ * CAN_CRASH, CALLER
  *
- * Transfers ownership of args.
+ * You have to free this memory by calling `pxs_free_str`
  *
- * runtime:BORROW
- * args:TRANSFER
+ * var:BORROW
  * return:OWNED
  */
-struct pxs_Var *pxs_call(struct pxs_Var *runtime, const char *method, struct pxs_Var *args);
+char *pxs_getstring(struct pxs_Var *var);
 
 /**
- * Call a ToString method on this Var. If already a string, it won't call it.
+ * Get a String without copying it. Writes the length in bytes (without the null terminator) into `out_len` if it is
+ * not null.
  *
- * Host must free this memory with `pxs_free_var`
+ * Returns null if `var` is not a `pxs_String`. The pointer is valid as long as `var` is, and until it is set to
+ * something else. Use `pxs_getstring` when you need a copy.
  *
- * runtime_var:BORROW
  * var:BORROW
- * return:OWNED
+ * out_len:BORROW
+ * return:BORROW&NULLABLE
  */
-struct pxs_Var *pxs_tostring(struct pxs_Var *runtime_var, struct pxs_Var *var);
+const char *pxs_getstring_view(pxs_VarT var,
+                               uintptr_t *out_len);
 
 /**
- * Create a new pxs_VarList.
+ * Get the number of characters (Unicode code points) of a String, without copying it. For the length in bytes use
+ * `pxs_getstring_view`.
  *
- * This does not take any arguments. To add to a list, you must call `pxs_var_list_add(ptr, item)`
+ * Returns -1 if `var` is not a `pxs_String` or is not valid UTF-8.
  *
- * return:OWNED
+ * var:BORROW
  */
-struct pxs_Var *pxs_newlist(void);
+int64_t pxs_getstring_utf8len(pxs_VarT var);
 
 /**
- * Add a item to a pxs_VarList.
+ * Make a new Var string from `len` UTF-16 code units (not null terminated).
  *
- * Expects a pointer to pxs_VarList. And a pointer for the item to add (pxs_Var*)
- *
- * This will take ownership of the added item. If you want to copy it instead first create a new `pxs_Var` with `pxs_var_newcopy(item)`
- *
- * Will return the index added at.
+ * Invalid input (unpaired surrogates and null characters) is handled by the policy of `pxs_set_utf16_policy`. With
+ * `pxs_Utf16Error` a `pxs_Exception` is returned instead.
  *
- * list:BORROW
- * item:TRANSFER
+ * str:BORROW
+ * return:OWNED
  */
-int32_t pxs_listadd(struct pxs_Var *list,
-                    struct pxs_Var *item);
+pxs_VarT pxs_newstring_utf16(const uint16_t *str,
+                             uintptr_t len);
 
 /**
- * Get a item from a pxs_VarList.
+ * Get a String as null terminated UTF-16. Writes the length in code units (without the null terminator) into
+ * `out_len` if it is not null.
  *
- * Expcts a pointer to pxs_VarList. And a index of i32. Supports negative indexes just like in Python.
+ * Invalid UTF-8 is handled by the policy of `pxs_set_utf16_policy`. Returns null if `var` is not a `pxs_String` or
+ * the policy is `pxs_Utf16Error` and it is invalid.
  *
- * This will NOT return a cloned variable, you must NOT free it.
+ * You have to free this memory by calling `pxs_freestr_utf16`
  *
- * list:BORROW
- * return:BORROW&NULLABLE
+ * var:BORROW
+ * out_len:BORROW
+ * return:OWNED&NULLABLE
  */
-struct pxs_Var *pxs_listget(struct pxs_Var *list,
-                            int32_t index);
+uint16_t *pxs_getstring_utf16(pxs_VarT var,
+                              uintptr_t *out_len);
 
 /**
- * Set a item at a specific index in a pxs_VarList.
- *
- * Expects a pointer to pxs_VarList, a index of i32, and a pxs_Var. Supports negative indexes jsut like in Python.
- *
- * Will take ownership of the pxs_Var.
- *
- * This will return a boolean for success = true, or failure = false.
+ * Free a string from `pxs_getstring_utf16`.
  *
- * list:BORROW
- * item:TRANSFER
+ * string:TRANSFER
  */
-bool pxs_listset(struct pxs_Var *list,
-                 int32_t index,
-                 struct pxs_Var *item);
+void pxs_freestr_utf16(uint16_t *string);
 
 /**
- * Get length of a pxs_VarList.
+ * Set how invalid input is handled when converting strings to and from UTF-16. `pxs_Utf16Replace` by default.
  *
- * Expects a pointer to a pxs_VarList
- *
- * list:BORROW
+ * Applies to all threads. Can be called before `pxs_initialize`.
  */
-int32_t pxs_listlen(struct pxs_Var *list);
+void pxs_set_utf16_policy(enum pxs_Utf16Policy policy);
 
 /**
- * Call a `pxs_Var`s function.
+ * Check if a variable is of a type.
  *
- * Expects runtime var, var function, and args that is a List.
+ * var:BORROW
+ */
+bool pxs_varis(struct pxs_Var *var, enum pxs_VarType var_type);
+
+/**
+ * Deep copy a var so it can be passed to a VM on another thread (i.e. a worker). Only data (numbers, strings,
+ * lists, maps, bytes) can be migrated, Objects, Functions, Factories and host objects return a exception.
  *
- * Transfers ownership of args.
+ * With the `pxs-debug` feature `pxs_varcall` and `pxs_objectcall` return a exception for vars of another VM.
  *
- * runtime:BORROW
- * var_func:BORROW
- * args:TRANSFER
+ * var:BORROW
  * return:OWNED
  */
-struct pxs_Var *pxs_varcall(struct pxs_Var *runtime,
-                            struct pxs_Var *var_func,
-                            struct pxs_Var *args);
+pxs_VarT pxs_migrate(struct pxs_Var *var);
 
 /**
- * Copy the pxs_Var.
+ * Validate `var` against a `schema` map, i.e. config provided by a mod. Schemas support `type` (a name or list of
+ * names), `required` (list of keys), `keys` (key => schema), `values` and `items` (schema) and `min`/`max` (number
+ * value or length).
  *
- * Memory is handled by caller
+ * Returns a list of error strings prefixed with the path of the value (i.e. `$.units[2].hp`), empty if `var` is
+ * valid. Returns a exception if the schema is invalid.
  *
- * item:BORROW
+ * var:BORROW
+ * schema:BORROW
  * return:OWNED
  */
-struct pxs_Var *pxs_newcopy(struct pxs_Var *item);
+pxs_VarT pxs_validate(pxs_VarT var,
+                      pxs_VarT schema);
 
 /**
- * Call a objects getter.
+ * Get the runtime (`pxs_Runtime`) that produced a Object or Function var. -1 for vars made by the host.
  *
- * runtime:BORROW
- * obj:BORROW
- * return:OWNED
+ * var:BORROW
  */
-pxs_VarT pxs_objectget(pxs_VarT runtime, pxs_VarT obj, const char *key);
+int32_t pxs_varorigin(struct pxs_Var *var);
 
 /**
- * Call a objects setter.
+ * Set a function for reading a file.
  *
- * value ownership is transfered.
+ * This is used to load files via import, require, etc
+ */
+void pxs_set_filereader(pxs_LoadFileFn func);
+
+/**
+ * Turn the compiled chunk cache on or off. Off by default, turning it off drops every chunk.
  *
- * runtime:BORROW
- * obj:BORROW
- * value:TRANSFER
+ * Files loaded by Lua `require` are compiled once and kept keyed by path and a hash of the source, so the next
+ * `require` (on any thread) skips parsing while the file is unchanged. At most 32 MiB of chunks are kept, the least
+ * recently used go first. pocketpy can not share compiled code between VMs, so Python imports are not cached. Can be
+ * called before `pxs_initialize`.
  */
-bool pxs_objectset(pxs_VarT runtime, pxs_VarT obj, const char *key, pxs_VarT value);
+void pxs_set_chunk_cache(bool enabled);
 
 /**
- * Evaluate code. This will return a `pxs_VarT`.
+ * Drop the cached chunks of `path` (as passed to the file reader, or the path of a resolved module) for hot reload. Null drops every chunk. Returns how
+ * many chunks were dropped.
  *
- * return:OWNED
+ * Chunks of changed files are never used, this only frees them early.
+ *
+ * path:BORROW
  */
-pxs_VarT pxs_eval(const char *script, enum pxs_Runtime rt);
+int32_t pxs_invalidate_cache(const char *path);
 
 /**
- * Evaluate named code. This will return a `pxs_VarT`.
+ * Set a function for reading a directory.
  *
- * return:OWNED
+ * This is used to read a dir.
  */
-pxs_VarT pxs_evalnamed(const char *script, const char *name, enum pxs_Runtime rt);
+void pxs_set_dirreader(pxs_ReadDirFn func);
 
 /**
- * Add a factory variable. This variable will be instantiated once at module startup.
+ * Add a module resolver (i.e. for archives, memory bundles or network sources). Resolvers are asked in the order they
+ * were added, with the requested module name and runtime, whenever a script imports a module that is not a host
+ * module. The first one returning a source wins, the file reader is only used when none has the module.
  *
- * Args should not contain runtime. It gets added automatically.
+ * Names are as written by the script: `require('a.b')` in Lua, `import a.b` in Python and `import 'a/b.js'` in JS.
+ * Lua uses the returned path as chunk name and chunk cache key. Python and JS keep the name, they key modules by it.
+ * Python imports the parent packages of `a.b` first, so resolvers need `a` too (a empty source is fine).
+ */
+void pxs_add_module_resolver(pxs_ModuleResolverFn func);
+
+/**
+ * Remove every resolver added with `pxs_add_module_resolver`.
+ */
+void pxs_clear_module_resolvers(void);
+
+/**
+ * Create a empty script bundle. Fill it with `pxs_bundle_add` and use it with `pxs_use_bundle`.
  *
- * Basically does:
- * ```python
- * var_name = callback(args)
- * ```
+ * return:OWNED (free with `pxs_bundle_free` unless passed to `pxs_use_bundle`)
+ */
+struct pxs_Bundle *pxs_bundle_new(void);
+
+/**
+ * Add a script to a bundle, replacing the one at the same `path`. Paths are relative to the bundle root, i.e.
+ * `utils/math.lua` or `game/__init__.py`.
  *
- * args:TRANSFER
- * return:OWNED
+ * path:BORROW
+ * contents:BORROW
  */
-pxs_VarT pxs_newfactory(pxs_Func func, struct pxs_Var *args);
+void pxs_bundle_add(struct pxs_Bundle *bundle,
+                    const char *path,
+                    const char *contents);
 
 /**
- * Get the `_pxs_ptr` of a `pxs_HostObject`. And type check it against `type_id`.
+ * Free a bundle that was not passed to `pxs_use_bundle`.
+ */
+void pxs_bundle_free(struct pxs_Bundle *bundle);
+
+/**
+ * Resolve imports from `bundle` (NULLABLE), replacing the last one. Null removes it.
  *
- * if `type_id` < 0, no type checking is done.
+ * The bundle is asked after the resolvers of `pxs_add_module_resolver` and before the file reader, so scripts packed
+ * into the binary need no filesystem callbacks. Lua `require('a.b')` looks for `a.b`, `a/b.lua` and `a/b/init.lua`,
+ * Python `import a.b` for `a/b.py`, `a/b/__init__.py` and `a/b/__import__.py` and JS for the module name with and
+ * without `.js`. Directories with Python files are packages.
  *
- * runtime: BORROW
- * var: BORROW
- * return: BORROW
+ * bundle:TRANSFER
  */
-void *pxs_gettype(pxs_VarT runtime, pxs_VarT var, int32_t type_id);
+void pxs_use_bundle(struct pxs_Bundle *bundle);
 
 /**
- * Get the HostPointer universally supported for:
- * - Objects that have `_pxs_ptr` assigned.
- * - Integers (signed and unsigned)
- * - HostObjects
- * - Factories (this will call it on the fly.)
- *
- * All other types will return NULL.
+ * Set a function for writing a file.
  *
- * runtime:BORROW
- * var:BORROW
- * return:BORROW
+ * This is used by `pxs_fs.write`, scripts can not write files without it.
  */
-void *pxs_gethost(pxs_VarT runtime, pxs_VarT var);
+void pxs_set_filewriter(pxs_WriteFileFn func);
 
 /**
- * Return a string rep of the `pxs_Var`.
+ * Set a prefix for the chunk names of code generated by pixelscript (i.e. `<create_Person>` becomes
+ * `<pxs:create_Person>` with the prefix `pxs:`). This lets crash reports and debuggers group internal code apart from
+ * user scripts.
  *
- * String must be freed via `pxs_freestr`.
+ * Only applies to code generated after this call. Can be called before `pxs_initialize`.
  *
- * var:BORROW
- * return:OWNED
+ * prefix:BORROW
  */
-char *pxs_debugvar(pxs_VarT var);
+void pxs_set_source_identity(const char *prefix);
 
 /**
- * Create a `pxs_Exception`.
+ * Set the catalog used to localize error messages generated by pixelscript (type mismatches, permission denials, ...).
  *
- * return:OWNED
+ * The catalog gets a stable message key (i.e. `incorrect_type`) and the default English template, and returns a
+ * translated template or null to keep the default. Templates use `{0}`, `{1}`, ... for their arguments.
+ * Script and host errors are passed through as is.
  */
-pxs_VarT pxs_newexception(const char *msg);
+void pxs_set_message_catalog(pxs_MessageCatalogFn func);
 
 /**
- * Get a variable reference from its name
+ * Set a budget for values deep-cloned in a single crossing between a runtime and the host (function arguments and
+ * results, `pxs_varcall`, `pxs_object_call` and `pxs_callmethod`). Elements counts list items plus map keys and
+ * values, bytes counts string and `pxs_Bytes` data. `0` means unlimited.
  *
- * rt:BORROW
- * return:OWNED
+ * With `pxs_BudgetWarn` a warning is printed, with `pxs_BudgetError` the value is replaced by a `pxs_Exception`.
+ * Large data should be passed with `pxs_iter_new`, `pxs_Bytes` or `pxs_sync_*` instead. Off by default.
+ * Can be called before `pxs_initialize`.
  */
-pxs_VarT pxs_var_fromname(pxs_VarT rt, const char *name);
+void pxs_set_clone_budget(int64_t max_elements,
+                          int64_t max_bytes,
+                          enum pxs_BudgetMode mode);
 
 /**
- * Remove a item from a list at a specific index.
+ * Turn strict mode on or off. Off by default.
  *
- * Returns true for success, false for failed.
+ * Many functions silently ignore invalid input (i.e. `pxs_addfunc` with a null module or `pxs_listset` on a var that
+ * is not a list). In strict mode they panic instead, with the name of the function and what was wrong, which aborts
+ * the process at the offending call. Meant for debugging host integrations. Can be called before `pxs_initialize`.
+ */
+void pxs_set_strict(bool enabled);
+
+/**
+ * Register a language implemented by the host. Returns its runtime id (`PXS_CUSTOM_RUNTIME` and up), or -1 if
+ * `vtable` is null.
  *
- * This will automatically call `pxs_freevar` on the found item.
+ * The backend is started, gets every module added so far and every module added later (as a Map, see
+ * `pxs_BackendVTable`). Runtime vars with its id route `pxs_varcall`, `pxs_objectcall`, `pxs_objectget`,
+ * `pxs_objectset` and `pxs_execobject` to it, and host functions get its id as the runtime. Run code with
+ * `pxs_backend_exec`, `pxs_backend_eval` and `pxs_backend_compile`.
  *
- * list:BORROW
+ * vtable:BORROW
  */
-bool pxs_listdel(pxs_VarT list, int32_t index);
+int32_t pxs_register_backend(const struct pxs_BackendVTable *vtable);
 
 /**
- * Do a Shallow Copy. Which means it gets the same data without get the deleter for (pxs_Object or pxs_Function).
+ * Execute code in a backend from `pxs_register_backend`. Null means no error, otherwise a exception.
  *
- * Memory is owned by caller.
- *
- * var:BORROW
+ * code:BORROW
+ * file_name:BORROW
  * return:OWNED
  */
-pxs_VarT pxs_new_shallowcopy(pxs_VarT var);
+pxs_VarT pxs_backend_exec(int32_t runtime,
+                          const char *code,
+                          const char *file_name);
 
 /**
- * Compile a code string into a code object for later execution.
- *
- * Pass in a optional gloabl scope (or null for default). Scope ownership is transferred.
- * Returns a `pxs_Var` whichs memory is handled by the caller.
+ * Evaluate code in a backend from `pxs_register_backend`.
  *
- * Resulting `pxs_Var` will contain (Associated Runtime, Code Object, Scope|default).
+ * script:BORROW
+ * return:OWNED
+ */
+pxs_VarT pxs_backend_eval(int32_t runtime, const char *script);
+
+/**
+ * Compile code in a backend from `pxs_register_backend`. Works like `pxs_compile`, run it with `pxs_execobject`.
  *
  * global_scope:TRANSFER
  * return:OWNED
  */
-pxs_VarT pxs_compile(enum pxs_Runtime runtime, const char *code, pxs_VarT global_scope);
+pxs_VarT pxs_backend_compile(int32_t runtime,
+                             const char *code,
+                             pxs_VarT global_scope);
 
 /**
- * Execute a compiled code object.
+ * Wrap a language value of a backend from `pxs_register_backend` as a `pxs_Function` or `pxs_Object` var produced
+ * by `runtime`. `deleter` (NULLABLE) is called with `ptr` when the var is freed. Returns null for other var types.
  *
- * Variable ownership is transfered. If this is not desired behavior, pass in a shallow copy.
- * Returned variable must be freed by caller.
+ * ptr:OWNED
+ * return:OWNED
+ */
+pxs_VarT pxs_backend_newvar(int32_t runtime,
+                            enum pxs_VarType var_type,
+                            pxs_Opaque ptr,
+                            struct Option_pxs_DeleterFn deleter);
+
+/**
+ * Get the `ptr` of a var made with `pxs_backend_newvar`. Null for other vars.
  *
- * Runtime is not required because the object is embedded with it in pxs_compile.
- * Pass in a optional local scope that gets passed along with the global scope.
- * Note: Do not use the same scope as in `pxs_compile`.
+ * var:BORROW
+ */
+pxs_Opaque pxs_backend_getptr(pxs_VarT var);
+
+/**
+ * Run the conformance cases against a runtime (a `pxs_Runtime` or a id from `pxs_register_backend`), to check it
+ * converts vars, exposes modules and objects and reports errors like the other runtimes.
  *
- * Scope ownership is transferred.
+ * Returns a Map `{runtime, passed, failed, skipped, cases: [{name, category, status, message}]}`. A `pxs_conformance`
+ * module is added while the cases run. Cases have built in scripts for Lua, Python and JavaScript, other runtimes
+ * need `pxs_conformance_setsource` or the case is skipped.
  *
- * object : TRANSFER
- * local  : TRANSFER
- * return : OWNED
+ * return:OWNED
  */
-pxs_VarT pxs_execobject(pxs_VarT object, pxs_VarT local);
+pxs_VarT pxs_run_conformance(int32_t runtime);
 
 /**
- * Create a new `pxs_Map`
+ * Set the script of a conformance case for a runtime, i.e. to port the cases to a language from
+ * `pxs_register_backend`. Scripts pass their result to `require('pxs_conformance').report(value)` (or the
+ * language's import).
  *
- * return:OWNED
+ * case:BORROW
+ * code:BORROW
  */
-pxs_VarT pxs_newmap(void);
+void pxs_conformance_setsource(int32_t runtime,
+                               const char *case_,
+                               const char *code);
 
 /**
- * Add a new key (`pxs_Var`) value (`pxs_Var`) pair in a map.
+ * Run `runtime` in a child process (a script worker), so a crash in its VM does not take down the host. Requires
+ * the `script-worker` feature.
  *
- * Keys can only be:
- * - `pxs_String`
- * - `pxs_Int64`
- * - `pxs_UInt64`
- * - `pxs_Float64`
- * - `pxs_Bool`
+ * The child is `exe_path` (NULLABLE, the current executable if null) started with `args` (NULLABLE, a List of
+ * strings). The default args are `--pxs-worker <runtime>`, the host calls `pxs_worker_main(runtime)` when started
+ * with them. Returns the runtime id of the worker (see `pxs_register_backend`) or -1 if it could not be started.
  *
- * Key and value ownership are transfered.
+ * Run code with `pxs_backend_exec` and `pxs_backend_eval`. Modules are sent to the worker and its scripts call the
+ * host functions, only data (no Objects or Functions) crosses the process boundary. If the child dies, every call
+ * returns a `worker_crashed` exception.
  *
- * map:BORROW
- * key:TRANSFER
- * value:TRANSFER
+ * exe_path:BORROW
+ * args:BORROW
  */
-void pxs_map_addpair(pxs_VarT map, pxs_VarT key, pxs_VarT value);
+int32_t pxs_worker_spawn(int32_t runtime,
+                         const char *exe_path,
+                         pxs_VarT args);
 
 /**
- * Remove a value (`pxs_Var`) from a map based on it's key (`pxs_Var`).
+ * Serve the host as a script worker for `runtime` (a built in runtime or one registered with
+ * `pxs_register_backend` in this process). Call after `pxs_initialize` when started with `--pxs-worker <runtime>`.
+ * Requires the `script-worker` feature.
  *
- * map:BORROW
- * key:BORROW
+ * Returns a exit code once the host exits.
  */
-void pxs_map_delitem(pxs_VarT map, pxs_VarT key);
+int32_t pxs_worker_main(int32_t runtime);
 
 /**
- * Get length of a `pxs_Map`.
+ * Set a observer for host object lifetimes. It gets a `pxs_ObjectEvent`, the type name and the object idx, so
+ * engines can mirror the objects scripts can see (i.e. in a editor inspector).
  *
- * -1 is invalid length.
- *
- * map:BORROW
+ * Events are sent when a object is first passed into a runtime, when its last reference is dropped and when it is
+ * freed.
  */
-int32_t pxs_maplen(pxs_VarT map);
+void pxs_set_object_observer(pxs_ObjectObserverFn func);
 
 /**
- * Get the keys of a `pxs_Map`.
- *
- * Returns a `pxs_List` or `pxs_Null` Which is owned by caller.
+ * Set a hook called when a script GC collects the language object (the Lua table or Python instance) of a host object.
+ * It gets the type name and the host pointer, so engines can release GPU handles or file descriptors as soon as
+ * scripts drop a object instead of at exit.
  *
- * map:BORROW
- * return:OWNED
+ * The hook runs during the GC, before the reference of the language object is dropped. It must not call into scripts.
  */
-pxs_VarT pxs_mapkeys(pxs_VarT map);
+void pxs_set_object_destructor_hook(pxs_ObjectDestructorFn func);
 
 /**
- * Get a value in a map from a key.
+ * Free a PixelScript var.
+ *
+ * You should only free results from `pxs_object_call`
+ *
+ * A var retained with `pxs_retain` is only freed once every reference is released, this drops one.
+ *
+ * var:TRANSFER
+ */
+void pxs_freevar(struct pxs_Var *var);
+
+/**
+ * Add a reference to `var`, so it (and the script value it holds) stays alive until `pxs_release` or
+ * `pxs_freevar` is called once more than `pxs_retain`. Returns the reference count, the owner being the first.
+ *
+ * Retained vars must be freed with `pxs_release` or `pxs_freevar`, not transferred to another function.
+ *
+ * var:BORROW
+ */
+uint32_t pxs_retain(pxs_VarT var);
+
+/**
+ * Drop a reference to `var`, freeing it when none are left. Returns the references left.
+ *
+ * var:TRANSFER
+ */
+uint32_t pxs_release(pxs_VarT var);
+
+/**
+ * The references to `var` (1 unless retained).
+ *
+ * var:BORROW
+ */
+uint32_t pxs_refcount(pxs_VarT var);
+
+/**
+ * Tells PixelScript that we are in a new thread.
+ */
+void pxs_startthread(void);
+
+/**
+ * Tells PixelScript that we just stopped the most recent thread.
+ */
+void pxs_stopthread(void);
+
+/**
+ * Create a isolated script world. A context has its own runtimes, function and object lookups, modules and file
+ * readers, so hosts can run scripts per document or tenant side by side. Use the `_ctx` variants of `pxs_exec`,
+ * `pxs_eval`, `pxs_addmod`, `pxs_varcall`, `pxs_objectcall` and `pxs_freevar` with it.
+ *
+ * Every context runs on its own thread, the `_ctx` functions wait for it. Host functions added to a context are
+ * called on that thread. Vars of a context's scripts must only be used with the same context.
+ *
+ * Free with `pxs_context_free` before `pxs_finalize`.
+ */
+struct pxs_Context *pxs_context_new(void);
+
+/**
+ * Free a context made with `pxs_context_new`, stopping its runtimes.
+ */
+void pxs_context_free(struct pxs_Context *ctx);
+
+/**
+ * Set the file and directory readers (NULLABLE) of a context. Without them the global ones are used.
+ */
+void pxs_context_setreaders(struct pxs_Context *ctx,
+                            struct Option_pxs_LoadFileFn file_reader,
+                            struct Option_pxs_ReadDirFn dir_reader);
+
+/**
+ * `pxs_freevar` in a context. Script vars must be freed in the context they came from.
+ *
+ * var:TRANSFER
+ */
+void pxs_freevar_ctx(struct pxs_Context *ctx, pxs_VarT var);
+
+/**
+ * `pxs_exec` in a context.
+ *
+ * code:BORROW
+ * file_name:BORROW
+ * return:OWNED
+ */
+pxs_VarT pxs_exec_ctx(struct pxs_Context *ctx,
+                      enum pxs_Runtime runtime,
+                      const char *code,
+                      const char *file_name);
+
+/**
+ * `pxs_eval` in a context.
+ *
+ * script:BORROW
+ * return:OWNED
+ */
+pxs_VarT pxs_eval_ctx(struct pxs_Context *ctx, const char *script, enum pxs_Runtime rt);
+
+/**
+ * `pxs_addmod` in a context.
+ *
+ * module_ptr:TRANSFER
+ */
+void pxs_addmod_ctx(struct pxs_Context *ctx, struct pxs_Module *module_ptr);
+
+/**
+ * `pxs_varcall` in a context.
+ *
+ * runtime:BORROW
+ * var_func:BORROW
+ * args:TRANSFER
+ * return:OWNED
+ */
+pxs_VarT pxs_varcall_ctx(struct pxs_Context *ctx,
+                         pxs_VarT runtime,
+                         pxs_VarT var_func,
+                         pxs_VarT args);
+
+/**
+ * `pxs_objectcall` in a context.
+ *
+ * runtime:BORROW
+ * var:BORROW
+ * args:TRANSFER
+ * return:OWNED
+ */
+pxs_VarT pxs_objectcall_ctx(struct pxs_Context *ctx,
+                            pxs_VarT runtime,
+                            pxs_VarT var,
+                            const char *method,
+                            pxs_VarT args);
+
+/**
+ * Start a script thread: a context (see `pxs_context_new`) running code in parallel with the host. The modules added
+ * with `pxs_addmod` on this thread are added to it, their functions are called on the script thread.
+ *
+ * Only data variables of the modules are copied, Objects and Functions stay on this thread. Host functions are shared
+ * with their opaque as is, so the opaques of functions called from several threads must be thread safe.
+ *
+ * Join with `pxs_thread_join` before `pxs_finalize`.
+ */
+struct pxs_Thread *pxs_thread_spawn(void);
+
+/**
+ * Queue code in a script thread without waiting for it. Code runs in the order it was queued. Returns false if
+ * `thread` or a string is null.
+ *
+ * code:BORROW
+ * file_name:BORROW
+ */
+bool pxs_thread_exec(struct pxs_Thread *thread,
+                     enum pxs_Runtime runtime,
+                     const char *code,
+                     const char *file_name);
+
+/**
+ * Wait until the queued code of a script thread ran, then stop and free it. Returns the first error, null if all
+ * code ran without one.
+ *
+ * thread:TRANSFER
+ * return:OWNED
+ */
+pxs_VarT pxs_thread_join(struct pxs_Thread *thread);
+
+/**
+ * Clear the current threads state for all languages.
+ *
+ * Optionally, if you want to run the garbage collector.
+ */
+void pxs_clear(void);
+
+/**
+ * Call a method within a specifed runtime.
+ *
+ * Runtime is a `pxs_Var`.
+ *
+ * Transfers ownership of args.
+ *
+ * runtime:BORROW
+ * args:TRANSFER
+ * return:OWNED
+ */
+struct pxs_Var *pxs_call(struct pxs_Var *runtime, const char *method, struct pxs_Var *args);
+
+/**
+ * Call a ToString method on this Var. If already a string, it won't call it.
+ *
+ * Host must free this memory with `pxs_free_var`
+ *
+ * runtime_var:BORROW
+ * var:BORROW
+ * return:OWNED
+ */
+struct pxs_Var *pxs_tostring(struct pxs_Var *runtime_var, struct pxs_Var *var);
+
+/**
+ * Create a new pxs_VarList.
+ *
+ * This does not take any arguments. To add to a list, you must call `pxs_var_list_add(ptr, item)`
+ *
+ * return:OWNED
+ */
+struct pxs_Var *pxs_newlist(void);
+
+/**
+ * Add a item to a pxs_VarList.
+ *
+ * Expects a pointer to pxs_VarList. And a pointer for the item to add (pxs_Var*)
+ *
+ * This will take ownership of the added item. If you want to copy it instead first create a new `pxs_Var` with `pxs_var_newcopy(item)`
+ *
+ * Will return the index added at.
+ *
+ * list:BORROW
+ * item:TRANSFER
+ */
+int32_t pxs_listadd(struct pxs_Var *list,
+                    struct pxs_Var *item);
+
+/**
+ * Get a item from a pxs_VarList.
+ *
+ * Expcts a pointer to pxs_VarList. And a index of i32. Supports negative indexes just like in Python.
+ *
+ * This will NOT return a cloned variable, you must NOT free it.
+ *
+ * list:BORROW
+ * return:BORROW&NULLABLE
+ */
+struct pxs_Var *pxs_listget(struct pxs_Var *list,
+                            int32_t index);
+
+/**
+ * Set a item at a specific index in a pxs_VarList.
+ *
+ * Expects a pointer to pxs_VarList, a index of i32, and a pxs_Var. Supports negative indexes jsut like in Python.
+ *
+ * Will take ownership of the pxs_Var.
+ *
+ * This will return a boolean for success = true, or failure = false.
+ *
+ * list:BORROW
+ * item:TRANSFER
+ */
+bool pxs_listset(struct pxs_Var *list,
+                 int32_t index,
+                 struct pxs_Var *item);
+
+/**
+ * Get length of a pxs_VarList.
+ *
+ * Expects a pointer to a pxs_VarList
+ *
+ * list:BORROW
+ */
+int32_t pxs_listlen(struct pxs_Var *list);
+
+/**
+ * Call a `pxs_Var`s function.
+ *
+ * Expects runtime var, var function, and args that is a List. If runtime is null the runtime that produced
+ * `var_func` is used, passing a different runtime is a `runtime_mismatch` exception.
+ *
+ * Transfers ownership of args.
+ *
+ * runtime:BORROW
+ * var_func:BORROW
+ * args:TRANSFER
+ * return:OWNED
+ */
+struct pxs_Var *pxs_varcall(struct pxs_Var *runtime,
+                            struct pxs_Var *var_func,
+                            struct pxs_Var *args);
+
+/**
+ * Same as `pxs_call`, but failures return the script error with its traceback instead of a exception var.
+ *
+ * runtime:BORROW
+ * args:TRANSFER
+ * return:OWNED
+ */
+struct pxs_CallResult pxs_call_try(pxs_VarT runtime,
+                                   const char *method,
+                                   pxs_VarT args);
+
+/**
+ * Same as `pxs_objectcall`, but failures return the script error with its traceback instead of a exception var.
+ *
+ * runtime:BORROW
+ * var:BORROW
+ * args:TRANSFER
+ * return:OWNED
+ */
+struct pxs_CallResult pxs_objectcall_try(pxs_VarT runtime,
+                                         pxs_VarT var,
+                                         const char *method,
+                                         pxs_VarT args);
+
+/**
+ * Same as `pxs_varcall`, but failures return the script error with its traceback instead of a exception var.
+ *
+ * runtime:BORROW
+ * var_func:BORROW
+ * args:TRANSFER
+ * return:OWNED
+ */
+struct pxs_CallResult pxs_varcall_try(pxs_VarT runtime,
+                                      pxs_VarT var_func,
+                                      pxs_VarT args);
+
+/**
+ * Call a `pxs_Var`s function that belongs to a mod.
+ *
+ * Works like `pxs_varcall` but tracks errors per mod. Once a mod throws `pxs_quarantine_setthreshold` times in a row
+ * it is quarantined: the `pxs_quarantine_setcallback` callback is called once, and further calls return null
+ * without running the function. Use this for a mod's event handlers and timers so one broken mod can not spam errors.
+ *
+ * mod_name:BORROW
+ * runtime:BORROW
+ * var_func:BORROW
+ * args:TRANSFER
+ * return:OWNED
+ */
+struct pxs_Var *pxs_modcall(const char *mod_name,
+                            struct pxs_Var *runtime,
+                            struct pxs_Var *var_func,
+                            struct pxs_Var *args);
+
+/**
+ * Report the result of running a mod's script that was not called through `pxs_modcall` (i.e. `pxs_exec`).
+ *
+ * A exception counts as a error, anything else resets the mods errors. Returns true if the mod is quarantined.
+ *
+ * mod_name:BORROW
+ * result:BORROW
+ */
+bool pxs_mod_report(const char *mod_name,
+                    struct pxs_Var *result);
+
+/**
+ * Is this mod quarantined?
+ *
+ * mod_name:BORROW
+ */
+bool pxs_mod_isquarantined(const char *mod_name);
+
+/**
+ * Take a mod out of quarantine and reset its errors.
+ *
+ * mod_name:BORROW
+ */
+void pxs_mod_release(const char *mod_name);
+
+/**
+ * Set how many errors in a row quarantine a mod. Defaults to 5, minimum is 1.
+ */
+void pxs_quarantine_setthreshold(uint32_t threshold);
+
+/**
+ * Set the callback for when a mod gets quarantined.
+ */
+void pxs_quarantine_setcallback(pxs_QuarantineFn func);
+
+/**
+ * Copy the pxs_Var.
+ *
+ * Memory is handled by caller. Objects and Functions move their deleter to the copy. Never returns on a list that
+ * contains itself, use `pxs_deepcopy` for values that are not trusted.
+ *
+ * item:BORROW
+ * return:OWNED
+ */
+struct pxs_Var *pxs_newcopy(struct pxs_Var *item);
+
+/**
+ * Copy the pxs_Var and everything in it, leaving the original untouched.
+ *
+ * `max_depth` limits how deep lists and maps may be nested, negative for no limit. Returns a exception if `item`
+ * contains itself (i.e. a list added to itself), is nested deeper than `max_depth` or holds Objects, Functions or
+ * Factories.
+ *
+ * item:BORROW
+ * return:OWNED
+ */
+pxs_VarT pxs_deepcopy(pxs_VarT item,
+                      int32_t max_depth);
+
+/**
+ * Call a objects getter.
+ *
+ * runtime:BORROW
+ * obj:BORROW
+ * return:OWNED
+ */
+pxs_VarT pxs_objectget(pxs_VarT runtime, pxs_VarT obj, const char *key);
+
+/**
+ * Call a objects setter.
+ *
+ * value ownership is transfered.
+ *
+ * runtime:BORROW
+ * obj:BORROW
+ * value:TRANSFER
+ */
+bool pxs_objectset(pxs_VarT runtime, pxs_VarT obj, const char *key, pxs_VarT value);
+
+/**
+ * Set a script global (`_G` in Lua, `__main__` in Python, `globalThis` in JavaScript) without building a module,
+ * i.e. frame state like `dt` before each tick. Returns false if `name` or `value` is null or the value can not be
+ * converted.
+ *
+ * name:BORROW
+ * value:TRANSFER
+ */
+bool pxs_setglobal(enum pxs_Runtime runtime,
+                   const char *name,
+                   pxs_VarT value);
+
+/**
+ * Read a script global. Returns a `pxs_Null` if it is not set.
+ *
+ * name:BORROW
+ * return:OWNED
+ */
+pxs_VarT pxs_getglobal(enum pxs_Runtime runtime, const char *name);
+
+/**
+ * Evaluate code. This will return a `pxs_VarT`.
+ *
+ * return:OWNED
+ */
+pxs_VarT pxs_eval(const char *script, enum pxs_Runtime rt);
+
+/**
+ * Evaluate named code. This will return a `pxs_VarT`.
+ *
+ * return:OWNED
+ */
+pxs_VarT pxs_evalnamed(const char *script, const char *name, enum pxs_Runtime rt);
+
+/**
+ * Evaluate code with `bindings` (a `pxs_Map` with string keys) available as local variables.
+ *
+ * Works like `pxs_eval`, in Lua the code still needs a `return`. The bindings are not visible after the call.
+ *
+ * script:BORROW
+ * bindings:BORROW
+ * return:OWNED
+ */
+pxs_VarT pxs_eval_with(const char *script,
+                       enum pxs_Runtime rt,
+                       pxs_VarT bindings);
+
+/**
+ * Evaluate a single expression without running any script code.
+ *
+ * Only identifiers found in `env` (a `pxs_Map` with string keys, or `pxs_Null`) can be referenced.
+ * Supports literals, `.` member access, unary `-`/`!`, arithmetic, comparisons, `&&`/`||` and `?:`.
+ * The runtime is only used for member access on language objects. Integer overflow is a error and expressions can
+ * be nested at most 128 levels deep (parentheses, unary operators and chained operators).
+ *
+ * Returns a `pxs_Exception` on any parse/evaluation error.
+ *
+ * expr:BORROW
+ * env:BORROW
+ * return:OWNED
+ */
+pxs_VarT pxs_eval_expr(enum pxs_Runtime rt,
+                       const char *expr,
+                       pxs_VarT env);
+
+/**
+ * Add a factory variable. This variable will be instantiated once at module startup.
+ *
+ * Args should not contain runtime. It gets added automatically.
+ *
+ * Basically does:
+ * ```python
+ * var_name = callback(args)
+ * ```
+ *
+ * args:TRANSFER
+ * return:OWNED
+ */
+pxs_VarT pxs_newfactory(pxs_Func func, struct pxs_Var *args);
+
+/**
+ * Create a function value from a host callback. Pass it to scripts (i.e. as a callback argument) and they can
+ * call it like any other function.
+ *
+ * `callback` receives the runtime as the first arg followed by the script args, same as module functions.
+ * Use `pxs_funcopaque` inside the callback to get `opaque`. Returns a `pxs_Exception` if the function lookup is full.
+ *
+ * opaque: BORROW
+ * return: OWNED
+ */
+pxs_VarT pxs_newfunc(pxs_Func callback,
+                     pxs_Opaque opaque);
+
+/**
+ * Get the `opaque` passed to `pxs_newfunc`, `pxs_addfunc_ex` or `pxs_object_addoperator` for the callback currently
+ * running. Null anywhere else.
+ *
+ * return: BORROW
+ */
+pxs_Opaque pxs_funcopaque(void);
+
+/**
+ * Get a handle to a registered module function, i.e. `pxs_funchandle("math", "add")`. Object methods work too
+ * with the type name as `module`.
+ *
+ * The handle stays valid until `pxs_finalize`. Use it with `pxs_call_handle` to skip name lookups on hot paths.
+ * Returns -1 if the function is not registered.
+ *
+ * module:BORROW
+ * name:BORROW
+ */
+int32_t pxs_funchandle(const char *module,
+                       const char *name);
+
+/**
+ * Call a registered function by its handle from `pxs_funchandle`.
+ *
+ * `args` is a List of the arguments, the runtime is passed to the function as the first arg like a script call.
+ * Capability tokens and usage recording apply as usual.
+ *
+ * runtime:BORROW
+ * args:TRANSFER
+ * return:OWNED
+ */
+pxs_VarT pxs_call_handle(pxs_VarT runtime,
+                         int32_t handle,
+                         pxs_VarT args);
+
+/**
+ * Start a lazy iteration over a script collection, so the host does not need to convert it all at once.
+ *
+ * `pxs_List` items and `pxs_Map` `[key, value]` lists are iterated on the host, `var` must outlive the iterator.
+ * Anything else is iterated by the runtime: Lua tables give `[key, value]` lists, Python uses `iter(var)` and JS uses
+ * `var[Symbol.iterator]` or `Object.entries(var)`.
+ *
+ * Free with `pxs_iter_free` before `pxs_finalize`. Returns null if `var` can not be iterated.
+ *
+ * runtime:BORROW
+ * var:BORROW
+ * return:OWNED
+ */
+struct pxs_Iterator *pxs_iter_new(pxs_VarT runtime,
+                                  pxs_VarT var);
+
+/**
+ * Get the next item of a iterator. Returns null once the iterator is exhausted.
+ *
+ * A `pxs_Exception` is returned if the script fails, this also ends the iteration.
+ *
+ * iter:BORROW
+ * return:OWNED
+ */
+pxs_VarT pxs_iter_next(struct pxs_Iterator *iter);
+
+/**
+ * Free a iterator.
+ *
+ * iter:TRANSFER
+ */
+void pxs_iter_free(struct pxs_Iterator *iter);
+
+/**
+ * Start `func` as a coroutine the host resumes across frames. Lua functions run as Lua coroutines, Python and
+ * JavaScript functions must be generator functions. `args` is a list passed to the first resume, it can be null.
+ *
+ * Nothing runs until `pxs_coroutine_resume`. Free with `pxs_coroutine_free` before `pxs_finalize`. Returns null if
+ * `func` is not a function or the runtime can not make a coroutine.
+ *
+ * runtime:BORROW
+ * func:BORROW
+ * args:TRANSFER
+ * return:OWNED
+ */
+struct pxs_Coroutine *pxs_coroutine_create(pxs_VarT runtime,
+                                           pxs_VarT func,
+                                           pxs_VarT args);
+
+/**
+ * Run a coroutine until its next yield and return the yielded value. Once the function returns, the returned value
+ * is given and the coroutine is dead.
+ *
+ * `args` is a list given to the script as the result of the yield (Python generators ignore it), it can be null.
+ * A `pxs_Exception` is returned if the script fails (this kills the coroutine) or the coroutine can not be resumed.
+ *
+ * co:BORROW
+ * args:TRANSFER
+ * return:OWNED
+ */
+pxs_VarT pxs_coroutine_resume(struct pxs_Coroutine *co,
+                              pxs_VarT args);
+
+/**
+ * Get the status of a coroutine. A null coroutine is dead.
+ *
+ * co:BORROW
+ */
+enum pxs_CoroutineStatus pxs_coroutine_status(struct pxs_Coroutine *co);
+
+/**
+ * Free a coroutine. A suspended coroutine is dropped without running the rest of it.
+ *
+ * co:TRANSFER
+ */
+void pxs_coroutine_free(struct pxs_Coroutine *co);
+
+/**
+ * Make a pending promise for a host function doing async work (IO, a network request). Returns its id.
+ *
+ * Return `pxs_promise_var(id)` from the host function and call `pxs_promise_resolve(id, result)` once the work is
+ * done. Scripts call `then(fn)` on the `Promise`, or yield it from a coroutine to await it.
+ */
+int64_t pxs_newpromise(void);
+
+/**
+ * Get a `Promise` object of a promise for scripts, with `done()`, `result()`, `error()` and `then(fn)` methods.
+ *
+ * Returns a exception if the promise is unknown.
+ *
+ * return:OWNED
+ */
+pxs_VarT pxs_promise_var(int64_t id);
+
+/**
+ * Resolve a promise. Runs its `then` callbacks, then resumes the coroutines awaiting it with `(result, error)`.
+ *
+ * Pass a `pxs_Exception` to reject the promise with its message. Returns false if the promise is unknown or already
+ * resolved. Must be called on the thread that made the promise.
+ *
+ * result:TRANSFER
+ */
+bool pxs_promise_resolve(int64_t id,
+                         pxs_VarT result);
+
+/**
+ * Get the `_pxs_ptr` of a `pxs_HostObject`. And type check it against `type_id`.
+ *
+ * if `type_id` < 0, no type checking is done.
+ *
+ * runtime: BORROW
+ * var: BORROW
+ * return: BORROW
+ */
+void *pxs_gettype(pxs_VarT runtime, pxs_VarT var, int32_t type_id);
+
+/**
+ * Get the HostPointer universally supported for:
+ * - Objects that have `_pxs_ptr` assigned.
+ * - Integers (signed and unsigned)
+ * - HostObjects
+ * - Factories (this will call it on the fly.)
+ *
+ * All other types will return NULL.
+ *
+ * runtime:BORROW
+ * var:BORROW
+ * return:BORROW
+ */
+void *pxs_gethost(pxs_VarT runtime, pxs_VarT var);
+
+/**
+ * Get the host pointer of `var` like `pxs_gethost`, but only if the object is a `type_name` or inherits from it (see
+ * `pxs_class_setparent`). Use it instead of casting the result of `pxs_gethost` when scripts can pass any object.
+ *
+ * Returns NULL if `var` is not a host object or of a other type.
+ *
+ * runtime:BORROW
+ * var:BORROW
+ * return:BORROW
+ */
+void *pxs_gethost_checked(pxs_VarT runtime,
+                          pxs_VarT var,
+                          const char *type_name);
+
+/**
+ * Get the type name of the host object of `var`, resolved like `pxs_gethost`.
+ *
+ * Returns NULL if `var` is not a host object. Free the string with `pxs_freestr`.
+ *
+ * runtime:BORROW
+ * var:BORROW
+ * return:OWNED
+ */
+char *pxs_object_typename(pxs_VarT runtime, pxs_VarT var);
+
+/**
+ * Return a string rep of the `pxs_Var`.
+ *
+ * String must be freed via `pxs_freestr`.
+ *
+ * var:BORROW
+ * return:OWNED
+ */
+char *pxs_debugvar(pxs_VarT var);
+
+/**
+ * Create a `pxs_Exception`.
+ *
+ * return:OWNED
+ */
+pxs_VarT pxs_newexception(const char *msg);
+
+/**
+ * Create a `pxs_Exception` of a `kind`. Returned from a `pxs_Func` it is raised in the script, as the matching
+ * exception class in Python (`TypeError`, `ValueError`, ...) and JavaScript (the error `name`). Lua raises the
+ * message.
+ *
+ * return:OWNED
+ */
+pxs_VarT pxs_newerror(const char *message,
+                      enum pxs_ErrorKind kind);
+
+/**
+ * Is `var` a error, i.e. a exception returned by `pxs_call` or `pxs_newerror`? Use `pxs_getstring` for the message.
+ *
+ * var:BORROW
+ */
+bool pxs_varis_error(pxs_VarT var);
+
+/**
+ * Get the kind of a error. `pxs_GenericError` for errors raised by scripts and for any other var.
+ *
+ * var:BORROW
+ */
+enum pxs_ErrorKind pxs_geterrorkind(pxs_VarT var);
+
+/**
+ * Get a variable reference from its name
+ *
+ * rt:BORROW
+ * return:OWNED
+ */
+pxs_VarT pxs_var_fromname(pxs_VarT rt, const char *name);
+
+/**
+ * Remove a item from a list at a specific index. Supports negative indexes just like in Python.
+ *
+ * Returns true for success, false for failed (e.g. the index is out of bounds).
+ *
+ * This will automatically call `pxs_freevar` on the found item.
+ *
+ * list:BORROW
+ */
+bool pxs_listdel(pxs_VarT list, int32_t index);
+
+/**
+ * Do a Shallow Copy. Which means it gets the same data without get the deleter for (pxs_Object or pxs_Function).
+ *
+ * Memory is owned by caller.
+ *
+ * var:BORROW
+ * return:OWNED
+ */
+pxs_VarT pxs_new_shallowcopy(pxs_VarT var);
+
+/**
+ * Compile a code string into a code object for later execution.
+ *
+ * Pass in a optional gloabl scope (or null for default). Scope ownership is transferred.
+ * Returns a `pxs_Var` whichs memory is handled by the caller.
+ *
+ * Resulting `pxs_Var` will contain (Associated Runtime, Code Object, Scope|default).
+ *
+ * global_scope:TRANSFER
+ * return:OWNED
+ */
+pxs_VarT pxs_compile(enum pxs_Runtime runtime, const char *code, pxs_VarT global_scope);
+
+/**
+ * Execute a compiled code object.
+ *
+ * Variable ownership is transfered. If this is not desired behavior, pass in a shallow copy.
+ * Returned variable must be freed by caller.
+ *
+ * Runtime is not required because the object is embedded with it in pxs_compile.
+ * Pass in a optional local scope that gets passed along with the global scope.
+ * Note: Do not use the same scope as in `pxs_compile`.
+ *
+ * Scope ownership is transferred.
+ *
+ * object : TRANSFER
+ * local  : TRANSFER
+ * return : OWNED
+ */
+pxs_VarT pxs_execobject(pxs_VarT object, pxs_VarT local);
+
+/**
+ * Create a new `pxs_Map`
+ *
+ * return:OWNED
+ */
+pxs_VarT pxs_newmap(void);
+
+/**
+ * Add a new key (`pxs_Var`) value (`pxs_Var`) pair in a map.
+ *
+ * Keys can only be:
+ * - `pxs_String`
+ * - `pxs_Int64`
+ * - `pxs_UInt64`
+ * - `pxs_Float64`
+ * - `pxs_Bool`
+ *
+ * Key and value ownership are transfered.
+ *
+ * map:BORROW
+ * key:TRANSFER
+ * value:TRANSFER
+ */
+void pxs_map_addpair(pxs_VarT map, pxs_VarT key, pxs_VarT value);
+
+/**
+ * Set `key` to `value` in a map, replacing the old value. Keys are the same as for `pxs_map_addpair`. Unlike
+ * `pxs_map_addpair` the key and value are copied, so the host keeps them. Returns false if nothing was set.
+ *
+ * map:BORROW
+ * key:BORROW
+ * value:BORROW
+ */
+bool pxs_mapset(pxs_VarT map,
+                pxs_VarT key,
+                pxs_VarT value);
+
+/**
+ * Convert a script table, dict or object into a `pxs_Map`, i.e. a Lua table, Python dict or JS object passed to a
+ * host function. Nested ones are converted too, lists are copied and other values are kept as is.
+ *
+ * Script collections arrive as `pxs_Object` handles so the host can change them in place, this makes a plain copy
+ * that does not need the runtime anymore. Returns a exception if `var` can not be iterated.
+ *
+ * runtime:BORROW
+ * var:BORROW
+ * return:OWNED
+ */
+pxs_VarT pxs_tomap(pxs_VarT runtime,
+                   pxs_VarT var);
+
+/**
+ * Remove a value (`pxs_Var`) from a map based on it's key (`pxs_Var`).
+ *
+ * map:BORROW
+ * key:BORROW
+ */
+void pxs_map_delitem(pxs_VarT map, pxs_VarT key);
+
+/**
+ * Get length of a `pxs_Map`.
+ *
+ * -1 is invalid length.
+ *
+ * map:BORROW
+ */
+int32_t pxs_maplen(pxs_VarT map);
+
+/**
+ * Get the keys of a `pxs_Map`.
+ *
+ * Returns a `pxs_List` or `pxs_Null` Which is owned by caller.
+ *
+ * map:BORROW
+ * return:OWNED
+ */
+pxs_VarT pxs_mapkeys(pxs_VarT map);
+
+/**
+ * Get a value in a map from a key.
  *
  * Result is not owned by caller. Use `pxs_newcopy` to transfer ownership.
  *
@@ -1003,111 +2913,488 @@ pxs_VarT pxs_mapget(pxs_VarT map, pxs_VarT key);
 /**
  * Insert a item into a list at a certain index, shifting all other items to the right.
  *
- * Item ownership is transferred.
+ * Item ownership is transferred. The index can be at most the length of the list (appends), otherwise the item is
+ * freed and nothing is inserted.
  *
  * list:BORROW
  * item:TRANSFER
  */
-void pxs_listinsert(pxs_VarT list, uintptr_t index, pxs_VarT item);
+void pxs_listinsert(pxs_VarT list,
+                    uintptr_t index,
+                    pxs_VarT item);
+
+/**
+ * Create a pxs_VarList from a array of `count` items in one call.
+ *
+ * Takes ownership of every item, the array itself still belongs to the caller. Null items are added as `pxs_Null`.
+ *
+ * items:TRANSFER
+ * return:OWNED
+ */
+pxs_VarT pxs_newlist_from(const pxs_VarT *items,
+                          uintptr_t count);
+
+/**
+ * Add a array of `count` items to the end of a pxs_VarList in one call.
+ *
+ * Takes ownership of every item (they are freed if `list` is not a list), the array itself still belongs to the
+ * caller. Null items are added as `pxs_Null`.
+ *
+ * Returns the new length, or -1 on failure.
+ *
+ * list:BORROW
+ * items:TRANSFER
+ */
+int32_t pxs_list_extend(pxs_VarT list,
+                        const pxs_VarT *items,
+                        uintptr_t count);
+
+/**
+ * Remove every item of a pxs_VarList, freeing them.
+ *
+ * Returns false if `list` is not a list.
+ *
+ * list:BORROW
+ */
+bool pxs_list_clear(pxs_VarT list);
+
+/**
+ * Remove a item from a pxs_VarList and return it. Supports negative indexes just like in Python.
+ *
+ * Unlike `pxs_listdel` the item is not freed, it is owned by the caller. Returns null if the index is out of bounds.
+ *
+ * list:BORROW
+ * return:OWNED&NULLABLE
+ */
+pxs_VarT pxs_list_remove(pxs_VarT list,
+                         int32_t index);
+
+/**
+ * Remove the last item of a pxs_VarList and return it.
+ *
+ * Returns null if the list is empty.
+ *
+ * list:BORROW
+ * return:OWNED&NULLABLE
+ */
+pxs_VarT pxs_listpop(pxs_VarT list);
+
+/**
+ * Create a new pxs_VarList with the items from `start` up to `end` (exclusive).
+ *
+ * Works like Python slicing: negative indexes count from the end and out of bounds indexes are clamped, so
+ * `pxs_listslice(list, 1, INT32_MAX)` is everything but the first item.
+ *
+ * Items are shallow copies (see `pxs_new_shallowcopy`), objects and functions in the slice are only valid as long
+ * as `list` is.
+ *
+ * list:BORROW
+ * return:OWNED
+ */
+pxs_VarT pxs_listslice(pxs_VarT list,
+                       int32_t start,
+                       int32_t end);
+
+/**
+ * Find the index of the first item in a pxs_VarList equal to `item`.
+ *
+ * Numbers, strings, bools and bytes are compared by value, the type must match (`1` does not equal `1.0`). Other
+ * types are never equal.
+ *
+ * Returns -1 if there is no such item.
+ *
+ * list:BORROW
+ * item:BORROW
+ */
+int32_t pxs_listfind(pxs_VarT list,
+                     pxs_VarT item);
+
+/**
+ * Create a new arena in memory.
+ * This does not return anything, it simply creates a scope that will allocate pxs_Var memory.
+ * when finished call, `pxs_freearena`
+ *
+ * result:OWNED
+ */
+struct pxs_PixelArena *pxs_newarena(void);
+
+/**
+ * Free arena. Upon freeing all variables allocated since `pxs_newarena` will be freed.
+ *
+ * arena:TRANSFER
+ */
+void pxs_freearena(struct pxs_PixelArena *arena);
+
+/**
+ * Add a `pxs_VarT` to a `pxs_PixelArena`. Upon freeing the Arena, the variable is freed aswell.
+ *
+ * A variable must only be added once.
+ *
+ * arena:BORROW
+ * var:TRANSFER
+ * result:BORROW
+ */
+pxs_VarT pxs_arenaput(struct pxs_PixelArena *arena, pxs_VarT var);
+
+/**
+ * Add a `char*` to a `pxs_PixelArena`. Upon freeing the Arena, the string is freed aswell.
+ *
+ * This must be a string allocated by pixelscript. Either in:
+ * - `pxs_getstring`
+ * - `pxs_smart_getstring`
+ * - `pxs_debugstate`
+ *
+ * A string must only be added once.
+ *
+ * arena:BORROW
+ * str:TRANSFER
+ * result:BORROW
+ */
+char *pxs_arena_putstr(struct pxs_PixelArena *arena, char *str);
+
+/**
+ * Debug state info.
+ *
+ * result:OWNED
+ */
+char *pxs_debugstate(enum pxs_Runtime runtime);
+
+/**
+ * Export everything registered on this thread in a machine readable `pxs_Map`. Useful for generating stubs and docs.
+ *
+ * ```json
+ * {
+ *     "modules": [{"name": "math", "functions": ["add"], "objects": ["Vector"], "constants": [{"name": "PI", "type": "Float64"}], "classes": [], "modules": []}],
+ *     "objects": [{"name": "Vector", "parent": null, "methods": ["length"], "properties": ["x", "y"]}]
+ * }
+ * ```
+ *
+ * Object types are listed once a object of that type was created, since methods are added per object.
+ *
+ * return:OWNED
+ */
+pxs_VarT pxs_registry_export(void);
+
+/**
+ * Issue a capability token. Code evaluated with the token (`pxs_eval_token`, or a remote console session that
+ * authenticates with it) can only call host functions matching `permissions`.
+ *
+ * `permissions` is a comma separated list of `module.func`/`Type.method` names, `module.*` prefixes, or `*`.
+ * Only calls into host functions are limited, plain script code always runs.
+ *
+ * Free the token with `pxs_freestr`.
+ *
+ * permissions:BORROW
+ * return:OWNED
+ */
+char *pxs_issue_token(const char *permissions);
+
+/**
+ * Revoke a capability token. Returns true if the token existed.
+ *
+ * token:BORROW
+ */
+bool pxs_revoke_token(const char *token);
+
+/**
+ * Evaluate code with the permissions of a capability token. Useful for in-game consoles.
+ *
+ * Returns a exception if the token does not exist or the code calls a host function it has no permission for.
+ *
+ * token:BORROW
+ * return:OWNED
+ */
+pxs_VarT pxs_eval_token(const char *token,
+                        const char *script,
+                        enum pxs_Runtime rt);
+
+/**
+ * Start the remote scripting console on `port`. Requires the `remote-console` feature.
+ *
+ * The console speaks a line based TCP protocol:
+ * 1. The client sends `auth_token` as the first line. The server answers `ok` or closes the connection.
+ * 2. The client sends `<runtime> <code>` lines, where runtime is `lua`, `python`, or `js`.
+ * 3. The server answers each line with `ok <result>` or `error <message>`. Newlines and backslashes in the answer
+ *    are escaped as `\n` and `\\`.
+ *
+ * Snippets are only evaluated when the host calls `pxs_remote_poll`, on the thread that calls it.
+ *
+ * Returns true if the console started.
+ *
+ * auth_token:BORROW
+ */
+bool pxs_remote_listen(uint16_t port,
+                       const char *auth_token);
+
+/**
+ * Evaluate the snippets sent to the remote console and send back the results. Call this once per frame.
+ *
+ * Returns the number of snippets evaluated.
+ */
+int32_t pxs_remote_poll(void);
+
+/**
+ * Stop the remote scripting console and close all connections.
+ */
+void pxs_remote_stop(void);
+
+/**
+ * Turn recording of API usage on or off. Off by default.
+ *
+ * While on, every call from a script into a module function or object method is counted. Get the counts with
+ * `pxs_usage_report`.
+ */
+void pxs_usage_record(bool enabled);
+
+/**
+ * Clear recorded API usage.
+ */
+void pxs_usage_reset(void);
+
+/**
+ * Get a `pxs_Map` of `"module.func"`/`"Type.method"` to the number of times it was called.
+ *
+ * Functions registered on this thread that were never called are included with 0 calls.
+ *
+ * return:OWNED
+ */
+pxs_VarT pxs_usage_report(void);
+
+/**
+ * Call GC for all backends.
+ */
+void pxs_garbagecollect(void);
+
+/**
+ * Tell pixelscript the host is low on memory, i.e. from a platform low memory warning. Every runtime runs a full
+ * garbage collection, `pxs_MemoryCritical` also drops the cached object bridges, the compiled chunk cache and the
+ * `pxs_noise` permutation tables of this thread.
+ *
+ * Nothing the host still references is released: vars retained with `pxs_retain`, host objects, functions made
+ * with `pxs_newfunc`, sync field ids and the sync buffer.
+ *
+ * Returns the bytes reclaimed by runtimes that can measure it (Lua and JavaScript).
+ */
+uint64_t pxs_notify_memory_pressure(enum pxs_MemoryPressure level);
+
+/**
+ * Get the host IDX from a `pxs_HostObject`.
+ *
+ * if result is < 0 then that means it is not a object.
+ *
+ * var: BORROW
+ */
+int32_t pxs_getidx(pxs_VarT var);
+
+/**
+ * Get a `pxs_VarT` from args without needing to worry about index checks.
+ *
+ * Literraly does `pxs_listget(args, idx + 1)`
+ *
+ * args: BORROW
+ * result: BORROW&NULLABLE
+ */
+pxs_VarT pxs_arg(pxs_VarT args, int32_t idx);
+
+/**
+ * Get the `pxs_VarT` runtime from args.
+ *
+ * Does `pxs_listget(args, 0)`
+ *
+ * args: BORROW
+ * result: BORROW&NULLABLE
+ */
+pxs_VarT pxs_getrt(pxs_VarT args);
+
+/**
+ * Get the length of args without the runtime.
+ *
+ * args: BORROW
+ */
+uintptr_t pxs_argc(pxs_VarT args);
+
+/**
+ * Create a `pxs_List` of u8. i.e. bytes.
+ *
+ * Each byte goes into the scripting language as a number. Use `pxs_newbytebuf` for binary data.
+ *
+ * data: BORROW
+ * result: OWNED
+ */
+pxs_VarT pxs_newbytes(pxs_Opaque data, uintptr_t el_size, uintptr_t size);
+
+/**
+ * Create a `pxs_Bytes` by copying `len` bytes from `data`.
+ *
+ * Lua (string), Python (bytes), JS (Uint8Array)
+ *
+ * data: BORROW
+ * result: OWNED
+ */
+pxs_VarT pxs_newbytebuf(pxs_Opaque data, uintptr_t len);
+
+/**
+ * Get the data of a `pxs_Bytes`. Writes the length into `out_len` if it is not null.
+ *
+ * Returns null if `var` is not `pxs_Bytes`. The pointer is valid as long as `var` is.
+ *
+ * var: BORROW
+ * out_len: BORROW
+ * result: BORROW
+ */
+const uint8_t *pxs_getbytes(pxs_VarT var, uintptr_t *out_len);
+
+/**
+ * Get the length of a `pxs_Bytes`. Returns -1 if `var` is not `pxs_Bytes`.
+ *
+ * var: BORROW
+ */
+int64_t pxs_byteslen(pxs_VarT var);
+
+/**
+ * Create a `pxs_TypedArray` of `len` elements of `kind` by copying them from `data`. A null `data` makes a array of
+ * zeros.
+ *
+ * Lua (table), Python (list), JS (Float32Array, Float64Array, Int32Array, Uint8Array)
+ *
+ * data: BORROW
+ * result: OWNED
+ */
+pxs_VarT pxs_newtypedarray(enum pxs_ArrayKind kind,
+                           pxs_Opaque data,
+                           uintptr_t len);
+
+/**
+ * Get the data of a `pxs_TypedArray`, it can be read and written in place. Writes the kind into `out_kind` and the
+ * number of elements into `out_len` if they are not null.
+ *
+ * Returns null if `var` is not `pxs_TypedArray`. The pointer is valid as long as `var` is.
+ *
+ * var: BORROW
+ * out_kind: BORROW
+ * out_len: BORROW
+ * result: BORROW
+ */
+pxs_Opaque pxs_gettypedarray(pxs_VarT var,
+                             enum pxs_ArrayKind *out_kind,
+                             uintptr_t *out_len);
+
+/**
+ * Get the number of elements of a `pxs_TypedArray`. Returns -1 if `var` is not `pxs_TypedArray`.
+ *
+ * var: BORROW
+ */
+int64_t pxs_typedarraylen(pxs_VarT var);
 
 /**
- * Create a new arena in memory.
- * This does not return anything, it simply creates a scope that will allocate pxs_Var memory.
- * when finished call, `pxs_freearena`
+ * Convert a `pxs_List` of numbers (i.e. one returned by a script) into a `pxs_TypedArray` of `kind`. Floats are
+ * truncated and out of range values wrap, like `pxs_getint`. Returns a exception if `list` is not a list of numbers.
  *
- * result:OWNED
+ * list: BORROW
+ * result: OWNED
  */
-struct pxs_PixelArena *pxs_newarena(void);
+pxs_VarT pxs_typedarray_fromlist(enum pxs_ArrayKind kind,
+                                 pxs_VarT list);
 
 /**
- * Free arena. Upon freeing all variables allocated since `pxs_newarena` will be freed.
+ * Copy a `pxs_TypedArray` into a new `pxs_List` of numbers. Returns a exception if `var` is not `pxs_TypedArray`.
  *
- * arena:TRANSFER
+ * var: BORROW
+ * result: OWNED
  */
-void pxs_freearena(struct pxs_PixelArena *arena);
+pxs_VarT pxs_typedarray_tolist(pxs_VarT var);
 
 /**
- * Add a `pxs_VarT` to a `pxs_PixelArena`. Upon freeing the Arena, the variable is freed aswell.
+ * Make a diff between two snapshots (`pxs_Bytes`), i.e. periodic autosaves of world state. Store the diff instead
+ * of `new` and rebuild `new` with `pxs_snapshot_apply(old, diff)`.
  *
- * A variable must only be added once.
+ * Works with any binary format, small changes to `old` make small diffs. Returns a exception if `old` or `new` are
+ * not `pxs_Bytes`.
  *
- * arena:BORROW
- * var:TRANSFER
- * result:BORROW
+ * old: BORROW
+ * new: BORROW
+ * return: OWNED
  */
-pxs_VarT pxs_arenaput(struct pxs_PixelArena *arena, pxs_VarT var);
+pxs_VarT pxs_snapshot_diff(pxs_VarT old,
+                           pxs_VarT new_);
 
 /**
- * Add a `char*` to a `pxs_PixelArena`. Upon freeing the Arena, the string is freed aswell.
- *
- * This must be a string allocated by pixelscript. Either in:
- * - `pxs_getstring`
- * - `pxs_smart_getstring`
- * - `pxs_debugstate`
+ * Apply a diff made by `pxs_snapshot_diff` to the snapshot it was made from. Returns the new snapshot as
+ * `pxs_Bytes`, or a exception if the diff is invalid or was made from a different snapshot.
  *
- * A string must only be added once.
- *
- * arena:BORROW
- * str:TRANSFER
- * result:BORROW
+ * base: BORROW
+ * diff: BORROW
+ * return: OWNED
  */
-char *pxs_arena_putstr(struct pxs_PixelArena *arena, char *str);
+pxs_VarT pxs_snapshot_apply(pxs_VarT base,
+                            pxs_VarT diff);
 
 /**
- * Debug state info.
+ * Encode a var as MessagePack, for exchanging data faster than JSON (i.e. syncing entity state). Returns a
+ * `pxs_Bytes` with a 4 byte big endian length followed by the MessagePack data, or a exception for values that are
+ * not data (Objects, Functions, ...). Map keys are sorted, so equal vars give equal bytes.
  *
- * result:OWNED
+ * var:BORROW
+ * return:OWNED
  */
-char *pxs_debugstate(enum pxs_Runtime runtime);
+pxs_VarT pxs_tomsgpack(pxs_VarT var);
 
 /**
- * Call GC for all backends.
+ * Decode a `pxs_Bytes` made by `pxs_tomsgpack`. Returns a exception if the data is invalid.
+ *
+ * bytes:BORROW
+ * return:OWNED
  */
-void pxs_garbagecollect(void);
+pxs_VarT pxs_frommsgpack(pxs_VarT bytes);
 
 /**
- * Get the host IDX from a `pxs_HostObject`.
+ * Register a script object (Lua table, Python object, JS object) as a sync target.
  *
- * if result is < 0 then that means it is not a object.
+ * Returns the target id used in sync records, or -1 if `target` is not a object. The target keeps the object alive until
+ * `pxs_sync_unbind`, `pxs_sync_clear` or `pxs_finalize`.
  *
- * var: BORROW
+ * rt: BORROW
+ * target: BORROW
  */
-int32_t pxs_getidx(pxs_VarT var);
+int64_t pxs_sync_bind(pxs_VarT rt,
+                      pxs_VarT target);
 
 /**
- * Get a `pxs_VarT` from args without needing to worry about index checks.
+ * Remove a sync target. Returns true if it existed.
+ */
+bool pxs_sync_unbind(uint32_t id);
+
+/**
+ * Get the id of a field name for sync records. Registering the same name twice returns the same id.
  *
- * Literraly does `pxs_listget(args, idx + 1)`
+ * Returns -1 if `name` is null.
  *
- * args: BORROW
- * result: BORROW&NULLABLE
+ * name: BORROW
  */
-pxs_VarT pxs_arg(pxs_VarT args, int32_t idx);
+int32_t pxs_sync_field(const char *name);
 
 /**
- * Get the `pxs_VarT` runtime from args.
+ * Begin a sync batch. Returns a zeroed buffer of `len` bytes for the host to write records into.
  *
- * Does `pxs_listget(args, 0)`
+ * Each record is `[u32 target][u16 field][u8 pxs_SyncType][payload]`, little endian. Strings are a u32 length
+ * followed by the UTF-8 bytes. The buffer is valid until the next `pxs_sync_begin`.
  *
- * args: BORROW
- * result: BORROW&NULLABLE
+ * result: BORROW
  */
-pxs_VarT pxs_getrt(pxs_VarT args);
+uint8_t *pxs_sync_begin(uintptr_t len);
 
 /**
- * Get the length of args without the runtime.
+ * Apply every record written since `pxs_sync_begin` to the sync targets.
  *
- * args: BORROW
+ * Returns the number of fields set. Returns -1 without applying anything if the buffer is malformed or references
+ * a unknown target or field.
  */
-uintptr_t pxs_argc(pxs_VarT args);
+int64_t pxs_sync_end(void);
 
 /**
- * Create a `pxs_List` of u8. i.e. bytes.
- *
- * data: BORROW
- * result: OWNED
+ * Drop every sync target, field and buffer on this thread. `pxs_finalize` calls this for you.
  */
-pxs_VarT pxs_newbytes(pxs_Opaque data, uintptr_t el_size, uintptr_t size);
+void pxs_sync_clear(void);
 
 /**
  * Get the memory size (in bytes) of a `pxs_VarT`
@@ -1121,9 +3408,15 @@ uintptr_t pxs_varsize(pxs_VarT var);
  *   - `pxs_UInt64`
  *   - `pxs_Int64`
  *   - `pxs_Float64`
+ *   - `pxs_UInt32`
+ *   - `pxs_Int32`
+ *   - `pxs_Float32`
  *   - `pxs_Bool`
  *   - `pxs_String`
  *   - `pxs_List`
+ *   - `pxs_Byte`
+ *   - `pxs_Bytes`
+ *   - `pxs_TypedArray`
  *
  * var: BORROW
  * data_ptr: BORROW
@@ -1171,11 +3464,10 @@ void pxs_smart_copystring(pxs_VarT rt,
 enum pxs_VarType pxs_vartype(pxs_VarT var);
 
 /**
- * Encode a `pxs_Var` into a JSON string. Will return a `pxs_Var` of type string.
- * Transfers ownership of args.
- * Basically calls the runtime.pxs_json.encode() function.
+ * Encode `args[0]` into a JSON string, `args[1]` is a optional indent (spaces per level). Returns a `pxs_String` or
+ * a exception for values that can not be encoded (i.e. Functions).
  *
- * Note: This function is already enabled in each scripting language. This is a host language wrapper for calling it easily.
+ * Note: `pxs_json` is already in each scripting language. This is a host language wrapper for calling it easily.
  *
  * rt:BORROW
  * args:TRANSFER
@@ -1185,11 +3477,9 @@ pxs_VarT pxs_json_encode(pxs_VarT rt,
                          pxs_VarT args);
 
 /**
- * Decode a `pxs_String` into a `pxs_Var`.
- * Make sure runtime is the first argument in args.
- * Transfers ownership of args.
+ * Decode the JSON `pxs_String` `args[0]`. Objects become `pxs_Map`s and arrays `pxs_List`s.
  *
- * Note: This function is already enabled in each scripting language. This is a host language wrapper for calling it easily.
+ * Note: `pxs_json` is already in each scripting language. This is a host language wrapper for calling it easily.
  *
  * rt:BORROW
  * args:TRANSFER
@@ -1205,6 +3495,321 @@ pxs_VarT pxs_json_decode(pxs_VarT rt,
  */
 void pxs_meminit(void);
 
+/**
+ * Initialize the `pxs_template` module.
+ *
+ * This needs to be called in each new thread too. Should only be called once per thread.
+ */
+void pxs_templateinit(void);
+
+/**
+ * Initialize the `pxs_csv` module.
+ *
+ * This needs to be called in each new thread too. Should only be called once per thread.
+ */
+void pxs_csvinit(void);
+
+/**
+ * Initialize the `pxs_color` module.
+ *
+ * This needs to be called in each new thread too. Should only be called once per thread.
+ */
+void pxs_colorinit(void);
+
+/**
+ * Initialize the `pxs_units` module.
+ *
+ * This needs to be called in each new thread too. Should only be called once per thread.
+ */
+void pxs_unitsinit(void);
+
+/**
+ * Initialize the `pxs_geom` module.
+ *
+ * This needs to be called in each new thread too. Should only be called once per thread.
+ */
+void pxs_geominit(void);
+
+/**
+ * Initialize the `pxs_noise` module.
+ *
+ * This needs to be called in each new thread too. Should only be called once per thread.
+ */
+void pxs_noiseinit(void);
+
+/**
+ * Set the grid used by the `pxs_path` module.
+ *
+ * `cost` is called with a cell and should return the cost of entering it (1.0 for normal ground).
+ * Return a negative number for blocked cells. The search assumes no cell costs less than 1.0, scripts pass the
+ * `min_cost` option to `find` for cheaper cells.
+ */
+void pxs_path_setprovider(int32_t width,
+                          int32_t height,
+                          pxs_PathCostFn cost);
+
+/**
+ * Initialize the `pxs_path` module.
+ *
+ * This needs to be called in each new thread too. Should only be called once per thread.
+ */
+void pxs_pathinit(void);
+
+/**
+ * Set the callbacks used by the `pxs_audio` module.
+ *
+ * `play` should return a id for the started voice, this id is passed to `set_param` and `stop`.
+ */
+void pxs_audio_setcallbacks(pxs_AudioPlayFn play, pxs_AudioParamFn set_param, pxs_AudioStopFn stop);
+
+/**
+ * Initialize the `pxs_audio` module.
+ *
+ * This needs to be called in each new thread too. Should only be called once per thread.
+ */
+void pxs_audioinit(void);
+
+/**
+ * Set the callback that receives bound value changes from the `pxs_ui` module.
+ */
+void pxs_ui_setcallback(pxs_UiChangeFn func);
+
+/**
+ * Poll every `pxs_ui` binding on this thread and notify the host of values changed since their last notification.
+ *
+ * Script writes are not watched, call this once per frame (or after running scripts) to pick them up.
+ * Returns the number of notifications sent.
+ */
+int32_t pxs_ui_poll(void);
+
+/**
+ * Drop every `pxs_ui` binding on this thread.
+ *
+ * Bindings keep script objects alive, call this before clearing or stopping a runtime. `pxs_finalize` calls this for you.
+ */
+void pxs_ui_clear(void);
+
+/**
+ * Initialize the `pxs_ui` module.
+ *
+ * This needs to be called in each new thread too. Should only be called once per thread.
+ */
+void pxs_uiinit(void);
+
+/**
+ * Migrate save data from version `from` to version `to` using the migrations registered by scripts
+ * with `pxs_save.register_migration`.
+ *
+ * Returns the migrated data or a exception if no chain of migrations exists.
+ *
+ * data:BORROW
+ * return:OWNED
+ */
+pxs_VarT pxs_migrate_save(pxs_VarT data, int64_t from, int64_t to);
+
+/**
+ * Drop every migration registered on this thread.
+ *
+ * Migrations keep script functions alive, call this before clearing or stopping a runtime. `pxs_finalize` calls this for you.
+ */
+void pxs_save_clear(void);
+
+/**
+ * Initialize the `pxs_save` module.
+ *
+ * This needs to be called in each new thread too. Should only be called once per thread.
+ */
+void pxs_saveinit(void);
+
+/**
+ * Set the callbacks used by the `pxs_clock` module.
+ *
+ * `game_time` and `real_time` return seconds, `frame` returns the current frame index.
+ */
+void pxs_clock_setcallbacks(pxs_ClockTimeFn game_time,
+                            pxs_ClockTimeFn real_time,
+                            pxs_ClockFrameFn frame);
+
+/**
+ * Initialize the `pxs_clock` module.
+ *
+ * This needs to be called in each new thread too. Should only be called once per thread.
+ */
+void pxs_clockinit(void);
+
+/**
+ * Set the executor that runs jobs submitted with `pxs_jobs.submit`.
+ */
+void pxs_job_setexecutor(pxs_JobSubmitFn func);
+
+/**
+ * Complete a job submitted with `pxs_jobs.submit` and run its `then` callbacks.
+ *
+ * Pass a `pxs_Exception` to fail the job with its message.
+ *
+ * result: TRANSFER
+ *
+ * Returns false if the job is unknown or already completed.
+ */
+bool pxs_job_complete(int64_t id, pxs_VarT result);
+
+/**
+ * Drop every `pxs_jobs` job on this thread.
+ *
+ * Jobs keep script callbacks alive, call this before clearing or stopping a runtime. `pxs_finalize` calls this for you.
+ */
+void pxs_jobs_clear(void);
+
+/**
+ * Initialize the `pxs_jobs` module.
+ *
+ * This needs to be called in each new thread too. Should only be called once per thread.
+ */
+void pxs_jobsinit(void);
+
+/**
+ * Create a channel for sending data between threads (the host, contexts and script threads). Returns its id, used
+ * by `pxs_channel_send` and `pxs_channel_recv` and by scripts through the `pxs_channel` module.
+ */
+int32_t pxs_channel_new(void);
+
+/**
+ * Send a deep copy of `var` to a channel. Only data (numbers, strings, bytes, lists and maps) can be sent. Returns
+ * false if the channel does not exist or `var` is not data.
+ *
+ * var:BORROW
+ */
+bool pxs_channel_send(int32_t channel,
+                      pxs_VarT var);
+
+/**
+ * Receive the oldest value of a channel. Waits up to `timeout_ms` for one (forever if negative, not at all if 0).
+ * Returns null on timeout, a exception if the channel does not exist or was closed.
+ *
+ * return:OWNED
+ */
+pxs_VarT pxs_channel_recv(int32_t channel,
+                          int64_t timeout_ms);
+
+/**
+ * Close a channel. Receivers waiting on it get the values still in it, then a exception. Returns false if it does
+ * not exist. `pxs_finalize` closes every channel.
+ */
+bool pxs_channel_close(int32_t channel);
+
+/**
+ * Initialize the `pxs_channel` module.
+ *
+ * This needs to be called in each new thread too. Should only be called once per thread. Script threads get it from
+ * the thread that spawned them.
+ */
+void pxs_channelinit(void);
+
+/**
+ * Emit a event to the `pxs_events` handlers of every runtime on this thread, in registration order. Returns the
+ * number of handlers called.
+ *
+ * name:BORROW
+ * payload:BORROW (NULLABLE)
+ */
+int32_t pxs_emit(const char *name,
+                 pxs_VarT payload);
+
+/**
+ * Drop every `pxs_events` handler on this thread.
+ *
+ * Handlers keep script functions alive, call this before clearing or stopping a runtime. `pxs_finalize` calls this
+ * for you.
+ */
+void pxs_events_clear(void);
+
+/**
+ * Initialize the `pxs_events` module.
+ *
+ * This needs to be called in each new thread too. Should only be called once per thread.
+ */
+void pxs_eventsinit(void);
+
+/**
+ * Initialize the `pxs_fs` module. Reads and writes go through `pxs_set_filereader`, `pxs_set_filewriter` and
+ * `pxs_set_dirreader`.
+ *
+ * This needs to be called in each new thread too. Should only be called once per thread.
+ */
+void pxs_fsinit(void);
+
+/**
+ * Initialize the `pxs_str` module.
+ *
+ * This needs to be called in each new thread too. Should only be called once per thread.
+ */
+void pxs_strinit(void);
+
+/**
+ * Initialize the `pxs_math` module.
+ *
+ * This needs to be called in each new thread too. Should only be called once per thread.
+ */
+void pxs_mathinit(void);
+
+/**
+ * Seed the `pxs_random` generator of this thread (the one used by the module functions, not `Random` objects).
+ * Generators start with seed 0.
+ */
+void pxs_random_setseed(int64_t seed);
+
+/**
+ * Initialize the `pxs_random` module.
+ *
+ * This needs to be called in each new thread too. Should only be called once per thread.
+ */
+void pxs_randominit(void);
+
+/**
+ * Set the time source (NULLABLE) of `pxs_time`, seconds since the Unix epoch. Replaces the system clock for
+ * `now` and `monotonic` so deterministic replays can feed their own time. Null restores the system clock.
+ */
+void pxs_set_clock(struct Option_pxs_ClockTimeFn func);
+
+/**
+ * Allow or forbid `pxs_time.sleep`. Forbidden by default, a sleeping script blocks its whole thread.
+ */
+void pxs_time_allowsleep(bool allow);
+
+/**
+ * Initialize the `pxs_time` module.
+ *
+ * This needs to be called in each new thread too. Should only be called once per thread.
+ */
+void pxs_timeinit(void);
+
+/**
+ * Set the sink (NULLABLE) of `pxs_log`. It gets every message at `min_level` or above with the script file and line
+ * that logged it. Null restores the default output (info and above on stderr).
+ */
+void pxs_set_log_sink(struct Option_pxs_LogSinkFn func,
+                      enum pxs_LogLevel min_level);
+
+/**
+ * Initialize the `pxs_log` module.
+ *
+ * This needs to be called in each new thread too. Should only be called once per thread.
+ */
+void pxs_loginit(void);
+
+/**
+ * Set the handler (NULLABLE) doing the network IO of `pxs_http`. It gets a `pxs_HttpRequest` and returns a
+ * `pxs_HttpResponse`, pixelscript itself does no networking. Without a handler requests raise a exception.
+ */
+void pxs_set_http_handler(struct Option_pxs_HttpHandlerFn func);
+
+/**
+ * Initialize the `pxs_http` module.
+ *
+ * This needs to be called in each new thread too. Should only be called once per thread.
+ */
+void pxs_httpinit(void);
+
 /**
  * Intialize the `yoyo` modules.
  *
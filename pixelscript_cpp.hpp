@@ -573,6 +573,8 @@ namespace pxs {
                 return "Exception";
             case pxs_Map:
                 return "Map";
+            case pxs_Bytes:
+                return "Bytes";
             default:
                 return "Unkown"; 
         }
@@ -10,11 +10,10 @@ use etffi::{
 use crate::{
     js::{
         func::create_callback,
-        module::add_local_module,
         utils::SmartJSValue,
         var::{js_into_pxs, pxs_into_js},
     }, pxs_debug, pxs_error, shared::{
-        PXS_METHOD_NAME, PixelScript, PxsRes, PxsResult, pxs_Opaque, read_file,
+        PXS_METHOD_NAME, PixelScript, PxsRes, PxsResult, internal_chunk_name, limit, pxs_Opaque, pxs_Runtime, read_file, resolve_module,
         var::{ObjectMethods, pxs_Var},
     }, with_feature,
 };
@@ -79,10 +78,8 @@ fn init(ptr: *mut State) {
         quickjs::JS_SetModuleLoaderFunc(rt, None, Some(js_module_loader), std::ptr::null_mut());
 
         with_feature!("pxs_json", {
-            // Add pxs_json.js
-            let pxs_json = add_local_module(ctx, include_str!("../../core/js/pxs_json.js"), "pxs_json");
-
-            (*ptr).modules.insert("pxs_json".to_string(), pxs_json);
+            // Add pxs_json
+            module::add_module(ctx, &crate::pxs_core::pxs_json::module());
         });
 
         with_feature!("js_commonjs", {
@@ -137,6 +134,22 @@ thread_local! {
     static JSTATE: ThreadSafePointer<State> = ThreadSafePointer::new_owned(new_state());
 }
 
+/// QuickJS calls the interrupt handler about every 10000 instructions (`JS_INTERRUPT_COUNTER_INIT`).
+const JS_LIMIT_STEP: u64 = 10000;
+
+/// Interrupt handler used under a execution limit.
+unsafe extern "C" fn limit_interrupt(_rt: *mut quickjs::JSRuntime, _opaque: *mut std::ffi::c_void) -> i32 {
+    match limit::step(&pxs_Runtime::pxs_JavaScript, JS_LIMIT_STEP) {
+        limit::Step::Continue => 0,
+        limit::Step::Stop => 1,
+        // A interrupt can not yield, so a suspension stops the script too.
+        limit::Step::Suspend => {
+            limit::suspend();
+            1
+        }
+    }
+}
+
 /// JS Module loader
 unsafe extern "C" fn js_module_loader(
     context: *mut quickjs::JSContext,
@@ -151,8 +164,11 @@ unsafe extern "C" fn js_module_loader(
             return *module;
         }
 
-        // Otherwise try to read the file...
-        let contents = read_file(name);
+        // Otherwise ask the resolvers, then try to read the file...
+        let contents = match resolve_module(name, pxs_Runtime::pxs_JavaScript) {
+            Some((contents, _)) => contents,
+            None => read_file(name),
+        };
         if contents.len() == 0 {
             return std::ptr::null_mut();
         }
@@ -230,7 +246,7 @@ unsafe extern "C" fn commonjs_require(
 
         // Evaluate it again or for the first time!
         let code = format!("import * as __{module_name}__ from '{module_name}';");
-        let res = run_js(&code, "<commonjs_require>", quickjs::JS_EVAL_TYPE_MODULE as i32);
+        let res = run_js(&code, &internal_chunk_name("commonjs_require"), quickjs::JS_EVAL_TYPE_MODULE as i32);
         if res.is_exception() {
             return res.dupped_value();
         }
@@ -276,7 +292,7 @@ fn run_js(code: &str, file_name: &str, eval_type: i32) -> SmartJSValue {
 
 /// Get JS Name (runs code without global this)
 fn get_js_name(name: &str) -> SmartJSValue {
-    run_js(name, "<get_js_name>", quickjs::JS_EVAL_TYPE_GLOBAL as i32)
+    run_js(name, &internal_chunk_name("get_js_name"), quickjs::JS_EVAL_TYPE_GLOBAL as i32)
 }
 
 /// Add main.js
@@ -300,7 +316,7 @@ fn import_all_modules() {
     }
     run_js(
         &import_modules_code,
-        "<cleanup>",
+        &internal_chunk_name("cleanup"),
         quickjs::JS_EVAL_TYPE_MODULE as i32,
     );
 }
@@ -329,6 +345,16 @@ impl PixelScript for JSScripting {
         module::add_module(get_context(state), &source);
     }
 
+    fn remove_module(source: &crate::shared::module::pxs_Module, object_types: &[String]) {
+        module::remove_module(source);
+        let state = get_js_state();
+        for type_name in object_types {
+            unsafe {
+                (*state).defined_objects.remove(type_name);
+            }
+        }
+    }
+
     fn execute(code: &str, file_name: &str) -> PxsResult {
         let res = run_js(code, file_name, quickjs::JS_EVAL_TYPE_MODULE as i32);
         let pxs_res = js_into_pxs(&res);
@@ -349,6 +375,42 @@ impl PixelScript for JSScripting {
         js_into_pxs(&res)
     }
 
+    fn eval_with(code: &str, name: &str, bindings: &pxs_Var) -> PxsResult {
+        // The bindings become the parameters of a function returning the expression.
+        let factory = Self::eval(
+            "((code, names) => Function(...Object.keys(names), 'return (' + code + ');')(...Object.values(names)))",
+            name,
+        )?;
+        let mut args = crate::shared::var::pxs_VarList::new();
+        args.add_item(pxs_Var::new_string(code.to_string()));
+        args.add_item(bindings.shallow_copy());
+        Self::var_call(&factory, &mut args)
+    }
+
+    fn object_overrides(var: &pxs_Var, method: &str) -> PxsRes<bool> {
+        // Walk the prototypes until the one of the host type, marked with `_pxs_bridge`.
+        let check = Self::eval(
+            "((obj, name) => { \
+                const own = (o, key) => Object.prototype.hasOwnProperty.call(o, key); \
+                for (let o = obj; o; o = Object.getPrototypeOf(o)) { \
+                    if (own(o, '_pxs_bridge')) return false; \
+                    if (own(o, name)) return true; \
+                } \
+                return false; \
+            })",
+            &internal_chunk_name("object_overrides"),
+        )?;
+        let mut args = crate::shared::var::pxs_VarList::new();
+        args.add_item(var.shallow_copy());
+        args.add_item(pxs_Var::new_string(method.to_string()));
+        Self::var_call(&check, &mut args)?.get_bool()
+    }
+
+    fn execute_with_result(code: &str, file_name: &str) -> PxsResult {
+        // Module scripts have no completion value, run it as a global script.
+        Self::eval(code, file_name)
+    }
+
     fn start_thread() {
         // This is dangerous and could potentially break your program.
         // You must only call this in a real thread
@@ -475,6 +537,89 @@ impl PixelScript for JSScripting {
             }
         }
     }
+
+    fn trim_memory(object_types: &[String]) -> usize {
+        let state = get_js_state();
+        unsafe {
+            if (*state).rt.is_null() {
+                return 0;
+            }
+            let used = || {
+                let mut usage: quickjs::JSMemoryUsage = std::mem::zeroed();
+                quickjs::JS_ComputeMemoryUsage((*state).rt, &mut usage);
+                usage.malloc_size.max(0) as usize
+            };
+            let before = used();
+            for type_name in object_types {
+                (*state).defined_objects.remove(type_name);
+            }
+            Self::garbage_collect();
+            before.saturating_sub(used())
+        }
+    }
+
+    fn new_function(fn_idx: i32) -> PxsResult {
+        let context = get_context(get_js_state());
+        js_into_pxs(&create_callback(context, fn_idx))
+    }
+
+    fn set_exec_hooks(enabled: bool) {
+        let state = get_js_state();
+        unsafe {
+            if enabled {
+                quickjs::JS_SetInterruptHandler((*state).rt, Some(limit_interrupt), std::ptr::null_mut());
+            } else {
+                quickjs::JS_SetInterruptHandler((*state).rt, None, std::ptr::null_mut());
+            }
+        }
+    }
+
+    fn caller_location() -> Option<(String, i32)> {
+        let context = get_context(get_js_state());
+        // A new error captures the backtrace, lines look like `    at name (file:line:col)` or `    at file:line:col`.
+        let error = SmartJSValue::new_owned(unsafe { quickjs::JS_NewError(context) }, context);
+        let stack = error.get_prop("stack").as_string().ok()?;
+        stack.lines().find_map(|frame| {
+            let frame = frame.trim().strip_prefix("at ")?;
+            let location = match frame.rfind('(') {
+                Some(start) => frame[start + 1..].strip_suffix(')')?,
+                None => frame,
+            };
+            // Host functions show up as `(native)`.
+            let mut parts = location.rsplitn(3, ':');
+            let _col = parts.next()?;
+            let line = parts.next()?.parse::<i32>().ok()?;
+            Some((parts.next()?.to_string(), line))
+        })
+    }
+
+    fn import_module(name: &str) -> PxsResult {
+        // The import promise is awaited by `run_js`.
+        Self::eval(&format!("import('{name}')"), &internal_chunk_name("import"))
+    }
+
+    fn new_iterator(var: &pxs_Var) -> PxsResult {
+        // Iterables use their iterator, plain objects iterate `[key, value]` entries.
+        let factory = Self::eval(
+            "((o) => { const it = (o != null && typeof o[Symbol.iterator] === 'function') ? o[Symbol.iterator]() : Object.entries(o)[Symbol.iterator](); return () => { const r = it.next(); return r.done ? null : [r.value]; }; })",
+            &internal_chunk_name("iter"),
+        )?;
+        let mut args = crate::shared::var::pxs_VarList::new();
+        args.add_item(var.shallow_copy());
+        Self::var_call(&factory, &mut args)
+    }
+
+    fn new_coroutine(func: &pxs_Var, args: &pxs_Var) -> PxsResult {
+        // `func` is a generator function, the first resume arg is sent as the result of `yield`.
+        let factory = Self::eval(
+            "((f, args) => { let gen = null; return (...a) => { const r = gen === null ? (gen = f(...args)).next() : gen.next(a[0]); return [r.done, r.value === undefined ? null : r.value]; }; })",
+            &internal_chunk_name("coroutine"),
+        )?;
+        let mut factory_args = crate::shared::var::pxs_VarList::new();
+        factory_args.add_item(func.shallow_copy());
+        factory_args.add_item(args.shallow_copy());
+        Self::var_call(&factory, &mut factory_args)
+    }
 }
 
 impl ObjectMethods for JSScripting {
@@ -558,4 +703,11 @@ impl ObjectMethods for JSScripting {
     fn get_from_name(name: &str) -> PxsResult {
         js_into_pxs(&get_js_name(name))
     }
+
+    fn set_global(name: &str, value: &pxs_Var) -> PxsRes<()> {
+        let state = get_js_state();
+        let mut value = pxs_into_js(get_context(state), value)?;
+        SmartJSValue::globalThis(get_context(state)).set_prop(name, &mut value);
+        Ok(())
+    }
 }
@@ -85,6 +85,29 @@ pub(super) fn add_module(context: *mut quickjs::JSContext, module: &Arc<pxs_Modu
         }
     }
 
+    // Classes are the constructor (or a object without one) with statics and constants as properties.
+    for class in module.classes.iter() {
+        let mut value = match &class.constructor {
+            Some(constructor) => create_callback(context, constructor.idx),
+            None => SmartJSValue::new_object(context),
+        };
+        for method in class.callbacks.iter() {
+            value.set_prop(&method.name, &mut create_callback(context, method.idx));
+        }
+        for class_var in class.variables.iter() {
+            match pxs_into_js(context, &class_var.var) {
+                Ok(mut var) => value.set_prop(&class_var.name, &mut var),
+                Err(err) => pxs_debug!("Could not convert {}.{}: {err}", class.name, class_var.name),
+            }
+        }
+        value.owned = false;
+        exports.push(JSModuleMethod { name: class.name.clone(), value });
+
+        unsafe {
+            quickjs::JS_AddModuleExport(context, js_mod, cstrsafe.new_string(&class.name));
+        }
+    }
+
     // Save in state
     let state = get_js_state();
     unsafe {
@@ -100,20 +123,15 @@ pub(super) fn add_module(context: *mut quickjs::JSContext, module: &Arc<pxs_Modu
     }
 }
 
-/// Add a local module to JS engine.
-pub(super) fn add_local_module(context: *mut quickjs::JSContext, code: &str, name: &str) -> *mut quickjs::JSModuleDef {
-    let mut cstrsafe = CStringSafe::new();
-
-    // Compile module
-    let smart_module = SmartJSValue::new_owned(unsafe {
-        quickjs::JS_Eval(context, cstrsafe.new_string(code), code.len(), cstrsafe.new_string(name), (quickjs::JS_EVAL_TYPE_MODULE | quickjs::JS_EVAL_FLAG_COMPILE_ONLY) as i32)
-    }, context);
-
-    // Check exception
-    if smart_module.is_exception() || smart_module.is_error() {
-        pxs_debug!("Error compiling module");
-        return std::ptr::null_mut();
+/// Remove a module from the state so the module loader no longer resolves it.
+pub(super) fn remove_module(module: &pxs_Module) {
+    let state = get_js_state();
+    unsafe {
+        (*state).module_exports.remove(&module.name);
+        (*state).modules.remove(&module.name);
     }
 
-    smart_module.get_module_ptr()
-}
\ No newline at end of file
+    for child in module.modules.iter() {
+        remove_module(child);
+    }
+}
@@ -1,6 +1,6 @@
 use std::sync::Arc;
 
-use crate::{js::{SmartJSValue, func::create_object_callback, get_js_state, quickjs}, shared::{PXS_PTR_NAME, object::{ObjectFlags, pxs_PixelObject}}};
+use crate::{js::{SmartJSValue, func::create_object_callback, get_js_state, quickjs}, shared::{PXS_PTR_NAME, object::{ObjectCallback, ObjectFlags, pxs_Operator, pxs_PixelObject}, registry}};
 
 pub(super) fn create_object(ctx: *mut quickjs::JSContext, idx: i32, source: Arc<pxs_PixelObject>) -> SmartJSValue {
     let state = get_js_state();
@@ -19,25 +19,54 @@ pub(super) fn create_object(ctx: *mut quickjs::JSContext, idx: i32, source: Arc<
         }
     }
 
-    // Create new object
-    for object_cbk in source.callbacks.iter() {
+    // Define the type, then create the object with it.
+    define_type(ctx, type_name, &source.callbacks);
+
+    // Recursion!
+    create_object(ctx, idx, source)
+}
+
+/// Define the prototype of `type_name`. The prototype of a parent type is defined first (from its registered
+/// callbacks) and becomes the prototype of this one.
+fn define_type(ctx: *mut quickjs::JSContext, type_name: &str, callbacks: &[ObjectCallback]) {
+    let state = get_js_state();
+    if unsafe { (*state).defined_objects.contains_key(type_name) } {
+        return;
+    }
+
+    let object = SmartJSValue::new_object(ctx);
+    // Marks the prototype of a host type for `object_overrides`.
+    object.set_prop("_pxs_bridge", &mut SmartJSValue::new_bool(ctx, true));
+    for object_cbk in callbacks.iter() {
         let module_cbk = &object_cbk.cbk;
         let flags = object_cbk.flags;
+        // No operator overloading in JS, only `toString` can be used.
+        let name = match object_cbk.operator {
+            Some(pxs_Operator::pxs_Operator_ToString) => "toString",
+            Some(_) => continue,
+            None => module_cbk.name.as_str(),
+        };
 
         let mut func = create_object_callback(ctx, module_cbk.idx, flags);
         if flags & (ObjectFlags::IsProp as u8) != 0 {
-            object.add_getter_setter(&module_cbk.name, &func);
+            object.add_getter_setter(name, &func);
         } else {
             // Set
-            object.set_prop(&module_cbk.name, &mut func);
+            object.set_prop(name, &mut func);
+        }
+    }
+
+    if let Some(parent) = registry::parent_of(type_name) {
+        define_type(ctx, &parent, &registry::type_callbacks(&parent));
+        unsafe {
+            if let Some(parent_proto) = (*state).defined_objects.get(&parent) {
+                object.set_proto(parent_proto);
+            }
         }
     }
 
     // Define obj
     unsafe {
-        (*state).defined_objects.insert(type_name.clone(), object);
+        (*state).defined_objects.insert(type_name.to_string(), object);
     }
-
-    // Recursion!
-    create_object(ctx, idx, source)
 }
@@ -3,7 +3,7 @@ use etffi::cstring::CStringSafe;
 use crate::{
     borrow_string, js::quickjs::{
             self, JS_IsArray, JS_IsError, JS_IsFunction, JS_IsPromise, JS_PromiseResult
-        }, pxs_error, shared::PxsRes
+        }, pxs_error, shared::{PxsRes, var::{pxs_ArrayKind, pxs_VarTypedArray}}
 };
 
 /// Macro for writing out the JS_Is functions
@@ -133,6 +133,31 @@ impl SmartJSValue {
         }
     }
 
+    /// Create a new Uint8Array holding a copy of `bytes` (owned)
+    pub fn new_bytes(context: *mut quickjs::JSContext, bytes: &[u8]) -> Self {
+        unsafe {
+            let arr = quickjs::JS_NewUint8ArrayCopy(context, bytes.as_ptr(), bytes.len());
+            Self::new_owned(arr, context)
+        }
+    }
+
+    /// Create a new Float32Array, Float64Array, Int32Array or Uint8Array holding a copy of `array` (owned)
+    pub fn new_typed_array(context: *mut quickjs::JSContext, array: &pxs_VarTypedArray) -> Self {
+        unsafe {
+            let bytes = array.as_bytes();
+            let buffer = Self::new_owned(quickjs::JS_NewArrayBufferCopy(context, bytes.as_ptr(), bytes.len()), context);
+            let kind = match array.kind() {
+                pxs_ArrayKind::pxs_ArrayF32 => quickjs::JSTypedArrayEnum_JS_TYPED_ARRAY_FLOAT32,
+                pxs_ArrayKind::pxs_ArrayF64 => quickjs::JSTypedArrayEnum_JS_TYPED_ARRAY_FLOAT64,
+                pxs_ArrayKind::pxs_ArrayI32 => quickjs::JSTypedArrayEnum_JS_TYPED_ARRAY_INT32,
+                pxs_ArrayKind::pxs_ArrayU8 => quickjs::JSTypedArrayEnum_JS_TYPED_ARRAY_UINT8,
+            };
+            let mut argv = [buffer.value];
+            let arr = quickjs::JS_NewTypedArray(context, 1, argv.as_mut_ptr(), kind);
+            Self::new_owned(arr, context)
+        }
+    }
+
     /// Create a new Array (owned)
     pub fn new_array(context: *mut quickjs::JSContext) -> Self {
         unsafe {
@@ -325,6 +350,49 @@ impl SmartJSValue {
         }
     }
 
+    /// Get a copy of the data of a Uint8Array or ArrayBuffer. `None` for anything else.
+    pub fn as_bytes(&self) -> Option<Vec<u8>> {
+        unsafe {
+            let mut size = 0;
+            let data = if quickjs::JS_GetTypedArrayType(self.value) == quickjs::JSTypedArrayEnum_JS_TYPED_ARRAY_UINT8 as i32 {
+                quickjs::JS_GetUint8Array(self.context, &mut size, self.value)
+            } else if quickjs::JS_IsArrayBuffer(self.value) {
+                quickjs::JS_GetArrayBuffer(self.context, &mut size, self.value)
+            } else {
+                return None;
+            };
+
+            if data.is_null() || size == 0 {
+                Some(vec![])
+            } else {
+                Some(std::slice::from_raw_parts(data, size).to_vec())
+            }
+        }
+    }
+
+    /// Get a copy of the data of a Float32Array, Float64Array or Int32Array. `None` for anything else.
+    pub fn as_typed_array(&self) -> Option<pxs_VarTypedArray> {
+        unsafe {
+            let kind = match quickjs::JS_GetTypedArrayType(self.value) {
+                t if t == quickjs::JSTypedArrayEnum_JS_TYPED_ARRAY_FLOAT32 as i32 => pxs_ArrayKind::pxs_ArrayF32,
+                t if t == quickjs::JSTypedArrayEnum_JS_TYPED_ARRAY_FLOAT64 as i32 => pxs_ArrayKind::pxs_ArrayF64,
+                t if t == quickjs::JSTypedArrayEnum_JS_TYPED_ARRAY_INT32 as i32 => pxs_ArrayKind::pxs_ArrayI32,
+                _ => return None,
+            };
+
+            let (mut offset, mut len, mut elem_size) = (0, 0, 0);
+            let buffer = quickjs::JS_GetTypedArrayBuffer(self.context, self.value, &mut offset, &mut len, &mut elem_size);
+            let buffer = Self::new_owned(buffer, self.context);
+            let mut size = 0;
+            let data = quickjs::JS_GetArrayBuffer(self.context, &mut size, buffer.value);
+            if data.is_null() || len == 0 {
+                return Some(pxs_VarTypedArray::new(kind, 0));
+            }
+            // Typed arrays are always aligned to their element size.
+            Some(pxs_VarTypedArray::copy_from(kind, data.add(offset) as *const std::ffi::c_void, len / kind.elem_size()))
+        }
+    }
+
     /// Get as i32 (only works on numbers)
     pub fn as_i32(&self) -> PxsRes<i32> {
         if !self.is_number() {
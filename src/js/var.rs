@@ -58,11 +58,15 @@ pub(super) fn js_into_pxs(value: &SmartJSValue) -> PxsResult {
         }
 
         Ok(pxs_Var::new_list_with(values))
+    } else if let Some(bytes) = value.as_bytes() {
+        Ok(pxs_Var::new_bytes(bytes))
+    } else if let Some(array) = value.as_typed_array() {
+        Ok(pxs_Var::new_typed_array(array))
     } else if value.is_function() {
         Ok(pxs_Var::new_function(
             JSPXSContainer::from_value(value.clone()).into_raw() as *mut c_void,
             Some(js_deleter),
-        ))
+        ).with_origin(pxs_Runtime::pxs_JavaScript))
     } else if value.is_exception() {
         // let exce = value.as_exception().unwrap();
         Ok(pxs_Var::new_exception(value.get_error_exception().unwrap()))
@@ -72,7 +76,7 @@ pub(super) fn js_into_pxs(value: &SmartJSValue) -> PxsResult {
         Ok(pxs_Var::new_null())
     } else {
         // As object.
-        Ok(pxs_Var::new_object(pxs_VarObject::new_lang_only(JSPXSContainer::from_value(value.clone()).into_void()), Some(js_deleter)))
+        Ok(pxs_Var::new_object(pxs_VarObject::new_lang_only(JSPXSContainer::from_value(value.clone()).into_void()), Some(js_deleter)).with_origin(pxs_Runtime::pxs_JavaScript))
     }
 }
 
@@ -144,7 +148,7 @@ pub(super) fn pxs_into_js(context: *mut quickjs::JSContext, var: &pxs_Var) -> Px
         },
         crate::shared::var::pxs_VarType::pxs_Exception => {
             let message = var.get_string().unwrap();
-            let error = SmartJSValue::new_exception(context, message, "Exception".to_string());
+            let error = SmartJSValue::new_exception(context, message, var.error_kind.name().to_string());
 
             unsafe {
                 Ok(SmartJSValue::new_owned(quickjs::JS_Throw(context, error.dupped_value()), context))
@@ -171,5 +175,15 @@ pub(super) fn pxs_into_js(context: *mut quickjs::JSContext, var: &pxs_Var) -> Px
         crate::shared::var::pxs_VarType::pxs_Byte => {
             Ok(SmartJSValue::new_i32(context, var.get_byte()? as i32))
         },
+        crate::shared::var::pxs_VarType::pxs_Bytes => {
+            Ok(SmartJSValue::new_bytes(context, var.get_bytes().unwrap()))
+        },
+        crate::shared::var::pxs_VarType::pxs_Int32 => Ok(SmartJSValue::new_i32(context, var.get_i32()?)),
+        // Does not fit in a i32, JS numbers are doubles anyway.
+        crate::shared::var::pxs_VarType::pxs_UInt32 => Ok(SmartJSValue::new_f64(context, var.get_u32()? as f64)),
+        crate::shared::var::pxs_VarType::pxs_Float32 => Ok(SmartJSValue::new_f64(context, var.get_f32()? as f64)),
+        crate::shared::var::pxs_VarType::pxs_TypedArray => {
+            Ok(SmartJSValue::new_typed_array(context, var.get_typed_array().unwrap()))
+        },
     }
 }
@@ -16,9 +16,12 @@
 use etffi::{borrow_string, create_raw_string, cstring::CStringSafe, free_raw_string, ptr_magic::PtrMagic};
 use shared::{func::pxs_Func, var::pxs_Var};
 use std::{
-    ffi::{CString, c_char, c_void},
+    ffi::{CStr, CString, c_char, c_void},
     ptr,
-    sync::Arc,
+    sync::{
+        Arc,
+        atomic::{AtomicU8, Ordering},
+    },
 };
 
 #[cfg(feature = "js")]
@@ -29,16 +32,36 @@ use crate::lua::LuaScripting;
 use crate::python::PythonScripting;
 
 use crate::shared::{
-    PXS_PTR_NAME, PixelScript,
+    PXS_PTR_NAME, PixelScript, PxsRes,
     arena::pxs_PixelArena,
-    func::{clear_function_lookup, lookup_add_function},
-    module::pxs_Module,
-    object::{ObjectFlags, clear_object_lookup, lookup_add_object, pxs_PixelObject},
-    pxs_LoadFileFn, pxs_Opaque, pxs_ReadDirFn, pxs_Runtime, set_read_dir, set_read_file,
-    var::{ObjectMethods, pxs_DeleterFn, pxs_VarList, pxs_VarT, pxs_VarType},
+    bundle::pxs_Bundle,
+    backend::{self, pxs_BackendVTable},
+    budget::{self, pxs_BudgetMode},
+    capability, catalog, channel, chunks, conformance, deepcopy, delta, last_error, msgpack, pin, strict,
+    context::{Sendable, pxs_Context},
+    convert::{self, pxs_Convert, pxs_Status},
+    coroutine::{pxs_Coroutine, pxs_CoroutineStatus},
+    error::{pxs_CallResult, pxs_Error, pxs_ErrorCode, pxs_ErrorKind},
+    iter::pxs_Iterator,
+    limit,
+    thread::pxs_Thread,
+    utf16::{self, pxs_Utf16Policy},
+    func::{call_function, clear_function_lookup, clear_function_lookup_all, current_opaque, lookup_add_function, lookup_add_function_with_opaque, lookup_add_host_function, lookup_add_owned_function, lookup_find_function, lookup_has_function, lookup_remove_function, lookup_set_signature, reset_usage, set_usage_recording, usage_report},
+    module::{ModuleCallback, ModuleVariable, pxs_Class, pxs_Module},
+    object::{
+        ObjectFlags, apply_ref_count_alloc, clear_object_lookup, get_object, lookup_add_object, pxs_Operator,
+        pxs_PixelObject, release_host_ref,
+    },
+    pxs_AudioParamFn, pxs_AudioPlayFn, pxs_AudioStopFn, pxs_ClockFrameFn, pxs_ClockTimeFn, pxs_JobSubmitFn, pxs_LoadFileFn, pxs_MessageCatalogFn, pxs_ModuleResolverFn, pxs_ObjectDestructorFn, pxs_ObjectObserverFn, pxs_Opaque, pxs_TickFn, pxs_PathCostFn, pxs_QuarantineFn, pxs_ReadDirFn, pxs_WriteFileFn, pxs_LogLevel, pxs_LogSinkFn, pxs_HttpHandlerFn,
+    pressure::{self, pxs_MemoryPressure},
+    promise,
+    share,
+    pxs_Runtime, pxs_UiChangeFn, quarantine, registry, signature::Signature, validate, set_audio_callbacks, set_thread_file_readers, sync, set_clock_callbacks, set_job_executor, set_message_catalog, set_object_destructor_hook, set_object_observer, set_on_quarantine, set_quarantine_threshold, set_path_provider, set_read_dir, set_read_file, set_source_identity, set_ui_change, set_write_file, set_time_source, set_allow_sleep, set_log_sink, set_http_handler, add_module_resolver, clear_module_resolvers, set_bundle,
+    var::{ObjectMethods, pxs_ArrayKind, pxs_DeleterFn, pxs_VarList, pxs_VarMap, pxs_VarObject, pxs_VarT, pxs_VarTypedArray, pxs_VarType},
 };
 
 pub mod shared;
+pub mod rust_api;
 
 #[cfg(feature = "include-core")]
 pub mod pxs_core;
@@ -51,11 +74,13 @@ pub mod lua;
 #[cfg(feature = "python")]
 pub mod python;
 
-/// Assert that the module is initiated.
+/// Return early when the library is not initiated. The caller gets the `Fallback` of its return type (null, false,
+/// -1 or a exception var) and `pxs_last_error` reports why.
 macro_rules! assert_initiated {
     () => {{
-        unsafe {
-            assert!(IS_INIT, "Pixel script library is not initialized.");
+        if !is_initialized() {
+            last_error::set(last_error::NOT_INITIALIZED);
+            return last_error::Fallback::fallback();
         }
     }};
 }
@@ -84,10 +109,19 @@ macro_rules! with_backend {
     };
 }
 
-/// Is initialized?
-static mut IS_INIT: bool = false;
-/// Is killed?
-static mut IS_KILLED: bool = false;
+/// Lifecycle of the library, one of the `LIFECYCLE_*` values. Changed with compare exchange so `pxs_initialize` and
+/// `pxs_finalize` can be called from any thread.
+static LIFECYCLE: AtomicU8 = AtomicU8::new(LIFECYCLE_STOPPED);
+
+const LIFECYCLE_STOPPED: u8 = 0;
+const LIFECYCLE_STARTING: u8 = 1;
+const LIFECYCLE_READY: u8 = 2;
+const LIFECYCLE_STOPPING: u8 = 3;
+
+/// Is initialized? Still true while finalizing, `on_unload` functions and backends use the api while stopping.
+fn is_initialized() -> bool {
+    matches!(LIFECYCLE.load(Ordering::Acquire), LIFECYCLE_READY | LIFECYCLE_STOPPING)
+}
 
 /// Current pixelscript version.
 #[unsafe(no_mangle)]
@@ -99,48 +133,113 @@ pub extern "C" fn pxs_version() -> u32 {
     (major << 16) | (minor << 8) | patch
 }
 
-/// Initialize the PixelScript runtime.
+/// Initialize the PixelScript runtime. Can be called again after `pxs_finalize` to restart scripting with fresh
+/// runtimes.
+///
+/// Safe to call from any thread and more than once. The first call starts the runtimes on its thread, calls racing it
+/// wait until it is done. Other threads still call `pxs_startthread` for runtimes of their own.
 #[unsafe(no_mangle)]
 pub extern "C" fn pxs_initialize() {
     pxs_debug!("pxs_initialize");
-    unsafe {
-        if IS_KILLED {
-            panic!("Once finalized, PixelScript can not be initalized again.");
+    loop {
+        match LIFECYCLE.compare_exchange(LIFECYCLE_STOPPED, LIFECYCLE_STARTING, Ordering::AcqRel, Ordering::Acquire) {
+            Ok(_) => break,
+            Err(LIFECYCLE_READY) => return,
+            // Started or finalized by another thread.
+            Err(_) => std::thread::yield_now(),
         }
-        if !IS_INIT {
-            with_feature!("lua", {
-                LuaScripting::start();
-            });
+    }
 
-            with_feature!("python", {
-                PythonScripting::start();
-            });
+    with_feature!("lua", {
+        LuaScripting::start();
+    });
 
-            with_feature!("js", {
-                JSScripting::start();
-            });
-        }
-            IS_INIT = true;
+    with_feature!("python", {
+        PythonScripting::start();
+    });
+
+    with_feature!("js", {
+        JSScripting::start();
+    });
+
+    LIFECYCLE.store(LIFECYCLE_READY, Ordering::Release);
+}
+
+/// Call the global `on_unload` function of a runtime if the scripts defined one.
+#[allow(unused)]
+fn call_unload_hook<Backend: ObjectMethods>() {
+    let func = match Backend::get_from_name("on_unload") {
+        Ok(func) => func,
+        Err(_) => return,
+    };
+    if !func.is_function() {
+        return;
+    }
+    if let Err(e) = Backend::var_call(&func, &mut pxs_VarList::new()) {
+        pxs_debug!("on_unload failed: {e}");
     }
 }
 
 /// Finalize the PixelScript runtime.
+///
+/// Teardown happens in this order:
+/// 1. Scripts global `on_unload` functions are called.
+/// 2. Vars pinned by core modules (i.e. `pxs_ui` bindings, `pxs_save` migrations) are dropped.
+/// 3. Host objects are dropped, calling their `FreeMethod`.
+/// 4. Functions are dropped.
+/// 5. Runtimes (and backends from `pxs_register_backend`) are stopped.
+///
+/// Afterwards `pxs_initialize` starts fresh runtimes. Host settings (file readers, callbacks, limits) are kept.
+///
+/// Only one thread finalizes, `pxs_initialize` calls on other threads wait until it is done.
 #[unsafe(no_mangle)]
 pub extern "C" fn pxs_finalize() {
     pxs_debug!("pxs_finalize");
     assert_initiated!();
-
-    unsafe {
-        if IS_KILLED {
-            panic!("Can not finalize the runtime twice.");
-        }
-        IS_KILLED = true;
+    if LIFECYCLE.compare_exchange(LIFECYCLE_READY, LIFECYCLE_STOPPING, Ordering::AcqRel, Ordering::Acquire).is_err() {
+        // Finalized by another thread.
+        last_error::set(last_error::NOT_INITIALIZED);
+        return;
     }
 
-    // Drop function lookup
-    clear_function_lookup();
+    // Script cleanup while everything is still alive
+    with_feature!("lua", {
+        call_unload_hook::<LuaScripting>();
+    });
+    with_feature!("python", {
+        call_unload_hook::<PythonScripting>();
+    });
+    with_feature!("js", {
+        call_unload_hook::<JSScripting>();
+    });
+
+    // Drop pinned vars
+    with_feature!("pxs_ui", {
+        pxs_core::pxs_ui::clear();
+    });
+    with_feature!("pxs_save", {
+        pxs_core::pxs_save::clear();
+    });
+    with_feature!("pxs_jobs", {
+        pxs_core::pxs_jobs::clear();
+    });
+    with_feature!("pxs_events", {
+        pxs_core::pxs_events::clear();
+    });
+    registry::clear();
+    sync::clear();
+    pin::clear();
+    channel::clear();
+    promise::clear();
+
     // Drop object lookup
     clear_object_lookup();
+    // Drop function lookup
+    clear_function_lookup_all();
+    // Drop mod quarantine
+    quarantine::clear();
+    // Drop capability tokens
+    capability::clear();
 
     with_feature!("lua", {
         LuaScripting::stop();
@@ -153,6 +252,12 @@ pub extern "C" fn pxs_finalize() {
     with_feature!("js", {
         JSScripting::stop();
     });
+
+    for backend in backend::clear() {
+        backend.stop();
+    }
+
+    LIFECYCLE.store(LIFECYCLE_STOPPED, Ordering::Release);
 }
 
 #[unsafe(no_mangle)]
@@ -168,7 +273,7 @@ pub extern "C" fn pxs_exec(
     assert_initiated!();
 
     if code.is_null() || file_name.is_null() {
-        pxs_Var::new_exception("code or file_name are null").into_raw();
+        return pxs_Var::new_exception("code or file_name are null").into_raw();
     }
 
     let rcode = borrow_string!(code);
@@ -179,7 +284,10 @@ pub extern "C" fn pxs_exec(
     }
 
     with_backend!(runtime, Backend => {
-        let res = Backend::execute(rcode, rfile_name);
+        let res = match run_limited::<Backend>(&runtime, || Backend::execute(rcode, rfile_name)) {
+            Ok(res) => res,
+            Err(outcome) => return pxs_Var::new_exception(stopped_message(outcome)).into_raw(),
+        };
         if res.is_err() {
             pxs_Var::new_exception(res.unwrap_err().to_string()).into_raw()
         } else {
@@ -188,6 +296,192 @@ pub extern "C" fn pxs_exec(
     })
 }
 
+/// Run script code under the execution limit and tick callback of `runtime`. Returns the outcome if the script was
+/// stopped by them.
+fn run_limited<Backend: PixelScript>(
+    runtime: &pxs_Runtime,
+    run: impl FnOnce() -> shared::PxsResult,
+) -> Result<shared::PxsResult, limit::Outcome> {
+    if !limit::begin(runtime) {
+        return Ok(run());
+    }
+
+    Backend::set_exec_hooks(true);
+    let res = run();
+    Backend::set_exec_hooks(false);
+
+    // A script finishing right at the limit is not a timeout.
+    let failed = match &res {
+        Ok(var) => var.is_exception(),
+        Err(_) => true,
+    };
+    match limit::end() {
+        limit::Outcome::Finished => Ok(res),
+        _ if !failed => Ok(res),
+        outcome => Err(outcome),
+    }
+}
+
+/// The error of a script stopped by `run_limited`.
+fn stopped_message(outcome: limit::Outcome) -> String {
+    match outcome {
+        limit::Outcome::Suspended => limit::suspended_message(),
+        _ => limit::timeout_message(),
+    }
+}
+
+/// Execute code in a runtime and return its result: the value of a top level `return` in Lua, of the last line if it
+/// is a expression in Python and the completion value in JavaScript (run as a global script, not a module). Returns a
+/// `pxs_Null` if there is none.
+///
+/// If the script fails null is returned and the exception is written to `out_err` (if not null).
+///
+/// out_err:OWNED
+/// return:OWNED
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_exec_with_result(
+    runtime: pxs_Runtime,
+    code: *const c_char,
+    file_name: *const c_char,
+    out_err: *mut pxs_VarT,
+) -> pxs_VarT {
+    pxs_debug!("pxs_exec_with_result");
+    assert_initiated!();
+
+    if !out_err.is_null() {
+        unsafe {
+            *out_err = ptr::null_mut();
+        }
+    }
+    let fail = |err: pxs_Var| {
+        if !out_err.is_null() {
+            unsafe {
+                *out_err = err.into_raw();
+            }
+        }
+        ptr::null_mut()
+    };
+    if code.is_null() || file_name.is_null() {
+        return fail(pxs_Var::new_exception("code or file_name are null"));
+    }
+
+    let rcode = borrow_string!(code);
+    let rfile_name = borrow_string!(file_name);
+
+    let res = with_backend!(runtime, Backend => {
+        run_limited::<Backend>(&runtime, || Backend::execute_with_result(rcode, rfile_name))
+    });
+    match res {
+        Ok(Ok(var)) if var.is_exception() => fail(var),
+        Ok(Ok(var)) => var.into_raw(),
+        Ok(Err(e)) => fail(pxs_Var::new_exception(e)),
+        Err(outcome) => fail(pxs_Var::new_exception(stopped_message(outcome))),
+    }
+}
+
+/// Limit how long a single `pxs_exec` (or `pxs_exec_ex`) call can run in a runtime, to protect against scripts like
+/// `while true do end`. `0` is unlimited, both `0` removes the limit. Limits are per thread.
+///
+/// Instructions are counted approximately: Lua counts VM instructions in steps of 1000, pocketpy counts executed
+/// lines and QuickJS counts in steps of its interrupt counter. `max_millis` is wall time checked at the same points, so
+/// time spent waiting in a host call counts but the script is only stopped once it runs again. A stopped script
+/// returns a exception (or a `pxs_TimeoutError` from `pxs_exec_ex`) with the `exec_limit` message.
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_set_exec_limit(runtime: pxs_Runtime, max_instructions: i64, max_millis: i64) {
+    pxs_debug!("pxs_set_exec_limit");
+    limit::set(&runtime, max_instructions.max(0) as u64, max_millis.max(0) as u64);
+}
+
+/// Call `func` about every `every_n_instructions` instructions while a `pxs_exec` (or `pxs_coroutine_resume`) runs,
+/// for frame budgeted execution of long scripts. Instructions are counted like `pxs_set_exec_limit`. Null removes the
+/// callback. Per thread.
+///
+/// Returning true from `func` suspends the script. A Lua coroutine yields, the next `pxs_coroutine_resume` continues
+/// where it was. Anything else is stopped with a exception (or a `pxs_SuspendedError` from `pxs_exec_ex`) with the
+/// `exec_suspended` message.
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_set_tick_callback(func: Option<pxs_TickFn>, every_n_instructions: i64) {
+    pxs_debug!("pxs_set_tick_callback");
+    limit::set_tick(func, every_n_instructions.max(1) as u64);
+}
+
+/// Execute code in a runtime. Same as `pxs_exec` but returns a structured `pxs_Error` with the message, file, line,
+/// column and traceback. Returns null on success.
+///
+/// Free the error with `pxs_error_free`.
+///
+/// return:OWNED
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_exec_ex(
+    runtime: pxs_Runtime,
+    code: *const c_char,
+    file_name: *const c_char,
+) -> *mut pxs_Error {
+    pxs_debug!("pxs_exec_ex");
+    assert_initiated!();
+
+    if code.is_null() || file_name.is_null() {
+        return pxs_Error::new(pxs_ErrorCode::pxs_InvalidArgument, "code or file_name are null").into_raw();
+    }
+
+    let rcode = borrow_string!(code);
+    let rfile_name = borrow_string!(file_name);
+
+    if rcode.is_empty() || rfile_name.is_empty() {
+        return pxs_Error::new(pxs_ErrorCode::pxs_InvalidArgument, "code or file_name are empty strings").into_raw();
+    }
+
+    let res = with_backend!(runtime, Backend => {
+        run_limited::<Backend>(&runtime, || Backend::execute(rcode, rfile_name))
+    });
+    let res = match res {
+        Ok(res) => res,
+        Err(limit::Outcome::Suspended) => {
+            return pxs_Error::new(pxs_ErrorCode::pxs_SuspendedError, &limit::suspended_message()).into_raw();
+        }
+        Err(_) => return pxs_Error::new(pxs_ErrorCode::pxs_TimeoutError, &limit::timeout_message()).into_raw(),
+    };
+    // Backends return script errors as a exception var.
+    let err = match res {
+        Ok(var) if var.is_exception() => var.get_string().unwrap_or_default(),
+        Ok(_) => return ptr::null_mut(),
+        Err(e) => e,
+    };
+    pxs_Error::from_runtime(&runtime, rfile_name, &err).into_raw()
+}
+
+/// Free a `pxs_Error` returned by `pxs_exec_ex`.
+///
+/// error:TRANSFER
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_error_free(error: *mut pxs_Error) {
+    pxs_debug!("pxs_error_free");
+    if error.is_null() {
+        return;
+    }
+    let _ = pxs_Error::from_raw(error);
+}
+
+/// Take the last error of the calling thread, i.e. a call made before `pxs_initialize`. Returns null if there was
+/// none. Free it with `pxs_freestr`.
+///
+/// return:OWNED
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_last_error() -> *mut c_char {
+    pxs_debug!("pxs_last_error");
+    match last_error::take() {
+        Some(msg) => create_raw_string!(msg),
+        None => ptr::null_mut(),
+    }
+}
+
+/// Is the library initialized? False before `pxs_initialize` and after `pxs_finalize`.
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_is_initialized() -> bool {
+    pxs_debug!("pxs_is_initialized");
+    is_initialized()
+}
+
 /// Free the string created by the pixelscript library
 ///
 /// string:TRANSFER
@@ -227,18 +521,67 @@ pub extern "C" fn pxs_newmod(name: *const c_char) -> *mut pxs_Module {
 pub extern "C" fn pxs_addfunc(module_ptr: *mut pxs_Module, name: *const c_char, func: pxs_Func) {
     pxs_debug!("pxs_addfunc");
     assert_initiated!();
-    if module_ptr.is_null() {
+    if module_ptr.is_null() || name.is_null() {
+        strict::misuse("pxs_addfunc", "module_ptr or name is null");
         return;
     }
 
-    if name.is_null() {
+    let module = unsafe { pxs_Module::from_borrow(module_ptr) };
+    if let Err(e) = add_callback_to_module(module, borrow_string!(name), func, ptr::null_mut(), None) {
+        strict::misuse("pxs_addfunc", &e);
+    }
+}
+
+/// Add a callback with declared arguments to a module.
+///
+/// `signature` is a map with `params`, a list of maps with a `name`, a optional `type` (a type name or list of type
+/// names like `pxs_validate`) and a optional `default`, and optional `varargs`, `true` or the type name(s) of extra
+/// arguments. Lua and Python check calls before `func` is called: missing arguments get their default, ints are
+/// converted for `float` params and whole floats for `int` params, anything else raises a script error (a
+/// `TypeError` in Python). `opaque` is returned by `pxs_funcopaque` during calls.
+///
+/// module_ptr:BORROW
+/// signature:TRANSFER
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_addfunc_ex(
+    module_ptr: *mut pxs_Module,
+    name: *const c_char,
+    func: pxs_Func,
+    opaque: pxs_Opaque,
+    signature: pxs_VarT,
+) {
+    pxs_debug!("pxs_addfunc_ex");
+    assert_initiated!();
+    let signature = own_var!(signature);
+    if module_ptr.is_null() || name.is_null() {
+        strict::misuse("pxs_addfunc_ex", "module_ptr or name is null");
         return;
     }
+    let signature = match Signature::parse(&signature) {
+        Ok(signature) => signature,
+        Err(msg) => {
+            strict::misuse("pxs_addfunc_ex", &msg);
+            return;
+        }
+    };
 
-    // Get actual data
     let module = unsafe { pxs_Module::from_borrow(module_ptr) };
-    let name_str = borrow_string!(name);
+    match add_callback_to_module(module, borrow_string!(name), func, opaque, None) {
+        Ok(idx) => lookup_set_signature(idx, signature),
+        Err(e) => strict::misuse("pxs_addfunc_ex", &e),
+    }
+}
 
+/// Add a callback to a module. If `free_opaque` is set the function owns the opaque and frees it when removed.
+///
+/// Returns the function idx, or a error if the function lookup is full.
+fn add_callback_to_module(
+    module: &mut pxs_Module,
+    name_str: &str,
+    func: pxs_Func,
+    opaque: pxs_Opaque,
+    free_opaque: Option<pxs_DeleterFn>,
+) -> PxsRes<i32> {
     // Mangle the name
     let full_name = format!("_{}{}", module.name, name_str);
 
@@ -250,15 +593,18 @@ pub extern "C" fn pxs_addfunc(module_ptr: *mut pxs_Module, name: *const c_char,
         .all(|v| v == false)
     {
         panic!("Function with name: {full_name} is already defined.");
-        #[allow(unreachable_code)]
-        return;
     }
 
     // Save the callback
-    let idx = lookup_add_function(&full_name, func);
+    let usage_name = format!("{}.{}", module.name, name_str);
+    let idx = match free_opaque {
+        Some(free_opaque) => lookup_add_owned_function(&full_name, &usage_name, func, opaque, free_opaque),
+        None => lookup_add_function_with_opaque(&full_name, &usage_name, func, opaque),
+    }?;
 
     // Now add callback
     module.add_callback(name_str, &full_name, idx);
+    Ok(idx)
 }
 
 /// Add the same function under different names.
@@ -271,11 +617,13 @@ pub extern "C" fn pxs_addfuncs(module_ptr: *mut pxs_Module, func_list: pxs_VarT,
     assert_initiated!();
 
     if module_ptr.is_null() || func_list.is_null() {
+        strict::misuse("pxs_addfuncs", "module_ptr or func_list is null");
         return;
     }
 
     let func_list = own_var!(func_list);
     if !func_list.is_list() {
+        strict::misuse("pxs_addfuncs", "func_list is not a list");
         return;
     }
     let list = func_list.get_list().unwrap();
@@ -307,6 +655,7 @@ pub extern "C" fn pxs_addvar(
     pxs_debug!("pxs_addvar");
     assert_initiated!();
     if module_ptr.is_null() || name.is_null() || variable.is_null() {
+        strict::misuse("pxs_addvar", "module_ptr, name or variable is null");
         return;
     }
 
@@ -336,6 +685,7 @@ pub extern "C" fn pxs_add_submod(parent_ptr: *mut pxs_Module, child_ptr: *mut px
 
     assert_initiated!();
     if parent_ptr.is_null() || child_ptr.is_null() {
+        strict::misuse("pxs_add_submod", "parent_ptr or child_ptr is null");
         return;
     }
 
@@ -360,23 +710,155 @@ pub extern "C" fn pxs_addmod(module_ptr: *mut pxs_Module) {
 
     assert_initiated!();
     if module_ptr.is_null() {
+        strict::misuse("pxs_addmod", "module_ptr is null");
         return;
     }
 
-    let module = Arc::new(pxs_Module::from_raw(module_ptr));
+    add_module(Arc::new(pxs_Module::from_raw(module_ptr)));
+}
+
+/// Share a script module with the other runtimes, i.e. so Lua can `require` a module written in Python.
+///
+/// `name` is imported in `runtime` with its own loader (`require`, `import`) and a module of the same name is added to
+/// every other runtime. Its functions call the functions of the source module, other exports are copied once.
+/// Arguments and results are copied between the runtimes: tables, dicts and objects become maps and functions can not
+/// be passed. Like `pxs_addmod` this needs to be called in each thread that uses the module.
+///
+/// return:OWNED (null on success, otherwise a exception)
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_share_module(runtime: pxs_Runtime, name: *const c_char) -> pxs_VarT {
+    pxs_debug!("pxs_share_module");
+    assert_initiated!();
+
+    if name.is_null() {
+        return pxs_Var::null_param_ep("name").into_raw();
+    }
+    let name = borrow_string!(name);
+    if let Err(e) = share::check_name(name) {
+        return pxs_Var::new_exception(e).into_raw();
+    }
+    let Some(rt) = enabled_runtime(runtime.into_i64() as i32) else {
+        return pxs_Var::unkown_runtime_ep(runtime.into_i64()).into_raw();
+    };
+
+    let exports: shared::PxsResult = with_backend!(rt.clone(), Backend => { Backend::import_module(name) });
+    let module = exports.and_then(|exports| {
+        if exports.is_exception() {
+            return Err(exports.get_string().unwrap_or_default());
+        }
+        share::new_module(&rt, name, &exports)
+    });
+    match module {
+        Ok(module) => {
+            add_module_except(&Arc::new(module), Some(&rt));
+            ptr::null_mut()
+        }
+        Err(e) => pxs_Var::new_exception(e).into_raw(),
+    }
+}
+
+/// Add a module to every runtime.
+fn add_module(module: Arc<pxs_Module>) {
+    add_module_except(&module, None);
 
+    // Keep it for `pxs_registry_export`
+    registry::register_module(module);
+}
+
+/// Add a module to every runtime but `except`.
+fn add_module_except(module: &Arc<pxs_Module>, except: Option<&pxs_Runtime>) {
+    let skip = |rt: pxs_Runtime| except.is_some_and(|except| except.into_i64() == rt.into_i64());
     // LUA
     with_feature!("lua", {
-        LuaScripting::add_module(Arc::clone(&module));
+        if !skip(pxs_Runtime::pxs_Lua) {
+            LuaScripting::add_module(Arc::clone(module));
+        }
+    });
+    with_feature!("python", {
+        if !skip(pxs_Runtime::pxs_Python) {
+            PythonScripting::add_module(Arc::clone(module));
+        }
+    });
+    with_feature!("js", {
+        if !skip(pxs_Runtime::pxs_JavaScript) {
+            JSScripting::add_module(Arc::clone(module));
+        }
+    });
+    for backend in backend::all() {
+        backend.add_module(module);
+    }
+}
+
+/// Remove a registered module from every runtime and release its functions. Returns false if there is none.
+fn remove_module(name: &str) -> bool {
+    let Some(module) = registry::unregister_module(name) else {
+        return false;
+    };
+
+    // Backends drop their cached bridges (metatables, classes) of these types.
+    let object_types = registry::module_object_types(&module);
+
+    with_feature!("lua", {
+        LuaScripting::remove_module(&module, &object_types);
     });
     with_feature!("python", {
-        PythonScripting::add_module(Arc::clone(&module));
+        PythonScripting::remove_module(&module, &object_types);
     });
     with_feature!("js", {
-        JSScripting::add_module(Arc::clone(&module));
+        JSScripting::remove_module(&module, &object_types);
     });
+    for backend in backend::all() {
+        backend.remove_module(&module.name);
+    }
+
+    for idx in module.callback_idxs() {
+        lookup_remove_function(idx);
+    }
+    registry::unregister_object_types(&object_types);
+
+    true
+}
+
+/// Replace a module added with `pxs_addmod` by a new version with the same name, so host APIs can be iterated on
+/// without restarting the process. If no module with this name was added it is just added.
+///
+/// The old functions are released, Lua's `package.loaded` entry is invalidated so the next `require` gets the new
+/// module, and the pocketpy module is refilled in place. Returns true if a module was replaced.
+///
+/// module_ptr:TRANSFER
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_reloadmod(module_ptr: *mut pxs_Module) -> bool {
+    pxs_debug!("pxs_reloadmod");
+
+    assert_initiated!();
+    if module_ptr.is_null() {
+        return false;
+    }
+
+    let module = Arc::new(pxs_Module::from_raw(module_ptr));
+    let replaced = remove_module(&module.name);
+    add_module(module);
+
+    replaced
+}
+
+/// Remove a module added with `pxs_addmod`, i.e. when unloading a plugin. Child modules are removed too.
+///
+/// The module is removed from Lua `package.preload` and `package.loaded`, emptied in pocketpy and no longer resolved
+/// by the JS module loader. Its functions are released from the function lookup, so script values still referencing
+/// them return null when called. Returns false if no module with this name was added.
+///
+/// name:BORROW
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_removemod(name: *const c_char) -> bool {
+    pxs_debug!("pxs_removemod");
+
+    assert_initiated!();
+    if name.is_null() {
+        return false;
+    }
 
-    // Module gets dropped here, and that is good!
+    remove_module(borrow_string!(name))
 }
 
 /// Optionally free a module if you changed your mind.
@@ -443,9 +925,16 @@ pub extern "C" fn pxs_newobject(
 fn add_callback_to_object(object: &mut pxs_PixelObject, name: &str, callback: pxs_Func, flags: u8) {
     // Add to function lookup
     let full_name = format!("_{}{}", object.type_name, name);
-    let idx = lookup_add_function(full_name.as_str(), callback);
+    let idx = match lookup_add_function(full_name.as_str(), &format!("{}.{}", object.type_name, name), callback) {
+        Ok(idx) => idx,
+        Err(e) => {
+            strict::misuse("pxs_object_addfunc", &e);
+            return;
+        }
+    };
 
     object.add_callback(name, full_name.as_str(), idx, flags);
+    registry::register_object_callback(&object.type_name, object.callbacks.last().unwrap());
 }
 
 /// Add a callback to a object.
@@ -461,6 +950,7 @@ pub extern "C" fn pxs_object_addfunc(
     assert_initiated!();
 
     if object_ptr.is_null() || name.is_null() {
+        strict::misuse("pxs_object_addfunc", "object_ptr or name is null");
         return;
     }
 
@@ -489,6 +979,7 @@ pub extern "C" fn pxs_object_add_reffunc(
     assert_initiated!();
 
     if object_ptr.is_null() || name.is_null() {
+        strict::misuse("pxs_object_add_reffunc", "object_ptr or name is null");
         return;
     }
 
@@ -518,6 +1009,7 @@ pub extern "C" fn pxs_object_addprop(
     assert_initiated!();
 
     if ptr.is_null() || name.is_null() {
+        strict::misuse("pxs_object_addprop", "ptr or name is null");
         return;
     }
 
@@ -529,6 +1021,43 @@ pub extern "C" fn pxs_object_addprop(
     add_callback_to_object(object_borrow, name_borrow, callback, flags);
 }
 
+/// Overload a operator of a object, i.e. `+` for a `Vec3`. Adding the same operator again replaces it.
+///
+/// `callback` receives the runtime, the object (`_pxs_ptr`) and the other operand if there is one, same as
+/// `pxs_object_addfunc`. The object is always the left operand, `v * 2` works but `2 * v` does not.
+/// Use `pxs_funcopaque` inside the callback to get `opaque`.
+///
+/// object_ptr:BORROW
+/// opaque:BORROW
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_object_addoperator(
+    object_ptr: *mut pxs_PixelObject,
+    operator: pxs_Operator,
+    callback: pxs_Func,
+    opaque: pxs_Opaque,
+) {
+    pxs_debug!("pxs_object_addoperator");
+    assert_initiated!();
+
+    if object_ptr.is_null() {
+        strict::misuse("pxs_object_addoperator", "object_ptr is null");
+        return;
+    }
+
+    let object = unsafe { pxs_PixelObject::from_borrow(object_ptr) };
+    let full_name = format!("_{}__{}", object.type_name, operator.name());
+    let usage_name = format!("{}.{}", object.type_name, operator.name());
+    let idx = match lookup_add_function_with_opaque(&full_name, &usage_name, callback, opaque) {
+        Ok(idx) => idx,
+        Err(e) => {
+            strict::misuse("pxs_object_addoperator", &e);
+            return;
+        }
+    };
+    object.add_operator(operator, &full_name, idx);
+    registry::register_object_callback(&object.type_name, object.callbacks.last().unwrap());
+}
+
 /// Add a object constructor to a module. This is the same as calling `pxs_addfunc`. Only named differently to distinguish
 /// when a function should be treated as a Object or a Function in your code.
 /// It is not required to call this function in order to expose a `pxs_HostObject` to a module. Any functoin that returns a `pxs_HostObject`
@@ -544,14 +1073,146 @@ pub extern "C" fn pxs_addobject(
     pxs_debug!("pxs_addobject");
     // Save module to object
     pxs_addfunc(module_ptr, name, object_constructor);
+    if module_ptr.is_null() {
+        return;
+    }
+    // Mark it as a object for `pxs_registry_export`
+    let module = unsafe { pxs_Module::from_borrow(module_ptr) };
+    if let Some(cbk) = module.callbacks.last_mut() {
+        cbk.is_object = true;
+    }
 }
 
-/// Make a new Var string.
+/// Add a class to a module, i.e. `Vec3` with `Vec3.ZERO`, `Vec3.dot(a, b)` and `Vec3(1, 2, 3)`.
 ///
-/// return:OWNED
+/// Returns a handle for adding static methods (`pxs_class_addfunc`), constants (`pxs_class_addvar`) and the
+/// constructor (`pxs_class_setconstructor`). The handle belongs to the module and is valid until the module is added.
+///
+/// module_ptr:BORROW
+/// return:BORROW
 #[unsafe(no_mangle)]
-pub extern "C" fn pxs_newstring(str: *const c_char) -> pxs_VarT {
-    pxs_debug!("pxs_newstring");
+pub extern "C" fn pxs_addclass(module_ptr: *mut pxs_Module, type_name: *const c_char) -> *mut pxs_Class {
+    pxs_debug!("pxs_addclass");
+    assert_initiated!();
+    if module_ptr.is_null() || type_name.is_null() {
+        strict::misuse("pxs_addclass", "module_ptr or type_name is null");
+        return ptr::null_mut();
+    }
+
+    let module = unsafe { pxs_Module::from_borrow(module_ptr) };
+    let type_name = borrow_string!(type_name);
+    if module.classes.iter().any(|class| class.name == type_name) {
+        panic!("Class with name: {type_name} is already defined.");
+    }
+
+    module.add_class(type_name) as *mut pxs_Class
+}
+
+/// Add a static method to a class. It is called without a object, `callback` receives the runtime followed by the
+/// script args like a module function.
+///
+/// class_ptr:BORROW
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_class_addfunc(class_ptr: *mut pxs_Class, name: *const c_char, callback: pxs_Func) {
+    pxs_debug!("pxs_class_addfunc");
+    assert_initiated!();
+    if class_ptr.is_null() || name.is_null() {
+        strict::misuse("pxs_class_addfunc", "class_ptr or name is null");
+        return;
+    }
+
+    let class = unsafe { pxs_Class::from_borrow(class_ptr) };
+    let name = borrow_string!(name);
+    let full_name = format!("_{}.{}", class.name, name);
+    if class.callbacks.iter().any(|cbk| cbk.full_name == full_name) {
+        panic!("Function with name: {full_name} is already defined.");
+    }
+
+    let idx = match lookup_add_function(&full_name, &format!("{}.{}", class.name, name), callback) {
+        Ok(idx) => idx,
+        Err(e) => {
+            strict::misuse("pxs_class_addfunc", &e);
+            return;
+        }
+    };
+    class.callbacks.push(ModuleCallback { name: name.to_string(), full_name, idx, is_object: false });
+}
+
+/// Add a constant to a class.
+///
+/// class_ptr:BORROW
+/// variable:TRANSFER
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_class_addvar(class_ptr: *mut pxs_Class, name: *const c_char, variable: pxs_VarT) {
+    pxs_debug!("pxs_class_addvar");
+    assert_initiated!();
+    if class_ptr.is_null() || name.is_null() || variable.is_null() {
+        strict::misuse("pxs_class_addvar", "class_ptr, name or variable is null");
+        return;
+    }
+
+    let class = unsafe { pxs_Class::from_borrow(class_ptr) };
+    let name = borrow_string!(name);
+    if class.variables.iter().any(|v| v.name == name) {
+        panic!("Variable name: {name} is already defined.");
+    }
+
+    class.variables.push(ModuleVariable::new(name.to_string(), own_var!(variable)));
+}
+
+/// Set the constructor of a class, called when scripts call the class. `callback` receives the runtime followed by
+/// the script args (not the class) and should return a `pxs_HostObject`. Setting it again replaces it.
+///
+/// class_ptr:BORROW
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_class_setconstructor(class_ptr: *mut pxs_Class, callback: pxs_Func) {
+    pxs_debug!("pxs_class_setconstructor");
+    assert_initiated!();
+    if class_ptr.is_null() {
+        strict::misuse("pxs_class_setconstructor", "class_ptr is null");
+        return;
+    }
+
+    let class = unsafe { pxs_Class::from_borrow(class_ptr) };
+    let full_name = format!("_{}.new", class.name);
+    let idx = match lookup_add_function(&full_name, &format!("{}.new", class.name), callback) {
+        Ok(idx) => idx,
+        Err(e) => {
+            strict::misuse("pxs_class_setconstructor", &e);
+            return;
+        }
+    };
+    if let Some(old) = class.constructor.replace(ModuleCallback { name: class.name.clone(), full_name, idx, is_object: true }) {
+        lookup_remove_function(old.idx);
+    }
+}
+
+/// Make objects of `child_type` inherit the methods, properties and operators of `parent_type`, i.e. `Enemy` from
+/// `Entity`. Objects of the child type only need their own callbacks.
+///
+/// Lua chains the metatables, Python uses the parent bridge class as base class and JavaScript chains the prototypes.
+/// The parent bridge is made from the callbacks of the last `parent_type` object, so create one before a child
+/// object reaches a script. Call this before objects of the child type are passed to a script.
+///
+/// Returns false if a name is null or `parent_type` already inherits from `child_type`.
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_class_setparent(child_type: *const c_char, parent_type: *const c_char) -> bool {
+    pxs_debug!("pxs_class_setparent");
+    assert_initiated!();
+    if child_type.is_null() || parent_type.is_null() {
+        strict::misuse("pxs_class_setparent", "child_type or parent_type is null");
+        return false;
+    }
+
+    registry::set_parent(borrow_string!(child_type), borrow_string!(parent_type))
+}
+
+/// Make a new Var string.
+///
+/// return:OWNED
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_newstring(str: *const c_char) -> pxs_VarT {
+    pxs_debug!("pxs_newstring");
     let val = borrow_string!(str);
     // Clone string
     pxs_Var::new_string(val.to_string().clone()).into_raw()
@@ -568,7 +1229,7 @@ pub extern "C" fn pxs_newnull() -> pxs_VarT {
 
 /// Make a new HostObject var.
 ///
-/// Transfers ownership
+/// Transfers ownership. Returns a `pxs_Exception` if the object lookup is full.
 ///
 /// pixel_object:TRANSFER
 /// return:OWNED
@@ -587,9 +1248,46 @@ pub extern "C" fn pxs_newhost(pixel_object: *mut pxs_PixelObject) -> pxs_VarT {
     let pixel_arc = Arc::new(pixel_owned);
 
     // Create it in the system
-    let idx = lookup_add_object(Arc::clone(&pixel_arc));
+    match lookup_add_object(Arc::clone(&pixel_arc)) {
+        Ok(idx) => pxs_Var::new_host_object(idx).into_raw(),
+        Err(e) => pxs_Var::new_exception(e).into_raw(),
+    }
+}
 
-    pxs_Var::new_host_object(idx).into_raw()
+/// Keep the host object `idx` alive after scripts stop referencing it. Every retain needs a `pxs_object_release`.
+///
+/// Returns false if there is no such object.
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_object_retain(idx: i32) -> bool {
+    pxs_debug!("pxs_object_retain");
+    assert_initiated!();
+
+    if get_object(idx).is_none() {
+        strict::misuse("pxs_object_retain", &format!("no host object {idx}"));
+        return false;
+    }
+    apply_ref_count_alloc(idx);
+    true
+}
+
+/// Drop a host reference of the host object `idx`, from `pxs_object_retain` or the one it was created with if it never
+/// reached a script. The object is freed once neither the host nor a script references it.
+///
+/// Host objects are owned by their language object (the Lua table or Python instance) once they are passed to a script,
+/// and freed when the script GC collects it. JavaScript keeps its objects until they are released here or with
+/// `pxs_mem.memdel`.
+///
+/// Returns false if there is no such object.
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_object_release(idx: i32) -> bool {
+    pxs_debug!("pxs_object_release");
+    assert_initiated!();
+
+    if !release_host_ref(idx) {
+        strict::misuse("pxs_object_release", &format!("no host object {idx}"));
+        return false;
+    }
+    true
 }
 
 /// Create a new variable int. (i64)
@@ -626,6 +1324,33 @@ pub extern "C" fn pxs_newfloat(val: f64) -> pxs_VarT {
     pxs_Var::new_f64(val).into_raw()
 }
 
+/// Create a new variable int32. Scripts get a regular int.
+///
+/// return:OWNED
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_newint32(val: i32) -> pxs_VarT {
+    pxs_debug!("pxs_newint32");
+    pxs_Var::new_i32(val).into_raw()
+}
+
+/// Create a new variable uint32. Scripts get a regular int.
+///
+/// return:OWNED
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_newuint32(val: u32) -> pxs_VarT {
+    pxs_debug!("pxs_newuint32");
+    pxs_Var::new_u32(val).into_raw()
+}
+
+/// Create a new variable float32. Scripts get a regular float.
+///
+/// return:OWNED
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_newfloat32(val: f32) -> pxs_VarT {
+    pxs_debug!("pxs_newfloat32");
+    pxs_Var::new_f32(val).into_raw()
+}
+
 /// Call a function on a object, and use a Enum for runtime rather than a var.
 ///
 /// var is self.
@@ -653,7 +1378,11 @@ pub extern "C" fn pxs_object_callrt(
 ///
 /// All memory is borrowed except for args. But the var returned need to be freed on host side if not returned by a function.
 ///
-/// You can get the runtime from the first Var in any callback.
+/// You can get the runtime from the first Var in any callback. If runtime is null the runtime that produced `var` is
+/// used, passing a different runtime is a `runtime_mismatch` exception.
+///
+/// `var` can also be a `pxs_HostObject`, then its language object is called. Methods overridden by a script (see
+/// `pxs_object_is_overridden`) are called instead of the host methods.
 ///
 /// Example
 /// ```C
@@ -676,12 +1405,11 @@ pub extern "C" fn pxs_objectcall(
     pxs_debug!("pxs_objectcall");
     assert_initiated!();
 
-    if var.is_null() || method.is_null() || args.is_null() || runtime.is_null() {
+    if var.is_null() || method.is_null() || args.is_null() {
         return pxs_Var::null_params_ep().into_raw();
     }
 
-    // Borrow runtime, var, and method, and argv
-    let runtime_borrow = unsafe { pxs_Var::from_borrow(runtime) };
+    // Borrow var, and method, and argv
     let var_borrow = unsafe { pxs_Var::from_borrow(var) };
     let method_borrow = borrow_string!(method);
     let args = own_var!(args);
@@ -690,26 +1418,26 @@ pub extern "C" fn pxs_objectcall(
     }
     let list = args.get_list().unwrap();
 
-    // Check that runtime is acually a int
-    let runtime_id = runtime_borrow.get_i64();
-    if runtime_id.is_err() {
-        return pxs_Var::incorrect_types_ep(
-            vec![pxs_VarType::pxs_Int64, pxs_VarType::pxs_UInt64],
-            runtime_borrow.tag,
-        )
-        .into_raw();
+    if let Some(e) = origin_mismatch(runtime, var_borrow) {
+        return e.into_raw();
     }
-    let runtime_val = runtime_id.unwrap();
-
-    let runtime = pxs_Runtime::from_i64(runtime_val);
-    if runtime.is_none() {
-        return pxs_Var::unkown_runtime_ep(runtime_val).into_raw();
+    if let Some(backend) = custom_backend(runtime, var_borrow) {
+        return budget::checked(backend.object_call(var_borrow, method_borrow, &args)).into_raw();
     }
-    let runtime = runtime.unwrap();
 
-    // Ensure type
-    if !var_borrow.is_object() {
-        return pxs_Var::incorrect_type_ep(pxs_VarType::pxs_Object, var_borrow.tag).into_raw();
+    let runtime = match object_runtime(runtime, var_borrow) {
+        Ok(runtime) => runtime,
+        Err(e) => return e.into_raw(),
+    };
+
+    // Ensure type. Host objects are called on their language object.
+    if !var_borrow.is_object() && !var_borrow.is_host_object() {
+        return pxs_Var::incorrect_types_ep(vec![pxs_VarType::pxs_Object, pxs_VarType::pxs_HostObject], var_borrow.tag)
+            .into_raw();
+    }
+    #[cfg(feature = "pxs-debug")]
+    if var_borrow.is_foreign() {
+        return pxs_Var::foreign_var_ep().into_raw();
     }
 
     with_backend!(runtime, Backend => {
@@ -717,12 +1445,62 @@ pub extern "C" fn pxs_objectcall(
         if res.is_err() {
             pxs_Var::new_exception(res.unwrap_err().to_string())
         } else {
-            res.unwrap()
+            budget::checked(res.unwrap())
         }
     })
     .into_raw()
 }
 
+/// Check if the script overrides `method` of a object: a script subclass defines it (`class Boss(Enemy)` in Python)
+/// or a function was assigned to the object (`function boss:attack() end` in Lua). Use it to only call script hooks
+/// with `pxs_objectcall` when a script has one.
+///
+/// `var` can be a `pxs_HostObject` or its language object. If runtime is null the runtime that produced `var` is used.
+/// Returns false for errors, methods of the host type and vars of custom backends.
+///
+/// runtime:BORROW
+/// var:BORROW
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_object_is_overridden(runtime: pxs_VarT, var: pxs_VarT, method: *const c_char) -> bool {
+    pxs_debug!("pxs_object_is_overridden");
+    assert_initiated!();
+
+    if var.is_null() || method.is_null() {
+        strict::misuse("pxs_object_is_overridden", "var or method is null");
+        return false;
+    }
+    let var_borrow = borrow_var!(var);
+    let method = borrow_string!(method);
+    if !var_borrow.is_object() && !var_borrow.is_host_object() {
+        return false;
+    }
+    if origin_mismatch(runtime, var_borrow).is_some() || custom_backend(runtime, var_borrow).is_some() {
+        return false;
+    }
+    let Ok(runtime) = object_runtime(runtime, var_borrow) else {
+        return false;
+    };
+
+    with_backend!(runtime, Backend => {
+        Backend::object_overrides(var_borrow, method).unwrap_or(false)
+    })
+}
+
+/// The runtime of a object call, `runtime` or if null the runtime that produced `var`.
+fn object_runtime(runtime: pxs_VarT, var: &pxs_Var) -> Result<pxs_Runtime, pxs_Var> {
+    if runtime.is_null() {
+        // Use the runtime that produced the var.
+        return var.get_origin().ok_or_else(|| pxs_Var::null_param_ep("runtime"));
+    }
+
+    // Check that runtime is acually a int
+    let runtime_borrow = borrow_var!(runtime);
+    let runtime_val = runtime_borrow.get_i64().map_err(|_| {
+        pxs_Var::incorrect_types_ep(vec![pxs_VarType::pxs_Int64, pxs_VarType::pxs_UInt64], runtime_borrow.tag)
+    })?;
+    pxs_Runtime::from_i64(runtime_val).ok_or_else(|| pxs_Var::unkown_runtime_ep(runtime_val))
+}
+
 /// Get a int (i64) from a var.
 ///
 /// var:BORROW
@@ -741,6 +1519,9 @@ pub extern "C" fn pxs_getint(var: *mut pxs_Var) -> i64 {
             pxs_VarType::pxs_UInt64 => b_var.value.u64_val as i64,
             pxs_VarType::pxs_Bool => b_var.value.bool_val.into(),
             pxs_VarType::pxs_Float64 => b_var.value.f64_val as i64,
+            pxs_VarType::pxs_Int32 => b_var.value.i32_val as i64,
+            pxs_VarType::pxs_UInt32 => b_var.value.u32_val as i64,
+            pxs_VarType::pxs_Float32 => b_var.value.f32_val as i64,
             _ => -1,
         }
     }
@@ -764,6 +1545,9 @@ pub extern "C" fn pxs_getuint(var: *mut pxs_Var) -> u64 {
             pxs_VarType::pxs_UInt64 => b_var.value.u64_val,
             pxs_VarType::pxs_Bool => b_var.value.bool_val.into(),
             pxs_VarType::pxs_Float64 => b_var.value.f64_val as u64,
+            pxs_VarType::pxs_Int32 => b_var.value.i32_val as u64,
+            pxs_VarType::pxs_UInt32 => b_var.value.u32_val as u64,
+            pxs_VarType::pxs_Float32 => b_var.value.f32_val as u64,
             _ => 0,
         }
     }
@@ -787,11 +1571,45 @@ pub extern "C" fn pxs_getfloat(var: *mut pxs_Var) -> f64 {
             pxs_VarType::pxs_UInt64 => b_var.value.u64_val as f64,
             pxs_VarType::pxs_Bool => b_var.value.bool_val.into(),
             pxs_VarType::pxs_Float64 => b_var.value.f64_val,
+            pxs_VarType::pxs_Int32 => b_var.value.i32_val as f64,
+            pxs_VarType::pxs_UInt32 => b_var.value.u32_val as f64,
+            pxs_VarType::pxs_Float32 => b_var.value.f32_val as f64,
             _ => 0 as f64,
         }
     }
 }
 
+/// Get a int32 from a var. Any number is cast like `pxs_getint`, use `pxs_getint_checked` to catch overflows.
+///
+/// var:BORROW
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_getint32(var: pxs_VarT) -> i32 {
+    pxs_debug!("pxs_getint32");
+    pxs_getint(var) as i32
+}
+
+/// Get a uint32 from a var. Any number is cast like `pxs_getuint`, use `pxs_getuint_checked` to catch overflows.
+///
+/// var:BORROW
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_getuint32(var: pxs_VarT) -> u32 {
+    pxs_debug!("pxs_getuint32");
+    pxs_getuint(var) as u32
+}
+
+/// Get a float32 from a var. Any number is cast like `pxs_getfloat`.
+///
+/// var:BORROW
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_getfloat32(var: pxs_VarT) -> f32 {
+    pxs_debug!("pxs_getfloat32");
+    if !var.is_null() && borrow_var!(var).is_f32() {
+        // Do not round trip through f64.
+        return borrow_var!(var).get_f32().unwrap();
+    }
+    pxs_getfloat(var) as f32
+}
+
 /// Get a Bool
 ///
 /// CAN_CRASH
@@ -806,6 +1624,82 @@ pub extern "C" fn pxs_getbool(var: *mut pxs_Var) -> bool {
     unsafe { pxs_Var::from_borrow(var).get_bool().unwrap() }
 }
 
+/// Write the result of a checked conversion into `out_value`.
+fn write_checked<T>(
+    var: pxs_VarT,
+    out_value: *mut T,
+    convert: impl FnOnce(&pxs_Var) -> Result<T, pxs_Status>,
+) -> pxs_Status {
+    if var.is_null() || out_value.is_null() {
+        return pxs_Status::pxs_StatusNull;
+    }
+
+    match convert(borrow_var!(var)) {
+        Ok(value) => {
+            unsafe { *out_value = value };
+            pxs_Status::pxs_StatusOk
+        }
+        Err(status) => status,
+    }
+}
+
+/// Get a int (i64) from a var without silently losing data. Writes it into `out_value` on `pxs_StatusOk`, otherwise
+/// `out_value` is left alone.
+///
+/// Floats with a fraction are handled by `policy`. Values out of range, NaN and infinity are `pxs_StatusOverflow`.
+///
+/// var:BORROW
+/// out_value:BORROW
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_getint_checked(var: pxs_VarT, out_value: *mut i64, policy: pxs_Convert) -> pxs_Status {
+    pxs_debug!("pxs_getint_checked");
+    assert_initiated!();
+    write_checked(var, out_value, |var| convert::to_i64(var, policy))
+}
+
+/// Get a uint (u64) from a var without silently losing data. Writes it into `out_value` on `pxs_StatusOk`, otherwise
+/// `out_value` is left alone.
+///
+/// Floats with a fraction are handled by `policy`. Negative values, values out of range, NaN and infinity are
+/// `pxs_StatusOverflow`.
+///
+/// var:BORROW
+/// out_value:BORROW
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_getuint_checked(var: pxs_VarT, out_value: *mut u64, policy: pxs_Convert) -> pxs_Status {
+    pxs_debug!("pxs_getuint_checked");
+    assert_initiated!();
+    write_checked(var, out_value, |var| convert::to_u64(var, policy))
+}
+
+/// Get a float (f64) from a var. Writes it into `out_value` on `pxs_StatusOk`, otherwise `out_value` is left alone.
+///
+/// Integers above 2^53 can not all be represented, with `pxs_ConvertError` those are `pxs_StatusInexact`. Otherwise
+/// they are rounded to the nearest float.
+///
+/// var:BORROW
+/// out_value:BORROW
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_getfloat_checked(var: pxs_VarT, out_value: *mut f64, policy: pxs_Convert) -> pxs_Status {
+    pxs_debug!("pxs_getfloat_checked");
+    assert_initiated!();
+    write_checked(var, out_value, |var| convert::to_f64(var, policy))
+}
+
+/// Get a bool from a var. Writes it into `out_value` on `pxs_StatusOk`, otherwise `out_value` is left alone.
+///
+/// The integers 0 and 1 are also bools, other integers are `pxs_StatusOverflow`. Unlike `pxs_getbool` this does not
+/// crash on other types.
+///
+/// var:BORROW
+/// out_value:BORROW
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_getbool_checked(var: pxs_VarT, out_value: *mut bool) -> pxs_Status {
+    pxs_debug!("pxs_getbool_checked");
+    assert_initiated!();
+    write_checked(var, out_value, convert::to_bool)
+}
+
 /// Get a String
 ///
 /// CAN_CRASH, CALLER
@@ -830,1505 +1724,4824 @@ pub extern "C" fn pxs_getstring(var: *mut pxs_Var) -> *mut c_char {
     create_raw_string!(string.clone())
 }
 
-/// Check if a variable is of a type.
+/// Get a String without copying it. Writes the length in bytes (without the null terminator) into `out_len` if it is
+/// not null.
+///
+/// Returns null if `var` is not a `pxs_String`. The pointer is valid as long as `var` is, and until it is set to
+/// something else. Use `pxs_getstring` when you need a copy.
 ///
 /// var:BORROW
+/// out_len:BORROW
+/// return:BORROW&NULLABLE
 #[unsafe(no_mangle)]
-pub extern "C" fn pxs_varis(var: *mut pxs_Var, var_type: pxs_VarType) -> bool {
-    pxs_debug!("pxs_varis");
+pub extern "C" fn pxs_getstring_view(var: pxs_VarT, out_len: *mut usize) -> *const c_char {
+    pxs_debug!("pxs_getstring_view");
+    assert_initiated!();
+
     if var.is_null() {
-        return false;
+        return ptr::null();
     }
 
-    let var_borrow = unsafe { pxs_Var::from_borrow(var) };
-
-    var_borrow.tag == var_type
+    let string = match borrow_var!(var).get_cstr() {
+        Ok(string) => string,
+        Err(_) => return ptr::null(),
+    };
+    if !out_len.is_null() {
+        unsafe { *out_len = string.count_bytes() };
+    }
+    string.as_ptr()
 }
 
-/// Set a function for reading a file.
+/// Get the number of characters (Unicode code points) of a String, without copying it. For the length in bytes use
+/// `pxs_getstring_view`.
 ///
-/// This is used to load files via import, require, etc
+/// Returns -1 if `var` is not a `pxs_String` or is not valid UTF-8.
+///
+/// var:BORROW
 #[unsafe(no_mangle)]
-pub extern "C" fn pxs_set_filereader(func: pxs_LoadFileFn) {
-    pxs_debug!("pxs_set_filereader");
+pub extern "C" fn pxs_getstring_utf8len(var: pxs_VarT) -> i64 {
+    pxs_debug!("pxs_getstring_utf8len");
     assert_initiated!();
-    set_read_file(func);
+
+    if var.is_null() {
+        return -1;
+    }
+
+    match borrow_var!(var).get_cstr().map(|string| string.to_str()) {
+        Ok(Ok(string)) => string.chars().count() as i64,
+        _ => -1,
+    }
 }
 
-/// Set a function for reading a directory.
+/// Make a new Var string from `len` UTF-16 code units (not null terminated).
 ///
-/// This is used to read a dir.
+/// Invalid input (unpaired surrogates and null characters) is handled by the policy of `pxs_set_utf16_policy`. With
+/// `pxs_Utf16Error` a `pxs_Exception` is returned instead.
+///
+/// str:BORROW
+/// return:OWNED
 #[unsafe(no_mangle)]
-pub extern "C" fn pxs_set_dirreader(func: pxs_ReadDirFn) {
-    pxs_debug!("pxs_set_dirreader");
+pub extern "C" fn pxs_newstring_utf16(str: *const u16, len: usize) -> pxs_VarT {
+    pxs_debug!("pxs_newstring_utf16");
     assert_initiated!();
-    set_read_dir(func);
+
+    if str.is_null() {
+        if len > 0 {
+            return pxs_Var::null_param_ep("str").into_raw();
+        }
+        return pxs_Var::new_string(String::new()).into_raw();
+    }
+
+    let units = unsafe { core::slice::from_raw_parts(str, len) };
+    match utf16::decode(units) {
+        Ok(string) => pxs_Var::new_string(string).into_raw(),
+        Err(e) => pxs_Var::new_exception(e).into_raw(),
+    }
 }
 
-/// Free a PixelScript var.
+/// Get a String as null terminated UTF-16. Writes the length in code units (without the null terminator) into
+/// `out_len` if it is not null.
 ///
-/// You should only free results from `pxs_object_call`
+/// Invalid UTF-8 is handled by the policy of `pxs_set_utf16_policy`. Returns null if `var` is not a `pxs_String` or
+/// the policy is `pxs_Utf16Error` and it is invalid.
 ///
-/// var:TRANSFER
+/// You have to free this memory by calling `pxs_freestr_utf16`
+///
+/// var:BORROW
+/// out_len:BORROW
+/// return:OWNED&NULLABLE
 #[unsafe(no_mangle)]
-pub extern "C" fn pxs_freevar(var: *mut pxs_Var) {
-    pxs_debug!("pxs_freevar");
+pub extern "C" fn pxs_getstring_utf16(var: pxs_VarT, out_len: *mut usize) -> *mut u16 {
+    pxs_debug!("pxs_getstring_utf16");
     assert_initiated!();
 
     if var.is_null() {
-        return;
+        return ptr::null_mut();
     }
 
-    let _ = pxs_Var::from_raw(var);
+    let string = match borrow_var!(var).get_cstr() {
+        Ok(string) => string,
+        Err(_) => return ptr::null_mut(),
+    };
+    let units = match utf16::encode(string) {
+        Ok(units) => units,
+        Err(e) => {
+            pxs_debug!("pxs_getstring_utf16 failed: {e}");
+            return ptr::null_mut();
+        }
+    };
+    if !out_len.is_null() {
+        unsafe { *out_len = units.len() - 1 };
+    }
+    Box::into_raw(units.into_boxed_slice()) as *mut u16
 }
 
-/// Tells PixelScript that we are in a new thread.
+/// Free a string from `pxs_getstring_utf16`.
+///
+/// string:TRANSFER
 #[unsafe(no_mangle)]
-pub extern "C" fn pxs_startthread() {
-    pxs_debug!("pxs_startthread");
+pub extern "C" fn pxs_freestr_utf16(string: *mut u16) {
+    pxs_debug!("pxs_freestr_utf16");
     assert_initiated!();
-    with_feature!("lua", {
-        LuaScripting::start_thread();
-    });
-    with_feature!("python", {
-        PythonScripting::start_thread();
-    });
-    with_feature!("js", {
-        JSScripting::start_thread();
-    });
+    if string.is_null() {
+        return;
+    }
+
+    unsafe {
+        // The allocation ends at the null terminator, strings from `pxs_getstring_utf16` have no other nulls.
+        let mut len = 0;
+        while *string.add(len) != 0 {
+            len += 1;
+        }
+        let _ = Box::from_raw(ptr::slice_from_raw_parts_mut(string, len + 1));
+    }
 }
 
-/// Tells PixelScript that we just stopped the most recent thread.
+/// Set how invalid input is handled when converting strings to and from UTF-16. `pxs_Utf16Replace` by default.
+///
+/// Applies to all threads. Can be called before `pxs_initialize`.
 #[unsafe(no_mangle)]
-pub extern "C" fn pxs_stopthread() {
-    pxs_debug!("pxs_stopthread");
-    assert_initiated!();
-    with_feature!("lua", {
-        LuaScripting::stop_thread();
-    });
-    with_feature!("python", {
-        PythonScripting::stop_thread();
-    });
-    with_feature!("js", {
-        JSScripting::stop_thread();
-    });
+pub extern "C" fn pxs_set_utf16_policy(policy: pxs_Utf16Policy) {
+    pxs_debug!("pxs_set_utf16_policy");
+    utf16::set_policy(policy);
 }
 
-/// Clear the current threads state for all languages.
+/// Check if a variable is of a type.
 ///
-/// Optionally, if you want to run the garbage collector.
+/// var:BORROW
 #[unsafe(no_mangle)]
-pub extern "C" fn pxs_clear() {
-    pxs_debug!("pxs_clear");
-    assert_initiated!();
-    // Drop function lookup
-    clear_function_lookup();
-    // Drop object lookup
-    clear_object_lookup();
+pub extern "C" fn pxs_varis(var: *mut pxs_Var, var_type: pxs_VarType) -> bool {
+    pxs_debug!("pxs_varis");
+    if var.is_null() {
+        return false;
+    }
 
-    with_feature!("lua", {
-        LuaScripting::clear();
-    });
-    with_feature!("python", {
-        PythonScripting::clear();
-    });
-    with_feature!("js", {
-        JSScripting::clear();
-    })
+    let var_borrow = unsafe { pxs_Var::from_borrow(var) };
+
+    var_borrow.tag == var_type
 }
 
-/// Call a method within a specifed runtime.
-///
-/// Runtime is a `pxs_Var`.
+/// Deep copy a var so it can be passed to a VM on another thread (i.e. a worker). Only data (numbers, strings,
+/// lists, maps, bytes) can be migrated, Objects, Functions, Factories and host objects return a exception.
 ///
-/// Transfers ownership of args.
+/// With the `pxs-debug` feature `pxs_varcall` and `pxs_objectcall` return a exception for vars of another VM.
 ///
-/// runtime:BORROW
-/// args:TRANSFER
+/// var:BORROW
 /// return:OWNED
 #[unsafe(no_mangle)]
-pub extern "C" fn pxs_call(
-    runtime: *mut pxs_Var,
-    method: *const c_char,
-    args: *mut pxs_Var,
-) -> *mut pxs_Var {
-    pxs_debug!("pxs_call");
-    assert_initiated!();
-
-    if runtime.is_null() || method.is_null() || args.is_null() {
-        return pxs_Var::null_params_ep().into_raw();
-    }
-
-    let runtime_var = unsafe { pxs_Var::from_borrow(runtime) };
-    let runtime_id = pxs_getint(runtime_var);
-
-    // Borrow runtime, var, and method, and argv
-    let runtime_borrow = pxs_Runtime::from_i64(runtime_id);
-    let method_borrow = borrow_string!(method);
-    // Own args
-    let args = own_var!(args);
-    if !args.is_list() {
-        return pxs_Var::incorrect_type_ep(pxs_VarType::pxs_List, args.tag).into_raw();
+pub extern "C" fn pxs_migrate(var: *mut pxs_Var) -> pxs_VarT {
+    pxs_debug!("pxs_migrate");
+    if var.is_null() {
+        return pxs_Var::null_param_ep("var").into_raw();
     }
-    let list = args.get_list().unwrap();
 
-    // Get runtime
-    if let Some(rt) = runtime_borrow {
-        with_backend!(rt, Backend => {
-            let res = Backend::call_method(method_borrow, list);
-            if res.is_err() {
-                pxs_Var::new_exception(res.unwrap_err().to_string())
-            } else {
-                res.unwrap()
-            }
-        })
-        .into_raw()
-    } else {
-        pxs_Var::unkown_runtime_ep(runtime_id).into_raw()
+    let var_borrow = unsafe { pxs_Var::from_borrow(var) };
+    match var_borrow.migrate() {
+        Ok(res) => res.into_raw(),
+        Err(err) => pxs_Var::new_exception(err).into_raw(),
     }
 }
 
-/// Call a ToString method on this Var. If already a string, it won't call it.
+/// Validate `var` against a `schema` map, i.e. config provided by a mod. Schemas support `type` (a name or list of
+/// names), `required` (list of keys), `keys` (key => schema), `values` and `items` (schema) and `min`/`max` (number
+/// value or length).
 ///
-/// Host must free this memory with `pxs_free_var`
+/// Returns a list of error strings prefixed with the path of the value (i.e. `$.units[2].hp`), empty if `var` is
+/// valid. Returns a exception if the schema is invalid.
 ///
-/// runtime_var:BORROW
 /// var:BORROW
+/// schema:BORROW
 /// return:OWNED
 #[unsafe(no_mangle)]
-pub extern "C" fn pxs_tostring(runtime_var: *mut pxs_Var, var: *mut pxs_Var) -> *mut pxs_Var {
-    pxs_debug!("pxs_tostring");
-    assert_initiated!();
-
-    if var.is_null() || runtime_var.is_null() {
+pub extern "C" fn pxs_validate(var: pxs_VarT, schema: pxs_VarT) -> pxs_VarT {
+    pxs_debug!("pxs_validate");
+    if var.is_null() || schema.is_null() {
         return pxs_Var::null_params_ep().into_raw();
     }
 
-    // Borrow
-    let b_var = unsafe { pxs_Var::from_borrow(var) };
-
-    // If string or primative, no object calling needed
-    match b_var.tag {
-        pxs_VarType::pxs_Int64 => {
-            let val = b_var.get_i64().unwrap();
-            return pxs_Var::new_string(val.to_string()).into_raw();
-        }
-        pxs_VarType::pxs_UInt64 => {
-            let val = b_var.get_u64().unwrap();
-            return pxs_Var::new_string(val.to_string()).into_raw();
-        }
-        pxs_VarType::pxs_String | pxs_VarType::pxs_Exception => {
-            return pxs_Var::new_string(b_var.get_string().unwrap().clone()).into_raw();
-        }
-        pxs_VarType::pxs_Bool => {
-            return pxs_Var::new_string(b_var.get_bool().unwrap().to_string()).into_raw();
-        }
-        pxs_VarType::pxs_Float64 => {
-            return pxs_Var::new_string(b_var.get_f64().unwrap().to_string()).into_raw();
-        }
-        pxs_VarType::pxs_Null => {
-            return pxs_Var::new_string("null".to_string()).into_raw();
-        }
-        _ => {
-            // Do nothing
-        }
+    let var_borrow = unsafe { pxs_Var::from_borrow(var) };
+    let schema_borrow = unsafe { pxs_Var::from_borrow(schema) };
+    match validate::validate(var_borrow, schema_borrow) {
+        Ok(errors) => pxs_Var::new_list_with(errors.into_iter().map(pxs_Var::new_string).collect()),
+        Err(err) => pxs_Var::new_exception(err),
     }
+    .into_raw()
+}
 
-    // Not a string, so let's convert
-    let runtime = unsafe { pxs_Runtime::from_var_ptr(runtime_var) };
-    if let Some(runtime) = runtime {
-        let args = pxs_Var::new_list();
-        let list = args.get_list().unwrap();
-        list.add_item(b_var.shallow_copy());
-        let res = match runtime {
-            pxs_Runtime::pxs_Lua => {
-                with_feature!("lua", { LuaScripting::call_method("tostring", list) }, {
-                    return pxs_Var::feature_not_enabled_ep("lua").into_raw();
-                })
-            }
-            pxs_Runtime::pxs_Python => {
-                with_feature!("python", { PythonScripting::call_method("str", list) }, {
-                    return pxs_Var::feature_not_enabled_ep("python").into_raw();
-                })
-            }
-            pxs_Runtime::pxs_JavaScript => {
-                with_feature!(
-                    "js",
-                    {
-                        // Check variable is null.
-                        if pxs_varis(var, pxs_VarType::pxs_Null) {
-                            return pxs_Var::new_string("null".to_owned()).into_raw();
-                        }
-
-                        let mut empty_list = pxs_VarList::new();
-                        JSScripting::object_call(b_var, "toString", &mut empty_list)
-                    },
-                    {
-                        return pxs_Var::feature_not_enabled_ep("js").into_raw();
-                    }
-                )
-            }
-            pxs_Runtime::pxs_Wren => {
-                todo!()
-            }
-        };
-
-        if let Ok(res) = res {
-            res.into_raw()
-        } else {
-            pxs_Var::new_exception(res.unwrap_err().to_string()).into_raw()
-        }
-    } else {
-        pxs_Var::unkown_runtime_var_ep(runtime_var).into_raw()
+/// Get the runtime (`pxs_Runtime`) that produced a Object or Function var. -1 for vars made by the host.
+///
+/// var:BORROW
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_varorigin(var: *mut pxs_Var) -> i32 {
+    pxs_debug!("pxs_varorigin");
+    if var.is_null() {
+        return -1;
     }
+
+    let var_borrow = unsafe { pxs_Var::from_borrow(var) };
+    var_borrow.origin
 }
 
-/// Create a new pxs_VarList.
-///
-/// This does not take any arguments. To add to a list, you must call `pxs_var_list_add(ptr, item)`
+/// Set a function for reading a file.
 ///
-/// return:OWNED
+/// This is used to load files via import, require, etc
 #[unsafe(no_mangle)]
-pub extern "C" fn pxs_newlist() -> *mut pxs_Var {
-    pxs_debug!("pxs_newlist");
+pub extern "C" fn pxs_set_filereader(func: pxs_LoadFileFn) {
+    pxs_debug!("pxs_set_filereader");
     assert_initiated!();
-
-    pxs_Var::new_list().into_raw()
+    set_read_file(func);
 }
 
-/// Add a item to a pxs_VarList.
+/// Turn the compiled chunk cache on or off. Off by default, turning it off drops every chunk.
 ///
-/// Expects a pointer to pxs_VarList. And a pointer for the item to add (pxs_Var*)
+/// Files loaded by Lua `require` are compiled once and kept keyed by path and a hash of the source, so the next
+/// `require` (on any thread) skips parsing while the file is unchanged. At most 32 MiB of chunks are kept, the least
+/// recently used go first. pocketpy can not share compiled code between VMs, so Python imports are not cached. Can be
+/// called before `pxs_initialize`.
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_set_chunk_cache(enabled: bool) {
+    pxs_debug!("pxs_set_chunk_cache");
+    chunks::set_enabled(enabled);
+}
+
+/// Drop the cached chunks of `path` (as passed to the file reader, or the path of a resolved module) for hot reload. Null drops every chunk. Returns how
+/// many chunks were dropped.
 ///
-/// This will take ownership of the added item. If you want to copy it instead first create a new `pxs_Var` with `pxs_var_newcopy(item)`
+/// Chunks of changed files are never used, this only frees them early.
 ///
-/// Will return the index added at.
+/// path:BORROW
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_invalidate_cache(path: *const c_char) -> i32 {
+    pxs_debug!("pxs_invalidate_cache");
+    if path.is_null() {
+        return chunks::invalidate(None) as i32;
+    }
+    chunks::invalidate(Some(borrow_string!(path))) as i32
+}
+
+/// Set a function for reading a directory.
 ///
-/// list:BORROW
-/// item:TRANSFER
+/// This is used to read a dir.
 #[unsafe(no_mangle)]
-pub extern "C" fn pxs_listadd(list: *mut pxs_Var, item: *mut pxs_Var) -> i32 {
-    pxs_debug!("pxs_listadd");
+pub extern "C" fn pxs_set_dirreader(func: pxs_ReadDirFn) {
+    pxs_debug!("pxs_set_dirreader");
     assert_initiated!();
+    set_read_dir(func);
+}
 
-    // Check null
-    if list.is_null() || item.is_null() {
-        return -1;
-    }
+/// Add a module resolver (i.e. for archives, memory bundles or network sources). Resolvers are asked in the order they
+/// were added, with the requested module name and runtime, whenever a script imports a module that is not a host
+/// module. The first one returning a source wins, the file reader is only used when none has the module.
+///
+/// Names are as written by the script: `require('a.b')` in Lua, `import a.b` in Python and `import 'a/b.js'` in JS.
+/// Lua uses the returned path as chunk name and chunk cache key. Python and JS keep the name, they key modules by it.
+/// Python imports the parent packages of `a.b` first, so resolvers need `a` too (a empty source is fine).
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_add_module_resolver(func: pxs_ModuleResolverFn) {
+    pxs_debug!("pxs_add_module_resolver");
+    assert_initiated!();
+    add_module_resolver(func);
+}
 
-    // Borrow list
-    let borrow_list = unsafe { pxs_Var::from_borrow(list) };
-    // Check that this is actually a list
-    if !borrow_list.is_list() {
-        return -1;
-    }
+/// Remove every resolver added with `pxs_add_module_resolver`.
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_clear_module_resolvers() {
+    pxs_debug!("pxs_clear_module_resolvers");
+    assert_initiated!();
+    clear_module_resolvers();
+}
 
-    // and own item
-    let owned_item = pxs_Var::from_raw(item);
+/// Create a empty script bundle. Fill it with `pxs_bundle_add` and use it with `pxs_use_bundle`.
+///
+/// return:OWNED (free with `pxs_bundle_free` unless passed to `pxs_use_bundle`)
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_bundle_new() -> *mut pxs_Bundle {
+    pxs_debug!("pxs_bundle_new");
+    pxs_Bundle::new().into_raw()
+}
 
-    // Get varlist and add item
-    let varlist = borrow_list.get_list().unwrap();
-    varlist.add_item(owned_item);
+/// Add a script to a bundle, replacing the one at the same `path`. Paths are relative to the bundle root, i.e.
+/// `utils/math.lua` or `game/__init__.py`.
+///
+/// path:BORROW
+/// contents:BORROW
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_bundle_add(bundle: *mut pxs_Bundle, path: *const c_char, contents: *const c_char) {
+    pxs_debug!("pxs_bundle_add");
+    if bundle.is_null() || path.is_null() || contents.is_null() {
+        strict::misuse("pxs_bundle_add", "bundle, path or contents is null");
+        return;
+    }
+    let bundle = unsafe { &mut *bundle };
+    bundle.add(borrow_string!(path), borrow_string!(contents).to_string());
+}
 
-    // Return index
-    (varlist.vars.len() - 1) as i32
+/// Free a bundle that was not passed to `pxs_use_bundle`.
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_bundle_free(bundle: *mut pxs_Bundle) {
+    pxs_debug!("pxs_bundle_free");
+    if bundle.is_null() {
+        return;
+    }
+    let _ = pxs_Bundle::from_raw(bundle);
 }
 
-/// Get a item from a pxs_VarList.
-///
-/// Expcts a pointer to pxs_VarList. And a index of i32. Supports negative indexes just like in Python.
+/// Resolve imports from `bundle` (NULLABLE), replacing the last one. Null removes it.
 ///
-/// This will NOT return a cloned variable, you must NOT free it.
+/// The bundle is asked after the resolvers of `pxs_add_module_resolver` and before the file reader, so scripts packed
+/// into the binary need no filesystem callbacks. Lua `require('a.b')` looks for `a.b`, `a/b.lua` and `a/b/init.lua`,
+/// Python `import a.b` for `a/b.py`, `a/b/__init__.py` and `a/b/__import__.py` and JS for the module name with and
+/// without `.js`. Directories with Python files are packages.
 ///
-/// list:BORROW
-/// return:BORROW&NULLABLE
+/// bundle:TRANSFER
 #[unsafe(no_mangle)]
-pub extern "C" fn pxs_listget(list: *mut pxs_Var, index: i32) -> *mut pxs_Var {
-    pxs_debug!("pxs_listget");
+pub extern "C" fn pxs_use_bundle(bundle: *mut pxs_Bundle) {
+    pxs_debug!("pxs_use_bundle");
     assert_initiated!();
+    set_bundle((!bundle.is_null()).then(|| pxs_Bundle::from_raw(bundle)));
+}
 
-    if list.is_null() {
-        return ptr::null_mut();
-    }
-
-    // Get list
-    let borrow_list = unsafe { pxs_Var::from_borrow(list) };
-    if !borrow_list.is_list() {
-        return pxs_Var::incorrect_type_ep(pxs_VarType::pxs_List, borrow_list.tag).into_raw();
-    }
+/// Set a function for writing a file.
+///
+/// This is used by `pxs_fs.write`, scripts can not write files without it.
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_set_filewriter(func: pxs_WriteFileFn) {
+    pxs_debug!("pxs_set_filewriter");
+    assert_initiated!();
+    set_write_file(func);
+}
 
-    // Derefernce list and get the item.
-    let varlist = borrow_list.get_list().unwrap();
-    if let Some(res) = varlist.get_item(index) {
-        res as *const pxs_Var as *mut pxs_Var
-    } else {
-        return ptr::null_mut();
+/// Set a prefix for the chunk names of code generated by pixelscript (i.e. `<create_Person>` becomes
+/// `<pxs:create_Person>` with the prefix `pxs:`). This lets crash reports and debuggers group internal code apart from
+/// user scripts.
+///
+/// Only applies to code generated after this call. Can be called before `pxs_initialize`.
+///
+/// prefix:BORROW
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_set_source_identity(prefix: *const c_char) {
+    pxs_debug!("pxs_set_source_identity");
+    if prefix.is_null() {
+        set_source_identity("");
+        return;
     }
+    set_source_identity(borrow_string!(prefix));
 }
 
-/// Set a item at a specific index in a pxs_VarList.
-///
-/// Expects a pointer to pxs_VarList, a index of i32, and a pxs_Var. Supports negative indexes jsut like in Python.
+/// Set the catalog used to localize error messages generated by pixelscript (type mismatches, permission denials, ...).
 ///
-/// Will take ownership of the pxs_Var.
+/// The catalog gets a stable message key (i.e. `incorrect_type`) and the default English template, and returns a
+/// translated template or null to keep the default. Templates use `{0}`, `{1}`, ... for their arguments.
+/// Script and host errors are passed through as is.
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_set_message_catalog(func: pxs_MessageCatalogFn) {
+    pxs_debug!("pxs_set_message_catalog");
+    assert_initiated!();
+    set_message_catalog(func);
+}
+
+/// Set a budget for values deep-cloned in a single crossing between a runtime and the host (function arguments and
+/// results, `pxs_varcall`, `pxs_object_call` and `pxs_callmethod`). Elements counts list items plus map keys and
+/// values, bytes counts string and `pxs_Bytes` data. `0` means unlimited.
 ///
-/// This will return a boolean for success = true, or failure = false.
+/// With `pxs_BudgetWarn` a warning is printed, with `pxs_BudgetError` the value is replaced by a `pxs_Exception`.
+/// Large data should be passed with `pxs_iter_new`, `pxs_Bytes` or `pxs_sync_*` instead. Off by default.
+/// Can be called before `pxs_initialize`.
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_set_clone_budget(max_elements: i64, max_bytes: i64, mode: pxs_BudgetMode) {
+    pxs_debug!("pxs_set_clone_budget");
+    budget::set(max_elements.max(0) as usize, max_bytes.max(0) as usize, mode);
+}
+
+/// Turn strict mode on or off. Off by default.
 ///
-/// list:BORROW
-/// item:TRANSFER
+/// Many functions silently ignore invalid input (i.e. `pxs_addfunc` with a null module or `pxs_listset` on a var that
+/// is not a list). In strict mode they panic instead, with the name of the function and what was wrong, which aborts
+/// the process at the offending call. Meant for debugging host integrations. Can be called before `pxs_initialize`.
 #[unsafe(no_mangle)]
-pub extern "C" fn pxs_listset(list: *mut pxs_Var, index: i32, item: *mut pxs_Var) -> bool {
-    pxs_debug!("pxs_listset");
-    assert_initiated!();
+pub extern "C" fn pxs_set_strict(enabled: bool) {
+    pxs_debug!("pxs_set_strict");
+    strict::set(enabled);
+}
 
-    if list.is_null() || item.is_null() {
-        return false;
+/// The backend from `pxs_register_backend` a call goes to: `runtime` if not null, otherwise the runtime that produced
+/// `var`. None for the built in runtimes.
+fn custom_backend(runtime: pxs_VarT, var: &pxs_Var) -> Option<backend::Backend> {
+    let id = if runtime.is_null() {
+        var.origin as i64
+    } else {
+        borrow_var!(runtime).get_i64().ok()?
+    };
+    if id < backend::PXS_CUSTOM_RUNTIME as i64 {
+        return None;
     }
+    backend::get(id)
+}
 
-    // Borrow list
-    let borrow_list = unsafe { pxs_Var::from_borrow(list) };
-    // Check if actually list
-    if !borrow_list.is_list() {
-        return false;
+/// A exception if `runtime` is not null and `var` was made by a different runtime. Host vars can be used anywhere.
+fn origin_mismatch(runtime: pxs_VarT, var: &pxs_Var) -> Option<pxs_Var> {
+    if runtime.is_null() || var.origin < 0 {
+        return None;
     }
+    let id = borrow_var!(runtime).get_i64().ok()?;
+    (id != var.origin as i64).then(|| pxs_Var::runtime_mismatch_ep(var.origin as i64, id))
+}
 
-    // Get list
-    let list = borrow_list.get_list().unwrap();
-    // Own item
-    let owned_item = pxs_Var::from_raw(item);
-    // Set in list
-    list.set_item(owned_item, index)
+/// Get a backend from `pxs_register_backend` by runtime id, or a exception.
+fn registered_backend(runtime: i32) -> Result<backend::Backend, pxs_Var> {
+    backend::get(runtime as i64).ok_or_else(|| pxs_Var::unkown_runtime_ep(runtime as i64))
 }
 
-/// Get length of a pxs_VarList.
+/// Register a language implemented by the host. Returns its runtime id (`PXS_CUSTOM_RUNTIME` and up), or -1 if
+/// `vtable` is null.
 ///
-/// Expects a pointer to a pxs_VarList
+/// The backend is started, gets every module added so far and every module added later (as a Map, see
+/// `pxs_BackendVTable`). Runtime vars with its id route `pxs_varcall`, `pxs_objectcall`, `pxs_objectget`,
+/// `pxs_objectset` and `pxs_execobject` to it, and host functions get its id as the runtime. Run code with
+/// `pxs_backend_exec`, `pxs_backend_eval` and `pxs_backend_compile`.
 ///
-/// list:BORROW
+/// vtable:BORROW
 #[unsafe(no_mangle)]
-pub extern "C" fn pxs_listlen(list: *mut pxs_Var) -> i32 {
-    pxs_debug!("pxs_listlen");
+pub extern "C" fn pxs_register_backend(vtable: *const pxs_BackendVTable) -> i32 {
+    pxs_debug!("pxs_register_backend");
     assert_initiated!();
 
-    if list.is_null() {
+    if vtable.is_null() {
+        strict::misuse("pxs_register_backend", "vtable is null");
         return -1;
     }
+    let vtable = unsafe { *vtable };
+    let name = if vtable.name.is_null() {
+        "custom".to_string()
+    } else {
+        borrow_string!(vtable.name).to_string()
+    };
 
-    let borrow_list = unsafe { pxs_Var::from_borrow(list) };
-    if !borrow_list.is_list() {
-        return -1;
-    }
+    start_backend(vtable, name)
+}
 
-    let list = borrow_list.get_list().unwrap();
+/// Register, start and send the modules to a backend. Returns its runtime id.
+fn start_backend(vtable: pxs_BackendVTable, name: String) -> i32 {
+    let id = backend::register(vtable, name);
+    let backend = backend::get(id as i64).unwrap();
+    backend.start();
+    with_feature!("pxs_json", {
+        backend.add_module(&pxs_core::pxs_json::module());
+    });
+    for module in registry::modules() {
+        backend.add_module(&module);
+    }
 
-    list.vars.len() as i32
+    id
 }
 
-/// Call a `pxs_Var`s function.
-///
-/// Expects runtime var, var function, and args that is a List.
-///
-/// Transfers ownership of args.
+/// Execute code in a backend from `pxs_register_backend`. Null means no error, otherwise a exception.
 ///
-/// runtime:BORROW
-/// var_func:BORROW
-/// args:TRANSFER
+/// code:BORROW
+/// file_name:BORROW
 /// return:OWNED
 #[unsafe(no_mangle)]
-pub extern "C" fn pxs_varcall(
-    runtime: *mut pxs_Var,
-    var_func: *mut pxs_Var,
-    args: *mut pxs_Var,
-) -> *mut pxs_Var {
-    pxs_debug!("pxs_varcall");
+pub extern "C" fn pxs_backend_exec(runtime: i32, code: *const c_char, file_name: *const c_char) -> pxs_VarT {
+    pxs_debug!("pxs_backend_exec");
     assert_initiated!();
 
-    if runtime.is_null() || var_func.is_null() || args.is_null() {
+    if code.is_null() || file_name.is_null() {
         return pxs_Var::null_params_ep().into_raw();
     }
+    let backend = match registered_backend(runtime) {
+        Ok(backend) => backend,
+        Err(e) => return e.into_raw(),
+    };
 
-    // Get the function pointer
-    let borrow_func = borrow_var!(var_func);
-    // Check if function
-    if !borrow_func.is_function() {
-        return pxs_Var::incorrect_type_ep(pxs_VarType::pxs_Function, borrow_func.tag).into_raw();
-    }
-
-    // own args
-    let args = own_var!(args);
-    // Check it's a list
-    if !args.is_list() {
-        return ptr::null_mut();
-    }
-    let list = args.get_list().unwrap();
-
-    // Match the runtime
-    let rt = unsafe { pxs_Runtime::from_var_ptr(runtime) };
-    if let Some(runtime) = rt {
-        with_backend!(runtime, Backend => {
-            let res = Backend::var_call(borrow_func, list);
-            if res.is_err() {
-                pxs_Var::new_exception(res.unwrap_err().to_string())
-            } else {
-                res.unwrap()
-            }
-        })
-        .into_raw()
+    let res = backend.execute(borrow_string!(code), borrow_string!(file_name));
+    if res.is_null() {
+        ptr::null_mut()
     } else {
-        pxs_Var::unkown_runtime_var_ep(runtime).into_raw()
+        res.into_raw()
     }
 }
 
-/// Copy the pxs_Var.
+/// Evaluate code in a backend from `pxs_register_backend`.
 ///
-/// Memory is handled by caller
-///
-/// item:BORROW
+/// script:BORROW
 /// return:OWNED
 #[unsafe(no_mangle)]
-pub extern "C" fn pxs_newcopy(item: *mut pxs_Var) -> *mut pxs_Var {
-    pxs_debug!("pxs_newcopy");
+pub extern "C" fn pxs_backend_eval(runtime: i32, script: *const c_char) -> pxs_VarT {
+    pxs_debug!("pxs_backend_eval");
     assert_initiated!();
 
-    if item.is_null() {
-        return pxs_Var::null_param_ep("item").into_raw();
+    if script.is_null() {
+        return pxs_Var::null_param_ep("script").into_raw();
+    }
+    match registered_backend(runtime) {
+        Ok(backend) => budget::checked(backend.eval(borrow_string!(script), "<eval>")).into_raw(),
+        Err(e) => e.into_raw(),
     }
-
-    // Borrow var
-    let borrow_item = borrow_var!(item);
-
-    // Clone the var
-    let cloned_var = borrow_item.clone();
-    cloned_var.into_raw()
 }
 
-/// Call a objects getter.
+/// Compile code in a backend from `pxs_register_backend`. Works like `pxs_compile`, run it with `pxs_execobject`.
 ///
-/// runtime:BORROW
-/// obj:BORROW
+/// global_scope:TRANSFER
 /// return:OWNED
 #[unsafe(no_mangle)]
-pub extern "C" fn pxs_objectget(runtime: pxs_VarT, obj: pxs_VarT, key: *const c_char) -> pxs_VarT {
-    pxs_debug!("pxs_objectget");
+pub extern "C" fn pxs_backend_compile(runtime: i32, code: *const c_char, global_scope: pxs_VarT) -> pxs_VarT {
+    pxs_debug!("pxs_backend_compile");
     assert_initiated!();
-    if runtime.is_null() || obj.is_null() || key.is_null() {
+
+    if code.is_null() || global_scope.is_null() {
         return pxs_Var::null_params_ep().into_raw();
     }
+    let scope = own_var!(global_scope);
+    if !scope.is_map() && !scope.is_null() {
+        return pxs_Var::incorrect_types_ep(vec![pxs_VarType::pxs_Map, pxs_VarType::pxs_Null], scope.tag).into_raw();
+    }
+    let backend = match registered_backend(runtime) {
+        Ok(backend) => backend,
+        Err(e) => return e.into_raw(),
+    };
 
-    // Borrow var
-    let borrow_obj = borrow_var!(obj);
-    let borrow_rt = unsafe { pxs_Runtime::from_var_ptr(runtime).unwrap() };
-    let borrow_key = borrow_string!(key);
+    let res = backend.compile(borrow_string!(code), &scope);
+    // Add the runtime to the object.
+    if let Some(list) = res.get_list() {
+        // 0 is always in bounds.
+        let _ = list.insert_item(0, pxs_Var::new_i64(runtime as i64));
+    }
+    res.into_raw()
+}
 
-    with_backend!(borrow_rt, Backend => {
-        let res = Backend::get(borrow_obj, borrow_key);
-        if res.is_err() {
-            pxs_Var::new_exception(res.unwrap_err().to_string())
-        } else {
-            res.unwrap()
+/// Wrap a language value of a backend from `pxs_register_backend` as a `pxs_Function` or `pxs_Object` var produced
+/// by `runtime`. `deleter` (NULLABLE) is called with `ptr` when the var is freed. Returns null for other var types.
+///
+/// ptr:OWNED
+/// return:OWNED
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_backend_newvar(
+    runtime: i32,
+    var_type: pxs_VarType,
+    ptr: pxs_Opaque,
+    deleter: Option<pxs_DeleterFn>,
+) -> pxs_VarT {
+    pxs_debug!("pxs_backend_newvar");
+    assert_initiated!();
+
+    let mut var = match var_type {
+        pxs_VarType::pxs_Function => pxs_Var::new_function(ptr, deleter),
+        pxs_VarType::pxs_Object => pxs_Var::new_object(pxs_VarObject::new_lang_only(ptr), deleter),
+        _ => {
+            strict::misuse("pxs_backend_newvar", "var_type must be pxs_Function or pxs_Object");
+            return ptr::null_mut();
         }
-    })
-    .into_raw()
+    };
+    var.origin = runtime;
+    var.into_raw()
 }
 
-/// Call a objects setter.
+/// Get the `ptr` of a var made with `pxs_backend_newvar`. Null for other vars.
 ///
-/// value ownership is transfered.
+/// var:BORROW
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_backend_getptr(var: pxs_VarT) -> pxs_Opaque {
+    pxs_debug!("pxs_backend_getptr");
+    if var.is_null() {
+        return ptr::null_mut();
+    }
+    let var = borrow_var!(var);
+    match var.tag {
+        pxs_VarType::pxs_Function => unsafe { var.value.function_val },
+        pxs_VarType::pxs_Object => var.get_object_ptr(),
+        _ => ptr::null_mut(),
+    }
+}
+
+/// Run the conformance cases against a runtime (a `pxs_Runtime` or a id from `pxs_register_backend`), to check it
+/// converts vars, exposes modules and objects and reports errors like the other runtimes.
 ///
-/// runtime:BORROW
-/// obj:BORROW
-/// value:TRANSFER
+/// Returns a Map `{runtime, passed, failed, skipped, cases: [{name, category, status, message}]}`. A `pxs_conformance`
+/// module is added while the cases run. Cases have built in scripts for Lua, Python and JavaScript, other runtimes
+/// need `pxs_conformance_setsource` or the case is skipped.
+///
+/// return:OWNED
 #[unsafe(no_mangle)]
-pub extern "C" fn pxs_objectset(
-    runtime: pxs_VarT,
-    obj: pxs_VarT,
-    key: *const c_char,
-    value: pxs_VarT,
-) -> bool {
-    pxs_debug!("pxs_objectset");
+pub extern "C" fn pxs_run_conformance(runtime: i32) -> pxs_VarT {
+    pxs_debug!("pxs_run_conformance");
     assert_initiated!();
 
-    if runtime.is_null() || obj.is_null() || key.is_null() || value.is_null() {
-        return false;
+    if let Some(backend) = backend::get(runtime as i64) {
+        return conformance::run(runtime, &|code| backend.execute(code, "<conformance>")).into_raw();
     }
+    let Some(rt) = enabled_runtime(runtime) else {
+        return pxs_Var::unkown_runtime_ep(runtime as i64).into_raw();
+    };
 
-    // Borrow
-    let rt = unsafe { pxs_Runtime::from_var_ptr(runtime).unwrap() };
-    let borrow_obj = borrow_var!(obj);
-    let borrow_key = borrow_string!(key);
-    // own
-    let owned_value = own_var!(value);
-
-    with_backend!(rt, Backend => {
-        let res = Backend::set(borrow_obj, borrow_key, &owned_value);
-        if res.is_err() {
-            false
-            // pxs_Var::new_exception(res.unwrap_err().to_string())
-        } else {
-            true
-            // res.unwrap()
-        }
+    conformance::run(runtime, &|code| {
+        let res: shared::PxsResult = with_backend!(rt.clone(), Backend => { Backend::execute(code, "<conformance>") });
+        res.unwrap_or_else(pxs_Var::new_exception)
     })
+    .into_raw()
 }
 
-/// Evaluate code. This will return a `pxs_VarT`.
+/// The built in runtime with this id, if its feature is enabled.
+fn enabled_runtime(runtime: i32) -> Option<pxs_Runtime> {
+    let rt = pxs_Runtime::from_i64(runtime as i64)?;
+    let enabled = match rt {
+        pxs_Runtime::pxs_Lua => cfg!(feature = "lua"),
+        pxs_Runtime::pxs_Python => cfg!(feature = "python"),
+        pxs_Runtime::pxs_JavaScript => cfg!(feature = "js"),
+        _ => false,
+    };
+    enabled.then_some(rt)
+}
+
+/// File and line of the script code calling the current host function in `runtime`. None for custom backends or when
+/// not called from a script.
+#[allow(unused)]
+pub(crate) fn caller_location(runtime: i32) -> Option<(String, i32)> {
+    let rt = enabled_runtime(runtime)?;
+    with_backend!(rt, Backend => { Backend::caller_location() })
+}
+
+/// Set the script of a conformance case for a runtime, i.e. to port the cases to a language from
+/// `pxs_register_backend`. Scripts pass their result to `require('pxs_conformance').report(value)` (or the
+/// language's import).
 ///
-/// return:OWNED
+/// case:BORROW
+/// code:BORROW
 #[unsafe(no_mangle)]
-pub extern "C" fn pxs_eval(script: *const c_char, rt: pxs_Runtime) -> pxs_VarT {
-    pxs_debug!("pxs_eval");
-    if script.is_null() {
-        return pxs_Var::null_param_ep("script").into_raw();
+pub extern "C" fn pxs_conformance_setsource(runtime: i32, case: *const c_char, code: *const c_char) {
+    pxs_debug!("pxs_conformance_setsource");
+    if case.is_null() || code.is_null() {
+        strict::misuse("pxs_conformance_setsource", "case or code is null");
+        return;
     }
+    conformance::set_source(runtime, borrow_string!(case), borrow_string!(code));
+}
 
-    let script = borrow_string!(script);
+/// Run `runtime` in a child process (a script worker), so a crash in its VM does not take down the host. Requires
+/// the `script-worker` feature.
+///
+/// The child is `exe_path` (NULLABLE, the current executable if null) started with `args` (NULLABLE, a List of
+/// strings). The default args are `--pxs-worker <runtime>`, the host calls `pxs_worker_main(runtime)` when started
+/// with them. Returns the runtime id of the worker (see `pxs_register_backend`) or -1 if it could not be started.
+///
+/// Run code with `pxs_backend_exec` and `pxs_backend_eval`. Modules are sent to the worker and its scripts call the
+/// host functions, only data (no Objects or Functions) crosses the process boundary. If the child dies, every call
+/// returns a `worker_crashed` exception.
+///
+/// exe_path:BORROW
+/// args:BORROW
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_worker_spawn(runtime: i32, exe_path: *const c_char, args: pxs_VarT) -> i32 {
+    pxs_debug!("pxs_worker_spawn");
+    assert_initiated!();
 
-    with_backend!(rt, Backend => {
-        let res = Backend::eval(script, "<eval>");
-        if res.is_err() {
-            pxs_Var::new_exception(res.unwrap_err().to_string())
-        } else {
-            res.unwrap()
+    let exe = if exe_path.is_null() {
+        match std::env::current_exe() {
+            Ok(path) => path.to_string_lossy().to_string(),
+            Err(_) => return -1,
+        }
+    } else {
+        borrow_string!(exe_path).to_string()
+    };
+    let args = if args.is_null() {
+        vec!["--pxs-worker".to_string(), runtime.to_string()]
+    } else {
+        let args = borrow_var!(args);
+        let Some(list) = args.get_list() else {
+            strict::misuse("pxs_worker_spawn", "args is not a pxs_List");
+            return -1;
+        };
+        list.vars.iter().map(|arg| arg.get_string().unwrap_or_default()).collect()
+    };
+
+    with_feature!("script-worker", {
+        match shared::worker::spawn(&exe, &args) {
+            Ok(vtable) => start_backend(vtable, "worker".to_string()),
+            Err(e) => {
+                eprintln!("{e}");
+                -1
+            }
         }
+    }, {
+        let _ = (exe, args);
+        -1
     })
-    .into_raw()
 }
 
-/// Evaluate named code. This will return a `pxs_VarT`.
-/// 
-/// return:OWNED
+/// Serve the host as a script worker for `runtime` (a built in runtime or one registered with
+/// `pxs_register_backend` in this process). Call after `pxs_initialize` when started with `--pxs-worker <runtime>`.
+/// Requires the `script-worker` feature.
+///
+/// Returns a exit code once the host exits.
 #[unsafe(no_mangle)]
-pub extern "C" fn pxs_evalnamed(script: *const c_char, name: *const c_char, rt: pxs_Runtime) -> pxs_VarT {
-    pxs_debug!("pxs_evalnamed");
-    if script.is_null() || name.is_null() {
-        return pxs_Var::null_params_ep().into_raw();
+pub extern "C" fn pxs_worker_main(runtime: i32) -> i32 {
+    pxs_debug!("pxs_worker_main");
+    assert_initiated!();
+
+    with_feature!("script-worker", {
+        shared::worker::serve(runtime, worker_execute, worker_eval)
+    }, {
+        let _ = runtime;
+        1
+    })
+}
+
+/// Execute code for `pxs_worker_main`.
+#[allow(unused)]
+fn worker_execute(runtime: i32, code: &str, file_name: &str) -> pxs_Var {
+    if let Some(backend) = backend::get(runtime as i64) {
+        return backend.execute(code, file_name);
     }
+    let Some(rt) = enabled_runtime(runtime) else {
+        return pxs_Var::unkown_runtime_ep(runtime as i64);
+    };
+    let (Ok(code), Ok(file_name)) = (CString::new(code), CString::new(file_name)) else {
+        return pxs_Var::new_exception("code or file_name contain a nul byte");
+    };
+    let res = pxs_exec(rt, code.as_ptr(), file_name.as_ptr());
+    if res.is_null() { pxs_Var::new_null() } else { pxs_Var::from_raw(res) }
+}
 
-    let script = borrow_string!(script);
-    let name = borrow_string!(name);
+/// Evaluate code for `pxs_worker_main`.
+#[allow(unused)]
+fn worker_eval(runtime: i32, code: &str, name: &str) -> pxs_Var {
+    if let Some(backend) = backend::get(runtime as i64) {
+        return backend.eval(code, name);
+    }
+    let Some(rt) = enabled_runtime(runtime) else {
+        return pxs_Var::unkown_runtime_ep(runtime as i64);
+    };
+    let (Ok(code), Ok(name)) = (CString::new(code), CString::new(name)) else {
+        return pxs_Var::new_exception("code or name contain a nul byte");
+    };
+    pxs_Var::from_raw(pxs_evalnamed(code.as_ptr(), name.as_ptr(), rt))
+}
 
-    with_backend!(rt, Backend => {
-        let res = Backend::eval(script, name);
-        if res.is_err() {
-            pxs_Var::new_exception(res.unwrap_err())
-        } else {
-            res.unwrap()
-        }
-    }).into_raw()
+/// Set a observer for host object lifetimes. It gets a `pxs_ObjectEvent`, the type name and the object idx, so
+/// engines can mirror the objects scripts can see (i.e. in a editor inspector).
+///
+/// Events are sent when a object is first passed into a runtime, when its last reference is dropped and when it is
+/// freed.
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_set_object_observer(func: pxs_ObjectObserverFn) {
+    pxs_debug!("pxs_set_object_observer");
+    assert_initiated!();
+    set_object_observer(func);
 }
 
-/// Add a factory variable. This variable will be instantiated once at module startup.
+/// Set a hook called when a script GC collects the language object (the Lua table or Python instance) of a host object.
+/// It gets the type name and the host pointer, so engines can release GPU handles or file descriptors as soon as
+/// scripts drop a object instead of at exit.
 ///
-/// Args should not contain runtime. It gets added automatically.
+/// The hook runs during the GC, before the reference of the language object is dropped. It must not call into scripts.
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_set_object_destructor_hook(func: pxs_ObjectDestructorFn) {
+    pxs_debug!("pxs_set_object_destructor_hook");
+    assert_initiated!();
+    set_object_destructor_hook(func);
+}
+
+/// Free a PixelScript var.
 ///
-/// Basically does:
-/// ```python
-/// var_name = callback(args)
-/// ```
+/// You should only free results from `pxs_object_call`
 ///
-/// args:TRANSFER
-/// return:OWNED
+/// A var retained with `pxs_retain` is only freed once every reference is released, this drops one.
+///
+/// var:TRANSFER
 #[unsafe(no_mangle)]
-pub extern "C" fn pxs_newfactory(func: pxs_Func, args: *mut pxs_Var) -> pxs_VarT {
-    pxs_debug!("pxs_newfactory");
+pub extern "C" fn pxs_freevar(var: *mut pxs_Var) {
+    pxs_debug!("pxs_freevar");
     assert_initiated!();
 
-    if args.is_null() {
-        return pxs_Var::null_param_ep("args").into_raw();
-    }
-    // Check args is list
-    let var = pxs_Var::from_raw(args);
-    if !var.is_list() {
-        return ptr::null_mut();
+    if var.is_null() {
+        return;
     }
 
-    pxs_Var::new_factory(func, var).into_raw()
+    let _ = pxs_release(var);
 }
 
-/// Get the `_pxs_ptr` of a `pxs_HostObject`. And type check it against `type_id`.
-/// 
-/// if `type_id` < 0, no type checking is done.
-/// 
-/// runtime: BORROW
-/// var: BORROW
-/// return: BORROW
+/// Add a reference to `var`, so it (and the script value it holds) stays alive until `pxs_release` or
+/// `pxs_freevar` is called once more than `pxs_retain`. Returns the reference count, the owner being the first.
+///
+/// Retained vars must be freed with `pxs_release` or `pxs_freevar`, not transferred to another function.
+///
+/// var:BORROW
 #[unsafe(no_mangle)]
-pub extern "C" fn pxs_gettype(runtime: pxs_VarT, var: pxs_VarT, type_id: i32) -> *mut c_void {
-    pxs_debug!("pxs_gettype");
+pub extern "C" fn pxs_retain(var: pxs_VarT) -> u32 {
+    pxs_debug!("pxs_retain");
     assert_initiated!();
-    if runtime.is_null() || var.is_null() {
-        return ptr::null_mut();
-    }
-
-    let borrow_var = unsafe { pxs_Var::from_borrow(var) };
-
-    if borrow_var.is_object() {
-        // Check for ptr
-        let key = create_raw_string!(PXS_PTR_NAME);
-        let idx_var = pxs_objectget(runtime, var, key);
-        unsafe {
-            free_raw_string!(key);
-        }
-        if idx_var.is_null() {
-            return ptr::null_mut();
-        }
-
-        let idx_own = pxs_Var::from_raw(idx_var);
-        if type_id >= 0 {
-            let t = idx_own.get_pxs_type();
-            if t != type_id {
-                return ptr::null_mut();
-            }
-        }
-        let host_ptr = idx_own.get_host_ptr();
-
-        if host_ptr.is_null() {
-            pxs_debug!("Host pointer is null. The variable is: {:#?}", idx_own);
-        }
 
-        host_ptr
-    } else if borrow_var.is_i64() || borrow_var.is_u64() || borrow_var.is_host_object() {
-        if type_id >= 0 {
-            let t = borrow_var.get_pxs_type();
-            if t != type_id {
-                return ptr::null_mut();
-            }
-        }
-        borrow_var.get_host_ptr()
-    } else if borrow_var.is_factory() {
-        // Get the factory
-        let factory = borrow_var.get_factory().unwrap();
-        // Call the function
-        let res = factory.call(unsafe { pxs_Runtime::from_var_ptr(runtime) }.unwrap());
-        // Now we should have our object, so just call pxs_gettype on it again
-        let res_raw = res.into_raw();
-        let host = pxs_gettype(runtime, res_raw, type_id);
-        // Free res_raw
-        let _ = pxs_Var::from_raw(res_raw);
-        // return host
-        host
-    } else {
-        ptr::null_mut()
+    if var.is_null() {
+        strict::misuse("pxs_retain", "var is null");
+        return 0;
     }
+    pin::retain(var)
 }
 
-/// Get the HostPointer universally supported for:
-/// - Objects that have `_pxs_ptr` assigned.
-/// - Integers (signed and unsigned)
-/// - HostObjects
-/// - Factories (this will call it on the fly.)
-///
-/// All other types will return NULL.
+/// Drop a reference to `var`, freeing it when none are left. Returns the references left.
 ///
-/// runtime:BORROW
-/// var:BORROW
-/// return:BORROW
+/// var:TRANSFER
 #[unsafe(no_mangle)]
-pub extern "C" fn pxs_gethost(runtime: pxs_VarT, var: pxs_VarT) -> *mut c_void {
-    pxs_debug!("pxs_gethost");
-    pxs_gettype(runtime, var, -1)
+pub extern "C" fn pxs_release(var: pxs_VarT) -> u32 {
+    pxs_debug!("pxs_release");
+    assert_initiated!();
+
+    if var.is_null() {
+        strict::misuse("pxs_release", "var is null");
+        return 0;
+    }
+    let left = pin::release(var);
+    if left == 0 {
+        let _ = pxs_Var::from_raw(var);
+    }
+    left
 }
 
-/// Return a string rep of the `pxs_Var`.
-///
-/// String must be freed via `pxs_freestr`.
+/// The references to `var` (1 unless retained).
 ///
 /// var:BORROW
-/// return:OWNED
 #[unsafe(no_mangle)]
-pub extern "C" fn pxs_debugvar(var: pxs_VarT) -> *mut c_char {
-    pxs_debug!("pxs_debugvar");
+pub extern "C" fn pxs_refcount(var: pxs_VarT) -> u32 {
+    pxs_debug!("pxs_refcount");
     if var.is_null() {
-        create_raw_string!("NULL")
-    } else {
-        let borrow_var = unsafe { pxs_Var::from_borrow(var) };
-        let str = format!("{:#?}", borrow_var);
-        create_raw_string!(str)
+        return 0;
     }
+    pin::count(var)
 }
 
-/// Create a `pxs_Exception`.
-///
-/// return:OWNED
+/// Tells PixelScript that we are in a new thread.
 #[unsafe(no_mangle)]
-pub extern "C" fn pxs_newexception(msg: *const c_char) -> pxs_VarT {
-    pxs_debug!("pxs_newexception");
-    if msg.is_null() {
-        return pxs_Var::null_param_ep("msg").into_raw();
-    }
+pub extern "C" fn pxs_startthread() {
+    pxs_debug!("pxs_startthread");
+    assert_initiated!();
+    with_feature!("lua", {
+        LuaScripting::start_thread();
+    });
+    with_feature!("python", {
+        PythonScripting::start_thread();
+    });
+    with_feature!("js", {
+        JSScripting::start_thread();
+    });
+}
 
-    // Borrow msg
-    let bmsg = borrow_string!(msg);
-    pxs_Var::new_exception(bmsg.to_string().clone()).into_raw()
+/// Tells PixelScript that we just stopped the most recent thread.
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_stopthread() {
+    pxs_debug!("pxs_stopthread");
+    assert_initiated!();
+    with_feature!("lua", {
+        LuaScripting::stop_thread();
+    });
+    with_feature!("python", {
+        PythonScripting::stop_thread();
+    });
+    with_feature!("js", {
+        JSScripting::stop_thread();
+    });
 }
 
-/// Get a variable reference from its name
+/// Create a isolated script world. A context has its own runtimes, function and object lookups, modules and file
+/// readers, so hosts can run scripts per document or tenant side by side. Use the `_ctx` variants of `pxs_exec`,
+/// `pxs_eval`, `pxs_addmod`, `pxs_varcall`, `pxs_objectcall` and `pxs_freevar` with it.
 ///
-/// rt:BORROW
-/// return:OWNED
+/// Every context runs on its own thread, the `_ctx` functions wait for it. Host functions added to a context are
+/// called on that thread. Vars of a context's scripts must only be used with the same context.
+///
+/// Free with `pxs_context_free` before `pxs_finalize`.
 #[unsafe(no_mangle)]
-pub extern "C" fn pxs_var_fromname(rt: pxs_VarT, name: *const c_char) -> pxs_VarT {
-    pxs_debug!("pxs_var_fromname");
+pub extern "C" fn pxs_context_new() -> *mut pxs_Context {
+    pxs_debug!("pxs_context_new");
     assert_initiated!();
-    if name.is_null() {
-        return pxs_Var::null_param_ep("name").into_raw();
-    }
 
-    let bname = borrow_string!(name);
-    let runtime = unsafe { pxs_Runtime::from_var_ptr(rt) };
-    if let Some(runtime) = runtime {
-        with_backend!(runtime, Backend => {
-            let res = Backend::get_from_name(bname);
-            if res.is_err() {
-                pxs_Var::new_exception(res.unwrap_err().to_string())
-            } else {
-                res.unwrap()
-            }
-        })
-        .into_raw()
-    } else {
-        pxs_Var::unkown_runtime_var_ep(rt).into_raw()
-    }
+    pxs_Context::new(|| pxs_startthread(), stop_context).into_raw()
 }
 
-/// Remove a item from a list at a specific index.
-///
-/// Returns true for success, false for failed.
-/// 
-/// This will automatically call `pxs_freevar` on the found item.
-///
-/// list:BORROW
+/// Drop the state of a context thread before it ends.
+fn stop_context() {
+    registry::clear();
+    promise::clear();
+    with_feature!("pxs_events", {
+        pxs_core::pxs_events::clear();
+    });
+    clear_object_lookup();
+    clear_function_lookup();
+    pxs_stopthread();
+}
+
+/// Free a context made with `pxs_context_new`, stopping its runtimes.
 #[unsafe(no_mangle)]
-pub extern "C" fn pxs_listdel(list: pxs_VarT, index: i32) -> bool {
-    pxs_debug!("pxs_listdel");
-    if list.is_null() {
-        return false;
+pub extern "C" fn pxs_context_free(ctx: *mut pxs_Context) {
+    pxs_debug!("pxs_context_free");
+    if ctx.is_null() {
+        return;
     }
+    let _ = pxs_Context::from_raw(ctx);
+}
 
-    let var = borrow_var!(list);
-    if !var.is_list() {
-        return false;
+/// Run `job` in `ctx`. A exception var if `ctx` is null or the job panicked.
+fn in_context(ctx: *mut pxs_Context, job: impl FnOnce() -> pxs_VarT + Send + 'static) -> pxs_VarT {
+    if ctx.is_null() {
+        return pxs_Var::null_param_ep("ctx").into_raw();
     }
+    let ctx = unsafe { &*ctx };
+    // Raw vars are not Send, they are passed back as a address.
+    match ctx.run(move || job() as usize) {
+        Some(res) => res as pxs_VarT,
+        None => pxs_Var::new_exception("Context job panicked.").into_raw(),
+    }
+}
 
-    // Ok so it's a List and it's not null! Lets delete the item
-    let var_list = var.get_list().unwrap();
-    var_list.del_item(index)
+/// Copy a C string for a context job.
+fn context_string(ptr: *const c_char) -> Option<CString> {
+    if ptr.is_null() { None } else { Some(unsafe { CStr::from_ptr(ptr) }.to_owned()) }
 }
 
-/// Do a Shallow Copy. Which means it gets the same data without get the deleter for (pxs_Object or pxs_Function).
-///
-/// Memory is owned by caller.
-///
-/// var:BORROW
-/// return:OWNED
+/// Set the file and directory readers (NULLABLE) of a context. Without them the global ones are used.
 #[unsafe(no_mangle)]
-pub extern "C" fn pxs_new_shallowcopy(var: pxs_VarT) -> pxs_VarT {
-    pxs_debug!("pxs_new_shallowcopy");
-    if var.is_null() {
-        return pxs_Var::null_param_ep("var").into_raw();
+pub extern "C" fn pxs_context_setreaders(
+    ctx: *mut pxs_Context,
+    file_reader: Option<pxs_LoadFileFn>,
+    dir_reader: Option<pxs_ReadDirFn>,
+) {
+    pxs_debug!("pxs_context_setreaders");
+    if ctx.is_null() {
+        strict::misuse("pxs_context_setreaders", "ctx is null");
+        return;
     }
+    let ctx = unsafe { &*ctx };
+    let _ = ctx.run(move || set_thread_file_readers(file_reader, dir_reader));
+}
 
-    // Apply a shallow copy.
-    let bvar = borrow_var!(var);
-    bvar.shallow_copy().into_raw()
+/// `pxs_freevar` in a context. Script vars must be freed in the context they came from.
+///
+/// var:TRANSFER
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_freevar_ctx(ctx: *mut pxs_Context, var: pxs_VarT) {
+    pxs_debug!("pxs_freevar_ctx");
+    let var = Sendable::new(var);
+    let _ = in_context(ctx, move || {
+        pxs_freevar(var.get());
+        ptr::null_mut()
+    });
 }
 
-/// Compile a code string into a code object for later execution.
+/// `pxs_exec` in a context.
 ///
-/// Pass in a optional gloabl scope (or null for default). Scope ownership is transferred.
-/// Returns a `pxs_Var` whichs memory is handled by the caller.
-///
-/// Resulting `pxs_Var` will contain (Associated Runtime, Code Object, Scope|default).
-///
-/// global_scope:TRANSFER
+/// code:BORROW
+/// file_name:BORROW
 /// return:OWNED
 #[unsafe(no_mangle)]
-pub extern "C" fn pxs_compile(
+pub extern "C" fn pxs_exec_ctx(
+    ctx: *mut pxs_Context,
     runtime: pxs_Runtime,
     code: *const c_char,
-    global_scope: pxs_VarT,
+    file_name: *const c_char,
 ) -> pxs_VarT {
-    pxs_debug!("pxs_compile");
-    assert_initiated!();
-
-    if code.is_null() || global_scope.is_null() {
+    pxs_debug!("pxs_exec_ctx");
+    let (Some(code), Some(file_name)) = (context_string(code), context_string(file_name)) else {
         return pxs_Var::null_params_ep().into_raw();
-    }
+    };
+    in_context(ctx, move || pxs_exec(runtime, code.as_ptr(), file_name.as_ptr()))
+}
 
-    let rcode = borrow_string!(code);
-    let scope = own_var!(global_scope);
+/// `pxs_eval` in a context.
+///
+/// script:BORROW
+/// return:OWNED
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_eval_ctx(ctx: *mut pxs_Context, script: *const c_char, rt: pxs_Runtime) -> pxs_VarT {
+    pxs_debug!("pxs_eval_ctx");
+    let Some(script) = context_string(script) else {
+        return pxs_Var::null_param_ep("script").into_raw();
+    };
+    in_context(ctx, move || pxs_eval(script.as_ptr(), rt))
+}
 
-    // Make sure scope is map or is null.
-    if !scope.is_map() && !scope.is_null() {
-        return pxs_Var::new_exception("Global scope must be a Map or Null".to_string()).into_raw();
+/// `pxs_addmod` in a context.
+///
+/// module_ptr:TRANSFER
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_addmod_ctx(ctx: *mut pxs_Context, module_ptr: *mut pxs_Module) {
+    pxs_debug!("pxs_addmod_ctx");
+    if ctx.is_null() || module_ptr.is_null() {
+        strict::misuse("pxs_addmod_ctx", "ctx or module_ptr is null");
+        return;
     }
-
-    let res = with_backend!(runtime, Backend => {
-        let res = Backend::compile(rcode, scope);
-        if res.is_err() {
-            pxs_Var::new_exception(res.unwrap_err().to_string())
-        } else {
-            res.unwrap()
-        }
+    let module = Sendable::new(module_ptr);
+    let _ = in_context(ctx, move || {
+        pxs_addmod(module.get());
+        ptr::null_mut()
     });
-
-    if !res.is_list() {
-        return res.into_raw();
-    }
-
-    // Add the runtime to the object.
-    let list = res.get_list().unwrap();
-    list.insert_item(0, runtime.into_var());
-
-    res.into_raw()
 }
 
-/// Execute a compiled code object.
+/// `pxs_varcall` in a context.
 ///
-/// Variable ownership is transfered. If this is not desired behavior, pass in a shallow copy.
-/// Returned variable must be freed by caller.
+/// runtime:BORROW
+/// var_func:BORROW
+/// args:TRANSFER
+/// return:OWNED
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_varcall_ctx(
+    ctx: *mut pxs_Context,
+    runtime: pxs_VarT,
+    var_func: pxs_VarT,
+    args: pxs_VarT,
+) -> pxs_VarT {
+    pxs_debug!("pxs_varcall_ctx");
+    let vars = Sendable::new((runtime, var_func, args));
+    in_context(ctx, move || {
+        let (runtime, var_func, args) = vars.get();
+        pxs_varcall(runtime, var_func, args)
+    })
+}
+
+/// `pxs_objectcall` in a context.
 ///
-/// Runtime is not required because the object is embedded with it in pxs_compile.
-/// Pass in a optional local scope that gets passed along with the global scope.
-/// Note: Do not use the same scope as in `pxs_compile`.
+/// runtime:BORROW
+/// var:BORROW
+/// args:TRANSFER
+/// return:OWNED
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_objectcall_ctx(
+    ctx: *mut pxs_Context,
+    runtime: pxs_VarT,
+    var: pxs_VarT,
+    method: *const c_char,
+    args: pxs_VarT,
+) -> pxs_VarT {
+    pxs_debug!("pxs_objectcall_ctx");
+    let Some(method) = context_string(method) else {
+        return pxs_Var::null_param_ep("method").into_raw();
+    };
+    let vars = Sendable::new((runtime, var, args));
+    in_context(ctx, move || {
+        let (runtime, var, args) = vars.get();
+        pxs_objectcall(runtime, var, method.as_ptr(), args)
+    })
+}
+
+/// Start a script thread: a context (see `pxs_context_new`) running code in parallel with the host. The modules added
+/// with `pxs_addmod` on this thread are added to it, their functions are called on the script thread.
 ///
-/// Scope ownership is transferred.
+/// Only data variables of the modules are copied, Objects and Functions stay on this thread. Host functions are shared
+/// with their opaque as is, so the opaques of functions called from several threads must be thread safe.
 ///
-/// object : TRANSFER
-/// local  : TRANSFER
-/// return : OWNED
+/// Join with `pxs_thread_join` before `pxs_finalize`.
 #[unsafe(no_mangle)]
-pub extern "C" fn pxs_execobject(object: pxs_VarT, local: pxs_VarT) -> pxs_VarT {
-    pxs_debug!("pxs_execobject");
+pub extern "C" fn pxs_thread_spawn() -> *mut pxs_Thread {
+    pxs_debug!("pxs_thread_spawn");
     assert_initiated!();
 
-    if object.is_null() || local.is_null() {
-        return pxs_Var::null_params_ep().into_raw();
-    }
-
-    let var = own_var!(object);
+    pxs_Thread::new(|| pxs_startthread(), stop_context).into_raw()
+}
 
-    if !var.is_list() {
-        return pxs_Var::incorrect_type_ep(pxs_VarType::pxs_List, var.tag).into_raw();
+/// Queue code in a script thread without waiting for it. Code runs in the order it was queued. Returns false if
+/// `thread` or a string is null.
+///
+/// code:BORROW
+/// file_name:BORROW
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_thread_exec(
+    thread: *mut pxs_Thread,
+    runtime: pxs_Runtime,
+    code: *const c_char,
+    file_name: *const c_char,
+) -> bool {
+    pxs_debug!("pxs_thread_exec");
+    let (Some(code), Some(file_name)) = (context_string(code), context_string(file_name)) else {
+        strict::misuse("pxs_thread_exec", "code or file_name is null");
+        return false;
+    };
+    if thread.is_null() {
+        strict::misuse("pxs_thread_exec", "thread is null");
+        return false;
     }
+    let thread = unsafe { &*thread };
+    thread.exec(move || pxs_exec(runtime, code.as_ptr(), file_name.as_ptr()))
+}
 
-    // Get scope
-    let scope = own_var!(local);
-    // Check if scope is a map
-    if !scope.is_map() && !scope.is_null() {
-        return pxs_Var::incorrect_types_ep(
-            vec![pxs_VarType::pxs_Map, pxs_VarType::pxs_Null],
-            scope.tag,
-        )
-        .into_raw();
+/// Wait until the queued code of a script thread ran, then stop and free it. Returns the first error, null if all
+/// code ran without one.
+///
+/// thread:TRANSFER
+/// return:OWNED
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_thread_join(thread: *mut pxs_Thread) -> pxs_VarT {
+    pxs_debug!("pxs_thread_join");
+    if thread.is_null() {
+        return pxs_Var::null_param_ep("thread").into_raw();
     }
-
-    let list = var.get_list().unwrap();
-    let rt = list.get_item(0);
-    if let Some(rt) = rt {
-        let runtime = pxs_Runtime::from_var(rt);
-        if let Some(runtime) = runtime {
-            // Now we can do stuff
-            with_backend!(runtime, Backend => {
-                let res = Backend::exec_object(var, scope);
-                if res.is_err() {
-                    pxs_Var::new_exception(res.unwrap_err().to_string())
-                } else {
-                    res.unwrap()
-                }
-            })
-            .into_raw()
-        } else {
-            pxs_Var::new_exception(format!("Unkown runtime: {:#?}", rt)).into_raw()
-        }
-    } else {
-        pxs_Var::new_exception("Code Object list is empty").into_raw()
+    match pxs_Thread::from_raw(thread).join() {
+        Some(error) => error.into_raw(),
+        None => ptr::null_mut(),
     }
 }
 
-/// Create a new `pxs_Map`
+/// Clear the current threads state for all languages.
 ///
-/// return:OWNED
+/// Optionally, if you want to run the garbage collector.
 #[unsafe(no_mangle)]
-pub extern "C" fn pxs_newmap() -> pxs_VarT {
-    pxs_debug!("pxs_newmap");
-    pxs_Var::new_map().into_raw()
+pub extern "C" fn pxs_clear() {
+    pxs_debug!("pxs_clear");
+    assert_initiated!();
+    // Drop function lookup
+    clear_function_lookup();
+    // Drop object lookup
+    clear_object_lookup();
+
+    with_feature!("lua", {
+        LuaScripting::clear();
+    });
+    with_feature!("python", {
+        PythonScripting::clear();
+    });
+    with_feature!("js", {
+        JSScripting::clear();
+    })
 }
 
-/// Add a new key (`pxs_Var`) value (`pxs_Var`) pair in a map.
+/// Call a method within a specifed runtime.
 ///
-/// Keys can only be:
-/// - `pxs_String`
-/// - `pxs_Int64`
-/// - `pxs_UInt64`
-/// - `pxs_Float64`
-/// - `pxs_Bool`
+/// Runtime is a `pxs_Var`.
 ///
-/// Key and value ownership are transfered.
+/// Transfers ownership of args.
 ///
-/// map:BORROW
-/// key:TRANSFER
-/// value:TRANSFER
+/// runtime:BORROW
+/// args:TRANSFER
+/// return:OWNED
 #[unsafe(no_mangle)]
-pub extern "C" fn pxs_map_addpair(map: pxs_VarT, key: pxs_VarT, value: pxs_VarT) {
-    pxs_debug!("pxs_map_addpair");
-    if map.is_null() || key.is_null() || value.is_null() {
-        // TODO: error
-        return;
+pub extern "C" fn pxs_call(
+    runtime: *mut pxs_Var,
+    method: *const c_char,
+    args: *mut pxs_Var,
+) -> *mut pxs_Var {
+    pxs_debug!("pxs_call");
+    assert_initiated!();
+
+    if runtime.is_null() || method.is_null() || args.is_null() {
+        return pxs_Var::null_params_ep().into_raw();
     }
 
-    // Check map is actually a map
-    let map = borrow_var!(map);
-    if !map.is_map() {
-        // TODO: error
-        return;
+    let runtime_var = unsafe { pxs_Var::from_borrow(runtime) };
+    let runtime_id = pxs_getint(runtime_var);
+
+    // Borrow runtime, var, and method, and argv
+    let runtime_borrow = pxs_Runtime::from_i64(runtime_id);
+    let method_borrow = borrow_string!(method);
+    // Own args
+    let args = own_var!(args);
+    if !args.is_list() {
+        return pxs_Var::incorrect_type_ep(pxs_VarType::pxs_List, args.tag).into_raw();
     }
+    let list = args.get_list().unwrap();
 
-    // Check key is valid pair
-    let key = own_var!(key);
-    if key.is_string() || key.is_i64() || key.is_bool() || key.is_u64() || key.is_f64() {
-        let internal = map.get_map().unwrap();
-        internal.add_item(key, own_var!(value));
+    // Get runtime
+    if let Some(rt) = runtime_borrow {
+        with_backend!(rt, Backend => {
+            let res = Backend::call_method(method_borrow, list);
+            if res.is_err() {
+                pxs_Var::new_exception(res.unwrap_err().to_string())
+            } else {
+                budget::checked(res.unwrap())
+            }
+        })
+        .into_raw()
     } else {
-        // TODO: error
-        return;
+        pxs_Var::unkown_runtime_ep(runtime_id).into_raw()
     }
 }
 
-/// Remove a value (`pxs_Var`) from a map based on it's key (`pxs_Var`).
+/// Call a ToString method on this Var. If already a string, it won't call it.
 ///
-/// map:BORROW
-/// key:BORROW
+/// Host must free this memory with `pxs_free_var`
+///
+/// runtime_var:BORROW
+/// var:BORROW
+/// return:OWNED
 #[unsafe(no_mangle)]
-pub extern "C" fn pxs_map_delitem(map: pxs_VarT, key: pxs_VarT) {
-    pxs_debug!("pxs_map_delitem");
-    if map.is_null() || key.is_null() {
-        // TODO: error
-        return;
-    }
+pub extern "C" fn pxs_tostring(runtime_var: *mut pxs_Var, var: *mut pxs_Var) -> *mut pxs_Var {
+    pxs_debug!("pxs_tostring");
+    assert_initiated!();
 
-    let map = borrow_var!(map);
-    if !map.is_map() {
-        // TODO: error
-        return;
+    if var.is_null() || runtime_var.is_null() {
+        return pxs_Var::null_params_ep().into_raw();
     }
 
-    let key = borrow_var!(key);
-    let internal = map.get_map().unwrap();
-    internal.del_item(key);
+    // Borrow
+    let b_var = unsafe { pxs_Var::from_borrow(var) };
+
+    // If string or primative, no object calling needed
+    match b_var.tag {
+        pxs_VarType::pxs_Int64 => {
+            let val = b_var.get_i64().unwrap();
+            return pxs_Var::new_string(val.to_string()).into_raw();
+        }
+        pxs_VarType::pxs_UInt64 => {
+            let val = b_var.get_u64().unwrap();
+            return pxs_Var::new_string(val.to_string()).into_raw();
+        }
+        pxs_VarType::pxs_String | pxs_VarType::pxs_Exception => {
+            return pxs_Var::new_string(b_var.get_string().unwrap().clone()).into_raw();
+        }
+        pxs_VarType::pxs_Bool => {
+            return pxs_Var::new_string(b_var.get_bool().unwrap().to_string()).into_raw();
+        }
+        pxs_VarType::pxs_Float64 => {
+            return pxs_Var::new_string(b_var.get_f64().unwrap().to_string()).into_raw();
+        }
+        pxs_VarType::pxs_Int32 | pxs_VarType::pxs_UInt32 | pxs_VarType::pxs_Float32 => {
+            return pxs_Var::new_string(unsafe { b_var.dbg() }).into_raw();
+        }
+        pxs_VarType::pxs_Null => {
+            return pxs_Var::new_string("null".to_string()).into_raw();
+        }
+        _ => {
+            // Do nothing
+        }
+    }
+
+    // Not a string, so let's convert
+    let runtime = unsafe { pxs_Runtime::from_var_ptr(runtime_var) };
+    if let Some(runtime) = runtime {
+        let args = pxs_Var::new_list();
+        let list = args.get_list().unwrap();
+        list.add_item(b_var.shallow_copy());
+        let res = match runtime {
+            pxs_Runtime::pxs_Lua => {
+                with_feature!("lua", { LuaScripting::call_method("tostring", list) }, {
+                    return pxs_Var::feature_not_enabled_ep("lua").into_raw();
+                })
+            }
+            pxs_Runtime::pxs_Python => {
+                with_feature!("python", { PythonScripting::call_method("str", list) }, {
+                    return pxs_Var::feature_not_enabled_ep("python").into_raw();
+                })
+            }
+            pxs_Runtime::pxs_JavaScript => {
+                with_feature!(
+                    "js",
+                    {
+                        // Check variable is null.
+                        if pxs_varis(var, pxs_VarType::pxs_Null) {
+                            return pxs_Var::new_string("null".to_owned()).into_raw();
+                        }
+
+                        let mut empty_list = pxs_VarList::new();
+                        JSScripting::object_call(b_var, "toString", &mut empty_list)
+                    },
+                    {
+                        return pxs_Var::feature_not_enabled_ep("js").into_raw();
+                    }
+                )
+            }
+            pxs_Runtime::pxs_Wren => {
+                todo!()
+            }
+        };
+
+        if let Ok(res) = res {
+            res.into_raw()
+        } else {
+            pxs_Var::new_exception(res.unwrap_err().to_string()).into_raw()
+        }
+    } else {
+        pxs_Var::unkown_runtime_var_ep(runtime_var).into_raw()
+    }
+}
+
+/// Create a new pxs_VarList.
+///
+/// This does not take any arguments. To add to a list, you must call `pxs_var_list_add(ptr, item)`
+///
+/// return:OWNED
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_newlist() -> *mut pxs_Var {
+    pxs_debug!("pxs_newlist");
+    assert_initiated!();
+
+    pxs_Var::new_list().into_raw()
+}
+
+/// Add a item to a pxs_VarList.
+///
+/// Expects a pointer to pxs_VarList. And a pointer for the item to add (pxs_Var*)
+///
+/// This will take ownership of the added item. If you want to copy it instead first create a new `pxs_Var` with `pxs_var_newcopy(item)`
+///
+/// Will return the index added at.
+///
+/// list:BORROW
+/// item:TRANSFER
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_listadd(list: *mut pxs_Var, item: *mut pxs_Var) -> i32 {
+    pxs_debug!("pxs_listadd");
+    assert_initiated!();
+
+    // Check null
+    if list.is_null() || item.is_null() {
+        strict::misuse("pxs_listadd", "list or item is null");
+        return -1;
+    }
+
+    // Borrow list
+    let borrow_list = unsafe { pxs_Var::from_borrow(list) };
+    // Check that this is actually a list
+    if !borrow_list.is_list() {
+        strict::misuse("pxs_listadd", "list is not a list");
+        return -1;
+    }
+
+    // and own item
+    let owned_item = pxs_Var::from_raw(item);
+
+    // Get varlist and add item
+    let varlist = borrow_list.get_list().unwrap();
+    varlist.add_item(owned_item);
+
+    // Return index
+    (varlist.vars.len() - 1) as i32
+}
+
+/// Get a item from a pxs_VarList.
+///
+/// Expcts a pointer to pxs_VarList. And a index of i32. Supports negative indexes just like in Python.
+///
+/// This will NOT return a cloned variable, you must NOT free it.
+///
+/// list:BORROW
+/// return:BORROW&NULLABLE
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_listget(list: *mut pxs_Var, index: i32) -> *mut pxs_Var {
+    pxs_debug!("pxs_listget");
+    assert_initiated!();
+
+    if list.is_null() {
+        return ptr::null_mut();
+    }
+
+    // Get list
+    let borrow_list = unsafe { pxs_Var::from_borrow(list) };
+    if !borrow_list.is_list() {
+        return pxs_Var::incorrect_type_ep(pxs_VarType::pxs_List, borrow_list.tag).into_raw();
+    }
+
+    // Derefernce list and get the item.
+    let varlist = borrow_list.get_list().unwrap();
+    if let Some(res) = varlist.get_item(index) {
+        res as *const pxs_Var as *mut pxs_Var
+    } else {
+        return ptr::null_mut();
+    }
+}
+
+/// Set a item at a specific index in a pxs_VarList.
+///
+/// Expects a pointer to pxs_VarList, a index of i32, and a pxs_Var. Supports negative indexes jsut like in Python.
+///
+/// Will take ownership of the pxs_Var.
+///
+/// This will return a boolean for success = true, or failure = false.
+///
+/// list:BORROW
+/// item:TRANSFER
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_listset(list: *mut pxs_Var, index: i32, item: *mut pxs_Var) -> bool {
+    pxs_debug!("pxs_listset");
+    assert_initiated!();
+
+    if list.is_null() || item.is_null() {
+        strict::misuse("pxs_listset", "list or item is null");
+        return false;
+    }
+
+    // Borrow list
+    let borrow_list = unsafe { pxs_Var::from_borrow(list) };
+    // Check if actually list
+    if !borrow_list.is_list() {
+        strict::misuse("pxs_listset", "list is not a list");
+        return false;
+    }
+
+    // Get list
+    let list = borrow_list.get_list().unwrap();
+    // Own item
+    let owned_item = pxs_Var::from_raw(item);
+    // Set in list
+    match list.set_item(owned_item, index) {
+        Ok(()) => true,
+        Err(e) => {
+            strict::misuse("pxs_listset", &e);
+            false
+        }
+    }
+}
+
+/// Get length of a pxs_VarList.
+///
+/// Expects a pointer to a pxs_VarList
+///
+/// list:BORROW
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_listlen(list: *mut pxs_Var) -> i32 {
+    pxs_debug!("pxs_listlen");
+    assert_initiated!();
+
+    if list.is_null() {
+        return -1;
+    }
+
+    let borrow_list = unsafe { pxs_Var::from_borrow(list) };
+    if !borrow_list.is_list() {
+        return -1;
+    }
+
+    let list = borrow_list.get_list().unwrap();
+
+    list.vars.len() as i32
+}
+
+/// Call a `pxs_Var`s function.
+///
+/// Expects runtime var, var function, and args that is a List. If runtime is null the runtime that produced
+/// `var_func` is used, passing a different runtime is a `runtime_mismatch` exception.
+///
+/// Transfers ownership of args.
+///
+/// runtime:BORROW
+/// var_func:BORROW
+/// args:TRANSFER
+/// return:OWNED
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_varcall(
+    runtime: *mut pxs_Var,
+    var_func: *mut pxs_Var,
+    args: *mut pxs_Var,
+) -> *mut pxs_Var {
+    pxs_debug!("pxs_varcall");
+    assert_initiated!();
+
+    if var_func.is_null() || args.is_null() {
+        return pxs_Var::null_params_ep().into_raw();
+    }
+
+    // Get the function pointer
+    let borrow_func = borrow_var!(var_func);
+    // Check if function
+    if !borrow_func.is_function() {
+        return pxs_Var::incorrect_type_ep(pxs_VarType::pxs_Function, borrow_func.tag).into_raw();
+    }
+    #[cfg(feature = "pxs-debug")]
+    if borrow_func.is_foreign() {
+        return pxs_Var::foreign_var_ep().into_raw();
+    }
+
+    // own args
+    let args = own_var!(args);
+    // Check it's a list
+    if !args.is_list() {
+        return ptr::null_mut();
+    }
+    if let Some(e) = origin_mismatch(runtime, borrow_func) {
+        return e.into_raw();
+    }
+    if let Some(backend) = custom_backend(runtime, borrow_func) {
+        return budget::checked(backend.call(borrow_func, &args)).into_raw();
+    }
+    let list = args.get_list().unwrap();
+
+    // Match the runtime, or use the runtime that produced the function.
+    let rt = if runtime.is_null() {
+        match borrow_func.get_origin() {
+            Some(runtime) => Some(runtime),
+            None => return pxs_Var::null_param_ep("runtime").into_raw(),
+        }
+    } else {
+        unsafe { pxs_Runtime::from_var_ptr(runtime) }
+    };
+    if let Some(runtime) = rt {
+        with_backend!(runtime, Backend => {
+            let res = Backend::var_call(borrow_func, list);
+            if res.is_err() {
+                pxs_Var::new_exception(res.unwrap_err().to_string())
+            } else {
+                budget::checked(res.unwrap())
+            }
+        })
+        .into_raw()
+    } else {
+        pxs_Var::unkown_runtime_var_ep(runtime).into_raw()
+    }
+}
+
+/// Run a host invoked `call` and turn its result into a `pxs_CallResult`. Lua errors get a stack traceback.
+fn try_call(runtime: Option<pxs_Runtime>, call: impl FnOnce() -> pxs_VarT) -> pxs_CallResult {
+    let res = with_feature!("lua", { crate::lua::with_traceback(call) }, { call() });
+    let res = if res.is_null() { pxs_Var::new_null() } else { pxs_Var::from_raw(res) };
+    if !res.is_exception() {
+        return pxs_CallResult { ok: true, value: res.into_raw(), error: ptr::null_mut() };
+    }
+
+    let msg = res.get_string().unwrap_or_default();
+    let error = match runtime {
+        Some(runtime) => pxs_Error::from_runtime(&runtime, "", &msg),
+        None => {
+            let mut error = pxs_Error::new(pxs_ErrorCode::pxs_RuntimeError, &msg);
+            error.traceback = create_raw_string!(msg);
+            error
+        }
+    };
+    pxs_CallResult { ok: false, value: ptr::null_mut(), error: error.into_raw() }
+}
+
+/// Same as `pxs_call`, but failures return the script error with its traceback instead of a exception var.
+///
+/// runtime:BORROW
+/// args:TRANSFER
+/// return:OWNED
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_call_try(runtime: pxs_VarT, method: *const c_char, args: pxs_VarT) -> pxs_CallResult {
+    pxs_debug!("pxs_call_try");
+    let rt = unsafe { pxs_Runtime::from_var_ptr(runtime) };
+    try_call(rt, || pxs_call(runtime, method, args))
+}
+
+/// Same as `pxs_objectcall`, but failures return the script error with its traceback instead of a exception var.
+///
+/// runtime:BORROW
+/// var:BORROW
+/// args:TRANSFER
+/// return:OWNED
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_objectcall_try(
+    runtime: pxs_VarT,
+    var: pxs_VarT,
+    method: *const c_char,
+    args: pxs_VarT,
+) -> pxs_CallResult {
+    pxs_debug!("pxs_objectcall_try");
+    let rt = if var.is_null() { None } else { object_runtime(runtime, borrow_var!(var)).ok() };
+    try_call(rt, || pxs_objectcall(runtime, var, method, args))
+}
+
+/// Same as `pxs_varcall`, but failures return the script error with its traceback instead of a exception var.
+///
+/// runtime:BORROW
+/// var_func:BORROW
+/// args:TRANSFER
+/// return:OWNED
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_varcall_try(runtime: pxs_VarT, var_func: pxs_VarT, args: pxs_VarT) -> pxs_CallResult {
+    pxs_debug!("pxs_varcall_try");
+    let rt = if var_func.is_null() { None } else { object_runtime(runtime, borrow_var!(var_func)).ok() };
+    try_call(rt, || pxs_varcall(runtime, var_func, args))
+}
+
+/// Call a `pxs_Var`s function that belongs to a mod.
+///
+/// Works like `pxs_varcall` but tracks errors per mod. Once a mod throws `pxs_quarantine_setthreshold` times in a row
+/// it is quarantined: the `pxs_quarantine_setcallback` callback is called once, and further calls return null
+/// without running the function. Use this for a mod's event handlers and timers so one broken mod can not spam errors.
+///
+/// mod_name:BORROW
+/// runtime:BORROW
+/// var_func:BORROW
+/// args:TRANSFER
+/// return:OWNED
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_modcall(
+    mod_name: *const c_char,
+    runtime: *mut pxs_Var,
+    var_func: *mut pxs_Var,
+    args: *mut pxs_Var,
+) -> *mut pxs_Var {
+    pxs_debug!("pxs_modcall");
+    assert_initiated!();
+
+    if mod_name.is_null() {
+        if !args.is_null() {
+            pxs_freevar(args);
+        }
+        return pxs_Var::null_param_ep("mod_name").into_raw();
+    }
+    let mod_name = borrow_string!(mod_name);
+
+    if quarantine::is_quarantined(mod_name) {
+        if !args.is_null() {
+            pxs_freevar(args);
+        }
+        return pxs_Var::new_null().into_raw();
+    }
+
+    let res = pxs_varcall(runtime, var_func, args);
+    quarantine::record(mod_name, borrow_var!(res));
+    res
+}
+
+/// Report the result of running a mod's script that was not called through `pxs_modcall` (i.e. `pxs_exec`).
+///
+/// A exception counts as a error, anything else resets the mods errors. Returns true if the mod is quarantined.
+///
+/// mod_name:BORROW
+/// result:BORROW
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_mod_report(mod_name: *const c_char, result: *mut pxs_Var) -> bool {
+    pxs_debug!("pxs_mod_report");
+    assert_initiated!();
+
+    if mod_name.is_null() {
+        return false;
+    }
+    let mod_name = borrow_string!(mod_name);
+    if result.is_null() {
+        // Null means no error.
+        return quarantine::record(mod_name, &pxs_Var::new_null());
+    }
+    quarantine::record(mod_name, borrow_var!(result))
+}
+
+/// Is this mod quarantined?
+///
+/// mod_name:BORROW
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_mod_isquarantined(mod_name: *const c_char) -> bool {
+    pxs_debug!("pxs_mod_isquarantined");
+    assert_initiated!();
+
+    if mod_name.is_null() {
+        return false;
+    }
+    quarantine::is_quarantined(borrow_string!(mod_name))
+}
+
+/// Take a mod out of quarantine and reset its errors.
+///
+/// mod_name:BORROW
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_mod_release(mod_name: *const c_char) {
+    pxs_debug!("pxs_mod_release");
+    assert_initiated!();
+
+    if mod_name.is_null() {
+        return;
+    }
+    quarantine::release(borrow_string!(mod_name));
+}
+
+/// Set how many errors in a row quarantine a mod. Defaults to 5, minimum is 1.
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_quarantine_setthreshold(threshold: u32) {
+    pxs_debug!("pxs_quarantine_setthreshold");
+    assert_initiated!();
+    set_quarantine_threshold(threshold);
+}
+
+/// Set the callback for when a mod gets quarantined.
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_quarantine_setcallback(func: pxs_QuarantineFn) {
+    pxs_debug!("pxs_quarantine_setcallback");
+    assert_initiated!();
+    set_on_quarantine(func);
+}
+
+/// Copy the pxs_Var.
+///
+/// Memory is handled by caller. Objects and Functions move their deleter to the copy. Never returns on a list that
+/// contains itself, use `pxs_deepcopy` for values that are not trusted.
+///
+/// item:BORROW
+/// return:OWNED
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_newcopy(item: *mut pxs_Var) -> *mut pxs_Var {
+    pxs_debug!("pxs_newcopy");
+    assert_initiated!();
+
+    if item.is_null() {
+        return pxs_Var::null_param_ep("item").into_raw();
+    }
+
+    // Borrow var
+    let borrow_item = borrow_var!(item);
+
+    // Clone the var
+    let cloned_var = borrow_item.clone();
+    cloned_var.into_raw()
+}
+
+/// Copy the pxs_Var and everything in it, leaving the original untouched.
+///
+/// `max_depth` limits how deep lists and maps may be nested, negative for no limit. Returns a exception if `item`
+/// contains itself (i.e. a list added to itself), is nested deeper than `max_depth` or holds Objects, Functions or
+/// Factories.
+///
+/// item:BORROW
+/// return:OWNED
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_deepcopy(item: pxs_VarT, max_depth: i32) -> pxs_VarT {
+    pxs_debug!("pxs_deepcopy");
+    assert_initiated!();
+
+    if item.is_null() {
+        return pxs_Var::null_param_ep("item").into_raw();
+    }
+
+    match deepcopy::deep_copy(borrow_var!(item), max_depth) {
+        Ok(copy) => copy,
+        Err(err) => pxs_Var::new_exception(err),
+    }
+    .into_raw()
+}
+
+/// Call a objects getter.
+///
+/// runtime:BORROW
+/// obj:BORROW
+/// return:OWNED
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_objectget(runtime: pxs_VarT, obj: pxs_VarT, key: *const c_char) -> pxs_VarT {
+    pxs_debug!("pxs_objectget");
+    assert_initiated!();
+    if runtime.is_null() || obj.is_null() || key.is_null() {
+        return pxs_Var::null_params_ep().into_raw();
+    }
+
+    // Borrow var
+    let borrow_obj = borrow_var!(obj);
+    let borrow_key = borrow_string!(key);
+    if let Some(backend) = custom_backend(runtime, borrow_obj) {
+        return backend.object_get(borrow_obj, borrow_key).into_raw();
+    }
+    let borrow_rt = unsafe { pxs_Runtime::from_var_ptr(runtime).unwrap() };
+
+    with_backend!(borrow_rt, Backend => {
+        let res = Backend::get(borrow_obj, borrow_key);
+        if res.is_err() {
+            pxs_Var::new_exception(res.unwrap_err().to_string())
+        } else {
+            res.unwrap()
+        }
+    })
+    .into_raw()
+}
+
+/// Call a objects setter.
+///
+/// value ownership is transfered.
+///
+/// runtime:BORROW
+/// obj:BORROW
+/// value:TRANSFER
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_objectset(
+    runtime: pxs_VarT,
+    obj: pxs_VarT,
+    key: *const c_char,
+    value: pxs_VarT,
+) -> bool {
+    pxs_debug!("pxs_objectset");
+    assert_initiated!();
+
+    if runtime.is_null() || obj.is_null() || key.is_null() || value.is_null() {
+        return false;
+    }
+
+    // Borrow
+    let borrow_obj = borrow_var!(obj);
+    let borrow_key = borrow_string!(key);
+    // own
+    let owned_value = own_var!(value);
+    if let Some(backend) = custom_backend(runtime, borrow_obj) {
+        return backend.object_set(borrow_obj, borrow_key, &owned_value);
+    }
+    let rt = unsafe { pxs_Runtime::from_var_ptr(runtime).unwrap() };
+
+    with_backend!(rt, Backend => {
+        let res = Backend::set(borrow_obj, borrow_key, &owned_value);
+        if res.is_err() {
+            false
+            // pxs_Var::new_exception(res.unwrap_err().to_string())
+        } else {
+            true
+            // res.unwrap()
+        }
+    })
+}
+
+/// Set a script global (`_G` in Lua, `__main__` in Python, `globalThis` in JavaScript) without building a module,
+/// i.e. frame state like `dt` before each tick. Returns false if `name` or `value` is null or the value can not be
+/// converted.
+///
+/// name:BORROW
+/// value:TRANSFER
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_setglobal(runtime: pxs_Runtime, name: *const c_char, value: pxs_VarT) -> bool {
+    pxs_debug!("pxs_setglobal");
+    assert_initiated!();
+
+    if value.is_null() {
+        strict::misuse("pxs_setglobal", "value is null");
+        return false;
+    }
+    let value = own_var!(value);
+    if name.is_null() {
+        strict::misuse("pxs_setglobal", "name is null");
+        return false;
+    }
+    let name = borrow_string!(name);
+
+    with_backend!(runtime, Backend => {
+        Backend::set_global(name, &value).is_ok()
+    })
+}
+
+/// Read a script global. Returns a `pxs_Null` if it is not set.
+///
+/// name:BORROW
+/// return:OWNED
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_getglobal(runtime: pxs_Runtime, name: *const c_char) -> pxs_VarT {
+    pxs_debug!("pxs_getglobal");
+    assert_initiated!();
+
+    if name.is_null() {
+        return pxs_Var::null_param_ep("name").into_raw();
+    }
+    let name = borrow_string!(name);
+
+    with_backend!(runtime, Backend => {
+        match Backend::get_from_name(name) {
+            Ok(var) => var,
+            Err(e) => pxs_Var::new_exception(e),
+        }
+    })
+    .into_raw()
+}
+
+/// Evaluate code. This will return a `pxs_VarT`.
+///
+/// return:OWNED
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_eval(script: *const c_char, rt: pxs_Runtime) -> pxs_VarT {
+    pxs_debug!("pxs_eval");
+    if script.is_null() {
+        return pxs_Var::null_param_ep("script").into_raw();
+    }
+
+    let script = borrow_string!(script);
+
+    with_backend!(rt, Backend => {
+        let res = Backend::eval(script, "<eval>");
+        if res.is_err() {
+            pxs_Var::new_exception(res.unwrap_err().to_string())
+        } else {
+            res.unwrap()
+        }
+    })
+    .into_raw()
+}
+
+/// Evaluate named code. This will return a `pxs_VarT`.
+/// 
+/// return:OWNED
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_evalnamed(script: *const c_char, name: *const c_char, rt: pxs_Runtime) -> pxs_VarT {
+    pxs_debug!("pxs_evalnamed");
+    if script.is_null() || name.is_null() {
+        return pxs_Var::null_params_ep().into_raw();
+    }
+
+    let script = borrow_string!(script);
+    let name = borrow_string!(name);
+
+    with_backend!(rt, Backend => {
+        let res = Backend::eval(script, name);
+        if res.is_err() {
+            pxs_Var::new_exception(res.unwrap_err())
+        } else {
+            res.unwrap()
+        }
+    }).into_raw()
+}
+
+/// Evaluate code with `bindings` (a `pxs_Map` with string keys) available as local variables.
+///
+/// Works like `pxs_eval`, in Lua the code still needs a `return`. The bindings are not visible after the call.
+///
+/// script:BORROW
+/// bindings:BORROW
+/// return:OWNED
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_eval_with(script: *const c_char, rt: pxs_Runtime, bindings: pxs_VarT) -> pxs_VarT {
+    pxs_debug!("pxs_eval_with");
+    if script.is_null() || bindings.is_null() {
+        return pxs_Var::null_params_ep().into_raw();
+    }
+
+    let script = borrow_string!(script);
+    let bindings = borrow_var!(bindings);
+    if !bindings.is_map() {
+        return pxs_Var::incorrect_type_ep(pxs_VarType::pxs_Map, bindings.tag).into_raw();
+    }
+    if bindings.get_map().unwrap().keys().iter().any(|key| !key.is_string()) {
+        return pxs_Var::new_exception("Binding names must be strings").into_raw();
+    }
+
+    with_backend!(rt, Backend => {
+        let res = Backend::eval_with(script, "<eval>", bindings);
+        if res.is_err() {
+            pxs_Var::new_exception(res.unwrap_err())
+        } else {
+            res.unwrap()
+        }
+    }).into_raw()
+}
+
+/// Evaluate a single expression without running any script code.
+///
+/// Only identifiers found in `env` (a `pxs_Map` with string keys, or `pxs_Null`) can be referenced.
+/// Supports literals, `.` member access, unary `-`/`!`, arithmetic, comparisons, `&&`/`||` and `?:`.
+/// The runtime is only used for member access on language objects. Integer overflow is a error and expressions can
+/// be nested at most 128 levels deep (parentheses, unary operators and chained operators).
+///
+/// Returns a `pxs_Exception` on any parse/evaluation error.
+///
+/// expr:BORROW
+/// env:BORROW
+/// return:OWNED
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_eval_expr(rt: pxs_Runtime, expr: *const c_char, env: pxs_VarT) -> pxs_VarT {
+    pxs_debug!("pxs_eval_expr");
+    assert_initiated!();
+    if expr.is_null() || env.is_null() {
+        return pxs_Var::null_params_ep().into_raw();
+    }
+
+    let expr = borrow_string!(expr);
+    let env = borrow_var!(env);
+    let empty = pxs_VarMap::new();
+    let env_map = if env.is_map() {
+        env.get_map().unwrap()
+    } else if env.is_null() {
+        &empty
+    } else {
+        return pxs_Var::incorrect_types_ep(vec![pxs_VarType::pxs_Map, pxs_VarType::pxs_Null], env.tag).into_raw();
+    };
+
+    with_backend!(rt, Backend => {
+        let res = shared::expr::evaluate(expr, env_map, &Backend::get);
+        if res.is_err() {
+            pxs_Var::new_exception(res.unwrap_err())
+        } else {
+            res.unwrap()
+        }
+    }).into_raw()
+}
+
+/// Add a factory variable. This variable will be instantiated once at module startup.
+///
+/// Args should not contain runtime. It gets added automatically.
+///
+/// Basically does:
+/// ```python
+/// var_name = callback(args)
+/// ```
+///
+/// args:TRANSFER
+/// return:OWNED
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_newfactory(func: pxs_Func, args: *mut pxs_Var) -> pxs_VarT {
+    pxs_debug!("pxs_newfactory");
+    assert_initiated!();
+
+    if args.is_null() {
+        return pxs_Var::null_param_ep("args").into_raw();
+    }
+    // Check args is list
+    let var = pxs_Var::from_raw(args);
+    if !var.is_list() {
+        return ptr::null_mut();
+    }
+
+    pxs_Var::new_factory(func, var).into_raw()
+}
+
+/// Factory behind `pxs_newfunc`. Turns the host function into a function of the runtime it is passed into.
+extern "C" fn host_function_factory(args: pxs_VarT) -> pxs_VarT {
+    let rt = unsafe { pxs_Runtime::from_var_ptr(pxs_listget(args, 0)) };
+    let fn_idx = borrow_var!(pxs_listget(args, 1)).get_i64();
+    let (rt, fn_idx) = match (rt, fn_idx) {
+        (Some(rt), Ok(fn_idx)) => (rt, fn_idx as i32),
+        _ => return pxs_Var::new_exception("Invalid host function.").into_raw(),
+    };
+
+    let res: shared::PxsResult = with_backend!(rt, Backend => {
+        Backend::new_function(fn_idx)
+    });
+    match res {
+        Ok(var) => var,
+        Err(e) => pxs_Var::new_exception(e),
+    }
+    .into_raw()
+}
+
+/// Create a function value from a host callback. Pass it to scripts (i.e. as a callback argument) and they can
+/// call it like any other function.
+/// 
+/// `callback` receives the runtime as the first arg followed by the script args, same as module functions.
+/// Use `pxs_funcopaque` inside the callback to get `opaque`. Returns a `pxs_Exception` if the function lookup is full.
+/// 
+/// opaque: BORROW
+/// return: OWNED
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_newfunc(callback: pxs_Func, opaque: pxs_Opaque) -> pxs_VarT {
+    pxs_debug!("pxs_newfunc");
+    assert_initiated!();
+
+    let fn_idx = match lookup_add_host_function(callback, opaque) {
+        Ok(idx) => idx,
+        Err(e) => return pxs_Var::new_exception(e).into_raw(),
+    };
+    let args = pxs_Var::new_list_with(vec![pxs_Var::new_i64(fn_idx as i64)]);
+    pxs_Var::new_factory(host_function_factory, args).into_raw()
+}
+
+/// Get the `opaque` passed to `pxs_newfunc`, `pxs_addfunc_ex` or `pxs_object_addoperator` for the callback currently
+/// running. Null anywhere else.
+/// 
+/// return: BORROW
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_funcopaque() -> pxs_Opaque {
+    pxs_debug!("pxs_funcopaque");
+    assert_initiated!();
+    current_opaque()
+}
+
+/// Get a handle to a registered module function, i.e. `pxs_funchandle("math", "add")`. Object methods work too
+/// with the type name as `module`.
+///
+/// The handle stays valid until `pxs_finalize`. Use it with `pxs_call_handle` to skip name lookups on hot paths.
+/// Returns -1 if the function is not registered.
+///
+/// module:BORROW
+/// name:BORROW
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_funchandle(module: *const c_char, name: *const c_char) -> i32 {
+    pxs_debug!("pxs_funchandle");
+    assert_initiated!();
+
+    if module.is_null() || name.is_null() {
+        return -1;
+    }
+    let usage_name = format!("{}.{}", borrow_string!(module), borrow_string!(name));
+    lookup_find_function(&usage_name).unwrap_or(-1)
+}
+
+/// Call a registered function by its handle from `pxs_funchandle`.
+///
+/// `args` is a List of the arguments, the runtime is passed to the function as the first arg like a script call.
+/// Capability tokens and usage recording apply as usual.
+///
+/// runtime:BORROW
+/// args:TRANSFER
+/// return:OWNED
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_call_handle(runtime: pxs_VarT, handle: i32, args: pxs_VarT) -> pxs_VarT {
+    pxs_debug!("pxs_call_handle");
+    assert_initiated!();
+
+    if runtime.is_null() || args.is_null() {
+        return pxs_Var::null_params_ep().into_raw();
+    }
+    let args = own_var!(args);
+    let list = match args.get_list() {
+        Some(list) => list,
+        None => return pxs_Var::incorrect_type_ep(pxs_VarType::pxs_List, args.tag).into_raw(),
+    };
+    if !lookup_has_function(handle) {
+        return pxs_Var::item_not_found_ep().into_raw();
+    }
+
+    let mut argv = Vec::with_capacity(list.vars.len() + 1);
+    argv.push(borrow_var!(runtime).clone());
+    argv.append(&mut list.vars);
+    unsafe { call_function(handle, argv) }.into_raw()
+}
+
+/// Start a lazy iteration over a script collection, so the host does not need to convert it all at once.
+///
+/// `pxs_List` items and `pxs_Map` `[key, value]` lists are iterated on the host, `var` must outlive the iterator.
+/// Anything else is iterated by the runtime: Lua tables give `[key, value]` lists, Python uses `iter(var)` and JS uses
+/// `var[Symbol.iterator]` or `Object.entries(var)`.
+///
+/// Free with `pxs_iter_free` before `pxs_finalize`. Returns null if `var` can not be iterated.
+///
+/// runtime:BORROW
+/// var:BORROW
+/// return:OWNED
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_iter_new(runtime: pxs_VarT, var: pxs_VarT) -> *mut pxs_Iterator {
+    pxs_debug!("pxs_iter_new");
+    assert_initiated!();
+
+    if runtime.is_null() || var.is_null() {
+        return ptr::null_mut();
+    }
+    let var = borrow_var!(var);
+    if let Some(iter) = pxs_Iterator::from_var(var) {
+        return iter.into_raw();
+    }
+
+    let rt = match unsafe { pxs_Runtime::from_var_ptr(runtime) } {
+        Some(rt) => rt,
+        None => return ptr::null_mut(),
+    };
+    let res: shared::PxsResult = with_backend!(rt, Backend => {
+        Backend::new_iterator(var)
+    });
+    match res {
+        Ok(next) if next.is_function() => pxs_Iterator::from_lang(borrow_var!(runtime).clone(), next).into_raw(),
+        #[allow(unused)]
+        Ok(next) => {
+            pxs_debug!("Iterator did not return a function: {:#?}", next.tag);
+            ptr::null_mut()
+        }
+        #[allow(unused)]
+        Err(e) => {
+            pxs_debug!("Can not iterate var: {e}");
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Get the next item of a iterator. Returns null once the iterator is exhausted.
+///
+/// A `pxs_Exception` is returned if the script fails, this also ends the iteration.
+///
+/// iter:BORROW
+/// return:OWNED
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_iter_next(iter: *mut pxs_Iterator) -> pxs_VarT {
+    pxs_debug!("pxs_iter_next");
+    assert_initiated!();
+
+    if iter.is_null() {
+        return ptr::null_mut();
+    }
+    let iter = unsafe { pxs_Iterator::from_borrow(iter) };
+    match iter.next() {
+        Some(item) => item.into_raw(),
+        None => ptr::null_mut(),
+    }
+}
+
+/// Free a iterator.
+///
+/// iter:TRANSFER
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_iter_free(iter: *mut pxs_Iterator) {
+    pxs_debug!("pxs_iter_free");
+    if iter.is_null() {
+        return;
+    }
+    let _ = unsafe { pxs_Iterator::from_raw(iter) };
+}
+
+/// Start `func` as a coroutine the host resumes across frames. Lua functions run as Lua coroutines, Python and
+/// JavaScript functions must be generator functions. `args` is a list passed to the first resume, it can be null.
+///
+/// Nothing runs until `pxs_coroutine_resume`. Free with `pxs_coroutine_free` before `pxs_finalize`. Returns null if
+/// `func` is not a function or the runtime can not make a coroutine.
+///
+/// runtime:BORROW
+/// func:BORROW
+/// args:TRANSFER
+/// return:OWNED
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_coroutine_create(runtime: pxs_VarT, func: pxs_VarT, args: pxs_VarT) -> *mut pxs_Coroutine {
+    pxs_debug!("pxs_coroutine_create");
+    assert_initiated!();
+
+    let args = if args.is_null() { pxs_Var::new_list() } else { own_var!(args) };
+    if runtime.is_null() || func.is_null() || !args.is_list() {
+        strict::misuse("pxs_coroutine_create", "runtime or func is null, or args is not a list");
+        return ptr::null_mut();
+    }
+    let func = borrow_var!(func);
+    if !func.is_function() {
+        strict::misuse("pxs_coroutine_create", "func is not a function");
+        return ptr::null_mut();
+    }
+
+    let rt = match unsafe { pxs_Runtime::from_var_ptr(runtime) } {
+        Some(rt) => rt,
+        None => return ptr::null_mut(),
+    };
+    let res: shared::PxsResult = with_backend!(rt, Backend => {
+        Backend::new_coroutine(func, &args)
+    });
+    match res {
+        Ok(resume) if resume.is_function() => pxs_Coroutine::new(borrow_var!(runtime).clone(), resume).into_raw(),
+        #[allow(unused)]
+        Ok(resume) => {
+            pxs_debug!("Coroutine did not return a function: {:#?}", resume.tag);
+            ptr::null_mut()
+        }
+        #[allow(unused)]
+        Err(e) => {
+            pxs_debug!("Can not create coroutine: {e}");
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Run a coroutine until its next yield and return the yielded value. Once the function returns, the returned value
+/// is given and the coroutine is dead.
+///
+/// `args` is a list given to the script as the result of the yield (Python generators ignore it), it can be null.
+/// A `pxs_Exception` is returned if the script fails (this kills the coroutine) or the coroutine can not be resumed.
+///
+/// co:BORROW
+/// args:TRANSFER
+/// return:OWNED
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_coroutine_resume(co: *mut pxs_Coroutine, args: pxs_VarT) -> pxs_VarT {
+    pxs_debug!("pxs_coroutine_resume");
+    assert_initiated!();
+
+    let args = if args.is_null() { pxs_Var::new_list() } else { own_var!(args) };
+    if co.is_null() {
+        return pxs_Var::null_param_ep("co").into_raw();
+    }
+    if !args.is_list() {
+        return pxs_Var::incorrect_type_ep(pxs_VarType::pxs_List, args.tag).into_raw();
+    }
+    let co_ptr = co;
+    let co = unsafe { pxs_Coroutine::from_borrow(co) };
+    let res = match co.runtime() {
+        None => co.resume(args),
+        Some(rt) => {
+            let res = with_backend!(rt, Backend => {
+                run_limited::<Backend>(&rt, || Ok(co.resume(args)))
+            });
+            match res {
+                Ok(res) => res.unwrap_or_else(pxs_Var::new_exception),
+                Err(outcome) => pxs_Var::new_exception(stopped_message(outcome)),
+            }
+        }
+    };
+    // A yielded promise resumes the coroutine once it resolves.
+    if co.status() == pxs_CoroutineStatus::pxs_CoroutineSuspended {
+        promise::await_yielded(co_ptr, co.rt(), &res);
+    }
+    res.into_raw()
+}
+
+/// Get the status of a coroutine. A null coroutine is dead.
+///
+/// co:BORROW
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_coroutine_status(co: *mut pxs_Coroutine) -> pxs_CoroutineStatus {
+    pxs_debug!("pxs_coroutine_status");
+    if co.is_null() {
+        return pxs_CoroutineStatus::pxs_CoroutineDead;
+    }
+    unsafe { pxs_Coroutine::from_borrow(co) }.status()
+}
+
+/// Free a coroutine. A suspended coroutine is dropped without running the rest of it.
+///
+/// co:TRANSFER
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_coroutine_free(co: *mut pxs_Coroutine) {
+    pxs_debug!("pxs_coroutine_free");
+    if co.is_null() {
+        return;
+    }
+    promise::forget_coroutine(co);
+    let _ = pxs_Coroutine::from_raw(co);
+}
+
+/// Make a pending promise for a host function doing async work (IO, a network request). Returns its id.
+///
+/// Return `pxs_promise_var(id)` from the host function and call `pxs_promise_resolve(id, result)` once the work is
+/// done. Scripts call `then(fn)` on the `Promise`, or yield it from a coroutine to await it.
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_newpromise() -> i64 {
+    pxs_debug!("pxs_newpromise");
+    assert_initiated!();
+    promise::new()
+}
+
+/// Get a `Promise` object of a promise for scripts, with `done()`, `result()`, `error()` and `then(fn)` methods.
+///
+/// Returns a exception if the promise is unknown.
+///
+/// return:OWNED
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_promise_var(id: i64) -> pxs_VarT {
+    pxs_debug!("pxs_promise_var");
+    assert_initiated!();
+    match promise::object(id) {
+        Ok(var) => var.into_raw(),
+        Err(e) => pxs_Var::new_exception(e).into_raw(),
+    }
+}
+
+/// Resolve a promise. Runs its `then` callbacks, then resumes the coroutines awaiting it with `(result, error)`.
+///
+/// Pass a `pxs_Exception` to reject the promise with its message. Returns false if the promise is unknown or already
+/// resolved. Must be called on the thread that made the promise.
+///
+/// result:TRANSFER
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_promise_resolve(id: i64, result: pxs_VarT) -> bool {
+    pxs_debug!("pxs_promise_resolve");
+    assert_initiated!();
+    let result = if result.is_null() { pxs_Var::new_null() } else { own_var!(result) };
+    promise::resolve(id, result)
+}
+
+/// Get the `_pxs_ptr` of a `pxs_HostObject`. And type check it against `type_id`.
+/// 
+/// if `type_id` < 0, no type checking is done.
+/// 
+/// runtime: BORROW
+/// var: BORROW
+/// return: BORROW
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_gettype(runtime: pxs_VarT, var: pxs_VarT, type_id: i32) -> *mut c_void {
+    pxs_debug!("pxs_gettype");
+    assert_initiated!();
+    if runtime.is_null() || var.is_null() {
+        return ptr::null_mut();
+    }
+
+    let borrow_var = unsafe { pxs_Var::from_borrow(var) };
+
+    if borrow_var.is_object() {
+        // Check for ptr
+        let key = create_raw_string!(PXS_PTR_NAME);
+        let idx_var = pxs_objectget(runtime, var, key);
+        unsafe {
+            free_raw_string!(key);
+        }
+        if idx_var.is_null() {
+            return ptr::null_mut();
+        }
+
+        let idx_own = pxs_Var::from_raw(idx_var);
+        if type_id >= 0 {
+            let t = idx_own.get_pxs_type();
+            if t != type_id {
+                return ptr::null_mut();
+            }
+        }
+        let host_ptr = idx_own.get_host_ptr();
+
+        if host_ptr.is_null() {
+            pxs_debug!("Host pointer is null. The variable is: {:#?}", idx_own);
+        }
+
+        host_ptr
+    } else if borrow_var.is_i64() || borrow_var.is_u64() || borrow_var.is_host_object() {
+        if type_id >= 0 {
+            let t = borrow_var.get_pxs_type();
+            if t != type_id {
+                return ptr::null_mut();
+            }
+        }
+        borrow_var.get_host_ptr()
+    } else if borrow_var.is_factory() {
+        // Get the factory
+        let factory = borrow_var.get_factory().unwrap();
+        // Call the function
+        let res = factory.call(unsafe { pxs_Runtime::from_var_ptr(runtime) }.unwrap());
+        // Now we should have our object, so just call pxs_gettype on it again
+        let res_raw = res.into_raw();
+        let host = pxs_gettype(runtime, res_raw, type_id);
+        // Free res_raw
+        let _ = pxs_Var::from_raw(res_raw);
+        // return host
+        host
+    } else {
+        ptr::null_mut()
+    }
+}
+
+/// Get the HostPointer universally supported for:
+/// - Objects that have `_pxs_ptr` assigned.
+/// - Integers (signed and unsigned)
+/// - HostObjects
+/// - Factories (this will call it on the fly.)
+///
+/// All other types will return NULL.
+///
+/// runtime:BORROW
+/// var:BORROW
+/// return:BORROW
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_gethost(runtime: pxs_VarT, var: pxs_VarT) -> *mut c_void {
+    pxs_debug!("pxs_gethost");
+    pxs_gettype(runtime, var, -1)
+}
+
+/// The host object of `var`, resolved like `pxs_gethost`.
+fn host_object_of(runtime: pxs_VarT, var: pxs_VarT) -> Option<Arc<pxs_PixelObject>> {
+    let borrow_var = borrow_var!(var);
+    if borrow_var.is_object() {
+        let key = CString::new(PXS_PTR_NAME).unwrap();
+        let idx_var = pxs_objectget(runtime, var, key.as_ptr());
+        if idx_var.is_null() {
+            return None;
+        }
+        get_object(pxs_Var::from_raw(idx_var).get_host_idx())
+    } else if borrow_var.is_i64() || borrow_var.is_u64() || borrow_var.is_host_object() {
+        get_object(borrow_var.get_host_idx())
+    } else if borrow_var.is_factory() {
+        let res = borrow_var.get_factory().unwrap().call(unsafe { pxs_Runtime::from_var_ptr(runtime) }?);
+        host_object_of(runtime, &res as *const pxs_Var as pxs_VarT)
+    } else {
+        None
+    }
+}
+
+/// Get the host pointer of `var` like `pxs_gethost`, but only if the object is a `type_name` or inherits from it (see
+/// `pxs_class_setparent`). Use it instead of casting the result of `pxs_gethost` when scripts can pass any object.
+///
+/// Returns NULL if `var` is not a host object or of a other type.
+///
+/// runtime:BORROW
+/// var:BORROW
+/// return:BORROW
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_gethost_checked(runtime: pxs_VarT, var: pxs_VarT, type_name: *const c_char) -> *mut c_void {
+    pxs_debug!("pxs_gethost_checked");
+    assert_initiated!();
+    if runtime.is_null() || var.is_null() || type_name.is_null() {
+        return ptr::null_mut();
+    }
+
+    let type_name = borrow_string!(type_name);
+    match host_object_of(runtime, var) {
+        Some(object) if registry::is_type(&object.type_name, type_name) => object.ptr,
+        _ => ptr::null_mut(),
+    }
+}
+
+/// Get the type name of the host object of `var`, resolved like `pxs_gethost`.
+///
+/// Returns NULL if `var` is not a host object. Free the string with `pxs_freestr`.
+///
+/// runtime:BORROW
+/// var:BORROW
+/// return:OWNED
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_object_typename(runtime: pxs_VarT, var: pxs_VarT) -> *mut c_char {
+    pxs_debug!("pxs_object_typename");
+    assert_initiated!();
+    if runtime.is_null() || var.is_null() {
+        return ptr::null_mut();
+    }
+
+    match host_object_of(runtime, var) {
+        Some(object) => create_raw_string!(object.type_name.clone()),
+        None => ptr::null_mut(),
+    }
+}
+
+/// Return a string rep of the `pxs_Var`.
+///
+/// String must be freed via `pxs_freestr`.
+///
+/// var:BORROW
+/// return:OWNED
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_debugvar(var: pxs_VarT) -> *mut c_char {
+    pxs_debug!("pxs_debugvar");
+    if var.is_null() {
+        create_raw_string!("NULL")
+    } else {
+        let borrow_var = unsafe { pxs_Var::from_borrow(var) };
+        let str = format!("{:#?}", borrow_var);
+        create_raw_string!(str)
+    }
+}
+
+/// Create a `pxs_Exception`.
+///
+/// return:OWNED
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_newexception(msg: *const c_char) -> pxs_VarT {
+    pxs_debug!("pxs_newexception");
+    if msg.is_null() {
+        return pxs_Var::null_param_ep("msg").into_raw();
+    }
+
+    // Borrow msg
+    let bmsg = borrow_string!(msg);
+    pxs_Var::new_exception(bmsg.to_string().clone()).into_raw()
+}
+
+/// Create a `pxs_Exception` of a `kind`. Returned from a `pxs_Func` it is raised in the script, as the matching
+/// exception class in Python (`TypeError`, `ValueError`, ...) and JavaScript (the error `name`). Lua raises the
+/// message.
+///
+/// return:OWNED
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_newerror(message: *const c_char, kind: pxs_ErrorKind) -> pxs_VarT {
+    pxs_debug!("pxs_newerror");
+    if message.is_null() {
+        return pxs_Var::null_param_ep("message").into_raw();
+    }
+
+    pxs_Var::new_error(borrow_string!(message), kind).into_raw()
+}
+
+/// Is `var` a error, i.e. a exception returned by `pxs_call` or `pxs_newerror`? Use `pxs_getstring` for the message.
+///
+/// var:BORROW
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_varis_error(var: pxs_VarT) -> bool {
+    pxs_debug!("pxs_varis_error");
+    if var.is_null() {
+        return false;
+    }
+    unsafe { pxs_Var::from_borrow(var) }.is_exception()
+}
+
+/// Get the kind of a error. `pxs_GenericError` for errors raised by scripts and for any other var.
+///
+/// var:BORROW
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_geterrorkind(var: pxs_VarT) -> pxs_ErrorKind {
+    pxs_debug!("pxs_geterrorkind");
+    if var.is_null() {
+        return pxs_ErrorKind::pxs_GenericError;
+    }
+    unsafe { pxs_Var::from_borrow(var) }.error_kind
+}
+
+/// Get a variable reference from its name
+///
+/// rt:BORROW
+/// return:OWNED
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_var_fromname(rt: pxs_VarT, name: *const c_char) -> pxs_VarT {
+    pxs_debug!("pxs_var_fromname");
+    assert_initiated!();
+    if name.is_null() {
+        return pxs_Var::null_param_ep("name").into_raw();
+    }
+
+    let bname = borrow_string!(name);
+    let runtime = unsafe { pxs_Runtime::from_var_ptr(rt) };
+    if let Some(runtime) = runtime {
+        with_backend!(runtime, Backend => {
+            let res = Backend::get_from_name(bname);
+            if res.is_err() {
+                pxs_Var::new_exception(res.unwrap_err().to_string())
+            } else {
+                res.unwrap()
+            }
+        })
+        .into_raw()
+    } else {
+        pxs_Var::unkown_runtime_var_ep(rt).into_raw()
+    }
+}
+
+/// Remove a item from a list at a specific index. Supports negative indexes just like in Python.
+///
+/// Returns true for success, false for failed (e.g. the index is out of bounds).
+/// 
+/// This will automatically call `pxs_freevar` on the found item.
+///
+/// list:BORROW
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_listdel(list: pxs_VarT, index: i32) -> bool {
+    pxs_debug!("pxs_listdel");
+    if list.is_null() {
+        return false;
+    }
+
+    let var = borrow_var!(list);
+    if !var.is_list() {
+        return false;
+    }
+
+    // Ok so it's a List and it's not null! Lets delete the item
+    let var_list = var.get_list().unwrap();
+    match var_list.del_item(index) {
+        Ok(()) => true,
+        Err(e) => {
+            strict::misuse("pxs_listdel", &e);
+            false
+        }
+    }
+}
+
+/// Do a Shallow Copy. Which means it gets the same data without get the deleter for (pxs_Object or pxs_Function).
+///
+/// Memory is owned by caller.
+///
+/// var:BORROW
+/// return:OWNED
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_new_shallowcopy(var: pxs_VarT) -> pxs_VarT {
+    pxs_debug!("pxs_new_shallowcopy");
+    if var.is_null() {
+        return pxs_Var::null_param_ep("var").into_raw();
+    }
+
+    // Apply a shallow copy.
+    let bvar = borrow_var!(var);
+    bvar.shallow_copy().into_raw()
+}
+
+/// Compile a code string into a code object for later execution.
+///
+/// Pass in a optional gloabl scope (or null for default). Scope ownership is transferred.
+/// Returns a `pxs_Var` whichs memory is handled by the caller.
+///
+/// Resulting `pxs_Var` will contain (Associated Runtime, Code Object, Scope|default).
+///
+/// global_scope:TRANSFER
+/// return:OWNED
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_compile(
+    runtime: pxs_Runtime,
+    code: *const c_char,
+    global_scope: pxs_VarT,
+) -> pxs_VarT {
+    pxs_debug!("pxs_compile");
+    assert_initiated!();
+
+    if code.is_null() || global_scope.is_null() {
+        return pxs_Var::null_params_ep().into_raw();
+    }
+
+    let rcode = borrow_string!(code);
+    let scope = own_var!(global_scope);
+
+    // Make sure scope is map or is null.
+    if !scope.is_map() && !scope.is_null() {
+        return pxs_Var::new_exception("Global scope must be a Map or Null".to_string()).into_raw();
+    }
+
+    let res = with_backend!(runtime, Backend => {
+        let res = Backend::compile(rcode, scope);
+        if res.is_err() {
+            pxs_Var::new_exception(res.unwrap_err().to_string())
+        } else {
+            res.unwrap()
+        }
+    });
+
+    if !res.is_list() {
+        return res.into_raw();
+    }
+
+    // Add the runtime to the object.
+    let list = res.get_list().unwrap();
+    // 0 is always in bounds.
+    let _ = list.insert_item(0, runtime.into_var());
+
+    res.into_raw()
+}
+
+/// Execute a compiled code object.
+///
+/// Variable ownership is transfered. If this is not desired behavior, pass in a shallow copy.
+/// Returned variable must be freed by caller.
+///
+/// Runtime is not required because the object is embedded with it in pxs_compile.
+/// Pass in a optional local scope that gets passed along with the global scope.
+/// Note: Do not use the same scope as in `pxs_compile`.
+///
+/// Scope ownership is transferred.
+///
+/// object : TRANSFER
+/// local  : TRANSFER
+/// return : OWNED
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_execobject(object: pxs_VarT, local: pxs_VarT) -> pxs_VarT {
+    pxs_debug!("pxs_execobject");
+    assert_initiated!();
+
+    if object.is_null() || local.is_null() {
+        return pxs_Var::null_params_ep().into_raw();
+    }
+
+    let var = own_var!(object);
+
+    if !var.is_list() {
+        return pxs_Var::incorrect_type_ep(pxs_VarType::pxs_List, var.tag).into_raw();
+    }
+
+    // Get scope
+    let scope = own_var!(local);
+    // Check if scope is a map
+    if !scope.is_map() && !scope.is_null() {
+        return pxs_Var::incorrect_types_ep(
+            vec![pxs_VarType::pxs_Map, pxs_VarType::pxs_Null],
+            scope.tag,
+        )
+        .into_raw();
+    }
+
+    let list = var.get_list().unwrap();
+    let rt = list.get_item(0);
+    if let Some(rt) = rt {
+        if let Some(backend) = rt.get_i64().ok().and_then(backend::get) {
+            return backend.exec_object(&var, &scope).into_raw();
+        }
+        let runtime = pxs_Runtime::from_var(rt);
+        if let Some(runtime) = runtime {
+            // Now we can do stuff
+            with_backend!(runtime, Backend => {
+                let res = Backend::exec_object(var, scope);
+                if res.is_err() {
+                    pxs_Var::new_exception(res.unwrap_err().to_string())
+                } else {
+                    res.unwrap()
+                }
+            })
+            .into_raw()
+        } else {
+            pxs_Var::unkown_runtime_var_ep(rt as *const pxs_Var as pxs_VarT).into_raw()
+        }
+    } else {
+        pxs_Var::new_exception("Code Object list is empty").into_raw()
+    }
+}
+
+/// Create a new `pxs_Map`
+///
+/// return:OWNED
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_newmap() -> pxs_VarT {
+    pxs_debug!("pxs_newmap");
+    pxs_Var::new_map().into_raw()
+}
+
+/// Add a new key (`pxs_Var`) value (`pxs_Var`) pair in a map.
+///
+/// Keys can only be:
+/// - `pxs_String`
+/// - `pxs_Int64`
+/// - `pxs_UInt64`
+/// - `pxs_Float64`
+/// - `pxs_Bool`
+///
+/// Key and value ownership are transfered.
+///
+/// map:BORROW
+/// key:TRANSFER
+/// value:TRANSFER
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_map_addpair(map: pxs_VarT, key: pxs_VarT, value: pxs_VarT) {
+    pxs_debug!("pxs_map_addpair");
+    if map.is_null() || key.is_null() || value.is_null() {
+        strict::misuse("pxs_map_addpair", "map, key or value is null");
+        return;
+    }
+
+    // Check map is actually a map
+    let map = borrow_var!(map);
+    if !map.is_map() {
+        strict::misuse("pxs_map_addpair", "map is not a map");
+        return;
+    }
+
+    // Check key is valid pair
+    let key = own_var!(key);
+    if key.is_string() || key.is_i64() || key.is_bool() || key.is_u64() || key.is_f64() {
+        let internal = map.get_map().unwrap();
+        internal.add_item(key, own_var!(value));
+    } else {
+        strict::misuse("pxs_map_addpair", "key must be a string, number or bool");
+    }
+}
+
+/// Set `key` to `value` in a map, replacing the old value. Keys are the same as for `pxs_map_addpair`. Unlike
+/// `pxs_map_addpair` the key and value are copied, so the host keeps them. Returns false if nothing was set.
+///
+/// map:BORROW
+/// key:BORROW
+/// value:BORROW
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_mapset(map: pxs_VarT, key: pxs_VarT, value: pxs_VarT) -> bool {
+    pxs_debug!("pxs_mapset");
+    if map.is_null() || key.is_null() || value.is_null() {
+        strict::misuse("pxs_mapset", "map, key or value is null");
+        return false;
+    }
+
+    let map = borrow_var!(map);
+    let Some(internal) = map.get_map() else {
+        strict::misuse("pxs_mapset", "map is not a map");
+        return false;
+    };
+    let key = borrow_var!(key);
+    if !(key.is_string() || key.is_i64() || key.is_bool() || key.is_u64() || key.is_f64()) {
+        strict::misuse("pxs_mapset", "key must be a string, number or bool");
+        return false;
+    }
+    internal.add_item(key.clone(), borrow_var!(value).clone());
+    true
+}
+
+/// Convert a script table, dict or object into a `pxs_Map`, i.e. a Lua table, Python dict or JS object passed to a
+/// host function. Nested ones are converted too, lists are copied and other values are kept as is.
+///
+/// Script collections arrive as `pxs_Object` handles so the host can change them in place, this makes a plain copy
+/// that does not need the runtime anymore. Returns a exception if `var` can not be iterated.
+///
+/// runtime:BORROW
+/// var:BORROW
+/// return:OWNED
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_tomap(runtime: pxs_VarT, var: pxs_VarT) -> pxs_VarT {
+    pxs_debug!("pxs_tomap");
+    assert_initiated!();
+    if runtime.is_null() || var.is_null() {
+        return pxs_Var::null_params_ep().into_raw();
+    }
+
+    let var = borrow_var!(var);
+    if !var.is_object() && !var.is_map() {
+        return pxs_Var::incorrect_types_ep(vec![pxs_VarType::pxs_Object, pxs_VarType::pxs_Map], var.tag).into_raw();
+    }
+    match shared::fields::to_map(runtime, var, 0) {
+        Ok(res) if res.is_map() => res.into_raw(),
+        Ok(_) => pxs_Var::new_exception("Can not convert a object that is not iterable into a map.").into_raw(),
+        Err(e) => pxs_Var::new_exception(e).into_raw(),
+    }
+}
+
+/// Remove a value (`pxs_Var`) from a map based on it's key (`pxs_Var`).
+///
+/// map:BORROW
+/// key:BORROW
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_map_delitem(map: pxs_VarT, key: pxs_VarT) {
+    pxs_debug!("pxs_map_delitem");
+    if map.is_null() || key.is_null() {
+        strict::misuse("pxs_map_delitem", "map or key is null");
+        return;
+    }
+
+    let map = borrow_var!(map);
+    if !map.is_map() {
+        strict::misuse("pxs_map_delitem", "map is not a map");
+        return;
+    }
+
+    let key = borrow_var!(key);
+    let internal = map.get_map().unwrap();
+    internal.del_item(key);
+}
+
+/// Get length of a `pxs_Map`.
+///
+/// -1 is invalid length.
+///
+/// map:BORROW
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_maplen(map: pxs_VarT) -> i32 {
+    pxs_debug!("pxs_maplen");
+    if map.is_null() {
+        return -1;
+    }
+
+    let bvar = borrow_var!(map);
+    if bvar.is_map() {
+        bvar.get_map().unwrap().len() as i32
+    } else {
+        -1
+    }
+}
+
+/// Get the keys of a `pxs_Map`.
+///
+/// Returns a `pxs_List` or `pxs_Null` Which is owned by caller.
+///
+/// map:BORROW
+/// return:OWNED
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_mapkeys(map: pxs_VarT) -> pxs_VarT {
+    pxs_debug!("pxs_mapkeys");
+    if map.is_null() {
+        return pxs_Var::null_param_ep("map").into_raw();
+    }
+
+    // Check is map
+    let map = borrow_var!(map);
+    if !map.is_map() {
+        return pxs_Var::incorrect_type_ep(pxs_VarType::pxs_Map, map.tag).into_raw();
+    }
+    let internals = map.get_map().unwrap();
+
+    let result = pxs_Var::new_list();
+    let list = result.get_list().unwrap();
+
+    // Get keys..
+    let keys = internals.keys();
+    for k in keys {
+        // Deep copy the key over.
+        list.add_item(k.clone());
+    }
+
+    result.into_raw()
+}
+
+/// Get a value in a map from a key.
+///
+/// Result is not owned by caller. Use `pxs_newcopy` to transfer ownership.
+///
+/// map:BORROW
+/// key:BORROW
+/// return:BORROW
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_mapget(map: pxs_VarT, key: pxs_VarT) -> pxs_VarT {
+    pxs_debug!("pxs_mapget");
+
+    if map.is_null() || key.is_null() {
+        return pxs_Var::null_params_ep().into_raw();
+    }
+
+    // check map
+    let map = borrow_var!(map);
+    if !map.is_map() {
+        return pxs_Var::incorrect_type_ep(pxs_VarType::pxs_Map, map.tag).into_raw();
+    }
+    let internal = map.get_map().unwrap();
+    let res = internal.get_item(borrow_var!(key));
+
+    // Return a const pxs_Var.
+    if let Some(res) = res {
+        res as *const pxs_Var as *mut pxs_Var
+    } else {
+        pxs_Var::item_not_found_ep().into_raw()
+    }
+}
+
+/// Insert a item into a list at a certain index, shifting all other items to the right.
+///
+/// Item ownership is transferred. The index can be at most the length of the list (appends), otherwise the item is
+/// freed and nothing is inserted.
+///
+/// list:BORROW
+/// item:TRANSFER
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_listinsert(list: pxs_VarT, index: usize, item: pxs_VarT) {
+    pxs_debug!("pxs_listinsert");
+
+    if list.is_null() || item.is_null() {
+        strict::misuse("pxs_listinsert", "list or item is null");
+        return;
+    }
+
+    // Check is list
+    let list = borrow_var!(list);
+    if !list.is_list() {
+        strict::misuse("pxs_listinsert", "list is not a list");
+        return;
+    }
+
+    let item = own_var!(item);
+    let internal = list.get_list().unwrap();
+    let inserted = match i32::try_from(index) {
+        Ok(index) => internal.insert_item(index, item),
+        Err(_) => Err(format!("Index {index} is out of bounds for a list of length {}.", internal.len())),
+    };
+    if let Err(e) = inserted {
+        strict::misuse("pxs_listinsert", &e);
+    }
+}
+
+/// Take ownership of `count` items. Null items become `pxs_Null`.
+fn own_items(items: *const pxs_VarT, count: usize) -> Vec<pxs_Var> {
+    if items.is_null() || count == 0 {
+        return vec![];
+    }
+
+    let items = unsafe { core::slice::from_raw_parts(items, count) };
+    items.iter().map(|&item| if item.is_null() { pxs_Var::new_null() } else { pxs_Var::from_raw(item) }).collect()
+}
+
+/// Create a pxs_VarList from a array of `count` items in one call.
+///
+/// Takes ownership of every item, the array itself still belongs to the caller. Null items are added as `pxs_Null`.
+///
+/// items:TRANSFER
+/// return:OWNED
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_newlist_from(items: *const pxs_VarT, count: usize) -> pxs_VarT {
+    pxs_debug!("pxs_newlist_from");
+    assert_initiated!();
+
+    if items.is_null() && count > 0 {
+        strict::misuse("pxs_newlist_from", "items is null");
+    }
+
+    pxs_Var::new_list_with(own_items(items, count)).into_raw()
+}
+
+/// Add a array of `count` items to the end of a pxs_VarList in one call.
+///
+/// Takes ownership of every item (they are freed if `list` is not a list), the array itself still belongs to the
+/// caller. Null items are added as `pxs_Null`.
+///
+/// Returns the new length, or -1 on failure.
+///
+/// list:BORROW
+/// items:TRANSFER
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_list_extend(list: pxs_VarT, items: *const pxs_VarT, count: usize) -> i32 {
+    pxs_debug!("pxs_list_extend");
+    assert_initiated!();
+
+    let owned_items = own_items(items, count);
+    if list.is_null() {
+        strict::misuse("pxs_list_extend", "list is null");
+        return -1;
+    }
+    if items.is_null() && count > 0 {
+        strict::misuse("pxs_list_extend", "items is null");
+        return -1;
+    }
+
+    let list = borrow_var!(list);
+    if !list.is_list() {
+        strict::misuse("pxs_list_extend", "list is not a list");
+        return -1;
+    }
+
+    let internal = list.get_list().unwrap();
+    internal.vars.extend(owned_items);
+    internal.len() as i32
+}
+
+/// Remove every item of a pxs_VarList, freeing them.
+///
+/// Returns false if `list` is not a list.
+///
+/// list:BORROW
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_list_clear(list: pxs_VarT) -> bool {
+    pxs_debug!("pxs_list_clear");
+    assert_initiated!();
+
+    if list.is_null() {
+        strict::misuse("pxs_list_clear", "list is null");
+        return false;
+    }
+
+    let list = borrow_var!(list);
+    if !list.is_list() {
+        strict::misuse("pxs_list_clear", "list is not a list");
+        return false;
+    }
+
+    list.get_list().unwrap().vars.clear();
+    true
+}
+
+/// Remove a item from a pxs_VarList and return it. Supports negative indexes just like in Python.
+///
+/// Unlike `pxs_listdel` the item is not freed, it is owned by the caller. Returns null if the index is out of bounds.
+///
+/// list:BORROW
+/// return:OWNED&NULLABLE
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_list_remove(list: pxs_VarT, index: i32) -> pxs_VarT {
+    pxs_debug!("pxs_list_remove");
+    assert_initiated!();
+
+    if list.is_null() {
+        strict::misuse("pxs_list_remove", "list is null");
+        return ptr::null_mut();
+    }
+
+    let list = borrow_var!(list);
+    if !list.is_list() {
+        strict::misuse("pxs_list_remove", "list is not a list");
+        return ptr::null_mut();
+    }
+
+    match list.get_list().unwrap().remove_item(index) {
+        Ok(item) => item.into_raw(),
+        Err(_) => ptr::null_mut(),
+    }
+}
+
+/// Remove the last item of a pxs_VarList and return it.
+///
+/// Returns null if the list is empty.
+///
+/// list:BORROW
+/// return:OWNED&NULLABLE
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_listpop(list: pxs_VarT) -> pxs_VarT {
+    pxs_debug!("pxs_listpop");
+    assert_initiated!();
+
+    if list.is_null() {
+        strict::misuse("pxs_listpop", "list is null");
+        return ptr::null_mut();
+    }
+
+    let list = borrow_var!(list);
+    if !list.is_list() {
+        strict::misuse("pxs_listpop", "list is not a list");
+        return ptr::null_mut();
+    }
+
+    match list.get_list().unwrap().vars.pop() {
+        Some(item) => item.into_raw(),
+        None => ptr::null_mut(),
+    }
+}
+
+/// Create a new pxs_VarList with the items from `start` up to `end` (exclusive).
+///
+/// Works like Python slicing: negative indexes count from the end and out of bounds indexes are clamped, so
+/// `pxs_listslice(list, 1, INT32_MAX)` is everything but the first item.
+///
+/// Items are shallow copies (see `pxs_new_shallowcopy`), objects and functions in the slice are only valid as long
+/// as `list` is.
+///
+/// list:BORROW
+/// return:OWNED
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_listslice(list: pxs_VarT, start: i32, end: i32) -> pxs_VarT {
+    pxs_debug!("pxs_listslice");
+    assert_initiated!();
+
+    if list.is_null() {
+        return pxs_Var::null_param_ep("list").into_raw();
+    }
+
+    let list = borrow_var!(list);
+    if !list.is_list() {
+        return pxs_Var::incorrect_type_ep(pxs_VarType::pxs_List, list.tag).into_raw();
+    }
+
+    pxs_Var::new_list_with(list.get_list().unwrap().slice(start, end)).into_raw()
+}
+
+/// Find the index of the first item in a pxs_VarList equal to `item`.
+///
+/// Numbers, strings, bools and bytes are compared by value, the type must match (`1` does not equal `1.0`). Other
+/// types are never equal.
+///
+/// Returns -1 if there is no such item.
+///
+/// list:BORROW
+/// item:BORROW
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_listfind(list: pxs_VarT, item: pxs_VarT) -> i32 {
+    pxs_debug!("pxs_listfind");
+    assert_initiated!();
+
+    if list.is_null() || item.is_null() {
+        strict::misuse("pxs_listfind", "list or item is null");
+        return -1;
+    }
+
+    let list = borrow_var!(list);
+    if !list.is_list() {
+        strict::misuse("pxs_listfind", "list is not a list");
+        return -1;
+    }
+
+    match list.get_list().unwrap().find(borrow_var!(item)) {
+        Some(index) => index as i32,
+        None => -1,
+    }
+}
+
+/// Create a new arena in memory.
+/// This does not return anything, it simply creates a scope that will allocate pxs_Var memory.
+/// when finished call, `pxs_freearena`
+///
+/// result:OWNED
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_newarena() -> *mut pxs_PixelArena {
+    pxs_debug!("pxs_newarena");
+    assert_initiated!();
+
+    let arena = pxs_PixelArena::new();
+    arena.into_raw()
+}
+
+/// Free arena. Upon freeing all variables allocated since `pxs_newarena` will be freed.
+///
+/// arena:TRANSFER
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_freearena(arena: *mut pxs_PixelArena) {
+    pxs_debug!("pxs_freearena");
+    assert_initiated!();
+
+    if arena.is_null() {
+        return;
+    }
+
+    let _ = pxs_PixelArena::from_raw(arena);
+}
+
+/// Add a `pxs_VarT` to a `pxs_PixelArena`. Upon freeing the Arena, the variable is freed aswell.
+///
+/// A variable must only be added once.
+/// 
+/// arena:BORROW
+/// var:TRANSFER
+/// result:BORROW
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_arenaput(arena: *mut pxs_PixelArena, var: pxs_VarT) -> pxs_VarT {
+    pxs_debug!("pxs_arenaput");
+    assert_initiated!();
+
+    if arena.is_null() || var.is_null() {
+        return std::ptr::null_mut();
+    }
+
+    let barena = unsafe { pxs_PixelArena::from_borrow(arena) };
+    barena.alloc(var);
+
+    var
+}
+
+/// Add a `char*` to a `pxs_PixelArena`. Upon freeing the Arena, the string is freed aswell.
+/// 
+/// This must be a string allocated by pixelscript. Either in:
+/// - `pxs_getstring`
+/// - `pxs_smart_getstring`
+/// - `pxs_debugstate`
+/// 
+/// A string must only be added once.
+/// 
+/// arena:BORROW
+/// str:TRANSFER
+/// result:BORROW
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_arena_putstr(arena: *mut pxs_PixelArena, str: *mut c_char) -> *mut c_char {
+    pxs_debug!("pxs_arena_putstr");
+    assert_initiated!();
+
+    if arena.is_null() || str.is_null() {
+        return core::ptr::null_mut();
+    }
+
+    let barena = unsafe { pxs_PixelArena::from_borrow(arena) };
+    barena.alloc_str(str); 
+
+    str
+}
+
+/// Debug state info.
+///
+/// result:OWNED
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_debugstate(runtime: pxs_Runtime) -> *mut c_char {
+    pxs_debug!("pxs_debugstate");
+    assert_initiated!();
+    with_backend!(runtime, Backend => {
+        create_raw_string!(Backend::debug())
+    })
+}
+
+/// Export everything registered on this thread in a machine readable `pxs_Map`. Useful for generating stubs and docs.
+///
+/// ```json
+/// {
+///     "modules": [{"name": "math", "functions": ["add"], "objects": ["Vector"], "constants": [{"name": "PI", "type": "Float64"}], "classes": [], "modules": []}],
+///     "objects": [{"name": "Vector", "parent": null, "methods": ["length"], "properties": ["x", "y"]}]
+/// }
+/// ```
+///
+/// Object types are listed once a object of that type was created, since methods are added per object.
+///
+/// return:OWNED
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_registry_export() -> pxs_VarT {
+    pxs_debug!("pxs_registry_export");
+    assert_initiated!();
+    registry::export().into_raw()
+}
+
+/// Issue a capability token. Code evaluated with the token (`pxs_eval_token`, or a remote console session that
+/// authenticates with it) can only call host functions matching `permissions`.
+///
+/// `permissions` is a comma separated list of `module.func`/`Type.method` names, `module.*` prefixes, or `*`.
+/// Only calls into host functions are limited, plain script code always runs.
+///
+/// Free the token with `pxs_freestr`.
+///
+/// permissions:BORROW
+/// return:OWNED
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_issue_token(permissions: *const c_char) -> *mut c_char {
+    pxs_debug!("pxs_issue_token");
+    assert_initiated!();
+
+    let permissions = if permissions.is_null() { "" } else { borrow_string!(permissions) };
+    create_raw_string!(capability::issue(permissions))
+}
+
+/// Revoke a capability token. Returns true if the token existed.
+///
+/// token:BORROW
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_revoke_token(token: *const c_char) -> bool {
+    pxs_debug!("pxs_revoke_token");
+    assert_initiated!();
+
+    if token.is_null() {
+        return false;
+    }
+    capability::revoke(borrow_string!(token))
+}
+
+/// Evaluate code with the permissions of a capability token. Useful for in-game consoles.
+///
+/// Returns a exception if the token does not exist or the code calls a host function it has no permission for.
+///
+/// token:BORROW
+/// return:OWNED
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_eval_token(token: *const c_char, script: *const c_char, rt: pxs_Runtime) -> pxs_VarT {
+    pxs_debug!("pxs_eval_token");
+    assert_initiated!();
+
+    if token.is_null() || script.is_null() {
+        return pxs_Var::null_params_ep().into_raw();
+    }
+    let permissions = match capability::lookup(borrow_string!(token)) {
+        Some(permissions) => permissions,
+        None => return pxs_Var::new_exception(catalog::message(catalog::MSG_UNKNOWN_TOKEN, "Unknown capability token.", &[])).into_raw(),
+    };
+
+    capability::with_permissions(Some(permissions), || pxs_eval(script, rt))
+}
+
+/// Start the remote scripting console on `port`. Requires the `remote-console` feature.
+///
+/// The console speaks a line based TCP protocol:
+/// 1. The client sends `auth_token` as the first line. The server answers `ok` or closes the connection.
+/// 2. The client sends `<runtime> <code>` lines, where runtime is `lua`, `python`, or `js`.
+/// 3. The server answers each line with `ok <result>` or `error <message>`. Newlines and backslashes in the answer
+///    are escaped as `\n` and `\\`.
+///
+/// Snippets are only evaluated when the host calls `pxs_remote_poll`, on the thread that calls it.
+///
+/// Returns true if the console started.
+///
+/// auth_token:BORROW
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_remote_listen(port: u16, auth_token: *const c_char) -> bool {
+    pxs_debug!("pxs_remote_listen");
+    assert_initiated!();
+
+    if auth_token.is_null() {
+        return false;
+    }
+    let auth_token = borrow_string!(auth_token);
+
+    with_feature!("remote-console", {
+        match shared::remote::listen(port, auth_token) {
+            Ok(_) => true,
+            Err(e) => {
+                eprintln!("{e}");
+                false
+            }
+        }
+    }, {
+        let _ = (port, auth_token);
+        false
+    })
+}
+
+/// Evaluate the snippets sent to the remote console and send back the results. Call this once per frame.
+///
+/// Returns the number of snippets evaluated.
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_remote_poll() -> i32 {
+    pxs_debug!("pxs_remote_poll");
+    assert_initiated!();
+
+    with_feature!("remote-console", {
+        let requests = shared::remote::take_requests();
+        let count = requests.len() as i32;
+        for request in requests {
+            let runtime: pxs_Runtime = match request.runtime.as_str() {
+                #[cfg(feature = "lua")]
+                "lua" => pxs_Runtime::pxs_Lua,
+                #[cfg(feature = "python")]
+                "python" => pxs_Runtime::pxs_Python,
+                #[cfg(feature = "js")]
+                "js" => pxs_Runtime::pxs_JavaScript,
+                _ => {
+                    let err = format!("Unknown or disabled runtime: '{}'.", request.runtime);
+                    request.reply(Err(err));
+                    continue;
+                }
+            };
+
+            let permissions = match &request.token {
+                Some(token) => match capability::lookup(token) {
+                    Some(permissions) => Some(permissions),
+                    None => {
+                        request.reply(Err("Token was revoked.".to_string()));
+                        continue;
+                    }
+                },
+                None => None,
+            };
+
+            let res: shared::PxsResult = capability::with_permissions(permissions, || {
+                with_backend!(runtime, Backend => {
+                    Backend::eval(&request.code, &shared::internal_chunk_name("remote"))
+                })
+            });
+            let res = res.and_then(|var| {
+                let mut rt = pxs_Var::new_i64(runtime.into_i64());
+                let mut var = var;
+                let string = own_var!(pxs_tostring(&mut rt, &mut var));
+                if string.is_exception() {
+                    return pxs_error!("{}", string.get_string()?);
+                }
+                string.get_string()
+            });
+            request.reply(res);
+        }
+        count
+    }, {
+        0
+    })
+}
+
+/// Stop the remote scripting console and close all connections.
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_remote_stop() {
+    pxs_debug!("pxs_remote_stop");
+    assert_initiated!();
+
+    with_feature!("remote-console", {
+        shared::remote::stop();
+    });
+}
+
+/// Turn recording of API usage on or off. Off by default.
+///
+/// While on, every call from a script into a module function or object method is counted. Get the counts with
+/// `pxs_usage_report`.
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_usage_record(enabled: bool) {
+    pxs_debug!("pxs_usage_record");
+    assert_initiated!();
+    set_usage_recording(enabled);
+}
+
+/// Clear recorded API usage.
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_usage_reset() {
+    pxs_debug!("pxs_usage_reset");
+    assert_initiated!();
+    reset_usage();
+}
+
+/// Get a `pxs_Map` of `"module.func"`/`"Type.method"` to the number of times it was called.
+///
+/// Functions registered on this thread that were never called are included with 0 calls.
+///
+/// return:OWNED
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_usage_report() -> pxs_VarT {
+    pxs_debug!("pxs_usage_report");
+    assert_initiated!();
+
+    let report = pxs_Var::new_map();
+    let map = report.get_map().unwrap();
+    for (name, calls) in usage_report() {
+        map.add_item(pxs_Var::new_string(name), pxs_Var::new_i64(calls as i64));
+    }
+    report.into_raw()
+}
+
+/// Call GC for all backends.
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_garbagecollect() {
+    pxs_debug!("pxs_gc");
+    assert_initiated!();
+
+    with_feature!("lua", {
+        LuaScripting::garbage_collect();
+    });
+    with_feature!("python", {
+        PythonScripting::garbage_collect();
+    });
+    with_feature!("js", {
+        JSScripting::garbage_collect();
+    });
+}
+
+/// Tell pixelscript the host is low on memory, i.e. from a platform low memory warning. Every runtime runs a full
+/// garbage collection, `pxs_MemoryCritical` also drops the cached object bridges, the compiled chunk cache and the
+/// `pxs_noise` permutation tables of this thread.
+///
+/// Nothing the host still references is released: vars retained with `pxs_retain`, host objects, functions made
+/// with `pxs_newfunc`, sync field ids and the sync buffer.
+///
+/// Returns the bytes reclaimed by runtimes that can measure it (Lua and JavaScript).
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_notify_memory_pressure(level: pxs_MemoryPressure) -> u64 {
+    pxs_debug!("pxs_notify_memory_pressure");
+    assert_initiated!();
+
+    let object_types = pressure::bridges_to_drop(level);
+    let mut reclaimed = 0;
+    with_feature!("lua", {
+        reclaimed += LuaScripting::trim_memory(&object_types);
+    });
+    with_feature!("python", {
+        reclaimed += PythonScripting::trim_memory(&object_types);
+    });
+    with_feature!("js", {
+        reclaimed += JSScripting::trim_memory(&object_types);
+    });
+    let _ = object_types;
+    pressure::trim_caches(level);
+
+    reclaimed as u64
+}
+
+/// Get the host IDX from a `pxs_HostObject`.
+/// 
+/// if result is < 0 then that means it is not a object.
+/// 
+/// var: BORROW
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_getidx(var: pxs_VarT) -> i32 {
+    pxs_debug!("pxs_getidx");
+    assert_initiated!();
+
+    if var.is_null() {
+        return -1;
+    }
+
+    let bvar = borrow_var!(var);
+    if !bvar.is_host_object() {
+        return -1;
+    }
+
+    bvar.get_host_idx()
+}
+
+/// Get a `pxs_VarT` from args without needing to worry about index checks.
+/// 
+/// Literraly does `pxs_listget(args, idx + 1)`
+/// 
+/// args: BORROW
+/// result: BORROW&NULLABLE
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_arg(args: pxs_VarT, idx: i32) -> pxs_VarT {
+    pxs_debug!("pxs_arg");
+    assert_initiated!();
+    pxs_listget(args, idx + 1)
+}
+
+/// Get the `pxs_VarT` runtime from args.
+/// 
+/// Does `pxs_listget(args, 0)`
+/// 
+/// args: BORROW
+/// result: BORROW&NULLABLE
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_getrt(args: pxs_VarT) -> pxs_VarT {
+    pxs_debug!("pxs_getrt");
+    assert_initiated!();
+    pxs_listget(args, 0)
+}
+
+/// Get the length of args without the runtime.
+/// 
+/// args: BORROW
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_argc(args: pxs_VarT) -> usize {
+    pxs_debug!("pxs_argc");
+    assert_initiated!();
+    let len = pxs_listlen(args);
+    if len <= 0 {
+        return 0;
+    } else {
+        return (len - 1) as usize;
+    }
+}
+
+/// Create a `pxs_List` of u8. i.e. bytes.
+/// 
+/// Each byte goes into the scripting language as a number. Use `pxs_newbytebuf` for binary data.
+/// 
+/// data: BORROW
+/// result: OWNED
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_newbytes(data: pxs_Opaque, el_size: usize, size: usize) -> pxs_VarT {
+    pxs_debug!("pxs_newbytes");
+    assert_initiated!();
+
+    if data.is_null() {
+        return pxs_newlist();
+    }
+
+    let ptr = data as *mut u8;
+    let total_bytes = el_size * size;
+    let raw_bytes: &mut [u8] = unsafe { core::slice::from_raw_parts_mut(ptr, total_bytes) };
+
+    let mut list = vec![];
+    for i in raw_bytes {
+        list.push(pxs_Var::new_byte(i.clone()));
+    }
+
+    pxs_Var::new_list_with(list).into_raw()
+}
+
+/// Create a `pxs_Bytes` by copying `len` bytes from `data`.
+/// 
+/// Lua (string), Python (bytes), JS (Uint8Array)
+/// 
+/// data: BORROW
+/// result: OWNED
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_newbytebuf(data: pxs_Opaque, len: usize) -> pxs_VarT {
+    pxs_debug!("pxs_newbytebuf");
+    assert_initiated!();
+
+    if data.is_null() || len == 0 {
+        return pxs_Var::new_bytes(vec![]).into_raw();
+    }
+
+    let raw_bytes: &[u8] = unsafe { core::slice::from_raw_parts(data as *const u8, len) };
+    pxs_Var::new_bytes(raw_bytes.to_vec()).into_raw()
+}
+
+/// Get the data of a `pxs_Bytes`. Writes the length into `out_len` if it is not null.
+/// 
+/// Returns null if `var` is not `pxs_Bytes`. The pointer is valid as long as `var` is.
+/// 
+/// var: BORROW
+/// out_len: BORROW
+/// result: BORROW
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_getbytes(var: pxs_VarT, out_len: *mut usize) -> *const u8 {
+    pxs_debug!("pxs_getbytes");
+    assert_initiated!();
+
+    if var.is_null() {
+        return ptr::null();
+    }
+
+    let bytes = match borrow_var!(var).get_bytes() {
+        Some(bytes) => bytes,
+        None => return ptr::null(),
+    };
+    if !out_len.is_null() {
+        unsafe { *out_len = bytes.len() };
+    }
+
+    bytes.as_ptr()
+}
+
+/// Get the length of a `pxs_Bytes`. Returns -1 if `var` is not `pxs_Bytes`.
+/// 
+/// var: BORROW
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_byteslen(var: pxs_VarT) -> i64 {
+    pxs_debug!("pxs_byteslen");
+    assert_initiated!();
+
+    if var.is_null() {
+        return -1;
+    }
+
+    match borrow_var!(var).get_bytes() {
+        Some(bytes) => bytes.len() as i64,
+        None => -1,
+    }
+}
+
+/// Create a `pxs_TypedArray` of `len` elements of `kind` by copying them from `data`. A null `data` makes a array of
+/// zeros.
+///
+/// Lua (table), Python (list), JS (Float32Array, Float64Array, Int32Array, Uint8Array)
+///
+/// data: BORROW
+/// result: OWNED
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_newtypedarray(kind: pxs_ArrayKind, data: pxs_Opaque, len: usize) -> pxs_VarT {
+    pxs_debug!("pxs_newtypedarray");
+    assert_initiated!();
+
+    if data.is_null() || len == 0 {
+        return pxs_Var::new_typed_array(pxs_VarTypedArray::new(kind, len)).into_raw();
+    }
+
+    pxs_Var::new_typed_array(unsafe { pxs_VarTypedArray::copy_from(kind, data, len) }).into_raw()
+}
+
+/// Get the data of a `pxs_TypedArray`, it can be read and written in place. Writes the kind into `out_kind` and the
+/// number of elements into `out_len` if they are not null.
+///
+/// Returns null if `var` is not `pxs_TypedArray`. The pointer is valid as long as `var` is.
+///
+/// var: BORROW
+/// out_kind: BORROW
+/// out_len: BORROW
+/// result: BORROW
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_gettypedarray(var: pxs_VarT, out_kind: *mut pxs_ArrayKind, out_len: *mut usize) -> pxs_Opaque {
+    pxs_debug!("pxs_gettypedarray");
+    assert_initiated!();
+
+    if var.is_null() {
+        return ptr::null_mut();
+    }
+
+    let array = match borrow_var!(var).get_typed_array() {
+        Some(array) => array,
+        None => return ptr::null_mut(),
+    };
+    if !out_kind.is_null() {
+        unsafe { *out_kind = array.kind() };
+    }
+    if !out_len.is_null() {
+        unsafe { *out_len = array.len() };
+    }
+
+    array.as_mut_ptr()
+}
+
+/// Get the number of elements of a `pxs_TypedArray`. Returns -1 if `var` is not `pxs_TypedArray`.
+///
+/// var: BORROW
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_typedarraylen(var: pxs_VarT) -> i64 {
+    pxs_debug!("pxs_typedarraylen");
+    assert_initiated!();
+
+    if var.is_null() {
+        return -1;
+    }
+
+    match borrow_var!(var).get_typed_array() {
+        Some(array) => array.len() as i64,
+        None => -1,
+    }
+}
+
+/// Convert a `pxs_List` of numbers (i.e. one returned by a script) into a `pxs_TypedArray` of `kind`. Floats are
+/// truncated and out of range values wrap, like `pxs_getint`. Returns a exception if `list` is not a list of numbers.
+///
+/// list: BORROW
+/// result: OWNED
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_typedarray_fromlist(kind: pxs_ArrayKind, list: pxs_VarT) -> pxs_VarT {
+    pxs_debug!("pxs_typedarray_fromlist");
+    assert_initiated!();
+
+    if list.is_null() {
+        return pxs_Var::null_param_ep("list").into_raw();
+    }
+
+    let list = borrow_var!(list);
+    let Some(items) = list.get_list() else {
+        return pxs_Var::incorrect_type_ep(pxs_VarType::pxs_List, list.tag).into_raw();
+    };
+    match pxs_VarTypedArray::from_list(kind, items) {
+        Ok(array) => pxs_Var::new_typed_array(array),
+        Err(err) => pxs_Var::new_exception(err),
+    }
+    .into_raw()
+}
+
+/// Copy a `pxs_TypedArray` into a new `pxs_List` of numbers. Returns a exception if `var` is not `pxs_TypedArray`.
+///
+/// var: BORROW
+/// result: OWNED
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_typedarray_tolist(var: pxs_VarT) -> pxs_VarT {
+    pxs_debug!("pxs_typedarray_tolist");
+    assert_initiated!();
+
+    if var.is_null() {
+        return pxs_Var::null_param_ep("var").into_raw();
+    }
+
+    let var = borrow_var!(var);
+    match var.get_typed_array() {
+        Some(array) => array.to_list().into_raw(),
+        None => pxs_Var::incorrect_type_ep(pxs_VarType::pxs_TypedArray, var.tag).into_raw(),
+    }
+}
+
+/// Make a diff between two snapshots (`pxs_Bytes`), i.e. periodic autosaves of world state. Store the diff instead
+/// of `new` and rebuild `new` with `pxs_snapshot_apply(old, diff)`.
+///
+/// Works with any binary format, small changes to `old` make small diffs. Returns a exception if `old` or `new` are
+/// not `pxs_Bytes`.
+///
+/// old: BORROW
+/// new: BORROW
+/// return: OWNED
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_snapshot_diff(old: pxs_VarT, new: pxs_VarT) -> pxs_VarT {
+    pxs_debug!("pxs_snapshot_diff");
+    assert_initiated!();
+
+    if old.is_null() || new.is_null() {
+        return pxs_Var::null_params_ep().into_raw();
+    }
+
+    let (old, new) = (borrow_var!(old), borrow_var!(new));
+    let (Some(old_bytes), Some(new_bytes)) = (old.get_bytes(), new.get_bytes()) else {
+        let found = if old.is_bytes() { new.tag } else { old.tag };
+        return pxs_Var::incorrect_type_ep(pxs_VarType::pxs_Bytes, found).into_raw();
+    };
+
+    pxs_Var::new_bytes(delta::diff(old_bytes, new_bytes)).into_raw()
+}
+
+/// Apply a diff made by `pxs_snapshot_diff` to the snapshot it was made from. Returns the new snapshot as
+/// `pxs_Bytes`, or a exception if the diff is invalid or was made from a different snapshot.
+///
+/// base: BORROW
+/// diff: BORROW
+/// return: OWNED
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_snapshot_apply(base: pxs_VarT, diff: pxs_VarT) -> pxs_VarT {
+    pxs_debug!("pxs_snapshot_apply");
+    assert_initiated!();
+
+    if base.is_null() || diff.is_null() {
+        return pxs_Var::null_params_ep().into_raw();
+    }
+
+    let (base, diff) = (borrow_var!(base), borrow_var!(diff));
+    let (Some(base_bytes), Some(diff_bytes)) = (base.get_bytes(), diff.get_bytes()) else {
+        let found = if base.is_bytes() { diff.tag } else { base.tag };
+        return pxs_Var::incorrect_type_ep(pxs_VarType::pxs_Bytes, found).into_raw();
+    };
+
+    match delta::apply(base_bytes, diff_bytes) {
+        Ok(bytes) => pxs_Var::new_bytes(bytes),
+        Err(err) => pxs_Var::new_exception(err),
+    }
+    .into_raw()
+}
+
+/// Encode a var as MessagePack, for exchanging data faster than JSON (i.e. syncing entity state). Returns a
+/// `pxs_Bytes` with a 4 byte big endian length followed by the MessagePack data, or a exception for values that are
+/// not data (Objects, Functions, ...). Map keys are sorted, so equal vars give equal bytes.
+///
+/// var:BORROW
+/// return:OWNED
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_tomsgpack(var: pxs_VarT) -> pxs_VarT {
+    pxs_debug!("pxs_tomsgpack");
+    assert_initiated!();
+
+    if var.is_null() {
+        return pxs_Var::null_param_ep("var").into_raw();
+    }
+
+    match msgpack::encode(borrow_var!(var)) {
+        Ok(bytes) => pxs_Var::new_bytes(bytes),
+        Err(err) => pxs_Var::new_exception(err),
+    }
+    .into_raw()
+}
+
+/// Decode a `pxs_Bytes` made by `pxs_tomsgpack`. Returns a exception if the data is invalid.
+///
+/// bytes:BORROW
+/// return:OWNED
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_frommsgpack(bytes: pxs_VarT) -> pxs_VarT {
+    pxs_debug!("pxs_frommsgpack");
+    assert_initiated!();
+
+    if bytes.is_null() {
+        return pxs_Var::null_param_ep("bytes").into_raw();
+    }
+
+    let bytes = borrow_var!(bytes);
+    let Some(data) = bytes.get_bytes() else {
+        return pxs_Var::incorrect_type_ep(pxs_VarType::pxs_Bytes, bytes.tag).into_raw();
+    };
+    match msgpack::decode(data) {
+        Ok(var) => var,
+        Err(err) => pxs_Var::new_exception(err),
+    }
+    .into_raw()
+}
+
+/// Register a script object (Lua table, Python object, JS object) as a sync target.
+/// 
+/// Returns the target id used in sync records, or -1 if `target` is not a object. The target keeps the object alive until
+/// `pxs_sync_unbind`, `pxs_sync_clear` or `pxs_finalize`.
+/// 
+/// rt: BORROW
+/// target: BORROW
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_sync_bind(rt: pxs_VarT, target: pxs_VarT) -> i64 {
+    pxs_debug!("pxs_sync_bind");
+    assert_initiated!();
+
+    if rt.is_null() || target.is_null() {
+        return -1;
+    }
+
+    match sync::bind(borrow_var!(rt), borrow_var!(target)) {
+        Some(id) => id as i64,
+        None => -1,
+    }
+}
+
+/// Remove a sync target. Returns true if it existed.
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_sync_unbind(id: u32) -> bool {
+    pxs_debug!("pxs_sync_unbind");
+    assert_initiated!();
+    sync::unbind(id)
+}
+
+/// Get the id of a field name for sync records. Registering the same name twice returns the same id.
+/// 
+/// Returns -1 if `name` is null.
+/// 
+/// name: BORROW
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_sync_field(name: *const c_char) -> i32 {
+    pxs_debug!("pxs_sync_field");
+    assert_initiated!();
+
+    if name.is_null() {
+        return -1;
+    }
+    sync::field(borrow_string!(name)) as i32
+}
+
+/// Begin a sync batch. Returns a zeroed buffer of `len` bytes for the host to write records into.
+/// 
+/// Each record is `[u32 target][u16 field][u8 pxs_SyncType][payload]`, little endian. Strings are a u32 length
+/// followed by the UTF-8 bytes. The buffer is valid until the next `pxs_sync_begin`.
+/// 
+/// result: BORROW
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_sync_begin(len: usize) -> *mut u8 {
+    pxs_debug!("pxs_sync_begin");
+    assert_initiated!();
+    sync::begin(len)
+}
+
+/// Apply every record written since `pxs_sync_begin` to the sync targets.
+/// 
+/// Returns the number of fields set. Returns -1 without applying anything if the buffer is malformed or references
+/// a unknown target or field.
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_sync_end() -> i64 {
+    pxs_debug!("pxs_sync_end");
+    assert_initiated!();
+
+    match sync::end() {
+        Ok(applied) => applied as i64,
+        #[allow(unused)]
+        Err(e) => {
+            pxs_debug!("pxs_sync_end failed: {e}");
+            -1
+        }
+    }
+}
+
+/// Drop every sync target, field and buffer on this thread. `pxs_finalize` calls this for you.
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_sync_clear() {
+    pxs_debug!("pxs_sync_clear");
+    assert_initiated!();
+    sync::clear();
+}
+
+/// Get the memory size (in bytes) of a `pxs_VarT`
+///
+/// var: BORROW
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_varsize(var: pxs_VarT) -> usize {
+    pxs_debug!("pxs_varsize");
+    assert_initiated!();
+
+    if var.is_null() {
+        return 0;
+    }
+
+    let bvar = borrow_var!(var);
+    bvar.get_size()
+}
+
+/// Copy a `pxs_VarT` into a list of u8. i.e. bytes, if values are:
+///   - `pxs_UInt64`
+///   - `pxs_Int64`
+///   - `pxs_Float64`
+///   - `pxs_UInt32`
+///   - `pxs_Int32`
+///   - `pxs_Float32`
+///   - `pxs_Bool`
+///   - `pxs_String`
+///   - `pxs_List`
+///   - `pxs_Byte`
+///   - `pxs_Bytes`
+///   - `pxs_TypedArray`
+/// 
+/// var: BORROW
+/// data_ptr: BORROW
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_copybytes(var: pxs_VarT, data_ptr: pxs_Opaque) {
+    pxs_debug!("pxs_copybytes");
+    assert_initiated!();
+
+    if var.is_null() || data_ptr.is_null() {
+        strict::misuse("pxs_copybytes", "var or data_ptr is null");
+        return;
+    }
+
+    let bvar = borrow_var!(var);
+    let bytes_ptr = data_ptr as *mut u8;
+
+    unsafe { bvar.copy_bytes(bytes_ptr) };
+}
+
+/// Copy a `pxs_String` into a char*.
+/// 
+/// var: BORROW
+/// str_ptr: BORROW
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_copystring(var: pxs_VarT, str_ptr: *mut c_char) {
+    pxs_debug!("pxs_copystring");
+    assert_initiated!();
+
+    if !pxs_varis(var, pxs_VarType::pxs_String) {
+        strict::misuse("pxs_copystring", "var is not a string");
+        return;
+    }
+
+    pxs_copybytes(var, str_ptr as pxs_Opaque);
+}
+
+/// Get a string (char*) from `pxs_String`. And calls `pxs_tostring` automatically if not already a string.
+/// Runtime is required.
+/// 
+/// Free the result using `pxs_freestr`.
+/// 
+/// rt: BORROW
+/// str: BORROW
+/// result: OWNED
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_smart_getstring(rt: pxs_VarT, str: pxs_VarT) -> *mut c_char {
+    pxs_debug!("pxs_smart_getstring");
+    assert_initiated!();
+    
+    if rt.is_null() || str.is_null() {
+        return core::ptr::null_mut();
+    }
+
+    let mut bstr = str;
+    let mut string_var_ptr: pxs_VarT = core::ptr::null_mut();
+
+    if !pxs_varis(bstr, pxs_VarType::pxs_String) {
+        string_var_ptr = pxs_tostring(rt, str);
+        bstr = string_var_ptr;
+    }
+
+    let str_value = pxs_getstring(bstr);
+
+    if !string_var_ptr.is_null() {
+        pxs_freevar(string_var_ptr);
+    }
+
+    str_value
+}
+
+/// Copy a `pxs_String` memory into a char*. Calls `pxs_tostring` automatically if not already a string.
+/// Runtime is required.
+/// 
+/// rt: BORROW
+/// str: BORROW
+/// str_ptr: BORROW
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_smart_copystring(rt: pxs_VarT, str: pxs_VarT, str_ptr: *mut c_char) {
+    pxs_debug!("pxs_smart_copystring");
+    assert_initiated!();
+
+    if rt.is_null() || str.is_null() || str_ptr.is_null() {
+        return;
+    }
+
+    let mut bstr = str;
+    let mut string_var_ptr: pxs_VarT = core::ptr::null_mut();
+
+    if !pxs_varis(bstr, pxs_VarType::pxs_String) {
+        string_var_ptr = pxs_tostring(rt, bstr);
+        bstr = string_var_ptr;
+    }
+
+    pxs_copystring(bstr, str_ptr);
+
+    // Free
+    if !string_var_ptr.is_null() {
+        pxs_freevar(string_var_ptr);
+    }
+}
+
+/// Get the `pxs_VarType` of a `pxs_VarT`
+///
+/// var:BORROW
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_vartype(var: pxs_VarT) -> pxs_VarType {
+    pxs_debug!("pxs_vartype");
+    assert_initiated!();
+
+    if var.is_null() {
+        pxs_VarType::pxs_Null
+    } else {
+       unsafe { (*var).tag }
+    }
+}
+
+// ====================================== Core functions Start =======================================
+
+/// Encode `args[0]` into a JSON string, `args[1]` is a optional indent (spaces per level). Returns a `pxs_String` or
+/// a exception for values that can not be encoded (i.e. Functions).
+///
+/// Note: `pxs_json` is already in each scripting language. This is a host language wrapper for calling it easily.
+///
+/// rt:BORROW
+/// args:TRANSFER
+/// return:OWNED
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_json_encode(rt: pxs_VarT, args: pxs_VarT) -> pxs_VarT {
+    pxs_debug!("pxs_json_encode");
+    assert_initiated!();
+    with_feature!(
+        "pxs_json",
+        {
+            unsafe {
+                if !pxs_core::is_valid_pxs_function(rt, args) {
+                    return pxs_Var::new_exception("Not a valid core call").into_raw();
+                }
+            }
+            pxs_core::pxs_json::encode_args(rt, args)
+        },
+        { pxs_Var::feature_not_enabled_ep("pxs_json").into_raw() }
+    )
+}
+
+/// Decode the JSON `pxs_String` `args[0]`. Objects become `pxs_Map`s and arrays `pxs_List`s.
+///
+/// Note: `pxs_json` is already in each scripting language. This is a host language wrapper for calling it easily.
+///
+/// rt:BORROW
+/// args:TRANSFER
+/// return:OWNED
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_json_decode(rt: pxs_VarT, args: pxs_VarT) -> pxs_VarT {
+    pxs_debug!("pxs_json_decode");
+    assert_initiated!();
+    with_feature!(
+        "pxs_json",
+        {
+            unsafe {
+                if !pxs_core::is_valid_pxs_function(rt, args) {
+                    return pxs_Var::new_exception("Not a valid core call").into_raw();
+                }
+            }
+            pxs_core::pxs_json::decode_args(args)
+        },
+        { pxs_Var::feature_not_enabled_ep("pxs_json").into_raw() }
+    )
+}
+
+/// Initialize the `pxs_mem` module.
+/// 
+/// This needs to be called in each new thread too. Should only be called once per thread.
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_meminit() {
+    pxs_debug!("pxs_meminit");
+    assert_initiated!();
+
+    with_feature!("pxs_mem", {
+        pxs_core::pxs_mem::init();
+    }, {
+        panic!("pxs_mem is not enabled.");
+    });
+}
+
+/// Initialize the `pxs_template` module.
+/// 
+/// This needs to be called in each new thread too. Should only be called once per thread.
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_templateinit() {
+    pxs_debug!("pxs_templateinit");
+    assert_initiated!();
+
+    with_feature!("pxs_template", {
+        pxs_core::pxs_template::init();
+    }, {
+        panic!("pxs_template is not enabled.");
+    });
+}
+
+/// Initialize the `pxs_csv` module.
+/// 
+/// This needs to be called in each new thread too. Should only be called once per thread.
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_csvinit() {
+    pxs_debug!("pxs_csvinit");
+    assert_initiated!();
+
+    with_feature!("pxs_csv", {
+        pxs_core::pxs_csv::init();
+    }, {
+        panic!("pxs_csv is not enabled.");
+    });
 }
 
-/// Get length of a `pxs_Map`.
-///
-/// -1 is invalid length.
-///
-/// map:BORROW
+/// Initialize the `pxs_color` module.
+/// 
+/// This needs to be called in each new thread too. Should only be called once per thread.
 #[unsafe(no_mangle)]
-pub extern "C" fn pxs_maplen(map: pxs_VarT) -> i32 {
-    pxs_debug!("pxs_maplen");
-    if map.is_null() {
-        return -1;
-    }
+pub extern "C" fn pxs_colorinit() {
+    pxs_debug!("pxs_colorinit");
+    assert_initiated!();
 
-    let bvar = borrow_var!(map);
-    if bvar.is_map() {
-        bvar.get_map().unwrap().len() as i32
-    } else {
-        -1
-    }
+    with_feature!("pxs_color", {
+        pxs_core::pxs_color::init();
+    }, {
+        panic!("pxs_color is not enabled.");
+    });
 }
 
-/// Get the keys of a `pxs_Map`.
-///
-/// Returns a `pxs_List` or `pxs_Null` Which is owned by caller.
-///
-/// map:BORROW
-/// return:OWNED
+/// Initialize the `pxs_units` module.
+/// 
+/// This needs to be called in each new thread too. Should only be called once per thread.
 #[unsafe(no_mangle)]
-pub extern "C" fn pxs_mapkeys(map: pxs_VarT) -> pxs_VarT {
-    pxs_debug!("pxs_mapkeys");
-    if map.is_null() {
-        return pxs_Var::null_param_ep("map").into_raw();
-    }
+pub extern "C" fn pxs_unitsinit() {
+    pxs_debug!("pxs_unitsinit");
+    assert_initiated!();
 
-    // Check is map
-    let map = borrow_var!(map);
-    if !map.is_map() {
-        return pxs_Var::incorrect_type_ep(pxs_VarType::pxs_Map, map.tag).into_raw();
-    }
-    let internals = map.get_map().unwrap();
+    with_feature!("pxs_units", {
+        pxs_core::pxs_units::init();
+    }, {
+        panic!("pxs_units is not enabled.");
+    });
+}
 
-    let result = pxs_Var::new_list();
-    let list = result.get_list().unwrap();
+/// Initialize the `pxs_geom` module.
+/// 
+/// This needs to be called in each new thread too. Should only be called once per thread.
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_geominit() {
+    pxs_debug!("pxs_geominit");
+    assert_initiated!();
 
-    // Get keys..
-    let keys = internals.keys();
-    for k in keys {
-        // Deep copy the key over.
-        list.add_item(k.clone());
-    }
+    with_feature!("pxs_geom", {
+        pxs_core::pxs_geom::init();
+    }, {
+        panic!("pxs_geom is not enabled.");
+    });
+}
 
-    result.into_raw()
+/// Initialize the `pxs_noise` module.
+/// 
+/// This needs to be called in each new thread too. Should only be called once per thread.
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_noiseinit() {
+    pxs_debug!("pxs_noiseinit");
+    assert_initiated!();
+
+    with_feature!("pxs_noise", {
+        pxs_core::pxs_noise::init();
+    }, {
+        panic!("pxs_noise is not enabled.");
+    });
 }
 
-/// Get a value in a map from a key.
+/// Set the grid used by the `pxs_path` module.
 ///
-/// Result is not owned by caller. Use `pxs_newcopy` to transfer ownership.
-///
-/// map:BORROW
-/// key:BORROW
-/// return:BORROW
+/// `cost` is called with a cell and should return the cost of entering it (1.0 for normal ground).
+/// Return a negative number for blocked cells. The search assumes no cell costs less than 1.0, scripts pass the
+/// `min_cost` option to `find` for cheaper cells.
 #[unsafe(no_mangle)]
-pub extern "C" fn pxs_mapget(map: pxs_VarT, key: pxs_VarT) -> pxs_VarT {
-    pxs_debug!("pxs_mapget");
-
-    if map.is_null() || key.is_null() {
-        return pxs_Var::null_params_ep().into_raw();
-    }
+pub extern "C" fn pxs_path_setprovider(width: i32, height: i32, cost: pxs_PathCostFn) {
+    pxs_debug!("pxs_path_setprovider");
+    assert_initiated!();
+    set_path_provider(width, height, cost);
+}
 
-    // check map
-    let map = borrow_var!(map);
-    if !map.is_map() {
-        return pxs_Var::incorrect_type_ep(pxs_VarType::pxs_Map, map.tag).into_raw();
-    }
-    let internal = map.get_map().unwrap();
-    let res = internal.get_item(borrow_var!(key));
+/// Initialize the `pxs_path` module.
+/// 
+/// This needs to be called in each new thread too. Should only be called once per thread.
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_pathinit() {
+    pxs_debug!("pxs_pathinit");
+    assert_initiated!();
 
-    // Return a const pxs_Var.
-    if let Some(res) = res {
-        res as *const pxs_Var as *mut pxs_Var
-    } else {
-        pxs_Var::item_not_found_ep().into_raw()
-    }
+    with_feature!("pxs_path", {
+        pxs_core::pxs_path::init();
+    }, {
+        panic!("pxs_path is not enabled.");
+    });
 }
 
-/// Insert a item into a list at a certain index, shifting all other items to the right.
-///
-/// Item ownership is transferred.
+/// Set the callbacks used by the `pxs_audio` module.
 ///
-/// list:BORROW
-/// item:TRANSFER
+/// `play` should return a id for the started voice, this id is passed to `set_param` and `stop`.
 #[unsafe(no_mangle)]
-pub extern "C" fn pxs_listinsert(list: pxs_VarT, index: usize, item: pxs_VarT) {
-    pxs_debug!("pxs_listinsert");
+pub extern "C" fn pxs_audio_setcallbacks(play: pxs_AudioPlayFn, set_param: pxs_AudioParamFn, stop: pxs_AudioStopFn) {
+    pxs_debug!("pxs_audio_setcallbacks");
+    assert_initiated!();
+    set_audio_callbacks(play, set_param, stop);
+}
 
-    if list.is_null() || item.is_null() {
-        // TODO: log something?
-        return;
-    }
+/// Initialize the `pxs_audio` module.
+/// 
+/// This needs to be called in each new thread too. Should only be called once per thread.
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_audioinit() {
+    pxs_debug!("pxs_audioinit");
+    assert_initiated!();
 
-    // Check is list
-    let list = borrow_var!(list);
-    if !list.is_list() {
-        // TODO: log something?
-        return;
-    }
+    with_feature!("pxs_audio", {
+        pxs_core::pxs_audio::init();
+    }, {
+        panic!("pxs_audio is not enabled.");
+    });
+}
 
-    let item = own_var!(item);
-    let internal = list.get_list().unwrap();
-    internal.insert_item(index, item);
+/// Set the callback that receives bound value changes from the `pxs_ui` module.
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_ui_setcallback(func: pxs_UiChangeFn) {
+    pxs_debug!("pxs_ui_setcallback");
+    assert_initiated!();
+    set_ui_change(func);
 }
 
-/// Create a new arena in memory.
-/// This does not return anything, it simply creates a scope that will allocate pxs_Var memory.
-/// when finished call, `pxs_freearena`
+/// Poll every `pxs_ui` binding on this thread and notify the host of values changed since their last notification.
 ///
-/// result:OWNED
+/// Script writes are not watched, call this once per frame (or after running scripts) to pick them up.
+/// Returns the number of notifications sent.
 #[unsafe(no_mangle)]
-pub extern "C" fn pxs_newarena() -> *mut pxs_PixelArena {
-    pxs_debug!("pxs_newarena");
+pub extern "C" fn pxs_ui_poll() -> i32 {
+    pxs_debug!("pxs_ui_poll");
     assert_initiated!();
-
-    let arena = pxs_PixelArena::new();
-    arena.into_raw()
+    with_feature!("pxs_ui", {
+        pxs_core::pxs_ui::poll() as i32
+    }, {
+        0
+    })
 }
 
-/// Free arena. Upon freeing all variables allocated since `pxs_newarena` will be freed.
+/// Drop every `pxs_ui` binding on this thread.
 ///
-/// arena:TRANSFER
+/// Bindings keep script objects alive, call this before clearing or stopping a runtime. `pxs_finalize` calls this for you.
 #[unsafe(no_mangle)]
-pub extern "C" fn pxs_freearena(arena: *mut pxs_PixelArena) {
-    pxs_debug!("pxs_freearena");
+pub extern "C" fn pxs_ui_clear() {
+    pxs_debug!("pxs_ui_clear");
     assert_initiated!();
+    with_feature!("pxs_ui", {
+        pxs_core::pxs_ui::clear();
+    });
+}
 
-    if arena.is_null() {
-        return;
-    }
+/// Initialize the `pxs_ui` module.
+/// 
+/// This needs to be called in each new thread too. Should only be called once per thread.
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_uiinit() {
+    pxs_debug!("pxs_uiinit");
+    assert_initiated!();
 
-    let _ = pxs_PixelArena::from_raw(arena);
+    with_feature!("pxs_ui", {
+        pxs_core::pxs_ui::init();
+    }, {
+        panic!("pxs_ui is not enabled.");
+    });
 }
 
-/// Add a `pxs_VarT` to a `pxs_PixelArena`. Upon freeing the Arena, the variable is freed aswell.
+/// Migrate save data from version `from` to version `to` using the migrations registered by scripts
+/// with `pxs_save.register_migration`.
 ///
-/// A variable must only be added once.
-/// 
-/// arena:BORROW
-/// var:TRANSFER
-/// result:BORROW
+/// Returns the migrated data or a exception if no chain of migrations exists.
+///
+/// data:BORROW
+/// return:OWNED
 #[unsafe(no_mangle)]
-pub extern "C" fn pxs_arenaput(arena: *mut pxs_PixelArena, var: pxs_VarT) -> pxs_VarT {
-    pxs_debug!("pxs_arenaput");
+pub extern "C" fn pxs_migrate_save(data: pxs_VarT, from: i64, to: i64) -> pxs_VarT {
+    pxs_debug!("pxs_migrate_save");
     assert_initiated!();
 
-    if arena.is_null() || var.is_null() {
-        return std::ptr::null_mut();
+    if data.is_null() {
+        return pxs_Var::null_param_ep("data").into_raw();
     }
 
-    let barena = unsafe { pxs_PixelArena::from_borrow(arena) };
-    barena.alloc(var);
+    with_feature!("pxs_save", {
+        match pxs_core::pxs_save::migrate(borrow_var!(data), from, to) {
+            Ok(var) => var,
+            Err(e) => pxs_Var::new_exception(e),
+        }
+        .into_raw()
+    }, {
+        ptr::null_mut()
+    })
+}
 
-    var
+/// Drop every migration registered on this thread.
+///
+/// Migrations keep script functions alive, call this before clearing or stopping a runtime. `pxs_finalize` calls this for you.
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_save_clear() {
+    pxs_debug!("pxs_save_clear");
+    assert_initiated!();
+    with_feature!("pxs_save", {
+        pxs_core::pxs_save::clear();
+    });
 }
 
-/// Add a `char*` to a `pxs_PixelArena`. Upon freeing the Arena, the string is freed aswell.
-/// 
-/// This must be a string allocated by pixelscript. Either in:
-/// - `pxs_getstring`
-/// - `pxs_smart_getstring`
-/// - `pxs_debugstate`
-/// 
-/// A string must only be added once.
+/// Initialize the `pxs_save` module.
 /// 
-/// arena:BORROW
-/// str:TRANSFER
-/// result:BORROW
+/// This needs to be called in each new thread too. Should only be called once per thread.
 #[unsafe(no_mangle)]
-pub extern "C" fn pxs_arena_putstr(arena: *mut pxs_PixelArena, str: *mut c_char) -> *mut c_char {
-    pxs_debug!("pxs_arena_putstr");
+pub extern "C" fn pxs_saveinit() {
+    pxs_debug!("pxs_saveinit");
     assert_initiated!();
 
-    if arena.is_null() || str.is_null() {
-        return core::ptr::null_mut();
-    }
-
-    let barena = unsafe { pxs_PixelArena::from_borrow(arena) };
-    barena.alloc_str(str); 
-
-    str
+    with_feature!("pxs_save", {
+        pxs_core::pxs_save::init();
+    }, {
+        panic!("pxs_save is not enabled.");
+    });
 }
 
-/// Debug state info.
+/// Set the callbacks used by the `pxs_clock` module.
 ///
-/// result:OWNED
+/// `game_time` and `real_time` return seconds, `frame` returns the current frame index.
 #[unsafe(no_mangle)]
-pub extern "C" fn pxs_debugstate(runtime: pxs_Runtime) -> *mut c_char {
-    pxs_debug!("pxs_debugstate");
+pub extern "C" fn pxs_clock_setcallbacks(game_time: pxs_ClockTimeFn, real_time: pxs_ClockTimeFn, frame: pxs_ClockFrameFn) {
+    pxs_debug!("pxs_clock_setcallbacks");
     assert_initiated!();
-    with_backend!(runtime, Backend => {
-        create_raw_string!(Backend::debug())
-    })
+    set_clock_callbacks(game_time, real_time, frame);
 }
 
-/// Call GC for all backends.
+/// Initialize the `pxs_clock` module.
+/// 
+/// This needs to be called in each new thread too. Should only be called once per thread.
 #[unsafe(no_mangle)]
-pub extern "C" fn pxs_garbagecollect() {
-    pxs_debug!("pxs_gc");
+pub extern "C" fn pxs_clockinit() {
+    pxs_debug!("pxs_clockinit");
     assert_initiated!();
 
-    with_feature!("lua", {
-        LuaScripting::garbage_collect();
-    });
-    with_feature!("python", {
-        PythonScripting::garbage_collect();
-    });
-    with_feature!("js", {
-        JSScripting::garbage_collect();
+    with_feature!("pxs_clock", {
+        pxs_core::pxs_clock::init();
+    }, {
+        panic!("pxs_clock is not enabled.");
     });
 }
 
-/// Get the host IDX from a `pxs_HostObject`.
-/// 
-/// if result is < 0 then that means it is not a object.
-/// 
-/// var: BORROW
+/// Set the executor that runs jobs submitted with `pxs_jobs.submit`.
 #[unsafe(no_mangle)]
-pub extern "C" fn pxs_getidx(var: pxs_VarT) -> i32 {
-    pxs_debug!("pxs_getidx");
+pub extern "C" fn pxs_job_setexecutor(func: pxs_JobSubmitFn) {
+    pxs_debug!("pxs_job_setexecutor");
     assert_initiated!();
+    set_job_executor(func);
+}
 
-    if var.is_null() {
-        return -1;
-    }
-
-    let bvar = borrow_var!(var);
-    if !bvar.is_host_object() {
-        return -1;
-    }
+/// Complete a job submitted with `pxs_jobs.submit` and run its `then` callbacks.
+///
+/// Pass a `pxs_Exception` to fail the job with its message.
+///
+/// result: TRANSFER
+///
+/// Returns false if the job is unknown or already completed.
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_job_complete(id: i64, result: pxs_VarT) -> bool {
+    pxs_debug!("pxs_job_complete");
+    assert_initiated!();
+    let result = if result.is_null() { pxs_Var::new_null() } else { pxs_Var::from_raw(result) };
+    with_feature!("pxs_jobs", {
+        pxs_core::pxs_jobs::complete(id, result)
+    }, {
+        drop(result);
+        false
+    })
+}
 
-    bvar.get_host_idx()
+/// Drop every `pxs_jobs` job on this thread.
+///
+/// Jobs keep script callbacks alive, call this before clearing or stopping a runtime. `pxs_finalize` calls this for you.
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_jobs_clear() {
+    pxs_debug!("pxs_jobs_clear");
+    assert_initiated!();
+    with_feature!("pxs_jobs", {
+        pxs_core::pxs_jobs::clear();
+    });
 }
 
-/// Get a `pxs_VarT` from args without needing to worry about index checks.
-/// 
-/// Literraly does `pxs_listget(args, idx + 1)`
+/// Initialize the `pxs_jobs` module.
 /// 
-/// args: BORROW
-/// result: BORROW&NULLABLE
+/// This needs to be called in each new thread too. Should only be called once per thread.
 #[unsafe(no_mangle)]
-pub extern "C" fn pxs_arg(args: pxs_VarT, idx: i32) -> pxs_VarT {
-    pxs_debug!("pxs_arg");
+pub extern "C" fn pxs_jobsinit() {
+    pxs_debug!("pxs_jobsinit");
     assert_initiated!();
-    pxs_listget(args, idx + 1)
+
+    with_feature!("pxs_jobs", {
+        pxs_core::pxs_jobs::init();
+    }, {
+        panic!("pxs_jobs is not enabled.");
+    });
 }
 
-/// Get the `pxs_VarT` runtime from args.
-/// 
-/// Does `pxs_listget(args, 0)`
-/// 
-/// args: BORROW
-/// result: BORROW&NULLABLE
+/// Create a channel for sending data between threads (the host, contexts and script threads). Returns its id, used
+/// by `pxs_channel_send` and `pxs_channel_recv` and by scripts through the `pxs_channel` module.
 #[unsafe(no_mangle)]
-pub extern "C" fn pxs_getrt(args: pxs_VarT) -> pxs_VarT {
-    pxs_debug!("pxs_getrt");
+pub extern "C" fn pxs_channel_new() -> i32 {
+    pxs_debug!("pxs_channel_new");
     assert_initiated!();
-    pxs_listget(args, 0)
+    channel::new()
 }
 
-/// Get the length of args without the runtime.
-/// 
-/// args: BORROW
+/// Send a deep copy of `var` to a channel. Only data (numbers, strings, bytes, lists and maps) can be sent. Returns
+/// false if the channel does not exist or `var` is not data.
+///
+/// var:BORROW
 #[unsafe(no_mangle)]
-pub extern "C" fn pxs_argc(args: pxs_VarT) -> usize {
-    pxs_debug!("pxs_argc");
+pub extern "C" fn pxs_channel_send(channel: i32, var: pxs_VarT) -> bool {
+    pxs_debug!("pxs_channel_send");
     assert_initiated!();
-    let len = pxs_listlen(args);
-    if len <= 0 {
-        return 0;
-    } else {
-        return (len - 1) as usize;
+    if var.is_null() {
+        strict::misuse("pxs_channel_send", "var is null");
+        return false;
+    }
+    match channel::send(channel, borrow_var!(var)) {
+        Ok(()) => true,
+        Err(e) => {
+            pxs_debug!("pxs_channel_send failed: {e}");
+            false
+        }
     }
 }
 
-/// Create a `pxs_List` of u8. i.e. bytes.
-/// 
-/// data: BORROW
-/// result: OWNED
+/// Receive the oldest value of a channel. Waits up to `timeout_ms` for one (forever if negative, not at all if 0).
+/// Returns null on timeout, a exception if the channel does not exist or was closed.
+///
+/// return:OWNED
 #[unsafe(no_mangle)]
-pub extern "C" fn pxs_newbytes(data: pxs_Opaque, el_size: usize, size: usize) -> pxs_VarT {
-    pxs_debug!("pxs_newbytes");
+pub extern "C" fn pxs_channel_recv(channel: i32, timeout_ms: i64) -> pxs_VarT {
+    pxs_debug!("pxs_channel_recv");
     assert_initiated!();
-
-    if data.is_null() {
-        return pxs_newlist();
+    match channel::recv(channel, timeout_ms) {
+        Ok(Some(var)) => var.into_raw(),
+        Ok(None) => ptr::null_mut(),
+        Err(e) => pxs_Var::new_exception(e).into_raw(),
     }
+}
 
-    let ptr = data as *mut u8;
-    let total_bytes = el_size * size;
-    let raw_bytes: &mut [u8] = unsafe { core::slice::from_raw_parts_mut(ptr, total_bytes) };
+/// Close a channel. Receivers waiting on it get the values still in it, then a exception. Returns false if it does
+/// not exist. `pxs_finalize` closes every channel.
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_channel_close(channel: i32) -> bool {
+    pxs_debug!("pxs_channel_close");
+    assert_initiated!();
+    channel::close(channel)
+}
 
-    let mut list = vec![];
-    for i in raw_bytes {
-        list.push(pxs_Var::new_byte(i.clone()));
-    }
+/// Initialize the `pxs_channel` module.
+/// 
+/// This needs to be called in each new thread too. Should only be called once per thread. Script threads get it from
+/// the thread that spawned them.
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_channelinit() {
+    pxs_debug!("pxs_channelinit");
+    assert_initiated!();
 
-    pxs_Var::new_list_with(list).into_raw()
+    with_feature!("pxs_channel", {
+        pxs_core::pxs_channel::init();
+    }, {
+        panic!("pxs_channel is not enabled.");
+    });
 }
 
-/// Get the memory size (in bytes) of a `pxs_VarT`
+/// Emit a event to the `pxs_events` handlers of every runtime on this thread, in registration order. Returns the
+/// number of handlers called.
 ///
-/// var: BORROW
+/// name:BORROW
+/// payload:BORROW (NULLABLE)
 #[unsafe(no_mangle)]
-pub extern "C" fn pxs_varsize(var: pxs_VarT) -> usize {
-    pxs_debug!("pxs_varsize");
+pub extern "C" fn pxs_emit(name: *const c_char, payload: pxs_VarT) -> i32 {
+    pxs_debug!("pxs_emit");
     assert_initiated!();
-
-    if var.is_null() {
+    if name.is_null() {
+        strict::misuse("pxs_emit", "name is null");
         return 0;
     }
 
-    let bvar = borrow_var!(var);
-    bvar.get_size()
+    with_feature!("pxs_events", {
+        let payload = if payload.is_null() { pxs_Var::new_null() } else { borrow_var!(payload).shallow_copy() };
+        pxs_core::pxs_events::emit(borrow_string!(name), &payload)
+    }, {
+        let _ = payload;
+        0
+    })
 }
 
-/// Copy a `pxs_VarT` into a list of u8. i.e. bytes, if values are:
-///   - `pxs_UInt64`
-///   - `pxs_Int64`
-///   - `pxs_Float64`
-///   - `pxs_Bool`
-///   - `pxs_String`
-///   - `pxs_List`
-/// 
-/// var: BORROW
-/// data_ptr: BORROW
+/// Drop every `pxs_events` handler on this thread.
+///
+/// Handlers keep script functions alive, call this before clearing or stopping a runtime. `pxs_finalize` calls this
+/// for you.
 #[unsafe(no_mangle)]
-pub extern "C" fn pxs_copybytes(var: pxs_VarT, data_ptr: pxs_Opaque) {
-    pxs_debug!("pxs_copybytes");
+pub extern "C" fn pxs_events_clear() {
+    pxs_debug!("pxs_events_clear");
     assert_initiated!();
 
-    if var.is_null() || data_ptr.is_null() {
-        return;
-    }
+    with_feature!("pxs_events", {
+        pxs_core::pxs_events::clear();
+    });
+}
 
-    let bvar = borrow_var!(var);
-    let bytes_ptr = data_ptr as *mut u8;
+/// Initialize the `pxs_events` module.
+/// 
+/// This needs to be called in each new thread too. Should only be called once per thread.
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_eventsinit() {
+    pxs_debug!("pxs_eventsinit");
+    assert_initiated!();
 
-    unsafe { bvar.copy_bytes(bytes_ptr) };
+    with_feature!("pxs_events", {
+        pxs_core::pxs_events::init();
+    }, {
+        panic!("pxs_events is not enabled.");
+    });
 }
 
-/// Copy a `pxs_String` into a char*.
+/// Initialize the `pxs_fs` module. Reads and writes go through `pxs_set_filereader`, `pxs_set_filewriter` and
+/// `pxs_set_dirreader`.
 /// 
-/// var: BORROW
-/// str_ptr: BORROW
+/// This needs to be called in each new thread too. Should only be called once per thread.
 #[unsafe(no_mangle)]
-pub extern "C" fn pxs_copystring(var: pxs_VarT, str_ptr: *mut c_char) {
-    pxs_debug!("pxs_copystring");
+pub extern "C" fn pxs_fsinit() {
+    pxs_debug!("pxs_fsinit");
     assert_initiated!();
 
-    if !pxs_varis(var, pxs_VarType::pxs_String) {
-        return;
-    }
-
-    pxs_copybytes(var, str_ptr as pxs_Opaque);
+    with_feature!("pxs_fs", {
+        pxs_core::pxs_fs::init();
+    }, {
+        panic!("pxs_fs is not enabled.");
+    });
 }
 
-/// Get a string (char*) from `pxs_String`. And calls `pxs_tostring` automatically if not already a string.
-/// Runtime is required.
-/// 
-/// Free the result using `pxs_freestr`.
+/// Initialize the `pxs_str` module.
 /// 
-/// rt: BORROW
-/// str: BORROW
-/// result: OWNED
+/// This needs to be called in each new thread too. Should only be called once per thread.
 #[unsafe(no_mangle)]
-pub extern "C" fn pxs_smart_getstring(rt: pxs_VarT, str: pxs_VarT) -> *mut c_char {
-    pxs_debug!("pxs_smart_getstring");
+pub extern "C" fn pxs_strinit() {
+    pxs_debug!("pxs_strinit");
     assert_initiated!();
-    
-    if rt.is_null() || str.is_null() {
-        return core::ptr::null_mut();
-    }
 
-    let mut bstr = str;
-    let mut string_var_ptr: pxs_VarT = core::ptr::null_mut();
+    with_feature!("pxs_str", {
+        pxs_core::pxs_str::init();
+    }, {
+        panic!("pxs_str is not enabled.");
+    });
+}
 
-    if !pxs_varis(bstr, pxs_VarType::pxs_String) {
-        string_var_ptr = pxs_tostring(rt, str);
-        bstr = string_var_ptr;
-    }
+/// Initialize the `pxs_math` module.
+/// 
+/// This needs to be called in each new thread too. Should only be called once per thread.
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_mathinit() {
+    pxs_debug!("pxs_mathinit");
+    assert_initiated!();
 
-    let str_value = pxs_getstring(bstr);
+    with_feature!("pxs_math", {
+        pxs_core::pxs_math::init();
+    }, {
+        panic!("pxs_math is not enabled.");
+    });
+}
 
-    if !string_var_ptr.is_null() {
-        pxs_freevar(string_var_ptr);
-    }
+/// Seed the `pxs_random` generator of this thread (the one used by the module functions, not `Random` objects).
+/// Generators start with seed 0.
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_random_setseed(seed: i64) {
+    pxs_debug!("pxs_random_setseed");
+    assert_initiated!();
 
-    str_value
+    with_feature!("pxs_random", {
+        pxs_core::pxs_random::seed(seed);
+    });
 }
 
-/// Copy a `pxs_String` memory into a char*. Calls `pxs_tostring` automatically if not already a string.
-/// Runtime is required.
+/// Initialize the `pxs_random` module.
 /// 
-/// rt: BORROW
-/// str: BORROW
-/// str_ptr: BORROW
+/// This needs to be called in each new thread too. Should only be called once per thread.
 #[unsafe(no_mangle)]
-pub extern "C" fn pxs_smart_copystring(rt: pxs_VarT, str: pxs_VarT, str_ptr: *mut c_char) {
-    pxs_debug!("pxs_smart_copystring");
+pub extern "C" fn pxs_randominit() {
+    pxs_debug!("pxs_randominit");
     assert_initiated!();
 
-    if rt.is_null() || str.is_null() || str_ptr.is_null() {
-        return;
-    }
-
-    let mut bstr = str;
-    let mut string_var_ptr: pxs_VarT = core::ptr::null_mut();
-
-    if !pxs_varis(bstr, pxs_VarType::pxs_String) {
-        string_var_ptr = pxs_tostring(rt, bstr);
-        bstr = string_var_ptr;
-    }
+    with_feature!("pxs_random", {
+        pxs_core::pxs_random::init();
+    }, {
+        panic!("pxs_random is not enabled.");
+    });
+}
 
-    pxs_copystring(bstr, str_ptr);
+/// Set the time source (NULLABLE) of `pxs_time`, seconds since the Unix epoch. Replaces the system clock for
+/// `now` and `monotonic` so deterministic replays can feed their own time. Null restores the system clock.
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_set_clock(func: Option<pxs_ClockTimeFn>) {
+    pxs_debug!("pxs_set_clock");
+    assert_initiated!();
+    set_time_source(func);
+}
 
-    // Free
-    if !string_var_ptr.is_null() {
-        pxs_freevar(string_var_ptr);
-    }
+/// Allow or forbid `pxs_time.sleep`. Forbidden by default, a sleeping script blocks its whole thread.
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_time_allowsleep(allow: bool) {
+    pxs_debug!("pxs_time_allowsleep");
+    assert_initiated!();
+    set_allow_sleep(allow);
 }
 
-/// Get the `pxs_VarType` of a `pxs_VarT`
-///
-/// var:BORROW
+/// Initialize the `pxs_time` module.
+/// 
+/// This needs to be called in each new thread too. Should only be called once per thread.
 #[unsafe(no_mangle)]
-pub extern "C" fn pxs_vartype(var: pxs_VarT) -> pxs_VarType {
-    pxs_debug!("pxs_vartype");
+pub extern "C" fn pxs_timeinit() {
+    pxs_debug!("pxs_timeinit");
     assert_initiated!();
 
-    if var.is_null() {
-        pxs_VarType::pxs_Null
-    } else {
-       unsafe { (*var).tag }
-    }
+    with_feature!("pxs_time", {
+        pxs_core::pxs_time::init();
+    }, {
+        panic!("pxs_time is not enabled.");
+    });
 }
 
-// ====================================== Core functions Start =======================================
+/// Set the sink (NULLABLE) of `pxs_log`. It gets every message at `min_level` or above with the script file and line
+/// that logged it. Null restores the default output (info and above on stderr).
+#[unsafe(no_mangle)]
+pub extern "C" fn pxs_set_log_sink(func: Option<pxs_LogSinkFn>, min_level: pxs_LogLevel) {
+    pxs_debug!("pxs_set_log_sink");
+    assert_initiated!();
+    set_log_sink(func.map(|func| (func, min_level)));
+}
 
-/// Encode a `pxs_Var` into a JSON string. Will return a `pxs_Var` of type string.
-/// Transfers ownership of args.
-/// Basically calls the runtime.pxs_json.encode() function.
-///
-/// Note: This function is already enabled in each scripting language. This is a host language wrapper for calling it easily.
-///
-/// rt:BORROW
-/// args:TRANSFER
-/// return:OWNED
+/// Initialize the `pxs_log` module.
+/// 
+/// This needs to be called in each new thread too. Should only be called once per thread.
 #[unsafe(no_mangle)]
-pub extern "C" fn pxs_json_encode(rt: pxs_VarT, args: pxs_VarT) -> pxs_VarT {
-    pxs_debug!("pxs_json_encode");
+pub extern "C" fn pxs_loginit() {
+    pxs_debug!("pxs_loginit");
     assert_initiated!();
-    with_feature!(
-        "pxs_json",
-        {
-            unsafe {
-                if !pxs_core::is_valid_pxs_function(rt, args) {
-                    return pxs_Var::new_exception("Not a valid core call").into_raw();
-                }
-            }
-            pxs_core::pxs_json::encode(rt, args)
-        },
-        { pxs_Var::feature_not_enabled_ep("pxs_json").into_raw() }
-    )
+
+    with_feature!("pxs_log", {
+        pxs_core::pxs_log::init();
+    }, {
+        panic!("pxs_log is not enabled.");
+    });
 }
 
-/// Decode a `pxs_String` into a `pxs_Var`.
-/// Make sure runtime is the first argument in args.
-/// Transfers ownership of args.
-///
-/// Note: This function is already enabled in each scripting language. This is a host language wrapper for calling it easily.
-///
-/// rt:BORROW
-/// args:TRANSFER
-/// return:OWNED
+/// Set the handler (NULLABLE) doing the network IO of `pxs_http`. It gets a `pxs_HttpRequest` and returns a
+/// `pxs_HttpResponse`, pixelscript itself does no networking. Without a handler requests raise a exception.
 #[unsafe(no_mangle)]
-pub extern "C" fn pxs_json_decode(rt: pxs_VarT, args: pxs_VarT) -> pxs_VarT {
-    pxs_debug!("pxs_json_decode");
+pub extern "C" fn pxs_set_http_handler(func: Option<pxs_HttpHandlerFn>) {
+    pxs_debug!("pxs_set_http_handler");
     assert_initiated!();
-    with_feature!(
-        "pxs_json",
-        {
-            unsafe {
-                if !pxs_core::is_valid_pxs_function(rt, args) {
-                    return pxs_Var::new_exception("Not a valid core call").into_raw();
-                }
-            }
-            pxs_core::pxs_json::decode(rt, args)
-        },
-        { pxs_Var::feature_not_enabled_ep("pxs_json").into_raw() }
-    )
+    set_http_handler(func);
 }
 
-/// Initialize the `pxs_mem` module.
+/// Initialize the `pxs_http` module.
 /// 
 /// This needs to be called in each new thread too. Should only be called once per thread.
 #[unsafe(no_mangle)]
-pub extern "C" fn pxs_meminit() {
-    pxs_debug!("pxs_meminit");
+pub extern "C" fn pxs_httpinit() {
+    pxs_debug!("pxs_httpinit");
     assert_initiated!();
 
-    with_feature!("pxs_mem", {
-        pxs_core::pxs_mem::init();
+    with_feature!("pxs_http", {
+        pxs_core::pxs_http::init();
     }, {
-        panic!("pxs_mem is not enabled.");
+        panic!("pxs_http is not enabled.");
     });
 }
 
@@ -346,6 +346,16 @@ impl Engine {
         unsafe { lua::lua_type(self.L, idx) }
     }
 
+    /// Call `lua_rawequal`
+    pub fn raw_equal(&self, a: i32, b: i32) -> bool {
+        unsafe { lua::lua_rawequal(self.L, a, b) == 1 }
+    }
+
+    /// Call `lua_iscfunction`
+    pub fn is_cfunction(&self, idx: i32) -> bool {
+        unsafe { lua::lua_iscfunction(self.L, idx) == 1 }
+    }
+
     /// Call `lua_getupvalue`
     pub fn get_upvalue(&self, idx: i32) -> i32 {
         unsafe {
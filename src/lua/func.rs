@@ -11,11 +11,11 @@
 use crate::{
     create_raw_string, free_raw_string,
     lua::{
-        engine::Engine, from_lua, lua, lua_pop, lua_upvalueindex, module_loader_func, object::{lua_index, lua_newindex}, var::push_lua_stack
+        engine::Engine, from_lua, lua, lua_pop, lua_remove, lua_upvalueindex, module_loader_func, object::{lua_gc, lua_index, lua_newindex}, var::push_lua_stack
     },
     pxs_error,
     shared::{
-        PXS_PTR_NAME, PxsRes, func::call_function, object::ObjectFlags, pxs_Runtime,
+        PXS_PTR_NAME, PxsRes, func::call_function, limit, object::ObjectFlags, pxs_Runtime,
     },
 };
 
@@ -24,6 +24,11 @@ pub(super) const LUA_MODULE_BRIDGE_FUNCTION: i32 = 1;
 pub(super) const LUA_INDEX_BRIDGE_FUNCTION: i32 = 2;
 pub(super) const LUA_NEWINDEX_BRIDGE_FUNCTION: i32 = 3;
 pub(super) const LUA_MODULE_LOADER_BRIDGE_FUNCTION: i32 = 4;
+pub(super) const LUA_CLASS_CALL_BRIDGE_FUNCTION: i32 = 5;
+pub(super) const LUA_GC_BRIDGE_FUNCTION: i32 = 6;
+
+/// Instructions between calls of the execution limit hook.
+pub(super) const LUA_LIMIT_STEP: i32 = 1000;
 
 /// cbindgen:ignore
 /// This is defined in libs/pxs_lua.h
@@ -47,6 +52,10 @@ unsafe extern "C" fn pxslua_rustbridge(
         lua_newindex(L)
     } else if function_type == LUA_MODULE_LOADER_BRIDGE_FUNCTION {
         module_loader_func(L)
+    } else if function_type == LUA_CLASS_CALL_BRIDGE_FUNCTION {
+        lua_class_call(L)
+    } else if function_type == LUA_GC_BRIDGE_FUNCTION {
+        lua_gc(L)
     } else {
         Ok(0)
     };
@@ -63,6 +72,33 @@ unsafe extern "C" fn pxslua_rustbridge(
     }
 }
 
+/// cbindgen:ignore
+/// This is defined in libs/pxs_lua.h
+/// Called by the count hook while running under a execution limit. Returns 2 if the script should yield.
+#[unsafe(no_mangle)]
+unsafe extern "C" fn pxslua_rustlimit(
+    _L: *mut lua::lua_State,
+    can_yield: core::ffi::c_int,
+    err_buff: *mut *mut core::ffi::c_char,
+) -> core::ffi::c_int {
+    let message = match limit::step(&pxs_Runtime::pxs_Lua, LUA_LIMIT_STEP as u64) {
+        limit::Step::Continue => return 0,
+        // Inside a coroutine, `pxs_coroutine_resume` continues it.
+        limit::Step::Suspend if can_yield != 0 => return 2,
+        limit::Step::Suspend => {
+            limit::suspend();
+            limit::suspended_message()
+        }
+        limit::Step::Stop => limit::timeout_message(),
+    };
+
+    let raw_string = create_raw_string!(message);
+    unsafe {
+        *err_buff = raw_string;
+    }
+    1
+}
+
 fn lua_object_bridge(L: *mut lua::lua_State) -> PxsRes<i32> {
     unsafe {
         let argc = lua::lua_gettop(L);
@@ -120,6 +156,12 @@ fn lua_object_bridge(L: *mut lua::lua_State) -> PxsRes<i32> {
     }
 }
 
+/// `__call` of a class table. The class itself is not passed to the constructor.
+fn lua_class_call(L: *mut lua::lua_State) -> PxsRes<i32> {
+    lua_remove(L, 1);
+    lua_bridge(L)
+}
+
 /// The lua function bridge
 fn lua_bridge(L: *mut lua::lua_State) -> PxsRes<i32> {
     unsafe {
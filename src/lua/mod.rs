@@ -14,22 +14,23 @@ pub mod module;
 pub mod object;
 pub mod var;
 
+use std::cell::Cell;
+
 use etffi::cstring::CStringSafe;
 use etffi::ptr_magic::{PtrMagic, ThreadSafePointer};
 
-use crate::lua::func::LUA_MODULE_LOADER_BRIDGE_FUNCTION;
-use crate::lua::module::preload_lua_module;
+use crate::lua::func::{LUA_LIMIT_STEP, LUA_MODULE_BRIDGE_FUNCTION, LUA_MODULE_LOADER_BRIDGE_FUNCTION};
 use crate::{
     borrow_string,
     lua::{
         engine::Engine,
-        module::compile_chunk,
+        module::{compile_chunk, dump_chunk, load_bytecode},
         var::{from_lua, push_lua_stack},
     },
     pxs_error,
     shared::{
-        PixelScript, PxsRes, PxsResult,
-        read_file,
+        PixelScript, PxsRes, PxsResult, chunks, pxs_Runtime,
+        internal_chunk_name, read_file, resolve_module,
         var::{ObjectMethods, pxs_Var, pxs_VarMap},
     },
     with_feature,
@@ -45,6 +46,8 @@ pub(self) mod lua {
 
 thread_local! {
     static LUASTATE: ThreadSafePointer<State> = ThreadSafePointer::new_owned(new_state());
+    /// Add a stack traceback to errors of `lua_call`. Set by `with_traceback`.
+    static TRACEBACK: Cell<bool> = const { Cell::new(false) };
 }
 
 /// This is the Lua state. Each language gets it's own private state
@@ -58,6 +61,7 @@ impl PtrMagic for State {}
 // Lua globals
 const LUA_REGISTRYINDEX: i32 = -(core::ffi::c_int::MAX / 2 + 1000);
 const LUA_OK: i32 = 0;
+const LUA_MASKCOUNT: i32 = 1 << 3;
 const LUA_TNONE: i32 = -1;
 #[allow(unused)]
 const LUA_TNIL: i32 = 0;
@@ -184,22 +188,12 @@ fn init(ptr: *mut State) {
         lua_globals.push_str(include_str!("../../core/lua/main.lua"));
 
         with_feature!("pxs_json", {
-            // Load dkjson module
-            let _ = preload_lua_module(
-                (*ptr).engine,
-                include_str!("../../libs/dkjson.lua"),
-                "__dkjson__",
-            );
             // Load in the pxs_json module
-            let _ = preload_lua_module(
-                (*ptr).engine,
-                include_str!("../../core/lua/pxs_json.lua"),
-                "pxs_json",
-            );
+            let _ = module::add_module(ptr, crate::pxs_core::pxs_json::module());
             // Import it globally
             lua_globals.push_str("\npxs_json = require('pxs_json')\n");
         });
-        let _ = execute(ptr, &lua_globals, "<lua_globals>");
+        let _ = execute(ptr, &lua_globals, &internal_chunk_name("lua_globals"));
 
         setup_module_loader((*ptr).engine);
     }
@@ -227,6 +221,9 @@ fn get_lua_engine() -> Engine {
 /// Will execute a lua function or chunk on -1 stack.
 /// Will add result to stack if not error. If error, its popped from stack.
 pub(self) fn lua_call(L: *mut lua::lua_State, args: i32, results: i32) -> PxsRes<()> {
+    if TRACEBACK.with(|traceback| traceback.get()) {
+        return lua_call_traced(L, args, results);
+    }
     unsafe {
         // 1
         let code = lua::lua_pcallk(L, args, results, 0, 0, None); // results
@@ -238,6 +235,45 @@ pub(self) fn lua_call(L: *mut lua::lua_State, args: i32, results: i32) -> PxsRes
     }
 }
 
+/// Message handler of `lua_call_traced`, adds the stack traceback to string errors.
+unsafe extern "C" fn traceback_handler(L: *mut lua::lua_State) -> core::ffi::c_int {
+    unsafe {
+        let msg = lua::lua_tolstring(L, 1, core::ptr::null_mut());
+        if !msg.is_null() {
+            lua::luaL_traceback(L, L, msg, 1);
+        }
+    }
+    1
+}
+
+/// Same as `lua_call`, the error includes the stack traceback.
+fn lua_call_traced(L: *mut lua::lua_State, args: i32, results: i32) -> PxsRes<()> {
+    unsafe {
+        // Put the handler below the function.
+        let handler = lua::lua_gettop(L) - args;
+        lua::lua_pushcclosure(L, Some(traceback_handler), 0);
+        lua::lua_rotate(L, handler, 1);
+
+        let code = lua::lua_pcallk(L, args, results, handler, 0, None);
+        let res = if code != LUA_OK {
+            let lua_error = lua_get_error(L);
+            pxs_error!("{lua_error}")
+        } else {
+            Ok(())
+        };
+        lua_remove(L, handler);
+        res
+    }
+}
+
+/// Run `f` with stack tracebacks in the errors of Lua calls.
+pub(crate) fn with_traceback<R>(f: impl FnOnce() -> R) -> R {
+    let previous = TRACEBACK.with(|traceback| traceback.replace(true));
+    let res = f();
+    TRACEBACK.with(|traceback| traceback.set(previous));
+    res
+}
+
 /// #define lua_pushglobaltable(L)  \
 ///	   ((void)lua_rawgeti(L, LUA_REGISTRYINDEX, LUA_RIDX_GLOBALS))
 pub(self) fn lua_push_globals(L: *mut lua::lua_State) {
@@ -269,15 +305,32 @@ pub(self) fn execute(state: *mut State, code: &str, file_name: &str) -> String {
 pub(self) fn module_loader_func(L: *mut lua::lua_State) -> PxsRes<i32> {
     let path_idx = 1;
     let mut engine = Engine::without_alloc(L);
-    let path = engine.to_string(path_idx);
+    let name = engine.to_string(path_idx);
 
-    let contents = read_file(&path);
+    let (contents, path) = match resolve_module(&name, pxs_Runtime::pxs_Lua) {
+        Some(module) => module,
+        None => (read_file(&name), name.clone()),
+    };
     if contents.is_empty() {
         return pxs_error!("{path} was not found.");
     }
 
-    // Compile chunk
-    let _ = engine.compile_chunk(&contents, &path)?;
+    if !chunks::is_enabled() {
+        // Compile chunk
+        let _ = engine.compile_chunk(&contents, &path)?;
+        return Ok(1);
+    }
+
+    // Skip parsing if the file did not change since it was compiled (by any thread).
+    match chunks::get(&pxs_Runtime::pxs_Lua, &path, &contents) {
+        Some(bytes) => {
+            load_bytecode(L, &bytes, &path)?;
+        }
+        None => {
+            let _ = engine.compile_chunk(&contents, &path)?;
+            chunks::put(&pxs_Runtime::pxs_Lua, &path, &contents, dump_chunk(L));
+        }
+    }
 
     // Donezo!
     Ok(1)
@@ -351,6 +404,12 @@ impl PixelScript for LuaScripting {
         }
     }
 
+    fn remove_module(source: &crate::shared::module::pxs_Module, object_types: &[String]) {
+        let state = get_lua_state();
+        module::remove_module(state, source);
+        object::remove_object_types(state, object_types);
+    }
+
     fn execute(code: &str, file_name: &str) -> PxsResult {
         let state = get_lua_state();
         let err = execute(state, code, file_name);
@@ -361,6 +420,27 @@ impl PixelScript for LuaScripting {
         }
     }
 
+    fn eval_with(code: &str, name: &str, bindings: &pxs_Var) -> PxsResult {
+        let mut engine = get_lua_engine();
+        let chunk = engine.compile_chunk(code, name)?;
+        // The bindings are the _ENV of the chunk, falling back to the globals.
+        let env_table = engine.push_pxs(bindings)?;
+        let mt_table = engine.create_table(0, 0);
+        engine.push_globals();
+        engine.set_field(mt_table, "__index");
+        engine.set_meta(env_table);
+        engine.set_upvalue(chunk, 1);
+
+        engine.push_value(chunk);
+        engine.call(0, 1)?;
+        engine.get_top_pxs()
+    }
+
+    fn execute_with_result(code: &str, file_name: &str) -> PxsResult {
+        // A chunk returns its value like a function.
+        Self::eval(code, file_name)
+    }
+
     fn start() {
         // Initalize the state
         init(get_lua_state());
@@ -483,6 +563,106 @@ impl PixelScript for LuaScripting {
             lua::lua_gc((*state).engine, lua::LUA_GCCOLLECT as i32);
         }
     }
+
+    fn trim_memory(object_types: &[String]) -> usize {
+        let state = get_lua_state();
+        let used = || unsafe {
+            let kb = lua::lua_gc((*state).engine, lua::LUA_GCCOUNT as i32) as usize;
+            kb * 1024 + lua::lua_gc((*state).engine, lua::LUA_GCCOUNTB as i32) as usize
+        };
+        let before = used();
+        object::remove_object_types(state, object_types);
+        Self::garbage_collect();
+        before.saturating_sub(used())
+    }
+
+    fn new_function(fn_idx: i32) -> PxsResult {
+        let state = get_lua_state();
+        let mut engine = Engine::from_state(state);
+        engine.push_integer(LUA_MODULE_BRIDGE_FUNCTION);
+        engine.push_integer(fn_idx);
+        engine.push_function(lua::pxslua_callback, 2);
+        // The closure is popped when the engine drops.
+        from_lua(-1)
+    }
+
+    fn set_exec_hooks(enabled: bool) {
+        let state = get_lua_state();
+        unsafe {
+            if enabled {
+                lua::lua_sethook((*state).engine, Some(lua::pxslua_limithook), LUA_MASKCOUNT, LUA_LIMIT_STEP);
+            } else {
+                lua::lua_sethook((*state).engine, None, 0, 0);
+            }
+        }
+    }
+
+    fn caller_location() -> Option<(String, i32)> {
+        let state = get_lua_state();
+        unsafe {
+            let mut ar: lua::lua_Debug = std::mem::zeroed();
+            // Level 0 is the host function itself.
+            if lua::lua_getstack((*state).engine, 1, &mut ar) == 0
+                || lua::lua_getinfo((*state).engine, c"Sl".as_ptr(), &mut ar) == 0
+                || ar.source.is_null()
+            {
+                return None;
+            }
+            let source = borrow_string!(ar.source);
+            // '@' marks a file name and '=' a custom name, anything else is the chunk itself.
+            let file = source.strip_prefix('@').or_else(|| source.strip_prefix('=')).unwrap_or(source);
+            Some((file.to_string(), ar.currentline))
+        }
+    }
+
+    fn import_module(name: &str) -> PxsResult {
+        Self::eval(&format!("return require('{name}')"), &internal_chunk_name("import"))
+    }
+
+    fn new_iterator(var: &pxs_Var) -> PxsResult {
+        // Tables iterate with `next`, items are `{key, value}`.
+        let factory = Self::eval(
+            r#"return function(t)
+    local k
+    return function()
+        local v
+        k, v = next(t, k)
+        if k == nil then return nil end
+        return {{k, v}}
+    end
+end"#,
+            &internal_chunk_name("iter"),
+        )?;
+        let mut args = crate::shared::var::pxs_VarList::new();
+        args.add_item(var.shallow_copy());
+        Self::var_call(&factory, &mut args)
+    }
+
+    fn new_coroutine(func: &pxs_Var, args: &pxs_Var) -> PxsResult {
+        // The first resume starts `f` with `args`, later ones pass their args as the results of `coroutine.yield`.
+        // The coroutine is made on the first resume so it gets the hooks of `pxs_set_tick_callback`.
+        let factory = Self::eval(
+            r#"return function(f, args)
+    local co
+    return function(...)
+        local res
+        if not co then
+            co = coroutine.create(f)
+            res = table.pack(coroutine.resume(co, table.unpack(args)))
+        else
+            res = table.pack(coroutine.resume(co, ...))
+        end
+        if not res[1] then error(res[2], 0) end
+        return {coroutine.status(co) == 'dead', res[2]}
+    end
+end"#,
+            &internal_chunk_name("coroutine"),
+        )?;
+        let mut factory_args = crate::shared::var::pxs_VarList::new();
+        factory_args.add_item(func.shallow_copy());
+        factory_args.add_item(args.shallow_copy());
+        Self::var_call(&factory, &mut factory_args)
+    }
 }
 
 /// Push args to lua stack.
@@ -502,11 +682,21 @@ impl ObjectMethods for LuaScripting {
         let mut engine = get_lua_engine();
         engine.push_pxs(var)?;
         engine.get_field(-1, method);
+        // Called like `var:method(...)`, the object is self.
+        engine.push_value(-2);
         args_to_lua(&mut engine, &args.vars)?;
-        engine.call(args.len() as i32, 1)?;
+        engine.call(args.len() as i32 + 1, 1)?;
         engine.get_top_pxs()
     }
 
+    fn object_overrides(var: &pxs_Var, method: &str) -> PxsRes<bool> {
+        let mut engine = get_lua_engine();
+        engine.push_pxs(var)?;
+        engine.get_field(-1, method);
+        // Host methods are C functions, the functions of a script are not.
+        Ok(engine.get_type(-1) == LUA_TFUNCTION && !engine.is_cfunction(-1))
+    }
+
     fn call_method(method: &str, args: &mut crate::shared::var::pxs_VarList) -> PxsResult {
         let mut engine = get_lua_engine();
         engine.push_globals();
@@ -559,4 +749,13 @@ impl ObjectMethods for LuaScripting {
         // result
         engine.from_lua(-1)
     }
+
+    fn set_global(name: &str, value: &pxs_Var) -> PxsRes<()> {
+        let mut engine = get_lua_engine();
+        engine.push_globals();
+        let globals = engine.get_top();
+        engine.push_pxs(value)?;
+        engine.set_field(globals, name);
+        Ok(())
+    }
 }
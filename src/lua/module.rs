@@ -12,10 +12,10 @@ use etffi::cstring::CStringSafe;
 
 use crate::{
     lua::{
-        State, engine::Engine, func::LUA_MODULE_BRIDGE_FUNCTION, lua, lua_get_error, lua_upvalueindex, LUA_OK
+        State, engine::Engine, func::{LUA_CLASS_CALL_BRIDGE_FUNCTION, LUA_MODULE_BRIDGE_FUNCTION}, lua, lua_get_error, lua_upvalueindex, LUA_OK
     },
     pxs_error,
-    shared::{PxsRes, module::pxs_Module},
+    shared::{PxsRes, module::{pxs_Class, pxs_Module}},
 };
 
 /// Load function
@@ -46,22 +46,76 @@ pub(super) fn compile_chunk(L: *mut lua::lua_State, code: &str, name: &str) -> P
     }
 }
 
-/// Preload a lua source code as a module.
-pub(super) fn preload_lua_module(L: *mut lua::lua_State, code: &str, name: &str) -> PxsRes<()> {
-    let mut engine = Engine::new(L);
-    // Get package
-    engine.get_global("package");
-    engine.get_field(-1, "preload");
-    let preload_idx = engine.get_top();
+/// Load a binary chunk made by `dump_chunk`.
+pub(super) fn load_bytecode(L: *mut lua::lua_State, bytes: &[u8], name: &str) -> PxsRes<i32> {
+    let mut cstring = CStringSafe::new();
+    unsafe {
+        let res = lua::luaL_loadbufferx(
+            L,
+            bytes.as_ptr() as *const core::ffi::c_char,
+            bytes.len(),
+            cstring.new_string(name),
+            cstring.new_string("b"),
+        );
+        if res != LUA_OK {
+            let lua_error = lua_get_error(L);
+            return pxs_error!("{lua_error}");
+        }
+
+        Ok(lua::lua_gettop(L))
+    }
+}
 
-    // Compile code
-    engine.compile_chunk(code, name)?;
-    // run it
-    engine.call(0, 1)?;
+/// `lua_Writer` appending to a `Vec<u8>`.
+unsafe extern "C" fn chunk_writer(
+    _L: *mut lua::lua_State,
+    p: *const core::ffi::c_void,
+    sz: usize,
+    ud: *mut core::ffi::c_void,
+) -> core::ffi::c_int {
+    unsafe {
+        let bytes = &mut *(ud as *mut Vec<u8>);
+        bytes.extend_from_slice(std::slice::from_raw_parts(p as *const u8, sz));
+    }
+    0
+}
 
-    // Pass result ito upvalue
-    engine.push_function(module_loader, 1);
-    engine.set_field(preload_idx, name);
+/// Dump the function on top of the stack to a binary chunk. Debug info is kept for error messages.
+pub(super) fn dump_chunk(L: *mut lua::lua_State) -> Vec<u8> {
+    let mut bytes: Vec<u8> = vec![];
+    unsafe {
+        lua::lua_dump(L, Some(chunk_writer), &mut bytes as *mut Vec<u8> as *mut core::ffi::c_void, 0);
+    }
+    bytes
+}
+
+/// Push the table of a class. Calling the table calls the constructor.
+fn push_class(engine: &mut Engine, class: &pxs_Class) -> PxsRes<()> {
+    let table = engine.create_table(0, (class.variables.len() + class.callbacks.len()) as i32);
+
+    for var in class.variables.iter() {
+        engine.push_string(&var.name);
+        engine.push_pxs(&var.var)?;
+        engine.raw_set(table);
+    }
+
+    for cbk in class.callbacks.iter() {
+        engine.push_string(&cbk.name);
+        engine.push_integer(LUA_MODULE_BRIDGE_FUNCTION);
+        engine.push_integer(cbk.idx);
+        engine.push_function(lua::pxslua_callback, 2);
+        engine.raw_set(table);
+    }
+
+    if let Some(constructor) = &class.constructor {
+        let mt = engine.create_table(0, 1);
+        engine.push_string("__call");
+        engine.push_integer(LUA_CLASS_CALL_BRIDGE_FUNCTION);
+        engine.push_integer(constructor.idx);
+        engine.push_function(lua::pxslua_callback, 2);
+        engine.raw_set(mt);
+        engine.set_meta(table);
+    }
 
     Ok(())
 }
@@ -88,6 +142,13 @@ pub(super) fn add_module(state: *mut State, module: Arc<pxs_Module>) -> PxsRes<(
         engine.raw_set(table);
     }
 
+    // Add classes
+    for class in module.classes.iter() {
+        engine.push_string(&class.name);
+        push_class(&mut engine, class)?;
+        engine.raw_set(table);
+    }
+
     // Setup loader
 
     // Get preload
@@ -112,3 +173,26 @@ pub(super) fn add_module(state: *mut State, module: Arc<pxs_Module>) -> PxsRes<(
 
     Ok(())
 }
+
+/// Remove a module from `package.preload` and `package.loaded`.
+pub(super) fn remove_module(state: *mut State, module: &pxs_Module) {
+    let mut engine = Engine::from_state(state);
+    engine.get_global("package");
+    let package_idx = engine.get_top();
+
+    for table in ["preload", "loaded"] {
+        engine.get_field(package_idx, table);
+        let table_idx = engine.get_top();
+        engine.push_string(&module.name);
+        engine.push_nil();
+        engine.raw_set(table_idx);
+        engine.pop(1);
+    }
+
+    // Drop the engine to clean stack.
+    drop(engine);
+
+    for child in module.modules.iter() {
+        remove_module(state, child);
+    }
+}
@@ -8,21 +8,126 @@
 //
 use std::sync::Arc;
 
+use etffi::ptr_magic::PtrMagic;
+
 use crate::{
     borrow_string, lua::{
-        State, engine::Engine, func::{LUA_INDEX_BRIDGE_FUNCTION, LUA_NEWINDEX_BRIDGE_FUNCTION, LUA_OBJECT_BRIDGE_FUNCTION}, lua
+        LUA_REGISTRYINDEX, State, engine::Engine, func::{LUA_GC_BRIDGE_FUNCTION, LUA_INDEX_BRIDGE_FUNCTION, LUA_NEWINDEX_BRIDGE_FUNCTION, LUA_OBJECT_BRIDGE_FUNCTION}, get_lua_state, lua, lua_remove
     }, pxs_error, shared::{
-        PXS_PTR_NAME, PxsRes, object::{ObjectFlags, pxs_PixelObject}, utils::create_private_name
+        PXS_PTR_NAME, PxsRes, object::{ObjectCallback, ObjectFlags, collect_script_ref, pxs_Operator, pxs_PixelObject}, registry, utils::create_private_name
     }
 };
 
+/// Metatable field of the `pxs_Operator_Index` callback. Not `__index`, that one is the bridge.
+const LUA_INDEX_OPERATOR: &str = "_pxs_index";
+
+/// Metatable field holding the metatable of the parent type.
+const LUA_PARENT_FIELD: &str = "_pxs_parent";
+
+/// Registry field of the table holding object tables by object idx. Its values are weak, so tables scripts no longer
+/// reference are collected.
+const LUA_OBJECTS_FIELD: &str = "_pxs_objects";
+
+/// The language object of a host object, its key in `_pxs_objects`. Unlike `LuaReference` it does not keep the table
+/// alive.
+pub(super) struct LuaObjectReference {
+    pub idx: i32,
+}
+
+impl PtrMagic for LuaObjectReference {}
+
+impl Drop for LuaObjectReference {
+    fn drop(&mut self) {
+        let mut engine = Engine::from_state(get_lua_state());
+        let objects = push_objects_table(&mut engine);
+        engine.push_nil();
+        engine.raw_set_index(objects, self.idx);
+    }
+}
+
+impl LuaObjectReference {
+    /// Push the table, or nil if it was collected.
+    pub fn push(&self) {
+        let state = get_lua_state();
+        let mut engine = Engine::without_alloc(unsafe { (*state).engine });
+        let objects = push_objects_table(&mut engine);
+        engine.raw_get_index(objects, self.idx);
+        lua_remove(unsafe { (*state).engine }, objects);
+    }
+
+    /// Store the table on top of the stack as the object table. Leaves the stack as is.
+    pub fn store(&self) {
+        let state = get_lua_state();
+        let mut engine = Engine::from_state(state);
+        let table = engine.get_top();
+        let objects = push_objects_table(&mut engine);
+        engine.push_value(table);
+        engine.raw_set_index(objects, self.idx);
+    }
+}
+
+/// Push `_pxs_objects`, creating it the first time. Returns its position on the stack.
+fn push_objects_table(engine: &mut Engine) -> i32 {
+    engine.get_field(LUA_REGISTRYINDEX, LUA_OBJECTS_FIELD);
+    if engine.get_top_type() == lua::LUA_TTABLE as i32 {
+        return engine.get_top();
+    }
+    engine.pop(1);
+
+    let objects = engine.create_table(0, 0);
+    let mt = engine.create_table(0, 1);
+    engine.push_string("v");
+    engine.set_field(mt, "__mode");
+    engine.set_meta(objects);
+    engine.push_value(objects);
+    engine.set_field(LUA_REGISTRYINDEX, LUA_OBJECTS_FIELD);
+    objects
+}
+
+/// __gc, drops the reference the table owns of its host object.
+pub(super) fn lua_gc(L: *mut lua::lua_State) -> PxsRes<i32> {
+    let table = 1;
+    let mut engine = Engine::new(L);
+
+    engine.push_string(PXS_PTR_NAME);
+    engine.raw_get(table);
+    if engine.get_top_type() != lua::LUA_TNUMBER as i32 {
+        return Ok(0);
+    }
+    let idx = engine.to_integer(-1) as i32;
+
+    // A table made after this one was collected owns the reference now.
+    let objects = push_objects_table(&mut engine);
+    engine.raw_get_index(objects, idx);
+    let current = engine.get_top_type() == lua::LUA_TNIL as i32 || engine.raw_equal(-1, table);
+    drop(engine);
+
+    if current {
+        collect_script_ref(idx);
+    }
+    Ok(0)
+}
+
+/// Metatable field of a operator callback.
+fn operator_field(operator: pxs_Operator) -> String {
+    match operator {
+        pxs_Operator::pxs_Operator_Index => LUA_INDEX_OPERATOR.to_string(),
+        _ => format!("__{}", operator.name()),
+    }
+}
+
 /// __index
 pub(super) fn lua_index(L: *mut lua::lua_State) -> PxsRes<i32> {
     let table = 1;
     let key = 2;
 
     let function_name = unsafe {
-        borrow_string!(lua::lua_tolstring(L, key, core::ptr::null_mut())).to_string()
+        // Convert a copy, `lua_tolstring` changes numbers in place and the index operator needs the real key.
+        lua::lua_pushvalue(L, key);
+        let name = lua::lua_tolstring(L, -1, core::ptr::null_mut());
+        let name = if name.is_null() { String::new() } else { borrow_string!(name).to_string() };
+        lua::lua_settop(L, -2);
+        name
     };
     // Possible private name.
     let private_name = create_private_name(&function_name);
@@ -40,50 +145,80 @@ pub(super) fn lua_index(L: *mut lua::lua_State) -> PxsRes<i32> {
     // Pop nil from the stack
     engine.pop(1);
 
-    // Get the meta table because that is what has the values
-    engine.get_meta(table); // 1 (3)
-    let mt = engine.get_top();
-
-    // Check for non private name first
-    engine.push_string(&function_name); // 2 (4)
-    engine.raw_get(mt); // 2 (4)
+    // Get the meta table because that is what has the values. Then the metatables of parent types.
+    engine.get_meta(table);
+    loop {
+        let mt = engine.get_top();
+
+        // Check for non private name first
+        engine.push_string(&function_name);
+        engine.raw_get(mt);
+        if engine.get_type(engine.get_top()) != lua::LUA_TNIL as i32 {
+            // We have our result!
+            return Ok(1);
+        }
+        engine.pop(1);
+
+        // We may have a property
+        engine.push_string(&private_name);
+        engine.raw_get(mt);
+        let value_type = engine.get_type(engine.get_top());
+        if value_type == lua::LUA_TFUNCTION as i32 {
+            // Call the getter with the table
+            engine.push_value(table);
+            let res = engine.call(1, 1);
+            if res.is_err() {
+                return pxs_error!("{}", res.unwrap_err().to_string());
+            }
+            return Ok(1);
+        } else if value_type != lua::LUA_TNIL as i32 {
+            // Who knows what this is, just return it
+            return Ok(1);
+        }
+        engine.pop(1);
 
-    if engine.get_type(engine.get_top()) != lua::LUA_TNIL as i32 {
-        // We have our result!
-        engine.remove(3); // remove MT
-        return Ok(1);
+        if !push_parent_meta(&mut engine, mt) {
+            break;
+        }
     }
 
-    // We may have a property
-    engine.pop(1); // 1 (3) Now only MT is on top.
-
-    // Check private name
-    engine.push_string(&private_name); // 2 (4)
-    engine.raw_get(mt); // 2 (4)
-
-    if engine.get_type(engine.get_top()) != lua::LUA_TFUNCTION as i32 {
-        // Who knows what this is, just return it
-        engine.remove(3); // remove MT
-        return Ok(1);
-    }
+    // Not a method or property, maybe the object overloads indexing.
+    engine.get_meta(table);
+    loop {
+        let mt = engine.get_top();
+        engine.push_string(LUA_INDEX_OPERATOR);
+        engine.raw_get(mt);
+        if engine.get_type(engine.get_top()) == lua::LUA_TFUNCTION as i32 {
+            engine.push_value(table);
+            engine.push_value(key);
+            let res = engine.call(2, 1);
+            if res.is_err() {
+                return pxs_error!("{}", res.unwrap_err().to_string());
+            }
+            return Ok(1);
+        }
+        engine.pop(1);
 
-    // We have our function on top!
-    // Lets push the table
-    engine.push_value(table); // 3 (5)
-    let res = engine.call(1, 1); // if successfull 3 (5)
-    if res.is_err() {
-        engine.pop(1); // MT 
-        return pxs_error!("{}", res.unwrap_err().to_string());
+        if !push_parent_meta(&mut engine, mt) {
+            break;
+        }
     }
 
-    // We got a value!
-    // Just need to return it
-    // I need to remove MT only
-    engine.remove(3);
-    // Done
+    engine.push_nil();
     Ok(1)
 }
 
+/// Push the metatable of the parent type of metatable `mt`. Returns false (with nothing pushed) if there is none.
+fn push_parent_meta(engine: &mut Engine, mt: i32) -> bool {
+    engine.push_string(LUA_PARENT_FIELD);
+    engine.raw_get(mt);
+    if engine.get_type(engine.get_top()) != lua::LUA_TTABLE as i32 {
+        engine.pop(1);
+        return false;
+    }
+    true
+}
+
 /// __newindex
 pub(super) fn lua_newindex(L: *mut lua::lua_State) -> PxsRes<i32> {
     let table = 1;
@@ -98,13 +233,25 @@ pub(super) fn lua_newindex(L: *mut lua::lua_State) -> PxsRes<i32> {
 
     // Get MT
     engine.get_meta(table);
-    let mt = engine.get_top();
 
-    // Check if MT has private name
-    engine.push_string(&private_name);
-    engine.raw_get(mt);
+    // Check if MT (or the MT of a parent type) has private name
+    let mut has_setter = false;
+    loop {
+        let mt = engine.get_top();
+        engine.push_string(&private_name);
+        engine.raw_get(mt);
+        if engine.get_type(engine.get_top()) == lua::LUA_TFUNCTION as i32 {
+            has_setter = true;
+            break;
+        }
+        engine.pop(1);
 
-    if engine.get_type(engine.get_top()) != lua::LUA_TFUNCTION as i32 {
+        if !push_parent_meta(&mut engine, mt) {
+            break;
+        }
+    }
+
+    if !has_setter {
         // Set it like you would normally
         engine.push_value(key);
         engine.push_value(value);
@@ -124,6 +271,63 @@ pub(super) fn lua_newindex(L: *mut lua::lua_State) -> PxsRes<i32> {
     Ok(0)
 }
 
+/// Add object callbacks and the `__index`/`__newindex` bridges to metatable `mt`.
+fn bind_callbacks(engine: &mut Engine, mt: i32, callbacks: &[ObjectCallback]) {
+    for method in callbacks.iter() {
+        // The method name changes if a prop _pxs{name}_ or a operator __{name}.
+        let method_name = if let Some(operator) = method.operator {
+            operator_field(operator)
+        } else if method.flags & ObjectFlags::IsProp as u8 != 0 {
+            create_private_name(&method.cbk.name)
+        } else {
+            method.cbk.name.clone()
+        };
+
+        // Setup the function up values
+        engine.push_integer(LUA_OBJECT_BRIDGE_FUNCTION);
+        engine.push_integer(method.cbk.idx);
+        engine.push_integer(method.flags as i32);
+        engine.push_function(lua::pxslua_callback, 3);
+
+        // Add to metatable
+        engine.set_field(mt, &method_name);
+    }
+
+    // Bind __index
+    engine.push_string("__index");
+    engine.push_integer(LUA_INDEX_BRIDGE_FUNCTION);
+    engine.push_function(lua::pxslua_callback, 1);
+    engine.raw_set(mt);
+
+    // Bind __newindex
+    engine.push_string("__newindex");
+    engine.push_integer(LUA_NEWINDEX_BRIDGE_FUNCTION);
+    engine.push_function(lua::pxslua_callback, 1);
+    engine.raw_set(mt);
+
+    // Bind __gc, it has to be set before the metatable is assigned.
+    engine.push_string("__gc");
+    engine.push_integer(LUA_GC_BRIDGE_FUNCTION);
+    engine.push_function(lua::pxslua_callback, 1);
+    engine.raw_set(mt);
+}
+
+/// Push the metatable of `type_name`. If no object of the type was created yet it is made from the registered
+/// callbacks of the type.
+fn push_type_meta(engine: &mut Engine, type_name: &str) {
+    if engine.new_meta(type_name) == 0 {
+        return;
+    }
+    let mt = engine.get_top();
+    bind_callbacks(engine, mt, &registry::type_callbacks(type_name));
+
+    if let Some(parent) = registry::parent_of(type_name) {
+        engine.push_string(LUA_PARENT_FIELD);
+        push_type_meta(engine, &parent);
+        engine.raw_set(mt);
+    }
+}
+
 /// Create a new lua table and push it to stack. It's position on stack is returned.
 pub(super) fn create_object(
     state: *mut State,
@@ -154,41 +358,28 @@ pub(super) fn create_object(
     // Create a new meta table.
     let mt = engine.get_top();
 
-    // Add callbacks
-    for method in source.callbacks.iter() {
-        // The method name changes if a prop _pxs{name}_.
-        let method_name = if method.flags & ObjectFlags::IsProp as u8 != 0 {
-            create_private_name(&method.cbk.name)
-        } else {
-            method.cbk.name.clone()
-        };
-
-        // Setup the function up values
-        engine.push_integer(LUA_OBJECT_BRIDGE_FUNCTION);
-        engine.push_integer(method.cbk.idx);
-        engine.push_integer(method.flags as i32);
-        engine.push_function(lua::pxslua_callback, 3);
-        // engine.push_function(lua_object_bridge, 2);
+    bind_callbacks(&mut engine, mt, &source.callbacks);
 
-        // Add to metatable
-        engine.set_field(mt, &method_name);
+    // Chain the metatable of the parent type.
+    if let Some(parent) = registry::parent_of(&source.type_name) {
+        engine.push_string(LUA_PARENT_FIELD);
+        push_type_meta(&mut engine, &parent);
+        engine.raw_set(mt);
     }
 
-    // Bind __index
-    engine.push_string("__index");
-    engine.push_integer(LUA_INDEX_BRIDGE_FUNCTION);
-    engine.push_function(lua::pxslua_callback, 1);
-    engine.raw_set(mt);
-
-    // Bind __newindex
-    engine.push_string("__newindex");
-    engine.push_integer(LUA_NEWINDEX_BRIDGE_FUNCTION);
-    engine.push_function(lua::pxslua_callback, 1);
-    engine.raw_set(mt);
-
     // Just put it on the top
     engine.push_value(mt);
 
     // Assign MT to table
     engine.set_meta(table);
 }
+
+/// Drop the metatables of `type_names` from the registry, so objects of a reloaded type get a new one. Existing objects
+/// keep their metatable.
+pub(super) fn remove_object_types(state: *mut State, type_names: &[String]) {
+    let mut engine = Engine::from_state(state);
+    for type_name in type_names {
+        engine.push_nil();
+        engine.set_field(LUA_REGISTRYINDEX, type_name);
+    }
+}
@@ -9,12 +9,12 @@
 
 use std::sync::Arc;
 
-use etffi::{borrow_string, cstring::CStringSafe};
+use etffi::cstring::CStringSafe;
 
 // Pure Rust goes here
 use crate::{
-    lua::{LUA_TBOOLEAN, LUA_TFUNCTION, LUA_TNONE, LUA_TNUMBER, LUA_TSTRING, LUA_TTABLE, LuaReference, get_lua_state, lua::{self, lua_createtable, lua_geti, lua_gettop, lua_rawseti, lua_settable}, lua_pop, object::create_object}, pxs_error, shared::{
-        PxsRes, PxsResult, object::get_object, pxs_Opaque, pxs_Runtime, var::{pxs_Var, pxs_VarObject, pxs_VarType}
+    lua::{LUA_TBOOLEAN, LUA_TFUNCTION, LUA_TNIL, LUA_TNONE, LUA_TNUMBER, LUA_TSTRING, LUA_TTABLE, LuaReference, get_lua_state, lua::{self, lua_createtable, lua_geti, lua_gettop, lua_rawseti, lua_settable}, lua_pop, object::{LuaObjectReference, create_object}}, pxs_error, shared::{
+        PxsRes, PxsResult, object::{get_object, retain_script_ref}, pxs_Opaque, pxs_Runtime, var::{pxs_Var, pxs_VarObject, pxs_VarType}
     }
 };
 use etffi::ptr_magic::PtrMagic;
@@ -24,6 +24,11 @@ unsafe extern "C" fn free_lua_mem(ptr: pxs_Opaque) {
     let _ = LuaReference::from_raw(ptr as *mut LuaReference);
 }
 
+/// Free the language object of a host object.
+unsafe extern "C" fn free_lua_object(ptr: pxs_Opaque) {
+    let _ = LuaObjectReference::from_raw(ptr as *mut LuaObjectReference);
+}
+
 /// Convert a Lua value to a Var.
 pub(super) fn from_lua(idx: i32) -> PxsResult {
     unsafe {
@@ -51,14 +56,19 @@ pub(super) fn from_lua(idx: i32) -> PxsResult {
             let lua_bool = lua::lua_toboolean(L, idx);
             Ok(pxs_Var::new_bool(lua_bool == 1))
         } else if lua_type == LUA_TSTRING {
-            let lua_string = lua::lua_tolstring(L, idx, std::ptr::null_mut());
-            let rust_string = borrow_string!(lua_string);
-            Ok(pxs_Var::new_string(rust_string.to_string()))
+            let mut len = 0;
+            let lua_string = lua::lua_tolstring(L, idx, &mut len);
+            let raw = std::slice::from_raw_parts(lua_string as *const u8, len);
+            // Lua strings are byte strings. Anything that is not valid text comes through as bytes.
+            match std::str::from_utf8(raw) {
+                Ok(rust_string) if !raw.contains(&0) => Ok(pxs_Var::new_string(rust_string.to_string())),
+                _ => Ok(pxs_Var::new_bytes(raw.to_vec())),
+            }
         } else if lua_type == LUA_TFUNCTION {
             // Register the lua value.
             let reference = LuaReference::new();
             reference.push();
-            Ok(pxs_Var::new_function(reference.into_void(), Some(free_lua_mem)))
+            Ok(pxs_Var::new_function(reference.into_void(), Some(free_lua_mem)).with_origin(pxs_Runtime::pxs_Lua))
         } else if lua_type == LUA_TTABLE {
             // Check length
             let t_length = lua::lua_rawlen(L, idx);
@@ -67,7 +77,7 @@ pub(super) fn from_lua(idx: i32) -> PxsResult {
                 // Register table
                 let reference = LuaReference::new();
                 reference.push();
-                Ok(pxs_Var::new_object(pxs_VarObject::new_lang_only(reference.into_void()), Some(free_lua_mem)))
+                Ok(pxs_Var::new_object(pxs_VarObject::new_lang_only(reference.into_void()), Some(free_lua_mem)).with_origin(pxs_Runtime::pxs_Lua))
             } else {
                 // List dayo!
                 let mut values = vec![];
@@ -122,23 +132,29 @@ pub(super) fn push_lua_stack(var: &pxs_Var) -> PxsRes<i32> {
             pxs_VarType::pxs_HostObject => {
                 // Get the `PixelObject`
                 let idx = var.value.host_object_val;
-                let pixel_object = get_object(idx).unwrap();
-                let lang_ptr_is_null = pixel_object.lang_ptr.lock().unwrap().is_null();
-                if lang_ptr_is_null {
-                    // Create the table for the first time
-                    create_object(state, idx, Arc::clone(&pixel_object));
-
-                    // Add table ptr
-                    let table_ptr = LuaReference::new();
-                    pixel_object.update_lang_ptr(table_ptr.into_void());
-                    pixel_object.update_pxs_free_method(free_lua_mem);
+                let Some(pixel_object) = get_object(idx) else {
+                    return pxs_error!("Host object {idx} was released");
+                };
+                let lang_ptr = *pixel_object.lang_ptr.lock().unwrap();
+                if !lang_ptr.is_null() {
+                    LuaObjectReference::from_borrow_void(lang_ptr).push();
+                    if lua::lua_type(L, -1) != LUA_TNIL {
+                        return Ok(lua_gettop(L));
+                    }
+                    lua_pop(L, 1);
                 }
-                // Get PTR again.
-                let lang_ptr = pixel_object.lang_ptr.lock().unwrap();
-                // Get as table.
-                let table = LuaReference::from_borrow_void(*lang_ptr);
-                // Return table
-                table.push();
+
+                // Create the table, for the first time or because the last one was collected.
+                create_object(state, idx, Arc::clone(&pixel_object));
+                let reference = LuaObjectReference { idx };
+                reference.store();
+                if lang_ptr.is_null() {
+                    pixel_object.update_lang_ptr(reference.into_void());
+                    pixel_object.update_pxs_free_method(free_lua_object);
+                }
+                drop(pixel_object);
+                // The table owns a reference until it is collected.
+                retain_script_ref(idx);
             },
             pxs_VarType::pxs_List => {
                 let items = &var.get_list().unwrap().vars;
@@ -184,6 +200,17 @@ pub(super) fn push_lua_stack(var: &pxs_Var) -> PxsRes<i32> {
             },
             pxs_VarType::pxs_Byte => {
                 lua::lua_pushinteger(L, var.get_byte()? as i64);
+            },
+            pxs_VarType::pxs_Bytes => {
+                let bytes = var.get_bytes().unwrap();
+                lua::lua_pushlstring(L, bytes.as_ptr() as *const std::ffi::c_char, bytes.len());
+            }
+            pxs_VarType::pxs_Int32 => lua::lua_pushinteger(L, var.get_i32()? as i64),
+            pxs_VarType::pxs_UInt32 => lua::lua_pushinteger(L, var.get_u32()? as i64),
+            pxs_VarType::pxs_Float32 => lua::lua_pushnumber(L, var.get_f32()? as f64),
+            // Lua has no typed arrays, scripts get a table of numbers.
+            pxs_VarType::pxs_TypedArray => {
+                push_lua_stack(&var.get_typed_array().unwrap().to_list())?;
             }
         }
 
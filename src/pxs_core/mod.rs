@@ -1,4 +1,8 @@
-use crate::{pxs_varis, shared::var::{pxs_VarT, pxs_VarType}};
+use etffi::ptr_magic::PtrMagic;
+
+use crate::{own_var, pxs_error, pxs_listget, pxs_listlen, pxs_tostring, pxs_varis, shared::{PxsRes, var::{pxs_Var, pxs_VarT, pxs_VarType}}};
+
+pub(crate) use crate::shared::fields::{get_entries, get_field, get_pairs, set_field};
 
 // Copyright 2026 Jordan Castro <jordan@grupojvm.com>
 //
@@ -13,6 +17,48 @@ use crate::{pxs_varis, shared::var::{pxs_VarT, pxs_VarType}};
 pub mod pxs_json;
 #[cfg(feature="pxs_mem")]
 pub mod pxs_mem;
+#[cfg(feature="pxs_template")]
+pub mod pxs_template;
+#[cfg(feature="pxs_csv")]
+pub mod pxs_csv;
+#[cfg(feature="pxs_color")]
+pub mod pxs_color;
+#[cfg(feature="pxs_units")]
+pub mod pxs_units;
+#[cfg(feature="pxs_geom")]
+pub mod pxs_geom;
+#[cfg(feature="pxs_noise")]
+pub mod pxs_noise;
+#[cfg(feature="pxs_path")]
+pub mod pxs_path;
+#[cfg(feature="pxs_audio")]
+pub mod pxs_audio;
+#[cfg(feature="pxs_ui")]
+pub mod pxs_ui;
+#[cfg(feature="pxs_save")]
+pub mod pxs_save;
+#[cfg(feature="pxs_clock")]
+pub mod pxs_clock;
+#[cfg(feature="pxs_jobs")]
+pub mod pxs_jobs;
+#[cfg(feature="pxs_channel")]
+pub mod pxs_channel;
+#[cfg(feature="pxs_events")]
+pub mod pxs_events;
+#[cfg(feature="pxs_fs")]
+pub mod pxs_fs;
+#[cfg(feature="pxs_str")]
+pub mod pxs_str;
+#[cfg(feature="pxs_math")]
+pub mod pxs_math;
+#[cfg(feature="pxs_random")]
+pub mod pxs_random;
+#[cfg(feature="pxs_time")]
+pub mod pxs_time;
+#[cfg(feature="pxs_log")]
+pub mod pxs_log;
+#[cfg(feature="pxs_http")]
+pub mod pxs_http;
 
 /// This will check if the arguments are valid to be passed into a pxs_Func.
 /// This is only used in core functions exposed to lib.
@@ -29,4 +75,67 @@ pub(crate) unsafe fn is_valid_pxs_function(rt: pxs_VarT, args: pxs_VarT) -> bool
     }
 
     true
-}
\ No newline at end of file
+}
+
+/// Get a number (`pxs_Int64`, `pxs_UInt64`, `pxs_Float64` and the 32 bit types) as a f64.
+#[allow(unused)]
+pub(crate) fn get_number(var: &pxs_Var) -> PxsRes<f64> {
+    match var.tag {
+        pxs_VarType::pxs_Int64 => Ok(var.get_i64()? as f64),
+        pxs_VarType::pxs_UInt64 => Ok(var.get_u64()? as f64),
+        pxs_VarType::pxs_Float64 => var.get_f64(),
+        pxs_VarType::pxs_Int32 => Ok(var.get_i32()? as f64),
+        pxs_VarType::pxs_UInt32 => Ok(var.get_u32()? as f64),
+        pxs_VarType::pxs_Float32 => Ok(var.get_f32()? as f64),
+        _ => pxs_error!("Expected a number, got: {:#?}.", var.tag),
+    }
+}
+
+/// Text of a value. Script objects use their own `tostring`.
+#[allow(unused)]
+pub(crate) fn to_text(rt: pxs_VarT, var: &pxs_Var) -> PxsRes<String> {
+    match var.tag {
+        pxs_VarType::pxs_String => var.get_string(),
+        pxs_VarType::pxs_Null => Ok("null".to_string()),
+        pxs_VarType::pxs_Bool => Ok(var.get_bool()?.to_string()),
+        pxs_VarType::pxs_Int64 => Ok(var.get_i64()?.to_string()),
+        pxs_VarType::pxs_UInt64 => Ok(var.get_u64()?.to_string()),
+        pxs_VarType::pxs_Int32 => Ok(var.get_i32()?.to_string()),
+        pxs_VarType::pxs_UInt32 => Ok(var.get_u32()?.to_string()),
+        pxs_VarType::pxs_Byte => Ok(var.get_byte()?.to_string()),
+        // Debug keeps the `.0` like Lua and Python do.
+        pxs_VarType::pxs_Float64 => Ok(format!("{:?}", var.get_f64()?)),
+        pxs_VarType::pxs_Float32 => Ok(format!("{:?}", var.get_f32()?)),
+        _ => {
+            let string = own_var!(pxs_tostring(rt, var as *const pxs_Var as pxs_VarT));
+            if string.is_exception() {
+                return pxs_error!("{}", string.get_string()?);
+            }
+            string.get_string()
+        }
+    }
+}
+
+/// SplitMix64. A tiny seedable generator that gives the same numbers on every platform.
+#[allow(unused)]
+pub(crate) fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E3779B97F4A7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+    z ^ (z >> 31)
+}
+
+/// Run a core function. Checks that `args` has between `min` and `max` items (runtime included) and converts
+/// errors into exceptions. `func` receives the runtime var.
+#[allow(unused)]
+pub(crate) fn core_call(args: pxs_VarT, min: usize, max: usize, func: impl FnOnce(pxs_VarT) -> PxsRes<pxs_Var>) -> pxs_VarT {
+    let len = pxs_listlen(args).max(0) as usize;
+    if len < min || len > max {
+        return pxs_Var::expected_n_args_ep(min as u32, len as u32).into_raw();
+    }
+    match func(pxs_listget(args, 0)) {
+        Ok(res) => res.into_raw(),
+        Err(e) => pxs_Var::new_exception(e).into_raw(),
+    }
+}
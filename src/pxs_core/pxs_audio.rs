@@ -0,0 +1,162 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+use std::{cell::RefCell, collections::HashSet};
+
+use etffi::cstring::CStringSafe;
+
+use crate::{
+    borrow_var, pxs_addfunc, pxs_addmod, pxs_error, pxs_gethost_checked, pxs_listget, pxs_listlen, pxs_newhost,
+    pxs_newmod, pxs_newobject, pxs_object_addfunc,
+    pxs_core::core_call,
+    shared::{
+        PxsRes, get_audio_callbacks, pxs_AudioParamFn, pxs_AudioPlayFn, pxs_AudioStopFn, pxs_Opaque,
+        var::{pxs_Var, pxs_VarT, pxs_VarType},
+    },
+};
+use etffi::ptr_magic::PtrMagic;
+
+thread_local! {
+    /// Voices started by scripts on this thread that have not been stopped yet.
+    static ACTIVE_VOICES: RefCell<HashSet<i64>> = RefCell::new(HashSet::new());
+}
+
+/// A playing voice. Stopped automatically when the script drops it.
+struct Voice {
+    id: i64,
+}
+
+impl PtrMagic for Voice {}
+
+fn callbacks() -> PxsRes<(pxs_AudioPlayFn, pxs_AudioParamFn, pxs_AudioStopFn)> {
+    match get_audio_callbacks() {
+        Some(cbks) => Ok(cbks),
+        None => pxs_error!("No audio callbacks set. Call `pxs_audio_setcallbacks` from the host."),
+    }
+}
+
+/// Stop a voice if it is still active.
+fn stop_voice(id: i64) {
+    let was_active = ACTIVE_VOICES.with(|voices| voices.borrow_mut().remove(&id));
+    if !was_active {
+        return;
+    }
+    if let Some((_, _, stop)) = get_audio_callbacks() {
+        unsafe { stop(id) };
+    }
+}
+
+/// Voice free method.
+unsafe extern "C" fn free_voice(ptr: pxs_Opaque) {
+    let voice = unsafe { Voice::from_raw(ptr as *mut Voice) };
+    stop_voice(voice.id);
+}
+
+/// Get the voice id from a `Voice` object. Errors on any other value, other host objects included.
+fn voice_id(rt: pxs_VarT, var: pxs_VarT) -> PxsRes<i64> {
+    let mut cstrgen = CStringSafe::new();
+    let ptr = pxs_gethost_checked(rt, var, cstrgen.new_string("Voice"));
+    if ptr.is_null() {
+        return pxs_error!("Expected a Voice.");
+    }
+    Ok(unsafe { Voice::from_borrow_void(ptr) }.id)
+}
+
+/// Forward a param to the host.
+fn set_param(id: i64, name: &pxs_Var, value: &pxs_Var) -> PxsRes<pxs_Var> {
+    if !name.is_string() {
+        return pxs_error!("Param name must be a String, got: {:#?}.", name.tag);
+    }
+    let (_, set_param, _) = callbacks()?;
+    let mut cstrgen = CStringSafe::new();
+    unsafe {
+        set_param(
+            id,
+            cstrgen.new_string(&name.get_string()?),
+            value as *const pxs_Var as pxs_VarT,
+        )
+    };
+    Ok(pxs_Var::new_null())
+}
+
+/// `voice.set_param(name, value)`
+extern "C" fn pxs_audio_voice_set_param(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 4, 4, |rt| {
+        let id = voice_id(rt, pxs_listget(args, 1))?;
+        set_param(id, borrow_var!(pxs_listget(args, 2)), borrow_var!(pxs_listget(args, 3)))
+    })
+}
+
+/// `voice.stop()`
+extern "C" fn pxs_audio_voice_stop(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 2, 2, |rt| {
+        stop_voice(voice_id(rt, pxs_listget(args, 1))?);
+        Ok(pxs_Var::new_null())
+    })
+}
+
+/// `play(event, params?)` returns a `Voice`.
+extern "C" fn pxs_audio_play(args: pxs_VarT) -> pxs_VarT {
+    let len = pxs_listlen(args);
+    if len != 2 && len != 3 {
+        return pxs_Var::expected_n_args_ep(2, len as u32).into_raw();
+    }
+    let (play, _, _) = match callbacks() {
+        Ok(cbks) => cbks,
+        Err(e) => return pxs_Var::new_exception(e).into_raw(),
+    };
+
+    let event = borrow_var!(pxs_listget(args, 1));
+    if !event.is_string() {
+        return pxs_Var::incorrect_type_ep(pxs_VarType::pxs_String, event.tag).into_raw();
+    }
+    let null = pxs_Var::new_null();
+    let params = if len == 3 { pxs_listget(args, 2) } else { &null as *const pxs_Var as pxs_VarT };
+
+    let mut cstrgen = CStringSafe::new();
+    let id = unsafe { play(cstrgen.new_string(&event.get_string().unwrap()), params) };
+    ACTIVE_VOICES.with(|voices| voices.borrow_mut().insert(id));
+
+    let object = pxs_newobject(Voice { id }.into_void(), free_voice, cstrgen.new_string("Voice"));
+    pxs_object_addfunc(object, cstrgen.new_string("set_param"), pxs_audio_voice_set_param);
+    pxs_object_addfunc(object, cstrgen.new_string("stop"), pxs_audio_voice_stop);
+    pxs_newhost(object)
+}
+
+/// `set_param(voice, name, value)`
+extern "C" fn pxs_audio_set_param(args: pxs_VarT) -> pxs_VarT {
+    pxs_audio_voice_set_param(args)
+}
+
+/// `stop(voice)`
+extern "C" fn pxs_audio_stop(args: pxs_VarT) -> pxs_VarT {
+    pxs_audio_voice_stop(args)
+}
+
+/// `stop_all()` stops every voice started on this thread.
+extern "C" fn pxs_audio_stop_all(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 1, 1, |_| {
+        let ids: Vec<i64> = ACTIVE_VOICES.with(|voices| voices.borrow().iter().copied().collect());
+        for id in ids {
+            stop_voice(id);
+        }
+        Ok(pxs_Var::new_null())
+    })
+}
+
+/// Initialize `pxs_audio` module.
+pub(crate) fn init() {
+    let mut cstrgen = CStringSafe::new();
+
+    let pxs_audio = pxs_newmod(cstrgen.new_string("pxs_audio"));
+    pxs_addfunc(pxs_audio, cstrgen.new_string("play"), pxs_audio_play);
+    pxs_addfunc(pxs_audio, cstrgen.new_string("set_param"), pxs_audio_set_param);
+    pxs_addfunc(pxs_audio, cstrgen.new_string("stop"), pxs_audio_stop);
+    pxs_addfunc(pxs_audio, cstrgen.new_string("stop_all"), pxs_audio_stop_all);
+    pxs_addmod(pxs_audio);
+}
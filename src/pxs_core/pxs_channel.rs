@@ -0,0 +1,73 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+use etffi::{cstring::CStringSafe, ptr_magic::PtrMagic};
+
+use crate::{
+    borrow_var, pxs_addfunc, pxs_addmod, pxs_listget, pxs_listlen, pxs_newmod,
+    pxs_core::{core_call, get_number},
+    shared::{
+        channel,
+        func::pxs_Func,
+        var::{pxs_Var, pxs_VarT},
+    },
+};
+
+/// The channel id argument.
+fn channel_id(args: pxs_VarT) -> Result<i32, String> {
+    Ok(get_number(borrow_var!(pxs_listget(args, 1)))? as i32)
+}
+
+/// `new()` a new channel id.
+extern "C" fn script_new(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 1, 1, |_| Ok(pxs_Var::new_i64(channel::new() as i64)))
+}
+
+/// `send(channel, value)` send a copy of `value`. Only data can be sent.
+extern "C" fn script_send(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 3, 3, |_| {
+        channel::send(channel_id(args)?, borrow_var!(pxs_listget(args, 2)))?;
+        Ok(pxs_Var::new_null())
+    })
+}
+
+/// `recv(channel, timeout_ms?)` the oldest value, nil after `timeout_ms`. Waits forever without a timeout.
+extern "C" fn script_recv(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 2, 3, |_| {
+        let timeout = if pxs_listlen(args) > 2 { get_number(borrow_var!(pxs_listget(args, 2)))? as i64 } else { -1 };
+        Ok(channel::recv(channel_id(args)?, timeout)?.unwrap_or_else(pxs_Var::new_null))
+    })
+}
+
+/// `len(channel)` number of values waiting.
+extern "C" fn script_len(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 2, 2, |_| Ok(pxs_Var::new_i64(channel::len(channel_id(args)?)? as i64)))
+}
+
+/// `close(channel)` remove the channel. Returns false if it did not exist.
+extern "C" fn script_close(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 2, 2, |_| Ok(pxs_Var::new_bool(channel::close(channel_id(args)?))))
+}
+
+/// Initialize `pxs_channel` module.
+pub(crate) fn init() {
+    let mut cstrgen = CStringSafe::new();
+
+    let pxs_channel = pxs_newmod(cstrgen.new_string("pxs_channel"));
+    let funcs: [(&str, pxs_Func); 5] = [
+        ("new", script_new),
+        ("send", script_send),
+        ("recv", script_recv),
+        ("len", script_len),
+        ("close", script_close),
+    ];
+    for (name, func) in funcs {
+        pxs_addfunc(pxs_channel, cstrgen.new_string(name), func);
+    }
+    pxs_addmod(pxs_channel);
+}
@@ -0,0 +1,78 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+use etffi::cstring::CStringSafe;
+
+use crate::{
+    borrow_var, pxs_addfunc, pxs_addmod, pxs_error, pxs_listget, pxs_newmod,
+    pxs_core::{core_call, get_number},
+    shared::{
+        PxsRes, get_clock_callbacks, pxs_ClockFrameFn, pxs_ClockTimeFn,
+        func::pxs_Func,
+        var::{pxs_Var, pxs_VarT},
+    },
+};
+use etffi::ptr_magic::PtrMagic;
+
+/// All time queries go through the host so scripts follow pause, time scaling, and replays.
+fn callbacks() -> PxsRes<(pxs_ClockTimeFn, pxs_ClockTimeFn, pxs_ClockFrameFn)> {
+    match get_clock_callbacks() {
+        Some(cbks) => Ok(cbks),
+        None => pxs_error!("No clock callbacks set. Call `pxs_clock_setcallbacks` from the host."),
+    }
+}
+
+/// `game_time()` seconds of game time. Follows pause and time scaling.
+extern "C" fn pxs_clock_game_time(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 1, 1, |_| {
+        let (game_time, _, _) = callbacks()?;
+        Ok(pxs_Var::new_f64(unsafe { game_time() }))
+    })
+}
+
+/// `real_time()` seconds of real time.
+extern "C" fn pxs_clock_real_time(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 1, 1, |_| {
+        let (_, real_time, _) = callbacks()?;
+        Ok(pxs_Var::new_f64(unsafe { real_time() }))
+    })
+}
+
+/// `frame()` current frame index.
+extern "C" fn pxs_clock_frame(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 1, 1, |_| {
+        let (_, _, frame) = callbacks()?;
+        Ok(pxs_Var::new_i64(unsafe { frame() }))
+    })
+}
+
+/// `since(t)` seconds of game time elapsed since `t`.
+extern "C" fn pxs_clock_since(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 2, 2, |_| {
+        let t = get_number(borrow_var!(pxs_listget(args, 1)))?;
+        let (game_time, _, _) = callbacks()?;
+        Ok(pxs_Var::new_f64(unsafe { game_time() } - t))
+    })
+}
+
+/// Initialize `pxs_clock` module.
+pub(crate) fn init() {
+    let mut cstrgen = CStringSafe::new();
+
+    let pxs_clock = pxs_newmod(cstrgen.new_string("pxs_clock"));
+    let funcs: [(&str, pxs_Func); 4] = [
+        ("game_time", pxs_clock_game_time),
+        ("real_time", pxs_clock_real_time),
+        ("frame", pxs_clock_frame),
+        ("since", pxs_clock_since),
+    ];
+    for (name, func) in funcs {
+        pxs_addfunc(pxs_clock, cstrgen.new_string(name), func);
+    }
+    pxs_addmod(pxs_clock);
+}
@@ -0,0 +1,222 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+use etffi::cstring::CStringSafe;
+
+use crate::{
+    borrow_var, pxs_addfunc, pxs_addmod, pxs_error, pxs_listget, pxs_listlen, pxs_newmod,
+    pxs_core::{core_call, get_field, get_number},
+    shared::{
+        PxsRes,
+        var::{pxs_Var, pxs_VarT},
+    },
+};
+use etffi::ptr_magic::PtrMagic;
+
+/// A RGBA color. Each channel is 0-255.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Rgba {
+    r: f64,
+    g: f64,
+    b: f64,
+    a: f64,
+}
+
+impl Rgba {
+    /// Read a color from a map/object with `r`, `g`, `b` and optional `a` keys.
+    fn from_var(rt: pxs_VarT, var: &pxs_Var) -> PxsRes<Self> {
+        let channel = |key: &str, default: Option<f64>| -> PxsRes<f64> {
+            match get_field(rt, var, key).filter(|v| !v.is_null()) {
+                Some(v) => Ok(get_number(&v)?.clamp(0.0, 255.0)),
+                None => match default {
+                    Some(d) => Ok(d),
+                    None => pxs_error!("Color is missing channel '{key}'."),
+                },
+            }
+        };
+        Ok(Rgba {
+            r: channel("r", None)?,
+            g: channel("g", None)?,
+            b: channel("b", None)?,
+            a: channel("a", Some(255.0))?,
+        })
+    }
+
+    /// Convert into a `pxs_Map` with `r`, `g`, `b`, `a` integer keys.
+    fn into_var(self) -> pxs_Var {
+        let res = pxs_Var::new_map();
+        let map = res.get_map().unwrap();
+        for (key, value) in [("r", self.r), ("g", self.g), ("b", self.b), ("a", self.a)] {
+            map.add_item(pxs_Var::new_string(key.to_string()), pxs_Var::new_i64(value.round() as i64));
+        }
+        res
+    }
+}
+
+/// Parse `#rgb`, `#rgba`, `#rrggbb` or `#rrggbbaa` (the `#` is optional).
+fn parse_hex(hex: &str) -> PxsRes<Rgba> {
+    let digits = hex.trim().trim_start_matches('#');
+    if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        return pxs_error!("Invalid hex color: '{hex}'.");
+    }
+    // Expand short forms.
+    let expanded: String = match digits.len() {
+        3 | 4 => digits.chars().flat_map(|c| [c, c]).collect(),
+        6 | 8 => digits.to_string(),
+        _ => return pxs_error!("Invalid hex color: '{hex}'."),
+    };
+    let channel = |i: usize| u8::from_str_radix(&expanded[i * 2..i * 2 + 2], 16).unwrap() as f64;
+    Ok(Rgba {
+        r: channel(0),
+        g: channel(1),
+        b: channel(2),
+        a: if expanded.len() == 8 { channel(3) } else { 255.0 },
+    })
+}
+
+/// Format as `#rrggbb` or `#rrggbbaa`.
+fn to_hex(color: &Rgba, alpha: bool) -> String {
+    let c = |v: f64| v.round() as u8;
+    if alpha {
+        format!("#{:02x}{:02x}{:02x}{:02x}", c(color.r), c(color.g), c(color.b), c(color.a))
+    } else {
+        format!("#{:02x}{:02x}{:02x}", c(color.r), c(color.g), c(color.b))
+    }
+}
+
+/// RGB to HSV. Returns (h 0-360, s 0-1, v 0-1).
+fn rgb_to_hsv(color: &Rgba) -> (f64, f64, f64) {
+    let (r, g, b) = (color.r / 255.0, color.g / 255.0, color.b / 255.0);
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let delta = max - min;
+
+    let h = if delta == 0.0 {
+        0.0
+    } else if max == r {
+        60.0 * ((g - b) / delta).rem_euclid(6.0)
+    } else if max == g {
+        60.0 * ((b - r) / delta + 2.0)
+    } else {
+        60.0 * ((r - g) / delta + 4.0)
+    };
+    let s = if max == 0.0 { 0.0 } else { delta / max };
+
+    (h, s, max)
+}
+
+/// HSV (h 0-360, s 0-1, v 0-1) to RGB.
+fn hsv_to_rgb(h: f64, s: f64, v: f64, a: f64) -> Rgba {
+    let h = h.rem_euclid(360.0);
+    let s = s.clamp(0.0, 1.0);
+    let v = v.clamp(0.0, 1.0);
+
+    let c = v * s;
+    let x = c * (1.0 - ((h / 60.0).rem_euclid(2.0) - 1.0).abs());
+    let m = v - c;
+    let (r, g, b) = match (h / 60.0) as i32 {
+        0 => (c, x, 0.0),
+        1 => (x, c, 0.0),
+        2 => (0.0, c, x),
+        3 => (0.0, x, c),
+        4 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+    };
+
+    Rgba {
+        r: (r + m) * 255.0,
+        g: (g + m) * 255.0,
+        b: (b + m) * 255.0,
+        a,
+    }
+}
+
+/// `hex_to_rgba(hex) -> {r, g, b, a}`
+extern "C" fn pxs_color_hex_to_rgba(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 2, 2, |_| {
+        let hex = borrow_var!(pxs_listget(args, 1)).get_string()?;
+        Ok(parse_hex(&hex)?.into_var())
+    })
+}
+
+/// `rgba_to_hex(color, alpha?) -> "#rrggbb"`
+extern "C" fn pxs_color_rgba_to_hex(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 2, 3, |rt| {
+        let color = Rgba::from_var(rt, borrow_var!(pxs_listget(args, 1)))?;
+        let alpha = if pxs_listlen(args) == 3 {
+            borrow_var!(pxs_listget(args, 2)).get_bool()?
+        } else {
+            false
+        };
+        Ok(pxs_Var::new_string(to_hex(&color, alpha)))
+    })
+}
+
+/// `rgb_to_hsv(color) -> {h, s, v, a}`
+extern "C" fn pxs_color_rgb_to_hsv(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 2, 2, |rt| {
+        let color = Rgba::from_var(rt, borrow_var!(pxs_listget(args, 1)))?;
+        let (h, s, v) = rgb_to_hsv(&color);
+        let res = pxs_Var::new_map();
+        let map = res.get_map().unwrap();
+        for (key, value) in [("h", h), ("s", s), ("v", v)] {
+            map.add_item(pxs_Var::new_string(key.to_string()), pxs_Var::new_f64(value));
+        }
+        map.add_item(pxs_Var::new_string("a".to_string()), pxs_Var::new_i64(color.a.round() as i64));
+        Ok(res)
+    })
+}
+
+/// `hsv_to_rgb(hsv) -> {r, g, b, a}`
+extern "C" fn pxs_color_hsv_to_rgb(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 2, 2, |rt| {
+        let hsv = borrow_var!(pxs_listget(args, 1));
+        let field = |key: &str| -> PxsRes<Option<f64>> {
+            match get_field(rt, hsv, key).filter(|v| !v.is_null()) {
+                Some(v) => Ok(Some(get_number(&v)?)),
+                None => Ok(None),
+            }
+        };
+        let (h, s, v) = match (field("h")?, field("s")?, field("v")?) {
+            (Some(h), Some(s), Some(v)) => (h, s, v),
+            _ => return pxs_error!("HSV color needs 'h', 's' and 'v'."),
+        };
+        let a = field("a")?.unwrap_or(255.0).clamp(0.0, 255.0);
+        Ok(hsv_to_rgb(h, s, v, a).into_var())
+    })
+}
+
+/// `lerp(from, to, t) -> {r, g, b, a}`
+extern "C" fn pxs_color_lerp(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 4, 4, |rt| {
+        let from = Rgba::from_var(rt, borrow_var!(pxs_listget(args, 1)))?;
+        let to = Rgba::from_var(rt, borrow_var!(pxs_listget(args, 2)))?;
+        let t = get_number(borrow_var!(pxs_listget(args, 3)))?.clamp(0.0, 1.0);
+        let lerp = |a: f64, b: f64| a + (b - a) * t;
+        Ok(Rgba {
+            r: lerp(from.r, to.r),
+            g: lerp(from.g, to.g),
+            b: lerp(from.b, to.b),
+            a: lerp(from.a, to.a),
+        }
+        .into_var())
+    })
+}
+
+/// Initialize `pxs_color` module.
+pub(crate) fn init() {
+    let mut cstrgen = CStringSafe::new();
+
+    let pxs_color = pxs_newmod(cstrgen.new_string("pxs_color"));
+    pxs_addfunc(pxs_color, cstrgen.new_string("hex_to_rgba"), pxs_color_hex_to_rgba);
+    pxs_addfunc(pxs_color, cstrgen.new_string("rgba_to_hex"), pxs_color_rgba_to_hex);
+    pxs_addfunc(pxs_color, cstrgen.new_string("rgb_to_hsv"), pxs_color_rgb_to_hsv);
+    pxs_addfunc(pxs_color, cstrgen.new_string("hsv_to_rgb"), pxs_color_hsv_to_rgb);
+    pxs_addfunc(pxs_color, cstrgen.new_string("lerp"), pxs_color_lerp);
+    pxs_addmod(pxs_color);
+}
@@ -0,0 +1,343 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+use etffi::cstring::CStringSafe;
+
+use crate::{
+    borrow_var, own_var, pxs_addfunc, pxs_addmod, pxs_error, pxs_listget, pxs_listlen, pxs_newmod,
+    pxs_core::get_field, pxs_tostring,
+    shared::{
+        PxsRes,
+        var::{pxs_Var, pxs_VarT, pxs_VarType},
+    },
+};
+use etffi::ptr_magic::PtrMagic;
+
+/// Options for parsing/stringifying.
+struct CsvOptions {
+    /// Field delimiter. Default `,`. Use `\t` for TSV.
+    delimiter: char,
+    /// First row is a header. Default `true`.
+    header: bool,
+    /// Infer Int/Float/Bool/Null types. Default `true`.
+    infer: bool,
+    /// Column order for stringify.
+    columns: Option<Vec<String>>,
+}
+
+impl CsvOptions {
+    /// Read options from a `pxs_Map`/object. `pxs_Null` is the default options.
+    fn from_var(rt: pxs_VarT, var: Option<&pxs_Var>) -> PxsRes<Self> {
+        let mut options = CsvOptions {
+            delimiter: ',',
+            header: true,
+            infer: true,
+            columns: None,
+        };
+        let var = match var {
+            Some(var) if !var.is_null() => var,
+            _ => return Ok(options),
+        };
+
+        if let Some(delimiter) = get_field(rt, var, "delimiter").filter(|v| !v.is_null()) {
+            let delimiter = delimiter.get_string()?;
+            let mut chars = delimiter.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) if c != '"' && c != '\n' && c != '\r' => options.delimiter = c,
+                _ => return pxs_error!("Delimiter must be a single character, got: '{delimiter}'."),
+            }
+        }
+        if let Some(header) = get_field(rt, var, "header").filter(|v| !v.is_null()) {
+            options.header = header.get_bool()?;
+        }
+        if let Some(infer) = get_field(rt, var, "infer").filter(|v| !v.is_null()) {
+            options.infer = infer.get_bool()?;
+        }
+        if let Some(columns) = get_field(rt, var, "columns").filter(|v| !v.is_null()) {
+            let list = match columns.get_list() {
+                Some(list) => list,
+                None => return pxs_error!("columns must be a List, got: {:#?}.", columns.tag),
+            };
+            let mut names = vec![];
+            for column in list.vars.iter() {
+                names.push(column.get_string()?);
+            }
+            options.columns = Some(names);
+        }
+
+        Ok(options)
+    }
+}
+
+/// A field of a row.
+#[derive(Default)]
+struct Field {
+    text: String,
+    /// Quoted fields are always strings.
+    quoted: bool,
+}
+
+/// Split delimited text into rows of fields. Supports quoted fields with `""` escapes and newlines.
+fn split_rows(text: &str, delimiter: char) -> PxsRes<Vec<Vec<Field>>> {
+    let mut rows = vec![];
+    let mut row = vec![];
+    let mut field = Field::default();
+    let mut in_quotes = false;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            if c == '"' {
+                if chars.peek() == Some(&'"') {
+                    chars.next();
+                    field.text.push('"');
+                } else {
+                    in_quotes = false;
+                }
+            } else {
+                field.text.push(c);
+            }
+            continue;
+        }
+
+        if c == '"' && field.text.is_empty() && !field.quoted {
+            in_quotes = true;
+            field.quoted = true;
+        } else if c == delimiter {
+            row.push(std::mem::take(&mut field));
+        } else if c == '\n' || c == '\r' {
+            if c == '\r' && chars.peek() == Some(&'\n') {
+                chars.next();
+            }
+            row.push(std::mem::take(&mut field));
+            rows.push(std::mem::take(&mut row));
+        } else {
+            field.text.push(c);
+        }
+    }
+
+    if in_quotes {
+        return pxs_error!("Unterminated quoted field.");
+    }
+    if !field.text.is_empty() || field.quoted || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+
+    // Skip blank lines
+    rows.retain(|r| !(r.len() == 1 && r[0].text.is_empty() && !r[0].quoted));
+    Ok(rows)
+}
+
+/// Convert a field into a `pxs_Var`. Quoted fields are not inferred, `"42"` stays a string.
+fn infer_field(field: &Field, infer: bool) -> pxs_Var {
+    let Field { text: field, quoted } = field;
+    if !infer || *quoted {
+        return pxs_Var::new_string(field.to_string());
+    }
+    if field.is_empty() {
+        return pxs_Var::new_null();
+    }
+    if let Ok(v) = field.parse::<i64>() {
+        return pxs_Var::new_i64(v);
+    }
+    if let Ok(v) = field.parse::<f64>() {
+        // Do not turn `inf`/`NaN` names into numbers.
+        if v.is_finite() {
+            return pxs_Var::new_f64(v);
+        }
+    }
+    match field.as_str() {
+        "true" | "True" | "TRUE" => pxs_Var::new_bool(true),
+        "false" | "False" | "FALSE" => pxs_Var::new_bool(false),
+        _ => pxs_Var::new_string(field.to_string()),
+    }
+}
+
+/// Parse delimited text into a `pxs_List` of `pxs_Map`s (header) or `pxs_List`s (no header).
+fn parse(text: &str, options: &CsvOptions) -> PxsRes<pxs_Var> {
+    let mut rows = split_rows(text, options.delimiter)?.into_iter();
+    let mut res = vec![];
+
+    if !options.header {
+        for row in rows {
+            let fields = row.iter().map(|f| infer_field(f, options.infer)).collect();
+            res.push(pxs_Var::new_list_with(fields));
+        }
+        return Ok(pxs_Var::new_list_with(res));
+    }
+
+    let header = match rows.next() {
+        Some(header) => header,
+        None => return Ok(pxs_Var::new_list()),
+    };
+    for row in rows {
+        let map = pxs_Var::new_map();
+        let internal = map.get_map().unwrap();
+        for (i, name) in header.iter().enumerate() {
+            let value = match row.get(i) {
+                Some(field) => infer_field(field, options.infer),
+                None => pxs_Var::new_null(),
+            };
+            internal.add_item(pxs_Var::new_string(name.text.clone()), value);
+        }
+        res.push(map);
+    }
+
+    Ok(pxs_Var::new_list_with(res))
+}
+
+/// Quote a field if needed.
+fn write_field(out: &mut String, field: &str, delimiter: char) {
+    if field.contains(delimiter) || field.contains('"') || field.contains('\n') || field.contains('\r') {
+        out.push('"');
+        out.push_str(&field.replace('"', "\"\""));
+        out.push('"');
+    } else {
+        out.push_str(field);
+    }
+}
+
+/// Convert a `pxs_Var` into a field string.
+fn field_to_string(rt: pxs_VarT, var: &pxs_Var) -> PxsRes<String> {
+    if var.is_null() {
+        return Ok(String::new());
+    }
+    let string = own_var!(pxs_tostring(rt, var as *const pxs_Var as pxs_VarT));
+    if string.is_exception() {
+        return pxs_error!("{}", string.get_string()?);
+    }
+    string.get_string()
+}
+
+/// Write a list of rows back into delimited text.
+///
+/// Rows can be `pxs_List`s or maps/objects. Maps/objects need `columns`, unless they are `pxs_Map`s in which case
+/// the first row's keys are used (sorted).
+fn stringify(rt: pxs_VarT, rows: &pxs_Var, options: &CsvOptions) -> PxsRes<String> {
+    let rows = match rows.get_list() {
+        Some(list) => &list.vars,
+        None => return pxs_error!("rows must be a List, got: {:#?}.", rows.tag),
+    };
+    let mut out = String::new();
+    let delimiter = options.delimiter;
+
+    let keyed = rows.first().map(|r| !r.is_list()).unwrap_or(false);
+    let columns = if keyed {
+        match &options.columns {
+            Some(columns) => columns.clone(),
+            None => {
+                let first = &rows[0];
+                if !first.is_map() {
+                    return pxs_error!("columns option is required when rows are objects.");
+                }
+                let mut keys = vec![];
+                for key in first.get_map().unwrap().keys() {
+                    keys.push(key.get_string()?);
+                }
+                keys.sort();
+                keys
+            }
+        }
+    } else {
+        vec![]
+    };
+
+    if keyed && options.header {
+        for (i, column) in columns.iter().enumerate() {
+            if i > 0 {
+                out.push(delimiter);
+            }
+            write_field(&mut out, column, delimiter);
+        }
+        out.push('\n');
+    }
+
+    for row in rows.iter() {
+        if let Some(list) = row.get_list() {
+            for (i, field) in list.vars.iter().enumerate() {
+                if i > 0 {
+                    out.push(delimiter);
+                }
+                write_field(&mut out, &field_to_string(rt, field)?, delimiter);
+            }
+        } else {
+            for (i, column) in columns.iter().enumerate() {
+                if i > 0 {
+                    out.push(delimiter);
+                }
+                let field = get_field(rt, row, column).unwrap_or_else(pxs_Var::new_null);
+                write_field(&mut out, &field_to_string(rt, &field)?, delimiter);
+            }
+        }
+        out.push('\n');
+    }
+
+    Ok(out)
+}
+
+/// Parse CSV/TSV text.
+///
+/// args: (rt, text, options?)
+extern "C" fn pxs_csv_parse(args: pxs_VarT) -> pxs_VarT {
+    let len = pxs_listlen(args);
+    if len != 2 && len != 3 {
+        return pxs_Var::expected_n_args_ep(2, len as u32).into_raw();
+    }
+
+    let rt = pxs_listget(args, 0);
+    let text = borrow_var!(pxs_listget(args, 1));
+    if !text.is_string() {
+        return pxs_Var::incorrect_type_ep(pxs_VarType::pxs_String, text.tag).into_raw();
+    }
+    let options = if len == 3 {
+        Some(borrow_var!(pxs_listget(args, 2)) as &pxs_Var)
+    } else {
+        None
+    };
+
+    let res = CsvOptions::from_var(rt, options).and_then(|options| parse(&text.get_string().unwrap(), &options));
+    match res {
+        Ok(res) => res.into_raw(),
+        Err(e) => pxs_Var::new_exception(e).into_raw(),
+    }
+}
+
+/// Stringify rows into CSV/TSV text.
+///
+/// args: (rt, rows, options?)
+extern "C" fn pxs_csv_stringify(args: pxs_VarT) -> pxs_VarT {
+    let len = pxs_listlen(args);
+    if len != 2 && len != 3 {
+        return pxs_Var::expected_n_args_ep(2, len as u32).into_raw();
+    }
+
+    let rt = pxs_listget(args, 0);
+    let rows = borrow_var!(pxs_listget(args, 1));
+    let options = if len == 3 {
+        Some(borrow_var!(pxs_listget(args, 2)) as &pxs_Var)
+    } else {
+        None
+    };
+
+    let res = CsvOptions::from_var(rt, options).and_then(|options| stringify(rt, rows, &options));
+    match res {
+        Ok(res) => pxs_Var::new_string(res).into_raw(),
+        Err(e) => pxs_Var::new_exception(e).into_raw(),
+    }
+}
+
+/// Initialize `pxs_csv` module.
+pub(crate) fn init() {
+    let mut cstrgen = CStringSafe::new();
+
+    let pxs_csv = pxs_newmod(cstrgen.new_string("pxs_csv"));
+    pxs_addfunc(pxs_csv, cstrgen.new_string("parse"), pxs_csv_parse);
+    pxs_addfunc(pxs_csv, cstrgen.new_string("stringify"), pxs_csv_stringify);
+    pxs_addmod(pxs_csv);
+}
@@ -0,0 +1,133 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+use std::cell::RefCell;
+
+use etffi::cstring::CStringSafe;
+
+use crate::{
+    borrow_var, own_var, pxs_addfunc, pxs_addmod, pxs_debug, pxs_error, pxs_listget, pxs_newmod, pxs_varcall,
+    pxs_core::core_call,
+    shared::{
+        func::pxs_Func,
+        var::{pxs_Var, pxs_VarT},
+    },
+};
+use etffi::ptr_magic::PtrMagic;
+
+/// A handler registered with `on`.
+struct Handler {
+    id: i64,
+    event: String,
+    /// Runtime the handler was registered from.
+    rt: pxs_Var,
+    /// Owned reference to the handler function.
+    func: pxs_Var,
+}
+
+thread_local! {
+    /// Handlers in registration order.
+    static HANDLERS: RefCell<Vec<Handler>> = const { RefCell::new(vec![]) };
+    static NEXT_ID: RefCell<i64> = const { RefCell::new(1) };
+}
+
+/// Call every handler of `event` with `payload`, in registration order. Returns the number of handlers called.
+///
+/// Handlers may add or remove handlers, ones added while emitting are called on the next emit.
+pub(crate) fn emit(event: &str, payload: &pxs_Var) -> i32 {
+    let ids: Vec<i64> = HANDLERS.with(|handlers| {
+        handlers.borrow().iter().filter(|h| h.event == event).map(|h| h.id).collect()
+    });
+
+    let mut called = 0;
+    for id in ids {
+        // Skip handlers removed by a earlier handler.
+        let handler = HANDLERS.with(|handlers| {
+            handlers.borrow().iter().find(|h| h.id == id).map(|h| (h.rt.shallow_copy(), h.func.shallow_copy()))
+        });
+        let Some((rt, func)) = handler else {
+            continue;
+        };
+        let args = pxs_Var::new_list_with(vec![payload.shallow_copy()]);
+        let res = own_var!(pxs_varcall(
+            &rt as *const pxs_Var as pxs_VarT,
+            &func as *const pxs_Var as pxs_VarT,
+            args.into_raw(),
+        ));
+        if res.is_exception() {
+            pxs_debug!("Handler of '{event}' failed: {}", res.get_string().unwrap_or_default());
+        }
+        called += 1;
+    }
+    called
+}
+
+/// Drop every handler on this thread.
+///
+/// Handlers hold references to script functions, so this must be called before the runtime stops.
+pub(crate) fn clear() {
+    HANDLERS.with(|handlers| handlers.borrow_mut().clear());
+}
+
+/// `on(event, fn)` calls `fn(payload)` for every emit of `event`. Returns a id for `off`.
+extern "C" fn pxs_events_on(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 3, 3, |rt| {
+        let event = borrow_var!(pxs_listget(args, 1)).get_string()?;
+        let func = borrow_var!(pxs_listget(args, 2));
+        if !func.is_function() {
+            return pxs_error!("Event handler must be a Function, got: {:#?}.", func.tag);
+        }
+
+        let id = NEXT_ID.with(|next| {
+            let mut next = next.borrow_mut();
+            *next += 1;
+            *next - 1
+        });
+        // Cloning a function moves the deleter, so the handler keeps it alive.
+        let handler = Handler { id, event, rt: borrow_var!(rt).clone(), func: func.clone() };
+        HANDLERS.with(|handlers| handlers.borrow_mut().push(handler));
+        Ok(pxs_Var::new_i64(id))
+    })
+}
+
+/// `off(id)` removes a handler. Returns false if there is none.
+extern "C" fn pxs_events_off(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 2, 2, |_| {
+        let id = borrow_var!(pxs_listget(args, 1)).get_i64()?;
+        let removed = HANDLERS.with(|handlers| {
+            let mut handlers = handlers.borrow_mut();
+            let idx = handlers.iter().position(|h| h.id == id)?;
+            Some(handlers.remove(idx))
+        });
+        Ok(pxs_Var::new_bool(removed.is_some()))
+    })
+}
+
+/// `emit(event, payload?)` calls the handlers of `event` in every runtime. Returns the number of handlers called.
+extern "C" fn pxs_events_emit(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 2, 3, |_| {
+        let event = borrow_var!(pxs_listget(args, 1)).get_string()?;
+        let payload = match pxs_listget(args, 2) {
+            payload if payload.is_null() => pxs_Var::new_null(),
+            payload => borrow_var!(payload).shallow_copy(),
+        };
+        Ok(pxs_Var::new_i64(emit(&event, &payload) as i64))
+    })
+}
+
+/// Initialize `pxs_events` module.
+pub(crate) fn init() {
+    let mut cstrgen = CStringSafe::new();
+
+    let pxs_events = pxs_newmod(cstrgen.new_string("pxs_events"));
+    let funcs: [(&str, pxs_Func); 3] = [("on", pxs_events_on), ("off", pxs_events_off), ("emit", pxs_events_emit)];
+    for (name, func) in funcs {
+        pxs_addfunc(pxs_events, cstrgen.new_string(name), func);
+    }
+    pxs_addmod(pxs_events);
+}
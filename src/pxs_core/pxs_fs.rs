@@ -0,0 +1,124 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+//! Filesystem access for scripts.
+//!
+//! Everything goes through the host callbacks (`pxs_set_filereader`, `pxs_set_filewriter` and `pxs_set_dirreader`),
+//! so the host decides what scripts can see and touch. Hosts return null for paths that do not exist.
+use std::ffi::CString;
+
+use etffi::cstring::CStringSafe;
+
+use crate::{
+    borrow_var, own_var, pxs_addfunc, pxs_addmod, pxs_error, pxs_listget, pxs_newmod,
+    pxs_core::core_call,
+    shared::{
+        PxsRes, get_file_readers, get_write_file,
+        func::pxs_Func,
+        var::{pxs_Var, pxs_VarT},
+    },
+};
+use etffi::ptr_magic::PtrMagic;
+
+/// String argument at `idx` as a C string.
+fn c_arg(args: pxs_VarT, idx: i32) -> PxsRes<CString> {
+    let val = borrow_var!(pxs_listget(args, idx)).get_string()?;
+    CString::new(val).map_err(|_| "Paths and contents can not contain a NUL byte.".to_string())
+}
+
+/// Read a file through the host. None if it does not exist.
+fn read(path: &CString) -> PxsRes<Option<pxs_Var>> {
+    let Some(load_file) = get_file_readers().0 else {
+        return pxs_error!("No file reader set. Call `pxs_set_filereader` from the host.");
+    };
+    let res = unsafe { load_file(path.as_ptr()) };
+    if res.is_null() {
+        return Ok(None);
+    }
+    Ok(Some(own_var!(res)))
+}
+
+/// List a directory through the host. None if it does not exist.
+fn list_dir(path: &CString) -> PxsRes<Option<pxs_Var>> {
+    let Some(read_dir) = get_file_readers().1 else {
+        return pxs_error!("No directory reader set. Call `pxs_set_dirreader` from the host.");
+    };
+    let res = unsafe { read_dir(path.as_ptr()) };
+    if res.is_null() {
+        return Ok(None);
+    }
+    Ok(Some(own_var!(res)))
+}
+
+/// `read(path)` the contents of a file as a string.
+extern "C" fn pxs_fs_read(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 2, 2, |_| {
+        let path = c_arg(args, 1)?;
+        match read(&path)? {
+            Some(contents) if contents.is_string() => Ok(contents),
+            _ => pxs_error!("Could not read file: {}.", path.to_string_lossy()),
+        }
+    })
+}
+
+/// `write(path, contents)` write a string to a file, replacing it.
+extern "C" fn pxs_fs_write(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 3, 3, |_| {
+        let path = c_arg(args, 1)?;
+        let contents = c_arg(args, 2)?;
+        let Some(write_file) = get_write_file() else {
+            return pxs_error!("No file writer set. Call `pxs_set_filewriter` from the host.");
+        };
+        if !unsafe { write_file(path.as_ptr(), contents.as_ptr()) } {
+            return pxs_error!("Could not write file: {}.", path.to_string_lossy());
+        }
+        Ok(pxs_Var::new_null())
+    })
+}
+
+/// `list_dir(path)` the entries of a directory as a list of strings.
+extern "C" fn pxs_fs_list_dir(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 2, 2, |_| {
+        let path = c_arg(args, 1)?;
+        match list_dir(&path)? {
+            Some(entries) if entries.is_list() => Ok(entries),
+            _ => pxs_error!("Could not read directory: {}.", path.to_string_lossy()),
+        }
+    })
+}
+
+/// `exists(path)` true if the host can read `path` as a file or a directory.
+extern "C" fn pxs_fs_exists(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 2, 2, |_| {
+        let path = c_arg(args, 1)?;
+        let (load_file, read_dir) = get_file_readers();
+        if load_file.is_none() && read_dir.is_none() {
+            return pxs_error!("No file or directory reader set. Call `pxs_set_filereader` from the host.");
+        }
+        let file = load_file.is_some() && read(&path)?.is_some_and(|contents| contents.is_string());
+        let dir = !file && read_dir.is_some() && list_dir(&path)?.is_some_and(|entries| entries.is_list());
+        Ok(pxs_Var::new_bool(file || dir))
+    })
+}
+
+/// Initialize `pxs_fs` module.
+pub(crate) fn init() {
+    let mut cstrgen = CStringSafe::new();
+
+    let pxs_fs = pxs_newmod(cstrgen.new_string("pxs_fs"));
+    let funcs: [(&str, pxs_Func); 4] = [
+        ("read", pxs_fs_read),
+        ("write", pxs_fs_write),
+        ("list_dir", pxs_fs_list_dir),
+        ("exists", pxs_fs_exists),
+    ];
+    for (name, func) in funcs {
+        pxs_addfunc(pxs_fs, cstrgen.new_string(name), func);
+    }
+    pxs_addmod(pxs_fs);
+}
@@ -0,0 +1,340 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+use etffi::cstring::CStringSafe;
+
+use crate::{
+    borrow_var, pxs_addfunc, pxs_addmod, pxs_error, pxs_listget, pxs_newmod,
+    pxs_core::{core_call, get_field, get_number},
+    shared::{
+        PxsRes,
+        func::pxs_Func,
+        var::{pxs_Var, pxs_VarT},
+    },
+};
+use etffi::ptr_magic::PtrMagic;
+
+/// Read a number field from a map/object.
+fn number_field(rt: pxs_VarT, var: &pxs_Var, key: &str) -> PxsRes<f64> {
+    match get_field(rt, var, key).filter(|v| !v.is_null()) {
+        Some(v) => get_number(&v),
+        None => pxs_error!("Missing field '{key}'."),
+    }
+}
+
+/// A 2D vector. Scripts pass these as `{x, y}` or `[x, y]`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Vec2 {
+    pub x: f64,
+    pub y: f64,
+}
+
+impl Vec2 {
+    pub(crate) fn new(x: f64, y: f64) -> Self {
+        Vec2 { x, y }
+    }
+
+    pub(crate) fn from_var(rt: pxs_VarT, var: &pxs_Var) -> PxsRes<Self> {
+        if let Some(list) = var.get_list() {
+            return match (list.get_item(0), list.get_item(1)) {
+                (Some(x), Some(y)) => Ok(Vec2::new(get_number(x)?, get_number(y)?)),
+                _ => pxs_error!("Vec2 list needs 2 items."),
+            };
+        }
+        Ok(Vec2::new(number_field(rt, var, "x")?, number_field(rt, var, "y")?))
+    }
+
+    /// Convert into a `{x, y}` map.
+    pub(crate) fn into_var(self) -> pxs_Var {
+        let res = pxs_Var::new_map();
+        let map = res.get_map().unwrap();
+        map.add_item(pxs_Var::new_string("x".to_string()), pxs_Var::new_f64(self.x));
+        map.add_item(pxs_Var::new_string("y".to_string()), pxs_Var::new_f64(self.y));
+        res
+    }
+
+    fn sub(self, other: Vec2) -> Vec2 {
+        Vec2::new(self.x - other.x, self.y - other.y)
+    }
+
+    fn dot(self, other: Vec2) -> f64 {
+        self.x * other.x + self.y * other.y
+    }
+
+    fn length(self) -> f64 {
+        self.dot(self).sqrt()
+    }
+}
+
+/// A axis aligned rectangle. Scripts pass these as `{x, y, w, h}`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Rect {
+    x: f64,
+    y: f64,
+    w: f64,
+    h: f64,
+}
+
+impl Rect {
+    fn from_var(rt: pxs_VarT, var: &pxs_Var) -> PxsRes<Self> {
+        let rect = Rect {
+            x: number_field(rt, var, "x")?,
+            y: number_field(rt, var, "y")?,
+            w: number_field(rt, var, "w")?,
+            h: number_field(rt, var, "h")?,
+        };
+        if rect.w < 0.0 || rect.h < 0.0 {
+            return pxs_error!("Rect width and height can not be negative.");
+        }
+        Ok(rect)
+    }
+
+    fn into_var(self) -> pxs_Var {
+        let res = pxs_Var::new_map();
+        let map = res.get_map().unwrap();
+        for (key, value) in [("x", self.x), ("y", self.y), ("w", self.w), ("h", self.h)] {
+            map.add_item(pxs_Var::new_string(key.to_string()), pxs_Var::new_f64(value));
+        }
+        res
+    }
+
+    fn right(&self) -> f64 {
+        self.x + self.w
+    }
+
+    fn bottom(&self) -> f64 {
+        self.y + self.h
+    }
+
+    fn contains(&self, p: Vec2) -> bool {
+        p.x >= self.x && p.x <= self.right() && p.y >= self.y && p.y <= self.bottom()
+    }
+
+    fn intersects(&self, other: &Rect) -> bool {
+        self.x < other.right() && other.x < self.right() && self.y < other.bottom() && other.y < self.bottom()
+    }
+
+    fn intersection(&self, other: &Rect) -> Option<Rect> {
+        if !self.intersects(other) {
+            return None;
+        }
+        let x = self.x.max(other.x);
+        let y = self.y.max(other.y);
+        Some(Rect {
+            x,
+            y,
+            w: self.right().min(other.right()) - x,
+            h: self.bottom().min(other.bottom()) - y,
+        })
+    }
+
+    /// Closest point in the rect to `p`.
+    fn closest(&self, p: Vec2) -> Vec2 {
+        Vec2::new(p.x.clamp(self.x, self.right()), p.y.clamp(self.y, self.bottom()))
+    }
+}
+
+/// A circle. Scripts pass these as `{x, y, r}`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Circle {
+    center: Vec2,
+    r: f64,
+}
+
+impl Circle {
+    fn from_var(rt: pxs_VarT, var: &pxs_Var) -> PxsRes<Self> {
+        let r = number_field(rt, var, "r")?;
+        if r < 0.0 {
+            return pxs_error!("Circle radius can not be negative.");
+        }
+        Ok(Circle {
+            center: Vec2::new(number_field(rt, var, "x")?, number_field(rt, var, "y")?),
+            r,
+        })
+    }
+
+    fn contains(&self, p: Vec2) -> bool {
+        p.sub(self.center).length() <= self.r
+    }
+
+    fn intersects(&self, other: &Circle) -> bool {
+        other.center.sub(self.center).length() <= self.r + other.r
+    }
+
+    fn intersects_rect(&self, rect: &Rect) -> bool {
+        self.contains(rect.closest(self.center))
+    }
+}
+
+/// A ray hit.
+struct Hit {
+    point: Vec2,
+    distance: f64,
+}
+
+impl Hit {
+    fn into_var(self) -> pxs_Var {
+        let res = self.point.into_var();
+        let map = res.get_map().unwrap();
+        map.add_item(pxs_Var::new_string("distance".to_string()), pxs_Var::new_f64(self.distance));
+        res
+    }
+}
+
+/// Normalize a ray direction.
+fn ray_dir(dir: Vec2) -> PxsRes<Vec2> {
+    let len = dir.length();
+    if len == 0.0 {
+        return pxs_error!("Ray direction can not be zero.");
+    }
+    Ok(Vec2::new(dir.x / len, dir.y / len))
+}
+
+/// Slab test against a rect.
+fn raycast_rect(origin: Vec2, dir: Vec2, rect: &Rect) -> Option<Hit> {
+    let mut t_min = 0.0_f64;
+    let mut t_max = f64::INFINITY;
+
+    for (o, d, min, max) in [
+        (origin.x, dir.x, rect.x, rect.right()),
+        (origin.y, dir.y, rect.y, rect.bottom()),
+    ] {
+        if d == 0.0 {
+            if o < min || o > max {
+                return None;
+            }
+        } else {
+            let t1 = (min - o) / d;
+            let t2 = (max - o) / d;
+            t_min = t_min.max(t1.min(t2));
+            t_max = t_max.min(t1.max(t2));
+            if t_min > t_max {
+                return None;
+            }
+        }
+    }
+
+    Some(Hit {
+        point: Vec2::new(origin.x + dir.x * t_min, origin.y + dir.y * t_min),
+        distance: t_min,
+    })
+}
+
+fn raycast_circle(origin: Vec2, dir: Vec2, circle: &Circle) -> Option<Hit> {
+    if circle.contains(origin) {
+        return Some(Hit {
+            point: origin,
+            distance: 0.0,
+        });
+    }
+    let to_center = circle.center.sub(origin);
+    let proj = to_center.dot(dir);
+    if proj < 0.0 {
+        return None;
+    }
+    let dist_sq = to_center.dot(to_center) - proj * proj;
+    let r_sq = circle.r * circle.r;
+    if dist_sq > r_sq {
+        return None;
+    }
+    let t = proj - (r_sq - dist_sq).sqrt();
+    Some(Hit {
+        point: Vec2::new(origin.x + dir.x * t, origin.y + dir.y * t),
+        distance: t,
+    })
+}
+
+/// Borrow argument `i`.
+fn arg<'a>(args: pxs_VarT, i: i32) -> &'a pxs_Var {
+    borrow_var!(pxs_listget(args, i))
+}
+
+extern "C" fn pxs_geom_rect_contains(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 3, 3, |rt| {
+        let rect = Rect::from_var(rt, arg(args, 1))?;
+        Ok(pxs_Var::new_bool(rect.contains(Vec2::from_var(rt, arg(args, 2))?)))
+    })
+}
+
+extern "C" fn pxs_geom_circle_contains(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 3, 3, |rt| {
+        let circle = Circle::from_var(rt, arg(args, 1))?;
+        Ok(pxs_Var::new_bool(circle.contains(Vec2::from_var(rt, arg(args, 2))?)))
+    })
+}
+
+extern "C" fn pxs_geom_rect_intersects(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 3, 3, |rt| {
+        let a = Rect::from_var(rt, arg(args, 1))?;
+        let b = Rect::from_var(rt, arg(args, 2))?;
+        Ok(pxs_Var::new_bool(a.intersects(&b)))
+    })
+}
+
+extern "C" fn pxs_geom_rect_intersection(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 3, 3, |rt| {
+        let a = Rect::from_var(rt, arg(args, 1))?;
+        let b = Rect::from_var(rt, arg(args, 2))?;
+        Ok(a.intersection(&b).map(Rect::into_var).unwrap_or_else(pxs_Var::new_null))
+    })
+}
+
+extern "C" fn pxs_geom_circle_intersects(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 3, 3, |rt| {
+        let a = Circle::from_var(rt, arg(args, 1))?;
+        let b = Circle::from_var(rt, arg(args, 2))?;
+        Ok(pxs_Var::new_bool(a.intersects(&b)))
+    })
+}
+
+extern "C" fn pxs_geom_circle_rect_intersects(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 3, 3, |rt| {
+        let circle = Circle::from_var(rt, arg(args, 1))?;
+        let rect = Rect::from_var(rt, arg(args, 2))?;
+        Ok(pxs_Var::new_bool(circle.intersects_rect(&rect)))
+    })
+}
+
+extern "C" fn pxs_geom_raycast_rect(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 4, 4, |rt| {
+        let origin = Vec2::from_var(rt, arg(args, 1))?;
+        let dir = ray_dir(Vec2::from_var(rt, arg(args, 2))?)?;
+        let rect = Rect::from_var(rt, arg(args, 3))?;
+        Ok(raycast_rect(origin, dir, &rect).map(Hit::into_var).unwrap_or_else(pxs_Var::new_null))
+    })
+}
+
+extern "C" fn pxs_geom_raycast_circle(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 4, 4, |rt| {
+        let origin = Vec2::from_var(rt, arg(args, 1))?;
+        let dir = ray_dir(Vec2::from_var(rt, arg(args, 2))?)?;
+        let circle = Circle::from_var(rt, arg(args, 3))?;
+        Ok(raycast_circle(origin, dir, &circle).map(Hit::into_var).unwrap_or_else(pxs_Var::new_null))
+    })
+}
+
+/// Initialize `pxs_geom` module.
+pub(crate) fn init() {
+    let mut cstrgen = CStringSafe::new();
+
+    let pxs_geom = pxs_newmod(cstrgen.new_string("pxs_geom"));
+    let funcs: [(&str, pxs_Func); 8] = [
+        ("rect_contains", pxs_geom_rect_contains),
+        ("circle_contains", pxs_geom_circle_contains),
+        ("rect_intersects", pxs_geom_rect_intersects),
+        ("rect_intersection", pxs_geom_rect_intersection),
+        ("circle_intersects", pxs_geom_circle_intersects),
+        ("circle_rect_intersects", pxs_geom_circle_rect_intersects),
+        ("raycast_rect", pxs_geom_raycast_rect),
+        ("raycast_circle", pxs_geom_raycast_circle),
+    ];
+    for (name, func) in funcs {
+        pxs_addfunc(pxs_geom, cstrgen.new_string(name), func);
+    }
+    pxs_addmod(pxs_geom);
+}
@@ -0,0 +1,136 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+//! HTTP client for scripts.
+//!
+//! pixelscript does no networking itself, every request goes to the handler set with `pxs_set_http_handler` and the
+//! script waits for its response. Responses are maps of `status`, `ok`, `headers` and `body`.
+use std::ffi::CString;
+
+use etffi::cstring::CStringSafe;
+
+use crate::{
+    borrow_var, own_var, pxs_addfunc, pxs_addmod, pxs_error, pxs_listget, pxs_listlen, pxs_newmod,
+    pxs_core::{core_call, get_entries, get_field, get_number, to_text},
+    shared::{
+        PxsRes, get_http_handler, pxs_HttpRequest,
+        func::pxs_Func,
+        var::{pxs_Var, pxs_VarT},
+    },
+};
+use etffi::ptr_magic::PtrMagic;
+
+/// Argument at `idx`, None if missing or null.
+fn opt_arg(args: pxs_VarT, idx: i32) -> Option<&'static pxs_Var> {
+    if pxs_listlen(args) <= idx {
+        return None;
+    }
+    let var = borrow_var!(pxs_listget(args, idx));
+    (!var.is_null()).then_some(var)
+}
+
+/// `pxs_Map` of the header names and values of a map or script object.
+fn headers_map(rt: pxs_VarT, var: &pxs_Var) -> PxsRes<pxs_Var> {
+    let Some(entries) = get_entries(rt, var, |key| key.get_string())? else {
+        return pxs_error!("HTTP headers must be a map, got: {:#?}.", var.tag);
+    };
+    let res = pxs_Var::new_map();
+    let map = res.get_map().unwrap();
+    for (name, value) in entries {
+        map.add_item(pxs_Var::new_string(name), pxs_Var::new_string(to_text(rt, &value)?));
+    }
+    Ok(res)
+}
+
+/// Send a request through the host handler. `options` may have `headers` and `timeout` (seconds).
+fn request(rt: pxs_VarT, method: &str, url: String, body: Option<&pxs_Var>, options: Option<&pxs_Var>) -> PxsRes<pxs_Var> {
+    let Some(handler) = get_http_handler() else {
+        return pxs_error!("No HTTP handler is set, the host must call `pxs_set_http_handler`.");
+    };
+    if let Some(body) = body
+        && !body.is_string()
+        && !body.is_bytes()
+    {
+        return pxs_error!("HTTP body must be a String or Bytes, got: {:#?}.", body.tag);
+    }
+
+    let mut headers = pxs_Var::new_map();
+    let mut timeout = 0.0;
+    if let Some(options) = options {
+        if let Some(value) = get_field(rt, options, "headers")
+            && !value.is_null()
+        {
+            headers = headers_map(rt, &value)?;
+        }
+        if let Some(value) = get_field(rt, options, "timeout")
+            && !value.is_null()
+        {
+            timeout = get_number(&value)?;
+            if timeout.is_nan() || timeout < 0.0 {
+                return pxs_error!("HTTP timeout must be 0 or more seconds, got: {timeout}.");
+            }
+        }
+    }
+
+    let (c_method, c_url) = (CString::new(method).unwrap_or_default(), CString::new(url.clone()).unwrap_or_default());
+    let request = pxs_HttpRequest {
+        method: c_method.as_ptr(),
+        url: c_url.as_ptr(),
+        headers: &headers as *const pxs_Var as pxs_VarT,
+        body: body.map_or(std::ptr::null_mut(), |body| body as *const pxs_Var as pxs_VarT),
+        timeout,
+    };
+    let response = unsafe { handler(&request) };
+    // Own every var first so none leaks on a error.
+    let take = |var: pxs_VarT| (!var.is_null()).then(|| own_var!(var));
+    let (headers, body, error) = (take(response.headers), take(response.body), take(response.error));
+    if let Some(error) = error {
+        return pxs_error!("HTTP {method} {url} failed: {}", error.get_string().unwrap_or_default());
+    }
+
+    let res = pxs_Var::new_map();
+    let map = res.get_map().unwrap();
+    let fields = [
+        ("status", pxs_Var::new_i64(response.status as i64)),
+        ("ok", pxs_Var::new_bool((200..300).contains(&response.status))),
+        ("headers", headers.unwrap_or_else(pxs_Var::new_map)),
+        ("body", body.unwrap_or_else(|| pxs_Var::new_string(String::new()))),
+    ];
+    for (key, value) in fields {
+        map.add_item(pxs_Var::new_string(key.to_string()), value);
+    }
+    Ok(res)
+}
+
+/// `get(url, options?)`
+extern "C" fn pxs_http_get(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 2, 3, |rt| {
+        let url = borrow_var!(pxs_listget(args, 1)).get_string()?;
+        request(rt, "GET", url, None, opt_arg(args, 2))
+    })
+}
+
+/// `post(url, body?, options?)`
+extern "C" fn pxs_http_post(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 2, 4, |rt| {
+        let url = borrow_var!(pxs_listget(args, 1)).get_string()?;
+        request(rt, "POST", url, opt_arg(args, 2), opt_arg(args, 3))
+    })
+}
+
+/// Initialize `pxs_http` module.
+pub(crate) fn init() {
+    let mut cstrgen = CStringSafe::new();
+
+    let pxs_http = pxs_newmod(cstrgen.new_string("pxs_http"));
+    let funcs: [(&str, pxs_Func); 2] = [("get", pxs_http_get), ("post", pxs_http_post)];
+    for (name, func) in funcs {
+        pxs_addfunc(pxs_http, cstrgen.new_string(name), func);
+    }
+    pxs_addmod(pxs_http);
+}
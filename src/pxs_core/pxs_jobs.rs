@@ -0,0 +1,249 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+use std::{cell::RefCell, collections::HashMap};
+
+use etffi::cstring::CStringSafe;
+
+use crate::{
+    borrow_var, own_var, pxs_addfunc, pxs_addmod, pxs_debug, pxs_error, pxs_gethost_checked, pxs_listget, pxs_newhost,
+    pxs_newmod, pxs_newobject, pxs_object_addfunc, pxs_varcall,
+    pxs_core::core_call,
+    shared::{
+        PxsRes, get_job_executor, pxs_Opaque,
+        var::{pxs_Var, pxs_VarT},
+    },
+};
+use etffi::ptr_magic::PtrMagic;
+
+/// State of a submitted job.
+struct JobState {
+    /// Runtime the job was submitted from.
+    rt: pxs_Var,
+    /// Set once the host completes the job.
+    done: bool,
+    result: Option<pxs_Var>,
+    error: Option<String>,
+    /// Owned references to the `then` callbacks.
+    callbacks: Vec<pxs_Var>,
+    /// The script dropped its `Job`. The state is removed once the job completes.
+    dropped: bool,
+}
+
+thread_local! {
+    static JOBS: RefCell<HashMap<i64, JobState>> = RefCell::new(HashMap::new());
+    static NEXT_ID: RefCell<i64> = const { RefCell::new(1) };
+}
+
+/// The `Job` object handed to scripts.
+struct Job {
+    id: i64,
+}
+
+impl PtrMagic for Job {}
+
+/// Job free method.
+unsafe extern "C" fn free_job(ptr: pxs_Opaque) {
+    let job = unsafe { Job::from_raw(ptr as *mut Job) };
+    JOBS.with(|jobs| {
+        let mut jobs = jobs.borrow_mut();
+        let remove = match jobs.get_mut(&job.id) {
+            Some(state) => {
+                state.dropped = true;
+                state.done
+            }
+            None => false,
+        };
+        if remove {
+            jobs.remove(&job.id);
+        }
+    });
+}
+
+/// Get the job id from a `Job` object.
+fn job_id(rt: pxs_VarT, var: pxs_VarT) -> PxsRes<i64> {
+    let mut cstrgen = CStringSafe::new();
+    let ptr = pxs_gethost_checked(rt, var, cstrgen.new_string("Job"));
+    if ptr.is_null() {
+        return pxs_error!("Expected a Job.");
+    }
+    Ok(unsafe { Job::from_borrow_void(ptr) }.id)
+}
+
+/// Run `f` on the state of job `id`.
+fn with_job<T>(id: i64, f: impl FnOnce(&mut JobState) -> T) -> PxsRes<T> {
+    JOBS.with(|jobs| match jobs.borrow_mut().get_mut(&id) {
+        Some(state) => Ok(f(state)),
+        None => pxs_error!("Unknown job: {id}."),
+    })
+}
+
+/// Call a `then` callback with `(result, error)`.
+fn call_callback(rt: &pxs_Var, func: &pxs_Var, result: &Option<pxs_Var>, error: &Option<String>) {
+    let args = pxs_Var::new_list_with(vec![
+        // The job keeps the result, do not take its deleter.
+        result.as_ref().map(|r| r.shallow_copy()).unwrap_or_else(pxs_Var::new_null),
+        error.clone().map(pxs_Var::new_string).unwrap_or_else(pxs_Var::new_null),
+    ]);
+    let res = own_var!(pxs_varcall(
+        rt as *const pxs_Var as pxs_VarT,
+        func as *const pxs_Var as pxs_VarT,
+        args.into_raw(),
+    ));
+    if res.is_exception() {
+        pxs_debug!("Job callback failed: {}", res.get_string().unwrap_or_default());
+    }
+}
+
+/// Complete a job with `result`. A `pxs_Exception` result fails the job with its message.
+///
+/// Runs every `then` callback. Returns false if the job is unknown or already completed.
+pub(crate) fn complete(id: i64, result: pxs_Var) -> bool {
+    // Take the callbacks out first so they can safely call back into `pxs_jobs`.
+    let taken = JOBS.with(|jobs| {
+        let mut jobs = jobs.borrow_mut();
+        let state = jobs.get_mut(&id)?;
+        if state.done {
+            return None;
+        }
+        state.done = true;
+        if result.is_exception() {
+            state.error = Some(result.get_string().unwrap_or_default());
+        } else {
+            state.result = Some(result);
+        }
+        let callbacks = std::mem::take(&mut state.callbacks);
+        // Nobody can read the result anymore, only keep it around for the callbacks.
+        if state.dropped {
+            let state = jobs.remove(&id).unwrap();
+            return Some((state.rt, callbacks, state.result, state.error));
+        }
+        Some((state.rt.shallow_copy(), callbacks, state.result.as_ref().map(|r| r.shallow_copy()), state.error.clone()))
+    });
+
+    let (rt, callbacks, result, error) = match taken {
+        Some(taken) => taken,
+        None => return false,
+    };
+    for func in callbacks.iter() {
+        call_callback(&rt, func, &result, &error);
+    }
+    true
+}
+
+/// Drop all jobs on this thread. Pending jobs can no longer be completed.
+///
+/// Jobs hold references to script values, so this must be called before the runtime stops.
+pub(crate) fn clear() {
+    JOBS.with(|jobs| jobs.borrow_mut().clear());
+}
+
+/// `job.done() -> bool`
+extern "C" fn pxs_jobs_job_done(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 2, 2, |rt| {
+        let id = job_id(rt, pxs_listget(args, 1))?;
+        Ok(pxs_Var::new_bool(with_job(id, |state| state.done)?))
+    })
+}
+
+/// `job.result()` returns the result or null if the job is pending or failed.
+extern "C" fn pxs_jobs_job_result(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 2, 2, |rt| {
+        let id = job_id(rt, pxs_listget(args, 1))?;
+        with_job(id, |state| match &state.result {
+            Some(result) => result.shallow_copy(),
+            None => pxs_Var::new_null(),
+        })
+    })
+}
+
+/// `job.error()` returns the error message or null.
+extern "C" fn pxs_jobs_job_error(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 2, 2, |rt| {
+        let id = job_id(rt, pxs_listget(args, 1))?;
+        with_job(id, |state| match &state.error {
+            Some(error) => pxs_Var::new_string(error.clone()),
+            None => pxs_Var::new_null(),
+        })
+    })
+}
+
+/// `job.then(fn)` calls `fn(result, error)` once the job completes. Runs right away if it already has.
+extern "C" fn pxs_jobs_job_then(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 3, 3, |rt| {
+        let id = job_id(rt, pxs_listget(args, 1))?;
+        let func = borrow_var!(pxs_listget(args, 2));
+        if !func.is_function() {
+            return pxs_error!("Job callback must be a Function, got: {:#?}.", func.tag);
+        }
+
+        let finished = with_job(id, |state| {
+            if state.done {
+                Some((state.result.as_ref().map(|r| r.shallow_copy()), state.error.clone()))
+            } else {
+                // Cloning a function moves the deleter, so the job keeps it alive.
+                state.callbacks.push(func.clone());
+                None
+            }
+        })?;
+        if let Some((result, error)) = finished {
+            call_callback(borrow_var!(rt), func, &result, &error);
+        }
+        Ok(pxs_Var::new_null())
+    })
+}
+
+/// `submit(kind, payload)` hands the job to the host executor and returns a `Job`.
+extern "C" fn pxs_jobs_submit(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 3, 3, |rt| {
+        let executor = match get_job_executor() {
+            Some(executor) => executor,
+            None => return pxs_error!("No job executor set. Call `pxs_job_setexecutor` from the host."),
+        };
+        let kind = borrow_var!(pxs_listget(args, 1)).get_string()?;
+
+        let id = NEXT_ID.with(|next| {
+            let mut next = next.borrow_mut();
+            *next += 1;
+            *next - 1
+        });
+        JOBS.with(|jobs| {
+            jobs.borrow_mut().insert(
+                id,
+                JobState {
+                    rt: borrow_var!(rt).clone(),
+                    done: false,
+                    result: None,
+                    error: None,
+                    callbacks: vec![],
+                    dropped: false,
+                },
+            )
+        });
+
+        let mut cstrgen = CStringSafe::new();
+        // The host may complete the job right away, the state already exists.
+        unsafe { executor(id, cstrgen.new_string(&kind), pxs_listget(args, 2)) };
+
+        let object = pxs_newobject(Job { id }.into_void(), free_job, cstrgen.new_string("Job"));
+        pxs_object_addfunc(object, cstrgen.new_string("done"), pxs_jobs_job_done);
+        pxs_object_addfunc(object, cstrgen.new_string("result"), pxs_jobs_job_result);
+        pxs_object_addfunc(object, cstrgen.new_string("error"), pxs_jobs_job_error);
+        pxs_object_addfunc(object, cstrgen.new_string("then"), pxs_jobs_job_then);
+        Ok(own_var!(pxs_newhost(object)))
+    })
+}
+
+/// Initialize `pxs_jobs` module.
+pub(crate) fn init() {
+    let mut cstrgen = CStringSafe::new();
+
+    let pxs_jobs = pxs_newmod(cstrgen.new_string("pxs_jobs"));
+    pxs_addfunc(pxs_jobs, cstrgen.new_string("submit"), pxs_jobs_submit);
+    pxs_addmod(pxs_jobs);
+}
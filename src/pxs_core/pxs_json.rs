@@ -1,50 +1,409 @@
-use crate::{create_raw_string, free_raw_string, pxs_freevar, pxs_objectget, pxs_var_fromname, pxs_varcall, shared::var::pxs_VarT};
-
-/// Encode a `pxs_Object` into JSON.
-pub extern "C" fn encode(rt: pxs_VarT, args: pxs_VarT) -> pxs_VarT {
-    // Get json module
-    let cname = create_raw_string!("pxs_json");
-    let json = pxs_var_fromname(rt, cname);
-    unsafe{
-        free_raw_string!(cname);
-    }
-
-    let cmethod = create_raw_string!("encode");
-    // Lets get the encode method as a var!
-    let encode_method = pxs_objectget(rt, json, cmethod);
-    // Now let's call it
-    let res = pxs_varcall(rt, encode_method, args);
-    unsafe{
-        free_raw_string!(cmethod);
-    }
-    // Free json
-    pxs_freevar(json);
-    // Free decode method
-    pxs_freevar(encode_method);
-
-    res
-} 
-
-/// Decode a JSON string into a `pxs_Object`
-pub extern "C" fn decode(rt: pxs_VarT, args: pxs_VarT) -> pxs_VarT {
-    let cname = create_raw_string!("pxs_json");
-    let json = pxs_var_fromname(rt, cname);
-    unsafe{
-        free_raw_string!(cname);
-    }
-
-    let cmethod = create_raw_string!("decode");
-    // Lets get the decode method as a var!
-    let decode_method = pxs_objectget(rt, json, cmethod);
-    // Now let's call it
-    let res = pxs_varcall(rt, decode_method, args);
-    unsafe {
-        free_raw_string!(cmethod);
-    }
-
-    // Free json
-    pxs_freevar(json);
-    // Free decode_method
-    pxs_freevar(decode_method);
-    res
-}
\ No newline at end of file
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+//! JSON for scripts, implemented once over `pxs_Var` so every runtime encodes and decodes the same way.
+//!
+//! Lists and typed arrays are arrays, maps and script objects (Lua tables, Python dicts, JS objects) are objects.
+//! Object keys are sorted so the output does not depend on the runtime. Decoding gives `pxs_Map`s and `pxs_List`s,
+//! integers without a fraction or exponent are ints.
+use std::sync::Arc;
+
+use crate::{
+    add_callback_to_module, borrow_var, own_var, pxs_debug, pxs_error, pxs_listget, pxs_listlen,
+    pxs_core::{core_call, get_entries},
+    shared::{
+        PxsRes,
+        func::pxs_Func,
+        module::pxs_Module,
+        var::{pxs_Var, pxs_VarT, pxs_VarType},
+    },
+};
+use etffi::ptr_magic::PtrMagic;
+
+/// Deepest nesting encoded or decoded. Also stops self referencing script tables.
+const MAX_DEPTH: usize = 128;
+
+struct Encoder {
+    /// Runtime used to walk script objects.
+    rt: pxs_VarT,
+    /// Spaces per level, 0 for compact output.
+    indent: usize,
+    out: String,
+}
+
+impl Encoder {
+    /// Start a new line at `depth` when pretty printing.
+    fn newline(&mut self, depth: usize) {
+        if self.indent > 0 {
+            self.out.push('\n');
+            self.out.push_str(&" ".repeat(self.indent * depth));
+        }
+    }
+
+    fn string(&mut self, val: &str) {
+        self.out.push('"');
+        for c in val.chars() {
+            match c {
+                '"' => self.out.push_str("\\\""),
+                '\\' => self.out.push_str("\\\\"),
+                '\n' => self.out.push_str("\\n"),
+                '\r' => self.out.push_str("\\r"),
+                '\t' => self.out.push_str("\\t"),
+                c if (c as u32) < 0x20 => self.out.push_str(&format!("\\u{:04x}", c as u32)),
+                c => self.out.push(c),
+            }
+        }
+        self.out.push('"');
+    }
+
+    fn float(&mut self, val: f64, repr: String) -> PxsRes<()> {
+        if !val.is_finite() {
+            return pxs_error!("Can not encode {val} as JSON.");
+        }
+        self.out.push_str(&repr);
+        Ok(())
+    }
+
+    /// Key of a object entry. Strings and numbers only.
+    fn key(key: &pxs_Var) -> PxsRes<String> {
+        match key.tag {
+            pxs_VarType::pxs_String => key.get_string(),
+            pxs_VarType::pxs_Int64 => Ok(key.get_i64()?.to_string()),
+            pxs_VarType::pxs_UInt64 => Ok(key.get_u64()?.to_string()),
+            pxs_VarType::pxs_Int32 => Ok(key.get_i32()?.to_string()),
+            pxs_VarType::pxs_UInt32 => Ok(key.get_u32()?.to_string()),
+            pxs_VarType::pxs_Float64 => Ok(key.get_f64()?.to_string()),
+            _ => pxs_error!("JSON object keys must be strings or numbers, got: {:#?}.", key.tag),
+        }
+    }
+
+    /// Entries of a map or script object, sorted by key.
+    fn entries(&self, var: &pxs_Var) -> PxsRes<Vec<(String, pxs_Var)>> {
+        let Some(mut entries) = get_entries(self.rt, var, Self::key)? else {
+            return pxs_error!("Can not encode a {:#?} as JSON.", var.tag);
+        };
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+        Ok(entries)
+    }
+
+    fn value(&mut self, var: &pxs_Var, depth: usize) -> PxsRes<()> {
+        if depth > MAX_DEPTH {
+            return pxs_error!("Can not encode a value nested deeper than {MAX_DEPTH} levels as JSON.");
+        }
+        match var.tag {
+            pxs_VarType::pxs_Null => self.out.push_str("null"),
+            pxs_VarType::pxs_Bool => self.out.push_str(if var.get_bool()? { "true" } else { "false" }),
+            pxs_VarType::pxs_Int64 => self.out.push_str(&var.get_i64()?.to_string()),
+            pxs_VarType::pxs_UInt64 => self.out.push_str(&var.get_u64()?.to_string()),
+            pxs_VarType::pxs_Int32 => self.out.push_str(&var.get_i32()?.to_string()),
+            pxs_VarType::pxs_UInt32 => self.out.push_str(&var.get_u32()?.to_string()),
+            pxs_VarType::pxs_Byte => self.out.push_str(&var.get_byte()?.to_string()),
+            // Debug keeps the `.0` so floats decode as floats.
+            pxs_VarType::pxs_Float64 => self.float(var.get_f64()?, format!("{:?}", var.get_f64()?))?,
+            pxs_VarType::pxs_Float32 => self.float(var.get_f32()? as f64, format!("{:?}", var.get_f32()?))?,
+            pxs_VarType::pxs_String => self.string(&var.get_string()?),
+            pxs_VarType::pxs_List | pxs_VarType::pxs_TypedArray => {
+                let array = var.get_typed_array().map(|array| array.to_list());
+                let items = &array.as_ref().unwrap_or(var).get_list().unwrap().vars;
+                self.out.push('[');
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        self.out.push(',');
+                    }
+                    self.newline(depth + 1);
+                    self.value(item, depth + 1)?;
+                }
+                if !items.is_empty() {
+                    self.newline(depth);
+                }
+                self.out.push(']');
+            }
+            pxs_VarType::pxs_Map | pxs_VarType::pxs_Object => {
+                let entries = self.entries(var)?;
+                self.out.push('{');
+                for (i, (key, value)) in entries.iter().enumerate() {
+                    if i > 0 {
+                        self.out.push(',');
+                    }
+                    self.newline(depth + 1);
+                    self.string(key);
+                    self.out.push_str(if self.indent > 0 { ": " } else { ":" });
+                    self.value(value, depth + 1)?;
+                }
+                if !entries.is_empty() {
+                    self.newline(depth);
+                }
+                self.out.push('}');
+            }
+            tag => return pxs_error!("Can not encode a {tag:#?} as JSON."),
+        }
+        Ok(())
+    }
+}
+
+/// Encode `var` as JSON. `indent` is the spaces per level, 0 for compact output. `rt` is used to walk script objects.
+pub(crate) fn encode(rt: pxs_VarT, var: &pxs_Var, indent: usize) -> PxsRes<String> {
+    let mut encoder = Encoder { rt, indent, out: String::new() };
+    encoder.value(var, 0)?;
+    Ok(encoder.out)
+}
+
+struct Parser<'a> {
+    src: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error<T>(&self, msg: &str) -> PxsRes<T> {
+        pxs_error!("Invalid JSON at {}: {msg}", self.pos)
+    }
+
+    fn skip_ws(&mut self) {
+        while let Some(b' ' | b'\t' | b'\n' | b'\r') = self.src.get(self.pos) {
+            self.pos += 1;
+        }
+    }
+
+    fn peek(&mut self) -> Option<u8> {
+        self.skip_ws();
+        self.src.get(self.pos).copied()
+    }
+
+    fn expect(&mut self, c: u8) -> PxsRes<()> {
+        if self.peek() != Some(c) {
+            return self.error(&format!("expected '{}'.", c as char));
+        }
+        self.pos += 1;
+        Ok(())
+    }
+
+    fn literal(&mut self, word: &str, var: pxs_Var) -> PxsRes<pxs_Var> {
+        if !self.src[self.pos..].starts_with(word.as_bytes()) {
+            return self.error("unexpected character.");
+        }
+        self.pos += word.len();
+        Ok(var)
+    }
+
+    fn hex4(&mut self) -> PxsRes<u32> {
+        let hex = self.src.get(self.pos..self.pos + 4).and_then(|hex| std::str::from_utf8(hex).ok());
+        match hex.and_then(|hex| u32::from_str_radix(hex, 16).ok()) {
+            Some(code) => {
+                self.pos += 4;
+                Ok(code)
+            }
+            None => self.error("invalid \\u escape."),
+        }
+    }
+
+    fn string(&mut self) -> PxsRes<String> {
+        self.expect(b'"')?;
+        let mut res = vec![];
+        loop {
+            let Some(&c) = self.src.get(self.pos) else {
+                return self.error("unterminated string.");
+            };
+            self.pos += 1;
+            match c {
+                b'"' => break,
+                b'\\' => {
+                    let Some(&escape) = self.src.get(self.pos) else {
+                        return self.error("unterminated string.");
+                    };
+                    self.pos += 1;
+                    let c = match escape {
+                        b'"' => '"',
+                        b'\\' => '\\',
+                        b'/' => '/',
+                        b'b' => '\u{8}',
+                        b'f' => '\u{c}',
+                        b'n' => '\n',
+                        b'r' => '\r',
+                        b't' => '\t',
+                        b'u' => {
+                            let mut code = self.hex4()?;
+                            // Surrogate pair
+                            if (0xd800..0xdc00).contains(&code) && self.src[self.pos..].starts_with(b"\\u") {
+                                self.pos += 2;
+                                let low = self.hex4()?;
+                                if !(0xdc00..0xe000).contains(&low) {
+                                    return self.error("invalid surrogate pair.");
+                                }
+                                code = 0x10000 + ((code - 0xd800) << 10) + (low - 0xdc00);
+                            }
+                            match char::from_u32(code) {
+                                Some(c) => c,
+                                None => return self.error("invalid \\u escape."),
+                            }
+                        }
+                        _ => return self.error("invalid escape."),
+                    };
+                    res.extend(c.encode_utf8(&mut [0; 4]).as_bytes());
+                }
+                c if c < 0x20 => return self.error("control character in string."),
+                c => res.push(c),
+            }
+        }
+        // The source is a &str, so this only fails on broken escapes which are caught above.
+        String::from_utf8(res).or_else(|_| self.error("invalid UTF-8."))
+    }
+
+    fn number(&mut self) -> PxsRes<pxs_Var> {
+        let start = self.pos;
+        let mut float = false;
+        while let Some(&c) = self.src.get(self.pos) {
+            match c {
+                b'0'..=b'9' | b'-' | b'+' => {}
+                b'.' | b'e' | b'E' => float = true,
+                _ => break,
+            }
+            self.pos += 1;
+        }
+        let text = std::str::from_utf8(&self.src[start..self.pos]).unwrap();
+        if !float && let Ok(val) = text.parse::<i64>() {
+            return Ok(pxs_Var::new_i64(val));
+        }
+        match text.parse::<f64>() {
+            Ok(val) => Ok(pxs_Var::new_f64(val)),
+            _ => {
+                self.pos = start;
+                self.error("invalid number.")
+            }
+        }
+    }
+
+    fn value(&mut self, depth: usize) -> PxsRes<pxs_Var> {
+        if depth > MAX_DEPTH {
+            return self.error(&format!("nested deeper than {MAX_DEPTH} levels."));
+        }
+        match self.peek() {
+            Some(b'n') => self.literal("null", pxs_Var::new_null()),
+            Some(b't') => self.literal("true", pxs_Var::new_bool(true)),
+            Some(b'f') => self.literal("false", pxs_Var::new_bool(false)),
+            Some(b'"') => Ok(pxs_Var::new_string(self.string()?)),
+            Some(b'-' | b'0'..=b'9') => self.number(),
+            Some(b'[') => {
+                self.pos += 1;
+                let mut items = vec![];
+                if self.peek() == Some(b']') {
+                    self.pos += 1;
+                    return Ok(pxs_Var::new_list_with(items));
+                }
+                loop {
+                    items.push(self.value(depth + 1)?);
+                    match self.peek() {
+                        Some(b',') => self.pos += 1,
+                        Some(b']') => {
+                            self.pos += 1;
+                            return Ok(pxs_Var::new_list_with(items));
+                        }
+                        _ => return self.error("expected ',' or ']'."),
+                    }
+                }
+            }
+            Some(b'{') => {
+                self.pos += 1;
+                let res = pxs_Var::new_map();
+                let map = res.get_map().unwrap();
+                if self.peek() == Some(b'}') {
+                    self.pos += 1;
+                    return Ok(res);
+                }
+                loop {
+                    if self.peek() != Some(b'"') {
+                        return self.error("expected a string key.");
+                    }
+                    let key = self.string()?;
+                    self.expect(b':')?;
+                    map.add_item(pxs_Var::new_string(key), self.value(depth + 1)?);
+                    match self.peek() {
+                        Some(b',') => self.pos += 1,
+                        Some(b'}') => {
+                            self.pos += 1;
+                            return Ok(res);
+                        }
+                        _ => return self.error("expected ',' or '}'."),
+                    }
+                }
+            }
+            Some(_) => self.error("unexpected character."),
+            None => self.error("unexpected end."),
+        }
+    }
+}
+
+/// Decode JSON text into `pxs_Map`s, `pxs_List`s and values.
+pub(crate) fn decode(src: &str) -> PxsRes<pxs_Var> {
+    let mut parser = Parser { src: src.as_bytes(), pos: 0 };
+    let res = parser.value(0)?;
+    if parser.peek().is_some() {
+        return parser.error("unexpected data after the value.");
+    }
+    Ok(res)
+}
+
+/// Indent argument at `idx` of a call, 0 if missing or null.
+fn indent_arg(args: pxs_VarT, idx: i32) -> PxsRes<usize> {
+    if pxs_listlen(args) <= idx {
+        return Ok(0);
+    }
+    let indent = borrow_var!(pxs_listget(args, idx));
+    if indent.is_null() {
+        return Ok(0);
+    }
+    Ok(super::get_number(indent)?.max(0.0) as usize)
+}
+
+/// `encode(value, indent?)` encode a value as a JSON string.
+extern "C" fn script_encode(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 2, 3, |rt| {
+        let indent = indent_arg(args, 2)?;
+        Ok(pxs_Var::new_string(encode(rt, borrow_var!(pxs_listget(args, 1)), indent)?))
+    })
+}
+
+/// `decode(string)` decode a JSON string. Objects become maps/dicts/tables.
+extern "C" fn script_decode(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 2, 2, |_| decode(&borrow_var!(pxs_listget(args, 1)).get_string()?))
+}
+
+/// The `pxs_json` module. Runtimes add it when they start, it is not part of the registry.
+pub(crate) fn module() -> Arc<pxs_Module> {
+    let mut module = pxs_Module::new("pxs_json".to_string());
+    for (name, func) in [("encode", script_encode as pxs_Func), ("decode", script_decode)] {
+        if let Err(e) = add_callback_to_module(&mut module, name, func, std::ptr::null_mut(), None) {
+            pxs_debug!("Not adding 'pxs_json.{name}': {e}");
+        }
+    }
+    Arc::new(module)
+}
+
+/// Encode `args[0]` into a JSON string, `args[1]` is a optional indent.
+pub(crate) fn encode_args(rt: pxs_VarT, args: pxs_VarT) -> pxs_VarT {
+    let args = own_var!(args);
+    let args_ptr = &args as *const pxs_Var as pxs_VarT;
+    let res = indent_arg(args_ptr, 1).and_then(|indent| encode(rt, borrow_var!(pxs_listget(args_ptr, 0)), indent));
+    match res {
+        Ok(json) => pxs_Var::new_string(json).into_raw(),
+        Err(e) => pxs_Var::new_exception(e).into_raw(),
+    }
+}
+
+/// Decode the JSON string `args[0]`.
+pub(crate) fn decode_args(args: pxs_VarT) -> pxs_VarT {
+    let args = own_var!(args);
+    let args_ptr = &args as *const pxs_Var as pxs_VarT;
+    let text = pxs_listget(args_ptr, 0);
+    if text.is_null() {
+        return pxs_Var::expected_n_args_ep(1, 0).into_raw();
+    }
+    match borrow_var!(text).get_string().and_then(|text| decode(&text)) {
+        Ok(res) => res.into_raw(),
+        Err(e) => pxs_Var::new_exception(e).into_raw(),
+    }
+}
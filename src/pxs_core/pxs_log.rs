@@ -0,0 +1,103 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+//! Leveled logging for scripts.
+//!
+//! Messages go to the sink set with `pxs_set_log_sink` together with the script file and line that logged them. Without
+//! a sink, info and above are written to stderr.
+use std::ffi::CString;
+
+use etffi::cstring::CStringSafe;
+
+use crate::{
+    borrow_var, caller_location, pxs_addfunc, pxs_addmod, pxs_listget, pxs_listlen, pxs_newmod,
+    pxs_core::{core_call, to_text},
+    shared::{
+        PxsRes, get_log_sink, pxs_LogLevel,
+        func::pxs_Func,
+        var::{pxs_Var, pxs_VarT},
+    },
+};
+use etffi::ptr_magic::PtrMagic;
+
+/// Name of a level in the default output.
+fn level_name(level: pxs_LogLevel) -> &'static str {
+    match level {
+        pxs_LogLevel::pxs_LogDebug => "DEBUG",
+        pxs_LogLevel::pxs_LogInfo => "INFO",
+        pxs_LogLevel::pxs_LogWarn => "WARN",
+        pxs_LogLevel::pxs_LogError => "ERROR",
+    }
+}
+
+/// Log the text of the args (after the runtime), joined by spaces.
+fn log(level: pxs_LogLevel, rt: pxs_VarT, args: pxs_VarT) -> PxsRes<pxs_Var> {
+    let sink = get_log_sink();
+    let min_level = sink.map(|(_, min_level)| min_level).unwrap_or(pxs_LogLevel::pxs_LogInfo);
+    if level < min_level {
+        return Ok(pxs_Var::new_null());
+    }
+
+    let parts = (1..pxs_listlen(args))
+        .map(|idx| to_text(rt, borrow_var!(pxs_listget(args, idx))))
+        .collect::<PxsRes<Vec<_>>>()?;
+    let message = parts.join(" ");
+    let (file, line) = borrow_var!(rt)
+        .get_i64()
+        .ok()
+        .and_then(|runtime| caller_location(runtime as i32))
+        .unwrap_or_default();
+
+    match sink {
+        Some((sink, _)) => {
+            let message = CString::new(message).unwrap_or_default();
+            let file = CString::new(file).unwrap_or_default();
+            unsafe { sink(level, message.as_ptr(), file.as_ptr(), line) };
+        }
+        None if file.is_empty() => eprintln!("[{}] {message}", level_name(level)),
+        None => eprintln!("[{}] {file}:{line}: {message}", level_name(level)),
+    }
+    Ok(pxs_Var::new_null())
+}
+
+/// `debug(...)`
+extern "C" fn pxs_log_debug(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 1, usize::MAX, |rt| log(pxs_LogLevel::pxs_LogDebug, rt, args))
+}
+
+/// `info(...)`
+extern "C" fn pxs_log_info(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 1, usize::MAX, |rt| log(pxs_LogLevel::pxs_LogInfo, rt, args))
+}
+
+/// `warn(...)`
+extern "C" fn pxs_log_warn(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 1, usize::MAX, |rt| log(pxs_LogLevel::pxs_LogWarn, rt, args))
+}
+
+/// `error(...)`
+extern "C" fn pxs_log_error(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 1, usize::MAX, |rt| log(pxs_LogLevel::pxs_LogError, rt, args))
+}
+
+/// Initialize `pxs_log` module.
+pub(crate) fn init() {
+    let mut cstrgen = CStringSafe::new();
+
+    let pxs_log = pxs_newmod(cstrgen.new_string("pxs_log"));
+    let funcs: [(&str, pxs_Func); 4] = [
+        ("debug", pxs_log_debug),
+        ("info", pxs_log_info),
+        ("warn", pxs_log_warn),
+        ("error", pxs_log_error),
+    ];
+    for (name, func) in funcs {
+        pxs_addfunc(pxs_log, cstrgen.new_string(name), func);
+    }
+    pxs_addmod(pxs_log);
+}
@@ -0,0 +1,143 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+//! Math helpers with the same results in every language (i.e. `round` is half away from zero, not Python's banker's
+//! rounding). Noise lives in `pxs_noise` and random numbers in `pxs_random`.
+use etffi::cstring::CStringSafe;
+
+use crate::{
+    borrow_var, pxs_addfunc, pxs_addmod, pxs_error, pxs_listget, pxs_listlen, pxs_newmod,
+    pxs_core::{core_call, get_number},
+    shared::{
+        PxsRes,
+        func::pxs_Func,
+        var::{pxs_Var, pxs_VarT},
+    },
+};
+use etffi::ptr_magic::PtrMagic;
+
+/// Number arguments `1..` of a call (after the runtime).
+fn numbers(args: pxs_VarT) -> PxsRes<Vec<f64>> {
+    (1..pxs_listlen(args)).map(|i| get_number(borrow_var!(pxs_listget(args, i)))).collect()
+}
+
+/// True if every argument after the runtime is a int, results then stay ints.
+fn all_ints(args: pxs_VarT) -> bool {
+    (1..pxs_listlen(args)).all(|i| {
+        let var = borrow_var!(pxs_listget(args, i));
+        var.is_i64() || var.is_u64() || var.is_i32() || var.is_u32()
+    })
+}
+
+/// A f64 result, or a int one if the inputs were ints.
+fn number(val: f64, int: bool) -> pxs_Var {
+    if int { pxs_Var::new_i64(val as i64) } else { pxs_Var::new_f64(val) }
+}
+
+/// `lerp(a, b, t)` `a + (b - a) * t`.
+extern "C" fn pxs_math_lerp(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 4, 4, |_| {
+        let n = numbers(args)?;
+        Ok(pxs_Var::new_f64(n[0] + (n[1] - n[0]) * n[2]))
+    })
+}
+
+/// `inverse_lerp(a, b, v)` where `v` is between `a` and `b`, 0 if `a == b`.
+extern "C" fn pxs_math_inverse_lerp(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 4, 4, |_| {
+        let n = numbers(args)?;
+        if n[0] == n[1] {
+            return Ok(pxs_Var::new_f64(0.0));
+        }
+        Ok(pxs_Var::new_f64((n[2] - n[0]) / (n[1] - n[0])))
+    })
+}
+
+/// `clamp(v, min, max)`
+extern "C" fn pxs_math_clamp(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 4, 4, |_| {
+        let n = numbers(args)?;
+        if n[1] > n[2] {
+            return pxs_error!("clamp min ({}) is greater than max ({}).", n[1], n[2]);
+        }
+        Ok(number(n[0].clamp(n[1], n[2]), all_ints(args)))
+    })
+}
+
+/// `remap(v, from_min, from_max, to_min, to_max)` maps `v` from one range onto another.
+extern "C" fn pxs_math_remap(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 6, 6, |_| {
+        let n = numbers(args)?;
+        let t = if n[1] == n[2] { 0.0 } else { (n[0] - n[1]) / (n[2] - n[1]) };
+        Ok(pxs_Var::new_f64(n[3] + (n[4] - n[3]) * t))
+    })
+}
+
+/// `smoothstep(edge0, edge1, x)` smooth Hermite step between 0 and 1.
+extern "C" fn pxs_math_smoothstep(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 4, 4, |_| {
+        let n = numbers(args)?;
+        let t = if n[0] == n[1] { 0.0 } else { ((n[2] - n[0]) / (n[1] - n[0])).clamp(0.0, 1.0) };
+        Ok(pxs_Var::new_f64(t * t * (3.0 - 2.0 * t)))
+    })
+}
+
+/// `sign(x)` -1, 0 or 1.
+extern "C" fn pxs_math_sign(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 2, 2, |_| {
+        let x = numbers(args)?[0];
+        Ok(pxs_Var::new_i64(if x > 0.0 { 1 } else if x < 0.0 { -1 } else { 0 }))
+    })
+}
+
+/// `round(x, digits?)` half away from zero. Without `digits` the result is a int.
+extern "C" fn pxs_math_round(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 2, 3, |_| {
+        let n = numbers(args)?;
+        match n.get(1) {
+            Some(digits) => {
+                let scale = 10f64.powi(*digits as i32);
+                Ok(pxs_Var::new_f64((n[0] * scale).round() / scale))
+            }
+            None => Ok(pxs_Var::new_i64(n[0].round() as i64)),
+        }
+    })
+}
+
+/// `wrap(v, min, max)` wraps `v` into `[min, max)`, i.e. angles or tile coordinates.
+extern "C" fn pxs_math_wrap(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 4, 4, |_| {
+        let n = numbers(args)?;
+        let range = n[2] - n[1];
+        if range <= 0.0 {
+            return pxs_error!("wrap max ({}) must be greater than min ({}).", n[2], n[1]);
+        }
+        Ok(number(n[1] + (n[0] - n[1]).rem_euclid(range), all_ints(args)))
+    })
+}
+
+/// Initialize `pxs_math` module.
+pub(crate) fn init() {
+    let mut cstrgen = CStringSafe::new();
+
+    let pxs_math = pxs_newmod(cstrgen.new_string("pxs_math"));
+    let funcs: [(&str, pxs_Func); 8] = [
+        ("lerp", pxs_math_lerp),
+        ("inverse_lerp", pxs_math_inverse_lerp),
+        ("clamp", pxs_math_clamp),
+        ("remap", pxs_math_remap),
+        ("smoothstep", pxs_math_smoothstep),
+        ("sign", pxs_math_sign),
+        ("round", pxs_math_round),
+        ("wrap", pxs_math_wrap),
+    ];
+    for (name, func) in funcs {
+        pxs_addfunc(pxs_math, cstrgen.new_string(name), func);
+    }
+    pxs_addmod(pxs_math);
+}
@@ -6,7 +6,7 @@ use crate::{
     pxs_newlist, pxs_newmod, pxs_newnull, pxs_objectget,
     shared::{
         PXS_PTR_NAME,
-        object::apply_ref_count_delete,
+        object::release_script_ref,
         var::{pxs_Var, pxs_VarT, pxs_VarType},
     },
 };
@@ -35,7 +35,7 @@ extern "C" fn pxs_mem_delete(args: pxs_VarT) -> pxs_VarT {
 
     // Drop it
     let idx = obj_idx.get_i64().unwrap();
-    apply_ref_count_delete(idx as i32);
+    release_script_ref(idx as i32);
 
     pxs_newnull()
     // pxs_newbool(true)
@@ -0,0 +1,319 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+use std::{cell::RefCell, rc::Rc};
+
+use etffi::cstring::CStringSafe;
+
+use crate::{
+    borrow_var, pxs_addfunc, pxs_addmod, pxs_error, pxs_listget, pxs_listlen, pxs_newmod,
+    pxs_core::{core_call, get_field, get_number, splitmix64},
+    shared::{
+        PxsRes,
+        func::pxs_Func,
+        var::{pxs_Var, pxs_VarT},
+    },
+};
+use etffi::ptr_magic::PtrMagic;
+
+/// Number of seeds whose permutation table is kept per thread.
+const CACHED_SEEDS: usize = 8;
+
+thread_local! {
+    /// Recently used permutation tables, most recent first.
+    static CACHE: RefCell<Vec<(i64, Rc<Noise>)>> = const { RefCell::new(vec![]) };
+}
+
+/// Drop the cached permutation tables of this thread.
+pub(crate) fn clear_cache() {
+    CACHE.with(|cache| cache.borrow_mut().clear());
+}
+
+/// Seeded permutation table.
+struct Noise {
+    perm: [u8; 512],
+}
+
+impl Noise {
+    /// The permutation table is shuffled with SplitMix64 so that the same seed gives the same noise everywhere.
+    fn new(seed: i64) -> Self {
+        let mut table: [u8; 256] = std::array::from_fn(|i| i as u8);
+        let mut state = seed as u64;
+        // Fisher-Yates
+        for i in (1..256).rev() {
+            let j = (splitmix64(&mut state) % (i as u64 + 1)) as usize;
+            table.swap(i, j);
+        }
+        let perm = std::array::from_fn(|i| table[i & 255]);
+        Noise { perm }
+    }
+
+    /// The table for `seed`, shuffled once and reused while the seed is sampled.
+    fn cached(seed: i64) -> Rc<Self> {
+        CACHE.with(|cache| {
+            let mut cache = cache.borrow_mut();
+            let noise = match cache.iter().position(|(s, _)| *s == seed) {
+                Some(i) => cache.remove(i).1,
+                None => Rc::new(Noise::new(seed)),
+            };
+            cache.insert(0, (seed, noise.clone()));
+            cache.truncate(CACHED_SEEDS);
+            noise
+        })
+    }
+
+    /// Hash of a lattice cell. Only the low 8 bits are used, so callers step with `wrapping_add`.
+    fn hash(&self, x: i64, y: i64) -> u8 {
+        let xi = (x & 255) as usize;
+        let yi = (y & 255) as usize;
+        self.perm[self.perm[xi] as usize + yi]
+    }
+
+    /// Classic perlin noise. Returns roughly [-1, 1].
+    fn perlin(&self, x: f64, y: f64) -> f64 {
+        let x0 = x.floor();
+        let y0 = y.floor();
+        let (fx, fy) = (x - x0, y - y0);
+        let (ix, iy) = (x0 as i64, y0 as i64);
+
+        let grad = |h: u8, dx: f64, dy: f64| -> f64 {
+            match h & 7 {
+                0 => dx + dy,
+                1 => -dx + dy,
+                2 => dx - dy,
+                3 => -dx - dy,
+                4 => dx,
+                5 => -dx,
+                6 => dy,
+                _ => -dy,
+            }
+        };
+
+        let n00 = grad(self.hash(ix, iy), fx, fy);
+        let n10 = grad(self.hash(ix.wrapping_add(1), iy), fx - 1.0, fy);
+        let n01 = grad(self.hash(ix, iy.wrapping_add(1)), fx, fy - 1.0);
+        let n11 = grad(self.hash(ix.wrapping_add(1), iy.wrapping_add(1)), fx - 1.0, fy - 1.0);
+
+        let (u, v) = (fade(fx), fade(fy));
+        lerp(lerp(n00, n10, u), lerp(n01, n11, u), v).clamp(-1.0, 1.0)
+    }
+
+    /// 2D simplex noise. Returns [-1, 1].
+    fn simplex(&self, x: f64, y: f64) -> f64 {
+        const F2: f64 = 0.366_025_403_784_438_6; // (sqrt(3) - 1) / 2
+        const G2: f64 = 0.211_324_865_405_187_1; // (3 - sqrt(3)) / 6
+        const GRADS: [(f64, f64); 8] = [
+            (1.0, 1.0),
+            (-1.0, 1.0),
+            (1.0, -1.0),
+            (-1.0, -1.0),
+            (1.0, 0.0),
+            (-1.0, 0.0),
+            (0.0, 1.0),
+            (0.0, -1.0),
+        ];
+
+        let s = (x + y) * F2;
+        let i = (x + s).floor();
+        let j = (y + s).floor();
+        let t = (i + j) * G2;
+        let x0 = x - (i - t);
+        let y0 = y - (j - t);
+
+        let (i1, j1) = if x0 > y0 { (1, 0) } else { (0, 1) };
+        let x1 = x0 - i1 as f64 + G2;
+        let y1 = y0 - j1 as f64 + G2;
+        let x2 = x0 - 1.0 + 2.0 * G2;
+        let y2 = y0 - 1.0 + 2.0 * G2;
+
+        let (ii, jj) = (i as i64, j as i64);
+        let corner = |h: u8, dx: f64, dy: f64| -> f64 {
+            let t = 0.5 - dx * dx - dy * dy;
+            if t < 0.0 {
+                0.0
+            } else {
+                let (gx, gy) = GRADS[(h & 7) as usize];
+                let t = t * t;
+                t * t * (gx * dx + gy * dy)
+            }
+        };
+
+        let n0 = corner(self.hash(ii, jj), x0, y0);
+        let n1 = corner(self.hash(ii.wrapping_add(i1), jj.wrapping_add(j1)), x1, y1);
+        let n2 = corner(self.hash(ii.wrapping_add(1), jj.wrapping_add(1)), x2, y2);
+
+        (70.0 * (n0 + n1 + n2)).clamp(-1.0, 1.0)
+    }
+
+    /// Value noise. Returns [-1, 1].
+    fn value(&self, x: f64, y: f64) -> f64 {
+        let x0 = x.floor();
+        let y0 = y.floor();
+        let (ix, iy) = (x0 as i64, y0 as i64);
+        let corner = |cx: i64, cy: i64| self.hash(cx, cy) as f64 / 127.5 - 1.0;
+
+        let (u, v) = (fade(x - x0), fade(y - y0));
+        lerp(
+            lerp(corner(ix, iy), corner(ix.wrapping_add(1), iy), u),
+            lerp(corner(ix, iy.wrapping_add(1)), corner(ix.wrapping_add(1), iy.wrapping_add(1)), u),
+            v,
+        )
+    }
+}
+
+fn fade(t: f64) -> f64 {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+fn lerp(a: f64, b: f64, t: f64) -> f64 {
+    a + (b - a) * t
+}
+
+/// Which noise function to use.
+#[derive(Debug, Clone, Copy)]
+enum Kind {
+    Perlin,
+    Simplex,
+    Value,
+}
+
+impl Kind {
+    fn from_name(name: &str) -> PxsRes<Self> {
+        match name {
+            "perlin" => Ok(Kind::Perlin),
+            "simplex" => Ok(Kind::Simplex),
+            "value" => Ok(Kind::Value),
+            _ => pxs_error!("Unknown noise kind: '{name}'. Expected 'perlin', 'simplex' or 'value'."),
+        }
+    }
+
+    fn sample(&self, noise: &Noise, x: f64, y: f64) -> f64 {
+        match self {
+            Kind::Perlin => noise.perlin(x, y),
+            Kind::Simplex => noise.simplex(x, y),
+            Kind::Value => noise.value(x, y),
+        }
+    }
+}
+
+/// Read the seed argument at `i` if it exists. Defaults to 0.
+fn seed_arg(args: pxs_VarT, i: i32) -> PxsRes<i64> {
+    if pxs_listlen(args) <= i {
+        return Ok(0);
+    }
+    let seed = borrow_var!(pxs_listget(args, i));
+    if seed.is_null() {
+        Ok(0)
+    } else {
+        Ok(get_number(seed)? as i64)
+    }
+}
+
+/// Read a coordinate. NaN and infinity have no lattice cell.
+fn coord(var: &pxs_Var) -> PxsRes<f64> {
+    let v = get_number(var)?;
+    if !v.is_finite() {
+        return pxs_error!("Noise coordinates must be finite, got: {v}.");
+    }
+    Ok(v)
+}
+
+/// `kind(x, y, seed?)`
+fn sample(args: pxs_VarT, kind: Kind) -> pxs_VarT {
+    core_call(args, 3, 4, |_| {
+        let x = coord(borrow_var!(pxs_listget(args, 1)))?;
+        let y = coord(borrow_var!(pxs_listget(args, 2)))?;
+        let noise = Noise::cached(seed_arg(args, 3)?);
+        Ok(pxs_Var::new_f64(kind.sample(&noise, x, y)))
+    })
+}
+
+extern "C" fn pxs_noise_perlin(args: pxs_VarT) -> pxs_VarT {
+    sample(args, Kind::Perlin)
+}
+
+extern "C" fn pxs_noise_simplex(args: pxs_VarT) -> pxs_VarT {
+    sample(args, Kind::Simplex)
+}
+
+extern "C" fn pxs_noise_value(args: pxs_VarT) -> pxs_VarT {
+    sample(args, Kind::Value)
+}
+
+/// `fbm(x, y, options?)` layered noise.
+///
+/// options: kind ("perlin"), seed (0), octaves (4), lacunarity (2.0), gain (0.5)
+extern "C" fn pxs_noise_fbm(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 3, 4, |rt| {
+        let x = coord(borrow_var!(pxs_listget(args, 1)))?;
+        let y = coord(borrow_var!(pxs_listget(args, 2)))?;
+
+        let mut kind = Kind::Perlin;
+        let mut seed = 0;
+        let mut octaves = 4;
+        let mut lacunarity = 2.0;
+        let mut gain = 0.5;
+        if pxs_listlen(args) == 4 {
+            let options = borrow_var!(pxs_listget(args, 3));
+            let option = |key: &str| get_field(rt, options, key).filter(|v| !v.is_null());
+            if let Some(v) = option("kind") {
+                kind = Kind::from_name(&v.get_string()?)?;
+            }
+            if let Some(v) = option("seed") {
+                seed = get_number(&v)? as i64;
+            }
+            if let Some(v) = option("octaves") {
+                octaves = get_number(&v)? as i64;
+            }
+            if let Some(v) = option("lacunarity") {
+                lacunarity = get_number(&v)?;
+            }
+            if let Some(v) = option("gain") {
+                gain = get_number(&v)?;
+            }
+        }
+        if !(1..=16).contains(&octaves) {
+            return pxs_error!("octaves must be between 1 and 16, got: {octaves}.");
+        }
+
+        let noise = Noise::cached(seed);
+        let mut total = 0.0;
+        let mut amplitude: f64 = 1.0;
+        let mut frequency = 1.0;
+        let mut max = 0.0;
+        for _ in 0..octaves {
+            let (fx, fy) = (x * frequency, y * frequency);
+            if !fx.is_finite() || !fy.is_finite() || !amplitude.is_finite() {
+                return pxs_error!("fbm overflowed, lower the coordinates, lacunarity or gain.");
+            }
+            total += kind.sample(&noise, fx, fy) * amplitude;
+            max += amplitude;
+            amplitude *= gain;
+            frequency *= lacunarity;
+        }
+
+        Ok(pxs_Var::new_f64(total / max))
+    })
+}
+
+/// Initialize `pxs_noise` module.
+pub(crate) fn init() {
+    let mut cstrgen = CStringSafe::new();
+
+    let pxs_noise = pxs_newmod(cstrgen.new_string("pxs_noise"));
+    let funcs: [(&str, pxs_Func); 4] = [
+        ("perlin", pxs_noise_perlin),
+        ("simplex", pxs_noise_simplex),
+        ("value", pxs_noise_value),
+        ("fbm", pxs_noise_fbm),
+    ];
+    for (name, func) in funcs {
+        pxs_addfunc(pxs_noise, cstrgen.new_string(name), func);
+    }
+    pxs_addmod(pxs_noise);
+}
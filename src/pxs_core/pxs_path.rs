@@ -0,0 +1,232 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap},
+};
+
+use etffi::cstring::CStringSafe;
+
+use crate::{
+    borrow_var, pxs_addfunc, pxs_addmod, pxs_error, pxs_listget, pxs_listlen, pxs_newmod,
+    pxs_core::{core_call, get_field, get_number},
+    shared::{
+        PxsRes, get_path_provider,
+        var::{pxs_Var, pxs_VarT},
+    },
+};
+use etffi::ptr_magic::PtrMagic;
+
+type Cell = (i32, i32);
+
+/// A node in the open set.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Node {
+    cell: Cell,
+    f: f64,
+}
+
+impl Eq for Node {}
+
+impl Ord for Node {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Min heap on `f`.
+        other.f.partial_cmp(&self.f).unwrap_or(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for Node {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Options for `find`.
+struct PathOptions {
+    /// Allow diagonal moves. Default `false`.
+    diagonal: bool,
+    /// Maximum number of expanded nodes before giving up. Default `100_000`.
+    max_nodes: usize,
+    /// Lowest cost of a walkable cell, scales the heuristic. Default `1.0`.
+    min_cost: f64,
+}
+
+/// Distance times the cheapest step. Cells cheaper than `min_cost` make this overestimate, and the path found may
+/// not be the cheapest one.
+fn heuristic(a: Cell, b: Cell, options: &PathOptions) -> f64 {
+    let dx = (a.0 - b.0).abs() as f64;
+    let dy = (a.1 - b.1).abs() as f64;
+    let distance = if options.diagonal {
+        // Octile distance
+        dx.max(dy) + (std::f64::consts::SQRT_2 - 1.0) * dx.min(dy)
+    } else {
+        dx + dy
+    };
+    distance * options.min_cost
+}
+
+/// Run A* over the grid. `cost` returns a negative number for blocked cells.
+fn astar(
+    width: i32,
+    height: i32,
+    cost: impl Fn(i32, i32) -> f64,
+    from: Cell,
+    to: Cell,
+    options: &PathOptions,
+) -> Option<Vec<Cell>> {
+    let in_bounds = |c: Cell| c.0 >= 0 && c.1 >= 0 && c.0 < width && c.1 < height;
+    if !in_bounds(from) || !in_bounds(to) || cost(to.0, to.1) < 0.0 {
+        return None;
+    }
+
+    let mut open = BinaryHeap::new();
+    let mut came_from: HashMap<Cell, Cell> = HashMap::new();
+    let mut g_score: HashMap<Cell, f64> = HashMap::new();
+
+    g_score.insert(from, 0.0);
+    open.push(Node {
+        cell: from,
+        f: heuristic(from, to, options),
+    });
+
+    const STRAIGHT: [Cell; 4] = [(1, 0), (-1, 0), (0, 1), (0, -1)];
+    const DIAGONAL: [Cell; 4] = [(1, 1), (1, -1), (-1, 1), (-1, -1)];
+
+    let mut expanded = 0;
+    while let Some(Node { cell, f }) = open.pop() {
+        if cell == to {
+            let mut path = vec![cell];
+            let mut current = cell;
+            while let Some(prev) = came_from.get(&current) {
+                current = *prev;
+                path.push(current);
+            }
+            path.reverse();
+            return Some(path);
+        }
+
+        let g = g_score[&cell];
+        // Skip stale entries.
+        if f > g + heuristic(cell, to, options) + 1e-9 {
+            continue;
+        }
+
+        expanded += 1;
+        if expanded > options.max_nodes {
+            return None;
+        }
+
+        let dirs = if options.diagonal {
+            STRAIGHT.iter().chain(DIAGONAL.iter())
+        } else {
+            STRAIGHT.iter().chain([].iter())
+        };
+        for (dx, dy) in dirs {
+            let next = (cell.0 + dx, cell.1 + dy);
+            if !in_bounds(next) {
+                continue;
+            }
+            let step = cost(next.0, next.1);
+            if step < 0.0 {
+                continue;
+            }
+            let is_diagonal = *dx != 0 && *dy != 0;
+            // Do not cut corners.
+            if is_diagonal && (cost(cell.0 + dx, cell.1) < 0.0 || cost(cell.0, cell.1 + dy) < 0.0) {
+                continue;
+            }
+            let distance = if is_diagonal { std::f64::consts::SQRT_2 } else { 1.0 };
+            let tentative = g + step * distance;
+            if tentative < *g_score.get(&next).unwrap_or(&f64::INFINITY) {
+                came_from.insert(next, cell);
+                g_score.insert(next, tentative);
+                open.push(Node {
+                    cell: next,
+                    f: tentative + heuristic(next, to, options),
+                });
+            }
+        }
+    }
+
+    None
+}
+
+/// Read a cell from `{x, y}` or `[x, y]`.
+fn cell_from_var(rt: pxs_VarT, var: &pxs_Var) -> PxsRes<Cell> {
+    if let Some(list) = var.get_list() {
+        return match (list.get_item(0), list.get_item(1)) {
+            (Some(x), Some(y)) => Ok((get_number(x)? as i32, get_number(y)? as i32)),
+            _ => pxs_error!("Point list needs 2 items."),
+        };
+    }
+    match (get_field(rt, var, "x"), get_field(rt, var, "y")) {
+        (Some(x), Some(y)) => Ok((get_number(&x)? as i32, get_number(&y)? as i32)),
+        _ => pxs_error!("Point needs 'x' and 'y'."),
+    }
+}
+
+/// `find(from, to, options?)` returns a list of `{x, y}` points or null if no path exists.
+extern "C" fn pxs_path_find(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 3, 4, |rt| {
+        let (width, height, cost_fn) = match get_path_provider() {
+            Some(provider) => provider,
+            None => return pxs_error!("No path provider set. Call `pxs_path_setprovider` from the host."),
+        };
+
+        let from = cell_from_var(rt, borrow_var!(pxs_listget(args, 1)))?;
+        let to = cell_from_var(rt, borrow_var!(pxs_listget(args, 2)))?;
+        let mut options = PathOptions {
+            diagonal: false,
+            max_nodes: 100_000,
+            min_cost: 1.0,
+        };
+        if pxs_listlen(args) == 4 {
+            let var = borrow_var!(pxs_listget(args, 3));
+            if let Some(diagonal) = get_field(rt, var, "diagonal").filter(|v| !v.is_null()) {
+                options.diagonal = diagonal.get_bool()?;
+            }
+            if let Some(max_nodes) = get_field(rt, var, "max_nodes").filter(|v| !v.is_null()) {
+                options.max_nodes = get_number(&max_nodes)?.max(0.0) as usize;
+            }
+            if let Some(min_cost) = get_field(rt, var, "min_cost").filter(|v| !v.is_null()) {
+                options.min_cost = get_number(&min_cost)?;
+                if !options.min_cost.is_finite() || options.min_cost < 0.0 {
+                    return pxs_error!("min_cost must be a finite number >= 0, got: {}.", options.min_cost);
+                }
+            }
+        }
+
+        let cost = |x: i32, y: i32| unsafe { cost_fn(x, y) };
+        match astar(width, height, cost, from, to, &options) {
+            Some(path) => {
+                let points = path
+                    .into_iter()
+                    .map(|(x, y)| {
+                        let point = pxs_Var::new_map();
+                        let map = point.get_map().unwrap();
+                        map.add_item(pxs_Var::new_string("x".to_string()), pxs_Var::new_i64(x as i64));
+                        map.add_item(pxs_Var::new_string("y".to_string()), pxs_Var::new_i64(y as i64));
+                        point
+                    })
+                    .collect();
+                Ok(pxs_Var::new_list_with(points))
+            }
+            None => Ok(pxs_Var::new_null()),
+        }
+    })
+}
+
+/// Initialize `pxs_path` module.
+pub(crate) fn init() {
+    let mut cstrgen = CStringSafe::new();
+
+    let pxs_path = pxs_newmod(cstrgen.new_string("pxs_path"));
+    pxs_addfunc(pxs_path, cstrgen.new_string("find"), pxs_path_find);
+    pxs_addmod(pxs_path);
+}
@@ -0,0 +1,233 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+//! Seedable random numbers that are the same in every language and on every platform.
+//!
+//! Module functions use a generator per thread (seed 0 until `seed` or `pxs_random_setseed` is called), `new(seed)`
+//! makes a `Random` object with its own stream so i.e. loot rolls do not shift when a particle system draws numbers.
+use std::cell::RefCell;
+
+use etffi::cstring::CStringSafe;
+
+use crate::{
+    borrow_var, own_var, pxs_addfunc, pxs_addmod, pxs_error, pxs_gethost_checked, pxs_listget, pxs_listlen, pxs_newhost,
+    pxs_newmod, pxs_newobject, pxs_object_addfunc,
+    pxs_core::{get_number, splitmix64},
+    shared::{
+        PxsRes, pxs_Opaque,
+        func::pxs_Func,
+        var::{pxs_Var, pxs_VarT},
+    },
+};
+use etffi::ptr_magic::PtrMagic;
+
+/// xoshiro256** seeded with SplitMix64.
+struct Random {
+    state: [u64; 4],
+}
+
+impl PtrMagic for Random {}
+
+impl Random {
+    fn new(seed: i64) -> Self {
+        let mut seed = seed as u64;
+        Random { state: std::array::from_fn(|_| splitmix64(&mut seed)) }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let s = &mut self.state;
+        let res = s[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = s[1] << 17;
+        s[2] ^= s[0];
+        s[3] ^= s[1];
+        s[1] ^= s[2];
+        s[0] ^= s[3];
+        s[2] ^= t;
+        s[3] = s[3].rotate_left(45);
+        res
+    }
+
+    /// `[0, 1)` with 53 random bits.
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 * (1.0 / (1u64 << 53) as f64)
+    }
+
+    /// `[0, n)` without modulo bias.
+    fn below(&mut self, n: u64) -> u64 {
+        let zone = u64::MAX - u64::MAX % n;
+        loop {
+            let val = self.next_u64();
+            if val < zone {
+                return val % n;
+            }
+        }
+    }
+
+    /// `[min, max]`
+    fn int(&mut self, min: i64, max: i64) -> PxsRes<i64> {
+        if min > max {
+            return pxs_error!("Random int min ({min}) is greater than max ({max}).");
+        }
+        let span = max.wrapping_sub(min) as u64;
+        let offset = if span == u64::MAX { self.next_u64() } else { self.below(span + 1) };
+        Ok(min.wrapping_add(offset as i64))
+    }
+}
+
+thread_local! {
+    static DEFAULT: RefCell<Random> = RefCell::new(Random::new(0));
+}
+
+/// Seed the generator of this thread.
+pub(crate) fn seed(seed: i64) {
+    DEFAULT.with(|random| *random.borrow_mut() = Random::new(seed));
+}
+
+/// Run `op` with the generator of a call and the arguments after it. Methods (`method`) use the `Random` object at
+/// `args[1]`, module functions the generator of this thread. `min` and `max` count those arguments.
+fn random_call(
+    args: pxs_VarT,
+    method: bool,
+    min: usize,
+    max: usize,
+    op: impl FnOnce(&mut Random, &[&pxs_Var]) -> PxsRes<pxs_Var>,
+) -> pxs_VarT {
+    let skip = if method { 2 } else { 1 };
+    let len = pxs_listlen(args).max(0) as usize;
+    if len < min + skip || len > max + skip {
+        return pxs_Var::expected_n_args_ep((min + skip) as u32, len as u32).into_raw();
+    }
+    let values: Vec<&pxs_Var> = (skip..len).map(|i| &*borrow_var!(pxs_listget(args, i as i32))).collect();
+    let res = if method {
+        let mut cstrgen = CStringSafe::new();
+        let ptr = pxs_gethost_checked(pxs_listget(args, 0), pxs_listget(args, 1), cstrgen.new_string("Random"));
+        if ptr.is_null() {
+            pxs_error!("Expected a Random.")
+        } else {
+            op(Random::from_borrow_void(ptr), &values)
+        }
+    } else {
+        DEFAULT.with(|random| op(&mut random.borrow_mut(), &values))
+    };
+    match res {
+        Ok(res) => res.into_raw(),
+        Err(e) => pxs_Var::new_exception(e).into_raw(),
+    }
+}
+
+fn list_arg(var: &pxs_Var) -> PxsRes<&[pxs_Var]> {
+    match var.get_list() {
+        Some(list) => Ok(&list.vars),
+        None => pxs_error!("Expected a List, got: {:#?}.", var.tag),
+    }
+}
+
+/// `random()` a float in `[0, 1)`.
+fn op_random(random: &mut Random, _: &[&pxs_Var]) -> PxsRes<pxs_Var> {
+    Ok(pxs_Var::new_f64(random.next_f64()))
+}
+
+/// `int(min, max)` a int in `[min, max]`.
+fn op_int(random: &mut Random, values: &[&pxs_Var]) -> PxsRes<pxs_Var> {
+    let (min, max) = (get_number(values[0])?, get_number(values[1])?);
+    Ok(pxs_Var::new_i64(random.int(min.ceil() as i64, max.floor() as i64)?))
+}
+
+/// `float(min, max)` a float in `[min, max)`.
+fn op_float(random: &mut Random, values: &[&pxs_Var]) -> PxsRes<pxs_Var> {
+    let (min, max) = (get_number(values[0])?, get_number(values[1])?);
+    Ok(pxs_Var::new_f64(min + (max - min) * random.next_f64()))
+}
+
+/// `chance(p)` true with probability `p`.
+fn op_chance(random: &mut Random, values: &[&pxs_Var]) -> PxsRes<pxs_Var> {
+    Ok(pxs_Var::new_bool(random.next_f64() < get_number(values[0])?))
+}
+
+/// `choice(list)` a random item, errors on a empty list.
+fn op_choice(random: &mut Random, values: &[&pxs_Var]) -> PxsRes<pxs_Var> {
+    let items = list_arg(values[0])?;
+    if items.is_empty() {
+        return pxs_error!("Can not choose from a empty List.");
+    }
+    Ok(items[random.below(items.len() as u64) as usize].shallow_copy())
+}
+
+/// `shuffle(list)` a shuffled copy of the list.
+fn op_shuffle(random: &mut Random, values: &[&pxs_Var]) -> PxsRes<pxs_Var> {
+    let mut items: Vec<pxs_Var> = list_arg(values[0])?.iter().map(|item| item.shallow_copy()).collect();
+    // Fisher-Yates
+    for i in (1..items.len()).rev() {
+        let j = random.below(i as u64 + 1) as usize;
+        items.swap(i, j);
+    }
+    Ok(pxs_Var::new_list_with(items))
+}
+
+/// `seed(n)` restart the generator from seed `n`.
+fn op_seed(random: &mut Random, values: &[&pxs_Var]) -> PxsRes<pxs_Var> {
+    *random = Random::new(get_number(values[0])? as i64);
+    Ok(pxs_Var::new_null())
+}
+
+/// Module function and `Random` method of each op.
+macro_rules! random_funcs {
+    ($($name:literal, $func:ident, $method:ident, $op:ident, $min:literal, $max:literal;)*) => {
+        $(
+            extern "C" fn $func(args: pxs_VarT) -> pxs_VarT {
+                random_call(args, false, $min, $max, $op)
+            }
+
+            extern "C" fn $method(args: pxs_VarT) -> pxs_VarT {
+                random_call(args, true, $min, $max, $op)
+            }
+        )*
+
+        const FUNCS: &[(&str, pxs_Func, pxs_Func)] = &[$(($name, $func, $method)),*];
+    };
+}
+
+random_funcs! {
+    "random", pxs_random_random, pxs_random_rng_random, op_random, 0, 0;
+    "int", pxs_random_int, pxs_random_rng_int, op_int, 2, 2;
+    "float", pxs_random_float, pxs_random_rng_float, op_float, 2, 2;
+    "chance", pxs_random_chance, pxs_random_rng_chance, op_chance, 1, 1;
+    "choice", pxs_random_choice, pxs_random_rng_choice, op_choice, 1, 1;
+    "shuffle", pxs_random_shuffle, pxs_random_rng_shuffle, op_shuffle, 1, 1;
+    "seed", pxs_random_seed, pxs_random_rng_seed, op_seed, 1, 1;
+}
+
+/// Random free method.
+unsafe extern "C" fn free_random(ptr: pxs_Opaque) {
+    let _ = Random::from_raw(ptr as *mut Random);
+}
+
+/// `new(seed)` a `Random` with its own stream. It has the module functions as methods.
+extern "C" fn pxs_random_new(args: pxs_VarT) -> pxs_VarT {
+    random_call(args, false, 1, 1, |_, values| {
+        let random = Random::new(get_number(values[0])? as i64);
+        let mut cstrgen = CStringSafe::new();
+        let object = pxs_newobject(random.into_void(), free_random, cstrgen.new_string("Random"));
+        for (name, _, method) in FUNCS {
+            pxs_object_addfunc(object, cstrgen.new_string(name), *method);
+        }
+        Ok(own_var!(pxs_newhost(object)))
+    })
+}
+
+/// Initialize `pxs_random` module.
+pub(crate) fn init() {
+    let mut cstrgen = CStringSafe::new();
+
+    let pxs_random = pxs_newmod(cstrgen.new_string("pxs_random"));
+    for (name, func, _) in FUNCS {
+        pxs_addfunc(pxs_random, cstrgen.new_string(name), *func);
+    }
+    pxs_addfunc(pxs_random, cstrgen.new_string("new"), pxs_random_new);
+    pxs_addmod(pxs_random);
+}
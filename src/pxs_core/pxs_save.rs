@@ -0,0 +1,159 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+use std::{
+    cell::RefCell,
+    collections::{HashMap, VecDeque},
+};
+
+use etffi::cstring::CStringSafe;
+
+use crate::{
+    borrow_var, own_var, pxs_addfunc, pxs_addmod, pxs_error, pxs_listget, pxs_newmod, pxs_varcall,
+    pxs_core::{core_call, get_number},
+    shared::{
+        PxsRes,
+        var::{pxs_Var, pxs_VarT},
+    },
+};
+use etffi::ptr_magic::PtrMagic;
+
+/// A script function that migrates save data from one version to another.
+struct Migration {
+    from: i64,
+    to: i64,
+    /// Runtime the migration was registered in.
+    rt: pxs_Var,
+    /// Owned reference to the script function.
+    func: pxs_Var,
+}
+
+thread_local! {
+    static MIGRATIONS: RefCell<Vec<Migration>> = const { RefCell::new(vec![]) };
+}
+
+/// Find the shortest chain of migrations from `from` to `to`. Returns indices into `migrations`.
+fn find_chain(migrations: &[Migration], from: i64, to: i64) -> Option<Vec<usize>> {
+    let mut came_from: HashMap<i64, usize> = HashMap::new();
+    let mut queue = VecDeque::from([from]);
+    while let Some(version) = queue.pop_front() {
+        if version == to {
+            let mut chain = vec![];
+            let mut current = to;
+            while current != from {
+                let idx = came_from[&current];
+                chain.push(idx);
+                current = migrations[idx].from;
+            }
+            chain.reverse();
+            return Some(chain);
+        }
+        for (idx, migration) in migrations.iter().enumerate() {
+            if migration.from == version && migration.to != from && !came_from.contains_key(&migration.to) {
+                came_from.insert(migration.to, idx);
+                queue.push_back(migration.to);
+            }
+        }
+    }
+    None
+}
+
+/// Run the registered migrations over `data` to bring it from version `from` to version `to`.
+///
+/// Each migration receives the data returned by the previous one.
+pub(crate) fn migrate(data: &pxs_Var, from: i64, to: i64) -> PxsRes<pxs_Var> {
+    // Take the functions out so a migration can register more migrations.
+    let steps: Vec<(i64, i64, pxs_Var, pxs_Var)> = MIGRATIONS.with(|migrations| {
+        let migrations = migrations.borrow();
+        match find_chain(&migrations, from, to) {
+            Some(chain) => Ok(chain
+                .into_iter()
+                .map(|idx| {
+                    let m = &migrations[idx];
+                    (m.from, m.to, m.rt.clone(), m.func.shallow_copy())
+                })
+                .collect()),
+            None => pxs_error!("No save migration path from version {from} to {to}."),
+        }
+    })?;
+
+    // `data` is borrowed, do not take its deleter.
+    let mut current = data.shallow_copy();
+    for (step_from, step_to, rt, func) in steps {
+        let args = pxs_Var::new_list_with(vec![current]);
+        let res = own_var!(pxs_varcall(
+            &rt as *const pxs_Var as pxs_VarT,
+            &func as *const pxs_Var as pxs_VarT,
+            args.into_raw(),
+        ));
+        if res.is_exception() {
+            return pxs_error!("Save migration {step_from} -> {step_to} failed: {}", res.get_string()?);
+        }
+        current = res;
+    }
+
+    Ok(current)
+}
+
+/// Drop all registered migrations on this thread.
+///
+/// Migrations hold references to script functions, so this must be called before the runtime stops.
+pub(crate) fn clear() {
+    MIGRATIONS.with(|migrations| migrations.borrow_mut().clear());
+}
+
+/// `register_migration(from_version, to_version, fn)`
+///
+/// `fn(data)` must return the migrated data. Registering the same versions again replaces the function.
+extern "C" fn pxs_save_register_migration(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 4, 4, |rt| {
+        let from = get_number(borrow_var!(pxs_listget(args, 1)))? as i64;
+        let to = get_number(borrow_var!(pxs_listget(args, 2)))? as i64;
+        if from == to {
+            return pxs_error!("Migration versions must be different, got: {from} -> {to}.");
+        }
+        let func = borrow_var!(pxs_listget(args, 3));
+        if !func.is_function() {
+            return pxs_error!("Migration must be a Function, got: {:#?}.", func.tag);
+        }
+
+        let migration = Migration {
+            from,
+            to,
+            rt: borrow_var!(rt).clone(),
+            // Cloning a function moves the deleter, so the migration keeps it alive.
+            func: func.clone(),
+        };
+        MIGRATIONS.with(|migrations| {
+            let mut migrations = migrations.borrow_mut();
+            migrations.retain(|m| m.from != from || m.to != to);
+            migrations.push(migration);
+        });
+        Ok(pxs_Var::new_null())
+    })
+}
+
+/// `migrate(data, from_version, to_version)` returns the migrated data.
+extern "C" fn pxs_save_migrate(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 4, 4, |_| {
+        let data = borrow_var!(pxs_listget(args, 1));
+        let from = get_number(borrow_var!(pxs_listget(args, 2)))? as i64;
+        let to = get_number(borrow_var!(pxs_listget(args, 3)))? as i64;
+        migrate(data, from, to)
+    })
+}
+
+/// Initialize `pxs_save` module.
+pub(crate) fn init() {
+    let mut cstrgen = CStringSafe::new();
+
+    let pxs_save = pxs_newmod(cstrgen.new_string("pxs_save"));
+    pxs_addfunc(pxs_save, cstrgen.new_string("register_migration"), pxs_save_register_migration);
+    pxs_addfunc(pxs_save, cstrgen.new_string("migrate"), pxs_save_migrate);
+    pxs_addmod(pxs_save);
+}
@@ -0,0 +1,223 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+//! String utilities with the same semantics in every language.
+//!
+//! Lengths, widths and indexes count characters (not bytes like Lua), and values are turned into text the same way
+//! everywhere (`true`, `null`, `2.0`).
+use etffi::cstring::CStringSafe;
+
+use crate::{
+    borrow_var, pxs_addfunc, pxs_addmod, pxs_error, pxs_listget, pxs_listlen, pxs_newmod,
+    pxs_core::{core_call, get_number, to_text},
+    shared::{
+        PxsRes,
+        func::pxs_Func,
+        var::{pxs_Var, pxs_VarT},
+    },
+};
+use etffi::ptr_magic::PtrMagic;
+
+/// Argument at `idx`, None if missing or null.
+fn opt_arg(args: pxs_VarT, idx: i32) -> Option<&'static pxs_Var> {
+    if pxs_listlen(args) <= idx {
+        return None;
+    }
+    let var = borrow_var!(pxs_listget(args, idx));
+    (!var.is_null()).then_some(var)
+}
+
+fn string_arg(args: pxs_VarT, idx: i32) -> PxsRes<String> {
+    borrow_var!(pxs_listget(args, idx)).get_string()
+}
+
+/// Split `val` on `sep` at most `max` times (negative for no limit). No `sep` splits on runs of whitespace and drops
+/// empty parts.
+fn split(val: &str, sep: Option<&str>, max: i64) -> PxsRes<Vec<String>> {
+    let max = if max < 0 { usize::MAX } else { max as usize };
+    let parts = match sep {
+        Some("") => return pxs_error!("Separator can not be empty."),
+        Some(sep) => val.splitn(max.saturating_add(1), sep).map(str::to_string).collect(),
+        None => {
+            let mut parts = vec![];
+            let mut rest = val.trim_start();
+            while !rest.is_empty() {
+                if parts.len() == max {
+                    parts.push(rest.to_string());
+                    break;
+                }
+                let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+                parts.push(rest[..end].to_string());
+                rest = rest[end..].trim_start();
+            }
+            parts
+        }
+    };
+    Ok(parts)
+}
+
+/// Replace `{}` with the next value and `{n}` with value `n`. `{{` and `}}` are literal braces.
+fn format(rt: pxs_VarT, fmt: &str, values: &[&pxs_Var]) -> PxsRes<String> {
+    let mut res = String::with_capacity(fmt.len());
+    let mut next = 0;
+    let mut chars = fmt.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                res.push('{');
+            }
+            '{' => {
+                let mut field = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(fc) => field.push(fc),
+                        None => return pxs_error!("Unclosed '{{' in format string."),
+                    }
+                }
+                let idx = if field.trim().is_empty() {
+                    next += 1;
+                    next - 1
+                } else {
+                    match field.trim().parse::<usize>() {
+                        Ok(idx) => idx,
+                        Err(_) => return pxs_error!("Invalid placeholder '{{{field}}}' in format string."),
+                    }
+                };
+                match values.get(idx) {
+                    Some(value) => res.push_str(&to_text(rt, value)?),
+                    None => return pxs_error!("Format string needs value {idx} but got {} values.", values.len()),
+                }
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                res.push('}');
+            }
+            '}' => return pxs_error!("Single '}}' in format string, use '}}}}'."),
+            _ => res.push(c),
+        }
+    }
+    Ok(res)
+}
+
+/// Pad `val` with `fill` to `width` characters. `align` is where `val` goes: "left", "right" or "center".
+fn pad(val: &str, width: usize, fill: char, align: &str) -> PxsRes<String> {
+    let missing = width.saturating_sub(val.chars().count());
+    let (before, after) = match align {
+        "left" => (0, missing),
+        "right" => (missing, 0),
+        "center" => (missing / 2, missing - missing / 2),
+        other => return pxs_error!("Unknown align: '{other}'. Expected 'left', 'right' or 'center'."),
+    };
+    let fill = |n: usize| std::iter::repeat_n(fill, n).collect::<String>();
+    Ok(format!("{}{val}{}", fill(before), fill(after)))
+}
+
+/// `split(s, sep?, max?)` split a string into a list. No `sep` splits on whitespace, `max` limits the splits.
+extern "C" fn pxs_str_split(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 2, 4, |_| {
+        let val = string_arg(args, 1)?;
+        let sep = opt_arg(args, 2).map(|sep| sep.get_string()).transpose()?;
+        let max = match opt_arg(args, 3) {
+            Some(max) => get_number(max)? as i64,
+            None => -1,
+        };
+        let parts = split(&val, sep.as_deref(), max)?;
+        Ok(pxs_Var::new_list_with(parts.into_iter().map(pxs_Var::new_string).collect()))
+    })
+}
+
+/// `join(list, sep?)` join the text of each item with `sep`.
+extern "C" fn pxs_str_join(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 2, 3, |rt| {
+        let list = borrow_var!(pxs_listget(args, 1));
+        let Some(list) = list.get_list() else {
+            return pxs_error!("Expected a List, got: {:#?}.", list.tag);
+        };
+        let sep = opt_arg(args, 2).map(|sep| sep.get_string()).transpose()?.unwrap_or_default();
+        let parts = list.vars.iter().map(|item| to_text(rt, item)).collect::<PxsRes<Vec<_>>>()?;
+        Ok(pxs_Var::new_string(parts.join(&sep)))
+    })
+}
+
+/// `trim(s, chars?)` remove whitespace, or any of `chars`, from both ends.
+extern "C" fn pxs_str_trim(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 2, 3, |_| {
+        let val = string_arg(args, 1)?;
+        let res = match opt_arg(args, 2) {
+            Some(chars) => {
+                let chars: Vec<char> = chars.get_string()?.chars().collect();
+                val.trim_matches(chars.as_slice()).to_string()
+            }
+            None => val.trim().to_string(),
+        };
+        Ok(pxs_Var::new_string(res))
+    })
+}
+
+/// `starts_with(s, prefix)`
+extern "C" fn pxs_str_starts_with(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 3, 3, |_| Ok(pxs_Var::new_bool(string_arg(args, 1)?.starts_with(&string_arg(args, 2)?))))
+}
+
+/// `ends_with(s, suffix)`
+extern "C" fn pxs_str_ends_with(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 3, 3, |_| Ok(pxs_Var::new_bool(string_arg(args, 1)?.ends_with(&string_arg(args, 2)?))))
+}
+
+/// `format(fmt, ...)` replace `{}`/`{n}` placeholders with the text of the values.
+extern "C" fn pxs_str_format(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 2, usize::MAX, |rt| {
+        let fmt = string_arg(args, 1)?;
+        let values: Vec<&pxs_Var> = (2..pxs_listlen(args)).map(|i| &*borrow_var!(pxs_listget(args, i))).collect();
+        Ok(pxs_Var::new_string(format(rt, &fmt, &values)?))
+    })
+}
+
+/// `pad(s, width, fill?, align?)` pad a string to `width` characters. `fill` defaults to a space and `align` to
+/// "left".
+extern "C" fn pxs_str_pad(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 3, 5, |_| {
+        let val = string_arg(args, 1)?;
+        let width = get_number(borrow_var!(pxs_listget(args, 2)))?.max(0.0) as usize;
+        let fill = match opt_arg(args, 3) {
+            Some(fill) => {
+                let fill = fill.get_string()?;
+                let mut chars = fill.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => c,
+                    _ => return pxs_error!("Fill must be a single character, got: '{fill}'."),
+                }
+            }
+            None => ' ',
+        };
+        let align = opt_arg(args, 4).map(|align| align.get_string()).transpose()?;
+        Ok(pxs_Var::new_string(pad(&val, width, fill, align.as_deref().unwrap_or("left"))?))
+    })
+}
+
+/// Initialize `pxs_str` module.
+pub(crate) fn init() {
+    let mut cstrgen = CStringSafe::new();
+
+    let pxs_str = pxs_newmod(cstrgen.new_string("pxs_str"));
+    let funcs: [(&str, pxs_Func); 7] = [
+        ("split", pxs_str_split),
+        ("join", pxs_str_join),
+        ("trim", pxs_str_trim),
+        ("starts_with", pxs_str_starts_with),
+        ("ends_with", pxs_str_ends_with),
+        ("format", pxs_str_format),
+        ("pad", pxs_str_pad),
+    ];
+    for (name, func) in funcs {
+        pxs_addfunc(pxs_str, cstrgen.new_string(name), func);
+    }
+    pxs_addmod(pxs_str);
+}
@@ -0,0 +1,179 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+use etffi::cstring::CStringSafe;
+
+use crate::{
+    borrow_var, own_var, pxs_addfunc, pxs_addmod, pxs_error, pxs_listget, pxs_listlen, pxs_newmod,
+    pxs_core::{get_field, get_pairs}, pxs_tostring,
+    shared::{
+        PxsRes, PxsResult,
+        var::{pxs_Var, pxs_VarT, pxs_VarType},
+    },
+};
+use etffi::ptr_magic::PtrMagic;
+
+/// How values are escaped before being placed in the template.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Escape {
+    None,
+    Html,
+}
+
+impl Escape {
+    fn from_var(var: &pxs_Var) -> PxsRes<Self> {
+        if var.is_null() {
+            return Ok(Escape::None);
+        }
+        match var.get_string()?.as_str() {
+            "none" | "" => Ok(Escape::None),
+            "html" => Ok(Escape::Html),
+            other => pxs_error!("Unknown escape mode: '{other}'. Expected 'none' or 'html'."),
+        }
+    }
+
+    fn apply(&self, value: &str) -> String {
+        match self {
+            Escape::None => value.to_string(),
+            Escape::Html => {
+                let mut res = String::with_capacity(value.len());
+                for c in value.chars() {
+                    match c {
+                        '&' => res.push_str("&amp;"),
+                        '<' => res.push_str("&lt;"),
+                        '>' => res.push_str("&gt;"),
+                        '"' => res.push_str("&quot;"),
+                        '\'' => res.push_str("&#39;"),
+                        _ => res.push(c),
+                    }
+                }
+                res
+            }
+        }
+    }
+}
+
+/// Get a single key from a `pxs_Map` or a language object.
+fn get_key(rt: pxs_VarT, data: &pxs_Var, key: &str) -> PxsResult {
+    if !data.is_map() && !data.is_object() && !data.is_host_object() {
+        return pxs_error!("Can not look up '{key}' in a {:#?}.", data.tag);
+    }
+    match get_field(rt, data, key) {
+        Some(v) if !v.is_null() || has_key(rt, data, key)? => Ok(v),
+        _ => pxs_error!("Template key '{key}' not found."),
+    }
+}
+
+/// Check that a key which came back null is set. Lua tables and JS objects give null for missing keys too, so their
+/// pairs are searched. Maps, Python dicts and objects that can not be iterated only return keys that exist.
+fn has_key(rt: pxs_VarT, data: &pxs_Var, key: &str) -> PxsRes<bool> {
+    if data.is_map() {
+        return Ok(true);
+    }
+    match get_pairs(rt, data)? {
+        Some(pairs) => Ok(pairs.iter().any(|(k, _)| k.is_string() && k.get_string().is_ok_and(|k| k == key))),
+        None => Ok(true),
+    }
+}
+
+/// Resolve a dotted path (`player.name`) into a string.
+fn resolve(rt: pxs_VarT, data: &pxs_Var, path: &str) -> PxsRes<String> {
+    let mut keys = path.split('.');
+    // Split always returns at least one item.
+    let mut current = get_key(rt, data, keys.next().unwrap().trim())?;
+    for key in keys {
+        current = get_key(rt, &current, key.trim())?;
+    }
+
+    let string = own_var!(pxs_tostring(rt, &current as *const pxs_Var as pxs_VarT));
+    if string.is_exception() {
+        return pxs_error!("{}", string.get_string()?);
+    }
+    string.get_string()
+}
+
+/// Render a template.
+///
+/// `{path}` is replaced by the value at `path` and `{{`/`}}` are literal braces.
+fn render(rt: pxs_VarT, template: &str, data: &pxs_Var, escape: Escape) -> PxsRes<String> {
+    let mut res = String::with_capacity(template.len());
+    let mut chars = template.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' => {
+                if chars.peek() == Some(&'{') {
+                    chars.next();
+                    res.push('{');
+                    continue;
+                }
+                let mut path = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(pc) => path.push(pc),
+                        None => return pxs_error!("Unclosed '{{' in template."),
+                    }
+                }
+                if path.trim().is_empty() {
+                    return pxs_error!("Empty placeholder in template.");
+                }
+                res.push_str(&escape.apply(&resolve(rt, data, &path)?));
+            }
+            '}' => {
+                if chars.peek() == Some(&'}') {
+                    chars.next();
+                }
+                res.push('}');
+            }
+            _ => res.push(c),
+        }
+    }
+
+    Ok(res)
+}
+
+/// Render a template string against a `pxs_Map` or object.
+///
+/// args: (rt, template, data, escape?)
+pub(crate) extern "C" fn pxs_template_render(args: pxs_VarT) -> pxs_VarT {
+    let len = pxs_listlen(args);
+    if len != 3 && len != 4 {
+        return pxs_Var::expected_n_args_ep(3, len as u32).into_raw();
+    }
+
+    let rt = pxs_listget(args, 0);
+    let template = borrow_var!(pxs_listget(args, 1));
+    if !template.is_string() {
+        return pxs_Var::incorrect_type_ep(pxs_VarType::pxs_String, template.tag).into_raw();
+    }
+    let data = borrow_var!(pxs_listget(args, 2));
+
+    let escape = if len == 4 {
+        match Escape::from_var(borrow_var!(pxs_listget(args, 3))) {
+            Ok(e) => e,
+            Err(e) => return pxs_Var::new_exception(e).into_raw(),
+        }
+    } else {
+        Escape::None
+    };
+
+    match render(rt, &template.get_string().unwrap(), data, escape) {
+        Ok(res) => pxs_Var::new_string(res).into_raw(),
+        Err(e) => pxs_Var::new_exception(e).into_raw(),
+    }
+}
+
+/// Initialize `pxs_template` module.
+pub(crate) fn init() {
+    let mut cstrgen = CStringSafe::new();
+
+    let pxs_template = pxs_newmod(cstrgen.new_string("pxs_template"));
+    pxs_addfunc(pxs_template, cstrgen.new_string("render"), pxs_template_render);
+    pxs_addmod(pxs_template);
+}
@@ -0,0 +1,226 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+//! Wall clock, monotonic clock, sleep and UTC dates for scripts.
+//!
+//! The host can replace the system clock with `pxs_set_clock` (i.e. for deterministic replays), `now` and `monotonic`
+//! then both read it. `sleep` is off until the host allows it with `pxs_time_allowsleep`.
+use std::{
+    sync::LazyLock,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use etffi::cstring::CStringSafe;
+
+use crate::{
+    borrow_var, pxs_addfunc, pxs_addmod, pxs_error, pxs_listget, pxs_listlen, pxs_newmod,
+    pxs_core::{core_call, get_number},
+    shared::{
+        PxsRes, get_allow_sleep, get_time_source,
+        func::pxs_Func,
+        var::{pxs_Var, pxs_VarT},
+    },
+};
+use etffi::ptr_magic::PtrMagic;
+
+/// Start of the system monotonic clock.
+static START: LazyLock<Instant> = LazyLock::new(Instant::now);
+
+const WEEKDAYS: [&str; 7] = ["Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday", "Sunday"];
+const MONTHS: [&str; 12] = [
+    "January", "February", "March", "April", "May", "June", "July", "August", "September", "October", "November",
+    "December",
+];
+
+/// Seconds since the Unix epoch.
+fn now() -> f64 {
+    match get_time_source() {
+        Some(source) => unsafe { source() },
+        None => SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs_f64()).unwrap_or(0.0),
+    }
+}
+
+/// Seconds since a unspecified start, never goes back (unless the host source does).
+fn monotonic() -> f64 {
+    match get_time_source() {
+        Some(source) => unsafe { source() },
+        None => START.elapsed().as_secs_f64(),
+    }
+}
+
+/// A UTC date.
+struct Date {
+    year: i64,
+    /// 1 to 12
+    month: u32,
+    /// 1 to 31
+    day: u32,
+    hour: u32,
+    minute: u32,
+    second: u32,
+    /// 1 (Monday) to 7 (Sunday), like ISO 8601.
+    weekday: u32,
+    /// 1 to 366
+    yday: u32,
+}
+
+impl Date {
+    /// Date of a Unix timestamp. Uses the civil from days algorithm, so it works for any year.
+    fn from_timestamp(t: f64) -> PxsRes<Self> {
+        if !t.is_finite() {
+            return pxs_error!("Invalid timestamp: {t}.");
+        }
+        let secs = t.floor() as i64;
+        let days = secs.div_euclid(86400);
+        let rem = secs.rem_euclid(86400) as u32;
+
+        let z = days + 719468;
+        let era = z.div_euclid(146097);
+        let doe = z.rem_euclid(146097);
+        let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+        let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+        let leap = (year % 4 == 0 && year % 100 != 0) || year % 400 == 0;
+        let before: [u32; 12] = [0, 31, 59, 90, 120, 151, 181, 212, 243, 273, 304, 334];
+        let yday = before[month as usize - 1] + day + if leap && month > 2 { 1 } else { 0 };
+
+        Ok(Date {
+            year,
+            month,
+            day,
+            hour: rem / 3600,
+            minute: rem % 3600 / 60,
+            second: rem % 60,
+            // 1970-01-01 was a Thursday.
+            weekday: ((days + 3).rem_euclid(7) + 1) as u32,
+            yday,
+        })
+    }
+
+    fn to_map(&self) -> pxs_Var {
+        let res = pxs_Var::new_map();
+        let map = res.get_map().unwrap();
+        let fields = [
+            ("year", self.year),
+            ("month", self.month as i64),
+            ("day", self.day as i64),
+            ("hour", self.hour as i64),
+            ("minute", self.minute as i64),
+            ("second", self.second as i64),
+            ("weekday", self.weekday as i64),
+            ("yday", self.yday as i64),
+        ];
+        for (key, value) in fields {
+            map.add_item(pxs_Var::new_string(key.to_string()), pxs_Var::new_i64(value));
+        }
+        res
+    }
+
+    /// strftime like formatting.
+    fn format(&self, fmt: &str) -> PxsRes<String> {
+        let mut res = String::with_capacity(fmt.len());
+        let mut chars = fmt.chars();
+        while let Some(c) = chars.next() {
+            if c != '%' {
+                res.push(c);
+                continue;
+            }
+            match chars.next() {
+                Some('Y') => res.push_str(&self.year.to_string()),
+                Some('y') => res.push_str(&format!("{:02}", self.year.rem_euclid(100))),
+                Some('m') => res.push_str(&format!("{:02}", self.month)),
+                Some('d') => res.push_str(&format!("{:02}", self.day)),
+                Some('H') => res.push_str(&format!("{:02}", self.hour)),
+                Some('M') => res.push_str(&format!("{:02}", self.minute)),
+                Some('S') => res.push_str(&format!("{:02}", self.second)),
+                Some('j') => res.push_str(&format!("{:03}", self.yday)),
+                Some('A') => res.push_str(WEEKDAYS[self.weekday as usize - 1]),
+                Some('a') => res.push_str(&WEEKDAYS[self.weekday as usize - 1][..3]),
+                Some('B') => res.push_str(MONTHS[self.month as usize - 1]),
+                Some('b') => res.push_str(&MONTHS[self.month as usize - 1][..3]),
+                Some('%') => res.push('%'),
+                Some(other) => return pxs_error!("Unknown date format '%{other}'."),
+                None => return pxs_error!("Date format ends with a single '%'."),
+            }
+        }
+        Ok(res)
+    }
+}
+
+/// Timestamp argument at `idx`, now if missing or null.
+fn timestamp_arg(args: pxs_VarT, idx: i32) -> PxsRes<f64> {
+    if pxs_listlen(args) <= idx {
+        return Ok(now());
+    }
+    let t = borrow_var!(pxs_listget(args, idx));
+    if t.is_null() {
+        return Ok(now());
+    }
+    get_number(t)
+}
+
+/// `now()` seconds since the Unix epoch.
+extern "C" fn pxs_time_now(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 1, 1, |_| Ok(pxs_Var::new_f64(now())))
+}
+
+/// `monotonic()` seconds of a clock that never goes back, for measuring durations.
+extern "C" fn pxs_time_monotonic(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 1, 1, |_| Ok(pxs_Var::new_f64(monotonic())))
+}
+
+/// `sleep(seconds)` block the thread. Only if the host allows it.
+extern "C" fn pxs_time_sleep(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 2, 2, |_| {
+        if !get_allow_sleep() {
+            return pxs_error!("Sleeping is disabled. Call `pxs_time_allowsleep` from the host.");
+        }
+        let seconds = get_number(borrow_var!(pxs_listget(args, 1)))?;
+        if !seconds.is_finite() || seconds < 0.0 {
+            return pxs_error!("Can not sleep for {seconds} seconds.");
+        }
+        std::thread::sleep(Duration::from_secs_f64(seconds));
+        Ok(pxs_Var::new_null())
+    })
+}
+
+/// `date(t?)` the UTC date of a timestamp as a map.
+extern "C" fn pxs_time_date(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 1, 2, |_| Ok(Date::from_timestamp(timestamp_arg(args, 1)?)?.to_map()))
+}
+
+/// `format(fmt, t?)` format the UTC date of a timestamp.
+extern "C" fn pxs_time_format(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 2, 3, |_| {
+        let fmt = borrow_var!(pxs_listget(args, 1)).get_string()?;
+        let date = Date::from_timestamp(timestamp_arg(args, 2)?)?;
+        Ok(pxs_Var::new_string(date.format(&fmt)?))
+    })
+}
+
+/// Initialize `pxs_time` module.
+pub(crate) fn init() {
+    let mut cstrgen = CStringSafe::new();
+
+    let pxs_time = pxs_newmod(cstrgen.new_string("pxs_time"));
+    let funcs: [(&str, pxs_Func); 5] = [
+        ("now", pxs_time_now),
+        ("monotonic", pxs_time_monotonic),
+        ("sleep", pxs_time_sleep),
+        ("date", pxs_time_date),
+        ("format", pxs_time_format),
+    ];
+    for (name, func) in funcs {
+        pxs_addfunc(pxs_time, cstrgen.new_string(name), func);
+    }
+    pxs_addmod(pxs_time);
+}
@@ -0,0 +1,192 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+//! Poll based data-binding between script values and host widgets.
+//!
+//! Script maps and objects can not be watched for writes in every language (i.e. Python dicts), so bindings are
+//! polled. The host calls `pxs_ui_poll` (once per frame) and every binding whose value changed since the last
+//! notification is sent to the `pxs_ui_setcallback` callback. `set()` writes and polls right away.
+use std::cell::RefCell;
+
+use etffi::cstring::CStringSafe;
+
+use crate::{
+    borrow_var, pxs_addfunc, pxs_addmod, pxs_error, pxs_listget, pxs_newmod,
+    pxs_core::{core_call, get_field, get_number, set_field},
+    shared::{
+        fields::to_map,
+        get_ui_change,
+        var::{pxs_Var, pxs_VarT, pxs_VarType},
+    },
+};
+use etffi::ptr_magic::PtrMagic;
+
+/// A binding between a widget and a key of a script map/object.
+struct Binding {
+    id: i64,
+    /// Runtime the binding was created in.
+    rt: pxs_Var,
+    widget_id: String,
+    /// Owned reference to the script object.
+    target: pxs_Var,
+    key: String,
+    /// Snapshot of the last value sent to the host.
+    last: Option<pxs_Var>,
+}
+
+thread_local! {
+    static BINDINGS: RefCell<Vec<Binding>> = const { RefCell::new(vec![]) };
+    static NEXT_ID: RefCell<i64> = const { RefCell::new(1) };
+}
+
+/// Deep compare snapshots. Lists and maps are compared by their items, host objects by handle. Values that can not
+/// be copied (functions, class instances) always count as changed.
+fn same_value(a: &pxs_Var, b: &pxs_Var) -> bool {
+    match (&a.tag, &b.tag) {
+        (pxs_VarType::pxs_Null, pxs_VarType::pxs_Null) => true,
+        (pxs_VarType::pxs_HostObject, pxs_VarType::pxs_HostObject) => unsafe {
+            a.value.host_object_val == b.value.host_object_val
+        },
+        (pxs_VarType::pxs_List, pxs_VarType::pxs_List) => {
+            let (a, b) = (&a.get_list().unwrap().vars, &b.get_list().unwrap().vars);
+            a.len() == b.len() && a.iter().zip(b.iter()).all(|(a, b)| same_value(a, b))
+        }
+        (pxs_VarType::pxs_Map, pxs_VarType::pxs_Map) => {
+            let (a, b) = (a.get_map().unwrap(), b.get_map().unwrap());
+            a.len() == b.len()
+                && a.keys().into_iter().all(|key| match (a.get_item(key), b.get_item(key)) {
+                    (Some(a), Some(b)) => same_value(a, b),
+                    _ => false,
+                })
+        }
+        _ => a == b,
+    }
+}
+
+/// Check every binding on this thread and notify the host of changed values.
+///
+/// Returns the number of notifications sent.
+pub(crate) fn poll() -> usize {
+    let cbk = match get_ui_change() {
+        Some(cbk) => cbk,
+        None => return 0,
+    };
+
+    // Collect first so the host callback can safely call back into `pxs_ui`.
+    let changes: Vec<(String, pxs_Var)> = BINDINGS.with(|bindings| {
+        let mut bindings = bindings.borrow_mut();
+        let mut changes = vec![];
+        for binding in bindings.iter_mut() {
+            let rt = &binding.rt as *const pxs_Var as pxs_VarT;
+            let value = get_field(rt, &binding.target, &binding.key).unwrap_or_else(pxs_Var::new_null);
+            // Copy language objects into plain maps so later writes to them show up as changes.
+            let value = match to_map(rt, &value, 0) {
+                Ok(snapshot) => snapshot,
+                Err(_) => value,
+            };
+            let changed = match &binding.last {
+                Some(last) => !same_value(last, &value),
+                None => true,
+            };
+            if changed {
+                binding.last = Some(value.shallow_copy());
+                changes.push((binding.widget_id.clone(), value));
+            }
+        }
+        changes
+    });
+
+    let mut cstrgen = CStringSafe::new();
+    for (widget_id, value) in changes.iter() {
+        unsafe { cbk(cstrgen.new_string(widget_id), value as *const pxs_Var as pxs_VarT) };
+    }
+
+    changes.len()
+}
+
+/// Drop all bindings on this thread.
+///
+/// Bindings hold references to script objects, so this must be called before the runtime stops.
+pub(crate) fn clear() {
+    BINDINGS.with(|bindings| bindings.borrow_mut().clear());
+}
+
+/// `bind(widget_id, target, key) -> binding id`
+extern "C" fn pxs_ui_bind(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 4, 4, |rt| {
+        let widget_id = borrow_var!(pxs_listget(args, 1)).get_string()?;
+        let target = borrow_var!(pxs_listget(args, 2));
+        if !target.is_map() && !target.is_object() && !target.is_host_object() {
+            return pxs_error!("Can only bind to a Map or Object, got: {:#?}.", target.tag);
+        }
+        let key = borrow_var!(pxs_listget(args, 3)).get_string()?;
+
+        let id = NEXT_ID.with(|next| {
+            let mut next = next.borrow_mut();
+            *next += 1;
+            *next - 1
+        });
+        BINDINGS.with(|bindings| {
+            bindings.borrow_mut().push(Binding {
+                id,
+                rt: borrow_var!(rt).clone(),
+                widget_id,
+                // Cloning a object moves the deleter, so the binding keeps it alive.
+                target: target.clone(),
+                key,
+                last: None,
+            })
+        });
+
+        // Send the initial value.
+        poll();
+        Ok(pxs_Var::new_i64(id))
+    })
+}
+
+/// `unbind(id)`
+extern "C" fn pxs_ui_unbind(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 2, 2, |_| {
+        let id = get_number(borrow_var!(pxs_listget(args, 1)))? as i64;
+        let removed = BINDINGS.with(|bindings| {
+            let mut bindings = bindings.borrow_mut();
+            let len = bindings.len();
+            bindings.retain(|b| b.id != id);
+            len != bindings.len()
+        });
+        Ok(pxs_Var::new_bool(removed))
+    })
+}
+
+/// `set(target, key, value)` sets the value and notifies bound widgets.
+extern "C" fn pxs_ui_set(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 4, 4, |rt| {
+        let target = borrow_var!(pxs_listget(args, 1));
+        let key = borrow_var!(pxs_listget(args, 2)).get_string()?;
+        set_field(rt, target, &key, borrow_var!(pxs_listget(args, 3)))?;
+        poll();
+        Ok(pxs_Var::new_null())
+    })
+}
+
+/// `poll() -> number of notifications`
+extern "C" fn pxs_ui_poll(args: pxs_VarT) -> pxs_VarT {
+    core_call(args, 1, 1, |_| Ok(pxs_Var::new_i64(poll() as i64)))
+}
+
+/// Initialize `pxs_ui` module.
+pub(crate) fn init() {
+    let mut cstrgen = CStringSafe::new();
+
+    let pxs_ui = pxs_newmod(cstrgen.new_string("pxs_ui"));
+    pxs_addfunc(pxs_ui, cstrgen.new_string("bind"), pxs_ui_bind);
+    pxs_addfunc(pxs_ui, cstrgen.new_string("unbind"), pxs_ui_unbind);
+    pxs_addfunc(pxs_ui, cstrgen.new_string("set"), pxs_ui_set);
+    pxs_addfunc(pxs_ui, cstrgen.new_string("poll"), pxs_ui_poll);
+    pxs_addmod(pxs_ui);
+}
@@ -0,0 +1,57 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+use etffi::cstring::CStringSafe;
+
+use crate::{
+    borrow_var, pxs_addfunc, pxs_addmod, pxs_listget, pxs_newmod,
+    pxs_core::{core_call, get_number},
+    shared::{func::pxs_Func, var::{pxs_Var, pxs_VarT}},
+};
+use etffi::ptr_magic::PtrMagic;
+
+/// Convert the first argument with `f`.
+fn convert(args: pxs_VarT, f: fn(f64) -> f64) -> pxs_VarT {
+    core_call(args, 2, 2, |_| {
+        let value = get_number(borrow_var!(pxs_listget(args, 1)))?;
+        Ok(pxs_Var::new_f64(f(value)))
+    })
+}
+
+extern "C" fn pxs_units_deg_to_rad(args: pxs_VarT) -> pxs_VarT {
+    convert(args, f64::to_radians)
+}
+
+extern "C" fn pxs_units_rad_to_deg(args: pxs_VarT) -> pxs_VarT {
+    convert(args, f64::to_degrees)
+}
+
+extern "C" fn pxs_units_ms_to_s(args: pxs_VarT) -> pxs_VarT {
+    convert(args, |v| v / 1000.0)
+}
+
+extern "C" fn pxs_units_s_to_ms(args: pxs_VarT) -> pxs_VarT {
+    convert(args, |v| v * 1000.0)
+}
+
+/// Initialize `pxs_units` module.
+pub(crate) fn init() {
+    let mut cstrgen = CStringSafe::new();
+
+    let pxs_units = pxs_newmod(cstrgen.new_string("pxs_units"));
+    let funcs: [(&str, pxs_Func); 4] = [
+        ("deg_to_rad", pxs_units_deg_to_rad),
+        ("rad_to_deg", pxs_units_rad_to_deg),
+        ("ms_to_s", pxs_units_ms_to_s),
+        ("s_to_ms", pxs_units_s_to_ms),
+    ];
+    for (name, func) in funcs {
+        pxs_addfunc(pxs_units, cstrgen.new_string(name), func);
+    }
+    pxs_addmod(pxs_units);
+}
@@ -7,8 +7,9 @@
 // Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
 //
 use crate::{
-    borrow_string, create_raw_string, free_raw_string, pxs_debug, python::{consume_error, pocketpy, var::pocketpyref_to_var, var_to_pocketpyref}, shared::{func::call_function, pxs_Runtime, var::pxs_Var}
+    borrow_string, create_raw_string, free_raw_string, pxs_debug, python::{consume_error, pocketpy, var::{adopt_object, finalize_object, pocketpyref_to_var}, var_to_pocketpyref}, shared::{PXS_PTR_NAME, func::{call_function, check_args}, pxs_Runtime, var::{pxs_Var, pxs_VarMap}}
 };
+use std::ffi::c_void;
 
 /// Use instead of the py_arg macro.
 pub(super) unsafe fn py_get_arg(argv: pocketpy::py_StackRef, i: usize) -> pocketpy::py_StackRef {
@@ -106,25 +107,30 @@ pub(super) unsafe fn raise(msg: &str) -> bool {
     }
 }
 
-/// The pocketpy bridge
+/// Add a `key`, `val` pair of a kwargs dict to the `pxs_VarMap` in `ctx`.
+unsafe extern "C" fn add_kwarg(key: pocketpy::py_Ref, val: pocketpy::py_Ref, ctx: *mut c_void) -> bool {
+    let map = unsafe { &mut *(ctx as *mut pxs_VarMap) };
+    map.add_item(pocketpyref_to_var(key), pocketpyref_to_var(val));
+    true
+}
+
+/// The pocketpy bridge, called as `_pxs_call(idx, kwargs, *args)`.
+///
+/// Keyword arguments are passed to the host function as a map after the positional arguments, only if there are any.
 pub(super) unsafe extern "C" fn pocketpy_bridge(argc: i32, argv: pocketpy::py_StackRef) -> bool {
-    // Future Self: Why do I do argc < 1 ? Weird...
-    // As for why... it's because we always require the function `idx` as the first argument. And if it is not
-    // there then someone is hacking.
-    if argc < 1 {
+    // Future Self: Why do I do argc < 2 ? Weird...
+    // As for why... it's because we always require the function `idx` and the kwargs as the first arguments. And if
+    // they are not there then someone is hacking.
+    if argc < 2 {
         unsafe {
-            return raise("Python: argc < 1");
+            return raise("Python: argc < 2");
         }
     }
     // let c_name = unsafe { pocketpy::py_tostr(py_get_arg(argv, 0)) };
     // let name = borrow_string!(c_name);
     // let fn_idx = get_fn_idx_from_name(name);
+    // Negative idxs are host pinned functions.
     let fn_idx = unsafe { pocketpy::py_toint(py_get_arg(argv, 0)) };
-    if fn_idx < 0 {
-        unsafe {
-            return raise("Python: fn_idx is < 0");
-        }
-    }
 
     // Convert argv into Vec<Var>
     let mut vars: Vec<pxs_Var> = vec![];
@@ -133,11 +139,36 @@ pub(super) unsafe extern "C" fn pocketpy_bridge(argc: i32, argv: pocketpy::py_St
     vars.push(pxs_Var::new_i64(pxs_Runtime::pxs_Python as i64));
 
     // Convert py_Ref into pxs_Var.
-    for i in 1..argc {
+    for i in 2..argc {
         let arg_ref = unsafe { py_get_arg(argv, i as usize) };
         vars.push(pocketpyref_to_var(arg_ref));
     }
 
+    // Keyword arguments
+    unsafe {
+        let kwargs = py_get_arg(argv, 1);
+        if pocketpy::py_istype(kwargs, pocketpy::py_PredefinedType::tp_dict as pocketpy::py_Type)
+            && pocketpy::py_dict_len(kwargs) > 0
+        {
+            let map = pxs_Var::new_map();
+            let ctx = map.get_map().unwrap() as *mut pxs_VarMap as *mut c_void;
+            if !pocketpy::py_dict_apply(kwargs, Some(add_kwarg), ctx) {
+                return false;
+            }
+            vars.push(map);
+        }
+    }
+
+    // Arguments not matching the signature are a TypeError, not a plain exception.
+    if let Err(msg) = check_args(fn_idx as i32, &mut vars) {
+        let c_msg = create_raw_string!(msg);
+        unsafe {
+            pocketpy::py_exception(pocketpy::py_PredefinedType::tp_TypeError as pocketpy::py_Type, c"%s".as_ptr(), c_msg);
+            free_raw_string!(c_msg);
+        }
+        return false;
+    }
+
     // Call internal function
     let mut success = true;
     unsafe {
@@ -157,3 +188,50 @@ pub(super) unsafe extern "C" fn pocketpy_bridge(argc: i32, argv: pocketpy::py_St
 
     success
 }
+
+/// `_pxs_adopt(obj)`, called when a script subclass of a host type is created. `obj` becomes the language object of
+/// its host object (`obj._pxs_ptr`), so the host calls the methods of the subclass.
+pub(super) unsafe extern "C" fn pocketpy_adopt(argc: i32, argv: pocketpy::py_StackRef) -> bool {
+    if argc != 1 {
+        unsafe {
+            return raise("Python: _pxs_adopt expects 1 argument");
+        }
+    }
+
+    unsafe {
+        let obj = py_get_arg(argv, 0);
+        let Some(ptr) = get_from_obj(obj, PXS_PTR_NAME) else {
+            return raise("Python: object has no _pxs_ptr");
+        };
+        let idx = pocketpy::py_toint(ptr) as i32;
+        if !adopt_object(idx, obj) {
+            return raise("Python: object is not a host object");
+        }
+        pocketpy::py_newnone(pocketpy::py_retval());
+    }
+
+    true
+}
+
+/// `_pxs_object.__new__(cls, ptr)`, the base type of the bridge classes. The instance keeps the object idx (`ptr`), so
+/// its destructor can release the host object.
+pub(super) unsafe extern "C" fn pocketpy_object_new(argc: i32, argv: pocketpy::py_StackRef) -> bool {
+    unsafe {
+        let cls = pocketpy::py_totype(py_get_arg(argv, 0));
+        let idx = if argc > 1 && pocketpy::py_istype(py_get_arg(argv, 1), pocketpy::py_PredefinedType::tp_int as pocketpy::py_Type) {
+            pocketpy::py_toint(py_get_arg(argv, 1)) as i32
+        } else {
+            -1
+        };
+        let ud = pocketpy::py_newobject(pocketpy::py_retval(), cls, -1, std::mem::size_of::<i32>() as i32) as *mut i32;
+        *ud = idx;
+    }
+
+    true
+}
+
+/// Destructor of `_pxs_object`, runs while the GC collects a instance.
+pub(super) unsafe extern "C" fn pocketpy_object_dtor(ud: *mut std::ffi::c_void) {
+    let idx = unsafe { *(ud as *mut i32) };
+    finalize_object(idx, ud);
+}
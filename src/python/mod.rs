@@ -7,18 +7,18 @@
 // Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
 //
 use std::{
-    cell::Cell, collections::{HashMap, HashSet}, sync::LazyLock
+    cell::Cell, collections::HashMap, sync::LazyLock
 };
 
 use etffi::{borrow_string, create_raw_string, cstring::CStringSafe, free_raw_string, ptr_magic::{PtrMagic, ThreadSafePointer}};
 
 use crate::{
     pxs_debug, pxs_error, python::{
-        func::{get_builtin, pocketpy_bridge, py_assign},
-        module::create_module,
+        func::{get_builtin, pocketpy_bridge, pocketpy_object_dtor, pocketpy_object_new, py_assign},
+        module::{create_module, remove_module},
         var::{PythonPointer, pocketpyref_to_var, var_to_pocketpyref},
     }, shared::{
-        PixelScript, PxsRes, PxsResult, pxs_Opaque, read_file, read_file_dir, var::{ObjectMethods, pxs_Var, pxs_VarList}
+        PixelScript, PxsRes, PxsResult, internal_chunk_name, limit, pxs_Opaque, pxs_Runtime, read_file, read_file_dir, resolve_module, var::{ObjectMethods, pxs_Var, pxs_VarList}
     }, with_feature
 };
 
@@ -68,13 +68,24 @@ thread_local! {
 
 /// _pxs_call
 const PXS_CALL_METHOD: &str = "_pxs_call";
+/// _pxs_adopt
+const PXS_ADOPT_METHOD: &str = "_pxs_adopt";
+/// _pxs_object, the base type of bridge classes. Defined in `builtins`.
+const PXS_OBJECT_TYPE: &str = "_pxs_object";
 /// __main__
 const PYTHON_MAIN_MODULE: &str = "__main__";
 
+/// A PixelObject class defined in a module.
+#[derive(Debug)]
+struct DefinedObject {
+    module_name: String,
+    type_name: String,
+}
+
 /// This is the Pocketpy state. Each language gets it's own private state
 struct State {
-    /// Keep a list of defined PixelObject as class
-    defined_objects: HashMap<i32, HashSet<String>>,
+    /// Keep a list of defined PixelObject as class, by thread and class name.
+    defined_objects: HashMap<i32, HashMap<String, DefinedObject>>,
     /// Thread pool 0-15
     thread_pool: Vec<ThreadStatus>
 }
@@ -194,25 +205,18 @@ pub(self) fn exec_main_py(code: &str, name: &str) -> String {
     run_py(code, name, pocketpy::py_CompileMode::EXEC_MODE, None)
 }
 
-/// Create a new module and load it with code.
-unsafe fn new_module(code: &str, name: &str) {
-    let cname = create_raw_string!(name);
-    let module = unsafe { pocketpy::py_getmodule(cname) };
-    if !module.is_null() {
-        pxs_debug!("PYTHON module: {name} already exists.");
-        return;
-    }
-
-    let _ = unsafe { pocketpy::py_newmodule(cname) };
-
-    // Exec the code
-    let err = exec_py(code, format!("<{}>", name).as_str(), name);
-    if !err.is_empty() {
-        panic!("Setting new module error: {err}");
-    }
-
+/// Does `code` compile as a expression?
+pub(self) fn is_expression(code: &str) -> bool {
+    let source = create_raw_string!(code);
+    let file_name = create_raw_string!("<expression>");
     unsafe {
-        free_raw_string!(cname);
+        let ok = pocketpy::py_compile(source, file_name, pocketpy::py_CompileMode::EVAL_MODE, true);
+        free_raw_string!(source);
+        free_raw_string!(file_name);
+        if !ok {
+            let _ = consume_error();
+        }
+        ok
     }
 }
 
@@ -222,16 +226,38 @@ pub(self) fn get_py_state() -> *mut State {
 }
 
 /// Add a new defined object
-pub(self) fn add_new_defined_object(name: &str) {
+pub(self) fn add_new_defined_object(name: &str, module_name: &str, type_name: &str) {
     let state = get_py_state();
     unsafe { 
         let t = get_thread_idx();
-        if let Some(names) = (*state).defined_objects.get_mut(&t) {
-            names.insert(name.to_string());
-        } else {
-            let mut set = HashSet::new();
-            set.insert(name.to_string());
-            (*state).defined_objects.insert(t, set);
+        (*state).defined_objects.entry(t).or_default().insert(
+            name.to_string(),
+            DefinedObject { module_name: module_name.to_string(), type_name: type_name.to_string() },
+        );
+    }
+}
+
+/// Delete the classes of `type_names` from their modules, so objects of a reloaded type get a new class.
+pub(self) fn remove_defined_objects(type_names: &[String]) {
+    let state = get_py_state();
+    let mut cstr_safe = CStringSafe::new();
+    unsafe {
+        let t = get_thread_idx();
+        let Some(defined) = (*state).defined_objects.get_mut(&t) else {
+            return;
+        };
+        let names: Vec<String> = defined
+            .iter()
+            .filter(|(_, object)| type_names.contains(&object.type_name))
+            .map(|(name, _)| name.clone())
+            .collect();
+        for name in names {
+            let object = defined.remove(&name).unwrap();
+            let pymodule = pocketpy::py_getmodule(cstr_safe.new_string(&object.module_name));
+            if !pymodule.is_null() {
+                let py_name = pocketpy::py_name(cstr_safe.new_string(&format!("_{name}")));
+                pocketpy::py_deldict(pymodule, py_name);
+            }
         }
     }
 }
@@ -242,7 +268,7 @@ pub(self) fn is_object_defined(name: &str) -> bool {
     unsafe { 
         let t = get_thread_idx();
         if let Some(set) = (*state).defined_objects.get(&t) {
-            set.contains(name)
+            set.contains_key(name)
         } else {
             false
         }
@@ -262,28 +288,39 @@ unsafe extern "C" fn pxspython_importfile(
 
     // Borrow file_path
     let b = borrow_string!(file_path);
-    // Remove .py and check if this is a directory
-    let file_path = {
-        let pos_dir = &b[0..b.len() - 3];
-        let files = read_file_dir(pos_dir);
-
-        if files.contains(&"__import__.py".to_string()) {
-            // Ok just use that then
-            format!("{pos_dir}__import__.py")
-        } else {
-            // No __import__.py so let's see first if there is any .py so we can return a pseudo type
-            for _ in files.iter() {
-                return pocketpy::PXSPYTHON_IS_DIR; // -2 is a specific thingy to return a empty string in C.
+    // Resolvers get the module name (`a/b.py` is `a.b`), once per module so not for `.pyc` and `__init__.py`.
+    let resolved = b
+        .strip_suffix(".py")
+        .filter(|name| !name.ends_with("__init__"))
+        .and_then(|name| resolve_module(&name.replace('/', "."), pxs_Runtime::pxs_Python));
+    let contents = match resolved {
+        Some((contents, _)) => contents,
+        None => {
+            // Remove .py and check if this is a directory
+            let file_path = {
+                let pos_dir = &b[0..b.len() - 3];
+                let files = read_file_dir(pos_dir);
+
+                if files.contains(&"__import__.py".to_string()) {
+                    // Ok just use that then
+                    format!("{pos_dir}__import__.py")
+                } else {
+                    // No __import__.py so let's see first if there is any .py so we can return a pseudo type
+                    for _ in files.iter() {
+                        return pocketpy::PXSPYTHON_IS_DIR; // -2 is a specific thingy to return a empty string in C.
+                    }
+                    b.to_string()
+                }
+            };
+
+            let contents = read_file(&file_path);
+            if contents.is_empty() {
+                return pocketpy::PXSPYTHON_NOT_FOUND;
             }
-            b.to_string()
+            contents
         }
     };
-
-    let contents = read_file(&file_path);
     let size = contents.len() as core::ffi::c_int;
-    if size == 0 {
-        return pocketpy::PXSPYTHON_NOT_FOUND;
-    }
     let raw_contents = create_raw_string!(contents);
 
     unsafe {
@@ -293,6 +330,25 @@ unsafe extern "C" fn pxspython_importfile(
     size + 1
 }
 
+/// Trace function used under a execution limit. Counts executed lines and checks the wall time.
+unsafe extern "C" fn limit_trace(_frame: *mut pocketpy::py_Frame, event: pocketpy::py_TraceEvent) {
+    if event != pocketpy::py_TraceEvent::TRACE_EVENT_LINE {
+        return;
+    }
+    let step = limit::step(&pxs_Runtime::pxs_Python, 1);
+    if step == limit::Step::Continue {
+        return;
+    }
+    // pocketpy can not yield from a trace function, so a suspension stops the script too.
+    if step == limit::Step::Suspend {
+        limit::suspend();
+    }
+    // Trace functions can not raise, so trip the watchdog which is checked right after.
+    unsafe {
+        pocketpy::py_watchdog_begin(-1);
+    }
+}
+
 /// Keep a reference to a python object/function.
 pub(self) fn python_pxs_new_register(obj_ref: pocketpy::py_Ref) -> i32 {
     // Nothing to add
@@ -389,6 +445,16 @@ unsafe fn python_setup() {
         let main = pocketpy::py_getmodule(cstr_safe.new_string(PYTHON_MAIN_MODULE));
         pocketpy::py_bindfunc(main, cstr_safe.new_string(PXS_CALL_METHOD), Some(pocketpy_bridge));
 
+        // Base type of the bridge classes, its destructor releases the host object.
+        let builtins = pocketpy::py_getmodule(cstr_safe.new_string("builtins"));
+        let object_type = pocketpy::py_newtype(
+            cstr_safe.new_string(PXS_OBJECT_TYPE),
+            pocketpy::py_PredefinedType::tp_object as pocketpy::py_Type,
+            builtins,
+            Some(pocketpy_object_dtor),
+        );
+        pocketpy::py_bindmagic(object_type, pocketpy::py_name(cstr_safe.new_string("__new__")), Some(pocketpy_object_new));
+
         // Setup module loader.
         let callbacks = pocketpy::py_callbacks();
         (*callbacks).importfile = Some(pocketpy::pxspython_import);
@@ -401,14 +467,12 @@ unsafe fn python_setup() {
 
     with_feature!("pxs_json", {
         // Create module
-        unsafe {
-            new_module(include_str!("../../core/python/pxs_json.py"), "pxs_json");
-        }
+        create_module(&crate::pxs_core::pxs_json::module());
         // Import into main
         python_code.push_str("\nimport pxs_json\n");
     });
 
-    let res = exec_main_py(&python_code, "<python_setup>");
+    let res = exec_main_py(&python_code, &internal_chunk_name("python_setup"));
     if !res.is_empty() {
         panic!("Python setup error: {res}");
     }
@@ -433,13 +497,23 @@ impl PixelScript for PythonScripting {
             pocketpy::py_resetallvm();
             pocketpy::py_finalize();
         }
-        clear(get_py_state());
+        // Every VM is gone, start from scratch on the next `start`.
+        let state = get_py_state();
+        unsafe {
+            (*state).defined_objects.clear();
+            (*state).thread_pool = setup_python_thread_pool();
+        }
     }
 
     fn add_module(source: std::sync::Arc<crate::shared::module::pxs_Module>) {
         create_module(&source);
     }
 
+    fn remove_module(source: &crate::shared::module::pxs_Module, object_types: &[String]) {
+        remove_module(source);
+        remove_defined_objects(object_types);
+    }
+
     fn execute(code: &str, file_name: &str) -> PxsResult {
         let res = exec_main_py(code, file_name);
         if res.is_empty() {
@@ -450,6 +524,40 @@ impl PixelScript for PythonScripting {
         // res
     }
 
+    fn eval_with(code: &str, name: &str, bindings: &pxs_Var) -> PxsResult {
+        // The builtin `eval` takes the locals as a dict.
+        let factory = Self::eval("lambda code, names: eval(code, None, names)", name)?;
+        let mut args = pxs_VarList::new();
+        args.add_item(pxs_Var::new_string(code.to_string()));
+        args.add_item(bindings.shallow_copy());
+        Self::var_call(&factory, &mut args)
+    }
+
+    fn object_overrides(var: &pxs_Var, method: &str) -> PxsRes<bool> {
+        // `_pxs_overrides` is defined in `core/python/main.py`.
+        let mut args = pxs_VarList::new();
+        args.add_item(var.shallow_copy());
+        args.add_item(pxs_Var::new_string(method.to_string()));
+        Self::call_method("_pxs_overrides", &mut args)?.get_bool()
+    }
+
+    fn execute_with_result(code: &str, file_name: &str) -> PxsResult {
+        // Exec mode has no value, so a last line that is a expression is evaluated on its own.
+        let code = code.trim_end();
+        let (body, last) = code.rsplit_once('\n').unwrap_or(("", code));
+        if last.starts_with(char::is_whitespace) || !is_expression(last) {
+            return Self::execute(code, file_name);
+        }
+
+        if !body.trim().is_empty() {
+            let res = exec_main_py(body, file_name);
+            if !res.is_empty() {
+                return Ok(pxs_Var::new_exception(res));
+            }
+        }
+        Self::eval(last, file_name)
+    }
+
     fn start_thread() {
         if THREAD_IDX.get().is_some() {
             pxs_debug!("THREAD is already active.");
@@ -643,6 +751,83 @@ impl PixelScript for PythonScripting {
             pocketpy::py_gc_collect();
         }
     }
+
+    fn trim_memory(object_types: &[String]) -> usize {
+        remove_defined_objects(object_types);
+        Self::garbage_collect();
+        // pocketpy only reports the number of objects freed.
+        0
+    }
+
+    fn new_function(fn_idx: i32) -> PxsResult {
+        // Same bridge as module functions.
+        Self::eval(
+            &format!("lambda *args, **kwargs: {PXS_CALL_METHOD}({fn_idx}, kwargs, *args)"),
+            &internal_chunk_name("function"),
+        )
+    }
+
+    fn set_exec_hooks(enabled: bool) {
+        unsafe {
+            if !enabled {
+                pocketpy::py_sys_settrace(None, true);
+                pocketpy::py_watchdog_end();
+                return;
+            }
+
+            // The trace checks `max_millis` too. pocketpy's own watchdog timeout uses CPU time, so a script waiting in a
+            // host call would never trip it.
+            let (max_instructions, max_millis) = limit::get(&pxs_Runtime::pxs_Python).unwrap_or((0, 0));
+            if max_instructions > 0 || max_millis > 0 || limit::has_tick() {
+                pocketpy::py_sys_settrace(Some(limit_trace), true);
+            }
+        }
+    }
+
+    fn caller_location() -> Option<(String, i32)> {
+        unsafe {
+            let frame = pocketpy::py_inspect_currentframe();
+            if frame.is_null() {
+                return None;
+            }
+            let mut line = 0;
+            let file = pocketpy::py_Frame_sourceloc(frame, &mut line);
+            if file.is_null() {
+                return None;
+            }
+            Some((borrow_string!(file).to_string(), line))
+        }
+    }
+
+    fn import_module(name: &str) -> PxsResult {
+        Self::eval(
+            &format!("{{k: v for k, v in __import__('{name}').__dict__.items() if not k.startswith('_')}}"),
+            &internal_chunk_name("import"),
+        )
+    }
+
+    fn new_iterator(var: &pxs_Var) -> PxsResult {
+        // `end` is a private sentinel so `None` items still come through.
+        let factory = Self::eval(
+            "lambda o: (lambda it, end: lambda: (lambda v: None if v is end else [v])(next(it, end)))(iter(o), object())",
+            &internal_chunk_name("iter"),
+        )?;
+        let mut args = pxs_VarList::new();
+        args.add_item(var.shallow_copy());
+        Self::var_call(&factory, &mut args)
+    }
+
+    fn new_coroutine(func: &pxs_Var, args: &pxs_Var) -> PxsResult {
+        // `func` is a generator function, the generator is its own end sentinel. Resume args are ignored.
+        let factory = Self::eval(
+            "lambda f, args: (lambda gen: lambda *_: (lambda v: [True, None] if v is gen else [False, v])(next(gen, gen)))(f(*args))",
+            &internal_chunk_name("coroutine"),
+        )?;
+        let mut factory_args = pxs_VarList::new();
+        factory_args.add_item(func.shallow_copy());
+        factory_args.add_item(args.shallow_copy());
+        Self::var_call(&factory, &mut factory_args)
+    }
 }
 
 /// Add pxs vars to the stack
@@ -875,4 +1060,17 @@ impl ObjectMethods for PythonScripting {
             Ok(pocketpyref_to_var(py_ref))
         }
     }
+
+    fn set_global(name: &str, value: &pxs_Var) -> PxsRes<()> {
+        unsafe {
+            let raw_name = create_raw_string!(name);
+            let pyname = pocketpy::py_name(raw_name);
+            free_raw_string!(raw_name);
+
+            let tmp = pocketpy::py_pushtmp();
+            var_to_pocketpyref(tmp, value, None);
+            pocketpy::py_setglobal(pyname, tmp);
+        }
+        Ok(())
+    }
 }
@@ -10,11 +10,37 @@ use etffi::cstring::CStringSafe;
 //
 use crate::{
     python::{
-        PXS_CALL_METHOD, exec_py, pocketpy, pocketpy_bridge, var_to_pocketpyref
+        PXS_ADOPT_METHOD, PXS_CALL_METHOD, PXS_OBJECT_TYPE, exec_py, func::{get_from_obj, pocketpy_adopt, py_assign}, pocketpy,
+        pocketpy_bridge, var_to_pocketpyref
     },
-    shared::module::pxs_Module,
+    shared::{internal_chunk_name, module::pxs_Module},
 };
 
+/// Name of the module helper creating instances of script subclasses of host types.
+const PXS_EXTEND_METHOD: &str = "_pxs_extend";
+
+/// Create a instance of a script subclass (`cls`) of the host type `base` for the host object `obj`. The methods of
+/// the bridge classes of `obj` are copied to `base` once, so subclasses inherit them like normal methods.
+fn extend_code() -> String {
+    format!(
+        r#"
+def {PXS_EXTEND_METHOD}(cls, base, obj):
+    if '_pxs_bridged' not in base.__dict__:
+        bridge = type(obj)
+        while bridge is not {PXS_OBJECT_TYPE}:
+            for name, value in bridge.__dict__.items():
+                if name != '__init__' and name not in base.__dict__:
+                    setattr(base, name, value)
+            bridge = bridge.__base__
+        base._pxs_bridged = True
+    sub = {PXS_OBJECT_TYPE}.__new__(cls, obj._pxs_ptr)
+    sub._pxs_ptr = obj._pxs_ptr
+    {PXS_ADOPT_METHOD}(sub)
+    return sub
+"#
+    )
+}
+
 pub(super) fn create_module(module: &pxs_Module) {
     // Get module name
     let module_name = module.name.clone();
@@ -56,31 +82,119 @@ pub(super) fn create_module(module: &pxs_Module) {
 
     unsafe {
         pocketpy::py_bindfunc(pymodule, cstr_safe.new_string(module_function_name), Some(pocketpy_bridge));
+        pocketpy::py_bindfunc(pymodule, cstr_safe.new_string(PXS_ADOPT_METHOD), Some(pocketpy_adopt));
     }
 
-    let mut methods = String::new();
+    let mut methods = extend_code();
 
     // Add callbacks to module... This also needs to go through the pybridge
     for method in module.callbacks.iter() {
+        if method.is_object {
+            // Object constructors are classes, so scripts can subclass the host type (`class Boss(Enemy)`). Calling
+            // the class itself returns the host object.
+            methods.push_str(&format!(
+                r#"
+class {0}({PXS_OBJECT_TYPE}):
+    _pxs_bridge = True
+
+    def __new__(cls, *args, **kwargs):
+        obj = {module_function_name}({1}, kwargs, *args)
+        if cls is {0}:
+            return obj
+        return {PXS_EXTEND_METHOD}(cls, {0}, obj)
+"#,
+                method.name, method.idx
+            ));
+            continue;
+        }
+
         let bridge_code = format!(
             r#"
-def {}(*args):
-    return {module_function_name}({}, *args)
+def {}(*args, **kwargs):
+    return {module_function_name}({}, kwargs, *args)
 "#,
             method.name, method.idx
         );
         methods.push_str(&bridge_code);
     }
 
+    // Classes, the constructor is `__new__` so calling the class returns the host object.
+    for class in module.classes.iter() {
+        methods.push_str(&format!("\nclass {}:\n    pass\n", class.name));
+        if let Some(constructor) = &class.constructor {
+            methods.push_str(&format!(
+                r#"
+    def __new__(cls, *args, **kwargs):
+        return {module_function_name}({}, kwargs, *args)
+"#,
+                constructor.idx
+            ));
+        }
+        for method in class.callbacks.iter() {
+            methods.push_str(&format!(
+                r#"
+    @staticmethod
+    def {}(*args, **kwargs):
+        return {module_function_name}({}, kwargs, *args)
+"#,
+                method.name, method.idx
+            ));
+        }
+    }
+
     // Run bridge_code in current module
     exec_py(
         &methods,
-        format!("<{}>", module_name).as_str(),
+        internal_chunk_name(&module_name).as_str(),
         &module_name,
     );
 
+    // Class constants
+    for class in module.classes.iter() {
+        unsafe {
+            let Some(class_ref) = get_from_obj(pymodule, &class.name) else {
+                continue;
+            };
+            let class_tmp = pocketpy::py_pushtmp();
+            py_assign(class_tmp, class_ref);
+            for var in class.variables.iter() {
+                let tmp = pocketpy::py_pushtmp();
+                var_to_pocketpyref(tmp, &var.var, Some(&module_name));
+                pocketpy::py_setattr(class_tmp, pocketpy::py_name(cstr_safe.new_string(&var.name)), tmp);
+                pocketpy::py_pop();
+            }
+            pocketpy::py_pop();
+        }
+    }
+
     // Do the same for internal modules
     for im in module.modules.iter() {
         create_module(im);
     }
 }
+
+/// Empty a module. pocketpy has no way to unregister a module, so its callbacks, variables and bridge function are
+/// deleted instead. Adding the module again fills it back in.
+pub(super) fn remove_module(module: &pxs_Module) {
+    let mut cstr_safe = CStringSafe::new();
+    let pymodule = unsafe { pocketpy::py_getmodule(cstr_safe.new_string(&module.name)) };
+    if !pymodule.is_null() {
+        let names = module
+            .callbacks
+            .iter()
+            .map(|cbk| cbk.name.as_str())
+            .chain(module.variables.iter().map(|var| var.name.as_str()))
+            .chain(module.classes.iter().map(|class| class.name.as_str()))
+            .chain([PXS_CALL_METHOD, PXS_ADOPT_METHOD, PXS_EXTEND_METHOD]);
+        for name in names {
+            unsafe {
+                let py_name = pocketpy::py_name(cstr_safe.new_string(name));
+                pocketpy::py_deldict(pymodule, py_name);
+            }
+        }
+    }
+
+    for im in module.modules.iter() {
+        remove_module(im);
+    }
+}
@@ -12,10 +12,24 @@ use etffi::cstring::CStringSafe;
 
 use crate::{
     pxs_debug, python::{
-        PXS_CALL_METHOD, add_new_defined_object, eval_py, exec_py, func::get_from_obj, is_object_defined, pocketpy, pocketpy_bridge
-    }, shared::{object::{ObjectFlags, pxs_PixelObject}}
+        PXS_CALL_METHOD, PXS_OBJECT_TYPE, add_new_defined_object, eval_py, exec_py, func::get_from_obj, is_object_defined, pocketpy, pocketpy_bridge
+    }, shared::{internal_chunk_name, object::{ObjectCallback, ObjectFlags, pxs_Operator, pxs_PixelObject}, registry}
 };
 
+/// Dunder method of a operator callback.
+fn operator_method(operator: pxs_Operator) -> &'static str {
+    match operator {
+        pxs_Operator::pxs_Operator_Add => "__add__",
+        pxs_Operator::pxs_Operator_Sub => "__sub__",
+        pxs_Operator::pxs_Operator_Mul => "__mul__",
+        pxs_Operator::pxs_Operator_Eq => "__eq__",
+        pxs_Operator::pxs_Operator_Lt => "__lt__",
+        pxs_Operator::pxs_Operator_ToString => "__str__",
+        pxs_Operator::pxs_Operator_Len => "__len__",
+        pxs_Operator::pxs_Operator_Index => "__getitem__",
+    }
+}
+
 /// Create a object type in the Python Runtime.
 ///
 /// idx: is the saved object.
@@ -33,20 +47,6 @@ pub(super) fn create_object(idx: i32, source: Arc<pxs_PixelObject>, module_name:
         }
     }
 
-    let object_name = format!("{rmodule_name}{}", source.type_name).replace(".", "_");
-    // Check if object is defined.
-    let obj_exists = is_object_defined(&object_name);
-    if obj_exists {
-        // pxs_debug!("Object is alredy defined!");
-        #[allow(unused)]
-        let eval_err = eval_py(
-            format!("_{}({})", object_name, idx).as_str(),
-            format!("<create_{}>", &object_name).as_str(),
-            module_name,
-        );
-        return;
-    }
-
     // Define _pxs_call for this objects module.
     unsafe {
         let module = pocketpy::py_getmodule(cstr_safe.new_string(&rmodule_name.clone()));
@@ -57,10 +57,40 @@ pub(super) fn create_object(idx: i32, source: Arc<pxs_PixelObject>, module_name:
         }
     }
 
-    // Object does not exist
+    let Some(object_name) = define_type(&source.type_name, &source.callbacks, &rmodule_name) else {
+        return;
+    };
+
+    // Ok but just create it now
+    let res = eval_py(
+        format!("_{}({})", object_name, idx).as_str(),
+        internal_chunk_name(&format!("create_{}", object_name)).as_str(),
+        &rmodule_name,
+    );
+    if !res.is_empty() {
+        pxs_debug!("Python create_object error: {res}");
+    }
+}
+
+/// Define the bridge class of `type_name` in `module_name` if it is not defined yet. The bridge class of a parent type
+/// is defined first (from its registered callbacks) and used as the base class.
+///
+/// Returns the object name (the class is `_{object_name}`), or None if the class could not be defined.
+fn define_type(type_name: &str, callbacks: &[ObjectCallback], module_name: &str) -> Option<String> {
+    let object_name = format!("{module_name}{type_name}").replace(".", "_");
+    // Check if object is defined.
+    if is_object_defined(&object_name) {
+        return Some(object_name);
+    }
+
+    let base = match registry::parent_of(type_name) {
+        Some(parent) => format!("(_{})", define_type(&parent, &registry::type_callbacks(&parent), module_name)?),
+        None => format!("({PXS_OBJECT_TYPE})"),
+    };
+
     // First register callbacks
     let mut methods_str = String::new();
-    for method in source.callbacks.iter() {
+    for method in callbacks.iter() {
         // Check input type
         let input = if method.flags & ObjectFlags::UsesId as u8 != 0 {
             "._pxs_ptr"
@@ -73,12 +103,17 @@ pub(super) fn create_object(idx: i32, source: Arc<pxs_PixelObject>, module_name:
             methods_str.push_str("\n    @property");
         }
 
+        let method_name = match method.operator {
+            Some(operator) => operator_method(operator),
+            None => &method.cbk.name,
+        };
+
         let function_string = format!(
             r#"
-    def {}(self, *args):
-        return {}({}, self{}, *args)
+    def {}(self, *args, **kwargs):
+        return {}({}, kwargs, self{}, *args)
 "#,
-        method.cbk.name, PXS_CALL_METHOD, method.cbk.idx, input
+        method_name, PXS_CALL_METHOD, method.cbk.idx, input
         );
         methods_str.push_str(&function_string);
 
@@ -92,14 +127,16 @@ pub(super) fn create_object(idx: i32, source: Arc<pxs_PixelObject>, module_name:
     let object_string = format!(
         r#"
 # Bridge for pocketpy
-class _{}:
+class _{}{}:
+    _pxs_bridge = True
+
     def __init__(self, ptr):
         # Set the ptr
         self._pxs_ptr = ptr
 
 {}
 "#,
-        object_name, methods_str
+        object_name, base, methods_str
     );
 
     pxs_debug!("{object_string} {module_name}");
@@ -107,23 +144,14 @@ class _{}:
     // Execute it
     let res = exec_py(
         &object_string,
-        format!("<first_{}>", &object_name).as_str(),
+        internal_chunk_name(&format!("first_{}", &object_name)).as_str(),
         module_name,
     );
     if !res.is_empty() {
-        return;
+        return None;
     }
 
     // add it
-    add_new_defined_object(&object_name);
-
-    // Ok but just create it now
-    let res = eval_py(
-        format!("_{}({})", object_name, idx).as_str(),
-        format!("<create_{}>", object_name).as_str(),
-        &rmodule_name,
-    );
-    if !res.is_empty() {
-        pxs_debug!("Python create_object error: {res}");
-    }
+    add_new_defined_object(&object_name, module_name, type_name);
+    Some(object_name)
 }
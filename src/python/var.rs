@@ -17,7 +17,7 @@ use crate::{
     pxs_debug, python::{
         consume_error, func::{get_string_from_obj, py_assign}, object::create_object, pocketpy::{self}, python_pxs_get_register, python_pxs_new_register, python_pxs_remove_ref
     }, shared::{
-        object::get_object, pxs_Runtime, var::{pxs_Var, pxs_VarObject, pxs_VarType}
+        error::pxs_ErrorKind, object::{collect_script_ref, get_object, retain_script_ref}, pxs_Runtime, var::{pxs_Var, pxs_VarObject, pxs_VarType}
     }
 };
 
@@ -40,6 +40,13 @@ impl PythonPointer {
     //     PythonPointer { is_index: false, ptr: py_ref as *mut c_void }
     // }
 
+    /// A copy of the object that does not keep it alive. Used for the language object of a host object, which is
+    /// set to nil when the GC collects it.
+    fn with_value(py_ref: pocketpy::py_Ref) -> Self {
+        let value = Box::new(unsafe { *py_ref });
+        PythonPointer { is_index: false, ptr: Box::into_raw(value) as *mut c_void }
+    }
+
     /// Get the Index pointer if `is_index` is `true`
     pub fn get_int(&self) -> i32 {
         if !self.is_index {
@@ -75,6 +82,46 @@ unsafe fn make_python_pointer(ptr: pocketpy::py_Ref) -> PythonPointer {
     PythonPointer::with_index(index)
 }
 
+/// Make `obj`, a instance of a script subclass, the language object of the host object `idx`. Returns false if there
+/// is no such object.
+pub(super) fn adopt_object(idx: i32, obj: pocketpy::py_Ref) -> bool {
+    let Some(pixel_object) = get_object(idx) else {
+        return false;
+    };
+    // Takes over the reference of the bridge instance.
+    let pyptr = PythonPointer::with_value(obj);
+    pixel_object.replace_lang_ptr(pyptr.into_void(), free_py_object);
+    true
+}
+
+/// Called from the `_pxs_object` destructor while the GC collects a instance (`ud` is its userdata) of the host object
+/// `idx`. Must not run Python.
+pub(super) fn finalize_object(idx: i32, ud: *mut c_void) {
+    let Some(pixel_object) = get_object(idx) else {
+        return;
+    };
+    {
+        let lang_ptr = pixel_object.lang_ptr.lock().unwrap();
+        if lang_ptr.is_null() {
+            return;
+        }
+        let value = PythonPointer::from_borrow_void(*lang_ptr).get_ptr();
+        // A bridge instance replaced by a subclass instance, or collected already.
+        if unsafe { !(*value).is_ptr || pocketpy::py_touserdata(value) != ud } {
+            return;
+        }
+        unsafe { pocketpy::py_newnil(value) };
+    }
+    drop(pixel_object);
+    collect_script_ref(idx);
+}
+
+/// Free the language object of a host object.
+unsafe extern "C" fn free_py_object(ptr: *mut c_void) {
+    let pp = PythonPointer::from_raw(ptr as *mut PythonPointer);
+    let _ = unsafe { Box::from_raw(pp.ptr as pocketpy::py_Ref) };
+}
+
 /// Frees a function and object memory.
 /// This works via a stack IDX.
 unsafe extern "C" fn free_py_mem(ptr: *mut c_void) {
@@ -104,6 +151,16 @@ pub(super) fn pocketpyref_to_var(pref: pocketpy::py_Ref) -> pxs_Var {
         let r_str = borrow_string!(cstr_ptr).to_string();
 
         pxs_Var::new_string(r_str)
+    } else if tp == pocketpy::py_PredefinedType::tp_bytes as i32 {
+        let mut size = 0;
+        let data = unsafe { pocketpy::py_tobytes(pref, &mut size) };
+        let bytes = if data.is_null() || size <= 0 {
+            vec![]
+        } else {
+            unsafe { std::slice::from_raw_parts(data, size as usize) }.to_vec()
+        };
+
+        pxs_Var::new_bytes(bytes)
     } else if tp == pocketpy::py_PredefinedType::tp_NoneType as i32 || pref.is_null() {
         pxs_Var::new_null()
     } else if tp == pocketpy::py_PredefinedType::tp_list as i32 || tp == pocketpy::py_PredefinedType::tp_tuple as i32 {
@@ -143,6 +200,7 @@ pub(super) fn pocketpyref_to_var(pref: pocketpy::py_Ref) -> pxs_Var {
         pxs_Var::new_list_with(vars)
     } else if tp == pocketpy::py_PredefinedType::tp_function as i32 {
         pxs_Var::new_function(unsafe { make_python_pointer(pref).into_raw() as *mut c_void }, Some(free_py_mem))
+            .with_origin(pxs_Runtime::pxs_Python)
     } else if tp == pocketpy::py_PredefinedType::tp_Exception as i32 {
         let msg = consume_error();
         pxs_Var::new_exception(msg)
@@ -151,10 +209,23 @@ pub(super) fn pocketpyref_to_var(pref: pocketpy::py_Ref) -> pxs_Var {
         unsafe {
             // Check if object has `_pxs_ptr` assigned
             pxs_Var::new_object(pxs_VarObject::new_lang_only(make_python_pointer(pref).into_raw() as *mut c_void), Some(free_py_mem))
+                .with_origin(pxs_Runtime::pxs_Python)
         }
     }
 }
 
+/// Python exception class of a error `kind`.
+fn exception_type(kind: pxs_ErrorKind) -> pocketpy::py_PredefinedType {
+    match kind {
+        pxs_ErrorKind::pxs_GenericError => pocketpy::py_PredefinedType::tp_BaseException,
+        pxs_ErrorKind::pxs_TypeError => pocketpy::py_PredefinedType::tp_TypeError,
+        pxs_ErrorKind::pxs_ValueError => pocketpy::py_PredefinedType::tp_ValueError,
+        pxs_ErrorKind::pxs_KeyError => pocketpy::py_PredefinedType::tp_KeyError,
+        pxs_ErrorKind::pxs_IndexError => pocketpy::py_PredefinedType::tp_IndexError,
+        pxs_ErrorKind::pxs_NotImplementedError => pocketpy::py_PredefinedType::tp_NotImplementedError,
+    }
+}
+
 /// Convert a Var into a PocketPy ref
 pub(super) fn var_to_pocketpyref(out: pocketpy::py_Ref, var: &pxs_Var, module_name: Option<&str>) {
     unsafe {
@@ -192,9 +263,14 @@ pub(super) fn var_to_pocketpyref(out: pocketpy::py_Ref, var: &pxs_Var, module_na
             }
             crate::shared::var::pxs_VarType::pxs_HostObject => {
                 let idx = var.value.host_object_val;
-                let pixel_object = get_object(idx).unwrap();
-                let lang_ptr_is_null = pixel_object.lang_ptr.lock().unwrap().is_null();
-                if lang_ptr_is_null {
+                let Some(pixel_object) = get_object(idx) else {
+                    // Released by the host.
+                    pocketpy::py_newnone(out);
+                    return;
+                };
+                let lang_ptr = *pixel_object.lang_ptr.lock().unwrap();
+                let collected = lang_ptr.is_null() || pocketpy::py_istype(PythonPointer::from_borrow_void(lang_ptr).get_ptr(), 0);
+                if collected {
                     // let module_name = get_module_name_from_obj_idx(id);
                     // Find current module
                     let obj_module_name = if let Some(module_name) = module_name {
@@ -215,15 +291,21 @@ pub(super) fn var_to_pocketpyref(out: pocketpy::py_Ref, var: &pxs_Var, module_na
                         }
                     };
                     // pxs_debug!("Full module path: {obj_module_name}");
-                    // Create the object for the first time...
+                    // Create the object, for the first time or because the last one was collected.
                     create_object(idx, Arc::clone(&pixel_object), &obj_module_name);
                     // Get py_retval
                     let pyobj = pocketpy::py_retval();
-                    // Save as PythonPointer
-                    let pyptr = make_python_pointer(pyobj);
-                    // Set that as the pointer
-                    pixel_object.update_lang_ptr(pyptr.into_void());
-                    pixel_object.update_pxs_free_method(free_py_mem);
+                    if lang_ptr.is_null() {
+                        // Save as PythonPointer
+                        let pyptr = PythonPointer::with_value(pyobj);
+                        // Set that as the pointer
+                        pixel_object.update_lang_ptr(pyptr.into_void());
+                        pixel_object.update_pxs_free_method(free_py_object);
+                    } else {
+                        py_assign(PythonPointer::from_borrow_void(lang_ptr).get_ptr(), pyobj);
+                    }
+                    // The instance owns a reference until it is collected.
+                    retain_script_ref(idx);
                 }
                 // Get PTR again
                 let lang_ptr = pixel_object.lang_ptr.lock().unwrap();
@@ -273,7 +355,7 @@ pub(super) fn var_to_pocketpyref(out: pocketpy::py_Ref, var: &pxs_Var, module_na
             pxs_VarType::pxs_Exception => {
                 // Raise exception
                 pocketpy::py_newstr(out, var.value.string_val);
-                let ok = pocketpy::py_tpcall(pocketpy::py_PredefinedType::tp_BaseException as i16, 1, out);
+                let ok = pocketpy::py_tpcall(exception_type(var.error_kind) as i16, 1, out);
                 if !ok {
                     let err = consume_error();
                     pxs_debug!("Exception could not be raised: {err}");
@@ -315,6 +397,20 @@ pub(super) fn var_to_pocketpyref(out: pocketpy::py_Ref, var: &pxs_Var, module_na
             pxs_VarType::pxs_Byte => {
                 pocketpy::py_newint(out, var.get_byte().unwrap() as i64);
             }
+            pxs_VarType::pxs_Bytes => {
+                let bytes = var.get_bytes().unwrap();
+                let data = pocketpy::py_newbytes(out, bytes.len() as i32);
+                if !data.is_null() {
+                    std::ptr::copy_nonoverlapping(bytes.as_ptr(), data, bytes.len());
+                }
+            }
+            pxs_VarType::pxs_Int32 => pocketpy::py_newint(out, var.get_i32().unwrap() as i64),
+            pxs_VarType::pxs_UInt32 => pocketpy::py_newint(out, var.get_u32().unwrap() as i64),
+            pxs_VarType::pxs_Float32 => pocketpy::py_newfloat(out, var.get_f32().unwrap() as f64),
+            // pocketpy has no typed arrays, scripts get a list of numbers.
+            pxs_VarType::pxs_TypedArray => {
+                var_to_pocketpyref(out, &var.get_typed_array().unwrap().to_list(), module_name);
+            }
         }
     }
 }
@@ -0,0 +1,495 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+//! A safe Rust API on top of the C FFI.
+//!
+//! Everything here goes through the same `pxs_*` functions a C host uses, it only takes care of the `CString`s, raw
+//! pointers and frees.
+//!
+//! ```ignore
+//! use pixelscript::rust_api::{self, Module, Runtime, Value};
+//!
+//! extern "C" fn add(args: pxs_VarT) -> pxs_VarT {
+//!     let args = unsafe { rust_api::args(args) };
+//!     let a = args[1].as_i64().unwrap_or(0);
+//!     let b = args[2].as_i64().unwrap_or(0);
+//!     Value::from(a + b).into_raw()
+//! }
+//!
+//! rust_api::initialize();
+//! let mut module = Module::new("math");
+//! module.add_func("add", add);
+//! module.register();
+//! let res = Runtime::Lua.eval("require('math').add(1, 2)");
+//! ```
+use std::{
+    ffi::{CString, c_void},
+    fmt, ptr,
+};
+
+use etffi::ptr_magic::PtrMagic;
+
+use crate::{
+    add_callback_to_module, pxs_add_submod, pxs_addfunc, pxs_addmod, pxs_addobject, pxs_addvar, pxs_eval, pxs_exec, pxs_finalize, pxs_freemod,
+    pxs_gethost, pxs_initialize, pxs_newhost, pxs_newmod, pxs_newobject, pxs_object_addfunc, pxs_object_addprop,
+    pxs_objectcall, pxs_varcall,
+    shared::{
+        func::{current_opaque, pxs_Func},
+        module::pxs_Module,
+        object::pxs_PixelObject,
+        pxs_Runtime, strict,
+        var::{pxs_Var, pxs_VarT, pxs_VarType},
+    },
+};
+
+/// Initialize pixelscript. Same as `pxs_initialize`.
+pub fn initialize() {
+    pxs_initialize();
+}
+
+/// Finalize pixelscript. Same as `pxs_finalize`.
+pub fn finalize() {
+    pxs_finalize();
+}
+
+/// Make a `CString`, strings with a nul byte are an error instead of a panic.
+fn cstring(s: &str) -> Result<CString, String> {
+    CString::new(s).map_err(|_| format!("String contains a nul byte: {s:?}"))
+}
+
+/// Turn a returned `pxs_VarT` into a result. Exceptions are errors.
+fn result(var: pxs_VarT) -> Result<Value, String> {
+    if var.is_null() {
+        return Ok(Value::null());
+    }
+    let value = Value(pxs_Var::from_raw(var));
+    if value.is_exception() {
+        Err(value.0.get_string().unwrap_or_default())
+    } else {
+        Ok(value)
+    }
+}
+
+/// A runtime to run code in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Runtime {
+    Lua,
+    Python,
+    JavaScript,
+}
+
+impl Runtime {
+    pub fn raw(self) -> pxs_Runtime {
+        match self {
+            Runtime::Lua => pxs_Runtime::pxs_Lua,
+            Runtime::Python => pxs_Runtime::pxs_Python,
+            Runtime::JavaScript => pxs_Runtime::pxs_JavaScript,
+        }
+    }
+
+    /// The runtime of a runtime var, i.e. the first arg of a callback.
+    pub fn from_value(value: &Value) -> Option<Self> {
+        match pxs_Runtime::from_i64(value.as_i64()?)? {
+            pxs_Runtime::pxs_Lua => Some(Runtime::Lua),
+            pxs_Runtime::pxs_Python => Some(Runtime::Python),
+            pxs_Runtime::pxs_JavaScript => Some(Runtime::JavaScript),
+            _ => None,
+        }
+    }
+
+    /// The runtime as a var, what `pxs_objectcall` and `pxs_varcall` expect.
+    pub fn value(self) -> Value {
+        Value::from(self.raw().into_i64())
+    }
+
+    /// Execute code. Same as `pxs_exec`.
+    pub fn exec(self, code: &str, file_name: &str) -> Result<Value, String> {
+        let code = cstring(code)?;
+        let file_name = cstring(file_name)?;
+        result(pxs_exec(self.raw(), code.as_ptr(), file_name.as_ptr()))
+    }
+
+    /// Evaluate a expression. Same as `pxs_eval`.
+    pub fn eval(self, script: &str) -> Result<Value, String> {
+        let script = cstring(script)?;
+        result(pxs_eval(script.as_ptr(), self.raw()))
+    }
+
+    /// Call a function var with `args`. Same as `pxs_varcall`.
+    pub fn call(self, func: &Value, args: Vec<Value>) -> Result<Value, String> {
+        let runtime = self.value();
+        result(pxs_varcall(runtime.as_ptr(), func.as_ptr(), Value::list(args).into_raw()))
+    }
+
+    /// Call a method of a object var with `args`. Same as `pxs_objectcall`.
+    pub fn call_method(self, object: &Value, method: &str, args: Vec<Value>) -> Result<Value, String> {
+        let runtime = self.value();
+        let method = cstring(method)?;
+        result(pxs_objectcall(runtime.as_ptr(), object.as_ptr(), method.as_ptr(), Value::list(args).into_raw()))
+    }
+}
+
+/// A owned `pxs_Var`. Freed on drop.
+#[repr(transparent)]
+pub struct Value(pxs_Var);
+
+impl Value {
+    pub fn null() -> Self {
+        Value(pxs_Var::new_null())
+    }
+
+    pub fn list(items: Vec<Value>) -> Self {
+        Value(pxs_Var::new_list_with(items.into_iter().map(|item| item.0).collect()))
+    }
+
+    pub fn map() -> Self {
+        Value(pxs_Var::new_map())
+    }
+
+    pub fn bytes(data: Vec<u8>) -> Self {
+        Value(pxs_Var::new_bytes(data))
+    }
+
+    pub fn exception(msg: &str) -> Self {
+        Value(pxs_Var::new_exception(msg))
+    }
+
+    pub fn from_var(var: pxs_Var) -> Self {
+        Value(var)
+    }
+
+    pub fn into_var(self) -> pxs_Var {
+        self.0
+    }
+
+    pub fn as_var(&self) -> &pxs_Var {
+        &self.0
+    }
+
+    /// Own a `pxs_VarT`, i.e. the return of a `pxs_*` function.
+    ///
+    /// # Safety
+    /// `var` must be a owned, non null `pxs_VarT`.
+    pub unsafe fn from_raw(var: pxs_VarT) -> Self {
+        Value(pxs_Var::from_raw(var))
+    }
+
+    /// Give up ownership, i.e. to return from a callback.
+    pub fn into_raw(self) -> pxs_VarT {
+        self.0.into_raw()
+    }
+
+    /// Pointer for `pxs_*` functions that BORROW a var.
+    pub fn as_ptr(&self) -> pxs_VarT {
+        &self.0 as *const pxs_Var as pxs_VarT
+    }
+
+    pub fn var_type(&self) -> pxs_VarType {
+        self.0.tag
+    }
+
+    pub fn is_null(&self) -> bool {
+        self.0.is_null()
+    }
+
+    pub fn is_exception(&self) -> bool {
+        self.0.is_exception()
+    }
+
+    pub fn as_i64(&self) -> Option<i64> {
+        self.0.get_i64().ok()
+    }
+
+    pub fn as_u64(&self) -> Option<u64> {
+        self.0.get_u64().ok()
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        self.0.get_f64().ok()
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        self.0.get_bool().ok()
+    }
+
+    /// The string of a string or exception.
+    pub fn as_string(&self) -> Option<String> {
+        self.0.get_string().ok()
+    }
+
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        self.0.get_bytes().map(|bytes| bytes.as_slice())
+    }
+
+    /// Items of a list.
+    pub fn items(&self) -> Option<&[Value]> {
+        let list = self.0.get_list()?;
+        // Value is repr(transparent) over pxs_Var.
+        Some(unsafe { std::slice::from_raw_parts(list.vars.as_ptr() as *const Value, list.vars.len()) })
+    }
+
+    /// Item of a list.
+    pub fn get(&self, index: usize) -> Option<&Value> {
+        self.items()?.get(index)
+    }
+
+    /// Add a item to a list. Does nothing if this is not a list.
+    pub fn push(&mut self, item: impl Into<Value>) {
+        if let Some(list) = self.0.get_list() {
+            list.add_item(item.into().0);
+        }
+    }
+
+    /// Value of a map key.
+    pub fn get_key(&self, key: impl Into<Value>) -> Option<&Value> {
+        let map = self.0.get_map()?;
+        map.get_item(&key.into().0).map(|value| unsafe { &*(value as *const pxs_Var as *const Value) })
+    }
+
+    /// Set a map key. Does nothing if this is not a map.
+    pub fn insert(&mut self, key: impl Into<Value>, value: impl Into<Value>) {
+        if let Some(map) = self.0.get_map() {
+            map.add_item(key.into().0, value.into().0);
+        }
+    }
+}
+
+impl fmt::Debug for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+impl From<pxs_Var> for Value {
+    fn from(var: pxs_Var) -> Self {
+        Value(var)
+    }
+}
+
+impl From<Value> for pxs_Var {
+    fn from(value: Value) -> Self {
+        value.0
+    }
+}
+
+impl From<()> for Value {
+    fn from(_: ()) -> Self {
+        Value::null()
+    }
+}
+
+impl From<i64> for Value {
+    fn from(val: i64) -> Self {
+        Value(pxs_Var::new_i64(val))
+    }
+}
+
+impl From<i32> for Value {
+    fn from(val: i32) -> Self {
+        Value(pxs_Var::new_i64(val as i64))
+    }
+}
+
+impl From<u64> for Value {
+    fn from(val: u64) -> Self {
+        Value(pxs_Var::new_u64(val))
+    }
+}
+
+impl From<f64> for Value {
+    fn from(val: f64) -> Self {
+        Value(pxs_Var::new_f64(val))
+    }
+}
+
+impl From<bool> for Value {
+    fn from(val: bool) -> Self {
+        Value(pxs_Var::new_bool(val))
+    }
+}
+
+impl From<&str> for Value {
+    fn from(val: &str) -> Self {
+        Value(pxs_Var::new_string(val.to_string()))
+    }
+}
+
+impl From<String> for Value {
+    fn from(val: String) -> Self {
+        Value(pxs_Var::new_string(val))
+    }
+}
+
+impl<T: Into<Value>> From<Vec<T>> for Value {
+    fn from(items: Vec<T>) -> Self {
+        Value::list(items.into_iter().map(Into::into).collect())
+    }
+}
+
+impl<T: Into<Value>> From<Option<T>> for Value {
+    fn from(val: Option<T>) -> Self {
+        val.map_or_else(Value::null, Into::into)
+    }
+}
+
+/// The args of a callback as values. `args[0]` is the runtime.
+///
+/// # Safety
+/// `args` must be the (borrowed) args list of a running callback. The slice must not outlive the callback.
+pub unsafe fn args<'a>(args: pxs_VarT) -> &'a [Value] {
+    if args.is_null() {
+        return &[];
+    }
+    let args = unsafe { &*(args as *const Value) };
+    args.items().unwrap_or(&[])
+}
+
+/// A Rust closure callback.
+type Closure = Box<dyn Fn(&[Value]) -> Value + Send>;
+
+/// Bridge from `pxs_Func` to the closure saved as the opaque of the function being called.
+unsafe extern "C" fn call_closure(args_ptr: pxs_VarT) -> pxs_VarT {
+    let closure = current_opaque() as *const Closure;
+    if closure.is_null() {
+        return Value::null().into_raw();
+    }
+    let args = unsafe { args(args_ptr) };
+    let closure = unsafe { &*closure };
+    match std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| closure(args))) {
+        Ok(value) => value.into_raw(),
+        Err(_) => Value::exception("Rust callback panicked.").into_raw(),
+    }
+}
+
+/// A module being built. Freed on drop unless registered.
+pub struct Module {
+    ptr: *mut pxs_Module,
+}
+
+impl Module {
+    /// Panics if `name` contains a nul byte.
+    pub fn new(name: &str) -> Self {
+        let name = cstring(name).unwrap();
+        Module { ptr: pxs_newmod(name.as_ptr()) }
+    }
+
+    pub fn add_func(&mut self, name: &str, func: pxs_Func) -> &mut Self {
+        let name = cstring(name).unwrap();
+        pxs_addfunc(self.ptr, name.as_ptr(), func);
+        self
+    }
+
+    /// Add a closure as a function. `args[0]` is the runtime. The closure is dropped when the function is removed,
+    /// i.e. by `pxs_clear` or `pxs_removemod`.
+    ///
+    /// A panic in the closure is returned as a exception.
+    pub fn add_fn(&mut self, name: &str, func: impl Fn(&[Value]) -> Value + Send + 'static) -> &mut Self {
+        let closure: Box<Closure> = Box::new(Box::new(func));
+        let opaque = Box::into_raw(closure) as *mut c_void;
+        let module = unsafe { pxs_Module::from_borrow(self.ptr) };
+        // The closure is dropped if the function lookup is full.
+        if let Err(e) = add_callback_to_module(module, name, call_closure, opaque, Some(drop_data::<Closure>)) {
+            strict::misuse("add_fn", &e);
+        }
+        self
+    }
+
+    /// Add a object constructor. Same as `pxs_addobject`.
+    pub fn add_object(&mut self, name: &str, constructor: pxs_Func) -> &mut Self {
+        let name = cstring(name).unwrap();
+        pxs_addobject(self.ptr, name.as_ptr(), constructor);
+        self
+    }
+
+    pub fn add_var(&mut self, name: &str, value: impl Into<Value>) -> &mut Self {
+        let name = cstring(name).unwrap();
+        pxs_addvar(self.ptr, name.as_ptr(), value.into().into_raw());
+        self
+    }
+
+    pub fn add_module(&mut self, child: Module) -> &mut Self {
+        pxs_add_submod(self.ptr, child.into_raw());
+        self
+    }
+
+    /// Give up ownership of the `pxs_Module`.
+    pub fn into_raw(mut self) -> *mut pxs_Module {
+        std::mem::replace(&mut self.ptr, ptr::null_mut())
+    }
+
+    /// Add the module to every runtime. Same as `pxs_addmod`.
+    pub fn register(self) {
+        pxs_addmod(self.into_raw());
+    }
+}
+
+impl Drop for Module {
+    fn drop(&mut self) {
+        if !self.ptr.is_null() {
+            pxs_freemod(self.ptr);
+        }
+    }
+}
+
+/// Drop the boxed data of a `Object<T>` or a closure.
+unsafe extern "C" fn drop_data<T>(ptr: *mut c_void) {
+    if !ptr.is_null() {
+        let _ = unsafe { Box::from_raw(ptr as *mut T) };
+    }
+}
+
+/// A host object being built, owning its data. Freed on drop unless turned into a value.
+pub struct Object {
+    ptr: *mut pxs_PixelObject,
+}
+
+impl Object {
+    /// Box `data` into a new object. The box is dropped when the object is freed.
+    ///
+    /// Panics if `type_name` contains a nul byte.
+    pub fn new<T: 'static>(type_name: &str, data: T) -> Self {
+        let type_name = cstring(type_name).unwrap();
+        let data = Box::into_raw(Box::new(data)) as *mut c_void;
+        Object { ptr: pxs_newobject(data, drop_data::<T>, type_name.as_ptr()) }
+    }
+
+    pub fn add_method(&mut self, name: &str, func: pxs_Func) -> &mut Self {
+        let name = cstring(name).unwrap();
+        pxs_object_addfunc(self.ptr, name.as_ptr(), func);
+        self
+    }
+
+    pub fn add_prop(&mut self, name: &str, func: pxs_Func) -> &mut Self {
+        let name = cstring(name).unwrap();
+        pxs_object_addprop(self.ptr, name.as_ptr(), func);
+        self
+    }
+
+    /// Make the object a `pxs_HostObject` value, i.e. to return from a constructor. Same as `pxs_newhost`.
+    pub fn into_value(mut self) -> Value {
+        let ptr = std::mem::replace(&mut self.ptr, ptr::null_mut());
+        Value(pxs_Var::from_raw(pxs_newhost(ptr)))
+    }
+
+    /// The data of a object var passed to a callback. Same as `pxs_gethost`.
+    ///
+    /// # Safety
+    /// The object must have been made with `Object::new::<T>`, and the reference must not outlive the callback.
+    pub unsafe fn data<'a, T>(runtime: &Value, var: &Value) -> Option<&'a mut T> {
+        let ptr = pxs_gethost(runtime.as_ptr(), var.as_ptr()) as *mut T;
+        unsafe { ptr.as_mut() }
+    }
+}
+
+impl Drop for Object {
+    fn drop(&mut self) {
+        if !self.ptr.is_null() {
+            let _ = pxs_PixelObject::from_raw(self.ptr);
+        }
+    }
+}
@@ -0,0 +1,266 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+use std::{
+    ffi::c_char,
+    sync::{LazyLock, Mutex},
+};
+
+use etffi::{cstring::CStringSafe, ptr_magic::PtrMagic};
+
+use crate::shared::{
+    module::{ModuleCallback, ModuleVariable, pxs_Module},
+    pxs_Opaque,
+    var::{pxs_Var, pxs_VarT},
+};
+
+/// Runtime id of the first backend registered with `pxs_register_backend`. Later backends get the next ids.
+pub const PXS_CUSTOM_RUNTIME: i32 = 16;
+
+/// A language implementation supplied by the host. Passed to `pxs_register_backend`.
+///
+/// Every function gets `opaque` as its first argument. Any function can be NULL, calling it then returns a
+/// `pxs_Exception` (or does nothing for `start`, `stop`, `add_module` and `remove_module`).
+///
+/// Vars passed to the functions are borrowed, returned vars are owned by pixelscript. Wrap language functions and
+/// objects with `pxs_backend_newvar` so `pxs_varcall`, `pxs_objectcall`, `pxs_objectget` and `pxs_objectset` are
+/// routed back to the backend.
+#[repr(C)]
+#[derive(Clone, Copy)]
+#[allow(non_camel_case_types)]
+pub struct pxs_BackendVTable {
+    /// Name of the language, used in errors. Copied on registration.
+    pub name: *const c_char,
+    /// Host data passed to every function.
+    pub opaque: pxs_Opaque,
+    /// Called on registration with the runtime id of the backend.
+    pub start: Option<unsafe extern "C" fn(opaque: pxs_Opaque, runtime: i32)>,
+    /// Called on `pxs_finalize`.
+    pub stop: Option<unsafe extern "C" fn(opaque: pxs_Opaque)>,
+    /// A module was added. `module` is a Map of
+    /// `{name, functions: [{name, handle}], objects: [{name, handle}], variables: [{name, value}], classes: [{name,
+    /// constructor, functions, variables}], modules: [...]}`. A class without constructor has a `constructor` of -1.
+    /// Call functions with `pxs_call_handle`.
+    pub add_module: Option<unsafe extern "C" fn(opaque: pxs_Opaque, module: pxs_VarT)>,
+    /// A module was removed with `pxs_removemod` or `pxs_reloadmod`.
+    pub remove_module: Option<unsafe extern "C" fn(opaque: pxs_Opaque, name: *const c_char)>,
+    /// Execute code. Return NULL on success or a `pxs_Exception`.
+    pub execute: Option<unsafe extern "C" fn(opaque: pxs_Opaque, code: *const c_char, file_name: *const c_char) -> pxs_VarT>,
+    /// Evaluate code and return the result.
+    pub eval: Option<unsafe extern "C" fn(opaque: pxs_Opaque, code: *const c_char, name: *const c_char) -> pxs_VarT>,
+    /// Compile code for `exec_object`. Return a List, pixelscript inserts the runtime id at index 0.
+    pub compile: Option<unsafe extern "C" fn(opaque: pxs_Opaque, code: *const c_char, global_scope: pxs_VarT) -> pxs_VarT>,
+    /// Execute a List returned by `compile` (with the runtime id at index 0).
+    pub exec_object: Option<unsafe extern "C" fn(opaque: pxs_Opaque, code: pxs_VarT, local_scope: pxs_VarT) -> pxs_VarT>,
+    /// Call a function var with a List of args.
+    pub call: Option<unsafe extern "C" fn(opaque: pxs_Opaque, func: pxs_VarT, args: pxs_VarT) -> pxs_VarT>,
+    /// Call a method of a object var with a List of args.
+    pub object_call:
+        Option<unsafe extern "C" fn(opaque: pxs_Opaque, obj: pxs_VarT, method: *const c_char, args: pxs_VarT) -> pxs_VarT>,
+    /// Get a property of a object var.
+    pub object_get: Option<unsafe extern "C" fn(opaque: pxs_Opaque, obj: pxs_VarT, key: *const c_char) -> pxs_VarT>,
+    /// Set a property of a object var. Return false on failure.
+    pub object_set:
+        Option<unsafe extern "C" fn(opaque: pxs_Opaque, obj: pxs_VarT, key: *const c_char, value: pxs_VarT) -> bool>,
+}
+
+/// A registered backend.
+#[derive(Clone)]
+pub(crate) struct Backend {
+    pub(crate) id: i32,
+    pub(crate) name: String,
+    vtable: pxs_BackendVTable,
+}
+
+// The host is responsible for `opaque` being usable from the threads it calls pixelscript on.
+unsafe impl Send for Backend {}
+
+static BACKENDS: LazyLock<Mutex<Vec<Backend>>> = LazyLock::new(|| Mutex::new(vec![]));
+
+/// Register a backend. Returns its runtime id.
+pub(crate) fn register(vtable: pxs_BackendVTable, name: String) -> i32 {
+    let mut backends = BACKENDS.lock().unwrap();
+    let id = PXS_CUSTOM_RUNTIME + backends.len() as i32;
+    backends.push(Backend { id, name, vtable });
+    id
+}
+
+/// Get the backend with this runtime id.
+pub(crate) fn get(id: i64) -> Option<Backend> {
+    // Copied out so the lock is not held while the host runs.
+    BACKENDS.lock().unwrap().iter().find(|b| b.id as i64 == id).cloned()
+}
+
+/// Every registered backend.
+pub(crate) fn all() -> Vec<Backend> {
+    BACKENDS.lock().unwrap().clone()
+}
+
+/// Remove every backend, returning them so they can be stopped.
+pub(crate) fn clear() -> Vec<Backend> {
+    std::mem::take(&mut *BACKENDS.lock().unwrap())
+}
+
+fn new_map(items: Vec<(&str, pxs_Var)>) -> pxs_Var {
+    let var = pxs_Var::new_map();
+    let map = var.get_map().unwrap();
+    for (key, value) in items {
+        map.add_item(pxs_Var::new_string(key.to_string()), value);
+    }
+    var
+}
+
+/// Borrow a var as a pointer for the host.
+fn ptr(var: &pxs_Var) -> pxs_VarT {
+    var as *const pxs_Var as pxs_VarT
+}
+
+impl Backend {
+    fn unsupported(&self, function: &str) -> pxs_Var {
+        pxs_Var::new_exception(format!("{function} is not supported by the {} backend.", self.name))
+    }
+
+    /// Own a var returned by the host. NULL is a `pxs_Null`.
+    fn result(res: pxs_VarT) -> pxs_Var {
+        if res.is_null() { pxs_Var::new_null() } else { pxs_Var::from_raw(res) }
+    }
+
+    /// Describe `module` for `add_module`. Factories are resolved with this runtime.
+    fn describe(&self, module: &pxs_Module) -> pxs_Var {
+        let handle = |cbk: &ModuleCallback| {
+            new_map(vec![
+                ("name", pxs_Var::new_string(cbk.name.clone())),
+                ("handle", pxs_Var::new_i64(cbk.idx as i64)),
+            ])
+        };
+        let callbacks = |objects: bool| {
+            module.callbacks.iter().filter(|cbk| cbk.is_object == objects).map(handle).collect()
+        };
+        let variables = module.variables.iter().map(|v| self.describe_var(v)).collect();
+        let modules = module.modules.iter().map(|m| self.describe(m)).collect();
+        let classes = module
+            .classes
+            .iter()
+            .map(|class| {
+                let constructor = class.constructor.as_ref().map_or(-1, |cbk| cbk.idx);
+                new_map(vec![
+                    ("name", pxs_Var::new_string(class.name.clone())),
+                    ("constructor", pxs_Var::new_i64(constructor as i64)),
+                    ("functions", pxs_Var::new_list_with(class.callbacks.iter().map(handle).collect())),
+                    ("variables", pxs_Var::new_list_with(class.variables.iter().map(|v| self.describe_var(v)).collect())),
+                ])
+            })
+            .collect();
+
+        new_map(vec![
+            ("name", pxs_Var::new_string(module.name.clone())),
+            ("functions", pxs_Var::new_list_with(callbacks(false))),
+            ("objects", pxs_Var::new_list_with(callbacks(true))),
+            ("variables", pxs_Var::new_list_with(variables)),
+            ("classes", pxs_Var::new_list_with(classes)),
+            ("modules", pxs_Var::new_list_with(modules)),
+        ])
+    }
+
+    /// Describe a module or class variable.
+    fn describe_var(&self, v: &ModuleVariable) -> pxs_Var {
+        let value = match v.var.get_factory() {
+            Some(factory) => factory.call_id(self.id as i64),
+            None => v.var.clone(),
+        };
+        new_map(vec![("name", pxs_Var::new_string(v.name.clone())), ("value", value)])
+    }
+
+    pub(crate) fn start(&self) {
+        if let Some(start) = self.vtable.start {
+            unsafe { start(self.vtable.opaque, self.id) };
+        }
+    }
+
+    pub(crate) fn stop(&self) {
+        if let Some(stop) = self.vtable.stop {
+            unsafe { stop(self.vtable.opaque) };
+        }
+    }
+
+    pub(crate) fn add_module(&self, module: &pxs_Module) {
+        if let Some(add_module) = self.vtable.add_module {
+            let desc = self.describe(module);
+            unsafe { add_module(self.vtable.opaque, ptr(&desc)) };
+        }
+    }
+
+    pub(crate) fn remove_module(&self, name: &str) {
+        if let Some(remove_module) = self.vtable.remove_module {
+            let mut cstrgen = CStringSafe::new();
+            unsafe { remove_module(self.vtable.opaque, cstrgen.new_string(name)) };
+        }
+    }
+
+    pub(crate) fn execute(&self, code: &str, file_name: &str) -> pxs_Var {
+        let Some(execute) = self.vtable.execute else {
+            return self.unsupported("execute");
+        };
+        let mut cstrgen = CStringSafe::new();
+        Self::result(unsafe { execute(self.vtable.opaque, cstrgen.new_string(code), cstrgen.new_string(file_name)) })
+    }
+
+    pub(crate) fn eval(&self, code: &str, name: &str) -> pxs_Var {
+        let Some(eval) = self.vtable.eval else {
+            return self.unsupported("eval");
+        };
+        let mut cstrgen = CStringSafe::new();
+        Self::result(unsafe { eval(self.vtable.opaque, cstrgen.new_string(code), cstrgen.new_string(name)) })
+    }
+
+    pub(crate) fn compile(&self, code: &str, global_scope: &pxs_Var) -> pxs_Var {
+        let Some(compile) = self.vtable.compile else {
+            return self.unsupported("compile");
+        };
+        let mut cstrgen = CStringSafe::new();
+        Self::result(unsafe { compile(self.vtable.opaque, cstrgen.new_string(code), ptr(global_scope)) })
+    }
+
+    pub(crate) fn exec_object(&self, code: &pxs_Var, local_scope: &pxs_Var) -> pxs_Var {
+        let Some(exec_object) = self.vtable.exec_object else {
+            return self.unsupported("exec_object");
+        };
+        Self::result(unsafe { exec_object(self.vtable.opaque, ptr(code), ptr(local_scope)) })
+    }
+
+    pub(crate) fn call(&self, func: &pxs_Var, args: &pxs_Var) -> pxs_Var {
+        let Some(call) = self.vtable.call else {
+            return self.unsupported("call");
+        };
+        Self::result(unsafe { call(self.vtable.opaque, ptr(func), ptr(args)) })
+    }
+
+    pub(crate) fn object_call(&self, obj: &pxs_Var, method: &str, args: &pxs_Var) -> pxs_Var {
+        let Some(object_call) = self.vtable.object_call else {
+            return self.unsupported("object_call");
+        };
+        let mut cstrgen = CStringSafe::new();
+        Self::result(unsafe { object_call(self.vtable.opaque, ptr(obj), cstrgen.new_string(method), ptr(args)) })
+    }
+
+    pub(crate) fn object_get(&self, obj: &pxs_Var, key: &str) -> pxs_Var {
+        let Some(object_get) = self.vtable.object_get else {
+            return self.unsupported("object_get");
+        };
+        let mut cstrgen = CStringSafe::new();
+        Self::result(unsafe { object_get(self.vtable.opaque, ptr(obj), cstrgen.new_string(key)) })
+    }
+
+    pub(crate) fn object_set(&self, obj: &pxs_Var, key: &str, value: &pxs_Var) -> bool {
+        let Some(object_set) = self.vtable.object_set else {
+            return false;
+        };
+        let mut cstrgen = CStringSafe::new();
+        unsafe { object_set(self.vtable.opaque, ptr(obj), cstrgen.new_string(key), ptr(value)) }
+    }
+}
@@ -0,0 +1,136 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+use std::{
+    ffi::CStr,
+    sync::atomic::{AtomicU8, AtomicUsize, Ordering},
+};
+
+use crate::shared::{
+    catalog::{MSG_CLONE_BUDGET, message},
+    var::{pxs_Var, pxs_VarType},
+};
+
+/// What happens when a value crossing between a runtime and the host is over the clone budget.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[allow(non_camel_case_types)]
+pub enum pxs_BudgetMode {
+    /// No checks. The default.
+    pxs_BudgetOff = 0,
+    /// Print a warning and let the value through.
+    pxs_BudgetWarn = 1,
+    /// Replace the value with a `pxs_Exception`.
+    pxs_BudgetError = 2,
+}
+
+/// Max elements (list items, map keys and values). 0 is unlimited.
+static MAX_ELEMENTS: AtomicUsize = AtomicUsize::new(0);
+/// Max string and bytes data. 0 is unlimited.
+static MAX_BYTES: AtomicUsize = AtomicUsize::new(0);
+static MODE: AtomicU8 = AtomicU8::new(pxs_BudgetMode::pxs_BudgetOff as u8);
+
+/// Set the clone budget for all threads.
+pub(crate) fn set(max_elements: usize, max_bytes: usize, mode: pxs_BudgetMode) {
+    MAX_ELEMENTS.store(max_elements, Ordering::SeqCst);
+    MAX_BYTES.store(max_bytes, Ordering::SeqCst);
+    MODE.store(mode as u8, Ordering::SeqCst);
+}
+
+/// Running totals of a measurement.
+struct Usage {
+    elements: usize,
+    bytes: usize,
+    max_elements: usize,
+    max_bytes: usize,
+}
+
+impl Usage {
+    fn over(&self) -> bool {
+        (self.max_elements > 0 && self.elements > self.max_elements)
+            || (self.max_bytes > 0 && self.bytes > self.max_bytes)
+    }
+
+    /// Add up `var`. Stops as soon as the budget is exceeded.
+    fn add(&mut self, var: &pxs_Var) {
+        if self.over() {
+            return;
+        }
+        match var.tag {
+            pxs_VarType::pxs_String => {
+                self.bytes += unsafe { CStr::from_ptr(var.value.string_val) }.to_bytes().len();
+            }
+            pxs_VarType::pxs_Bytes => self.bytes += var.get_bytes().map_or(0, |b| b.len()),
+            pxs_VarType::pxs_TypedArray => self.bytes += var.get_typed_array().map_or(0, |a| a.as_bytes().len()),
+            pxs_VarType::pxs_List => {
+                for item in var.get_list().unwrap().vars.iter() {
+                    self.elements += 1;
+                    self.add(item);
+                    if self.over() {
+                        return;
+                    }
+                }
+            }
+            pxs_VarType::pxs_Map => {
+                let map = var.get_map().unwrap();
+                for key in map.keys() {
+                    self.elements += 2;
+                    self.add(key);
+                    if let Some(value) = map.get_item(key) {
+                        self.add(value);
+                    }
+                    if self.over() {
+                        return;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Check `vars` against the clone budget. Returns the error message if it is over and the mode is `pxs_BudgetError`.
+pub(crate) fn check(vars: &[&pxs_Var]) -> Option<String> {
+    let mode = MODE.load(Ordering::Relaxed);
+    if mode == pxs_BudgetMode::pxs_BudgetOff as u8 {
+        return None;
+    }
+
+    let mut usage = Usage {
+        elements: 0,
+        bytes: 0,
+        max_elements: MAX_ELEMENTS.load(Ordering::Relaxed),
+        max_bytes: MAX_BYTES.load(Ordering::Relaxed),
+    };
+    for var in vars {
+        usage.add(var);
+    }
+    if !usage.over() {
+        return None;
+    }
+
+    let msg = message(
+        MSG_CLONE_BUDGET,
+        "Value is over the clone budget ({0} elements, {1} bytes). Use `pxs_iter_new`, `pxs_Bytes` or `pxs_sync_*` to pass large data without copying it.",
+        &[usage.max_elements.to_string(), usage.max_bytes.to_string()],
+    );
+    if mode == pxs_BudgetMode::pxs_BudgetWarn as u8 {
+        eprintln!("{msg}");
+        None
+    } else {
+        Some(msg)
+    }
+}
+
+/// Check a value crossing the boundary. Returns a exception instead if it is over the budget.
+pub(crate) fn checked(var: pxs_Var) -> pxs_Var {
+    match check(&[&var]) {
+        Some(msg) => pxs_Var::new_exception(msg),
+        None => var,
+    }
+}
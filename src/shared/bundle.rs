@@ -0,0 +1,70 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+//! In memory script bundles.
+//!
+//! A bundle maps file paths to sources so hosts can ship every script inside the binary. The bundle set with
+//! `pxs_use_bundle` is asked after the module resolvers, with the file names each language looks for.
+use std::collections::HashMap;
+
+use etffi::ptr_magic::PtrMagic;
+
+use crate::shared::pxs_Runtime;
+
+/// Scripts by path. Created by `pxs_bundle_new`.
+#[allow(non_camel_case_types)]
+#[derive(Default)]
+pub struct pxs_Bundle {
+    files: HashMap<String, String>,
+}
+
+impl PtrMagic for pxs_Bundle {}
+
+/// `./a\b` is `a/b`.
+fn normalize(path: &str) -> String {
+    let path = path.replace('\\', "/");
+    path.trim_start_matches("./").trim_start_matches('/').to_string()
+}
+
+impl pxs_Bundle {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add or replace the file at `path`.
+    pub(crate) fn add(&mut self, path: &str, contents: String) {
+        self.files.insert(normalize(path), contents);
+    }
+
+    /// Source and path of the module `name` imported by `runtime`. Lua looks for `a.b`, `a/b.lua` and `a/b/init.lua`,
+    /// Python for `a/b.py`, `a/b/__init__.py` and `a/b/__import__.py`, and JS for `a/b` and `a/b.js`.
+    pub(crate) fn find(&self, name: &str, runtime: &pxs_Runtime) -> Option<(String, String)> {
+        let name = normalize(name);
+        let slashed = name.replace('.', "/");
+        let candidates = match runtime {
+            pxs_Runtime::pxs_Lua => vec![name.clone(), format!("{slashed}.lua"), format!("{slashed}/init.lua")],
+            pxs_Runtime::pxs_Python => vec![
+                format!("{slashed}.py"),
+                format!("{slashed}/__init__.py"),
+                format!("{slashed}/__import__.py"),
+            ],
+            pxs_Runtime::pxs_JavaScript => vec![name.clone(), format!("{name}.js")],
+            _ => vec![name.clone()],
+        };
+        for path in candidates {
+            if let Some(contents) = self.files.get(&path) {
+                return Some((contents.clone(), path));
+            }
+        }
+
+        // A Python package without a `__init__.py` is a empty module.
+        let dir = format!("{slashed}/");
+        let is_package = self.files.keys().any(|path| path.starts_with(&dir));
+        (matches!(runtime, pxs_Runtime::pxs_Python) && is_package).then(|| (String::new(), slashed))
+    }
+}
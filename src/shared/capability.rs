@@ -0,0 +1,109 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+use std::{
+    cell::RefCell,
+    collections::{HashMap, hash_map::RandomState},
+    hash::{BuildHasher, Hasher},
+    sync::{LazyLock, Mutex},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Permissions of a token. Each pattern is a exact name (`math.add`, `Person.get_name`), a prefix (`math.*`), or `*`.
+#[derive(Clone, Debug)]
+pub(crate) struct Permissions {
+    patterns: Vec<String>,
+}
+
+impl Permissions {
+    /// Parse a comma separated list of patterns.
+    pub fn parse(permissions: &str) -> Self {
+        Self {
+            patterns: permissions
+                .split(',')
+                .map(str::trim)
+                .filter(|p| !p.is_empty())
+                .map(str::to_string)
+                .collect(),
+        }
+    }
+
+    /// Can `name` (i.e. `module.func` or `Type.method`) be called?
+    pub fn allows(&self, name: &str) -> bool {
+        self.patterns.iter().any(|pattern| {
+            if pattern == "*" {
+                true
+            } else if let Some(prefix) = pattern.strip_suffix('*') {
+                name.starts_with(prefix)
+            } else {
+                pattern == name
+            }
+        })
+    }
+}
+
+/// Issued tokens. Shared between all threads so a token can be checked from a network thread.
+static TOKENS: LazyLock<Mutex<HashMap<String, Permissions>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+
+thread_local! {
+    /// Permissions of the code running on this thread. `None` means everything is allowed.
+    static ACTIVE: RefCell<Option<Permissions>> = const { RefCell::new(None) };
+}
+
+/// Create a new random token string.
+fn new_token() -> String {
+    let mut token = String::new();
+    let nanos = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_nanos()).unwrap_or(0);
+    for i in 0..2u8 {
+        // RandomState is seeded randomly per process and per instance.
+        let mut hasher = RandomState::new().build_hasher();
+        hasher.write_u128(nanos);
+        hasher.write_u8(i);
+        token.push_str(&format!("{:016x}", hasher.finish()));
+    }
+    token
+}
+
+/// Issue a token with these permissions.
+pub(crate) fn issue(permissions: &str) -> String {
+    let mut tokens = TOKENS.lock().unwrap();
+    let mut token = new_token();
+    while tokens.contains_key(&token) {
+        token = new_token();
+    }
+    tokens.insert(token.clone(), Permissions::parse(permissions));
+    token
+}
+
+/// Revoke a token. Returns true if it existed.
+pub(crate) fn revoke(token: &str) -> bool {
+    TOKENS.lock().unwrap().remove(token).is_some()
+}
+
+/// Get the permissions of a token.
+pub(crate) fn lookup(token: &str) -> Option<Permissions> {
+    TOKENS.lock().unwrap().get(token).cloned()
+}
+
+/// Run `func` with `permissions` enforced for every call into host functions on this thread.
+pub(crate) fn with_permissions<T>(permissions: Option<Permissions>, func: impl FnOnce() -> T) -> T {
+    let previous = ACTIVE.with(|active| active.replace(permissions));
+    let res = func();
+    ACTIVE.with(|active| *active.borrow_mut() = previous);
+    res
+}
+
+/// Can the code running on this thread call `name`?
+pub(crate) fn is_allowed(name: &str) -> bool {
+    ACTIVE.with(|active| active.borrow().as_ref().is_none_or(|p| p.allows(name)))
+}
+
+/// Drop all tokens.
+pub(crate) fn clear() {
+    TOKENS.lock().unwrap().clear();
+}
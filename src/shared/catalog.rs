@@ -0,0 +1,103 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+use etffi::{cstring::CStringSafe, ptr_magic::PtrMagic};
+
+use crate::{own_var, shared::{get_message_catalog, var::pxs_Var}};
+
+/// `{0}` expected type, `{1}` found type.
+pub const MSG_INCORRECT_TYPE: &str = "incorrect_type";
+/// `{0}` allowed types, `{1}` found type.
+pub const MSG_INCORRECT_TYPES: &str = "incorrect_types";
+pub const MSG_NULL_PARAMS: &str = "null_params";
+/// `{0}` param name.
+pub const MSG_NULL_PARAM: &str = "null_param";
+/// `{0}` runtime.
+pub const MSG_UNKNOWN_RUNTIME: &str = "unknown_runtime";
+pub const MSG_ITEM_NOT_FOUND: &str = "item_not_found";
+/// `{0}` feature name.
+pub const MSG_FEATURE_NOT_ENABLED: &str = "feature_not_enabled";
+/// `{0}` expected count, `{1}` passed count.
+pub const MSG_EXPECTED_N_ARGS: &str = "expected_n_args";
+/// `{0}` function name.
+pub const MSG_PERMISSION_DENIED: &str = "permission_denied";
+pub const MSG_UNKNOWN_TOKEN: &str = "unknown_token";
+/// `{0}` max elements, `{1}` max bytes.
+pub const MSG_CLONE_BUDGET: &str = "clone_budget";
+pub const MSG_EXEC_LIMIT: &str = "exec_limit";
+pub const MSG_EXEC_SUSPENDED: &str = "exec_suspended";
+/// `{0}` var type.
+pub const MSG_NOT_MIGRATABLE: &str = "not_migratable";
+pub const MSG_FOREIGN_VAR: &str = "foreign_var";
+/// `{0}` runtime that made the var, `{1}` runtime passed.
+pub const MSG_RUNTIME_MISMATCH: &str = "runtime_mismatch";
+/// `{0}` path.
+pub const MSG_INVALID_SCHEMA: &str = "invalid_schema";
+/// `{0}` path, `{1}` expected types, `{2}` found type.
+pub const MSG_SCHEMA_TYPE: &str = "schema_type";
+/// `{0}` path, `{1}` missing key.
+pub const MSG_SCHEMA_REQUIRED: &str = "schema_required";
+/// `{0}` path, `{1}` min, `{2}` found value or length.
+pub const MSG_SCHEMA_MIN: &str = "schema_min";
+/// `{0}` path, `{1}` max, `{2}` found value or length.
+pub const MSG_SCHEMA_MAX: &str = "schema_max";
+/// `{0}` exit status.
+pub const MSG_WORKER_CRASHED: &str = "worker_crashed";
+pub const MSG_COROUTINE_DEAD: &str = "coroutine_dead";
+pub const MSG_COROUTINE_RUNNING: &str = "coroutine_running";
+/// `{0}` function name, `{1}` expected count, `{2}` passed count.
+pub const MSG_SIGNATURE_ARGS: &str = "signature_args";
+/// `{0}` function name, `{1}` param name, `{2}` expected types, `{3}` found type.
+pub const MSG_SIGNATURE_TYPE: &str = "signature_type";
+
+/// Replace `{0}`, `{1}`, ... in `template` with `args`. Unknown placeholders are kept as is.
+fn fill(template: &str, args: &[String]) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let arg = after
+            .find('}')
+            .and_then(|end| after[..end].parse::<usize>().ok().map(|idx| (idx, end)))
+            .and_then(|(idx, end)| args.get(idx).map(|arg| (arg, end)));
+        match arg {
+            Some((arg, end)) => {
+                out.push_str(arg);
+                rest = &after[end + 1..];
+            }
+            None => {
+                out.push('{');
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Build a pixelscript error message.
+///
+/// Asks the host catalog for a template of `key` and falls back to `default`. Both use `{0}`, `{1}`, ... for `args`.
+pub(crate) fn message(key: &str, default: &str, args: &[String]) -> String {
+    let template = match get_message_catalog() {
+        Some(cbk) => {
+            let mut cstrgen = CStringSafe::new();
+            let res = unsafe { cbk(cstrgen.new_string(key), cstrgen.new_string(default)) };
+            if res.is_null() {
+                None
+            } else {
+                let var = own_var!(res);
+                var.get_string().ok().filter(|_| var.is_string())
+            }
+        }
+        None => None,
+    };
+
+    fill(template.as_deref().unwrap_or(default), args)
+}
@@ -0,0 +1,111 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+//! Channels passing data between threads (the host, contexts and script threads).
+//!
+//! Channels are shared by all threads and named by a id. Values are sent as MessagePack (see `msgpack.rs`), so the
+//! receiver gets a deep copy and only data can be sent.
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{
+        Arc, Condvar, LazyLock, Mutex,
+        atomic::{AtomicI32, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use crate::shared::{msgpack, var::pxs_Var};
+
+#[derive(Default)]
+struct Queue {
+    values: VecDeque<Vec<u8>>,
+    /// Set by `close`, wakes waiting receivers.
+    closed: bool,
+}
+
+#[derive(Default)]
+struct Channel {
+    queue: Mutex<Queue>,
+    /// Notified on every send and on close.
+    ready: Condvar,
+}
+
+impl Channel {
+    fn close(&self) {
+        self.queue.lock().unwrap().closed = true;
+        self.ready.notify_all();
+    }
+}
+
+static CHANNELS: LazyLock<Mutex<HashMap<i32, Arc<Channel>>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+static NEXT_ID: AtomicI32 = AtomicI32::new(1);
+
+fn get(id: i32) -> Result<Arc<Channel>, String> {
+    let channels = CHANNELS.lock().unwrap();
+    channels.get(&id).cloned().ok_or_else(|| format!("Channel {id} does not exist."))
+}
+
+/// Create a channel. Returns its id.
+pub(crate) fn new() -> i32 {
+    let id = NEXT_ID.fetch_add(1, Ordering::SeqCst);
+    CHANNELS.lock().unwrap().insert(id, Arc::new(Channel::default()));
+    id
+}
+
+/// Send a copy of `var`. Err if the channel does not exist or `var` is not data.
+pub(crate) fn send(id: i32, var: &pxs_Var) -> Result<(), String> {
+    let channel = get(id)?;
+    let data = msgpack::encode(var)?;
+    channel.queue.lock().unwrap().values.push_back(data);
+    channel.ready.notify_one();
+    Ok(())
+}
+
+/// Receive the oldest value. Waits up to `timeout_ms` for one, forever if negative. None on timeout.
+pub(crate) fn recv(id: i32, timeout_ms: i64) -> Result<Option<pxs_Var>, String> {
+    let channel = get(id)?;
+    let deadline = (timeout_ms >= 0).then(|| Instant::now() + Duration::from_millis(timeout_ms as u64));
+    let mut queue = channel.queue.lock().unwrap();
+    loop {
+        if let Some(data) = queue.values.pop_front() {
+            return msgpack::decode(&data).map(Some);
+        }
+        if queue.closed {
+            return Err(format!("Channel {id} is closed."));
+        }
+        queue = match deadline {
+            None => channel.ready.wait(queue).unwrap(),
+            Some(deadline) => {
+                let now = Instant::now();
+                if now >= deadline {
+                    return Ok(None);
+                }
+                channel.ready.wait_timeout(queue, deadline - now).unwrap().0
+            }
+        };
+    }
+}
+
+/// Number of values waiting in a channel.
+pub(crate) fn len(id: i32) -> Result<usize, String> {
+    Ok(get(id)?.queue.lock().unwrap().values.len())
+}
+
+/// Remove a channel. Receivers already holding it get the values still in it, then a error.
+pub(crate) fn close(id: i32) -> bool {
+    let channel = CHANNELS.lock().unwrap().remove(&id);
+    channel.map(|channel| channel.close()).is_some()
+}
+
+/// Close every channel.
+pub(crate) fn clear() {
+    let channels: Vec<_> = CHANNELS.lock().unwrap().drain().collect();
+    for (_, channel) in channels {
+        channel.close();
+    }
+}
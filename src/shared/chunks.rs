@@ -0,0 +1,127 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+use std::{
+    collections::HashMap,
+    hash::{DefaultHasher, Hash, Hasher},
+    sync::{
+        Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+
+use crate::shared::pxs_Runtime;
+
+/// Bytes of compiled code kept at most. The least recently used chunks are dropped first.
+const MAX_BYTES: usize = 32 * 1024 * 1024;
+
+/// A compiled file.
+struct Chunk {
+    /// Hash of the source it was compiled from.
+    hash: u64,
+    bytes: Vec<u8>,
+    /// `Chunks::clock` of the last `get` or `put`.
+    used: u64,
+}
+
+/// Chunks by runtime and path.
+#[derive(Default)]
+struct Chunks {
+    chunks: HashMap<(i64, String), Chunk>,
+    /// Total size of every chunk.
+    bytes: usize,
+    clock: u64,
+}
+
+impl Chunks {
+    fn tick(&mut self) -> u64 {
+        self.clock += 1;
+        self.clock
+    }
+
+    fn remove(&mut self, key: &(i64, String)) {
+        if let Some(chunk) = self.chunks.remove(key) {
+            self.bytes -= chunk.bytes.len();
+        }
+    }
+
+    /// Drop the least recently used chunks until `extra` more bytes fit.
+    fn make_room(&mut self, extra: usize) {
+        while self.bytes + extra > MAX_BYTES {
+            let Some(key) = self.chunks.iter().min_by_key(|(_, chunk)| chunk.used).map(|(key, _)| key.clone()) else {
+                return;
+            };
+            self.remove(&key);
+        }
+    }
+}
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+/// Shared by every thread, so a new thread does not compile them again.
+static CHUNKS: Mutex<Option<Chunks>> = Mutex::new(None);
+
+fn hash(source: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    source.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Turn the cache on or off. Turning it off drops every chunk.
+pub(crate) fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::SeqCst);
+    if !enabled {
+        invalidate(None);
+    }
+}
+
+pub(crate) fn is_enabled() -> bool {
+    ENABLED.load(Ordering::SeqCst)
+}
+
+/// The cached chunk of `path`, if it was compiled from `source`.
+pub(crate) fn get(runtime: &pxs_Runtime, path: &str, source: &str) -> Option<Vec<u8>> {
+    let mut chunks = CHUNKS.lock().unwrap();
+    let chunks = chunks.as_mut()?;
+    let used = chunks.tick();
+    let chunk = chunks.chunks.get_mut(&(runtime.into_i64(), path.to_string()))?;
+    if chunk.hash != hash(source) {
+        return None;
+    }
+    chunk.used = used;
+    Some(chunk.bytes.clone())
+}
+
+/// Cache the chunk of `path` compiled from `source`. Chunks bigger than `MAX_BYTES` are not kept.
+pub(crate) fn put(runtime: &pxs_Runtime, path: &str, source: &str, bytes: Vec<u8>) {
+    let mut chunks = CHUNKS.lock().unwrap();
+    let chunks = chunks.get_or_insert_with(Chunks::default);
+    let key = (runtime.into_i64(), path.to_string());
+    chunks.remove(&key);
+    if bytes.len() > MAX_BYTES {
+        return;
+    }
+    chunks.make_room(bytes.len());
+    let used = chunks.tick();
+    chunks.bytes += bytes.len();
+    chunks.chunks.insert(key, Chunk { hash: hash(source), bytes, used });
+}
+
+/// Drop the chunks of `path` in every runtime, or every chunk if None. Returns how many were dropped.
+pub(crate) fn invalidate(path: Option<&str>) -> usize {
+    let mut chunks = CHUNKS.lock().unwrap();
+    let Some(chunks) = chunks.as_mut() else {
+        return 0;
+    };
+    let before = chunks.chunks.len();
+    match path {
+        Some(path) => chunks.chunks.retain(|(_, p), _| p != path),
+        None => chunks.chunks.clear(),
+    }
+    chunks.bytes = chunks.chunks.values().map(|chunk| chunk.bytes.len()).sum();
+    before - chunks.chunks.len()
+}
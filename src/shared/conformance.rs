@@ -0,0 +1,358 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+//! Conformance cases every backend should pass.
+//!
+//! A case is a script per language plus the expected result. Scripts pass their result to the host with
+//! `pxs_conformance.report(value)`, or are expected to fail with a error containing a message. The
+//! `pxs_conformance` module only exists while the cases run.
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    sync::{LazyLock, Mutex},
+};
+
+use crate::{
+    rust_api::{self, Module, Object, Value},
+    shared::var::{pxs_Var, pxs_VarT, pxs_VarType},
+};
+
+/// Name of the module the cases use.
+const MODULE: &str = "pxs_conformance";
+
+enum Expected {
+    /// `report` is called with this value.
+    Value(fn() -> pxs_Var),
+    /// Executing fails with a error containing this.
+    Error(&'static str),
+}
+
+struct Case {
+    name: &'static str,
+    category: &'static str,
+    /// Lua, Python and JavaScript (the `pxs_Runtime` order).
+    sources: [&'static str; 3],
+    expected: Expected,
+}
+
+const CASES: &[Case] = &[
+    Case {
+        name: "int",
+        category: "vars",
+        sources: [
+            "local c = require('pxs_conformance')\nc.report(42)",
+            "import pxs_conformance as c\nc.report(42)",
+            "import * as c from 'pxs_conformance';\nc.report(42);",
+        ],
+        expected: Expected::Value(|| pxs_Var::new_i64(42)),
+    },
+    Case {
+        name: "float",
+        category: "vars",
+        sources: [
+            "local c = require('pxs_conformance')\nc.report(1.5)",
+            "import pxs_conformance as c\nc.report(1.5)",
+            "import * as c from 'pxs_conformance';\nc.report(1.5);",
+        ],
+        expected: Expected::Value(|| pxs_Var::new_f64(1.5)),
+    },
+    Case {
+        name: "string",
+        category: "vars",
+        sources: [
+            "local c = require('pxs_conformance')\nc.report('héllo')",
+            "import pxs_conformance as c\nc.report('héllo')",
+            "import * as c from 'pxs_conformance';\nc.report('héllo');",
+        ],
+        expected: Expected::Value(|| pxs_Var::new_string("héllo".to_string())),
+    },
+    Case {
+        name: "bool",
+        category: "vars",
+        sources: [
+            "local c = require('pxs_conformance')\nc.report(true)",
+            "import pxs_conformance as c\nc.report(True)",
+            "import * as c from 'pxs_conformance';\nc.report(true);",
+        ],
+        expected: Expected::Value(|| pxs_Var::new_bool(true)),
+    },
+    Case {
+        name: "null",
+        category: "vars",
+        sources: [
+            "local c = require('pxs_conformance')\nc.report(nil)",
+            "import pxs_conformance as c\nc.report(None)",
+            "import * as c from 'pxs_conformance';\nc.report(null);",
+        ],
+        expected: Expected::Value(pxs_Var::new_null),
+    },
+    Case {
+        name: "list",
+        category: "vars",
+        sources: [
+            "local c = require('pxs_conformance')\nc.report({1, 'two', true})",
+            "import pxs_conformance as c\nc.report([1, 'two', True])",
+            "import * as c from 'pxs_conformance';\nc.report([1, 'two', true]);",
+        ],
+        expected: Expected::Value(|| {
+            pxs_Var::new_list_with(vec![
+                pxs_Var::new_i64(1),
+                pxs_Var::new_string("two".to_string()),
+                pxs_Var::new_bool(true),
+            ])
+        }),
+    },
+    Case {
+        name: "map",
+        category: "vars",
+        sources: [
+            "local c = require('pxs_conformance')\nc.report({hp = 10})",
+            "import pxs_conformance as c\nc.report({'hp': 10})",
+            "import * as c from 'pxs_conformance';\nc.report({hp: 10});",
+        ],
+        expected: Expected::Value(|| {
+            let var = pxs_Var::new_map();
+            var.get_map().unwrap().add_item(pxs_Var::new_string("hp".to_string()), pxs_Var::new_i64(10));
+            var
+        }),
+    },
+    Case {
+        name: "bytes",
+        category: "vars",
+        sources: [
+            "local c = require('pxs_conformance')\nc.report(c.bytes())",
+            "import pxs_conformance as c\nc.report(c.bytes())",
+            "import * as c from 'pxs_conformance';\nc.report(c.bytes());",
+        ],
+        expected: Expected::Value(|| pxs_Var::new_bytes(vec![0, 1, 2])),
+    },
+    Case {
+        name: "function",
+        category: "modules",
+        sources: [
+            "local c = require('pxs_conformance')\nc.report(c.add(2, 3))",
+            "import pxs_conformance as c\nc.report(c.add(2, 3))",
+            "import * as c from 'pxs_conformance';\nc.report(c.add(2, 3));",
+        ],
+        expected: Expected::Value(|| pxs_Var::new_i64(5)),
+    },
+    Case {
+        name: "variable",
+        category: "modules",
+        sources: [
+            "local c = require('pxs_conformance')\nc.report(c.version)",
+            "import pxs_conformance as c\nc.report(c.version)",
+            "import * as c from 'pxs_conformance';\nc.report(c.version);",
+        ],
+        expected: Expected::Value(|| pxs_Var::new_string("1.0".to_string())),
+    },
+    Case {
+        name: "method",
+        category: "objects",
+        sources: [
+            "local c = require('pxs_conformance')\nlocal o = c.Counter()\no:bump()\nc.report(o:bump())",
+            "import pxs_conformance as c\no = c.Counter()\no.bump()\nc.report(o.bump())",
+            "import * as c from 'pxs_conformance';\nconst o = c.Counter();\no.bump();\nc.report(o.bump());",
+        ],
+        expected: Expected::Value(|| pxs_Var::new_i64(2)),
+    },
+    Case {
+        name: "property",
+        category: "objects",
+        sources: [
+            "local c = require('pxs_conformance')\nlocal o = c.Counter()\no:bump()\nc.report(o.count)",
+            "import pxs_conformance as c\no = c.Counter()\no.bump()\nc.report(o.count)",
+            "import * as c from 'pxs_conformance';\nconst o = c.Counter();\no.bump();\nc.report(o.count);",
+        ],
+        expected: Expected::Value(|| pxs_Var::new_i64(1)),
+    },
+    Case {
+        name: "host_error",
+        category: "errors",
+        sources: [
+            "local c = require('pxs_conformance')\nc.fail()",
+            "import pxs_conformance as c\nc.fail()",
+            "import * as c from 'pxs_conformance';\nc.fail();",
+        ],
+        expected: Expected::Error("conformance failure"),
+    },
+    Case {
+        name: "script_error",
+        category: "errors",
+        sources: ["error('boom')", "raise Exception('boom')", "throw new Error('boom');"],
+        expected: Expected::Error("boom"),
+    },
+];
+
+/// Sources set by the host, by runtime id and case name.
+static SOURCES: LazyLock<Mutex<HashMap<(i32, String), String>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+
+thread_local! {
+    /// Value passed to `report` by the running case.
+    static REPORTED: RefCell<Option<pxs_Var>> = const { RefCell::new(None) };
+}
+
+/// Set the source of a case for a runtime, i.e. for a backend from `pxs_register_backend`.
+pub(crate) fn set_source(runtime: i32, case: &str, code: &str) {
+    SOURCES.lock().unwrap().insert((runtime, case.to_string()), code.to_string());
+}
+
+/// The source of `case` for `runtime`. Sources set by the host come first.
+fn source(runtime: i32, case: &Case) -> Option<String> {
+    if let Some(code) = SOURCES.lock().unwrap().get(&(runtime, case.name.to_string())) {
+        return Some(code.clone());
+    }
+    usize::try_from(runtime).ok().and_then(|idx| case.sources.get(idx)).map(|code| code.to_string())
+}
+
+/// Are two vars equal? Numbers compare by value, lists and maps by their items.
+fn equal(a: &pxs_Var, b: &pxs_Var) -> bool {
+    let number = |var: &pxs_Var| match var.tag {
+        pxs_VarType::pxs_Int64 => var.get_i64().ok().map(|n| n as f64),
+        pxs_VarType::pxs_UInt64 => var.get_u64().ok().map(|n| n as f64),
+        pxs_VarType::pxs_Float64 => var.get_f64().ok(),
+        pxs_VarType::pxs_Int32 => var.get_i32().ok().map(|n| n as f64),
+        pxs_VarType::pxs_UInt32 => var.get_u32().ok().map(|n| n as f64),
+        pxs_VarType::pxs_Float32 => var.get_f32().ok().map(|n| n as f64),
+        _ => None,
+    };
+    if let (Some(a), Some(b)) = (number(a), number(b)) {
+        return a == b;
+    }
+    if a.tag != b.tag {
+        return false;
+    }
+    match a.tag {
+        pxs_VarType::pxs_List => {
+            let (a, b) = (&a.get_list().unwrap().vars, &b.get_list().unwrap().vars);
+            a.len() == b.len() && a.iter().zip(b.iter()).all(|(a, b)| equal(a, b))
+        }
+        pxs_VarType::pxs_Map => {
+            let (a, b) = (a.get_map().unwrap(), b.get_map().unwrap());
+            a.len() == b.len()
+                && a.keys().into_iter().all(|key| b.get_item(key).is_some_and(|value| equal(a.get_item(key).unwrap(), value)))
+        }
+        pxs_VarType::pxs_Null => true,
+        pxs_VarType::pxs_String | pxs_VarType::pxs_Bool | pxs_VarType::pxs_Bytes => a == b,
+        _ => false,
+    }
+}
+
+extern "C" fn counter_bump(args: pxs_VarT) -> pxs_VarT {
+    let args = unsafe { rust_api::args(args) };
+    let Some(count) = (unsafe { Object::data::<i64>(&args[0], &args[1]) }) else {
+        return Value::exception("Not a Counter").into_raw();
+    };
+    *count += 1;
+    Value::from(*count).into_raw()
+}
+
+extern "C" fn counter_count(args: pxs_VarT) -> pxs_VarT {
+    let args = unsafe { rust_api::args(args) };
+    match unsafe { Object::data::<i64>(&args[0], &args[1]) } {
+        Some(count) => Value::from(*count).into_raw(),
+        None => Value::exception("Not a Counter").into_raw(),
+    }
+}
+
+extern "C" fn new_counter(_args: pxs_VarT) -> pxs_VarT {
+    let mut object = Object::new("ConformanceCounter", 0i64);
+    object.add_method("bump", counter_bump).add_prop("count", counter_count);
+    object.into_value().into_raw()
+}
+
+fn add_module() {
+    let mut module = Module::new(MODULE);
+    module
+        .add_fn("report", |args| {
+            let value = args.get(1).map(|v| v.as_var().clone()).unwrap_or_else(pxs_Var::new_null);
+            REPORTED.with(|reported| *reported.borrow_mut() = Some(value));
+            Value::null()
+        })
+        .add_fn("add", |args| {
+            let n = |i: usize| args.get(i).and_then(Value::as_i64).unwrap_or(0);
+            Value::from(n(1) + n(2))
+        })
+        .add_fn("bytes", |_| Value::bytes(vec![0, 1, 2]))
+        .add_fn("fail", |_| Value::exception("conformance failure"))
+        .add_object("Counter", new_counter)
+        .add_var("version", "1.0");
+    module.register();
+}
+
+/// Run one case. Returns the status and a message for failures.
+fn run_case(case: &Case, code: &str, execute: &dyn Fn(&str) -> pxs_Var) -> (&'static str, String) {
+    REPORTED.with(|reported| *reported.borrow_mut() = None);
+    let res = execute(code);
+    let error = res.is_exception().then(|| res.get_string().unwrap_or_default());
+    let reported = REPORTED.with(|reported| reported.borrow_mut().take());
+
+    match (&case.expected, error, reported) {
+        (Expected::Error(msg), Some(error), _) if error.contains(msg) => ("pass", String::new()),
+        (Expected::Error(msg), Some(error), _) => ("fail", format!("Expected a error containing '{msg}', got '{error}'.")),
+        (Expected::Error(msg), None, _) => ("fail", format!("Expected a error containing '{msg}'.")),
+        (Expected::Value(_), Some(error), _) => ("fail", error),
+        (Expected::Value(_), None, None) => ("fail", "report was not called.".to_string()),
+        (Expected::Value(expected), None, Some(found)) => {
+            let expected = expected();
+            if equal(&found, &expected) {
+                ("pass", String::new())
+            } else {
+                let (expected, found) = unsafe { (expected.dbg(), found.dbg()) };
+                ("fail", format!("Expected {expected}, found {found}."))
+            }
+        }
+    }
+}
+
+fn new_map(items: Vec<(&str, pxs_Var)>) -> pxs_Var {
+    let var = pxs_Var::new_map();
+    let map = var.get_map().unwrap();
+    for (key, value) in items {
+        map.add_item(pxs_Var::new_string(key.to_string()), value);
+    }
+    var
+}
+
+/// Run every case against `runtime`. `execute` runs code in the runtime and returns a exception on failure.
+///
+/// Returns `{runtime, passed, failed, skipped, cases: [{name, category, status, message}]}`. Cases without a source
+/// for the runtime are skipped.
+pub(crate) fn run(runtime: i32, execute: &dyn Fn(&str) -> pxs_Var) -> pxs_Var {
+    add_module();
+
+    let (mut passed, mut failed, mut skipped) = (0, 0, 0);
+    let mut results = vec![];
+    for case in CASES {
+        let (status, message) = match source(runtime, case) {
+            Some(code) => run_case(case, &code, execute),
+            None => ("skip", String::new()),
+        };
+        match status {
+            "pass" => passed += 1,
+            "fail" => failed += 1,
+            _ => skipped += 1,
+        }
+        results.push(new_map(vec![
+            ("name", pxs_Var::new_string(case.name.to_string())),
+            ("category", pxs_Var::new_string(case.category.to_string())),
+            ("status", pxs_Var::new_string(status.to_string())),
+            ("message", pxs_Var::new_string(message)),
+        ]));
+    }
+
+    crate::remove_module(MODULE);
+
+    new_map(vec![
+        ("runtime", pxs_Var::new_i64(runtime as i64)),
+        ("passed", pxs_Var::new_i64(passed)),
+        ("failed", pxs_Var::new_i64(failed)),
+        ("skipped", pxs_Var::new_i64(skipped)),
+        ("cases", pxs_Var::new_list_with(results)),
+    ])
+}
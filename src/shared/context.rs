@@ -0,0 +1,91 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+//! Independent script worlds.
+//!
+//! Runtimes, the function and object lookups and the module registry are per thread, so a context is a thread owning
+//! its own set of them. The `_ctx` functions send a job to the thread and wait for it.
+use std::{
+    panic::{AssertUnwindSafe, catch_unwind},
+    sync::mpsc::{self, Sender},
+    thread::{self, JoinHandle},
+};
+
+use etffi::ptr_magic::PtrMagic;
+
+type Job = Box<dyn FnOnce() + Send>;
+
+/// A isolated set of runtimes. Created by `pxs_context_new` and freed by `pxs_context_free`.
+#[allow(non_camel_case_types)]
+pub struct pxs_Context {
+    jobs: Option<Sender<Job>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl PtrMagic for pxs_Context {}
+
+/// Moves a value that is not `Send` (i.e. a var pointer) to the context thread. The host is responsible for not
+/// using it on two threads at once.
+pub(crate) struct Sendable<T>(T);
+
+unsafe impl<T> Send for Sendable<T> {}
+
+impl<T> Sendable<T> {
+    pub(crate) fn new(val: T) -> Self {
+        Sendable(val)
+    }
+
+    /// Take the value. A method so closures capture the whole wrapper.
+    pub(crate) fn get(self) -> T {
+        self.0
+    }
+}
+
+impl pxs_Context {
+    /// Start the thread of a context. `start` and `stop` run on it first and last.
+    pub(crate) fn new(start: fn(), stop: fn()) -> Self {
+        let (jobs, receiver) = mpsc::channel::<Job>();
+        let thread = thread::spawn(move || {
+            start();
+            for job in receiver {
+                // A panicking job must not take the context down with it.
+                let _ = catch_unwind(AssertUnwindSafe(job));
+            }
+            stop();
+        });
+        pxs_Context { jobs: Some(jobs), thread: Some(thread) }
+    }
+
+    /// Queue `job` on the context thread without waiting for it. False if the thread is gone.
+    pub(crate) fn post(&self, job: impl FnOnce() + Send + 'static) -> bool {
+        match &self.jobs {
+            Some(jobs) => jobs.send(Box::new(job)).is_ok(),
+            None => false,
+        }
+    }
+
+    /// Run `job` on the context thread and wait for it. None if it panicked.
+    pub(crate) fn run<R: Send + 'static>(&self, job: impl FnOnce() -> R + Send + 'static) -> Option<R> {
+        let (sender, receiver) = mpsc::channel();
+        let job: Job = Box::new(move || {
+            let _ = sender.send(job());
+        });
+        self.jobs.as_ref()?.send(job).ok()?;
+        receiver.recv().ok()
+    }
+}
+
+impl Drop for pxs_Context {
+    fn drop(&mut self) {
+        // Closing the channel ends the thread.
+        self.jobs.take();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
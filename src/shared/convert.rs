@@ -0,0 +1,149 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+//! Checked numeric conversions. Unlike `pxs_getint` and friends failures are reported instead of wrapping,
+//! truncating or returning a value that can not be told apart from a real one.
+use crate::shared::var::{pxs_Var, pxs_VarType};
+
+/// Result of a checked accessor like `pxs_getint_checked`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[allow(non_camel_case_types)]
+pub enum pxs_Status {
+    /// The value was converted.
+    pxs_StatusOk = 0,
+    /// The var or out pointer is null.
+    pxs_StatusNull = 1,
+    /// The var is not a number (or bool).
+    pxs_StatusWrongType = 2,
+    /// The value does not fit in the target type. Also used for NaN and infinity.
+    pxs_StatusOverflow = 3,
+    /// The value would lose precision and the policy is `pxs_ConvertError`.
+    pxs_StatusInexact = 4,
+    /// Called before `pxs_initialize` or after `pxs_finalize`.
+    pxs_StatusNotInitialized = 5,
+}
+
+/// What a checked accessor does with values that can not be represented exactly (i.e. `2.5` as a int).
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[allow(non_camel_case_types)]
+pub enum pxs_Convert {
+    /// Drop the fraction, like a C cast.
+    pxs_ConvertTruncate = 0,
+    /// Round to the nearest, halfway away from zero.
+    pxs_ConvertRound = 1,
+    /// Fail with `pxs_StatusInexact`.
+    pxs_ConvertError = 2,
+}
+
+/// Apply `policy` to a float that becomes a integer.
+fn integral(value: f64, policy: pxs_Convert) -> Result<f64, pxs_Status> {
+    if !value.is_finite() {
+        return Err(pxs_Status::pxs_StatusOverflow);
+    }
+    match policy {
+        pxs_Convert::pxs_ConvertTruncate => Ok(value.trunc()),
+        pxs_Convert::pxs_ConvertRound => Ok(value.round()),
+        pxs_Convert::pxs_ConvertError if value.fract() != 0.0 => Err(pxs_Status::pxs_StatusInexact),
+        pxs_Convert::pxs_ConvertError => Ok(value),
+    }
+}
+
+/// Convert `var` into a i64.
+pub(crate) fn to_i64(var: &pxs_Var, policy: pxs_Convert) -> Result<i64, pxs_Status> {
+    unsafe {
+        match var.tag {
+            pxs_VarType::pxs_Int64 => Ok(var.value.i64_val),
+            pxs_VarType::pxs_UInt64 => i64::try_from(var.value.u64_val).map_err(|_| pxs_Status::pxs_StatusOverflow),
+            pxs_VarType::pxs_Bool => Ok(var.value.bool_val.into()),
+            pxs_VarType::pxs_Int32 => Ok(var.value.i32_val.into()),
+            pxs_VarType::pxs_UInt32 => Ok(var.value.u32_val.into()),
+            pxs_VarType::pxs_Float64 | pxs_VarType::pxs_Float32 => {
+                let value = integral(to_f64(var, policy)?, policy)?;
+                // i64::MAX is not a f64, 2^63 is the first value out of range.
+                if value >= -9_223_372_036_854_775_808.0 && value < 9_223_372_036_854_775_808.0 {
+                    Ok(value as i64)
+                } else {
+                    Err(pxs_Status::pxs_StatusOverflow)
+                }
+            }
+            _ => Err(pxs_Status::pxs_StatusWrongType),
+        }
+    }
+}
+
+/// Convert `var` into a u64. Negative values overflow.
+pub(crate) fn to_u64(var: &pxs_Var, policy: pxs_Convert) -> Result<u64, pxs_Status> {
+    unsafe {
+        match var.tag {
+            pxs_VarType::pxs_Int64 => u64::try_from(var.value.i64_val).map_err(|_| pxs_Status::pxs_StatusOverflow),
+            pxs_VarType::pxs_UInt64 => Ok(var.value.u64_val),
+            pxs_VarType::pxs_Bool => Ok(var.value.bool_val.into()),
+            pxs_VarType::pxs_Int32 => u64::try_from(var.value.i32_val).map_err(|_| pxs_Status::pxs_StatusOverflow),
+            pxs_VarType::pxs_UInt32 => Ok(var.value.u32_val.into()),
+            pxs_VarType::pxs_Float64 | pxs_VarType::pxs_Float32 => {
+                let value = integral(to_f64(var, policy)?, policy)?;
+                // Truncating -0.5 gives -0.0, which is fine.
+                if value > -1.0 && value < 18_446_744_073_709_551_616.0 {
+                    Ok(value as u64)
+                } else {
+                    Err(pxs_Status::pxs_StatusOverflow)
+                }
+            }
+            _ => Err(pxs_Status::pxs_StatusWrongType),
+        }
+    }
+}
+
+/// Convert `var` into a f64. Integers above 2^53 lose precision, `pxs_ConvertError` rejects those.
+pub(crate) fn to_f64(var: &pxs_Var, policy: pxs_Convert) -> Result<f64, pxs_Status> {
+    let exact = |value: f64, back: bool| {
+        if policy == pxs_Convert::pxs_ConvertError && !back {
+            Err(pxs_Status::pxs_StatusInexact)
+        } else {
+            Ok(value)
+        }
+    };
+    unsafe {
+        match var.tag {
+            pxs_VarType::pxs_Int64 => {
+                let value = var.value.i64_val;
+                // 2^63 does not fit back into a i64.
+                exact(value as f64, value as f64 != 9_223_372_036_854_775_808.0 && value as f64 as i64 == value)
+            }
+            pxs_VarType::pxs_UInt64 => {
+                let value = var.value.u64_val;
+                exact(value as f64, value as f64 != 18_446_744_073_709_551_616.0 && value as f64 as u64 == value)
+            }
+            pxs_VarType::pxs_Bool => Ok(if var.value.bool_val { 1.0 } else { 0.0 }),
+            pxs_VarType::pxs_Float64 => Ok(var.value.f64_val),
+            pxs_VarType::pxs_Int32 => Ok(var.value.i32_val.into()),
+            pxs_VarType::pxs_UInt32 => Ok(var.value.u32_val.into()),
+            pxs_VarType::pxs_Float32 => Ok(var.value.f32_val.into()),
+            _ => Err(pxs_Status::pxs_StatusWrongType),
+        }
+    }
+}
+
+/// Convert `var` into a bool. Only the integers 0 and 1 are bools.
+pub(crate) fn to_bool(var: &pxs_Var) -> Result<bool, pxs_Status> {
+    unsafe {
+        match var.tag {
+            pxs_VarType::pxs_Bool => Ok(var.value.bool_val),
+            pxs_VarType::pxs_Int64 | pxs_VarType::pxs_UInt64 | pxs_VarType::pxs_Int32 | pxs_VarType::pxs_UInt32 => {
+                match to_i64(var, pxs_Convert::pxs_ConvertError) {
+                    Ok(0) => Ok(false),
+                    Ok(1) => Ok(true),
+                    _ => Err(pxs_Status::pxs_StatusOverflow),
+                }
+            }
+            _ => Err(pxs_Status::pxs_StatusWrongType),
+        }
+    }
+}
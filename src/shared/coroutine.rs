@@ -0,0 +1,104 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+use etffi::ptr_magic::PtrMagic;
+
+use crate::{
+    own_var, pxs_varcall,
+    shared::{
+        catalog::{MSG_COROUTINE_DEAD, MSG_COROUTINE_RUNNING, message},
+        pxs_Runtime,
+        var::{pxs_Var, pxs_VarT},
+    },
+};
+
+/// State of a `pxs_Coroutine`. Returned by `pxs_coroutine_status`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[allow(non_camel_case_types)]
+pub enum pxs_CoroutineStatus {
+    /// Not started yet, or waiting at a `yield`.
+    pxs_CoroutineSuspended = 0,
+    /// Inside `pxs_coroutine_resume`.
+    pxs_CoroutineRunning = 1,
+    /// Returned or failed. Can not be resumed.
+    pxs_CoroutineDead = 2,
+}
+
+/// A script function driven by the host across frames. Created by `pxs_coroutine_create` and freed by
+/// `pxs_coroutine_free`.
+#[allow(non_camel_case_types)]
+pub struct pxs_Coroutine {
+    /// Runtime the coroutine lives in.
+    rt: pxs_Var,
+    /// Language function made by `PixelScript::new_coroutine`, returns `[done, value]`.
+    resume: pxs_Var,
+    status: pxs_CoroutineStatus,
+}
+
+impl PtrMagic for pxs_Coroutine {}
+
+impl pxs_Coroutine {
+    pub(crate) fn new(rt: pxs_Var, resume: pxs_Var) -> Self {
+        Self { rt, resume, status: pxs_CoroutineStatus::pxs_CoroutineSuspended }
+    }
+
+    /// The built in runtime of the coroutine. None for backends from `pxs_register_backend`.
+    pub(crate) fn runtime(&self) -> Option<pxs_Runtime> {
+        pxs_Runtime::from_var(&self.rt)
+    }
+
+    /// The runtime var of the coroutine.
+    pub(crate) fn rt(&self) -> &pxs_Var {
+        &self.rt
+    }
+
+    pub(crate) fn status(&self) -> pxs_CoroutineStatus {
+        self.status
+    }
+
+    /// Run until the next `yield` (or the end) and return the yielded (or returned) value. `args` is a list.
+    ///
+    /// A exception is returned if the script fails, this also kills the coroutine.
+    pub(crate) fn resume(&mut self, args: pxs_Var) -> pxs_Var {
+        match self.status {
+            pxs_CoroutineStatus::pxs_CoroutineDead => {
+                return pxs_Var::new_exception(message(MSG_COROUTINE_DEAD, "Can not resume a dead coroutine.", &[]));
+            }
+            pxs_CoroutineStatus::pxs_CoroutineRunning => {
+                return pxs_Var::new_exception(message(MSG_COROUTINE_RUNNING, "Coroutine is already running.", &[]));
+            }
+            pxs_CoroutineStatus::pxs_CoroutineSuspended => {}
+        }
+
+        self.status = pxs_CoroutineStatus::pxs_CoroutineRunning;
+        let res = own_var!(pxs_varcall(
+            &self.rt as *const pxs_Var as pxs_VarT,
+            &self.resume as *const pxs_Var as pxs_VarT,
+            args.into_raw(),
+        ));
+        if res.is_exception() {
+            self.status = pxs_CoroutineStatus::pxs_CoroutineDead;
+            return res;
+        }
+
+        let (done, value) = match res.get_list() {
+            Some(list) => (
+                list.get_item(0).map(|done| done.get_bool().unwrap_or(true)).unwrap_or(true),
+                list.get_item(1).cloned().unwrap_or_else(pxs_Var::new_null),
+            ),
+            None => (true, pxs_Var::new_null()),
+        };
+        self.status = if done {
+            pxs_CoroutineStatus::pxs_CoroutineDead
+        } else {
+            pxs_CoroutineStatus::pxs_CoroutineSuspended
+        };
+        value
+    }
+}
@@ -0,0 +1,83 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+//! Structured clone of vars.
+//!
+//! Unlike `Clone` this never takes deleters from the original and is safe on self referencing lists and maps (i.e.
+//! a host adding a list to itself). Lists and maps on the current path are tracked, finding one again is a cycle.
+//! Vars own their items so a cycle can not be copied, it is reported as a error instead.
+use std::{collections::HashSet, ffi::c_void};
+
+use crate::{
+    pxs_error,
+    shared::{
+        PxsRes,
+        var::{pxs_Var, pxs_VarType},
+    },
+};
+
+struct Cloner {
+    /// Lists and maps being copied.
+    path: HashSet<*const c_void>,
+    max_depth: i32,
+}
+
+impl Cloner {
+    /// Enter a list or map at `ptr`. Err on a cycle or when it is nested too deep.
+    fn enter(&mut self, ptr: *const c_void) -> PxsRes<()> {
+        if self.max_depth >= 0 && self.path.len() as i32 >= self.max_depth {
+            return pxs_error!("Can not copy a value nested deeper than {} levels.", self.max_depth);
+        }
+        if !self.path.insert(ptr) {
+            return pxs_error!("Can not copy a value that contains itself.");
+        }
+        Ok(())
+    }
+
+    fn copy(&mut self, var: &pxs_Var) -> PxsRes<pxs_Var> {
+        let mut res = match var.tag {
+            pxs_VarType::pxs_List => {
+                let ptr = unsafe { var.value.list_val } as *const c_void;
+                self.enter(ptr)?;
+                let mut items = vec![];
+                for item in var.get_list().unwrap().vars.iter() {
+                    items.push(self.copy(item)?);
+                }
+                self.path.remove(&ptr);
+                pxs_Var::new_list_with(items)
+            }
+            pxs_VarType::pxs_Map => {
+                let ptr = unsafe { var.value.map_val } as *const c_void;
+                self.enter(ptr)?;
+                let map = var.get_map().unwrap();
+                let res = pxs_Var::new_map();
+                let copy = res.get_map().unwrap();
+                for key in map.keys() {
+                    // Keys are basic types.
+                    copy.add_item(key.clone(), self.copy(map.get_item(key).unwrap())?);
+                }
+                self.path.remove(&ptr);
+                res
+            }
+            pxs_VarType::pxs_Object | pxs_VarType::pxs_Function | pxs_VarType::pxs_Factory => {
+                return pxs_error!("Can not copy a {:#?}, only data can be copied.", var.tag);
+            }
+            // Everything else is data without children.
+            _ => var.clone(),
+        };
+        res.origin = var.origin;
+        Ok(res)
+    }
+}
+
+/// Copy `var` and everything in it. `max_depth` limits how deep lists and maps may be nested, negative for no limit.
+///
+/// Err if `var` contains itself, is nested deeper than `max_depth` or contains Objects, Functions or Factories.
+pub(crate) fn deep_copy(var: &pxs_Var, max_depth: i32) -> PxsRes<pxs_Var> {
+    Cloner { path: HashSet::new(), max_depth }.copy(var)
+}
@@ -0,0 +1,172 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+//! Binary deltas between two snapshots.
+//!
+//! A diff is `PXSD`, a version byte, the base length, the base checksum and the new length (varints, checksum is
+//! FNV-1a), followed by ops:
+//! - `0x00 offset len`: copy `len` bytes of the base starting at `offset`.
+//! - `0x01 len bytes`: insert `len` bytes.
+use std::collections::HashMap;
+
+const MAGIC: &[u8] = b"PXSD";
+const VERSION: u8 = 1;
+const OP_COPY: u8 = 0;
+const OP_INSERT: u8 = 1;
+/// Size of the blocks of the base that are matched against.
+const BLOCK: usize = 16;
+
+fn checksum(data: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in data {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
+
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+/// Reader over a diff.
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], String> {
+        let end = self.pos.checked_add(len).filter(|end| *end <= self.data.len()).ok_or("Diff is truncated.")?;
+        let res = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(res)
+    }
+
+    fn varint(&mut self) -> Result<u64, String> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.take(1)?[0];
+            value |= ((byte & 0x7f) as u64) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err("Diff has a invalid number.".to_string())
+    }
+
+    fn len(&mut self) -> Result<usize, String> {
+        usize::try_from(self.varint()?).map_err(|_| "Diff has a invalid length.".to_string())
+    }
+}
+
+/// Add the pending insert to `out`.
+fn flush_insert(out: &mut Vec<u8>, literal: &[u8]) {
+    if literal.is_empty() {
+        return;
+    }
+    out.push(OP_INSERT);
+    write_varint(out, literal.len() as u64);
+    out.extend_from_slice(literal);
+}
+
+/// Make a diff that turns `old` into `new`.
+pub fn diff(old: &[u8], new: &[u8]) -> Vec<u8> {
+    let mut out = MAGIC.to_vec();
+    out.push(VERSION);
+    write_varint(&mut out, old.len() as u64);
+    write_varint(&mut out, checksum(old));
+    write_varint(&mut out, new.len() as u64);
+
+    // First offset of every block of the base.
+    let mut blocks: HashMap<&[u8], usize> = HashMap::new();
+    for offset in (0..old.len().saturating_sub(BLOCK - 1)).step_by(BLOCK) {
+        blocks.entry(&old[offset..offset + BLOCK]).or_insert(offset);
+    }
+
+    let mut literal_start = 0;
+    let mut pos = 0;
+    while pos + BLOCK <= new.len() {
+        let Some(&offset) = blocks.get(&new[pos..pos + BLOCK]) else {
+            pos += 1;
+            continue;
+        };
+
+        // Grow the match both ways.
+        let mut start = pos;
+        let mut base = offset;
+        while start > literal_start && base > 0 && new[start - 1] == old[base - 1] {
+            start -= 1;
+            base -= 1;
+        }
+        let mut end = pos + BLOCK;
+        let mut base_end = offset + BLOCK;
+        while end < new.len() && base_end < old.len() && new[end] == old[base_end] {
+            end += 1;
+            base_end += 1;
+        }
+
+        flush_insert(&mut out, &new[literal_start..start]);
+        out.push(OP_COPY);
+        write_varint(&mut out, base as u64);
+        write_varint(&mut out, (end - start) as u64);
+
+        pos = end;
+        literal_start = end;
+    }
+    flush_insert(&mut out, &new[literal_start..]);
+
+    out
+}
+
+/// Apply a diff made by `diff` to `base`. Err if the diff is invalid or was made against a different base.
+pub fn apply(base: &[u8], diff: &[u8]) -> Result<Vec<u8>, String> {
+    let mut reader = Reader { data: diff, pos: 0 };
+    if reader.take(MAGIC.len()).ok() != Some(MAGIC) {
+        return Err("Not a pixelscript diff.".to_string());
+    }
+    let version = reader.take(1)?[0];
+    if version != VERSION {
+        return Err(format!("Unsupported diff version {version}."));
+    }
+    let base_len = reader.len()?;
+    let base_checksum = reader.varint()?;
+    if base_len != base.len() || base_checksum != checksum(base) {
+        return Err("Diff was made against a different base.".to_string());
+    }
+    let new_len = reader.len()?;
+
+    let mut out = Vec::with_capacity(new_len.min(base.len() + diff.len()));
+    while reader.pos < diff.len() {
+        match reader.take(1)?[0] {
+            OP_COPY => {
+                let offset = reader.len()?;
+                let len = reader.len()?;
+                let end = offset.checked_add(len).filter(|end| *end <= base.len()).ok_or("Diff copies outside of the base.")?;
+                out.extend_from_slice(&base[offset..end]);
+            }
+            OP_INSERT => {
+                let len = reader.len()?;
+                out.extend_from_slice(reader.take(len)?);
+            }
+            op => return Err(format!("Diff has a unknown op {op}.")),
+        }
+        if out.len() > new_len {
+            return Err("Diff is longer than expected.".to_string());
+        }
+    }
+
+    if out.len() != new_len {
+        return Err("Diff is truncated.".to_string());
+    }
+    Ok(out)
+}
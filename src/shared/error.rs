@@ -0,0 +1,202 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+use std::{ffi::c_char, ptr};
+
+use etffi::{create_raw_string, free_raw_string, ptr_magic::PtrMagic};
+
+use crate::shared::{pxs_Runtime, var::pxs_Var};
+
+/// Kind of error returned by `pxs_exec_ex`.
+#[repr(C)]
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[allow(non_camel_case_types)]
+pub enum pxs_ErrorCode {
+    /// Error while running the script.
+    pxs_RuntimeError = 1,
+    /// The script could not be parsed.
+    pxs_SyntaxError = 2,
+    /// Invalid arguments were passed to pixelscript.
+    pxs_InvalidArgument = 3,
+    /// The script was stopped by `pxs_set_exec_limit`.
+    pxs_TimeoutError = 4,
+    /// The script was suspended by the callback of `pxs_set_tick_callback` and could not yield.
+    pxs_SuspendedError = 5,
+    /// Called before `pxs_initialize` or after `pxs_finalize`.
+    pxs_NotInitialized = 6,
+}
+
+/// Kind of a exception made by the host with `pxs_newerror`, decides what scripts see: a `TypeError`, `ValueError`,
+/// ... in Python and JavaScript. Lua only gets the message.
+#[repr(C)]
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[allow(non_camel_case_types)]
+pub enum pxs_ErrorKind {
+    /// `BaseException` in Python, a error named `Exception` in JavaScript.
+    pxs_GenericError = 0,
+    pxs_TypeError = 1,
+    pxs_ValueError = 2,
+    pxs_KeyError = 3,
+    pxs_IndexError = 4,
+    pxs_NotImplementedError = 5,
+}
+
+impl pxs_ErrorKind {
+    /// Name of the exception class in scripts.
+    pub fn name(&self) -> &'static str {
+        match self {
+            pxs_ErrorKind::pxs_GenericError => "Exception",
+            pxs_ErrorKind::pxs_TypeError => "TypeError",
+            pxs_ErrorKind::pxs_ValueError => "ValueError",
+            pxs_ErrorKind::pxs_KeyError => "KeyError",
+            pxs_ErrorKind::pxs_IndexError => "IndexError",
+            pxs_ErrorKind::pxs_NotImplementedError => "NotImplementedError",
+        }
+    }
+}
+
+/// A structured script error. Free with `pxs_error_free`.
+///
+/// `file` and `traceback` can be null. `line` and `column` are 0 when unknown.
+#[repr(C)]
+#[allow(non_camel_case_types)]
+pub struct pxs_Error {
+    pub code: pxs_ErrorCode,
+    /// The error message without location info.
+    pub message: *mut c_char,
+    pub file: *mut c_char,
+    pub line: i32,
+    pub column: i32,
+    /// The full error as reported by the runtime.
+    pub traceback: *mut c_char,
+}
+
+impl PtrMagic for pxs_Error {}
+
+/// Result of `pxs_call_try`, `pxs_objectcall_try` and `pxs_varcall_try`.
+///
+/// On success `value` is the returned var (free with `pxs_free_var`) and `error` is null. On failure `value` is null
+/// and `error` has the message and traceback (free with `pxs_error_free`).
+#[repr(C)]
+#[allow(non_camel_case_types)]
+pub struct pxs_CallResult {
+    pub ok: bool,
+    pub value: *mut pxs_Var,
+    pub error: *mut pxs_Error,
+}
+
+impl Drop for pxs_Error {
+    fn drop(&mut self) {
+        for string in [self.message, self.file, self.traceback] {
+            if !string.is_null() {
+                unsafe {
+                    free_raw_string!(string);
+                }
+            }
+        }
+    }
+}
+
+impl pxs_Error {
+    /// A error without location info.
+    pub fn new(code: pxs_ErrorCode, message: &str) -> Self {
+        Self {
+            code,
+            message: create_raw_string!(message),
+            file: ptr::null_mut(),
+            line: 0,
+            column: 0,
+            traceback: ptr::null_mut(),
+        }
+    }
+
+    /// Build a error from the message a runtime returned for `file_name`.
+    pub fn from_runtime(runtime: &pxs_Runtime, file_name: &str, error: &str) -> Self {
+        // Python and JS name the error, Lua parser errors end with `near '<token>'`.
+        let is_syntax = ["SyntaxError", "syntax error", "unexpected symbol", "expected near"]
+            .iter()
+            .any(|pattern| error.contains(pattern));
+        let code = if is_syntax {
+            pxs_ErrorCode::pxs_SyntaxError
+        } else {
+            pxs_ErrorCode::pxs_RuntimeError
+        };
+
+        // Python prints the innermost frame last, Lua and JS print it first.
+        let location = match runtime {
+            pxs_Runtime::pxs_Python => find_locations(error, file_name).pop(),
+            _ => find_locations(error, file_name).into_iter().next(),
+        };
+
+        let mut res = Self::new(code, &error_message(runtime, error));
+        res.traceback = create_raw_string!(error);
+        if let Some((line, column)) = location {
+            res.file = create_raw_string!(file_name);
+            res.line = line;
+            res.column = column;
+        }
+        res
+    }
+}
+
+/// Read a number at the start of `s`.
+fn leading_number(s: &str) -> Option<i32> {
+    let end = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    s[..end].parse().ok()
+}
+
+/// Find every `(line, column)` reported for `file_name`. Supports:
+/// - `file:line:column` and `file:line:` (JS, Lua)
+/// - `[string "file"]:line:` (Lua)
+/// - `File "file", line N` (Python)
+fn find_locations(error: &str, file_name: &str) -> Vec<(i32, i32)> {
+    let mut locations = vec![];
+    if file_name.is_empty() {
+        return locations;
+    }
+    for (idx, _) in error.match_indices(file_name) {
+        let rest = &error[idx + file_name.len()..];
+        let rest = rest.strip_prefix("\"]").or_else(|| rest.strip_prefix('"')).unwrap_or(rest);
+
+        if let Some(after) = rest.strip_prefix(", line ") {
+            if let Some(line) = leading_number(after) {
+                locations.push((line, 0));
+            }
+        } else if let Some(after) = rest.strip_prefix(':') {
+            if let Some(line) = leading_number(after) {
+                let after = &after[line.to_string().len()..];
+                let column = after.strip_prefix(':').and_then(leading_number).unwrap_or(0);
+                locations.push((line, column));
+            }
+        }
+    }
+    locations
+}
+
+/// Get the message without location info.
+fn error_message(runtime: &pxs_Runtime, error: &str) -> String {
+    let lines = error.lines().map(str::trim).filter(|l| !l.is_empty());
+    let message = match runtime {
+        // Last line of the traceback: `NameError: name 'x' is not defined`
+        pxs_Runtime::pxs_Python => lines.last(),
+        // First line, the rest is the stack.
+        _ => lines.into_iter().next(),
+    }
+    .unwrap_or(error);
+
+    // Lua prefixes the message with `file:line: `
+    if let pxs_Runtime::pxs_Lua = runtime {
+        let mut parts = message.splitn(3, ':');
+        if let (Some(_), Some(line), Some(rest)) = (parts.next(), parts.next(), parts.next()) {
+            if leading_number(line).is_some() {
+                return rest.trim().to_string();
+            }
+        }
+    }
+    message.to_string()
+}
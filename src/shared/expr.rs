@@ -0,0 +1,533 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+use crate::{
+    pxs_error,
+    shared::{
+        PxsRes, PxsResult,
+        var::{pxs_Var, pxs_VarMap, pxs_VarType},
+    },
+};
+
+/// A token within a expression.
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Int(i64),
+    Float(f64),
+    Str(String),
+    Ident(String),
+    Op(&'static str),
+}
+
+/// Operators sorted so that the longest match is tried first.
+const OPERATORS: [&str; 18] = [
+    "==", "!=", "<=", ">=", "&&", "||", "+", "-", "*", "/", "%", "<", ">", "!", "(", ")", ".", "?",
+];
+
+/// Split a expression into tokens.
+fn tokenize(source: &str) -> PxsRes<Vec<Token>> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = vec![];
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+
+        // Numbers
+        if c.is_ascii_digit() {
+            let start = i;
+            let mut is_float = false;
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                if chars[i] == '.' {
+                    // `1.foo` is not a number.
+                    if is_float || i + 1 >= chars.len() || !chars[i + 1].is_ascii_digit() {
+                        break;
+                    }
+                    is_float = true;
+                }
+                i += 1;
+            }
+            let text: String = chars[start..i].iter().collect();
+            if is_float {
+                tokens.push(Token::Float(text.parse::<f64>().map_err(|e| e.to_string())?));
+            } else {
+                tokens.push(Token::Int(text.parse::<i64>().map_err(|e| e.to_string())?));
+            }
+            continue;
+        }
+
+        // Strings
+        if c == '"' || c == '\'' {
+            let quote = c;
+            let mut text = String::new();
+            i += 1;
+            loop {
+                if i >= chars.len() {
+                    return pxs_error!("Unterminated string in expression.");
+                }
+                let sc = chars[i];
+                if sc == quote {
+                    i += 1;
+                    break;
+                }
+                if sc == '\\' && i + 1 < chars.len() {
+                    i += 1;
+                    text.push(match chars[i] {
+                        'n' => '\n',
+                        't' => '\t',
+                        other => other,
+                    });
+                } else {
+                    text.push(sc);
+                }
+                i += 1;
+            }
+            tokens.push(Token::Str(text));
+            continue;
+        }
+
+        // Identifiers and keywords
+        if c.is_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            let word: String = chars[start..i].iter().collect();
+            // Allow the Lua/Python spelling of logical operators.
+            let token = match word.as_str() {
+                "and" => Token::Op("&&"),
+                "or" => Token::Op("||"),
+                "not" => Token::Op("!"),
+                _ => Token::Ident(word),
+            };
+            tokens.push(token);
+            continue;
+        }
+
+        // Operators
+        let rest: String = chars[i..].iter().take(2).collect();
+        let op = OPERATORS.iter().find(|op| rest.starts_with(**op));
+        if let Some(op) = op {
+            tokens.push(Token::Op(op));
+            i += op.len();
+            continue;
+        }
+        if c == ':' {
+            tokens.push(Token::Op(":"));
+            i += 1;
+            continue;
+        }
+
+        return pxs_error!("Unexpected character '{c}' in expression.");
+    }
+
+    Ok(tokens)
+}
+
+/// A parsed expression.
+#[derive(Debug)]
+enum Expr {
+    Int(i64),
+    Float(f64),
+    Str(String),
+    Bool(bool),
+    Null,
+    Ident(String),
+    Member(Box<Expr>, String),
+    Unary(&'static str, Box<Expr>),
+    Binary(&'static str, Box<Expr>, Box<Expr>),
+    Ternary(Box<Expr>, Box<Expr>, Box<Expr>),
+}
+
+/// Deepest nesting of parentheses, unary and chained operators. Deeper expressions would overflow the stack.
+const MAX_DEPTH: usize = 128;
+
+/// Simple recursive descent parser.
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    /// Nesting of the expression being parsed.
+    depth: usize,
+}
+
+impl Parser {
+    /// Enter one more level of nesting.
+    fn nest(&mut self) -> PxsRes<()> {
+        self.depth += 1;
+        if self.depth > MAX_DEPTH {
+            return pxs_error!("Expression is nested deeper than {MAX_DEPTH} levels.");
+        }
+        Ok(())
+    }
+
+    fn peek_op(&self) -> Option<&'static str> {
+        match self.tokens.get(self.pos) {
+            Some(Token::Op(op)) => Some(op),
+            _ => None,
+        }
+    }
+
+    /// Consume the next token if it is one of `ops`.
+    fn eat(&mut self, ops: &[&'static str]) -> Option<&'static str> {
+        let op = self.peek_op()?;
+        if ops.contains(&op) {
+            self.pos += 1;
+            Some(op)
+        } else {
+            None
+        }
+    }
+
+    fn expect(&mut self, op: &'static str) -> PxsRes<()> {
+        if self.eat(&[op]).is_some() {
+            Ok(())
+        } else {
+            pxs_error!("Expected '{op}' in expression.")
+        }
+    }
+
+    fn ternary(&mut self) -> PxsRes<Expr> {
+        self.nest()?;
+        let mut expr = self.binary(0)?;
+        if self.eat(&["?"]).is_some() {
+            let a = self.ternary()?;
+            self.expect(":")?;
+            let b = self.ternary()?;
+            expr = Expr::Ternary(Box::new(expr), Box::new(a), Box::new(b));
+        }
+        self.depth -= 1;
+        Ok(expr)
+    }
+
+    /// Binary operators from lowest to highest precedence.
+    const LEVELS: [&'static [&'static str]; 6] = [
+        &["||"],
+        &["&&"],
+        &["==", "!="],
+        &["<", "<=", ">", ">="],
+        &["+", "-"],
+        &["*", "/", "%"],
+    ];
+
+    fn binary(&mut self, level: usize) -> PxsRes<Expr> {
+        if level >= Self::LEVELS.len() {
+            return self.unary();
+        }
+        let mut left = self.binary(level + 1)?;
+        // Every chained operator nests the left side one level deeper.
+        let mut levels = 0;
+        while let Some(op) = self.eat(Self::LEVELS[level]) {
+            self.nest()?;
+            levels += 1;
+            let right = self.binary(level + 1)?;
+            left = Expr::Binary(op, Box::new(left), Box::new(right));
+        }
+        self.depth -= levels;
+        Ok(left)
+    }
+
+    fn unary(&mut self) -> PxsRes<Expr> {
+        if let Some(op) = self.eat(&["-", "!"]) {
+            self.nest()?;
+            let inner = self.unary()?;
+            self.depth -= 1;
+            return Ok(Expr::Unary(op, Box::new(inner)));
+        }
+        self.postfix()
+    }
+
+    fn postfix(&mut self) -> PxsRes<Expr> {
+        let mut expr = self.primary()?;
+        let mut levels = 0;
+        while self.eat(&["."]).is_some() {
+            self.nest()?;
+            levels += 1;
+            match self.tokens.get(self.pos).cloned() {
+                Some(Token::Ident(name)) => {
+                    self.pos += 1;
+                    expr = Expr::Member(Box::new(expr), name);
+                }
+                _ => return pxs_error!("Expected a name after '.' in expression."),
+            }
+        }
+        self.depth -= levels;
+        Ok(expr)
+    }
+
+    fn primary(&mut self) -> PxsRes<Expr> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        match token {
+            Some(Token::Int(v)) => Ok(Expr::Int(v)),
+            Some(Token::Float(v)) => Ok(Expr::Float(v)),
+            Some(Token::Str(v)) => Ok(Expr::Str(v)),
+            Some(Token::Ident(name)) => Ok(match name.as_str() {
+                "true" | "True" => Expr::Bool(true),
+                "false" | "False" => Expr::Bool(false),
+                "null" | "nil" | "None" => Expr::Null,
+                _ => Expr::Ident(name),
+            }),
+            Some(Token::Op("(")) => {
+                let inner = self.ternary()?;
+                self.expect(")")?;
+                Ok(inner)
+            }
+            Some(other) => pxs_error!("Unexpected token {:?} in expression.", other),
+            None => pxs_error!("Unexpected end of expression."),
+        }
+    }
+}
+
+/// Parse a expression string.
+fn parse(source: &str) -> PxsRes<Expr> {
+    let mut parser = Parser {
+        tokens: tokenize(source)?,
+        pos: 0,
+        depth: 0,
+    };
+    let expr = parser.ternary()?;
+    if parser.pos < parser.tokens.len() {
+        return pxs_error!("Unexpected trailing input in expression.");
+    }
+    Ok(expr)
+}
+
+/// A number extracted from a `pxs_Var`.
+enum Number {
+    Int(i64),
+    Float(f64),
+}
+
+fn as_number(var: &pxs_Var) -> Option<Number> {
+    match var.tag {
+        pxs_VarType::pxs_Int64 => var.get_i64().ok().map(Number::Int),
+        // Above `i64::MAX` only fits a float.
+        pxs_VarType::pxs_UInt64 => var
+            .get_u64()
+            .ok()
+            .map(|v| i64::try_from(v).map(Number::Int).unwrap_or(Number::Float(v as f64))),
+        pxs_VarType::pxs_Byte => var.get_byte().ok().map(|v| Number::Int(v as i64)),
+        pxs_VarType::pxs_Float64 => var.get_f64().ok().map(Number::Float),
+        pxs_VarType::pxs_Int32 => var.get_i32().ok().map(|v| Number::Int(v as i64)),
+        pxs_VarType::pxs_UInt32 => var.get_u32().ok().map(|v| Number::Int(v as i64)),
+        pxs_VarType::pxs_Float32 => var.get_f32().ok().map(|v| Number::Float(v as f64)),
+        _ => None,
+    }
+}
+
+fn as_f64(n: &Number) -> f64 {
+    match n {
+        Number::Int(v) => *v as f64,
+        Number::Float(v) => *v,
+    }
+}
+
+/// `null`, `false`, `0` and `""` are falsy. Everything else is truthy.
+fn is_truthy(var: &pxs_Var) -> bool {
+    match var.tag {
+        pxs_VarType::pxs_Null => false,
+        pxs_VarType::pxs_Bool => var.get_bool().unwrap_or(false),
+        pxs_VarType::pxs_String => !var.get_string().unwrap_or_default().is_empty(),
+        _ => match as_number(var) {
+            Some(n) => as_f64(&n) != 0.0,
+            None => true,
+        },
+    }
+}
+
+fn values_equal(a: &pxs_Var, b: &pxs_Var) -> bool {
+    if a.is_null() || b.is_null() {
+        return a.is_null() && b.is_null();
+    }
+    match (as_number(a), as_number(b)) {
+        (Some(x), Some(y)) => as_f64(&x) == as_f64(&y),
+        _ => a == b,
+    }
+}
+
+fn arithmetic(op: &str, a: &pxs_Var, b: &pxs_Var) -> PxsResult {
+    // String concatenation
+    if op == "+" && a.is_string() && b.is_string() {
+        return Ok(pxs_Var::new_string(format!(
+            "{}{}",
+            a.get_string()?,
+            b.get_string()?
+        )));
+    }
+
+    let (x, y) = match (as_number(a), as_number(b)) {
+        (Some(x), Some(y)) => (x, y),
+        _ => return pxs_error!("Can not apply '{op}' to {:?} and {:?}.", a.tag, b.tag),
+    };
+
+    if let (Number::Int(x), Number::Int(y), true) = (&x, &y, op != "/") {
+        let res = match op {
+            "+" => x.checked_add(*y),
+            "-" => x.checked_sub(*y),
+            "*" => x.checked_mul(*y),
+            _ => {
+                if *y == 0 {
+                    return pxs_error!("Modulo by zero in expression.");
+                }
+                x.checked_rem_euclid(*y)
+            }
+        };
+        return match res {
+            Some(v) => Ok(pxs_Var::new_i64(v)),
+            None => pxs_error!("Integer overflow in expression."),
+        };
+    }
+
+    let (x, y) = (as_f64(&x), as_f64(&y));
+    let res = match op {
+        "+" => x + y,
+        "-" => x - y,
+        "*" => x * y,
+        "/" => {
+            if y == 0.0 {
+                return pxs_error!("Division by zero in expression.");
+            }
+            x / y
+        }
+        _ => {
+            if y == 0.0 {
+                return pxs_error!("Modulo by zero in expression.");
+            }
+            x.rem_euclid(y)
+        }
+    };
+    Ok(pxs_Var::new_f64(res))
+}
+
+fn compare(op: &str, a: &pxs_Var, b: &pxs_Var) -> PxsResult {
+    let ordering = if a.is_string() && b.is_string() {
+        a.get_string()?.partial_cmp(&b.get_string()?)
+    } else {
+        match (as_number(a), as_number(b)) {
+            (Some(x), Some(y)) => as_f64(&x).partial_cmp(&as_f64(&y)),
+            _ => return pxs_error!("Can not compare {:?} and {:?}.", a.tag, b.tag),
+        }
+    };
+    let ordering = match ordering {
+        Some(o) => o,
+        None => return Ok(pxs_Var::new_bool(false)),
+    };
+    Ok(pxs_Var::new_bool(match op {
+        "<" => ordering.is_lt(),
+        "<=" => ordering.is_le(),
+        ">" => ordering.is_gt(),
+        _ => ordering.is_ge(),
+    }))
+}
+
+/// Evaluates a parsed expression.
+struct Evaluator<'a> {
+    env: &'a pxs_VarMap,
+    getter: &'a dyn Fn(&pxs_Var, &str) -> PxsResult,
+}
+
+impl Evaluator<'_> {
+    fn eval(&self, expr: &Expr) -> PxsResult {
+        match expr {
+            Expr::Int(v) => Ok(pxs_Var::new_i64(*v)),
+            Expr::Float(v) => Ok(pxs_Var::new_f64(*v)),
+            Expr::Str(v) => Ok(pxs_Var::new_string(v.clone())),
+            Expr::Bool(v) => Ok(pxs_Var::new_bool(*v)),
+            Expr::Null => Ok(pxs_Var::new_null()),
+            Expr::Ident(name) => match self.env.get_item(&pxs_Var::new_string(name.clone())) {
+                Some(v) => Ok(v.clone()),
+                None => pxs_error!("Unknown identifier '{name}' in expression."),
+            },
+            Expr::Member(base, key) => {
+                let base = self.eval(base)?;
+                if base.is_map() {
+                    let map = base.get_map().unwrap();
+                    match map.get_item(&pxs_Var::new_string(key.clone())) {
+                        Some(v) => Ok(v.clone()),
+                        None => pxs_error!("Unknown member '{key}' in expression."),
+                    }
+                } else if base.is_object() {
+                    let res = (self.getter)(&base, key)?;
+                    if res.is_exception() {
+                        return pxs_error!("{}", res.get_string()?);
+                    }
+                    Ok(res)
+                } else {
+                    pxs_error!("Can not access '{key}' on {:?}.", base.tag)
+                }
+            }
+            Expr::Unary(op, inner) => {
+                let v = self.eval(inner)?;
+                if *op == "!" {
+                    return Ok(pxs_Var::new_bool(!is_truthy(&v)));
+                }
+                match as_number(&v) {
+                    Some(Number::Int(i)) => match i.checked_neg() {
+                        Some(v) => Ok(pxs_Var::new_i64(v)),
+                        None => pxs_error!("Integer overflow in expression."),
+                    },
+                    Some(Number::Float(f)) => Ok(pxs_Var::new_f64(-f)),
+                    None => pxs_error!("Can not negate {:?}.", v.tag),
+                }
+            }
+            Expr::Binary(op, left, right) => {
+                let a = self.eval(left)?;
+                // Short circuit logical operators.
+                match *op {
+                    "&&" => {
+                        if !is_truthy(&a) {
+                            return Ok(pxs_Var::new_bool(false));
+                        }
+                        return Ok(pxs_Var::new_bool(is_truthy(&self.eval(right)?)));
+                    }
+                    "||" => {
+                        if is_truthy(&a) {
+                            return Ok(pxs_Var::new_bool(true));
+                        }
+                        return Ok(pxs_Var::new_bool(is_truthy(&self.eval(right)?)));
+                    }
+                    _ => {}
+                }
+                let b = self.eval(right)?;
+                match *op {
+                    "==" => Ok(pxs_Var::new_bool(values_equal(&a, &b))),
+                    "!=" => Ok(pxs_Var::new_bool(!values_equal(&a, &b))),
+                    "<" | "<=" | ">" | ">=" => compare(op, &a, &b),
+                    _ => arithmetic(op, &a, &b),
+                }
+            }
+            Expr::Ternary(cond, a, b) => {
+                if is_truthy(&self.eval(cond)?) {
+                    self.eval(a)
+                } else {
+                    self.eval(b)
+                }
+            }
+        }
+    }
+}
+
+/// Evaluate a single expression against a environment.
+///
+/// Only names found in `env` can be referenced. There are no function calls, assignments, or globals so data files
+/// can hold formulas without being able to run arbitrary code.
+///
+/// `getter` is used for member access on `pxs_Object`s (i.e. `weapon.power` where weapon is a script table).
+pub(crate) fn evaluate(
+    source: &str,
+    env: &pxs_VarMap,
+    getter: &dyn Fn(&pxs_Var, &str) -> PxsResult,
+) -> PxsResult {
+    let expr = parse(source)?;
+    Evaluator { env, getter }.eval(&expr)
+}
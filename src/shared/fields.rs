@@ -0,0 +1,186 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+//! Field access on `pxs_Map`s and language objects (Lua tables, Python dicts and objects, JS objects).
+use etffi::cstring::CStringSafe;
+
+use crate::{
+    own_var, pxs_error, pxs_iter_free, pxs_iter_new, pxs_iter_next, pxs_listadd, pxs_newlist, pxs_newstring,
+    pxs_objectcall, pxs_objectget, pxs_objectset,
+    shared::{
+        PxsRes,
+        var::{pxs_Var, pxs_VarT, pxs_VarType},
+    },
+};
+use etffi::ptr_magic::PtrMagic;
+
+/// Get a field from a `pxs_Map` or a language object (Lua table, Python dict/object, JS object).
+///
+/// Returns `None` if the field could not be found.
+#[allow(unused)]
+pub(crate) fn get_field(rt: pxs_VarT, var: &pxs_Var, key: &str) -> Option<pxs_Var> {
+    match var.tag {
+        pxs_VarType::pxs_Map => var.get_map().unwrap().get_item(&pxs_Var::new_string(key.to_string())).cloned(),
+        pxs_VarType::pxs_Object | pxs_VarType::pxs_HostObject => {
+            let mut cstrgen = CStringSafe::new();
+            let var_ptr = var as *const pxs_Var as pxs_VarT;
+            let res = own_var!(pxs_objectget(rt, var_ptr, cstrgen.new_string(key)));
+            if !res.is_exception() {
+                return Some(res);
+            }
+            // Python dicts do not support getattr.
+            let args = pxs_newlist();
+            pxs_listadd(args, pxs_newstring(cstrgen.new_string(key)));
+            let res = own_var!(pxs_objectcall(rt, var_ptr, cstrgen.new_string("__getitem__"), args));
+            if res.is_exception() {
+                None
+            } else {
+                Some(res)
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Get the value of `key` from a iterated language object. `__getitem__` first so dict keys win over attributes.
+fn get_item(rt: pxs_VarT, var: &pxs_Var, key: &pxs_Var) -> Option<pxs_Var> {
+    let mut cstrgen = CStringSafe::new();
+    let args = pxs_newlist();
+    pxs_listadd(args, key.clone().into_raw());
+    let res = own_var!(pxs_objectcall(rt, var as *const pxs_Var as pxs_VarT, cstrgen.new_string("__getitem__"), args));
+    if !res.is_exception() {
+        return Some(res);
+    }
+    get_field(rt, var, &key.get_string().ok()?)
+}
+
+/// Key and value pairs of a `pxs_Map` or a language object (Lua table, Python dict, JS object), in iteration order.
+///
+/// Returns `None` if `var` can not be iterated.
+#[allow(unused)]
+pub(crate) fn get_pairs(rt: pxs_VarT, var: &pxs_Var) -> PxsRes<Option<Vec<(pxs_Var, pxs_Var)>>> {
+    let mut entries = vec![];
+    if let Some(map) = var.get_map() {
+        for k in map.keys() {
+            entries.push((k.clone(), map.get_item(k).unwrap().shallow_copy()));
+        }
+        return Ok(Some(entries));
+    }
+
+    let var_ptr = var as *const pxs_Var as pxs_VarT;
+    let iter = pxs_iter_new(rt, var_ptr);
+    if iter.is_null() {
+        return Ok(None);
+    }
+    let next = || -> PxsRes<Option<(pxs_Var, pxs_Var)>> {
+        let item = pxs_iter_next(iter);
+        if item.is_null() {
+            return Ok(None);
+        }
+        let item = own_var!(item);
+        if item.is_exception() {
+            return pxs_error!("{}", item.get_string()?);
+        }
+        // Lua and JS give `[key, value]`, Python gives the keys of a dict.
+        if let Some(pair) = item.get_list()
+            && pair.len() == 2
+        {
+            return Ok(Some((pair.vars[0].clone(), pair.vars[1].clone())));
+        }
+        match get_item(rt, var, &item) {
+            Some(value) => Ok(Some((item, value))),
+            None => pxs_error!("Can not read a key of a {:#?}.", var.tag),
+        }
+    };
+    let res = loop {
+        match next() {
+            Ok(Some(entry)) => entries.push(entry),
+            Ok(None) => break Ok(()),
+            Err(e) => break Err(e),
+        }
+    };
+    pxs_iter_free(iter);
+    res.map(|_| Some(entries))
+}
+
+/// Entries of a `pxs_Map` or a language object like `get_pairs`. `key` turns the keys into strings.
+///
+/// Returns `None` if `var` can not be iterated.
+#[allow(unused)]
+pub(crate) fn get_entries(
+    rt: pxs_VarT,
+    var: &pxs_Var,
+    key: impl Fn(&pxs_Var) -> PxsRes<String>,
+) -> PxsRes<Option<Vec<(String, pxs_Var)>>> {
+    let Some(pairs) = get_pairs(rt, var)? else {
+        return Ok(None);
+    };
+    let entries = pairs.into_iter().map(|(k, value)| Ok((key(&k)?, value))).collect::<PxsRes<Vec<_>>>()?;
+    Ok(Some(entries))
+}
+
+/// Deepest nesting `to_map` converts. Also stops self referencing tables.
+const MAX_DEPTH: usize = 64;
+
+/// Copy `var` with its language objects (Lua tables, Python dicts, JS objects) turned into `pxs_Map`s, nested ones
+/// too. Objects that can not be iterated (i.e. class instances) and other values are kept as is.
+#[allow(unused)]
+pub(crate) fn to_map(rt: pxs_VarT, var: &pxs_Var, depth: usize) -> PxsRes<pxs_Var> {
+    if depth > MAX_DEPTH {
+        return pxs_error!("Can not convert a value nested deeper than {MAX_DEPTH} levels.");
+    }
+    match var.tag {
+        pxs_VarType::pxs_List => {
+            let items = var.get_list().unwrap().vars.iter().map(|item| to_map(rt, item, depth + 1));
+            Ok(pxs_Var::new_list_with(items.collect::<PxsRes<Vec<_>>>()?))
+        }
+        pxs_VarType::pxs_Map | pxs_VarType::pxs_Object => {
+            let Some(pairs) = get_pairs(rt, var)? else {
+                return Ok(var.clone());
+            };
+            let res = pxs_Var::new_map();
+            for (key, value) in pairs {
+                if !(key.is_string() || key.is_i64() || key.is_u64() || key.is_f64() || key.is_bool()) {
+                    return pxs_error!("A {:#?} can not be a map key.", key.tag);
+                }
+                let value = to_map(rt, &value, depth + 1)?;
+                res.get_map().unwrap().add_item(key, value);
+            }
+            Ok(res)
+        }
+        _ => Ok(var.clone()),
+    }
+}
+
+/// Set a field on a `pxs_Map` or a language object. `value` is cloned.
+#[allow(unused)]
+pub(crate) fn set_field(rt: pxs_VarT, var: &pxs_Var, key: &str, value: &pxs_Var) -> PxsRes<()> {
+    match var.tag {
+        pxs_VarType::pxs_Map => {
+            var.get_map().unwrap().add_item(pxs_Var::new_string(key.to_string()), value.clone());
+            Ok(())
+        }
+        pxs_VarType::pxs_Object | pxs_VarType::pxs_HostObject => {
+            let mut cstrgen = CStringSafe::new();
+            let var_ptr = var as *const pxs_Var as pxs_VarT;
+            if pxs_objectset(rt, var_ptr, cstrgen.new_string(key), value.shallow_copy().into_raw()) {
+                return Ok(());
+            }
+            // Python dicts do not support setattr.
+            let args = pxs_newlist();
+            pxs_listadd(args, pxs_newstring(cstrgen.new_string(key)));
+            pxs_listadd(args, value.shallow_copy().into_raw());
+            let res = own_var!(pxs_objectcall(rt, var_ptr, cstrgen.new_string("__setitem__"), args));
+            if res.is_exception() {
+                return pxs_error!("{}", res.get_string()?);
+            }
+            Ok(())
+        }
+        _ => pxs_error!("Can not set '{key}' on a {:#?}.", var.tag),
+    }
+}
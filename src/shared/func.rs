@@ -8,9 +8,15 @@ use etffi::ptr_magic::{PtrMagic, ThreadSafePointer};
 //
 // Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
 //
-use super::var::pxs_Var;
-use std::collections::HashMap
-;
+use super::{
+    PxsRes, budget, capability, object::get_object, pxs_Opaque, registry, signature::Signature, slots::SlotMap,
+    var::{pxs_DeleterFn, pxs_Var},
+};
+use std::{
+    cell::Cell,
+    collections::HashMap,
+    sync::{Arc, LazyLock, Mutex, atomic::{AtomicBool, Ordering}},
+};
 
 /// Function reference used in C.
 ///
@@ -26,7 +32,25 @@ pub type pxs_Func = unsafe extern "C" fn(args: *mut pxs_Var) -> *mut pxs_Var;
 /// Basic rust structure to track Funcs and opaques together.
 pub struct Function {
     pub name: String,
+    /// Readable name used in usage reports, i.e. `module.func` or `Type.method`.
+    pub usage_name: String,
     pub func: pxs_Func,
+    /// Opaque host data for functions created with `pxs_newfunc`. Null otherwise.
+    pub opaque: pxs_Opaque,
+    /// Frees `opaque` when the function is dropped, for functions owning their opaque (i.e. Rust closures).
+    pub free_opaque: Option<pxs_DeleterFn>,
+    /// Declared arguments, checked before each call. Set by `pxs_addfunc_ex`.
+    pub(crate) signature: Option<Arc<Signature>>,
+}
+
+impl Drop for Function {
+    fn drop(&mut self) {
+        if let Some(free_opaque) = self.free_opaque
+            && !self.opaque.is_null()
+        {
+            unsafe { free_opaque(self.opaque) };
+        }
+    }
 }
 
 unsafe impl Send for Function {}
@@ -34,40 +58,80 @@ unsafe impl Sync for Function {}
 
 /// Lookup state structure
 pub struct FunctionLookup {
-    /// Function hash shared between all runtimes.
+    /// Functions shared between all runtimes.
     ///
-    /// Negative numbers are valid here.
-    pub function_hash: HashMap<i32, Function>,
+    /// Host pinned functions (`pxs_newfunc`) have negative idxs.
+    pub functions: SlotMap<Function>,
 }
 
 impl PtrMagic for FunctionLookup {}
 
 impl FunctionLookup {
     pub fn get_function(&self, idx: i32) -> Option<&Function> {
-        self.function_hash.get(&idx)
+        self.functions.get(idx)
     }
-    pub fn add_function(&mut self, name: &str, func: pxs_Func) -> i32 {
-        // TODO: Allow for negative idxs.
-        self.function_hash.insert(
-            self.function_hash.len() as i32,
-            Function {
-                name: name.to_string(),
-                func,
-            },
-        );
-
-        return (self.function_hash.len() - 1) as i32;
+    pub fn add_function(&mut self, name: &str, usage_name: &str, func: pxs_Func) -> PxsRes<i32> {
+        self.add_function_with_opaque(name, usage_name, func, std::ptr::null_mut())
+    }
+    pub fn add_function_with_opaque(&mut self, name: &str, usage_name: &str, func: pxs_Func, opaque: pxs_Opaque) -> PxsRes<i32> {
+        self.functions.insert(Function {
+            name: name.to_string(),
+            usage_name: usage_name.to_string(),
+            func,
+            opaque,
+            free_opaque: None,
+            signature: None,
+        })
+    }
+    /// Add a function that owns `opaque`. `free_opaque` is called once the function is removed or cleared.
+    pub fn add_owned_function(
+        &mut self,
+        name: &str,
+        usage_name: &str,
+        func: pxs_Func,
+        opaque: pxs_Opaque,
+        free_opaque: pxs_DeleterFn,
+    ) -> PxsRes<i32> {
+        self.functions.insert(Function {
+            name: name.to_string(),
+            usage_name: usage_name.to_string(),
+            func,
+            opaque,
+            free_opaque: Some(free_opaque),
+            signature: None,
+        })
+    }
+    /// Add a function that survives `pxs_clear`. Gets a negative idx.
+    pub fn add_pinned_function(&mut self, name: &str, usage_name: &str, func: pxs_Func, opaque: pxs_Opaque) -> PxsRes<i32> {
+        self.functions.insert_pinned(Function {
+            name: name.to_string(),
+            usage_name: usage_name.to_string(),
+            func,
+            opaque,
+            free_opaque: None,
+            signature: None,
+        })
     }
 }
 
+/// Is API usage being recorded?
+static USAGE_ENABLED: AtomicBool = AtomicBool::new(false);
+/// Number of calls per usage name. Shared between all threads.
+static USAGE: LazyLock<Mutex<HashMap<String, u64>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Usage name of functions created with `pxs_newfunc`.
+pub(crate) const HOST_FUNCTION_NAME: &str = "<function>";
+
 thread_local! {
     static FUNCTION_LOOKUP: ThreadSafePointer<FunctionLookup> = ThreadSafePointer::new_owned(new_function_lookup());
+    /// Opaque of the host function currently being called.
+    static CURRENT_OPAQUE: Cell<pxs_Opaque> = const { Cell::new(std::ptr::null_mut()) };
 }
 
 /// Create a new function lookup.
 fn new_function_lookup() -> *mut FunctionLookup {
     FunctionLookup {
-        function_hash: HashMap::new(),
+        functions: SlotMap::new(),
     }.into_raw()
 }
 
@@ -79,24 +143,154 @@ fn get_function_lookup() -> *mut FunctionLookup {
 }
 
 /// Add a function to the lookup
-pub fn lookup_add_function(name: &str, func: pxs_Func) -> i32 {
+pub fn lookup_add_function(name: &str, usage_name: &str, func: pxs_Func) -> PxsRes<i32> {
+    let lookup = get_function_lookup();
+    unsafe {
+        (*lookup).add_function(name, usage_name, func)
+    }
+}
+
+/// Add a function with host `opaque` data to the lookup.
+pub(crate) fn lookup_add_function_with_opaque(name: &str, usage_name: &str, func: pxs_Func, opaque: pxs_Opaque) -> PxsRes<i32> {
+    let lookup = get_function_lookup();
+    unsafe { (*lookup).add_function_with_opaque(name, usage_name, func, opaque) }
+}
+
+/// Add a function owning its `opaque` to the lookup.
+pub(crate) fn lookup_add_owned_function(
+    name: &str,
+    usage_name: &str,
+    func: pxs_Func,
+    opaque: pxs_Opaque,
+    free_opaque: pxs_DeleterFn,
+) -> PxsRes<i32> {
+    let lookup = get_function_lookup();
+    unsafe { (*lookup).add_owned_function(name, usage_name, func, opaque, free_opaque) }
+}
+
+/// Set the declared arguments of the function at `idx`.
+pub(crate) fn lookup_set_signature(idx: i32, signature: Signature) {
+    let lookup = get_function_lookup();
+    if let Some(function) = unsafe { (*lookup).functions.get_mut(idx) } {
+        function.signature = Some(Arc::new(signature));
+    }
+}
+
+/// Check and coerce `args` against the signature of the function at `idx`, see `Signature::apply`.
+pub(crate) fn check_args(idx: i32, args: &mut Vec<pxs_Var>) -> Result<(), String> {
+    let lookup = get_function_lookup();
+    let (usage_name, signature) = match unsafe { (*lookup).get_function(idx) } {
+        Some(Function { usage_name, signature: Some(signature), .. }) => (usage_name.clone(), signature.clone()),
+        _ => return Ok(()),
+    };
+    signature.apply(&usage_name, args)
+}
+
+/// Add a host function value to the lookup. The same `func` and `opaque` pair always gets the same idx.
+///
+/// Host functions are pinned, factories made by `pxs_newfunc` keep working after `pxs_clear`.
+pub(crate) fn lookup_add_host_function(func: pxs_Func, opaque: pxs_Opaque) -> PxsRes<i32> {
+    let lookup = get_function_lookup();
+    unsafe {
+        let existing = (*lookup).functions.iter().find(|(_, f)| {
+            f.usage_name == HOST_FUNCTION_NAME && f.func as usize == func as usize && f.opaque == opaque
+        });
+        if let Some((idx, _)) = existing {
+            return Ok(idx);
+        }
+        (*lookup).add_pinned_function(HOST_FUNCTION_NAME, HOST_FUNCTION_NAME, func, opaque)
+    }
+}
+
+/// Find the idx of a registered function by its usage name, i.e. `math.add`.
+pub(crate) fn lookup_find_function(usage_name: &str) -> Option<i32> {
     let lookup = get_function_lookup();
     unsafe {
-        (*lookup).add_function(name, func)
+        (*lookup)
+            .functions
+            .iter()
+            .filter(|(_, f)| f.usage_name == usage_name)
+            .map(|(idx, _)| idx)
+            .last()
     }
 }
 
-/// Clear function lookup hash
+/// Copy the function at `idx` for the lookup of another thread. The copy does not own the opaque, the original stays
+/// responsible for freeing it. Both threads use the same opaque, pixelscript does not synchronize it.
+pub(crate) fn lookup_copy_function(idx: i32) -> Option<Function> {
+    let function = unsafe { (*get_function_lookup()).get_function(idx) }?;
+    Some(Function {
+        name: function.name.clone(),
+        usage_name: function.usage_name.clone(),
+        func: function.func,
+        opaque: function.opaque,
+        free_opaque: None,
+        signature: function.signature.clone(),
+    })
+}
+
+/// Add a function copied with `lookup_copy_function`. Returns its idx in this thread's lookup.
+pub(crate) fn lookup_insert_function(function: Function) -> PxsRes<i32> {
+    unsafe { (*get_function_lookup()).functions.insert(function) }
+}
+
+/// Remove the function at `idx`. Returns false if there was none.
+pub(crate) fn lookup_remove_function(idx: i32) -> bool {
+    unsafe { (*get_function_lookup()).functions.remove(idx).is_some() }
+}
+
+/// Is there a function saved at `idx`?
+pub(crate) fn lookup_has_function(idx: i32) -> bool {
+    unsafe { (*get_function_lookup()).get_function(idx).is_some() }
+}
+
+/// Get the opaque of the host function currently being called. Null outside of a `pxs_newfunc` callback.
+pub(crate) fn current_opaque() -> pxs_Opaque {
+    CURRENT_OPAQUE.with(|opaque| opaque.get())
+}
+
+/// Turn API usage recording on or off.
+pub(crate) fn set_usage_recording(enabled: bool) {
+    USAGE_ENABLED.store(enabled, Ordering::SeqCst);
+}
+
+/// Clear recorded API usage.
+pub(crate) fn reset_usage() {
+    USAGE.lock().unwrap().clear();
+}
+
+/// Get the number of calls per function. Functions registered on this thread that were never called are included
+/// with 0 calls.
+pub(crate) fn usage_report() -> HashMap<String, u64> {
+    let mut report = USAGE.lock().unwrap().clone();
+    let lookup = get_function_lookup();
+    for function in unsafe { (*lookup).functions.values() } {
+        report.entry(function.usage_name.clone()).or_insert(0);
+    }
+    report
+}
+
+/// Clear function lookup. Host pinned functions are kept.
+///
+/// Idxs of cleared functions never point to a new function, so stale language callbacks fail instead of calling
+/// the wrong function.
 pub fn clear_function_lookup() {
     unsafe {
-        (*get_function_lookup()).function_hash.clear();
+        (*get_function_lookup()).functions.clear();
+    }
+}
+
+/// Clear function lookup including host pinned functions.
+pub fn clear_function_lookup_all() {
+    unsafe {
+        (*get_function_lookup()).functions.clear_all();
     }
 }
 
 /// Call a function that is saved in the lookup by a idx.
 ///
 /// This should only be used within languages and never from a end user.
-pub unsafe fn call_function(fn_idx: i32, args: Vec<pxs_Var>) -> pxs_Var {
+pub unsafe fn call_function(fn_idx: i32, mut args: Vec<pxs_Var>) -> pxs_Var {
     let func = {
         let fl = get_function_lookup();
         let function = unsafe { (*fl).get_function(fn_idx) };
@@ -106,8 +300,25 @@ pub unsafe fn call_function(fn_idx: i32, args: Vec<pxs_Var>) -> pxs_Var {
 
         let function = function.unwrap();
 
-        function.func
+        if !capability::is_allowed(&function.usage_name) {
+            return pxs_Var::permission_denied_ep(&function.usage_name);
+        }
+
+        if USAGE_ENABLED.load(Ordering::Relaxed) {
+            *USAGE.lock().unwrap().entry(function.usage_name.clone()).or_insert(0) += 1;
+        }
+
+        (function.func, function.opaque)
     };
+    let (func, opaque) = func;
+
+    if let Err(msg) = check_args(fn_idx, &mut args) {
+        return pxs_Var::new_exception(msg);
+    }
+
+    if let Some(msg) = budget::check(&args.iter().collect::<Vec<_>>()) {
+        return pxs_Var::new_exception(msg);
+    }
 
     // Convert the pxs_Var vector into a list.
     // Do this because I don't want to mess with the older code.
@@ -115,7 +326,10 @@ pub unsafe fn call_function(fn_idx: i32, args: Vec<pxs_Var>) -> pxs_Var {
     let args_ptr = args.into_raw();
 
     unsafe {
+        // Restore the previous opaque afterwards so nested calls work.
+        let previous = CURRENT_OPAQUE.with(|current| current.replace(opaque));
         let res = func(args_ptr);
+        CURRENT_OPAQUE.with(|current| current.set(previous));
         // Free args
         let _ = pxs_Var::from_raw(args_ptr);
 
@@ -125,6 +339,14 @@ pub unsafe fn call_function(fn_idx: i32, args: Vec<pxs_Var>) -> pxs_Var {
             pxs_Var::from_raw(res)
         };
 
-        result
+        // Remember which module made the object type, so its bridge artifacts go with the module.
+        if result.is_host_object()
+            && let Some(object) = get_object(result.get_host_idx())
+            && let Some(function) = (*get_function_lookup()).get_function(fn_idx)
+        {
+            registry::register_object_module(&object.type_name, &function.usage_name);
+        }
+
+        budget::checked(result)
     }
 }
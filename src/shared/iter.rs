@@ -0,0 +1,91 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+use std::collections::VecDeque;
+
+use etffi::ptr_magic::PtrMagic;
+
+use crate::{
+    own_var, pxs_varcall,
+    shared::var::{pxs_Var, pxs_VarT},
+};
+
+/// Where the items of a iterator come from.
+enum Source {
+    /// Items that are already on the host (`pxs_List`, `pxs_Map`).
+    Items(VecDeque<pxs_Var>),
+    /// A language function that returns `[item]`, or null once exhausted. Created by `PixelScript::new_iterator`.
+    Lang {
+        /// Runtime the function lives in.
+        rt: pxs_Var,
+        /// Owned reference to the function.
+        next: pxs_Var,
+    },
+    /// Exhausted or failed.
+    Done,
+}
+
+/// A lazy iterator over a script collection. Created by `pxs_iter_new` and freed by `pxs_iter_free`.
+#[allow(non_camel_case_types)]
+pub struct pxs_Iterator {
+    source: Source,
+}
+
+impl PtrMagic for pxs_Iterator {}
+
+impl pxs_Iterator {
+    /// Iterate a `pxs_List` (items) or `pxs_Map` (`[key, value]` lists) on the host.
+    ///
+    /// Items are shallow copies, `var` must outlive the iterator.
+    pub(crate) fn from_var(var: &pxs_Var) -> Option<Self> {
+        let items = if let Some(list) = var.get_list() {
+            list.vars.iter().map(|item| item.shallow_copy()).collect()
+        } else if let Some(map) = var.get_map() {
+            map.keys()
+                .into_iter()
+                .map(|key| {
+                    let value = map.get_item(key).map(|v| v.shallow_copy()).unwrap_or_else(pxs_Var::new_null);
+                    pxs_Var::new_list_with(vec![key.shallow_copy(), value])
+                })
+                .collect()
+        } else {
+            return None;
+        };
+        Some(Self { source: Source::Items(items) })
+    }
+
+    /// Iterate with a language `next` function.
+    pub(crate) fn from_lang(rt: pxs_Var, next: pxs_Var) -> Self {
+        Self { source: Source::Lang { rt, next } }
+    }
+
+    /// Get the next item. `None` once exhausted.
+    ///
+    /// A exception is returned as a item and ends the iteration.
+    pub(crate) fn next(&mut self) -> Option<pxs_Var> {
+        let res = match &mut self.source {
+            Source::Items(items) => return items.pop_front(),
+            Source::Lang { rt, next } => own_var!(pxs_varcall(
+                rt as *const pxs_Var as pxs_VarT,
+                next as *const pxs_Var as pxs_VarT,
+                pxs_Var::new_list().into_raw(),
+            )),
+            Source::Done => return None,
+        };
+
+        if res.is_exception() {
+            self.source = Source::Done;
+            return Some(res);
+        }
+        let item = res.get_list().and_then(|list| list.get_item(0)).map(|item| item.clone());
+        if item.is_none() {
+            self.source = Source::Done;
+        }
+        item
+    }
+}
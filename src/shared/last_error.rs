@@ -0,0 +1,92 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+//! Last error of the FFI on the current thread, for failures that can not be returned (i.e. calls before
+//! `pxs_initialize`).
+use std::{cell::RefCell, ffi::{c_char, c_void}, ptr};
+
+use etffi::ptr_magic::PtrMagic;
+
+use crate::shared::{
+    arena::pxs_PixelArena,
+    context::pxs_Context,
+    convert::pxs_Status,
+    coroutine::pxs_Coroutine,
+    error::{pxs_Error, pxs_ErrorCode},
+    iter::pxs_Iterator,
+    module::{pxs_Class, pxs_Module},
+    object::pxs_PixelObject,
+    thread::pxs_Thread,
+    var::{pxs_Var, pxs_VarType},
+};
+
+/// Message of calls made before `pxs_initialize` or after `pxs_finalize`.
+pub(crate) const NOT_INITIALIZED: &str = "pixelscript is not initialized.";
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Remember `msg` as the last error of this thread.
+pub(crate) fn set(msg: &str) {
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some(msg.to_string()));
+}
+
+/// Take the last error of this thread.
+pub(crate) fn take() -> Option<String> {
+    LAST_ERROR.with(|last| last.borrow_mut().take())
+}
+
+/// Value returned by a FFI function that could not run.
+pub(crate) trait Fallback {
+    fn fallback() -> Self;
+}
+
+impl Fallback for () {
+    fn fallback() -> Self {}
+}
+
+impl Fallback for bool {
+    fn fallback() -> Self {
+        false
+    }
+}
+
+macro_rules! fallback_value {
+    ($value:expr => $($ty:ty),*) => {
+        $(impl Fallback for $ty {
+            fn fallback() -> Self {
+                $value
+            }
+        })*
+    };
+}
+
+fallback_value!(-1 => i32, i64);
+fallback_value!(0 => u32, u64, usize);
+fallback_value!(ptr::null_mut() =>
+    *mut pxs_Module, *mut pxs_Class, *mut pxs_PixelObject, *mut pxs_Context, *mut pxs_Iterator, *mut pxs_Coroutine,
+    *mut pxs_Thread, *mut pxs_PixelArena, *mut c_void, *mut c_char, *mut u8, *mut u16
+);
+fallback_value!(ptr::null() => *const u8, *const c_char);
+fallback_value!(pxs_VarType::pxs_Null => pxs_VarType);
+fallback_value!(pxs_Status::pxs_StatusNotInitialized => pxs_Status);
+
+/// Null is a valid result for vars (i.e. no error from `pxs_exec`), so it is a exception.
+impl Fallback for *mut pxs_Var {
+    fn fallback() -> Self {
+        pxs_Var::new_exception(NOT_INITIALIZED).into_raw()
+    }
+}
+
+/// Null is success for `pxs_exec_ex`.
+impl Fallback for *mut pxs_Error {
+    fn fallback() -> Self {
+        pxs_Error::new(pxs_ErrorCode::pxs_NotInitialized, NOT_INITIALIZED).into_raw()
+    }
+}
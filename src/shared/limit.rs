@@ -0,0 +1,199 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+use std::{cell::RefCell, collections::HashMap, time::Instant};
+
+use crate::shared::{
+    catalog::{MSG_EXEC_LIMIT, MSG_EXEC_SUSPENDED, message},
+    pxs_Runtime, pxs_TickFn,
+};
+
+/// Limits of a runtime. 0 is unlimited.
+#[derive(Clone, Copy)]
+struct ExecLimit {
+    max_instructions: u64,
+    max_millis: u64,
+}
+
+/// The host callback from `pxs_set_tick_callback`.
+#[derive(Clone, Copy)]
+struct Tick {
+    func: pxs_TickFn,
+    every: u64,
+}
+
+/// The `pxs_exec` currently running under a limit.
+struct Run {
+    runtime: i64,
+    limit: ExecLimit,
+    started: Instant,
+    instructions: u64,
+    /// Instructions at the last tick.
+    ticked: u64,
+    tripped: bool,
+    suspended: bool,
+}
+
+/// What the runtime hook should do after `step`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Step {
+    Continue,
+    /// Over the limit, stop the script.
+    Stop,
+    /// The tick callback asked for a suspension. Yield if the script can, otherwise stop it.
+    Suspend,
+}
+
+/// How a run ended.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Outcome {
+    Finished,
+    OverLimit,
+    Suspended,
+}
+
+thread_local! {
+    /// Limits by runtime.
+    static LIMITS: RefCell<HashMap<i64, ExecLimit>> = RefCell::new(HashMap::new());
+    static TICK: RefCell<Option<Tick>> = const { RefCell::new(None) };
+    static RUN: RefCell<Option<Run>> = const { RefCell::new(None) };
+}
+
+/// Set the tick callback, called about every `every` instructions. None removes it.
+pub(crate) fn set_tick(func: Option<pxs_TickFn>, every: u64) {
+    TICK.with(|tick| *tick.borrow_mut() = func.map(|func| Tick { func, every: every.max(1) }));
+}
+
+/// Set the limits of a runtime. Both 0 removes the limit.
+pub(crate) fn set(runtime: &pxs_Runtime, max_instructions: u64, max_millis: u64) {
+    LIMITS.with(|limits| {
+        let mut limits = limits.borrow_mut();
+        if max_instructions == 0 && max_millis == 0 {
+            limits.remove(&runtime.into_i64());
+        } else {
+            limits.insert(runtime.into_i64(), ExecLimit { max_instructions, max_millis });
+        }
+    });
+}
+
+/// Get the `(max_instructions, max_millis)` of a runtime.
+pub(crate) fn get(runtime: &pxs_Runtime) -> Option<(u64, u64)> {
+    LIMITS.with(|limits| {
+        limits
+            .borrow()
+            .get(&runtime.into_i64())
+            .map(|limit| (limit.max_instructions, limit.max_millis))
+    })
+}
+
+/// Start counting for a run of `runtime`. Returns false if the runtime has no limit and there is no tick callback,
+/// or a run is already going on (nested `pxs_exec` calls count towards the outer one).
+pub(crate) fn begin(runtime: &pxs_Runtime) -> bool {
+    let Some((max_instructions, max_millis)) = get(runtime).or(has_tick().then_some((0, 0))) else {
+        return false;
+    };
+    RUN.with(|run| {
+        let mut run = run.borrow_mut();
+        if run.is_some() {
+            return false;
+        }
+        *run = Some(Run {
+            runtime: runtime.into_i64(),
+            limit: ExecLimit { max_instructions, max_millis },
+            started: Instant::now(),
+            instructions: 0,
+            ticked: 0,
+            tripped: false,
+            suspended: false,
+        });
+        true
+    })
+}
+
+/// Count `instructions` for the current run and call the tick callback when it is due. Called by the runtime hooks.
+pub(crate) fn step(runtime: &pxs_Runtime, instructions: u64) -> Step {
+    let due = RUN.with(|run| {
+        let mut run = run.borrow_mut();
+        let Some(run) = run.as_mut() else {
+            return None;
+        };
+        if run.runtime != runtime.into_i64() {
+            return None;
+        }
+        run.instructions += instructions;
+        if (run.limit.max_instructions > 0 && run.instructions > run.limit.max_instructions)
+            || (run.limit.max_millis > 0 && run.started.elapsed().as_millis() as u64 >= run.limit.max_millis)
+        {
+            run.tripped = true;
+        }
+        if run.tripped {
+            return Some(Err(()));
+        }
+        let tick = TICK.with(|tick| *tick.borrow())?;
+        if run.instructions - run.ticked < tick.every {
+            return None;
+        }
+        run.ticked = run.instructions;
+        Some(Ok((tick.func, run.instructions)))
+    });
+
+    match due {
+        None => Step::Continue,
+        Some(Err(())) => Step::Stop,
+        // Called without the borrow, the host may run more code.
+        Some(Ok((func, instructions))) => {
+            if unsafe { func(runtime.clone(), instructions) } {
+                Step::Suspend
+            } else {
+                Step::Continue
+            }
+        }
+    }
+}
+
+/// The hook stops the script after a `Step::Suspend` instead of yielding, so the run ends as suspended.
+pub(crate) fn suspend() {
+    RUN.with(|run| {
+        if let Some(run) = run.borrow_mut().as_mut() {
+            run.suspended = true;
+        }
+    });
+}
+
+/// Is there a tick callback?
+pub(crate) fn has_tick() -> bool {
+    TICK.with(|tick| tick.borrow().is_some())
+}
+
+/// End the current run.
+pub(crate) fn end() -> Outcome {
+    RUN.with(|run| {
+        let Some(run) = run.borrow_mut().take() else {
+            return Outcome::Finished;
+        };
+        if run.tripped
+            || (run.limit.max_millis > 0 && run.started.elapsed().as_millis() as u64 >= run.limit.max_millis)
+        {
+            Outcome::OverLimit
+        } else if run.suspended {
+            Outcome::Suspended
+        } else {
+            Outcome::Finished
+        }
+    })
+}
+
+/// The error returned when a script is stopped.
+pub(crate) fn timeout_message() -> String {
+    message(MSG_EXEC_LIMIT, "Script stopped, execution limit exceeded.", &[])
+}
+
+/// The error returned when a script is suspended by the tick callback and can not yield.
+pub(crate) fn suspended_message() -> String {
+    message(MSG_EXEC_SUSPENDED, "Script suspended by the host.", &[])
+}
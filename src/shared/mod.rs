@@ -7,26 +7,98 @@
 // Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
 //
 use std::{
-    ffi::{CString, c_char, c_void}, sync::{Arc, LazyLock}
+    cell::Cell, ffi::{CString, c_char, c_void}, sync::{Arc, LazyLock, RwLock, RwLockReadGuard, RwLockWriteGuard, atomic::{AtomicI32, Ordering}}
 };
 
-use etffi::{ptr_magic::{PtrMagic, ThreadSafePointer}, cstring::CStringSafe};
+use etffi::{ptr_magic::PtrMagic, cstring::CStringSafe};
 
 use crate::{
     own_var, shared::{var::{pxs_Var, pxs_VarT}}
 };
 
+/// Host supplied language backends.
+pub mod backend;
+/// Guard against deep-cloning large values across the boundary.
+pub mod budget;
+/// In memory script bundles.
+pub mod bundle;
+/// Script modules shared between runtimes.
+pub(crate) mod share;
+/// Field access on maps and language objects.
+pub(crate) mod fields;
+/// Capability tokens limiting which host functions code can call.
+pub mod capability;
+/// Channels passing data between threads.
+pub mod channel;
+/// Compiled chunk cache for required files.
+pub mod chunks;
+/// Host localization of pixelscript error messages.
+pub mod catalog;
+/// Conformance cases for backends.
+pub mod conformance;
+/// Checked numeric conversions.
+pub mod convert;
+/// Script coroutines resumed by the host.
+pub mod coroutine;
+/// Independent script worlds, each on its own thread.
+pub mod context;
+/// Structured clone of vars.
+pub mod deepcopy;
+/// Lazy host iteration over script collections.
+pub mod iter;
+/// Binary diffs between snapshots.
+pub mod delta;
+/// Generational slot map used by the function and object lookups.
+pub mod slots;
+/// Structured script errors.
+pub mod error;
+/// Sandboxed expression evaluation.
+pub mod expr;
+/// Last FFI error of a thread.
+pub mod last_error;
+/// Instruction and time limits for `pxs_exec`.
+pub mod limit;
 /// Helper methods/macros for using PixelScript
 pub mod ffi;
 /// The internal PixelScript function logic.
 pub mod func;
 /// The internal PixelScript Module structure.
 pub mod module;
+/// MessagePack encoding of vars.
+pub mod msgpack;
 /// The internal PixelScript PixelObject logic.
 pub mod object;
+/// Reference counts of vars retained by the host.
+pub mod pin;
+/// Host memory pressure handling.
+pub mod pressure;
+/// Promises resolved by the host.
+pub mod promise;
+/// Per mod error tracking and quarantine.
+pub mod quarantine;
+/// Registry of everything exposed to scripts.
+pub mod registry;
+/// Declared arguments of module callbacks.
+pub mod signature;
+/// Remote scripting console over TCP.
+#[cfg(feature = "remote-console")]
+pub mod remote;
+/// Strict mode for invalid FFI input.
+pub mod strict;
+/// Bulk host to script variable sync.
+pub mod sync;
+/// Script threads running code in parallel with the host.
+pub mod thread;
+/// UTF-16 string conversions.
+pub mod utf16;
 pub mod utils;
+/// Schema validation of vars.
+pub mod validate;
 /// The internal PixelScript Var logic.
 pub mod var;
+/// Runtimes in a child process.
+#[cfg(feature = "script-worker")]
+pub mod worker;
 pub mod arena;
 
 /// cbindgen:ignore
@@ -46,6 +118,169 @@ pub type pxs_LoadFileFn = unsafe extern "C" fn(file_path: *const c_char) -> pxs_
 /// Function Type for reading a Dir. Should return a `pxs_List`
 pub type pxs_ReadDirFn = unsafe extern "C" fn(dir_path: *const c_char) -> pxs_VarT;
 
+/// A module found by a `pxs_ModuleResolverFn`.
+#[repr(C)]
+#[allow(non_camel_case_types)]
+pub struct pxs_ResolvedModule {
+    /// Source of the module, a `pxs_String`. Null if the resolver does not have the module. TRANSFER (NULLABLE)
+    pub source: pxs_VarT,
+    /// Canonical path of the module, a `pxs_String`. Null to keep the requested name. TRANSFER (NULLABLE)
+    pub path: pxs_VarT,
+}
+
+#[allow(non_camel_case_types)]
+/// Function Type for resolving a imported module. Used by `pxs_add_module_resolver`.
+///
+/// name:BORROW
+pub type pxs_ModuleResolverFn = unsafe extern "C" fn(name: *const c_char, runtime: pxs_Runtime) -> pxs_ResolvedModule;
+
+#[allow(non_camel_case_types)]
+/// Function Type for writing a file. Used by `pxs_fs`. Should return false if the file could not be written.
+///
+/// file_path:BORROW
+/// contents:BORROW
+pub type pxs_WriteFileFn = unsafe extern "C" fn(file_path: *const c_char, contents: *const c_char) -> bool;
+
+#[allow(non_camel_case_types)]
+/// Function Type for getting the cost of entering a grid cell. Used by `pxs_path`.
+/// Return a negative number for blocked cells.
+pub type pxs_PathCostFn = unsafe extern "C" fn(x: i32, y: i32) -> f64;
+
+#[allow(non_camel_case_types)]
+/// Function Type for playing a audio event. Used by `pxs_audio`. Should return a host voice id.
+///
+/// event:BORROW
+/// params:BORROW
+pub type pxs_AudioPlayFn = unsafe extern "C" fn(event: *const c_char, params: pxs_VarT) -> i64;
+
+#[allow(non_camel_case_types)]
+/// Function Type for setting a parameter on a playing voice. Used by `pxs_audio`.
+///
+/// name:BORROW
+/// value:BORROW
+pub type pxs_AudioParamFn = unsafe extern "C" fn(voice: i64, name: *const c_char, value: pxs_VarT);
+
+#[allow(non_camel_case_types)]
+/// Function Type for stopping a voice. Used by `pxs_audio`.
+pub type pxs_AudioStopFn = unsafe extern "C" fn(voice: i64);
+
+#[allow(non_camel_case_types)]
+/// Function Type for receiving bound UI value changes. Used by `pxs_ui`.
+///
+/// widget_id:BORROW
+/// value:BORROW
+pub type pxs_UiChangeFn = unsafe extern "C" fn(widget_id: *const c_char, value: pxs_VarT);
+
+#[allow(non_camel_case_types)]
+/// Function Type for reading a time in seconds. Used by `pxs_clock` and `pxs_time`.
+pub type pxs_ClockTimeFn = unsafe extern "C" fn() -> f64;
+
+#[allow(non_camel_case_types)]
+/// Function Type for reading the current frame index. Used by `pxs_clock`.
+pub type pxs_ClockFrameFn = unsafe extern "C" fn() -> i64;
+
+/// A HTTP request made by a script with `pxs_http`. Only valid during the `pxs_HttpHandlerFn` call.
+#[repr(C)]
+#[allow(non_camel_case_types)]
+pub struct pxs_HttpRequest {
+    /// `GET` or `POST`. BORROW
+    pub method: *const c_char,
+    /// BORROW
+    pub url: *const c_char,
+    /// `pxs_Map` of header names to string values. BORROW
+    pub headers: pxs_VarT,
+    /// A string or bytes var, null for no body. BORROW (NULLABLE)
+    pub body: pxs_VarT,
+    /// Timeout in seconds, 0 for the host default.
+    pub timeout: f64,
+}
+
+/// The response to a `pxs_HttpRequest`, filled by the host.
+#[repr(C)]
+#[allow(non_camel_case_types)]
+pub struct pxs_HttpResponse {
+    /// HTTP status code.
+    pub status: i32,
+    /// `pxs_Map` of header names to string values. TRANSFER (NULLABLE)
+    pub headers: pxs_VarT,
+    /// A string or bytes var. TRANSFER (NULLABLE)
+    pub body: pxs_VarT,
+    /// Message of a request that got no response (i.e. a unreachable host), scripts get it as a exception.
+    /// TRANSFER (NULLABLE)
+    pub error: pxs_VarT,
+}
+
+#[allow(non_camel_case_types)]
+/// Function Type for doing the network IO of `pxs_http`. May block, the calling script waits for the response.
+///
+/// request:BORROW
+pub type pxs_HttpHandlerFn = unsafe extern "C" fn(request: *const pxs_HttpRequest) -> pxs_HttpResponse;
+
+/// Level of a `pxs_log` message.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+#[allow(non_camel_case_types)]
+pub enum pxs_LogLevel {
+    pxs_LogDebug = 0,
+    pxs_LogInfo = 1,
+    pxs_LogWarn = 2,
+    pxs_LogError = 3,
+}
+
+#[allow(non_camel_case_types)]
+/// Function Type for receiving script log messages. Used by `pxs_log`.
+///
+/// `file` is empty and `line` is 0 when the location is unknown.
+///
+/// message:BORROW
+/// file:BORROW
+pub type pxs_LogSinkFn = unsafe extern "C" fn(level: pxs_LogLevel, message: *const c_char, file: *const c_char, line: i32);
+
+#[allow(non_camel_case_types)]
+/// Function Type for running a job submitted by a script. Used by `pxs_jobs`.
+///
+/// The host reports completion with `pxs_job_complete(id, result)`, now or later.
+///
+/// kind:BORROW
+/// payload:BORROW
+pub type pxs_JobSubmitFn = unsafe extern "C" fn(id: i64, kind: *const c_char, payload: pxs_VarT);
+
+#[allow(non_camel_case_types)]
+/// Function Type for being notified when a mod gets quarantined.
+///
+/// mod_name:BORROW
+/// error:BORROW
+pub type pxs_QuarantineFn = unsafe extern "C" fn(mod_name: *const c_char, error: *const c_char);
+
+#[allow(non_camel_case_types)]
+/// Function Type for localizing pixelscript error messages.
+///
+/// Return a `pxs_String` template using `{0}`, `{1}`, ... for the arguments, or null to keep `default`.
+///
+/// key:BORROW
+/// default:BORROW
+/// return:TRANSFER
+pub type pxs_MessageCatalogFn = unsafe extern "C" fn(key: *const c_char, default: *const c_char) -> pxs_VarT;
+
+#[allow(non_camel_case_types)]
+/// Function Type for observing host object lifetimes.
+///
+/// type_name:BORROW
+pub type pxs_ObjectObserverFn = unsafe extern "C" fn(event: object::pxs_ObjectEvent, type_name: *const c_char, idx: i32);
+
+#[allow(non_camel_case_types)]
+/// Function Type called when a script GC collects the language object of a host object. Used by
+/// `pxs_set_object_destructor_hook`.
+///
+/// type_name:BORROW
+/// ptr:BORROW
+pub type pxs_ObjectDestructorFn = unsafe extern "C" fn(type_name: *const c_char, ptr: *mut c_void);
+
+#[allow(non_camel_case_types)]
+/// Function Type called periodically while a script runs. Used by `pxs_set_tick_callback`.
+/// `instructions` is the count since the run started. Return true to suspend the script.
+pub type pxs_TickFn = unsafe extern "C" fn(runtime: pxs_Runtime, instructions: u64) -> bool;
+
 #[allow(non_camel_case_types)]
 pub type pxs_Opaque = *mut c_void;
 
@@ -62,14 +297,54 @@ pub(crate) type PxsResult = PxsRes<pxs_Var>;
 pub(crate) struct PixelState {
     pub load_file: Option<pxs_LoadFileFn>,
     pub read_dir: Option<pxs_ReadDirFn>,
+    /// Module resolvers, asked in order before the file reader.
+    pub module_resolvers: Vec<pxs_ModuleResolverFn>,
+    /// Bundle asked after the module resolvers.
+    pub bundle: Option<Arc<bundle::pxs_Bundle>>,
+    /// File writer used by `pxs_fs`.
+    pub write_file: Option<pxs_WriteFileFn>,
+    /// Grid width, height and cost function used by `pxs_path`.
+    pub path_provider: Option<(i32, i32, pxs_PathCostFn)>,
+    /// Play, set param, and stop callbacks used by `pxs_audio`.
+    pub audio: Option<(pxs_AudioPlayFn, pxs_AudioParamFn, pxs_AudioStopFn)>,
+    /// Change callback used by `pxs_ui`.
+    pub ui_change: Option<pxs_UiChangeFn>,
+    /// Game time, real time, and frame callbacks used by `pxs_clock`.
+    pub clock: Option<(pxs_ClockTimeFn, pxs_ClockTimeFn, pxs_ClockFrameFn)>,
+    /// Time source replacing the system clock in `pxs_time`.
+    pub time_source: Option<pxs_ClockTimeFn>,
+    /// `pxs_time.sleep` is allowed.
+    pub allow_sleep: bool,
+    /// Sink and minimum level used by `pxs_log`.
+    pub log_sink: Option<(pxs_LogSinkFn, pxs_LogLevel)>,
+    /// Transport used by `pxs_http`.
+    pub http_handler: Option<pxs_HttpHandlerFn>,
+    /// Job executor used by `pxs_jobs`.
+    pub job_executor: Option<pxs_JobSubmitFn>,
+    /// Errors in a row before a mod is quarantined.
+    pub quarantine_threshold: u32,
+    /// Callback for when a mod is quarantined.
+    pub on_quarantine: Option<pxs_QuarantineFn>,
+    /// Prefix for chunk names of code generated by pixelscript.
+    pub source_identity: String,
+    /// Catalog for localizing pixelscript error messages.
+    pub message_catalog: Option<pxs_MessageCatalogFn>,
+    /// Observer of host object lifetimes.
+    pub object_observer: Option<pxs_ObjectObserverFn>,
+    /// Called when a script GC collects the language object of a host object.
+    pub object_destructor_hook: Option<pxs_ObjectDestructorFn>,
 }
 
-impl PtrMagic for PixelState {}
+/// The State static variable for PixelScript. Shared by all threads, host settings can be changed from any thread.
+static PIXEL_STATE: LazyLock<RwLock<PixelState>> = LazyLock::new(|| RwLock::new(init_state()));
 
-/// The State static variable for PixelScript.
-static PIXEL_STATE: LazyLock<ThreadSafePointer<PixelState>> = LazyLock::new(|| {
-    ThreadSafePointer::<PixelState>::new_owned(init_state())
-});
+fn state() -> RwLockReadGuard<'static, PixelState> {
+    PIXEL_STATE.read().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn state_mut() -> RwLockWriteGuard<'static, PixelState> {
+    PIXEL_STATE.write().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
 
 /// This is a internal macro to create a PxsError type.
 #[macro_export]
@@ -79,32 +354,257 @@ macro_rules! pxs_error {
     }
 }
 
-fn init_state() -> *mut PixelState {
+fn init_state() -> PixelState {
     PixelState{
         load_file: None,
-        read_dir: None
-    }.into_raw()
+        read_dir: None,
+        module_resolvers: vec![],
+        bundle: None,
+        write_file: None,
+        path_provider: None,
+        audio: None,
+        ui_change: None,
+        clock: None,
+        time_source: None,
+        allow_sleep: false,
+        log_sink: None,
+        http_handler: None,
+        job_executor: None,
+        quarantine_threshold: 5,
+        on_quarantine: None,
+        source_identity: String::new(),
+        message_catalog: None,
+        object_observer: None,
+        object_destructor_hook: None,
+    }
 }
 
 /// Set `read_file` function in PixelState global.
 pub(crate) fn set_read_file(func: pxs_LoadFileFn) {
-    unsafe { 
-        (*PIXEL_STATE.get_ptr()).load_file = Some(func);
-    }
+    state_mut().load_file = Some(func);
+}
+
+thread_local! {
+    /// File readers of a `pxs_Context` thread, overriding the global ones.
+    static THREAD_FILE_READERS: Cell<(Option<pxs_LoadFileFn>, Option<pxs_ReadDirFn>)> = const { Cell::new((None, None)) };
+}
+
+/// Set the file readers of this thread, i.e. of a `pxs_Context`.
+pub(crate) fn set_thread_file_readers(load_file: Option<pxs_LoadFileFn>, read_dir: Option<pxs_ReadDirFn>) {
+    THREAD_FILE_READERS.with(|readers| readers.set((load_file, read_dir)));
 }
 
 /// Set `read_dir` function in PixelState global
 pub(crate) fn set_read_dir(func: pxs_ReadDirFn) {
-    unsafe {
-        (*PIXEL_STATE.get_ptr()).read_dir = Some(func);
-    }
+    state_mut().read_dir = Some(func);
+}
+
+/// Add a resolver to the end of the `module_resolvers` chain.
+pub(crate) fn add_module_resolver(func: pxs_ModuleResolverFn) {
+    state_mut().module_resolvers.push(func);
+}
+
+/// Remove every module resolver.
+pub(crate) fn clear_module_resolvers() {
+    state_mut().module_resolvers.clear();
+}
+
+/// Set the `bundle` in PixelState global. None removes it.
+pub(crate) fn set_bundle(bundle: Option<bundle::pxs_Bundle>) {
+    state_mut().bundle = bundle.map(Arc::new);
+}
+
+/// The file and directory readers of this thread, the ones of this thread's context first.
+pub(crate) fn get_file_readers() -> (Option<pxs_LoadFileFn>, Option<pxs_ReadDirFn>) {
+    let (load_file, read_dir) = THREAD_FILE_READERS.with(|readers| readers.get());
+    let state = state();
+    (load_file.or(state.load_file), read_dir.or(state.read_dir))
+}
+
+/// Set `write_file` function in PixelState global.
+pub(crate) fn set_write_file(func: pxs_WriteFileFn) {
+    state_mut().write_file = Some(func);
+}
+
+/// Get the `write_file` function set by the host.
+#[allow(unused)]
+pub(crate) fn get_write_file() -> Option<pxs_WriteFileFn> {
+    state().write_file
+}
+
+/// Set the `path_provider` in PixelState global.
+pub(crate) fn set_path_provider(width: i32, height: i32, func: pxs_PathCostFn) {
+    state_mut().path_provider = Some((width, height, func));
+}
+
+/// Get the `path_provider` set by the host.
+#[allow(unused)]
+pub(crate) fn get_path_provider() -> Option<(i32, i32, pxs_PathCostFn)> {
+    state().path_provider
+}
+
+/// Set the `audio` callbacks in PixelState global.
+pub(crate) fn set_audio_callbacks(play: pxs_AudioPlayFn, set_param: pxs_AudioParamFn, stop: pxs_AudioStopFn) {
+    state_mut().audio = Some((play, set_param, stop));
+}
+
+/// Get the `audio` callbacks set by the host.
+#[allow(unused)]
+pub(crate) fn get_audio_callbacks() -> Option<(pxs_AudioPlayFn, pxs_AudioParamFn, pxs_AudioStopFn)> {
+    state().audio
+}
+
+/// Set the `ui_change` callback in PixelState global.
+pub(crate) fn set_ui_change(func: pxs_UiChangeFn) {
+    state_mut().ui_change = Some(func);
+}
+
+/// Get the `ui_change` callback set by the host.
+#[allow(unused)]
+pub(crate) fn get_ui_change() -> Option<pxs_UiChangeFn> {
+    state().ui_change
+}
+
+/// Set the `clock` callbacks in PixelState global.
+pub(crate) fn set_clock_callbacks(game_time: pxs_ClockTimeFn, real_time: pxs_ClockTimeFn, frame: pxs_ClockFrameFn) {
+    state_mut().clock = Some((game_time, real_time, frame));
+}
+
+/// Get the `clock` callbacks set by the host.
+#[allow(unused)]
+pub(crate) fn get_clock_callbacks() -> Option<(pxs_ClockTimeFn, pxs_ClockTimeFn, pxs_ClockFrameFn)> {
+    state().clock
+}
+
+/// Set the `time_source` in PixelState global. None restores the system clock.
+pub(crate) fn set_time_source(func: Option<pxs_ClockTimeFn>) {
+    state_mut().time_source = func;
+}
+
+/// Get the `time_source` set by the host.
+#[allow(unused)]
+pub(crate) fn get_time_source() -> Option<pxs_ClockTimeFn> {
+    state().time_source
+}
+
+/// Allow or forbid `pxs_time.sleep`.
+pub(crate) fn set_allow_sleep(allow: bool) {
+    state_mut().allow_sleep = allow;
+}
+
+/// Is `pxs_time.sleep` allowed.
+#[allow(unused)]
+pub(crate) fn get_allow_sleep() -> bool {
+    state().allow_sleep
+}
+
+/// Set the `log_sink` in PixelState global. None restores the default output.
+pub(crate) fn set_log_sink(sink: Option<(pxs_LogSinkFn, pxs_LogLevel)>) {
+    state_mut().log_sink = sink;
+}
+
+/// Get the `log_sink` set by the host.
+#[allow(unused)]
+pub(crate) fn get_log_sink() -> Option<(pxs_LogSinkFn, pxs_LogLevel)> {
+    state().log_sink
+}
+
+/// Set the `http_handler` in PixelState global. None removes it.
+pub(crate) fn set_http_handler(func: Option<pxs_HttpHandlerFn>) {
+    state_mut().http_handler = func;
+}
+
+/// Get the `http_handler` set by the host.
+#[allow(unused)]
+pub(crate) fn get_http_handler() -> Option<pxs_HttpHandlerFn> {
+    state().http_handler
+}
+
+/// Set the `job_executor` callback in PixelState global.
+pub(crate) fn set_job_executor(func: pxs_JobSubmitFn) {
+    state_mut().job_executor = Some(func);
+}
+
+/// Get the `job_executor` callback set by the host.
+#[allow(unused)]
+pub(crate) fn get_job_executor() -> Option<pxs_JobSubmitFn> {
+    state().job_executor
+}
+
+/// Set the `on_quarantine` callback in PixelState global.
+pub(crate) fn set_on_quarantine(func: pxs_QuarantineFn) {
+    state_mut().on_quarantine = Some(func);
+}
+
+/// Set the `quarantine_threshold` in PixelState global. Minimum is 1.
+pub(crate) fn set_quarantine_threshold(threshold: u32) {
+    state_mut().quarantine_threshold = threshold.max(1);
+}
+
+/// Get the quarantine threshold and callback.
+pub(crate) fn get_quarantine() -> (u32, Option<pxs_QuarantineFn>) {
+    let state = state();
+    (state.quarantine_threshold, state.on_quarantine)
+}
+
+/// Set the `source_identity` in PixelState global.
+pub(crate) fn set_source_identity(prefix: &str) {
+    state_mut().source_identity = prefix.to_string();
+}
+
+/// Set the `message_catalog` callback in PixelState global.
+pub(crate) fn set_message_catalog(func: pxs_MessageCatalogFn) {
+    state_mut().message_catalog = Some(func);
+}
+
+/// Get the `message_catalog` callback set by the host.
+pub(crate) fn get_message_catalog() -> Option<pxs_MessageCatalogFn> {
+    state().message_catalog
+}
+
+/// Set the `object_observer` callback in PixelState global.
+pub(crate) fn set_object_observer(func: pxs_ObjectObserverFn) {
+    state_mut().object_observer = Some(func);
+}
+
+/// Get the `object_observer` callback set by the host.
+pub(crate) fn get_object_observer() -> Option<pxs_ObjectObserverFn> {
+    state().object_observer
+}
+
+/// Set the `object_destructor_hook` callback in PixelState global.
+pub(crate) fn set_object_destructor_hook(func: pxs_ObjectDestructorFn) {
+    state_mut().object_destructor_hook = Some(func);
+}
+
+/// Get the `object_destructor_hook` callback set by the host.
+pub(crate) fn get_object_destructor_hook() -> Option<pxs_ObjectDestructorFn> {
+    state().object_destructor_hook
+}
+
+/// Next VM id. Each thread has its own set of runtimes (a VM).
+static NEXT_VM: AtomicI32 = AtomicI32::new(0);
+
+thread_local! {
+    static CURRENT_VM: Cell<i32> = Cell::new(NEXT_VM.fetch_add(1, Ordering::Relaxed));
+}
+
+/// Get the id of the VM (the runtimes of this thread).
+pub(crate) fn current_vm() -> i32 {
+    CURRENT_VM.with(|vm| vm.get())
+}
+
+/// Chunk name for code generated by pixelscript, i.e. `<create_Person>`. Includes the source identity prefix.
+#[allow(unused)]
+pub(crate) fn internal_chunk_name(name: &str) -> String {
+    format!("<{}{name}>", state().source_identity)
 }
 
 /// Read a file using pxs api.
 /// This must be set by host language.
 pub fn read_file(file_path: &str) -> String {
-    // Get callback
-    let cbk = unsafe { (*PIXEL_STATE.get_ptr()).load_file };
+    // Get callback, the one of this thread's context first.
+    let cbk = get_file_readers().0;
     if cbk.is_none() {
         return String::new();
     }
@@ -124,10 +624,30 @@ pub fn read_file(file_path: &str) -> String {
     var.get_string().unwrap()
 }
 
+/// Source and canonical path of the module `name` imported by `runtime`, from the first resolver that has it.
+/// Resolvers are asked in the order they were added, then the bundle. None to fall back to the file reader.
+#[allow(unused)]
+pub(crate) fn resolve_module(name: &str, runtime: pxs_Runtime) -> Option<(String, String)> {
+    // Copy them out, resolvers may call back into pixelscript.
+    let resolvers = state().module_resolvers.clone();
+    let mut cstring = CStringSafe::new();
+    for resolver in resolvers {
+        let res = unsafe { resolver(cstring.new_string(name), runtime.clone()) };
+        let take = |var: pxs_VarT| (!var.is_null()).then(|| own_var!(var)).and_then(|var| var.get_string().ok());
+        let (source, path) = (take(res.source), take(res.path));
+        if let Some(source) = source {
+            return Some((source, path.unwrap_or_else(|| name.to_string())));
+        }
+    }
+
+    let bundle = state().bundle.clone();
+    bundle.and_then(|bundle| bundle.find(name, &runtime))
+}
+
 /// Read a Directory using pxs api.
 /// This must be set by host language.
 pub fn read_file_dir(dir_path: &str) -> Vec<String> {
-    let cbk = unsafe { (*PIXEL_STATE.get_ptr()).read_dir };
+    let cbk = get_file_readers().1;
     if cbk.is_none() {
         return vec![];
     }
@@ -172,12 +692,26 @@ pub trait PixelScript {
     /// Add a global module to the runtime.
     fn add_module(source: Arc<module::pxs_Module>);
 
+    /// Remove a module added with `add_module`, including its child modules. Cached bridges of `object_types` (the
+    /// object types returned by the module) are dropped too, so a reloaded module does not get the old ones.
+    fn remove_module(source: &module::pxs_Module, object_types: &[String]);
+
     /// Execute a script in this runtime.
     fn execute(code: &str, file_name: &str) -> PxsResult;
 
+    /// Execute a script and return its result: a top level `return` in Lua, the last expression statement in Python,
+    /// the completion value in JavaScript.
+    fn execute_with_result(code: &str, file_name: &str) -> PxsResult;
+
     /// Evaluate a script in this runtime. Returns a pxs_Var.
     fn eval(code: &str, name: &str) -> PxsResult;
 
+    /// Evaluate a script like `eval` with the items of the `bindings` map (string keys) as local variables.
+    fn eval_with(code: &str, name: &str, bindings: &pxs_Var) -> PxsResult;
+
+    /// Check if the script overrides `method` of a object, with a subclass or a function assigned to the object.
+    fn object_overrides(var: &pxs_Var, method: &str) -> PxsRes<bool>;
+
     /// Some langauges (pocketpy) need to be explicitly told that a new thread is starting.
     /// For most languages this is NOT needed.
     fn start_thread();
@@ -204,6 +738,30 @@ pub trait PixelScript {
 
     /// Call the garbage collector. Will also free internal types.
     fn garbage_collect();
+
+    /// Free what the runtime can under memory pressure: drop the cached bridges of `object_types` and run a full
+    /// collection. Returns the bytes freed, 0 if the engine can not measure them.
+    fn trim_memory(object_types: &[String]) -> usize;
+
+    /// Create a language function that calls the host function at `fn_idx`. Returns a `pxs_Function`.
+    fn new_function(fn_idx: i32) -> PxsResult;
+
+    /// Create a language function that returns `[item]` for each item of `var`, then null. Used by `pxs_iter_new`.
+    fn new_iterator(var: &pxs_Var) -> PxsResult;
+
+    /// Create a language function that resumes `func` as a coroutine, started with the items of the `args` list. Each
+    /// call returns `[done, value]`. Used by `pxs_coroutine_create`.
+    fn new_coroutine(func: &pxs_Var, args: &pxs_Var) -> PxsResult;
+
+    /// Install (or remove) the hooks calling `limit::step` while code runs under a execution limit.
+    fn set_exec_hooks(enabled: bool);
+
+    /// File and line of the script code calling the current host function. None if it is not called from a script.
+    fn caller_location() -> Option<(String, i32)>;
+
+    /// Import the script module `name` with the language's own loader. Returns what it exports: the table returned
+    /// by a Lua module, a dict of the public globals of a Python module, the namespace of a JS module.
+    fn import_module(name: &str) -> PxsResult;
 }
 
 /// Public enum for supported runtimes.
@@ -43,7 +43,27 @@ pub struct pxs_Module {
     /// Variables that need to be added.
     pub variables: Vec<ModuleVariable>,
     /// Internal modules
-    pub modules: Vec<Arc<pxs_Module>>
+    pub modules: Vec<Arc<pxs_Module>>,
+    /// Classes added with `pxs_addclass`. Boxed so their handles stay valid while more are added.
+    pub classes: Vec<Box<pxs_Class>>,
+}
+
+/// A class of a module. Holds static methods, class constants and the constructor, which is called when the class
+/// itself is called (`Vec3(1, 2, 3)`).
+///
+/// In Lua this is a table with a `__call` metamethod, in Python a class and in JavaScript the constructor function
+/// with the statics and constants as properties.
+#[derive(Clone)]
+#[allow(non_camel_case_types)]
+pub struct pxs_Class {
+    /// Name of the class, usually the type name of its objects.
+    pub name: String,
+    /// Static methods. Called without a object.
+    pub callbacks: Vec<ModuleCallback>,
+    /// Class constants.
+    pub variables: Vec<ModuleVariable>,
+    /// Returns a new object.
+    pub constructor: Option<ModuleCallback>,
 }
 
 /// Wraps a idx with a name.
@@ -52,6 +72,8 @@ pub struct ModuleCallback {
     pub name: String,
     pub full_name: String,
     pub idx: i32,
+    /// Added with `pxs_addobject`.
+    pub is_object: bool,
 }
 
 /// Wraps a Var with a name.
@@ -78,6 +100,7 @@ impl pxs_Module {
             callbacks: vec![],
             variables: vec![],
             modules: vec![],
+            classes: vec![],
         }
     }
 
@@ -87,6 +110,7 @@ impl pxs_Module {
             name: name.to_string(),
             full_name: full_name.to_string(),
             idx,
+            is_object: false,
         });
     }
 
@@ -103,6 +127,29 @@ impl pxs_Module {
         self.modules.push(child);
     }
 
+    /// Add a class to current module. Returns the class to add its members to.
+    pub fn add_class(&mut self, name: &str) -> &mut pxs_Class {
+        self.classes.push(Box::new(pxs_Class {
+            name: name.to_string(),
+            callbacks: vec![],
+            variables: vec![],
+            constructor: None,
+        }));
+        self.classes.last_mut().unwrap()
+    }
+
+    /// Function idxs of the callbacks of this module and its internal modules.
+    pub fn callback_idxs(&self) -> Vec<i32> {
+        let mut idxs: Vec<i32> = self.callbacks.iter().map(|cbk| cbk.idx).collect();
+        for class in self.classes.iter() {
+            idxs.extend(class.callbacks.iter().chain(class.constructor.iter()).map(|cbk| cbk.idx));
+        }
+        for child in self.modules.iter() {
+            idxs.extend(child.callback_idxs());
+        }
+        idxs
+    }
+
     /// Get name without package
     pub fn get_name(&self) -> String {
         if !self.name.contains(".") {
@@ -124,10 +171,14 @@ impl pxs_Module {
 }
 
 impl PtrMagic for pxs_Module {}
+impl PtrMagic for pxs_Class {}
 
 unsafe impl Send for pxs_Module {}
 unsafe impl Sync for pxs_Module {}
 
+unsafe impl Send for pxs_Class {}
+unsafe impl Sync for pxs_Class {}
+
 unsafe impl Send for ModuleCallback {}
 unsafe impl Sync for ModuleCallback {}
 
@@ -0,0 +1,269 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+//! MessagePack encoding of vars.
+//!
+//! A buffer is a big endian u32 length followed by that many bytes of MessagePack, so buffers can be concatenated on
+//! a stream. Map keys are written in the order of their encoding so equal vars encode to equal buffers.
+use crate::shared::var::{pxs_Var, pxs_VarType};
+
+/// Deepest nesting of lists and maps `decode` accepts.
+const MAX_DEPTH: usize = 256;
+
+fn write_uint(out: &mut Vec<u8>, val: u64) {
+    if val < 0x80 {
+        out.push(val as u8);
+    } else if val <= u8::MAX as u64 {
+        out.extend([0xcc, val as u8]);
+    } else if val <= u16::MAX as u64 {
+        out.push(0xcd);
+        out.extend((val as u16).to_be_bytes());
+    } else if val <= u32::MAX as u64 {
+        out.push(0xce);
+        out.extend((val as u32).to_be_bytes());
+    } else {
+        out.push(0xcf);
+        out.extend(val.to_be_bytes());
+    }
+}
+
+fn write_int(out: &mut Vec<u8>, val: i64) {
+    if val >= 0 {
+        write_uint(out, val as u64);
+    } else if val >= -32 {
+        out.push(val as i8 as u8);
+    } else if val >= i8::MIN as i64 {
+        out.extend([0xd0, val as i8 as u8]);
+    } else if val >= i16::MIN as i64 {
+        out.push(0xd1);
+        out.extend((val as i16).to_be_bytes());
+    } else if val >= i32::MIN as i64 {
+        out.push(0xd2);
+        out.extend((val as i32).to_be_bytes());
+    } else {
+        out.push(0xd3);
+        out.extend(val.to_be_bytes());
+    }
+}
+
+/// Write a str, bin, array or map header. `fix` is the fix format marker and its max length, `codes` the 8 (if any),
+/// 16 and 32 bit markers.
+fn write_header(out: &mut Vec<u8>, len: usize, fix: Option<(u8, usize)>, codes: [Option<u8>; 3]) -> Result<(), String> {
+    match fix {
+        Some((marker, max)) if len <= max => out.push(marker | len as u8),
+        _ => match codes {
+            [Some(code), _, _] if len <= u8::MAX as usize => out.extend([code, len as u8]),
+            [_, Some(code), _] if len <= u16::MAX as usize => {
+                out.push(code);
+                out.extend((len as u16).to_be_bytes());
+            }
+            [_, _, Some(code)] if len <= u32::MAX as usize => {
+                out.push(code);
+                out.extend((len as u32).to_be_bytes());
+            }
+            _ => return Err(format!("Can not encode a length of {len} in MessagePack.")),
+        },
+    }
+    Ok(())
+}
+
+fn write_str(out: &mut Vec<u8>, val: &str) -> Result<(), String> {
+    write_header(out, val.len(), Some((0xa0, 31)), [Some(0xd9), Some(0xda), Some(0xdb)])?;
+    out.extend(val.as_bytes());
+    Ok(())
+}
+
+fn write_var(out: &mut Vec<u8>, var: &pxs_Var) -> Result<(), String> {
+    match var.tag {
+        pxs_VarType::pxs_Null => out.push(0xc0),
+        pxs_VarType::pxs_Bool => out.push(if var.get_bool().unwrap() { 0xc3 } else { 0xc2 }),
+        pxs_VarType::pxs_Int64 => write_int(out, var.get_i64().unwrap()),
+        pxs_VarType::pxs_UInt64 => write_uint(out, var.get_u64().unwrap()),
+        pxs_VarType::pxs_Byte => write_uint(out, unsafe { var.value.byte_val } as u64),
+        pxs_VarType::pxs_Float64 => {
+            out.push(0xcb);
+            out.extend(var.get_f64().unwrap().to_be_bytes());
+        }
+        pxs_VarType::pxs_Int32 => write_int(out, var.get_i32().unwrap() as i64),
+        pxs_VarType::pxs_UInt32 => write_uint(out, var.get_u32().unwrap() as u64),
+        pxs_VarType::pxs_Float32 => {
+            out.push(0xca);
+            out.extend(var.get_f32().unwrap().to_be_bytes());
+        }
+        pxs_VarType::pxs_String => write_str(out, &var.get_string().unwrap())?,
+        pxs_VarType::pxs_Bytes => {
+            let bytes = var.get_bytes().unwrap();
+            write_header(out, bytes.len(), None, [Some(0xc4), Some(0xc5), Some(0xc6)])?;
+            out.extend(bytes.iter());
+        }
+        // Decodes as a list of numbers.
+        pxs_VarType::pxs_TypedArray => write_var(out, &var.get_typed_array().unwrap().to_list())?,
+        pxs_VarType::pxs_List => {
+            let list = var.get_list().unwrap();
+            write_header(out, list.vars.len(), Some((0x90, 15)), [None, Some(0xdc), Some(0xdd)])?;
+            for item in list.vars.iter() {
+                write_var(out, item)?;
+            }
+        }
+        pxs_VarType::pxs_Map => {
+            let map = var.get_map().unwrap();
+            let mut pairs = Vec::with_capacity(map.len());
+            for key in map.keys() {
+                let mut encoded = vec![];
+                write_var(&mut encoded, key)?;
+                pairs.push((encoded, map.get_item(key).unwrap()));
+            }
+            pairs.sort_by(|a, b| a.0.cmp(&b.0));
+
+            write_header(out, pairs.len(), Some((0x80, 15)), [None, Some(0xde), Some(0xdf)])?;
+            for (key, value) in pairs {
+                out.extend(key);
+                write_var(out, value)?;
+            }
+        }
+        tag => return Err(format!("Can not encode a {tag:?} as MessagePack.")),
+    }
+    Ok(())
+}
+
+/// Encode `var` into a length prefixed MessagePack buffer. Err for Objects, Functions and other values that are not
+/// data.
+pub fn encode(var: &pxs_Var) -> Result<Vec<u8>, String> {
+    let mut out = vec![0; 4];
+    write_var(&mut out, var)?;
+    let len = u32::try_from(out.len() - 4).map_err(|_| "MessagePack buffer is too large.".to_string())?;
+    out[..4].copy_from_slice(&len.to_be_bytes());
+    Ok(out)
+}
+
+/// Reader over a MessagePack buffer.
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], String> {
+        let end = self.pos.checked_add(len).filter(|end| *end <= self.data.len()).ok_or("MessagePack is truncated.")?;
+        let res = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(res)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], String> {
+        Ok(self.take(N)?.try_into().unwrap())
+    }
+
+    /// Read a 8, 16 or 32 bit length.
+    fn len(&mut self, size: usize) -> Result<usize, String> {
+        Ok(match size {
+            1 => self.array::<1>()?[0] as usize,
+            2 => u16::from_be_bytes(self.array()?) as usize,
+            _ => u32::from_be_bytes(self.array()?) as usize,
+        })
+    }
+
+    fn string(&mut self, len: usize) -> Result<pxs_Var, String> {
+        let bytes = self.take(len)?;
+        let val = std::str::from_utf8(bytes).map_err(|_| "MessagePack has a invalid UTF-8 string.".to_string())?;
+        Ok(pxs_Var::new_string(val.to_string()))
+    }
+
+    fn list(&mut self, len: usize, depth: usize) -> Result<pxs_Var, String> {
+        // Every item is at least a byte, so a bad length can not allocate much.
+        let mut items = Vec::with_capacity(len.min(self.data.len() - self.pos));
+        for _ in 0..len {
+            items.push(self.var(depth + 1)?);
+        }
+        Ok(pxs_Var::new_list_with(items))
+    }
+
+    fn map(&mut self, len: usize, depth: usize) -> Result<pxs_Var, String> {
+        let var = pxs_Var::new_map();
+        let map = var.get_map().unwrap();
+        for _ in 0..len {
+            let key = self.var(depth + 1)?;
+            if !matches!(
+                key.tag,
+                pxs_VarType::pxs_String | pxs_VarType::pxs_Int64 | pxs_VarType::pxs_UInt64 | pxs_VarType::pxs_Float64 | pxs_VarType::pxs_Bool
+            ) {
+                return Err(format!("MessagePack map key can not be a {:?}.", key.tag));
+            }
+            let value = self.var(depth + 1)?;
+            map.add_item(key, value);
+        }
+        Ok(var)
+    }
+
+    /// Positive ints are `pxs_Int64` unless they only fit a `pxs_UInt64`.
+    fn uint(val: u64) -> pxs_Var {
+        match i64::try_from(val) {
+            Ok(val) => pxs_Var::new_i64(val),
+            Err(_) => pxs_Var::new_u64(val),
+        }
+    }
+
+    fn var(&mut self, depth: usize) -> Result<pxs_Var, String> {
+        if depth > MAX_DEPTH {
+            return Err("MessagePack is nested too deep.".to_string());
+        }
+        let marker = self.array::<1>()?[0];
+        Ok(match marker {
+            0x00..=0x7f => pxs_Var::new_i64(marker as i64),
+            0x80..=0x8f => self.map((marker & 0x0f) as usize, depth)?,
+            0x90..=0x9f => self.list((marker & 0x0f) as usize, depth)?,
+            0xa0..=0xbf => self.string((marker & 0x1f) as usize)?,
+            0xc0 => pxs_Var::new_null(),
+            0xc2 => pxs_Var::new_bool(false),
+            0xc3 => pxs_Var::new_bool(true),
+            0xc4..=0xc6 => {
+                let len = self.len(1 << (marker - 0xc4))?;
+                pxs_Var::new_bytes(self.take(len)?.to_vec())
+            }
+            0xca => pxs_Var::new_f64(f32::from_be_bytes(self.array()?) as f64),
+            0xcb => pxs_Var::new_f64(f64::from_be_bytes(self.array()?)),
+            0xcc => Self::uint(self.array::<1>()?[0] as u64),
+            0xcd => Self::uint(u16::from_be_bytes(self.array()?) as u64),
+            0xce => Self::uint(u32::from_be_bytes(self.array()?) as u64),
+            0xcf => Self::uint(u64::from_be_bytes(self.array()?)),
+            0xd0 => pxs_Var::new_i64(i8::from_be_bytes(self.array()?) as i64),
+            0xd1 => pxs_Var::new_i64(i16::from_be_bytes(self.array()?) as i64),
+            0xd2 => pxs_Var::new_i64(i32::from_be_bytes(self.array()?) as i64),
+            0xd3 => pxs_Var::new_i64(i64::from_be_bytes(self.array()?)),
+            0xd9..=0xdb => {
+                let len = self.len(1 << (marker - 0xd9))?;
+                self.string(len)?
+            }
+            0xdc | 0xdd => {
+                let len = self.len(2 << (marker - 0xdc))?;
+                self.list(len, depth)?
+            }
+            0xde | 0xdf => {
+                let len = self.len(2 << (marker - 0xde))?;
+                self.map(len, depth)?
+            }
+            0xe0..=0xff => pxs_Var::new_i64(marker as i8 as i64),
+            // 0xc1 is never used, the rest are extension types.
+            _ => return Err(format!("MessagePack type 0x{marker:02x} is not supported.")),
+        })
+    }
+}
+
+/// Decode a buffer made by `encode` (or any length prefixed MessagePack without extension types).
+pub fn decode(data: &[u8]) -> Result<pxs_Var, String> {
+    let mut reader = Reader { data, pos: 0 };
+    let len = reader.len(4)?;
+    if data.len() - 4 != len {
+        return Err(format!("MessagePack length is {len}, buffer has {}.", data.len() - 4));
+    }
+    let var = reader.var(0)?;
+    if reader.pos != data.len() {
+        return Err("MessagePack has trailing bytes.".to_string());
+    }
+    Ok(var)
+}
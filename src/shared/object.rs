@@ -12,7 +12,64 @@ use std::{
 
 use etffi::ptr_magic::ThreadSafePointer;
 
-use crate::{shared::{PtrMagic, module::ModuleCallback, var::{default_deleter, pxs_DeleterFn}}};
+use etffi::cstring::CStringSafe;
+
+use crate::{shared::{PtrMagic, PxsRes, get_object_destructor_hook, get_object_observer, module::ModuleCallback, slots::SlotMap, var::{default_deleter, pxs_DeleterFn}}};
+
+/// Lifetime events of a host object. Sent to the `pxs_set_object_observer` callback.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[allow(non_camel_case_types)]
+pub enum pxs_ObjectEvent {
+    /// The object got its language representation, i.e. it was passed into a runtime.
+    pxs_ObjectWrapped = 0,
+    /// The last reference was dropped (i.e. by the script GC). The object is about to be finalized.
+    pxs_ObjectReleased = 1,
+    /// The object is being freed. Its `free_method` runs right after this.
+    pxs_ObjectFinalized = 2,
+}
+
+/// Operators a host object can overload with `pxs_object_addoperator`.
+///
+/// Lua uses metamethods (`__add`, `__tostring`, ...), Python dunder methods (`__add__`, `__str__`, ...).
+/// JavaScript has no operator overloading, only `pxs_Operator_ToString` is used (as `toString`).
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[allow(non_camel_case_types)]
+pub enum pxs_Operator {
+    /// `a + b`
+    pxs_Operator_Add = 0,
+    /// `a - b`
+    pxs_Operator_Sub = 1,
+    /// `a * b`
+    pxs_Operator_Mul = 2,
+    /// `a == b`
+    pxs_Operator_Eq = 3,
+    /// `a < b`
+    pxs_Operator_Lt = 4,
+    /// `tostring(a)`/`str(a)`
+    pxs_Operator_ToString = 5,
+    /// `#a`/`len(a)`
+    pxs_Operator_Len = 6,
+    /// `a[key]`. In Lua only called for keys that are not a method or property.
+    pxs_Operator_Index = 7,
+}
+
+impl pxs_Operator {
+    /// Name used in usage reports, i.e. `Vec3.add`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            pxs_Operator::pxs_Operator_Add => "add",
+            pxs_Operator::pxs_Operator_Sub => "sub",
+            pxs_Operator::pxs_Operator_Mul => "mul",
+            pxs_Operator::pxs_Operator_Eq => "eq",
+            pxs_Operator::pxs_Operator_Lt => "lt",
+            pxs_Operator::pxs_Operator_ToString => "tostring",
+            pxs_Operator::pxs_Operator_Len => "len",
+            pxs_Operator::pxs_Operator_Index => "index",
+        }
+    }
+}
 
 /// Flags for `ObjectCallback`.
 /// 
@@ -21,10 +78,12 @@ use crate::{shared::{PtrMagic, module::ModuleCallback, var::{default_deleter, px
 /// When `UsesId`, the callback should use `_pxs_ptr`.
 /// When `UsesRef`, the callback should use the language reference.
 /// When `IsProp`, the callback should be used as a getter/setter.
+/// When `IsOperator`, the callback overloads `ObjectCallback::operator`.
 pub enum ObjectFlags {
     UsesId = 1 << 0,
     UsesRef = 1 << 1,
-    IsProp = 1 << 2
+    IsProp = 1 << 2,
+    IsOperator = 1 << 3,
 }
 
 impl BitOr for ObjectFlags {
@@ -54,7 +113,9 @@ pub struct ObjectCallback {
     /// The internal callback structure (same as module.)
     pub cbk: ModuleCallback,
     /// Flags for determining how a object method should be defined
-    pub flags: u8
+    pub flags: u8,
+    /// The overloaded operator when `IsOperator` is set.
+    pub operator: Option<pxs_Operator>,
 }
 
 /// A PixelScript Object.
@@ -143,7 +204,13 @@ pub struct pxs_PixelObject {
     /// Refernce Counting. This is internal reference counting PXS side.
     pub ref_count: Mutex<u16>,
     /// Optional type. < 0 == None.
-    pub t: i32
+    pub t: i32,
+    /// Idx in the object lookup. -1 until added.
+    idx: Mutex<i32>,
+    /// The creation reference is not claimed yet. The first language object (or `pxs_object_release`) claims it.
+    floating: Mutex<bool>,
+    /// The language object owns a reference, dropped when the script GC collects it.
+    script_owned: Mutex<bool>,
 }
 
 impl pxs_PixelObject {
@@ -157,7 +224,10 @@ impl pxs_PixelObject {
             pxs_free_method: Mutex::new(default_deleter),
             // TODO: Do I need to set this to 0?
             ref_count: Mutex::new(1),
-            t: -1
+            t: -1,
+            idx: Mutex::new(-1),
+            floating: Mutex::new(true),
+            script_owned: Mutex::new(false),
         }
     }
 
@@ -171,7 +241,10 @@ impl pxs_PixelObject {
             pxs_free_method: Mutex::new(default_deleter),
             // TODO: Do I need to set this to 0?
             ref_count: Mutex::new(1),
-            t
+            t,
+            idx: Mutex::new(-1),
+            floating: Mutex::new(true),
+            script_owned: Mutex::new(false),
         }
     }
 
@@ -182,7 +255,23 @@ impl pxs_PixelObject {
             name: name.to_string(),
             full_name: full_name.to_string(),
             idx,
-        }, flags});
+            is_object: false,
+        }, flags, operator: None});
+    }
+
+    /// Add a operator callback. Replaces a earlier callback for the same operator.
+    pub fn add_operator(&mut self, operator: pxs_Operator, full_name: &str, idx: i32) {
+        self.callbacks.retain(|cbk| cbk.operator != Some(operator));
+        self.callbacks.push(ObjectCallback {
+            cbk: ModuleCallback {
+                name: operator.name().to_string(),
+                full_name: full_name.to_string(),
+                idx,
+                is_object: false,
+            },
+            flags: ObjectFlags::UsesId | ObjectFlags::IsOperator,
+            operator: Some(operator),
+        });
     }
 
     pub fn update_lang_ptr(&self, n_ptr: *mut c_void) {
@@ -194,6 +283,33 @@ impl pxs_PixelObject {
         }
 
         *guard = n_ptr;
+        drop(guard);
+        self.notify(pxs_ObjectEvent::pxs_ObjectWrapped);
+    }
+
+    /// Replace the language object, i.e. with a instance of a script subclass. The old one is freed.
+    pub fn replace_lang_ptr(&self, n_ptr: *mut c_void, free_method: pxs_DeleterFn) {
+        let mut guard = self.lang_ptr.lock().unwrap();
+        let mut free_guard = self.pxs_free_method.lock().unwrap();
+
+        if !guard.is_null() {
+            unsafe { (*free_guard)(*guard) };
+        }
+        *guard = n_ptr;
+        *free_guard = free_method;
+    }
+
+    /// Idx in the object lookup. -1 if not added.
+    pub fn idx(&self) -> i32 {
+        *self.idx.lock().unwrap()
+    }
+
+    /// Send a lifetime event to the host observer if there is one.
+    fn notify(&self, event: pxs_ObjectEvent) {
+        if let Some(cbk) = get_object_observer() {
+            let mut cstrgen = CStringSafe::new();
+            unsafe { cbk(event, cstrgen.new_string(&self.type_name), self.idx()) };
+        }
     }
 
     /// Update pxs_free method
@@ -229,6 +345,7 @@ unsafe impl Send for pxs_PixelObject {}
 unsafe impl Sync for pxs_PixelObject {}
 impl Drop for pxs_PixelObject {
     fn drop(&mut self) {
+        self.notify(pxs_ObjectEvent::pxs_ObjectFinalized);
         let mut lang_ptr = self.lang_ptr.lock().unwrap();
         if !lang_ptr.is_null() {
             // Free Language memory
@@ -249,10 +366,8 @@ impl Drop for pxs_PixelObject {
 
 /// Lookup state structure
 pub struct ObjectLookup {
-    /// Object hash shared between all runtimes.
-    pub object_hash: HashMap<i32, Arc<pxs_PixelObject>>,
-    /// next idx avail
-    pub next_idx: i32
+    /// Objects shared between all runtimes.
+    pub objects: SlotMap<Arc<pxs_PixelObject>>,
 }
 
 impl PtrMagic for ObjectLookup {}
@@ -264,8 +379,7 @@ thread_local! {
 
 fn new_object_lookup() -> *mut ObjectLookup {
     ObjectLookup {
-        object_hash: HashMap::new(),
-        next_idx: 0,
+        objects: SlotMap::new(),
     }.into_raw()
 }
 
@@ -279,20 +393,81 @@ fn get_object_lookup() -> *mut ObjectLookup {
 /// Apply reference counting to object in lookup.
 pub(crate) fn apply_ref_count_delete(idx: i32) {
     let lookup = get_object_lookup();
-    if let Some(object) = unsafe { (*lookup).object_hash.get(&idx) } {
+    if let Some(object) = unsafe { (*lookup).objects.get(idx) } {
         // Check # of references
         if object.sub_reference() == 0 {
-            unsafe{(*lookup).object_hash.remove(&idx)};
+            object.notify(pxs_ObjectEvent::pxs_ObjectReleased);
+            unsafe{(*lookup).objects.remove(idx)};
         }
     }
 }
 
+/// A language object of `idx` was created, it owns a reference until `release_script_ref`. The first language object
+/// takes the creation reference.
+pub(crate) fn retain_script_ref(idx: i32) {
+    let Some(object) = get_object(idx) else {
+        return;
+    };
+    let mut script_owned = object.script_owned.lock().unwrap();
+    if *script_owned {
+        return;
+    }
+    *script_owned = true;
+
+    let mut floating = object.floating.lock().unwrap();
+    if *floating {
+        *floating = false;
+    } else {
+        object.add_reference();
+    }
+}
+
+/// The language object of `idx` was collected (or deleted with `pxs_mem`), drop its reference. A object that was never
+/// wrapped drops the creation reference instead.
+pub(crate) fn release_script_ref(idx: i32) {
+    let Some(object) = get_object(idx) else {
+        return;
+    };
+    let owned = std::mem::take(&mut *object.script_owned.lock().unwrap())
+        || std::mem::take(&mut *object.floating.lock().unwrap());
+    drop(object);
+    if owned {
+        apply_ref_count_delete(idx);
+    }
+}
+
+/// The script GC collected the language object of `idx`. Calls the `pxs_set_object_destructor_hook` callback, then
+/// drops the reference of the language object.
+pub(crate) fn collect_script_ref(idx: i32) {
+    let Some(object) = get_object(idx) else {
+        return;
+    };
+    if let Some(hook) = get_object_destructor_hook() {
+        let mut cstrgen = CStringSafe::new();
+        unsafe { hook(cstrgen.new_string(&object.type_name), object.ptr) };
+    }
+    drop(object);
+    release_script_ref(idx);
+}
+
+/// Drop a host reference of `idx`. The creation reference is dropped first if no language object claimed it yet.
+/// Returns false if there is no such object.
+pub(crate) fn release_host_ref(idx: i32) -> bool {
+    let Some(object) = get_object(idx) else {
+        return false;
+    };
+    *object.floating.lock().unwrap() = false;
+    drop(object);
+    apply_ref_count_delete(idx);
+    true
+}
+
 /// Apply reference counting to a object in lookup.
 /// Adds a new count if found.
 pub(crate) fn apply_ref_count_alloc(idx: i32) {
     let lookup = get_object_lookup();
     // Check for object.
-    if let Some(object) = unsafe {(*lookup).object_hash.get(&idx)} {
+    if let Some(object) = unsafe {(*lookup).objects.get(idx)} {
         object.add_reference();
     }
 }
@@ -300,28 +475,26 @@ pub(crate) fn apply_ref_count_alloc(idx: i32) {
 pub(crate) fn clear_object_lookup() {
     let lookup = get_object_lookup();
     unsafe {
-        (*lookup).object_hash.clear();
-        (*lookup).next_idx = 0;
+        (*lookup).objects.clear_all();
     }
 }
 
 // add_object(Arc::clone(&pixel_arc))
-pub(crate) fn lookup_add_object(pixel_obj: Arc<pxs_PixelObject>) -> i32 {
+pub(crate) fn lookup_add_object(pixel_obj: Arc<pxs_PixelObject>) -> PxsRes<i32> {
     let lookup = get_object_lookup();
 
-    unsafe {
-        let idx = (*lookup).next_idx;
-        (*lookup).object_hash.insert(idx, Arc::clone(&pixel_obj));
-        (*lookup).next_idx += 1;
-        idx
-    }
+    let idx = unsafe {
+        (*lookup).objects.insert(Arc::clone(&pixel_obj))
+    }?;
+    *pixel_obj.idx.lock().unwrap() = idx;
+    Ok(idx)
 }
 
 /// Get a PixelObject Arc
 pub(crate) fn get_object(idx: i32) -> Option<Arc<pxs_PixelObject>> {
     let lookup = get_object_lookup();
 
-    if let Some(res) = unsafe { (*lookup).object_hash.get(&idx) } {
+    if let Some(res) = unsafe { (*lookup).objects.get(idx) } {
         Some(res.to_owned())
     } else {
         None
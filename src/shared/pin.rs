@@ -0,0 +1,54 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+//! Reference counts of vars retained by the host with `pxs_retain`.
+//!
+//! A var starts with one reference, its owner. Only extra references are stored, keyed by the address of the var,
+//! so vars that were never retained cost nothing.
+use std::{
+    collections::HashMap,
+    sync::{LazyLock, Mutex},
+};
+
+use crate::shared::var::pxs_Var;
+
+static EXTRA: LazyLock<Mutex<HashMap<usize, u32>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Add a reference to `var`. Returns its count.
+pub(crate) fn retain(var: *const pxs_Var) -> u32 {
+    let mut extra = EXTRA.lock().unwrap();
+    let count = extra.entry(var as usize).or_insert(0);
+    *count += 1;
+    *count + 1
+}
+
+/// Drop a reference to `var`. Returns the count left, the caller frees the var at 0.
+pub(crate) fn release(var: *const pxs_Var) -> u32 {
+    let mut extra = EXTRA.lock().unwrap();
+    match extra.get_mut(&(var as usize)) {
+        Some(count) if *count > 1 => {
+            *count -= 1;
+            *count + 1
+        }
+        Some(_) => {
+            extra.remove(&(var as usize));
+            1
+        }
+        None => 0,
+    }
+}
+
+/// References to `var`, at least 1.
+pub(crate) fn count(var: *const pxs_Var) -> u32 {
+    EXTRA.lock().unwrap().get(&(var as usize)).copied().unwrap_or(0) + 1
+}
+
+/// Forget every count.
+pub(crate) fn clear() {
+    EXTRA.lock().unwrap().clear();
+}
@@ -0,0 +1,43 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+use crate::shared::{chunks, registry};
+
+/// How low the host is on memory. Passed to `pxs_notify_memory_pressure`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[allow(non_camel_case_types)]
+pub enum pxs_MemoryPressure {
+    /// Run a full garbage collection in every runtime.
+    pxs_MemoryModerate = 0,
+    /// Also drop cached object bridges (Lua metatables, pocketpy classes, QuickJS prototypes) and the caches
+    /// pixelscript owns, see `trim_caches`. They are made again when needed.
+    pxs_MemoryCritical = 1,
+}
+
+/// Object types whose cached bridges are dropped at `level`.
+pub(crate) fn bridges_to_drop(level: pxs_MemoryPressure) -> Vec<String> {
+    match level {
+        pxs_MemoryPressure::pxs_MemoryModerate => vec![],
+        pxs_MemoryPressure::pxs_MemoryCritical => registry::object_types(),
+    }
+}
+
+/// Drop the caches pixelscript owns at `level`: the compiled chunk cache and the `pxs_noise` permutation tables of
+/// this thread.
+///
+/// Nothing the host holds a handle or pointer to is released. Vars retained with `pxs_retain`, host objects,
+/// functions made with `pxs_newfunc`, sync field ids and the sync buffer all stay until the host lets go of them.
+pub(crate) fn trim_caches(level: pxs_MemoryPressure) {
+    if level != pxs_MemoryPressure::pxs_MemoryCritical {
+        return;
+    }
+    chunks::invalidate(None);
+    #[cfg(feature = "pxs_noise")]
+    crate::pxs_core::pxs_noise::clear_cache();
+}
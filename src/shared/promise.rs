@@ -0,0 +1,303 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+//! Promises for host functions doing async work.
+//!
+//! The host makes a promise with `pxs_newpromise`, returns its `Promise` object from a host function with
+//! `pxs_promise_var` and resolves it later with `pxs_promise_resolve`. Scripts use `then(fn)`, or yield the promise
+//! from a coroutine (see `coroutine.rs`): the coroutine is parked and resumed with `(result, error)` once the
+//! promise resolves.
+use std::{cell::RefCell, collections::HashMap};
+
+use etffi::{cstring::CStringSafe, ptr_magic::PtrMagic};
+
+use crate::{
+    borrow_var, own_var, pxs_coroutine_resume, pxs_debug, pxs_error, pxs_gethost_checked, pxs_listget, pxs_listlen,
+    pxs_newhost, pxs_newobject, pxs_object_addfunc, pxs_varcall,
+    shared::{
+        PxsRes,
+        coroutine::pxs_Coroutine,
+        pxs_Opaque,
+        var::{pxs_Var, pxs_VarT},
+    },
+};
+
+/// Type name of promise objects.
+const PROMISE_TYPE: &str = "Promise";
+
+/// State of a promise.
+#[derive(Default)]
+struct PromiseState {
+    done: bool,
+    result: Option<pxs_Var>,
+    error: Option<String>,
+    /// Runtime and owned reference of the `then` callbacks.
+    callbacks: Vec<(pxs_Var, pxs_Var)>,
+    /// Coroutines that yielded the promise.
+    waiting: Vec<*mut pxs_Coroutine>,
+    /// Live `Promise` objects.
+    objects: u32,
+    /// A `Promise` object was made. Until then the host may still ask for one after resolving.
+    made: bool,
+}
+
+thread_local! {
+    static PROMISES: RefCell<HashMap<i64, PromiseState>> = RefCell::new(HashMap::new());
+    static NEXT_ID: RefCell<i64> = const { RefCell::new(1) };
+}
+
+/// The `Promise` object handed to scripts.
+struct Promise {
+    id: i64,
+}
+
+impl PtrMagic for Promise {}
+
+/// Drop the state of a resolved promise nobody can read anymore.
+fn remove_if_unused(promises: &mut HashMap<i64, PromiseState>, id: i64) {
+    if let Some(state) = promises.get(&id)
+        && state.done
+        && state.made
+        && state.objects == 0
+        && state.waiting.is_empty()
+    {
+        promises.remove(&id);
+    }
+}
+
+/// Promise free method.
+unsafe extern "C" fn free_promise(ptr: pxs_Opaque) {
+    let promise = Promise::from_raw(ptr as *mut Promise);
+    PROMISES.with(|promises| {
+        let mut promises = promises.borrow_mut();
+        if let Some(state) = promises.get_mut(&promise.id) {
+            state.objects = state.objects.saturating_sub(1);
+        }
+        remove_if_unused(&mut promises, promise.id);
+    });
+}
+
+/// Run `f` on the state of promise `id`.
+fn with_promise<T>(id: i64, f: impl FnOnce(&mut PromiseState) -> T) -> PxsRes<T> {
+    PROMISES.with(|promises| match promises.borrow_mut().get_mut(&id) {
+        Some(state) => Ok(f(state)),
+        None => pxs_error!("Unknown promise: {id}."),
+    })
+}
+
+/// The id of a `Promise` object. None for anything else.
+fn promise_id(rt: pxs_VarT, var: pxs_VarT) -> Option<i64> {
+    let borrowed = borrow_var!(var);
+    // Ints are host object idxs to `pxs_gethost`, only real objects can be promises.
+    if !borrowed.is_object() && !borrowed.is_host_object() {
+        return None;
+    }
+    let mut cstrgen = CStringSafe::new();
+    let ptr = pxs_gethost_checked(rt, var, cstrgen.new_string(PROMISE_TYPE));
+    if ptr.is_null() {
+        return None;
+    }
+    Some(Promise::from_borrow_void(ptr).id)
+}
+
+/// The promise id of the `self` argument.
+fn self_id(rt: pxs_VarT, args: pxs_VarT) -> PxsRes<i64> {
+    match promise_id(rt, pxs_listget(args, 1)) {
+        Some(id) => Ok(id),
+        None => pxs_error!("Expected a Promise."),
+    }
+}
+
+/// Call a `then` callback with `(result, error)`.
+fn call_callback(rt: &pxs_Var, func: &pxs_Var, result: &Option<pxs_Var>, error: &Option<String>) {
+    let args = pxs_Var::new_list_with(vec![
+        // The promise keeps the result, do not take its deleter.
+        result.as_ref().map(|r| r.shallow_copy()).unwrap_or_else(pxs_Var::new_null),
+        error.clone().map(pxs_Var::new_string).unwrap_or_else(pxs_Var::new_null),
+    ]);
+    let res = own_var!(pxs_varcall(
+        rt as *const pxs_Var as pxs_VarT,
+        func as *const pxs_Var as pxs_VarT,
+        args.into_raw(),
+    ));
+    if res.is_exception() {
+        pxs_debug!("Promise callback failed: {}", res.get_string().unwrap_or_default());
+    }
+}
+
+/// Make a pending promise. Returns its id.
+pub(crate) fn new() -> i64 {
+    let id = NEXT_ID.with(|next| {
+        let mut next = next.borrow_mut();
+        *next += 1;
+        *next - 1
+    });
+    PROMISES.with(|promises| promises.borrow_mut().insert(id, PromiseState::default()));
+    id
+}
+
+/// Make a `Promise` object for scripts. Err if the promise is unknown.
+pub(crate) fn object(id: i64) -> PxsRes<pxs_Var> {
+    with_promise(id, |state| {
+        state.objects += 1;
+        state.made = true;
+    })?;
+
+    let mut cstrgen = CStringSafe::new();
+    let object = pxs_newobject(Promise { id }.into_void(), free_promise, cstrgen.new_string(PROMISE_TYPE));
+    pxs_object_addfunc(object, cstrgen.new_string("done"), pxs_promise_done);
+    pxs_object_addfunc(object, cstrgen.new_string("result"), pxs_promise_result);
+    pxs_object_addfunc(object, cstrgen.new_string("error"), pxs_promise_error);
+    pxs_object_addfunc(object, cstrgen.new_string("then"), pxs_promise_then);
+    Ok(own_var!(pxs_newhost(object)))
+}
+
+/// Resolve a promise with `result`, a `pxs_Exception` rejects it with its message.
+///
+/// Runs the `then` callbacks, then resumes the coroutines waiting on it. Returns false if the promise is unknown or
+/// already resolved.
+pub(crate) fn resolve(id: i64, result: pxs_Var) -> bool {
+    // Take the callbacks out first so they can safely use the promise.
+    let taken = PROMISES.with(|promises| {
+        let mut promises = promises.borrow_mut();
+        let state = promises.get_mut(&id)?;
+        if state.done {
+            return None;
+        }
+        state.done = true;
+        if result.is_exception() {
+            state.error = Some(result.get_string().unwrap_or_default());
+        } else {
+            state.result = Some(result);
+        }
+        let callbacks = std::mem::take(&mut state.callbacks);
+        let waiting = std::mem::take(&mut state.waiting);
+        // Nobody can read the result anymore, only keep it around for the callbacks.
+        if state.made && state.objects == 0 && waiting.is_empty() {
+            let state = promises.remove(&id).unwrap();
+            return Some((callbacks, waiting, state.result, state.error));
+        }
+        Some((callbacks, waiting, state.result.as_ref().map(|r| r.shallow_copy()), state.error.clone()))
+    });
+
+    let (callbacks, waiting, result, error) = match taken {
+        Some(taken) => taken,
+        None => return false,
+    };
+    for (rt, func) in callbacks.iter() {
+        call_callback(rt, func, &result, &error);
+    }
+    for co in waiting {
+        let args = pxs_Var::new_list_with(vec![
+            result.as_ref().map(|r| r.shallow_copy()).unwrap_or_else(pxs_Var::new_null),
+            error.clone().map(pxs_Var::new_string).unwrap_or_else(pxs_Var::new_null),
+        ]);
+        // Parks the coroutine again if it yields another promise.
+        let res = own_var!(pxs_coroutine_resume(co, args.into_raw()));
+        if res.is_exception() {
+            pxs_debug!("Awaiting coroutine failed: {}", res.get_string().unwrap_or_default());
+        }
+    }
+    true
+}
+
+/// Park `co` if `value` (what it yielded) is a pending promise. Called by `pxs_coroutine_resume`.
+pub(crate) fn await_yielded(co: *mut pxs_Coroutine, rt: &pxs_Var, value: &pxs_Var) {
+    let Some(id) = promise_id(rt as *const pxs_Var as pxs_VarT, value as *const pxs_Var as pxs_VarT) else {
+        return;
+    };
+    let _ = with_promise(id, |state| {
+        if !state.done {
+            state.waiting.push(co);
+        }
+    });
+}
+
+/// Forget a coroutine that is being freed.
+pub(crate) fn forget_coroutine(co: *mut pxs_Coroutine) {
+    PROMISES.with(|promises| {
+        for state in promises.borrow_mut().values_mut() {
+            state.waiting.retain(|waiting| *waiting != co);
+        }
+    });
+}
+
+/// Drop all promises on this thread. Pending promises can no longer be resolved.
+///
+/// Promises hold references to script values, so this must be called before the runtime stops.
+pub(crate) fn clear() {
+    PROMISES.with(|promises| promises.borrow_mut().clear());
+}
+
+/// Run a `Promise` method like `core_call`, promises do not need the core modules. Checks that `args` has between
+/// `min` and `max` items (runtime included) and converts errors into exceptions.
+fn method_call(args: pxs_VarT, min: usize, max: usize, func: impl FnOnce(pxs_VarT) -> PxsRes<pxs_Var>) -> pxs_VarT {
+    let len = pxs_listlen(args).max(0) as usize;
+    if len < min || len > max {
+        return pxs_Var::expected_n_args_ep(min as u32, len as u32).into_raw();
+    }
+    match func(pxs_listget(args, 0)) {
+        Ok(res) => res.into_raw(),
+        Err(e) => pxs_Var::new_exception(e).into_raw(),
+    }
+}
+
+/// `promise.done() -> bool`
+extern "C" fn pxs_promise_done(args: pxs_VarT) -> pxs_VarT {
+    method_call(args, 2, 2, |rt| {
+        let id = self_id(rt, args)?;
+        Ok(pxs_Var::new_bool(with_promise(id, |state| state.done)?))
+    })
+}
+
+/// `promise.result()` returns the result or null if the promise is pending or rejected.
+extern "C" fn pxs_promise_result(args: pxs_VarT) -> pxs_VarT {
+    method_call(args, 2, 2, |rt| {
+        let id = self_id(rt, args)?;
+        with_promise(id, |state| match &state.result {
+            Some(result) => result.shallow_copy(),
+            None => pxs_Var::new_null(),
+        })
+    })
+}
+
+/// `promise.error()` returns the error message or null.
+extern "C" fn pxs_promise_error(args: pxs_VarT) -> pxs_VarT {
+    method_call(args, 2, 2, |rt| {
+        let id = self_id(rt, args)?;
+        with_promise(id, |state| match &state.error {
+            Some(error) => pxs_Var::new_string(error.clone()),
+            None => pxs_Var::new_null(),
+        })
+    })
+}
+
+/// `promise.then(fn)` calls `fn(result, error)` once the promise resolves. Runs right away if it already has.
+extern "C" fn pxs_promise_then(args: pxs_VarT) -> pxs_VarT {
+    method_call(args, 3, 3, |rt| {
+        let id = self_id(rt, args)?;
+        let func = borrow_var!(pxs_listget(args, 2));
+        if !func.is_function() {
+            return pxs_error!("Promise callback must be a Function, got: {:#?}.", func.tag);
+        }
+
+        let finished = with_promise(id, |state| {
+            if state.done {
+                Some((state.result.as_ref().map(|r| r.shallow_copy()), state.error.clone()))
+            } else {
+                // Cloning a function moves the deleter, so the promise keeps it alive.
+                state.callbacks.push((borrow_var!(rt).clone(), func.clone()));
+                None
+            }
+        })?;
+        if let Some((result, error)) = finished {
+            call_callback(borrow_var!(rt), func, &result, &error);
+        }
+        Ok(pxs_Var::new_null())
+    })
+}
@@ -0,0 +1,74 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+use std::{
+    collections::HashMap,
+    sync::{LazyLock, Mutex},
+};
+
+use etffi::cstring::CStringSafe;
+
+use crate::shared::{get_quarantine, var::pxs_Var};
+
+/// Error tracking for a single mod.
+#[derive(Default)]
+struct ModHealth {
+    /// Errors in a row. Reset on success.
+    errors: u32,
+    quarantined: bool,
+}
+
+static MODS: LazyLock<Mutex<HashMap<String, ModHealth>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Is this mod quarantined?
+pub(crate) fn is_quarantined(mod_name: &str) -> bool {
+    MODS.lock().unwrap().get(mod_name).is_some_and(|m| m.quarantined)
+}
+
+/// Record the result of running a mod's script. Exceptions count as errors, anything else as success.
+///
+/// Returns true if the mod is quarantined.
+pub(crate) fn record(mod_name: &str, result: &pxs_Var) -> bool {
+    let (threshold, cbk) = get_quarantine();
+
+    let newly_quarantined = {
+        let mut mods = MODS.lock().unwrap();
+        let health = mods.entry(mod_name.to_string()).or_default();
+        if health.quarantined {
+            return true;
+        }
+        if !result.is_exception() {
+            health.errors = 0;
+            return false;
+        }
+        health.errors += 1;
+        health.quarantined = health.errors >= threshold;
+        health.quarantined
+    };
+
+    // Notify outside of the lock so the host can call back into pixelscript.
+    if newly_quarantined {
+        if let Some(cbk) = cbk {
+            let mut cstrgen = CStringSafe::new();
+            let error = result.get_string().unwrap_or_default();
+            unsafe { cbk(cstrgen.new_string(mod_name), cstrgen.new_string(&error)) };
+        }
+    }
+
+    newly_quarantined
+}
+
+/// Take a mod out of quarantine and reset its errors.
+pub(crate) fn release(mod_name: &str) {
+    MODS.lock().unwrap().remove(mod_name);
+}
+
+/// Drop the tracking for all mods.
+pub(crate) fn clear() {
+    MODS.lock().unwrap().clear();
+}
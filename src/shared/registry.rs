@@ -0,0 +1,268 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, BTreeSet, HashMap},
+    sync::Arc,
+};
+
+use crate::shared::{module::pxs_Module, object::{ObjectCallback, ObjectFlags}, var::pxs_Var};
+
+/// Methods and properties seen for a object type.
+#[derive(Default)]
+struct ObjectType {
+    methods: BTreeSet<String>,
+    properties: BTreeSet<String>,
+    /// Module whose function returned objects of this type, if any.
+    module: Option<String>,
+    /// The latest callback per method, property and operator. Used to make the bridge of a parent type without a
+    /// object of it.
+    callbacks: Vec<ObjectCallback>,
+}
+
+thread_local! {
+    /// Modules added with `pxs_addmod`.
+    static MODULES: RefCell<Vec<Arc<pxs_Module>>> = const { RefCell::new(vec![]) };
+    /// Object types by type name. Filled as objects are created since object methods are added per object.
+    static OBJECT_TYPES: RefCell<BTreeMap<String, ObjectType>> = const { RefCell::new(BTreeMap::new()) };
+    /// Parent type by type name, set with `pxs_class_setparent`. Kept when a module is reloaded.
+    static TYPE_PARENTS: RefCell<HashMap<String, String>> = RefCell::new(HashMap::new());
+}
+
+/// Remember a module added to the runtimes.
+pub(crate) fn register_module(module: Arc<pxs_Module>) {
+    MODULES.with(|modules| modules.borrow_mut().push(module));
+}
+
+/// Forget a module by name. Returns the module if it was registered.
+pub(crate) fn unregister_module(name: &str) -> Option<Arc<pxs_Module>> {
+    MODULES.with(|modules| {
+        let mut modules = modules.borrow_mut();
+        let idx = modules.iter().position(|module| module.name == name)?;
+        Some(modules.remove(idx))
+    })
+}
+
+/// Every module added with `pxs_addmod`, in order.
+pub(crate) fn modules() -> Vec<Arc<pxs_Module>> {
+    MODULES.with(|modules| modules.borrow().clone())
+}
+
+/// Remember a method, property or operator of a object type.
+pub(crate) fn register_object_callback(type_name: &str, cbk: &ObjectCallback) {
+    OBJECT_TYPES.with(|types| {
+        let mut types = types.borrow_mut();
+        let object_type = types.entry(type_name.to_string()).or_default();
+        if cbk.operator.is_none() {
+            if cbk.flags & ObjectFlags::IsProp as u8 != 0 {
+                object_type.properties.insert(cbk.cbk.name.clone());
+            } else {
+                object_type.methods.insert(cbk.cbk.name.clone());
+            }
+        }
+        object_type.callbacks.retain(|c| c.cbk.name != cbk.cbk.name || c.operator != cbk.operator);
+        object_type.callbacks.push(cbk.clone());
+    });
+}
+
+/// Callbacks seen for a object type.
+pub(crate) fn type_callbacks(type_name: &str) -> Vec<ObjectCallback> {
+    OBJECT_TYPES.with(|types| types.borrow().get(type_name).map(|t| t.callbacks.clone()).unwrap_or_default())
+}
+
+/// Make `parent` the parent type of `child`. Returns false if `parent` already inherits from `child`.
+pub(crate) fn set_parent(child: &str, parent: &str) -> bool {
+    let mut current = Some(parent.to_string());
+    while let Some(type_name) = current {
+        if type_name == child {
+            return false;
+        }
+        current = parent_of(&type_name);
+    }
+    TYPE_PARENTS.with(|parents| parents.borrow_mut().insert(child.to_string(), parent.to_string()));
+    true
+}
+
+/// Parent type of `type_name`, if it has one.
+pub(crate) fn parent_of(type_name: &str) -> Option<String> {
+    TYPE_PARENTS.with(|parents| parents.borrow().get(type_name).cloned())
+}
+
+/// Is `type_name` the type `expected` or does it inherit from it?
+pub(crate) fn is_type(type_name: &str, expected: &str) -> bool {
+    let mut current = Some(type_name.to_string());
+    while let Some(type_name) = current {
+        if type_name == expected {
+            return true;
+        }
+        current = parent_of(&type_name);
+    }
+    false
+}
+
+/// Is a module (or submodule) with this name registered?
+fn has_module(name: &str) -> bool {
+    fn contains(module: &pxs_Module, name: &str) -> bool {
+        module.name == name || module.modules.iter().any(|child| contains(child, name))
+    }
+    MODULES.with(|modules| modules.borrow().iter().any(|module| contains(module, name)))
+}
+
+/// Remember that a function returned a object of `type_name`. `usage_name` is the function's usage name, only module
+/// functions (`module.func`) are remembered.
+pub(crate) fn register_object_module(type_name: &str, usage_name: &str) {
+    let Some((module, _)) = usage_name.rsplit_once('.') else {
+        return;
+    };
+    if !has_module(module) {
+        return;
+    }
+    OBJECT_TYPES.with(|types| {
+        let mut types = types.borrow_mut();
+        let object_type = types.entry(type_name.to_string()).or_default();
+        if object_type.module.is_none() {
+            object_type.module = Some(module.to_string());
+        }
+    });
+}
+
+/// Object types returned by functions of `module` or its submodules.
+pub(crate) fn module_object_types(module: &pxs_Module) -> Vec<String> {
+    fn names<'a>(module: &'a pxs_Module, out: &mut Vec<&'a str>) {
+        out.push(&module.name);
+        for child in module.modules.iter() {
+            names(child, out);
+        }
+    }
+    let mut module_names = vec![];
+    names(module, &mut module_names);
+
+    OBJECT_TYPES.with(|types| {
+        types
+            .borrow()
+            .iter()
+            .filter(|(_, object_type)| object_type.module.as_deref().is_some_and(|m| module_names.contains(&m)))
+            .map(|(name, _)| name.clone())
+            .collect()
+    })
+}
+
+/// Every object type returned by a function so far.
+pub(crate) fn object_types() -> Vec<String> {
+    OBJECT_TYPES.with(|types| types.borrow().keys().cloned().collect())
+}
+
+/// Forget object types, i.e. those of a unloaded module.
+pub(crate) fn unregister_object_types(type_names: &[String]) {
+    OBJECT_TYPES.with(|types| {
+        let mut types = types.borrow_mut();
+        for name in type_names {
+            types.remove(name);
+        }
+    });
+}
+
+/// Drop the registry.
+pub(crate) fn clear() {
+    MODULES.with(|modules| modules.borrow_mut().clear());
+    OBJECT_TYPES.with(|types| types.borrow_mut().clear());
+    TYPE_PARENTS.with(|parents| parents.borrow_mut().clear());
+}
+
+fn new_string(s: &str) -> pxs_Var {
+    pxs_Var::new_string(s.to_string())
+}
+
+/// Build a Map with string keys.
+fn new_map(items: Vec<(&str, pxs_Var)>) -> pxs_Var {
+    let var = pxs_Var::new_map();
+    let map = var.get_map().unwrap();
+    for (key, value) in items {
+        map.add_item(new_string(key), value);
+    }
+    var
+}
+
+fn export_module(module: &pxs_Module) -> pxs_Var {
+    let functions = module
+        .callbacks
+        .iter()
+        .filter(|cbk| !cbk.is_object)
+        .map(|cbk| new_string(&cbk.name))
+        .collect();
+    let objects = module
+        .callbacks
+        .iter()
+        .filter(|cbk| cbk.is_object)
+        .map(|cbk| new_string(&cbk.name))
+        .collect();
+    let constants = module
+        .variables
+        .iter()
+        .map(|v| {
+            let type_name = format!("{:?}", v.var.tag);
+            new_map(vec![
+                ("name", new_string(&v.name)),
+                ("type", new_string(type_name.strip_prefix("pxs_").unwrap_or(&type_name))),
+            ])
+        })
+        .collect();
+    let modules = module.modules.iter().map(|m| export_module(m)).collect();
+    let classes = module
+        .classes
+        .iter()
+        .map(|class| {
+            new_map(vec![
+                ("name", new_string(&class.name)),
+                ("functions", pxs_Var::new_list_with(class.callbacks.iter().map(|cbk| new_string(&cbk.name)).collect())),
+                ("constants", pxs_Var::new_list_with(class.variables.iter().map(|v| new_string(&v.name)).collect())),
+            ])
+        })
+        .collect();
+
+    new_map(vec![
+        ("name", new_string(&module.name)),
+        ("functions", pxs_Var::new_list_with(functions)),
+        ("objects", pxs_Var::new_list_with(objects)),
+        ("constants", pxs_Var::new_list_with(constants)),
+        ("classes", pxs_Var::new_list_with(classes)),
+        ("modules", pxs_Var::new_list_with(modules)),
+    ])
+}
+
+/// Export everything registered on this thread as a Map:
+///
+/// `{modules: [{name, functions, objects, constants, classes: [{name, functions, constants}], modules}], objects:
+/// [{name, parent, methods, properties}]}`
+pub(crate) fn export() -> pxs_Var {
+    let modules = MODULES.with(|modules| modules.borrow().iter().map(|m| export_module(m)).collect());
+    let objects = OBJECT_TYPES.with(|types| {
+        types
+            .borrow()
+            .iter()
+            .map(|(name, object_type)| {
+                let parent = parent_of(name).map_or(pxs_Var::new_null(), |parent| new_string(&parent));
+                new_map(vec![
+                    ("name", new_string(name)),
+                    ("parent", parent),
+                    ("methods", pxs_Var::new_list_with(object_type.methods.iter().map(|m| new_string(m)).collect())),
+                    (
+                        "properties",
+                        pxs_Var::new_list_with(object_type.properties.iter().map(|p| new_string(p)).collect()),
+                    ),
+                ])
+            })
+            .collect()
+    });
+
+    new_map(vec![
+        ("modules", pxs_Var::new_list_with(modules)),
+        ("objects", pxs_Var::new_list_with(objects)),
+    ])
+}
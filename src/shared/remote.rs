@@ -0,0 +1,164 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+use std::{
+    collections::VecDeque,
+    io::{BufRead, BufReader, ErrorKind, Write},
+    net::{TcpListener, TcpStream},
+    sync::{
+        LazyLock, Mutex,
+        atomic::{AtomicBool, Ordering},
+        mpsc::{Sender, channel},
+    },
+    thread,
+    time::Duration,
+};
+
+use crate::{pxs_error, shared::{PxsRes, capability}};
+
+/// A snippet waiting to be evaluated on the host thread.
+pub(crate) struct RemoteRequest {
+    pub runtime: String,
+    pub code: String,
+    /// Capability token of the session. `None` for the console auth token which can call everything.
+    pub token: Option<String>,
+    reply: Sender<String>,
+}
+
+impl RemoteRequest {
+    /// Send the result back to the client.
+    pub fn reply(self, result: PxsRes<String>) {
+        let line = match result {
+            Ok(value) => format!("ok {}", escape(&value)),
+            Err(e) => format!("error {}", escape(&e)),
+        };
+        let _ = self.reply.send(line);
+    }
+}
+
+static RUNNING: AtomicBool = AtomicBool::new(false);
+static REQUESTS: LazyLock<Mutex<VecDeque<RemoteRequest>>> = LazyLock::new(|| Mutex::new(VecDeque::new()));
+
+/// How often background threads check if the console was stopped.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+/// Start listening on `port`. Fails if the console is already running or the port can not be bound.
+pub(crate) fn listen(port: u16, auth_token: &str) -> PxsRes<()> {
+    if auth_token.is_empty() {
+        return pxs_error!("Remote console needs a auth token.");
+    }
+    if RUNNING.swap(true, Ordering::SeqCst) {
+        return pxs_error!("Remote console is already running.");
+    }
+
+    let listener = match TcpListener::bind(("0.0.0.0", port)).and_then(|l| l.set_nonblocking(true).map(|_| l)) {
+        Ok(listener) => listener,
+        Err(e) => {
+            RUNNING.store(false, Ordering::SeqCst);
+            return pxs_error!("Could not listen on port {port}: {e}");
+        }
+    };
+
+    let auth_token = auth_token.to_string();
+    thread::spawn(move || {
+        while RUNNING.load(Ordering::SeqCst) {
+            match listener.accept() {
+                Ok((stream, _)) => {
+                    let auth_token = auth_token.clone();
+                    thread::spawn(move || handle_connection(stream, &auth_token));
+                }
+                Err(e) if e.kind() == ErrorKind::WouldBlock => thread::sleep(POLL_INTERVAL),
+                Err(_) => thread::sleep(POLL_INTERVAL),
+            }
+        }
+    });
+
+    Ok(())
+}
+
+/// Read a line, waking up every `POLL_INTERVAL` to check if the console was stopped.
+///
+/// Returns `None` when the connection closed or the console stopped.
+fn read_line(reader: &mut BufReader<TcpStream>) -> Option<String> {
+    let mut line = String::new();
+    loop {
+        if !RUNNING.load(Ordering::SeqCst) {
+            return None;
+        }
+        match reader.read_line(&mut line) {
+            Ok(0) => return None,
+            Ok(_) => return Some(line.trim_end_matches(['\r', '\n']).to_string()),
+            Err(e) if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => continue,
+            Err(_) => return None,
+        }
+    }
+}
+
+fn handle_connection(stream: TcpStream, auth_token: &str) {
+    if stream.set_nonblocking(false).is_err() || stream.set_read_timeout(Some(POLL_INTERVAL)).is_err() {
+        return;
+    }
+    let mut writer = match stream.try_clone() {
+        Ok(writer) => writer,
+        Err(_) => return,
+    };
+    let mut reader = BufReader::new(stream);
+
+    // The console auth token, or a token from `pxs_issue_token`.
+    let token = match read_line(&mut reader) {
+        Some(token) if token == auth_token => None,
+        Some(token) if capability::lookup(&token).is_some() => Some(token),
+        _ => return,
+    };
+    if writeln!(writer, "ok").is_err() {
+        return;
+    }
+
+    while let Some(line) = read_line(&mut reader) {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let (runtime, code) = line.split_once(' ').unwrap_or((line.as_str(), ""));
+
+        let (reply, answer) = channel();
+        REQUESTS.lock().unwrap().push_back(RemoteRequest {
+            runtime: runtime.to_string(),
+            code: code.to_string(),
+            token: token.clone(),
+            reply,
+        });
+
+        // Wait for the host thread to evaluate it.
+        let answer = loop {
+            match answer.recv_timeout(POLL_INTERVAL) {
+                Ok(answer) => break Some(answer),
+                Err(_) if RUNNING.load(Ordering::SeqCst) => continue,
+                Err(_) => break None,
+            }
+        };
+        match answer {
+            Some(answer) if writeln!(writer, "{answer}").is_ok() => {}
+            _ => return,
+        }
+    }
+}
+
+/// Take all snippets waiting to be evaluated.
+pub(crate) fn take_requests() -> Vec<RemoteRequest> {
+    REQUESTS.lock().unwrap().drain(..).collect()
+}
+
+/// Stop listening and close all connections.
+pub(crate) fn stop() {
+    RUNNING.store(false, Ordering::SeqCst);
+    REQUESTS.lock().unwrap().clear();
+}
@@ -0,0 +1,162 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+//! Script modules shared between runtimes.
+//!
+//! `pxs_share_module` imports a module with the loader of the runtime it is written in (see
+//! `PixelScript::import_module`) and turns its exports into a `pxs_Module` for the other runtimes. Exported functions
+//! become proxies calling into the source runtime, everything else is copied once. Arguments and results are copied
+//! between the runtimes: tables, dicts and objects become maps, so only data and host objects cross.
+use etffi::ptr_magic::PtrMagic;
+
+use crate::{
+    add_callback_to_module, borrow_var, own_var, pxs_debug, pxs_error, pxs_listget, pxs_listlen, pxs_varcall,
+    shared::{
+        PxsRes,
+        fields::get_entries,
+        func::current_opaque,
+        module::pxs_Module,
+        pxs_Opaque, pxs_Runtime,
+        var::{pxs_Var, pxs_VarT, pxs_VarType},
+    },
+};
+
+/// Deepest nesting copied between runtimes. Also stops self referencing tables.
+const MAX_DEPTH: usize = 64;
+
+/// A exported function, the opaque of its proxy.
+struct Export {
+    /// Runtime the function lives in.
+    rt: pxs_Var,
+    /// Owned reference to the function.
+    func: pxs_Var,
+}
+
+impl PtrMagic for Export {}
+
+/// Export free method.
+unsafe extern "C" fn free_export(ptr: pxs_Opaque) {
+    let _ = Export::from_raw(ptr as *mut Export);
+}
+
+/// Check that `name` is a module name: letters, digits, `_`, `.`, `/` and `-`. Names end up in script code.
+pub(crate) fn check_name(name: &str) -> PxsRes<()> {
+    let valid = name.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '/' | '-'));
+    if name.is_empty() || !valid {
+        return pxs_error!("Invalid module name: '{name}'.");
+    }
+    Ok(())
+}
+
+/// Key of a table, dict or object entry. Strings and ints only.
+fn key_text(key: &pxs_Var) -> PxsRes<String> {
+    match key.tag {
+        pxs_VarType::pxs_String => key.get_string(),
+        pxs_VarType::pxs_Int64 => Ok(key.get_i64()?.to_string()),
+        pxs_VarType::pxs_UInt64 => Ok(key.get_u64()?.to_string()),
+        pxs_VarType::pxs_Int32 => Ok(key.get_i32()?.to_string()),
+        pxs_VarType::pxs_UInt32 => Ok(key.get_u32()?.to_string()),
+        _ => pxs_error!("Only string and int keys can be shared, got: {:#?}.", key.tag),
+    }
+}
+
+/// Copy `var` (made by the runtime `rt`) so another runtime can use it. Tables, dicts and objects become maps.
+fn to_data(rt: pxs_VarT, var: &pxs_Var, depth: usize) -> PxsRes<pxs_Var> {
+    if depth > MAX_DEPTH {
+        return pxs_error!("Can not share a value nested deeper than {MAX_DEPTH} levels.");
+    }
+    match var.tag {
+        pxs_VarType::pxs_List => {
+            let items = var.get_list().unwrap().vars.iter().map(|item| to_data(rt, item, depth + 1));
+            Ok(pxs_Var::new_list_with(items.collect::<PxsRes<Vec<_>>>()?))
+        }
+        pxs_VarType::pxs_Map => {
+            let res = pxs_Var::new_map();
+            let map = var.get_map().unwrap();
+            for key in map.keys() {
+                let value = to_data(rt, map.get_item(key).unwrap(), depth + 1)?;
+                res.get_map().unwrap().add_item(key.clone(), value);
+            }
+            Ok(res)
+        }
+        pxs_VarType::pxs_Object => {
+            let Some(entries) = get_entries(rt, var, key_text)? else {
+                return pxs_error!("Can not share a {:#?}.", var.tag);
+            };
+            let res = pxs_Var::new_map();
+            for (key, value) in entries {
+                let value = to_data(rt, &value, depth + 1)?;
+                res.get_map().unwrap().add_item(pxs_Var::new_string(key), value);
+            }
+            Ok(res)
+        }
+        pxs_VarType::pxs_Function | pxs_VarType::pxs_Factory => {
+            pxs_error!("Functions can not be passed between runtimes.")
+        }
+        _ => Ok(var.clone()),
+    }
+}
+
+/// Proxy of a exported function. Copies the args into the source runtime and the result back.
+extern "C" fn call_export(args: pxs_VarT) -> pxs_VarT {
+    let export = current_opaque();
+    if export.is_null() {
+        return pxs_Var::new_exception("Shared function has no export.").into_raw();
+    }
+    let export = Export::from_borrow_void(export);
+    let caller = pxs_listget(args, 0);
+
+    let items = (1..pxs_listlen(args))
+        .map(|idx| to_data(caller, borrow_var!(pxs_listget(args, idx)), 0))
+        .collect::<PxsRes<Vec<_>>>();
+    let items = match items {
+        Ok(items) => items,
+        Err(e) => return pxs_Var::new_exception(e).into_raw(),
+    };
+
+    let rt = &export.rt as *const pxs_Var as pxs_VarT;
+    let res = own_var!(pxs_varcall(
+        rt,
+        &export.func as *const pxs_Var as pxs_VarT,
+        pxs_Var::new_list_with(items).into_raw(),
+    ));
+    if res.is_exception() {
+        return res.into_raw();
+    }
+    match to_data(rt, &res, 0) {
+        Ok(res) => res.into_raw(),
+        Err(e) => pxs_Var::new_exception(e).into_raw(),
+    }
+}
+
+/// Make the module `name` out of the `exports` of a module imported in `runtime`.
+///
+/// Values that can not be shared (i.e. classes and modules) are left out.
+pub(crate) fn new_module(runtime: &pxs_Runtime, name: &str, exports: &pxs_Var) -> PxsRes<pxs_Module> {
+    let rt = runtime.into_var();
+    let rt_ptr = &rt as *const pxs_Var as pxs_VarT;
+    let Some(entries) = get_entries(rt_ptr, exports, key_text)? else {
+        return pxs_error!("Module '{name}' exports a {:#?}, expected a table, module or object.", exports.tag);
+    };
+
+    let mut module = pxs_Module::new(name.to_string());
+    for (key, value) in entries {
+        if value.is_function() {
+            let export = Export { rt: rt.clone(), func: value };
+            add_callback_to_module(&mut module, &key, call_export, export.into_void(), Some(free_export))?;
+            continue;
+        }
+        match to_data(rt_ptr, &value, 0) {
+            Ok(value) => module.add_variable(&key, value),
+            Err(e) => {
+                pxs_debug!("Not sharing '{name}.{key}': {e}");
+            }
+        }
+    }
+    Ok(module)
+}
@@ -0,0 +1,155 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+//! Declared arguments of module callbacks, see `pxs_addfunc_ex`.
+//!
+//! A signature is a map with:
+//! - `params`: a list of maps with a `name`, a optional `type` (a type name or list of type names, see `validate`) and
+//!   a optional `default`. Params without a default can not follow one with a default.
+//! - `varargs`: optional, `true` or the type name(s) of extra arguments. Extra arguments are an error without it.
+use crate::shared::{
+    catalog::{MSG_SIGNATURE_ARGS, MSG_SIGNATURE_TYPE, message},
+    validate::{type_matches, type_name},
+    var::{pxs_Var, pxs_VarType},
+};
+
+struct Param {
+    name: String,
+    /// Allowed type names. Empty for any type.
+    types: Vec<String>,
+    default: Option<pxs_Var>,
+}
+
+/// Arity, types and defaults of a callback.
+pub(crate) struct Signature {
+    params: Vec<Param>,
+    /// Allowed type names of extra arguments, empty for any type. None if extra arguments are not allowed.
+    varargs: Option<Vec<String>>,
+}
+
+fn invalid(problem: &str) -> String {
+    format!("Invalid signature: {problem}.")
+}
+
+fn field<'a>(map: &'a pxs_Var, key: &str) -> Option<&'a pxs_Var> {
+    map.get_map()?.get_item(&pxs_Var::new_string(key.to_string()))
+}
+
+/// Type names of a `type` or `varargs` field.
+fn types_of(var: &pxs_Var) -> Result<Vec<String>, String> {
+    let names = match var.get_list() {
+        Some(list) => list.vars.iter().map(|name| name.get_string()).collect::<Result<Vec<_>, _>>(),
+        None => var.get_string().map(|name| vec![name]),
+    }
+    .map_err(|_| invalid("a type must be a type name or a list of type names"))?;
+
+    if let Some(unknown) = names.iter().find(|name| type_matches(&pxs_Var::new_null(), name).is_none()) {
+        return Err(invalid(&format!("unknown type '{unknown}'")));
+    }
+    Ok(names)
+}
+
+/// Does `var` match `types`? Ints are accepted as floats and floats without a fraction as ints, both are converted.
+fn coerce(var: &mut pxs_Var, types: &[String]) -> bool {
+    if types.is_empty() || types.iter().any(|name| type_matches(var, name) == Some(true)) {
+        return true;
+    }
+    let wants = |name: &str| types.iter().any(|t| t == name);
+    match var.tag {
+        pxs_VarType::pxs_Int64 | pxs_VarType::pxs_UInt64 if wants("float") => {
+            let value = if var.is_i64() { var.get_i64().unwrap() as f64 } else { var.get_u64().unwrap() as f64 };
+            *var = pxs_Var::new_f64(value);
+            true
+        }
+        pxs_VarType::pxs_Float64 if wants("int") => {
+            let value = var.get_f64().unwrap();
+            if value.fract() != 0.0 || value < i64::MIN as f64 || value > i64::MAX as f64 {
+                return false;
+            }
+            *var = pxs_Var::new_i64(value as i64);
+            true
+        }
+        _ => false,
+    }
+}
+
+impl Signature {
+    /// Parse a signature map.
+    pub fn parse(var: &pxs_Var) -> Result<Self, String> {
+        if !var.is_map() {
+            return Err(invalid("expected a map"));
+        }
+
+        let mut params = vec![];
+        if let Some(list) = field(var, "params") {
+            let list = list.get_list().ok_or_else(|| invalid("params must be a list"))?;
+            for param in list.vars.iter() {
+                let name = field(param, "name")
+                    .and_then(|name| name.get_string().ok())
+                    .ok_or_else(|| invalid("every param needs a name"))?;
+                let types = match field(param, "type") {
+                    Some(types) => types_of(types)?,
+                    None => vec![],
+                };
+                let default = field(param, "default").cloned();
+                if default.is_none() && params.last().is_some_and(|p: &Param| p.default.is_some()) {
+                    return Err(invalid(&format!("param '{name}' without a default follows one with a default")));
+                }
+                params.push(Param { name, types, default });
+            }
+        }
+
+        let varargs = match field(var, "varargs") {
+            None => None,
+            Some(varargs) if varargs.is_bool() => varargs.get_bool().unwrap().then(Vec::new),
+            Some(varargs) => Some(types_of(varargs)?),
+        };
+
+        Ok(Signature { params, varargs })
+    }
+
+    /// Check and coerce the `args` of a call of `name`, the first arg is the runtime. Missing arguments with a default
+    /// are added.
+    pub fn apply(&self, name: &str, args: &mut Vec<pxs_Var>) -> Result<(), String> {
+        let passed = args.len().saturating_sub(1);
+        let required = self.params.iter().filter(|p| p.default.is_none()).count();
+        let too_many = self.varargs.is_none() && passed > self.params.len();
+        if passed < required || too_many {
+            let expected = match (&self.varargs, required == self.params.len()) {
+                (Some(_), _) => format!("at least {required}"),
+                (None, true) => required.to_string(),
+                (None, false) => format!("{required} to {}", self.params.len()),
+            };
+            return Err(message(
+                MSG_SIGNATURE_ARGS,
+                "{0}() takes {1} arguments ({2} given).",
+                &[name.to_string(), expected, passed.to_string()],
+            ));
+        }
+
+        for param in self.params.iter().skip(passed) {
+            args.push(param.default.clone().unwrap());
+        }
+
+        let empty = vec![];
+        for (i, arg) in args.iter_mut().enumerate().skip(1) {
+            let (param_name, types) = match self.params.get(i - 1) {
+                Some(param) => (param.name.clone(), &param.types),
+                None => (format!("arg {i}"), self.varargs.as_ref().unwrap_or(&empty)),
+            };
+            if !coerce(arg, types) {
+                return Err(message(
+                    MSG_SIGNATURE_TYPE,
+                    "{0}() argument '{1}' must be {2}, not {3}.",
+                    &[name.to_string(), param_name, types.join(" or "), type_name(arg).to_string()],
+                ));
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,173 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+use crate::{pxs_error, shared::PxsRes};
+
+/// Bits of a handle used for the slot index. The rest is the generation.
+const SLOT_BITS: u32 = 20;
+const SLOT_MASK: i32 = (1 << SLOT_BITS) - 1;
+/// Highest generation that fits in a positive `i32` handle.
+const MAX_GENERATION: u16 = (i32::MAX >> SLOT_BITS) as u16;
+
+/// A slot and the generation of the value in it.
+struct Slot<T> {
+    /// Starts at 1 so that a handle is never 0..=SLOT_MASK, and never -1.
+    generation: u16,
+    value: Option<T>,
+}
+
+/// Slots with a free-list.
+struct Slots<T> {
+    slots: Vec<Slot<T>>,
+    free: Vec<usize>,
+}
+
+impl<T> Slots<T> {
+    fn new() -> Self {
+        Self { slots: vec![], free: vec![] }
+    }
+
+    fn insert(&mut self, value: T) -> PxsRes<i32> {
+        let index = match self.free.pop() {
+            Some(index) => index,
+            None => {
+                if self.slots.len() > SLOT_MASK as usize {
+                    return pxs_error!("Too many entries in lookup, {} are alive.", self.slots.len());
+                }
+                self.slots.push(Slot { generation: 1, value: None });
+                self.slots.len() - 1
+            }
+        };
+        let slot = &mut self.slots[index];
+        slot.value = Some(value);
+        Ok(((slot.generation as i32) << SLOT_BITS) | index as i32)
+    }
+
+    /// Split a handle into a slot index if the generation matches.
+    fn index(&self, handle: i32) -> Option<usize> {
+        let index = (handle & SLOT_MASK) as usize;
+        let generation = (handle >> SLOT_BITS) as u16;
+        match self.slots.get(index) {
+            Some(slot) if slot.generation == generation && slot.value.is_some() => Some(index),
+            _ => None,
+        }
+    }
+
+    fn get(&self, handle: i32) -> Option<&T> {
+        let index = self.index(handle)?;
+        self.slots[index].value.as_ref()
+    }
+
+    fn get_mut(&mut self, handle: i32) -> Option<&mut T> {
+        let index = self.index(handle)?;
+        self.slots[index].value.as_mut()
+    }
+
+    /// Free a slot. The generation is bumped so old handles stop resolving.
+    fn free_slot(&mut self, index: usize) -> Option<T> {
+        let slot = &mut self.slots[index];
+        let value = slot.value.take()?;
+        slot.generation = if slot.generation >= MAX_GENERATION { 1 } else { slot.generation + 1 };
+        self.free.push(index);
+        Some(value)
+    }
+
+    fn remove(&mut self, handle: i32) -> Option<T> {
+        let index = self.index(handle)?;
+        self.free_slot(index)
+    }
+
+    fn clear(&mut self) {
+        for index in 0..self.slots.len() {
+            self.free_slot(index);
+        }
+    }
+
+    fn iter(&self) -> impl Iterator<Item = (i32, &T)> {
+        self.slots.iter().enumerate().filter_map(|(index, slot)| {
+            slot.value.as_ref().map(|value| ((((slot.generation as i32) << SLOT_BITS) | index as i32), value))
+        })
+    }
+}
+
+/// A generational slot map with `i32` handles.
+///
+/// Regular entries get positive handles, entries pinned by the host get negative handles. Handles of removed entries
+/// never resolve to a new entry until the slot's generation wraps around. `-1` and `0..=SLOT_MASK` are never handles.
+pub struct SlotMap<T> {
+    regular: Slots<T>,
+    pinned: Slots<T>,
+}
+
+impl<T> Default for SlotMap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> SlotMap<T> {
+    pub fn new() -> Self {
+        Self { regular: Slots::new(), pinned: Slots::new() }
+    }
+
+    /// Add a value. Returns its handle, or a error once `SLOT_MASK + 1` values are alive.
+    pub fn insert(&mut self, value: T) -> PxsRes<i32> {
+        self.regular.insert(value)
+    }
+
+    /// Add a value in the host pinned range. Returns its (negative) handle.
+    ///
+    /// Pinned values survive `clear`.
+    pub fn insert_pinned(&mut self, value: T) -> PxsRes<i32> {
+        self.pinned.insert(value).map(|handle| -handle)
+    }
+
+    pub fn get(&self, handle: i32) -> Option<&T> {
+        if handle < 0 {
+            self.pinned.get(handle.checked_neg()?)
+        } else {
+            self.regular.get(handle)
+        }
+    }
+
+    pub fn get_mut(&mut self, handle: i32) -> Option<&mut T> {
+        if handle < 0 {
+            self.pinned.get_mut(handle.checked_neg()?)
+        } else {
+            self.regular.get_mut(handle)
+        }
+    }
+
+    pub fn remove(&mut self, handle: i32) -> Option<T> {
+        if handle < 0 {
+            self.pinned.remove(handle.checked_neg()?)
+        } else {
+            self.regular.remove(handle)
+        }
+    }
+
+    /// Remove every regular value. Pinned values are kept.
+    pub fn clear(&mut self) {
+        self.regular.clear();
+    }
+
+    /// Remove every value, including pinned values.
+    pub fn clear_all(&mut self) {
+        self.regular.clear();
+        self.pinned.clear();
+    }
+
+    /// Every handle and value. Regular values first.
+    pub fn iter(&self) -> impl Iterator<Item = (i32, &T)> {
+        self.regular.iter().chain(self.pinned.iter().map(|(handle, value)| (-handle, value)))
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.iter().map(|(_, value)| value)
+    }
+}
@@ -0,0 +1,29 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Turns silent no-ops on invalid input into panics.
+static STRICT: AtomicBool = AtomicBool::new(false);
+
+/// Turn strict mode on or off for all threads.
+pub(crate) fn set(enabled: bool) {
+    STRICT.store(enabled, Ordering::SeqCst);
+}
+
+pub(crate) fn is_strict() -> bool {
+    STRICT.load(Ordering::Relaxed)
+}
+
+/// Report a invalid call of `function` that is otherwise ignored. Panics in strict mode.
+#[track_caller]
+pub(crate) fn misuse(function: &str, problem: &str) {
+    if is_strict() {
+        panic!("{function}: {problem}");
+    }
+}
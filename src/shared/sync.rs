@@ -0,0 +1,259 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+use std::{cell::RefCell, collections::HashMap, ffi::CString};
+
+use etffi::ptr_magic::PtrMagic;
+
+use crate::{
+    pxs_debug, pxs_error, pxs_objectset,
+    shared::{
+        PxsRes,
+        var::{pxs_Var, pxs_VarT},
+    },
+};
+
+/// Value type of a record in a sync buffer.
+///
+/// Every value is little endian.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[allow(non_camel_case_types)]
+pub enum pxs_SyncType {
+    /// No payload.
+    pxs_SyncNull = 0,
+    /// 8 byte signed integer.
+    pxs_SyncInt64 = 1,
+    /// 8 byte float.
+    pxs_SyncFloat64 = 2,
+    /// 4 byte signed integer.
+    pxs_SyncInt32 = 3,
+    /// 4 byte float.
+    pxs_SyncFloat32 = 4,
+    /// 1 byte, 0 is false.
+    pxs_SyncBool = 5,
+    /// 4 byte length followed by UTF-8 bytes.
+    pxs_SyncString = 6,
+}
+
+impl pxs_SyncType {
+    fn from_u8(tag: u8) -> Option<Self> {
+        match tag {
+            0 => Some(Self::pxs_SyncNull),
+            1 => Some(Self::pxs_SyncInt64),
+            2 => Some(Self::pxs_SyncFloat64),
+            3 => Some(Self::pxs_SyncInt32),
+            4 => Some(Self::pxs_SyncFloat32),
+            5 => Some(Self::pxs_SyncBool),
+            6 => Some(Self::pxs_SyncString),
+            _ => None,
+        }
+    }
+}
+
+/// A script object that receives updates.
+struct Target {
+    /// Runtime the object lives in.
+    rt: pxs_Var,
+    /// Owned reference to the script object.
+    object: pxs_Var,
+}
+
+thread_local! {
+    static TARGETS: RefCell<HashMap<u32, Target>> = RefCell::new(HashMap::new());
+    static NEXT_TARGET: RefCell<u32> = const { RefCell::new(0) };
+    /// Interned field names. The index is the field id.
+    static FIELDS: RefCell<Vec<CString>> = const { RefCell::new(vec![]) };
+    /// The buffer handed out by `begin`.
+    static BUFFER: RefCell<Vec<u8>> = const { RefCell::new(vec![]) };
+}
+
+/// Register a script object as a sync target. `object` is cloned so the target keeps it alive.
+///
+/// Returns `None` if `object` is not a `pxs_Object` or `pxs_HostObject`.
+pub(crate) fn bind(rt: &pxs_Var, object: &pxs_Var) -> Option<u32> {
+    if !object.is_object() && !object.is_host_object() {
+        return None;
+    }
+
+    let id = NEXT_TARGET.with(|next| {
+        let mut next = next.borrow_mut();
+        *next += 1;
+        *next - 1
+    });
+    TARGETS.with(|targets| {
+        targets.borrow_mut().insert(
+            id,
+            Target {
+                rt: rt.clone(),
+                object: object.clone(),
+            },
+        )
+    });
+    Some(id)
+}
+
+/// Remove a sync target. Returns true if it existed.
+pub(crate) fn unbind(id: u32) -> bool {
+    TARGETS.with(|targets| targets.borrow_mut().remove(&id).is_some())
+}
+
+/// Get the id of a field name, interning it if needed.
+pub(crate) fn field(name: &str) -> u16 {
+    FIELDS.with(|fields| {
+        let mut fields = fields.borrow_mut();
+        if let Some(idx) = fields.iter().position(|f| f.to_bytes() == name.as_bytes()) {
+            return idx as u16;
+        }
+        fields.push(CString::new(name).expect("Field name contains a null byte."));
+        (fields.len() - 1) as u16
+    })
+}
+
+/// Number of interned fields.
+pub(crate) fn field_count() -> usize {
+    FIELDS.with(|fields| fields.borrow().len())
+}
+
+/// Resize the buffer to `len` zeroed bytes and return a pointer to it.
+pub(crate) fn begin(len: usize) -> *mut u8 {
+    BUFFER.with(|buffer| {
+        let mut buffer = buffer.borrow_mut();
+        buffer.clear();
+        buffer.resize(len, 0);
+        buffer.as_mut_ptr()
+    })
+}
+
+/// A decoded record.
+struct Record {
+    target: u32,
+    field: u16,
+    value: pxs_Var,
+}
+
+/// Reads little endian values out of the buffer.
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take<const N: usize>(&mut self) -> PxsRes<[u8; N]> {
+        match self.data.get(self.pos..self.pos + N) {
+            Some(bytes) => {
+                self.pos += N;
+                Ok(bytes.try_into().unwrap())
+            }
+            None => pxs_error!("Sync buffer ended early at byte {}.", self.pos),
+        }
+    }
+
+    fn take_slice(&mut self, len: usize) -> PxsRes<&'a [u8]> {
+        match self.data.get(self.pos..self.pos + len) {
+            Some(bytes) => {
+                self.pos += len;
+                Ok(bytes)
+            }
+            None => pxs_error!("Sync buffer ended early at byte {}.", self.pos),
+        }
+    }
+
+    fn value(&mut self, tag: pxs_SyncType) -> PxsRes<pxs_Var> {
+        Ok(match tag {
+            pxs_SyncType::pxs_SyncNull => pxs_Var::new_null(),
+            pxs_SyncType::pxs_SyncInt64 => pxs_Var::new_i64(i64::from_le_bytes(self.take()?)),
+            pxs_SyncType::pxs_SyncFloat64 => pxs_Var::new_f64(f64::from_le_bytes(self.take()?)),
+            pxs_SyncType::pxs_SyncInt32 => pxs_Var::new_i64(i32::from_le_bytes(self.take()?) as i64),
+            pxs_SyncType::pxs_SyncFloat32 => pxs_Var::new_f64(f32::from_le_bytes(self.take()?) as f64),
+            pxs_SyncType::pxs_SyncBool => pxs_Var::new_bool(self.take::<1>()?[0] != 0),
+            pxs_SyncType::pxs_SyncString => {
+                let len = u32::from_le_bytes(self.take()?) as usize;
+                match std::str::from_utf8(self.take_slice(len)?) {
+                    Ok(s) => pxs_Var::new_string(s.to_string()),
+                    Err(e) => return pxs_error!("Sync string is not valid UTF-8: {e}"),
+                }
+            }
+        })
+    }
+}
+
+/// Decode every record in `data`. Fails without partial results if anything is malformed.
+///
+/// A record is `[u32 target][u16 field][u8 pxs_SyncType][payload]`.
+fn decode(data: &[u8]) -> PxsRes<Vec<Record>> {
+    let fields = field_count();
+    let mut reader = Reader { data, pos: 0 };
+    let mut records = vec![];
+    while reader.pos < data.len() {
+        let target = u32::from_le_bytes(reader.take()?);
+        let field = u16::from_le_bytes(reader.take()?);
+        if field as usize >= fields {
+            return pxs_error!("Unknown sync field: {field}.");
+        }
+        let tag_byte = reader.take::<1>()?[0];
+        let tag = match pxs_SyncType::from_u8(tag_byte) {
+            Some(tag) => tag,
+            None => return pxs_error!("Unknown sync value type: {tag_byte}."),
+        };
+        records.push(Record {
+            target,
+            field,
+            value: reader.value(tag)?,
+        });
+    }
+    Ok(records)
+}
+
+/// Decode the buffer filled since `begin` and apply every record.
+///
+/// Returns the number of fields set.
+pub(crate) fn end() -> PxsRes<usize> {
+    let records = BUFFER.with(|buffer| decode(&buffer.borrow()))?;
+
+    // Resolve targets first so setters can call back into `pxs_sync_*` without a double borrow.
+    let mut resolved = Vec::with_capacity(records.len());
+    TARGETS.with(|targets| -> PxsRes<()> {
+        let targets = targets.borrow();
+        for record in records {
+            match targets.get(&record.target) {
+                Some(target) => resolved.push((target.rt.shallow_copy(), target.object.shallow_copy(), record)),
+                None => return pxs_error!("Unknown sync target: {}.", record.target),
+            }
+        }
+        Ok(())
+    })?;
+    let names: Vec<CString> = FIELDS.with(|fields| fields.borrow().clone());
+
+    let mut applied = 0;
+    for (rt, object, record) in resolved {
+        let name = &names[record.field as usize];
+        let ok = pxs_objectset(
+            &rt as *const pxs_Var as pxs_VarT,
+            &object as *const pxs_Var as pxs_VarT,
+            name.as_ptr(),
+            record.value.into_raw(),
+        );
+        if ok {
+            applied += 1;
+        } else {
+            pxs_debug!("Could not sync field '{}' of target {}.", name.to_string_lossy(), record.target);
+        }
+    }
+
+    Ok(applied)
+}
+
+/// Drop every target, field and the buffer on this thread.
+///
+/// Targets hold references to script objects, so this must be called before the runtime stops.
+pub(crate) fn clear() {
+    TARGETS.with(|targets| targets.borrow_mut().clear());
+    FIELDS.with(|fields| fields.borrow_mut().clear());
+    BUFFER.with(|buffer| *buffer.borrow_mut() = vec![]);
+}
@@ -0,0 +1,154 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+//! Script threads, contexts running code in parallel with the host.
+//!
+//! Unlike the `_ctx` functions `pxs_thread_exec` does not wait, code is queued and `pxs_thread_join` waits for all of
+//! it. Modules of the spawning thread are added to the thread when it starts. Their functions are copied into the
+//! thread's function lookup (the spawning thread keeps owning the opaques) and only data variables are kept. The
+//! opaques are not copied, every thread calls its functions with the same pointer.
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use etffi::ptr_magic::PtrMagic;
+
+use crate::shared::{
+    context::{Sendable, pxs_Context},
+    func::{Function, lookup_copy_function, lookup_insert_function},
+    module::{ModuleCallback, ModuleVariable, pxs_Class, pxs_Module},
+    msgpack, registry,
+    var::{pxs_Var, pxs_VarT},
+};
+
+/// A script thread. Created by `pxs_thread_spawn` and freed by `pxs_thread_join`.
+#[allow(non_camel_case_types)]
+pub struct pxs_Thread {
+    context: pxs_Context,
+    /// First error of the queued code.
+    error: Arc<Mutex<Option<Sendable<pxs_Var>>>>,
+}
+
+impl PtrMagic for pxs_Thread {}
+
+/// Call `f` for every callback of `module`, its classes and its submodules.
+fn for_each_callback(module: &mut pxs_Module, f: &mut impl FnMut(&mut ModuleCallback)) {
+    module.callbacks.iter_mut().for_each(&mut *f);
+    for class in module.classes.iter_mut() {
+        class.callbacks.iter_mut().for_each(&mut *f);
+        if let Some(constructor) = class.constructor.as_mut() {
+            f(constructor);
+        }
+    }
+    for child in module.modules.iter_mut() {
+        for_each_callback(Arc::make_mut(child), f);
+    }
+}
+
+/// Copy the data variables. Objects and Functions belong to this thread.
+fn copy_variables(variables: &[ModuleVariable]) -> Vec<ModuleVariable> {
+    variables.iter().filter(|variable| msgpack::encode(&variable.var).is_ok()).cloned().collect()
+}
+
+/// Copy a module with only its data variables.
+fn copy_module(module: &pxs_Module) -> pxs_Module {
+    let classes = module
+        .classes
+        .iter()
+        .map(|class| {
+            Box::new(pxs_Class {
+                name: class.name.clone(),
+                callbacks: class.callbacks.clone(),
+                variables: copy_variables(&class.variables),
+                constructor: class.constructor.clone(),
+            })
+        })
+        .collect();
+    pxs_Module {
+        name: module.name.clone(),
+        callbacks: module.callbacks.clone(),
+        variables: copy_variables(&module.variables),
+        modules: module.modules.iter().map(|child| Arc::new(copy_module(child))).collect(),
+        classes,
+    }
+}
+
+/// Modules of a thread with the functions their callbacks point to.
+struct Modules {
+    modules: Vec<pxs_Module>,
+    functions: HashMap<i32, Function>,
+}
+
+impl Modules {
+    /// The modules added with `pxs_addmod` on this thread.
+    fn copy() -> Self {
+        let mut functions = HashMap::new();
+        let mut modules: Vec<pxs_Module> = registry::modules().iter().map(|module| copy_module(module)).collect();
+        for module in modules.iter_mut() {
+            for_each_callback(module, &mut |cbk| {
+                if let Some(function) = lookup_copy_function(cbk.idx) {
+                    functions.insert(cbk.idx, function);
+                }
+            });
+        }
+        Modules { modules, functions }
+    }
+
+    /// Add the modules on this thread, pointing their callbacks to the copied functions.
+    fn add(self) {
+        // Functions that do not fit in the lookup are left out, their callbacks get -1.
+        let idxs: HashMap<i32, i32> = self
+            .functions
+            .into_iter()
+            .filter_map(|(idx, function)| Some((idx, lookup_insert_function(function).ok()?)))
+            .collect();
+        for mut module in self.modules {
+            for_each_callback(&mut module, &mut |cbk| {
+                cbk.idx = idxs.get(&cbk.idx).copied().unwrap_or(-1);
+            });
+            crate::add_module(Arc::new(module));
+        }
+    }
+}
+
+impl pxs_Thread {
+    /// Start a script thread with the modules of this thread. `start` and `stop` run on it first and last.
+    pub(crate) fn new(start: fn(), stop: fn()) -> Self {
+        let context = pxs_Context::new(start, stop);
+        let modules = Sendable::new(Modules::copy());
+        context.post(move || modules.get().add());
+        pxs_Thread { context, error: Arc::new(Mutex::new(None)) }
+    }
+
+    /// Queue `job` without waiting for it. A non null result is kept if it is the first error. False if the thread
+    /// is gone.
+    pub(crate) fn exec(&self, job: impl FnOnce() -> pxs_VarT + Send + 'static) -> bool {
+        let error = Arc::clone(&self.error);
+        self.context.post(move || {
+            let res = job();
+            if res.is_null() {
+                return;
+            }
+            let mut error = error.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            if error.is_none() {
+                *error = Some(Sendable::new(pxs_Var::from_raw(res)));
+            } else {
+                let _ = pxs_Var::from_raw(res);
+            }
+        })
+    }
+
+    /// Wait for the queued code and stop the thread. Returns the first error.
+    pub(crate) fn join(self) -> Option<pxs_Var> {
+        // Dropping the context runs the queued jobs and ends the thread.
+        drop(self.context);
+        let mut error = self.error.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        error.take().map(Sendable::get)
+    }
+}
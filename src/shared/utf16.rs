@@ -0,0 +1,82 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+//! UTF-16 conversions for hosts that keep their strings as UTF-16 (i.e. Windows, C#).
+use std::{
+    ffi::CStr,
+    sync::atomic::{AtomicU8, Ordering},
+};
+
+/// What happens to invalid input when converting between UTF-16 and the UTF-8 of a `pxs_String`.
+///
+/// Unpaired surrogates (UTF-16), invalid bytes (UTF-8) and null characters, which a `pxs_String` can not hold, are
+/// invalid.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[allow(non_camel_case_types)]
+pub enum pxs_Utf16Policy {
+    /// Replace invalid input with U+FFFD. The default.
+    pxs_Utf16Replace = 0,
+    /// Drop invalid input.
+    pxs_Utf16Skip = 1,
+    /// Fail the conversion.
+    pxs_Utf16Error = 2,
+}
+
+static POLICY: AtomicU8 = AtomicU8::new(pxs_Utf16Policy::pxs_Utf16Replace as u8);
+
+/// Set the policy for all threads.
+pub(crate) fn set_policy(policy: pxs_Utf16Policy) {
+    POLICY.store(policy as u8, Ordering::SeqCst);
+}
+
+fn policy() -> pxs_Utf16Policy {
+    match POLICY.load(Ordering::Relaxed) {
+        1 => pxs_Utf16Policy::pxs_Utf16Skip,
+        2 => pxs_Utf16Policy::pxs_Utf16Error,
+        _ => pxs_Utf16Policy::pxs_Utf16Replace,
+    }
+}
+
+/// Apply the policy to a invalid character. None drops it.
+fn invalid(what: &str) -> Result<Option<char>, String> {
+    match policy() {
+        pxs_Utf16Policy::pxs_Utf16Replace => Ok(Some(char::REPLACEMENT_CHARACTER)),
+        pxs_Utf16Policy::pxs_Utf16Skip => Ok(None),
+        pxs_Utf16Policy::pxs_Utf16Error => Err(format!("String contains {what}.")),
+    }
+}
+
+/// Decode UTF-16 into a string for a `pxs_String`.
+pub(crate) fn decode(units: &[u16]) -> Result<String, String> {
+    let mut string = String::with_capacity(units.len());
+    for c in char::decode_utf16(units.iter().copied()) {
+        let c = match c {
+            Ok('\0') => invalid("a null character")?,
+            Ok(c) => Some(c),
+            Err(e) => invalid(&format!("a unpaired surrogate {:#x}", e.unpaired_surrogate()))?,
+        };
+        string.extend(c);
+    }
+    Ok(string)
+}
+
+/// Encode the string of a `pxs_String` as null terminated UTF-16.
+pub(crate) fn encode(string: &CStr) -> Result<Vec<u16>, String> {
+    let mut units = Vec::with_capacity(string.count_bytes() + 1);
+    for chunk in string.to_bytes().utf8_chunks() {
+        units.extend(chunk.valid().encode_utf16());
+        if !chunk.invalid().is_empty()
+            && let Some(c) = invalid("invalid UTF-8")?
+        {
+            units.extend(c.encode_utf16(&mut [0; 2]).iter());
+        }
+    }
+    units.push(0);
+    Ok(units)
+}
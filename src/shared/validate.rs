@@ -0,0 +1,224 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+//! Validating vars against declarative schemas.
+//!
+//! A schema is a map with any of:
+//! - `type`: a type name or a list of type names. `int`, `uint`, `float`, `number`, `string`, `bool`, `null`,
+//!   `list`, `map`, `bytes`, `function`, `object` or `any`.
+//! - `required`: a list of keys a map must have.
+//! - `keys`: a map of key => schema for values of a map.
+//! - `values`: a schema for every value of a map.
+//! - `items`: a schema for every item of a list.
+//! - `min`/`max`: a range for numbers, or for the length of strings, bytes, lists and maps.
+use std::ffi::CStr;
+
+use crate::shared::{
+    catalog::{MSG_INVALID_SCHEMA, MSG_SCHEMA_MAX, MSG_SCHEMA_MIN, MSG_SCHEMA_REQUIRED, MSG_SCHEMA_TYPE, message},
+    var::{pxs_Var, pxs_VarType},
+};
+
+/// Value of a schema key.
+fn field<'a>(schema: &'a pxs_Var, key: &str) -> Option<&'a pxs_Var> {
+    schema.get_map()?.get_item(&pxs_Var::new_string(key.to_string()))
+}
+
+/// Does `var` match the type name `name`.
+pub(crate) fn type_matches(var: &pxs_Var, name: &str) -> Option<bool> {
+    let tag = var.tag;
+    Some(match name {
+        "int" => matches!(tag, pxs_VarType::pxs_Int64 | pxs_VarType::pxs_Int32),
+        "uint" => matches!(tag, pxs_VarType::pxs_UInt64 | pxs_VarType::pxs_UInt32),
+        "float" => matches!(tag, pxs_VarType::pxs_Float64 | pxs_VarType::pxs_Float32),
+        "number" => number(var).is_some(),
+        "string" => tag == pxs_VarType::pxs_String,
+        "bool" => tag == pxs_VarType::pxs_Bool,
+        "null" => tag == pxs_VarType::pxs_Null,
+        "list" => tag == pxs_VarType::pxs_List,
+        "map" => tag == pxs_VarType::pxs_Map,
+        "bytes" => tag == pxs_VarType::pxs_Bytes,
+        "typed_array" => tag == pxs_VarType::pxs_TypedArray,
+        "function" => tag == pxs_VarType::pxs_Function,
+        "object" => matches!(tag, pxs_VarType::pxs_Object | pxs_VarType::pxs_HostObject),
+        "any" => true,
+        _ => return None,
+    })
+}
+
+/// Type name of `var` for errors.
+pub(crate) fn type_name(var: &pxs_Var) -> &'static str {
+    match var.tag {
+        pxs_VarType::pxs_Int64 | pxs_VarType::pxs_Int32 => "int",
+        pxs_VarType::pxs_UInt64 | pxs_VarType::pxs_UInt32 => "uint",
+        pxs_VarType::pxs_Float64 | pxs_VarType::pxs_Float32 => "float",
+        pxs_VarType::pxs_String => "string",
+        pxs_VarType::pxs_Bool => "bool",
+        pxs_VarType::pxs_Null => "null",
+        pxs_VarType::pxs_List => "list",
+        pxs_VarType::pxs_Map => "map",
+        pxs_VarType::pxs_Bytes => "bytes",
+        pxs_VarType::pxs_TypedArray => "typed_array",
+        pxs_VarType::pxs_Function => "function",
+        pxs_VarType::pxs_Object | pxs_VarType::pxs_HostObject => "object",
+        pxs_VarType::pxs_Factory => "factory",
+        pxs_VarType::pxs_Exception => "exception",
+        pxs_VarType::pxs_Byte => "byte",
+    }
+}
+
+/// A map key for errors.
+fn key_name(key: &pxs_Var) -> String {
+    match key.tag {
+        pxs_VarType::pxs_String => format!("{:?}", key.get_string().unwrap_or_default()),
+        _ => size(key).map_or_else(|| type_name(key).to_string(), |n| n.to_string()),
+    }
+}
+
+/// The number `min`/`max` are checked against: the value of a number, the length of anything else.
+fn size(var: &pxs_Var) -> Option<f64> {
+    match var.tag {
+        pxs_VarType::pxs_Int64 => var.get_i64().ok().map(|v| v as f64),
+        pxs_VarType::pxs_UInt64 => var.get_u64().ok().map(|v| v as f64),
+        pxs_VarType::pxs_Float64 => var.get_f64().ok(),
+        pxs_VarType::pxs_Int32 => var.get_i32().ok().map(|v| v as f64),
+        pxs_VarType::pxs_UInt32 => var.get_u32().ok().map(|v| v as f64),
+        pxs_VarType::pxs_Float32 => var.get_f32().ok().map(|v| v as f64),
+        pxs_VarType::pxs_String => Some(unsafe { CStr::from_ptr(var.value.string_val) }.to_bytes().len() as f64),
+        pxs_VarType::pxs_Bytes => var.get_bytes().map(|b| b.len() as f64),
+        pxs_VarType::pxs_TypedArray => var.get_typed_array().map(|a| a.len() as f64),
+        pxs_VarType::pxs_List => var.get_list().map(|l| l.len() as f64),
+        pxs_VarType::pxs_Map => var.get_map().map(|m| m.len() as f64),
+        _ => None,
+    }
+}
+
+/// A schema number.
+fn number(var: &pxs_Var) -> Option<f64> {
+    match var.tag {
+        pxs_VarType::pxs_Int64
+        | pxs_VarType::pxs_UInt64
+        | pxs_VarType::pxs_Float64
+        | pxs_VarType::pxs_Int32
+        | pxs_VarType::pxs_UInt32
+        | pxs_VarType::pxs_Float32 => size(var),
+        _ => None,
+    }
+}
+
+/// Path of a map key.
+fn key_path(path: &str, key: &pxs_Var) -> String {
+    match key.get_string() {
+        Ok(name) if key.is_string() && !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_') => {
+            format!("{path}.{name}")
+        }
+        _ => format!("{path}[{}]", key_name(key)),
+    }
+}
+
+fn invalid(path: &str) -> String {
+    message(MSG_INVALID_SCHEMA, "Invalid schema at {0}.", &[path.to_string()])
+}
+
+/// Check `var` at `path`. Errors go into `errors` with their path, a invalid schema is returned as Err.
+fn check(var: &pxs_Var, schema: &pxs_Var, path: &str, errors: &mut Vec<(String, String)>) -> Result<(), String> {
+    if !schema.is_map() {
+        return Err(invalid(path));
+    }
+
+    if let Some(types) = field(schema, "type") {
+        let names = if let Some(list) = types.get_list() {
+            list.vars.iter().map(|name| name.get_string()).collect::<Result<Vec<_>, _>>().map_err(|_| invalid(path))?
+        } else {
+            vec![types.get_string().map_err(|_| invalid(path))?]
+        };
+        let mut matched = false;
+        for name in names.iter() {
+            matched |= type_matches(var, name).ok_or_else(|| invalid(path))?;
+        }
+        if !matched {
+            errors.push((path.to_string(), message(
+                MSG_SCHEMA_TYPE,
+                "{0}: expected {1}, found {2}.",
+                &[path.to_string(), names.join(" or "), type_name(var).to_string()],
+            )));
+            // Nothing else makes sense for the wrong type.
+            return Ok(());
+        }
+    }
+
+    if let Some(value) = size(var) {
+        if let Some(min) = field(schema, "min") {
+            let min = number(min).ok_or_else(|| invalid(path))?;
+            if value < min {
+                errors.push((path.to_string(), message(
+                    MSG_SCHEMA_MIN,
+                    "{0}: must be at least {1}, found {2}.",
+                    &[path.to_string(), min.to_string(), value.to_string()],
+                )));
+            }
+        }
+        if let Some(max) = field(schema, "max") {
+            let max = number(max).ok_or_else(|| invalid(path))?;
+            if value > max {
+                errors.push((path.to_string(), message(
+                    MSG_SCHEMA_MAX,
+                    "{0}: must be at most {1}, found {2}.",
+                    &[path.to_string(), max.to_string(), value.to_string()],
+                )));
+            }
+        }
+    }
+
+    if let Some(list) = var.get_list()
+        && let Some(items) = field(schema, "items")
+    {
+        for (i, item) in list.vars.iter().enumerate() {
+            check(item, items, &format!("{path}[{i}]"), errors)?;
+        }
+    }
+
+    if let Some(map) = var.get_map() {
+        if let Some(required) = field(schema, "required") {
+            let required = required.get_list().ok_or_else(|| invalid(path))?;
+            for key in required.vars.iter() {
+                if map.get_item(key).is_none() {
+                    errors.push((path.to_string(), message(
+                        MSG_SCHEMA_REQUIRED,
+                        "{0}: missing required key {1}.",
+                        &[path.to_string(), key_name(key)],
+                    )));
+                }
+            }
+        }
+        if let Some(keys) = field(schema, "keys") {
+            let keys = keys.get_map().ok_or_else(|| invalid(path))?;
+            for key in keys.keys() {
+                if let Some(value) = map.get_item(key) {
+                    check(value, keys.get_item(key).unwrap(), &key_path(path, key), errors)?;
+                }
+            }
+        }
+        if let Some(values) = field(schema, "values") {
+            for key in map.keys() {
+                check(map.get_item(key).unwrap(), values, &key_path(path, key), errors)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Validate `var` against `schema`. Returns the errors, each prefixed with the path of the value (i.e.
+/// `$.units[2].hp`), sorted by path. Err if the schema itself is invalid.
+pub fn validate(var: &pxs_Var, schema: &pxs_Var) -> Result<Vec<String>, String> {
+    let mut errors = vec![];
+    check(var, schema, "$", &mut errors)?;
+    // Stable, errors of the same path keep their order.
+    errors.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(errors.into_iter().map(|(_, error)| error).collect())
+}
@@ -13,7 +13,7 @@ use std::{
 use etffi::{create_raw_string, borrow_string, ptr_magic::PtrMagic};
 
 use crate::{
-    pxs_error, shared::{PxsError, PxsRes, PxsResult, func::pxs_Func, object::{apply_ref_count_alloc, apply_ref_count_delete, get_object}, pxs_Runtime}
+    pxs_error, shared::{PxsError, PxsRes, PxsResult, catalog::*, convert, error::pxs_ErrorKind, func::pxs_Func, object::{apply_ref_count_alloc, apply_ref_count_delete, get_object}, current_vm, pxs_Runtime}
 };
 
 /// Macro for writing out the Var:: get methods.
@@ -90,6 +90,17 @@ pub enum pxs_VarType {
     pxs_Map,
     /// Holds 1 byte of memory (u8).
     pxs_Byte,
+    /// A buffer of raw bytes.
+    /// Lua (string), Python (bytes), JS/easyjs (Uint8Array)
+    pxs_Bytes,
+    /// Compact numbers for hosts streaming 32 bit data. Scripts get a regular number and always return the 64 bit
+    /// types.
+    pxs_Int32,
+    pxs_UInt32,
+    pxs_Float32,
+    /// A contiguous buffer of f32, f64, i32 or u8 numbers the host can read and write in place.
+    /// Lua (table), Python (list), JS/easyjs (Float32Array, Float64Array, Int32Array, Uint8Array)
+    pxs_TypedArray,
 }
 
 /// A `Object` in pixelscript is wrapped with a potential host_ptr. This allows for non language specific ref counting.
@@ -230,20 +241,30 @@ impl pxs_FactoryHolder {
     /// Get a cloned list of args. This list will include the runtime passed as the
     /// first item. Returns a owned pxs_Var not a pointer.
     pub fn get_args(&self, rt: pxs_Runtime) -> pxs_Var {
+        self.get_args_id(rt.into_i64())
+    }
+
+    /// Same as `get_args` but with a runtime id, which can be a custom backend.
+    pub fn get_args_id(&self, runtime_id: i64) -> pxs_Var {
         // Clone
         let args_clone = self.args.clone();
         // Check list
         assert!(args_clone.is_list(), "Factory args must be a list");
         // Get list and add runtime
         let args_list = args_clone.get_list().unwrap();
-        args_list.vars.insert(0, pxs_Var::new_i64(rt.into_i64()));
+        args_list.vars.insert(0, pxs_Var::new_i64(runtime_id));
 
         args_clone
     }
 
     /// Call the FactoryHolder function with the args!
     pub fn call(&self, rt: pxs_Runtime) -> pxs_Var {
-        let args = self.get_args(rt);
+        self.call_id(rt.into_i64())
+    }
+
+    /// Same as `call` but with a runtime id, which can be a custom backend.
+    pub fn call_id(&self, runtime_id: i64) -> pxs_Var {
+        let args = self.get_args_id(runtime_id);
         // Create raw memory that lasts for the direction of this call.
         let args_raw = args.into_raw();
 
@@ -302,11 +323,28 @@ impl pxs_VarList {
         pxs_VarList { vars: vec![] }
     }
 
-    fn get_rindex(&self, index: i32) -> i32 {
+    /// Index counted from the end for negative indexes, like Python. Can still be out of bounds.
+    fn get_rindex(&self, index: i32) -> i64 {
         if index < 0 {
-            (self.vars.len() as i32) + index
+            self.vars.len() as i64 + index as i64
+        } else {
+            index as i64
+        }
+    }
+
+    /// Resolve a index (negative ones count from the end) into a position in the list. `allow_end` also accepts the
+    /// position after the last item, for inserting.
+    ///
+    /// Err if the index is out of bounds.
+    pub fn resolve_index(&self, index: i32, allow_end: bool) -> PxsRes<usize> {
+        let len = self.vars.len() as i64;
+        let r_index = self.get_rindex(index);
+        let last = if allow_end { len } else { len - 1 };
+
+        if r_index < 0 || r_index > last {
+            pxs_error!("Index {index} is out of bounds for a list of length {len}.")
         } else {
-            index
+            Ok(r_index as usize)
         }
     }
 
@@ -317,64 +355,233 @@ impl pxs_VarList {
 
     /// Get a Var from the list. Supports negative based indexes.
     pub fn get_item(&self, index: i32) -> Option<&pxs_Var> {
-        // Get correct negative index.
-        let r_index = self.get_rindex(index);
-
-        if r_index < 0 {
-            None
-        } else {
-            self.vars.get(r_index as usize)
-        }
+        let r_index = self.resolve_index(index, false).ok()?;
+        self.vars.get(r_index)
     }
 
-    /// Set at a specific index a item.
+    /// Set at a specific index a item. Supports negative based indexes.
     ///
     /// Index must already be filled.
-    pub fn set_item(&mut self, item: pxs_Var, index: i32) -> bool {
-        // Get correct negative index.
-        let r_index = self.get_rindex(index);
-
-        if r_index < 0 {
-            return false;
-        }
+    pub fn set_item(&mut self, item: pxs_Var, index: i32) -> PxsRes<()> {
+        let r_index = self.resolve_index(index, false)?;
+        self.vars[r_index] = item;
+        Ok(())
+    }
 
-        if self.vars.len() < r_index as usize {
-            false
-        } else {
-            self.vars[r_index as usize] = item;
-            true
-        }
+    /// Remove a item at a specific index. Supports negative based indexes.
+    pub fn del_item(&mut self, index: i32) -> PxsRes<()> {
+        self.remove_item(index).map(|_| ())
     }
 
-    /// Remove a item at a specific index.
-    pub fn del_item(&mut self, index: i32) -> bool {
-        let r_index = self.get_rindex(index);
+    /// Remove and return the item at a index. Supports negative based indexes.
+    pub fn remove_item(&mut self, index: i32) -> PxsRes<pxs_Var> {
+        let r_index = self.resolve_index(index, false)?;
+        Ok(self.vars.remove(r_index))
+    }
 
-        if r_index < 0 {
-            return false;
-        }
+    /// Shallow copies of the items from `start` up to `end` (exclusive). Like Python, negative indexes count from
+    /// the end and out of bounds indexes are clamped.
+    pub fn slice(&self, start: i32, end: i32) -> Vec<pxs_Var> {
+        let len = self.vars.len() as i64;
+        let start = self.get_rindex(start).clamp(0, len) as usize;
+        let end = self.get_rindex(end).clamp(0, len) as usize;
 
-        if self.vars.len() < r_index as usize {
-            false
+        if start >= end {
+            vec![]
         } else {
-            self.vars.remove(index as usize);
-            true
+            self.vars[start..end].iter().map(|item| item.shallow_copy()).collect()
         }
     }
 
+    /// Index of the first item equal to `item`.
+    pub fn find(&self, item: &pxs_Var) -> Option<usize> {
+        self.vars.iter().position(|var| var == item)
+    }
+
     /// Get length
     pub fn len(&self) -> usize {
         self.vars.len()
     }
 
-    /// Insert a item moving all the rest to the right.
-    pub fn insert_item(&mut self, index: usize, item: pxs_Var) {
-        self.vars.insert(index, item);
+    /// Insert a item moving all the rest to the right. Supports negative based indexes, `-1` inserts before the last
+    /// item and `len` appends.
+    pub fn insert_item(&mut self, index: i32, item: pxs_Var) -> PxsRes<()> {
+        let r_index = self.resolve_index(index, true)?;
+        self.vars.insert(r_index, item);
+        Ok(())
     }
 }
 
 impl PtrMagic for pxs_VarList {}
 
+/// Holds data for a pxs_Var of bytes.
+///
+/// Unlike a `pxs_List` of `pxs_Byte`s the data is stored contiguously and goes into the scripting language
+/// as its native binary type.
+///
+/// When creating call:
+///
+/// `pxs_newbytebuf(ptr, len)`.
+///
+/// To read the data
+///
+/// `pxs_getbytes(bytes_ptr, &len)`
+#[allow(non_camel_case_types)]
+pub struct pxs_VarBytes {
+    pub data: Vec<u8>,
+}
+
+impl PtrMagic for pxs_VarBytes {}
+
+/// Element type of a `pxs_TypedArray`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[allow(non_camel_case_types)]
+pub enum pxs_ArrayKind {
+    pxs_ArrayF32 = 0,
+    pxs_ArrayF64 = 1,
+    pxs_ArrayI32 = 2,
+    pxs_ArrayU8 = 3,
+}
+
+impl pxs_ArrayKind {
+    /// Size of one element in bytes.
+    pub fn elem_size(&self) -> usize {
+        match self {
+            pxs_ArrayKind::pxs_ArrayF32 | pxs_ArrayKind::pxs_ArrayI32 => 4,
+            pxs_ArrayKind::pxs_ArrayF64 => 8,
+            pxs_ArrayKind::pxs_ArrayU8 => 1,
+        }
+    }
+}
+
+/// Holds data for a pxs_Var of a typed array.
+///
+/// Like `pxs_VarBytes` the data is stored contiguously, but as numbers of one `pxs_ArrayKind`. Scripts get their
+/// native typed array where there is one (JS) and a list of numbers otherwise.
+///
+/// When creating call:
+///
+/// `pxs_newtypedarray(kind, ptr, len)`.
+///
+/// To read or write the data in place
+///
+/// `pxs_gettypedarray(array_ptr, &kind, &len)`
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, PartialEq)]
+pub enum pxs_VarTypedArray {
+    F32(Vec<f32>),
+    F64(Vec<f64>),
+    I32(Vec<i32>),
+    U8(Vec<u8>),
+}
+
+impl PtrMagic for pxs_VarTypedArray {}
+
+impl pxs_VarTypedArray {
+    /// A array of `len` zeros.
+    pub fn new(kind: pxs_ArrayKind, len: usize) -> Self {
+        match kind {
+            pxs_ArrayKind::pxs_ArrayF32 => Self::F32(vec![0.0; len]),
+            pxs_ArrayKind::pxs_ArrayF64 => Self::F64(vec![0.0; len]),
+            pxs_ArrayKind::pxs_ArrayI32 => Self::I32(vec![0; len]),
+            pxs_ArrayKind::pxs_ArrayU8 => Self::U8(vec![0; len]),
+        }
+    }
+
+    /// Copy `len` elements of `kind` from `data`.
+    ///
+    /// # Safety
+    /// `data` must point to `len` aligned elements of `kind`.
+    pub unsafe fn copy_from(kind: pxs_ArrayKind, data: *const c_void, len: usize) -> Self {
+        unsafe {
+            match kind {
+                pxs_ArrayKind::pxs_ArrayF32 => Self::F32(std::slice::from_raw_parts(data as *const f32, len).to_vec()),
+                pxs_ArrayKind::pxs_ArrayF64 => Self::F64(std::slice::from_raw_parts(data as *const f64, len).to_vec()),
+                pxs_ArrayKind::pxs_ArrayI32 => Self::I32(std::slice::from_raw_parts(data as *const i32, len).to_vec()),
+                pxs_ArrayKind::pxs_ArrayU8 => Self::U8(std::slice::from_raw_parts(data as *const u8, len).to_vec()),
+            }
+        }
+    }
+
+    /// Convert a list of numbers. Floats are truncated and out of range values wrap, like `pxs_getint`.
+    pub fn from_list(kind: pxs_ArrayKind, list: &pxs_VarList) -> PxsRes<Self> {
+        let mut res = Self::new(kind, list.len());
+        for (i, item) in list.vars.iter().enumerate() {
+            match convert::to_f64(item, convert::pxs_Convert::pxs_ConvertTruncate) {
+                Ok(num) => res.set(i, num),
+                Err(_) => return pxs_error!("Typed array item {i} is not a number, got: {:#?}.", item.tag),
+            }
+        }
+        Ok(res)
+    }
+
+    pub fn kind(&self) -> pxs_ArrayKind {
+        match self {
+            Self::F32(_) => pxs_ArrayKind::pxs_ArrayF32,
+            Self::F64(_) => pxs_ArrayKind::pxs_ArrayF64,
+            Self::I32(_) => pxs_ArrayKind::pxs_ArrayI32,
+            Self::U8(_) => pxs_ArrayKind::pxs_ArrayU8,
+        }
+    }
+
+    /// Number of elements.
+    pub fn len(&self) -> usize {
+        match self {
+            Self::F32(v) => v.len(),
+            Self::F64(v) => v.len(),
+            Self::I32(v) => v.len(),
+            Self::U8(v) => v.len(),
+        }
+    }
+
+    /// Pointer to the first element.
+    pub fn as_mut_ptr(&mut self) -> *mut c_void {
+        match self {
+            Self::F32(v) => v.as_mut_ptr() as *mut c_void,
+            Self::F64(v) => v.as_mut_ptr() as *mut c_void,
+            Self::I32(v) => v.as_mut_ptr() as *mut c_void,
+            Self::U8(v) => v.as_mut_ptr() as *mut c_void,
+        }
+    }
+
+    /// The elements as native endian bytes.
+    pub fn as_bytes(&self) -> &[u8] {
+        let (ptr, len) = match self {
+            Self::F32(v) => (v.as_ptr() as *const u8, v.len() * 4),
+            Self::F64(v) => (v.as_ptr() as *const u8, v.len() * 8),
+            Self::I32(v) => (v.as_ptr() as *const u8, v.len() * 4),
+            Self::U8(v) => (v.as_ptr(), v.len()),
+        };
+        unsafe { std::slice::from_raw_parts(ptr, len) }
+    }
+
+    /// Element `i` as a number var. Scripts get the 64 bit types.
+    pub fn get(&self, i: usize) -> Option<pxs_Var> {
+        match self {
+            Self::F32(v) => v.get(i).map(|n| pxs_Var::new_f64(*n as f64)),
+            Self::F64(v) => v.get(i).map(|n| pxs_Var::new_f64(*n)),
+            Self::I32(v) => v.get(i).map(|n| pxs_Var::new_i64(*n as i64)),
+            Self::U8(v) => v.get(i).map(|n| pxs_Var::new_i64(*n as i64)),
+        }
+    }
+
+    /// Set element `i`, converting like `as`. Does nothing if `i` is out of bounds.
+    pub fn set(&mut self, i: usize, num: f64) {
+        match self {
+            Self::F32(v) => v.get_mut(i).map(|n| *n = num as f32),
+            Self::F64(v) => v.get_mut(i).map(|n| *n = num),
+            Self::I32(v) => v.get_mut(i).map(|n| *n = num as i64 as i32),
+            Self::U8(v) => v.get_mut(i).map(|n| *n = num as i64 as u8),
+        };
+    }
+
+    /// Copy the elements into a `pxs_List` of numbers.
+    pub fn to_list(&self) -> pxs_Var {
+        pxs_Var::new_list_with((0..self.len()).filter_map(|i| self.get(i)).collect())
+    }
+}
+
 /// The Variables actual value union.
 #[repr(C)]
 #[allow(non_camel_case_types)]
@@ -391,7 +598,12 @@ pub union pxs_VarValue {
     pub function_val: *mut c_void,
     pub factory_val: *mut pxs_FactoryHolder,
     pub map_val: *mut pxs_VarMap,
-    pub byte_val: u8
+    pub byte_val: u8,
+    pub bytes_val: *mut pxs_VarBytes,
+    pub typed_array_val: *mut pxs_VarTypedArray,
+    pub i32_val: i32,
+    pub u32_val: u32,
+    pub f32_val: f32,
 }
 
 #[allow(non_camel_case_types)]
@@ -422,6 +634,7 @@ pub unsafe extern "C" fn default_deleter(_ptr: *mut c_void) {
 /// - Factory (a function that is run on the fly and its result is treated as a variable.)
 /// - Exception
 /// - byte (u8)
+/// - Bytes (buffer of u8)
 ///
 /// When working with objects you must use the C-api:
 /// ```c
@@ -444,6 +657,14 @@ pub struct pxs_Var {
 
     /// Optional delete method. This is used for Pointers in Objects, and Functions.
     pub deleter: Cell<pxs_DeleterFn>,
+
+    /// The runtime (`pxs_Runtime`) that produced this var, -1 for vars made by the host. Set by the language
+    /// converters for Objects and Functions.
+    pub origin: i32,
+    /// The VM (thread) that produced this var, -1 for vars made by the host. Set together with `origin`.
+    pub vm: i32,
+    /// Kind of a `pxs_Exception`, see `pxs_newerror`. `pxs_GenericError` for every other var.
+    pub error_kind: pxs_ErrorKind,
 }
 
 // Rust specific functions
@@ -452,7 +673,72 @@ impl pxs_Var {
         Self {
             tag,
             value,
-            deleter: Cell::new(deleter)
+            deleter: Cell::new(deleter),
+            origin: -1,
+            vm: -1,
+            error_kind: pxs_ErrorKind::pxs_GenericError,
+        }
+    }
+
+    /// Mark this var as produced by `runtime` in the current VM.
+    pub fn with_origin(mut self, runtime: pxs_Runtime) -> Self {
+        self.origin = runtime.into_i64() as i32;
+        self.vm = current_vm();
+        self
+    }
+
+    /// Was this var produced by a VM on another thread?
+    pub fn is_foreign(&self) -> bool {
+        self.vm >= 0 && self.vm != current_vm()
+    }
+
+    /// Deep copy a data var so it can be used in any runtime of any VM. Objects, Functions, Factories and host
+    /// objects belong to the VM they were made in and can not be migrated.
+    pub fn migrate(&self) -> PxsResult {
+        match self.tag {
+            pxs_VarType::pxs_List => {
+                let mut vars = vec![];
+                for item in self.get_list().unwrap().vars.iter() {
+                    vars.push(item.migrate()?);
+                }
+                Ok(pxs_Var::new_list_with(vars))
+            }
+            pxs_VarType::pxs_Map => {
+                let res = pxs_Var::new_map();
+                let new_map = res.get_map().unwrap();
+                let map = self.get_map().unwrap();
+                for key in map.keys() {
+                    let value = map.get_item(key).unwrap();
+                    new_map.add_item(key.migrate()?, value.migrate()?);
+                }
+                Ok(res)
+            }
+            pxs_VarType::pxs_Object
+            | pxs_VarType::pxs_Function
+            | pxs_VarType::pxs_Factory
+            | pxs_VarType::pxs_HostObject => pxs_error!(
+                "{}",
+                message(
+                    MSG_NOT_MIGRATABLE,
+                    "Can not migrate a {0} to another VM. Pass data (numbers, strings, lists, maps, bytes) instead.",
+                    &[format!("{:?}", self.tag)],
+                )
+            ),
+            _ => {
+                let mut res = self.clone();
+                res.origin = -1;
+                res.vm = -1;
+                Ok(res)
+            }
+        }
+    }
+
+    /// Get the runtime that produced this var. None for vars made by the host.
+    pub fn get_origin(&self) -> Option<pxs_Runtime> {
+        if self.origin < 0 {
+            None
+        } else {
+            pxs_Runtime::from_i64(self.origin as i64)
         }
     }
 
@@ -500,6 +786,19 @@ impl pxs_Var {
         }
     }
 
+    /// Borrow the C string of a pxs_String without copying it.
+    pub fn get_cstr(&self) -> Result<&CStr, PxsError> {
+        if self.tag != pxs_VarType::pxs_String {
+            return pxs_error!("Var is not a string.");
+        }
+        unsafe {
+            if self.value.string_val.is_null() {
+                return Err(String::from("String pointer is null"));
+            }
+            Ok(CStr::from_ptr(self.value.string_val))
+        }
+    }
+
     /// Create a new String var.
     ///
     /// The memory is leaked and needs to be freed eventually. It is freed by Var::free_var(). And done so automatically
@@ -568,16 +867,34 @@ impl pxs_Var {
         Self::new(pxs_VarType::pxs_Exception, pxs_VarValue{string_val: create_raw_string!(msg.to_string())}, default_deleter)
     }
 
+    /// Create a new Exception var of a `kind`.
+    pub fn new_error<T: ToString>(msg: T, kind: pxs_ErrorKind) -> Self {
+        let mut var = Self::new_exception(msg);
+        var.error_kind = kind;
+        var
+    }
+
     /// Create a new Map var.
     pub fn new_map() -> Self {
         Self::new(pxs_VarType::pxs_Map, pxs_VarValue{map_val: pxs_VarMap::new().into_raw()}, default_deleter)
     }
 
+    /// Create a new Bytes var.
+    pub fn new_bytes(data: Vec<u8>) -> Self {
+        Self::new(pxs_VarType::pxs_Bytes, pxs_VarValue{bytes_val: pxs_VarBytes { data }.into_raw()}, default_deleter)
+    }
+
+    /// Create a new TypedArray var.
+    pub fn new_typed_array(array: pxs_VarTypedArray) -> Self {
+        Self::new(pxs_VarType::pxs_TypedArray, pxs_VarValue{typed_array_val: array.into_raw()}, default_deleter)
+    }
+
     /// Get the IDX of the object if Host, i64, u64
     pub fn get_host_idx(&self) -> i32 {
         match self.tag {
             pxs_VarType::pxs_Int64 => self.get_i64().unwrap() as i32,
             pxs_VarType::pxs_UInt64 => self.get_u64().unwrap() as i32,
+            pxs_VarType::pxs_Int32 => unsafe { self.value.i32_val },
             pxs_VarType::pxs_HostObject => unsafe { self.value.host_object_val },
             _ => -1,
         }
@@ -627,8 +944,26 @@ impl pxs_Var {
         }
     }
 
+    /// Get the bytes of a pxs_Bytes.
+    pub fn get_bytes(&self) -> Option<&mut Vec<u8>> {
+        if !self.is_bytes() {
+            None
+        } else {
+            unsafe { Some(&mut pxs_VarBytes::from_borrow(self.value.bytes_val).data) }
+        }
+    }
+
+    /// Get the array of a pxs_TypedArray.
+    pub fn get_typed_array(&self) -> Option<&mut pxs_VarTypedArray> {
+        if !self.is_typed_array() {
+            None
+        } else {
+            unsafe { Some(pxs_VarTypedArray::from_borrow(self.value.typed_array_val)) }
+        }
+    }
+
     /// Debug struct
-    unsafe fn dbg(&self) -> String {
+    pub(crate) unsafe fn dbg(&self) -> String {
         unsafe {
             let details = match self.tag {
                 pxs_VarType::pxs_Int64 => self.value.i64_val.to_string(),
@@ -663,7 +998,15 @@ impl pxs_Var {
 
                     res
                 },
-                pxs_VarType::pxs_Byte => self.value.byte_val.to_string()
+                pxs_VarType::pxs_Byte => self.value.byte_val.to_string(),
+                pxs_VarType::pxs_Bytes => format!("Bytes({})", self.get_bytes().unwrap().len()),
+                pxs_VarType::pxs_Int32 => self.value.i32_val.to_string(),
+                pxs_VarType::pxs_UInt32 => self.value.u32_val.to_string(),
+                pxs_VarType::pxs_Float32 => self.value.f32_val.to_string(),
+                pxs_VarType::pxs_TypedArray => {
+                    let array = self.get_typed_array().unwrap();
+                    format!("TypedArray({:?}, {})", array.kind(), array.len())
+                },
             };
 
             details
@@ -681,7 +1024,10 @@ impl pxs_Var {
             *mut c_void,
             pxs_VarType::pxs_Function
         ),
-        (get_byte, byte_val, u8, pxs_VarType::pxs_Byte)
+        (get_byte, byte_val, u8, pxs_VarType::pxs_Byte),
+        (get_i32, i32_val, i32, pxs_VarType::pxs_Int32),
+        (get_u32, u32_val, u32, pxs_VarType::pxs_UInt32),
+        (get_f32, f32_val, f32, pxs_VarType::pxs_Float32)
     );
 
     // $t:ty, $func:ident, $vt:expr, $vn:ident
@@ -690,7 +1036,10 @@ impl pxs_Var {
         u64, new_u64, pxs_VarType::pxs_UInt64, u64_val;
         f64, new_f64, pxs_VarType::pxs_Float64, f64_val;
         bool, new_bool, pxs_VarType::pxs_Bool, bool_val;
-        u8, new_byte, pxs_VarType::pxs_Byte, byte_val
+        u8, new_byte, pxs_VarType::pxs_Byte, byte_val;
+        i32, new_i32, pxs_VarType::pxs_Int32, i32_val;
+        u32, new_u32, pxs_VarType::pxs_UInt32, u32_val;
+        f32, new_f32, pxs_VarType::pxs_Float32, f32_val
     }
 
     write_is_methods! {
@@ -707,15 +1056,28 @@ impl pxs_Var {
         is_factory, pxs_VarType::pxs_Factory;
         is_exception, pxs_VarType::pxs_Exception;
         is_map, pxs_VarType::pxs_Map;
-        is_byte, pxs_VarType::pxs_Byte
+        is_byte, pxs_VarType::pxs_Byte;
+        is_bytes, pxs_VarType::pxs_Bytes;
+        is_i32, pxs_VarType::pxs_Int32;
+        is_u32, pxs_VarType::pxs_UInt32;
+        is_f32, pxs_VarType::pxs_Float32;
+        is_typed_array, pxs_VarType::pxs_TypedArray
     }
 
     /// Do a shallow copy on this variable.
     /// 
-    /// Int/Uint/Float/String/Bool/Null/Exception/HostObject are cloned.
+    /// Int/Uint/Float (64 and 32 bit)/String/Bool/Null/Exception/HostObject/Byte/Bytes/TypedArray are cloned.
     /// 
     /// List/Maps/Objects/Functions/Factories are cloned without deleters.
     pub fn shallow_copy(&self) -> pxs_Var {
+        let mut res = self.shallow_copy_value();
+        res.origin = self.origin;
+        res.vm = self.vm;
+        res.error_kind = self.error_kind;
+        res
+    }
+
+    fn shallow_copy_value(&self) -> pxs_Var {
         unsafe{
             match self.tag {
                 pxs_VarType::pxs_Int64 => self.clone(),
@@ -783,7 +1145,10 @@ impl pxs_Var {
                     // Follows a similar structure to pxs_List shallow copy
                     Self::new(pxs_VarType::pxs_Map, pxs_VarValue{map_val: map.into_raw()}, default_deleter)
                 },
-                pxs_VarType::pxs_Byte => self.clone()
+                pxs_VarType::pxs_Byte => self.clone(),
+                pxs_VarType::pxs_Bytes => self.clone(),
+                pxs_VarType::pxs_Int32 | pxs_VarType::pxs_UInt32 | pxs_VarType::pxs_Float32 => self.clone(),
+                pxs_VarType::pxs_TypedArray => self.clone(),
             }
         }
     }
@@ -816,6 +1181,9 @@ impl pxs_Var {
                 size
             },
             pxs_VarType::pxs_Byte => 1,
+            pxs_VarType::pxs_Bytes => self.get_bytes().unwrap().len(),
+            pxs_VarType::pxs_Int32 | pxs_VarType::pxs_UInt32 | pxs_VarType::pxs_Float32 => 4,
+            pxs_VarType::pxs_TypedArray => self.get_typed_array().unwrap().as_bytes().len(),
             _ => 0
         }
     }
@@ -863,6 +1231,26 @@ impl pxs_Var {
                 pxs_VarType::pxs_Byte => {
                     let val = self.get_byte().unwrap().to_ne_bytes();
                     core::ptr::copy_nonoverlapping(val.as_ptr(), ptr, size);
+                },
+                pxs_VarType::pxs_Bytes => {
+                    let val = self.get_bytes().unwrap();
+                    core::ptr::copy_nonoverlapping(val.as_ptr(), ptr, size);
+                }
+                pxs_VarType::pxs_Int32 => {
+                    let val = self.value.i32_val.to_ne_bytes();
+                    core::ptr::copy_nonoverlapping(val.as_ptr(), ptr, size);
+                }
+                pxs_VarType::pxs_UInt32 => {
+                    let val = self.value.u32_val.to_ne_bytes();
+                    core::ptr::copy_nonoverlapping(val.as_ptr(), ptr, size);
+                }
+                pxs_VarType::pxs_Float32 => {
+                    let val = self.value.f32_val.to_ne_bytes();
+                    core::ptr::copy_nonoverlapping(val.as_ptr(), ptr, size);
+                }
+                pxs_VarType::pxs_TypedArray => {
+                    let val = self.get_typed_array().unwrap().as_bytes();
+                    core::ptr::copy_nonoverlapping(val.as_ptr(), ptr, size);
                 }
                 _ => {
                     return 0;
@@ -919,6 +1307,14 @@ impl Drop for pxs_Var {
             let _ = unsafe {
                 pxs_VarMap::from_raw(self.value.map_val)
             };
+        } else if self.tag == pxs_VarType::pxs_Bytes {
+            let _ = unsafe {
+                pxs_VarBytes::from_raw(self.value.bytes_val)
+            };
+        } else if self.tag == pxs_VarType::pxs_TypedArray {
+            let _ = unsafe {
+                pxs_VarTypedArray::from_raw(self.value.typed_array_val)
+            };
         }
     }
 }
@@ -927,6 +1323,17 @@ impl PtrMagic for pxs_Var {}
 
 impl Clone for pxs_Var {
     fn clone(&self) -> Self {
+        let mut res = self.clone_value();
+        res.origin = self.origin;
+        res.vm = self.vm;
+        res.error_kind = self.error_kind;
+        res
+    }
+}
+
+impl pxs_Var {
+    /// Clone the value. Used by `clone`.
+    fn clone_value(&self) -> Self {
         unsafe {
             match self.tag {
                 pxs_VarType::pxs_Int64 => pxs_Var::new_i64(self.value.i64_val),
@@ -1016,6 +1423,15 @@ impl Clone for pxs_Var {
                 pxs_VarType::pxs_Byte => {
                     pxs_Var::new_byte(self.value.byte_val)
                 }
+                pxs_VarType::pxs_Bytes => {
+                    pxs_Var::new_bytes(self.get_bytes().unwrap().clone())
+                }
+                pxs_VarType::pxs_Int32 => pxs_Var::new_i32(self.value.i32_val),
+                pxs_VarType::pxs_UInt32 => pxs_Var::new_u32(self.value.u32_val),
+                pxs_VarType::pxs_Float32 => pxs_Var::new_f32(self.value.f32_val),
+                pxs_VarType::pxs_TypedArray => {
+                    pxs_Var::new_typed_array(self.get_typed_array().unwrap().clone())
+                }
             }
         }
     }
@@ -1071,6 +1487,20 @@ impl PartialEq for pxs_Var {
                 (pxs_VarType::pxs_Byte, _) => {
                     false
                 }
+                (pxs_VarType::pxs_Bytes, pxs_VarType::pxs_Bytes) => {
+                    self.get_bytes() == other.get_bytes()
+                }
+                (pxs_VarType::pxs_Bytes, _) => false,
+                (pxs_VarType::pxs_Int32, pxs_VarType::pxs_Int32) => self.value.i32_val == other.value.i32_val,
+                (pxs_VarType::pxs_Int32, _) => false,
+                (pxs_VarType::pxs_UInt32, pxs_VarType::pxs_UInt32) => self.value.u32_val == other.value.u32_val,
+                (pxs_VarType::pxs_UInt32, _) => false,
+                (pxs_VarType::pxs_Float32, pxs_VarType::pxs_Float32) => self.value.f32_val == other.value.f32_val,
+                (pxs_VarType::pxs_Float32, _) => false,
+                (pxs_VarType::pxs_TypedArray, pxs_VarType::pxs_TypedArray) => {
+                    self.get_typed_array() == other.get_typed_array()
+                }
+                (pxs_VarType::pxs_TypedArray, _) => false,
             }
         }
     }
@@ -1088,6 +1518,15 @@ impl Hash for pxs_Var {
                 pxs_VarType::pxs_Bool => self.value.bool_val.hash(state),
                 pxs_VarType::pxs_Float64 => self.value.f64_val.to_bits().hash(state),
                 pxs_VarType::pxs_Byte => self.value.byte_val.hash(state),
+                pxs_VarType::pxs_Bytes => self.get_bytes().unwrap().hash(state),
+                pxs_VarType::pxs_Int32 => self.value.i32_val.hash(state),
+                pxs_VarType::pxs_UInt32 => self.value.u32_val.hash(state),
+                pxs_VarType::pxs_Float32 => self.value.f32_val.to_bits().hash(state),
+                pxs_VarType::pxs_TypedArray => {
+                    let array = self.get_typed_array().unwrap();
+                    array.kind().hash(state);
+                    array.as_bytes().hash(state);
+                }
                 _ => panic!("Can not Hash none basic pxs_VarType")
             }
         }
@@ -1097,48 +1536,66 @@ impl Hash for pxs_Var {
 impl pxs_Var {
     /// Return a error saying that the found type was not expected.
     pub fn incorrect_type_ep(expected: pxs_VarType, found: pxs_VarType) -> Self {
-        Self::new_exception(format!("Expected {:#?}, found {:#?}", expected, found))
+        Self::new_exception(message(MSG_INCORRECT_TYPE, "Expected {0}, found {1}", &[format!("{:#?}", expected), format!("{:#?}", found)]))
     }
     
     /// Return a error saying that the found type was not expected of a vector of types
     pub fn incorrect_types_ep(allowed: Vec<pxs_VarType>, found: pxs_VarType) -> Self {
-        Self::new_exception(format!("Allowed types: {:#?}, found: {:#?}", allowed, found))
+        Self::new_exception(message(MSG_INCORRECT_TYPES, "Allowed types: {0}, found: {1}", &[format!("{:#?}", allowed), format!("{:#?}", found)]))
     }
 
     /// A general error saying that paramaters passed in to the function were null.
     pub fn null_params_ep() -> Self {
-        Self::new_exception("Paramaters are null")
+        Self::new_exception(message(MSG_NULL_PARAMS, "Paramaters are null", &[]))
     }
 
     /// A specific error that a specific param was null.
     pub fn null_param_ep<T: ToString>(name:T) -> Self {
-        Self::new_exception(format!("{} is null", name.to_string()))
+        Self::new_exception(message(MSG_NULL_PARAM, "{0} is null", &[name.to_string()]))
     }
 
     /// Unkownn runtime exception
     pub fn unkown_runtime_ep(runtime: i64) -> Self {
-        Self::new_exception(format!("Unkown runtime: {runtime}"))
+        Self::new_exception(message(MSG_UNKNOWN_RUNTIME, "Unkown runtime: {0}", &[runtime.to_string()]))
     }
 
     /// Unkown runtime var exception
+    pub fn foreign_var_ep() -> Self {
+        Self::new_exception(message(MSG_FOREIGN_VAR, "Var belongs to a VM on another thread. Use `pxs_migrate` to pass data between VMs.", &[]))
+    }
+
+    /// A var made by one runtime used with another.
+    pub fn runtime_mismatch_ep(origin: i64, runtime: i64) -> Self {
+        Self::new_exception(message(
+            MSG_RUNTIME_MISMATCH,
+            "Var belongs to runtime {0} but was used with runtime {1}.",
+            &[origin.to_string(), runtime.to_string()],
+        ))
+    }
+
     pub fn unkown_runtime_var_ep(runtime: pxs_VarT) -> Self {
         let var = unsafe{Self::from_borrow(runtime)};
-        Self::new_exception(format!("Unkown runtime: {:#?}", var))
+        Self::new_exception(message(MSG_UNKNOWN_RUNTIME, "Unkown runtime: {0}", &[format!("{:#?}", var)]))
     }
 
     /// When a item is not found in list or map.
     pub fn item_not_found_ep() -> Self {
-        Self::new_exception("Item not found")
+        Self::new_exception(message(MSG_ITEM_NOT_FOUND, "Item not found", &[]))
     }
 
     /// Feature not enabled
     pub fn feature_not_enabled_ep(feature: &str) -> Self {
-        Self::new_exception(format!("{feature} is not enabled."))
+        Self::new_exception(message(MSG_FEATURE_NOT_ENABLED, "{0} is not enabled.", &[feature.to_string()]))
     }
 
     /// Expected n args
     pub fn expected_n_args_ep(n: u32, c: u32) -> Self {
-        Self::new_exception(format!("Expected {n} args. Got {c}."))
+        Self::new_exception(message(MSG_EXPECTED_N_ARGS, "Expected {0} args. Got {1}.", &[n.to_string(), c.to_string()]))
+    }
+
+    /// Calling a function the current capability token does not allow.
+    pub fn permission_denied_ep(name: &str) -> Self {
+        Self::new_exception(message(MSG_PERMISSION_DENIED, "Permission denied: '{0}'.", &[name.to_string()]))
     }
 }
 
@@ -1161,6 +1618,9 @@ pub trait ObjectMethods {
 
     /// Get a object/function based off their name
     fn get_from_name(name: &str) -> PxsResult;
+
+    /// Set a global variable (`_G` in Lua, `__main__` in Python, `globalThis` in JavaScript).
+    fn set_global(name: &str, value: &pxs_Var) -> PxsRes<()>;
 }
 
 /// Type Helper for a pxs_Var
@@ -0,0 +1,372 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+//! Script workers, a runtime running in a child process so a crash in its VM does not take down the host.
+//!
+//! The host side is a backend (see `backend.rs`) forwarding to the child over its stdin and stdout. Messages are Maps
+//! with a `op` key, encoded with `msgpack::encode` and written after `MAGIC`. Anything else the child writes to
+//! stdout (i.e. `print` in scripts) is passed through to the host's stdout.
+//!
+//! Requests to the child are `add_module`, `remove_module`, `execute`, `eval` and `exit`, each answered with a
+//! `result`. While running code the child calls host functions with `call`, answered with a `return`. Both sides
+//! handle requests while waiting for an answer, so host functions can run more code in the worker.
+use std::{
+    ffi::{CStr, c_char},
+    io::{self, BufReader, Read, Write},
+    process::{Child, ChildStdin, ChildStdout, Command, Stdio},
+    sync::OnceLock,
+};
+
+use etffi::ptr_magic::PtrMagic;
+
+use crate::{
+    rust_api::{Module, Value},
+    shared::{
+        backend::pxs_BackendVTable,
+        catalog::{MSG_WORKER_CRASHED, message},
+        func::{call_function, lookup_has_function},
+        msgpack,
+        pxs_Opaque,
+        var::{pxs_Var, pxs_VarT},
+    },
+};
+
+/// Marks the start of a message on the child's stdout.
+const MAGIC: &[u8] = b"\x00PXSW";
+
+fn new_map(items: Vec<(&str, pxs_Var)>) -> pxs_Var {
+    let var = pxs_Var::new_map();
+    let map = var.get_map().unwrap();
+    for (key, value) in items {
+        map.add_item(pxs_Var::new_string(key.to_string()), value);
+    }
+    var
+}
+
+fn field(msg: &pxs_Var, key: &str) -> pxs_Var {
+    msg.get_map()
+        .and_then(|map| map.get_item(&pxs_Var::new_string(key.to_string())))
+        .cloned()
+        .unwrap_or_else(pxs_Var::new_null)
+}
+
+fn string_field(msg: &pxs_Var, key: &str) -> String {
+    field(msg, key).get_string().unwrap_or_default()
+}
+
+/// A `result` or `return` message for `value`, exceptions are sent as `error`.
+fn answer(op: &str, value: pxs_Var) -> pxs_Var {
+    if value.is_exception() {
+        let error = value.get_string().unwrap_or_default();
+        new_map(vec![("op", pxs_Var::new_string(op.to_string())), ("error", pxs_Var::new_string(error))])
+    } else {
+        new_map(vec![("op", pxs_Var::new_string(op.to_string())), ("value", value)])
+    }
+}
+
+/// The value of a `result` or `return` message.
+fn answered(msg: &pxs_Var) -> pxs_Var {
+    match field(msg, "error").get_string() {
+        Ok(error) => pxs_Var::new_exception(error),
+        Err(_) => field(msg, "value"),
+    }
+}
+
+fn write_message(out: &mut impl Write, msg: &pxs_Var) -> io::Result<()> {
+    let data = msgpack::encode(msg).map_err(io::Error::other)?;
+    out.write_all(MAGIC)?;
+    out.write_all(&data)?;
+    out.flush()
+}
+
+/// Write a answer, values that are not data are sent as a error.
+fn write_answer(out: &mut impl Write, op: &str, value: pxs_Var) -> io::Result<()> {
+    let msg = answer(op, value);
+    match msgpack::encode(&msg) {
+        Ok(_) => write_message(out, &msg),
+        Err(e) => write_message(out, &answer(op, pxs_Var::new_exception(e))),
+    }
+}
+
+/// Read the next message. Bytes before it are written to `passthrough`.
+fn read_message(input: &mut impl Read, passthrough: &mut impl Write) -> io::Result<pxs_Var> {
+    let mut matched = 0;
+    let mut byte = [0u8];
+    while matched < MAGIC.len() {
+        input.read_exact(&mut byte)?;
+        if byte[0] == MAGIC[matched] {
+            matched += 1;
+            continue;
+        }
+        passthrough.write_all(&MAGIC[..matched])?;
+        if byte[0] == MAGIC[0] {
+            matched = 1;
+        } else {
+            passthrough.write_all(&byte)?;
+            matched = 0;
+        }
+    }
+    passthrough.flush()?;
+
+    let mut len = [0u8; 4];
+    input.read_exact(&mut len)?;
+    let mut data = len.to_vec();
+    data.resize(4 + u32::from_be_bytes(len) as usize, 0);
+    input.read_exact(&mut data[4..])?;
+    msgpack::decode(&data).map_err(io::Error::other)
+}
+
+/// Runs code in the child.
+pub(crate) type RunFn = fn(runtime: i32, code: &str, name: &str) -> pxs_Var;
+
+struct ChildState {
+    runtime: i32,
+    execute: RunFn,
+    eval: RunFn,
+}
+
+static CHILD: OnceLock<ChildState> = OnceLock::new();
+
+/// Drop the Objects and non data variables of a module description, they can not be sent to a worker.
+fn data_only(module: &pxs_Var) -> pxs_Var {
+    let list = |key: &str| field(module, key).get_list().map(|l| l.vars.clone()).unwrap_or_default();
+    let variables = list("variables").into_iter().filter(|v| msgpack::encode(v).is_ok()).collect();
+    let modules = list("modules").iter().map(data_only).collect();
+    new_map(vec![
+        ("name", field(module, "name")),
+        ("functions", pxs_Var::new_list_with(list("functions"))),
+        ("variables", pxs_Var::new_list_with(variables)),
+        ("modules", pxs_Var::new_list_with(modules)),
+    ])
+}
+
+/// Build a module whose functions call the host.
+fn proxy_module(desc: &pxs_Var) -> Module {
+    let mut module = Module::new(&string_field(desc, "name"));
+    let list = |key: &str| field(desc, key).get_list().map(|l| l.vars.clone()).unwrap_or_default();
+    for function in list("functions") {
+        let handle = field(&function, "handle").get_i64().unwrap_or(-1);
+        module.add_fn(&string_field(&function, "name"), move |args| {
+            let args = args.iter().skip(1).map(|arg| arg.as_var().clone()).collect();
+            Value::from_var(call_host(handle, pxs_Var::new_list_with(args)))
+        });
+    }
+    for variable in list("variables") {
+        module.add_var(&string_field(&variable, "name"), Value::from_var(field(&variable, "value")));
+    }
+    for child in list("modules") {
+        module.add_module(proxy_module(&child));
+    }
+    module
+}
+
+/// Handle a request from the host.
+fn handle_request(state: &ChildState, msg: &pxs_Var) -> pxs_Var {
+    match string_field(msg, "op").as_str() {
+        "add_module" => {
+            proxy_module(&field(msg, "module")).register();
+            pxs_Var::new_null()
+        }
+        "remove_module" => {
+            crate::remove_module(&string_field(msg, "name"));
+            pxs_Var::new_null()
+        }
+        "execute" => (state.execute)(state.runtime, &string_field(msg, "code"), &string_field(msg, "name")),
+        "eval" => (state.eval)(state.runtime, &string_field(msg, "code"), &string_field(msg, "name")),
+        op => pxs_Var::new_exception(format!("Unknown worker request '{op}'.")),
+    }
+}
+
+/// Read messages from the host until a `return`, handling its requests on the way. Err when the host is gone.
+fn child_wait() -> io::Result<pxs_Var> {
+    let state = CHILD.get().unwrap();
+    loop {
+        let msg = read_message(&mut io::stdin().lock(), &mut io::sink())?;
+        match string_field(&msg, "op").as_str() {
+            "return" => return Ok(answered(&msg)),
+            "exit" => std::process::exit(0),
+            _ => {
+                let res = handle_request(state, &msg);
+                write_answer(&mut io::stdout().lock(), "result", res)?;
+            }
+        }
+    }
+}
+
+/// Call a host function from the child.
+fn call_host(handle: i64, args: pxs_Var) -> pxs_Var {
+    let msg = new_map(vec![
+        ("op", pxs_Var::new_string("call".to_string())),
+        ("handle", pxs_Var::new_i64(handle)),
+        ("args", args),
+    ]);
+    if let Err(e) = write_message(&mut io::stdout().lock(), &msg) {
+        return pxs_Var::new_exception(format!("Can not call the host: {e}"));
+    }
+    child_wait().unwrap_or_else(|e| pxs_Var::new_exception(format!("Can not call the host: {e}")))
+}
+
+/// Serve the host until it exits or closes stdin. Returns the exit code of the worker.
+pub(crate) fn serve(runtime: i32, execute: RunFn, eval: RunFn) -> i32 {
+    if CHILD.set(ChildState { runtime, execute, eval }).is_err() {
+        return 1;
+    }
+    let state = CHILD.get().unwrap();
+    loop {
+        let msg = match read_message(&mut io::stdin().lock(), &mut io::sink()) {
+            Ok(msg) => msg,
+            Err(_) => return 0,
+        };
+        if string_field(&msg, "op") == "exit" {
+            return 0;
+        }
+        let res = handle_request(state, &msg);
+        if write_answer(&mut io::stdout().lock(), "result", res).is_err() {
+            return 0;
+        }
+    }
+}
+
+/// The host side of a worker. The opaque of its backend.
+struct Worker {
+    /// Runtime id of the backend.
+    runtime: i32,
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+    /// Set once the child died.
+    crashed: Option<String>,
+}
+
+fn worker<'a>(opaque: pxs_Opaque) -> &'a mut Worker {
+    unsafe { &mut *(opaque as *mut Worker) }
+}
+
+/// Mark the worker as crashed and return the error for it.
+fn crashed(opaque: pxs_Opaque) -> pxs_Var {
+    let worker = worker(opaque);
+    if worker.crashed.is_none() {
+        let status = match worker.child.wait() {
+            Ok(status) => status.to_string(),
+            Err(e) => e.to_string(),
+        };
+        worker.crashed = Some(status);
+    }
+    let status = worker.crashed.clone().unwrap();
+    pxs_Var::new_exception(message(MSG_WORKER_CRASHED, "Script worker crashed ({0}).", &[status]))
+}
+
+/// Run a host function for the child.
+fn call_for_child(runtime: i32, msg: &pxs_Var) -> pxs_Var {
+    let handle = field(msg, "handle").get_i64().unwrap_or(-1) as i32;
+    if !lookup_has_function(handle) {
+        return pxs_Var::item_not_found_ep();
+    }
+    let mut argv = vec![pxs_Var::new_i64(runtime as i64)];
+    if let Some(list) = field(msg, "args").get_list() {
+        argv.extend(list.vars.iter().cloned());
+    }
+    unsafe { call_function(handle, argv) }
+}
+
+/// Send a request to the child and wait for its result, running host functions it calls in the meantime.
+///
+/// The worker is borrowed again for every step, host functions may send requests of their own.
+fn request(opaque: pxs_Opaque, msg: pxs_Var) -> pxs_Var {
+    if worker(opaque).crashed.is_some() {
+        return crashed(opaque);
+    }
+    if write_message(&mut worker(opaque).stdin, &msg).is_err() {
+        return crashed(opaque);
+    }
+    loop {
+        let msg = match read_message(&mut worker(opaque).stdout, &mut io::stdout()) {
+            Ok(msg) => msg,
+            Err(_) => return crashed(opaque),
+        };
+        if string_field(&msg, "op") != "call" {
+            return answered(&msg);
+        }
+        let res = call_for_child(worker(opaque).runtime, &msg);
+        if write_answer(&mut worker(opaque).stdin, "return", res).is_err() {
+            return crashed(opaque);
+        }
+    }
+}
+
+fn code_request(op: &str, code: *const c_char, name: *const c_char) -> pxs_Var {
+    let string = |ptr: *const c_char| unsafe { CStr::from_ptr(ptr) }.to_string_lossy().to_string();
+    new_map(vec![
+        ("op", pxs_Var::new_string(op.to_string())),
+        ("code", pxs_Var::new_string(string(code))),
+        ("name", pxs_Var::new_string(string(name))),
+    ])
+}
+
+unsafe extern "C" fn worker_start(opaque: pxs_Opaque, runtime: i32) {
+    worker(opaque).runtime = runtime;
+}
+
+unsafe extern "C" fn worker_stop(opaque: pxs_Opaque) {
+    let mut worker = unsafe { Box::from_raw(opaque as *mut Worker) };
+    if worker.crashed.is_none() {
+        let exit = new_map(vec![("op", pxs_Var::new_string("exit".to_string()))]);
+        if write_message(&mut worker.stdin, &exit).is_err() {
+            let _ = worker.child.kill();
+        }
+    }
+    let _ = worker.child.wait();
+}
+
+unsafe extern "C" fn worker_add_module(opaque: pxs_Opaque, module: pxs_VarT) {
+    let module = data_only(unsafe { pxs_Var::from_borrow(module) });
+    let _ = request(opaque, new_map(vec![("op", pxs_Var::new_string("add_module".to_string())), ("module", module)]));
+}
+
+unsafe extern "C" fn worker_remove_module(opaque: pxs_Opaque, name: *const c_char) {
+    let name = unsafe { CStr::from_ptr(name) }.to_string_lossy().to_string();
+    let msg = new_map(vec![
+        ("op", pxs_Var::new_string("remove_module".to_string())),
+        ("name", pxs_Var::new_string(name)),
+    ]);
+    let _ = request(opaque, msg);
+}
+
+unsafe extern "C" fn worker_execute(opaque: pxs_Opaque, code: *const c_char, file_name: *const c_char) -> pxs_VarT {
+    let res = request(opaque, code_request("execute", code, file_name));
+    if res.is_exception() { res.into_raw() } else { std::ptr::null_mut() }
+}
+
+unsafe extern "C" fn worker_eval(opaque: pxs_Opaque, code: *const c_char, name: *const c_char) -> pxs_VarT {
+    request(opaque, code_request("eval", code, name)).into_raw()
+}
+
+/// Start `exe` with `args` as a worker. Returns the vtable to register, its opaque is freed by `stop`.
+pub(crate) fn spawn(exe: &str, args: &[String]) -> io::Result<pxs_BackendVTable> {
+    let mut child = Command::new(exe).args(args).stdin(Stdio::piped()).stdout(Stdio::piped()).spawn()?;
+    let stdin = child.stdin.take().unwrap();
+    let stdout = BufReader::new(child.stdout.take().unwrap());
+    let worker = Box::new(Worker { runtime: -1, child, stdin, stdout, crashed: None });
+
+    Ok(pxs_BackendVTable {
+        name: c"worker".as_ptr(),
+        opaque: Box::into_raw(worker) as pxs_Opaque,
+        start: Some(worker_start),
+        stop: Some(worker_stop),
+        add_module: Some(worker_add_module),
+        remove_module: Some(worker_remove_module),
+        execute: Some(worker_execute),
+        eval: Some(worker_eval),
+        compile: None,
+        exec_object: None,
+        call: None,
+        object_call: None,
+        object_get: None,
+        object_set: None,
+    })
+}
@@ -0,0 +1,203 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_audio --no-default-features --features "lua,python,js,include-core,testing" -- --nocapture --test-threads=1
+
+#[cfg(all(test, feature = "pxs_audio"))]
+#[allow(unused)]
+mod tests {
+    use std::{
+        ffi::{CStr, c_char, c_void},
+        sync::{
+            Mutex,
+            atomic::{AtomicI64, Ordering},
+        },
+    };
+
+    use etffi::ptr_magic::PtrMagic;
+    use pixelscript::{
+        pxs_addmod, pxs_audio_setcallbacks, pxs_audioinit, pxs_finalize, pxs_initialize, pxs_newhost, pxs_newobject,
+        shared::{
+            pxs_Runtime, utils,
+            var::{pxs_Var, pxs_VarT},
+        },
+    };
+
+    /// Calls the host received, i.e. `play:jump`, `param:1:pitch` and `stop:1`.
+    static EVENTS: Mutex<Vec<String>> = Mutex::new(vec![]);
+    static NEXT_VOICE: AtomicI64 = AtomicI64::new(1);
+
+    fn print_helper(lang: &str) {
+        println!("====================== {lang} ===================");
+    }
+
+    unsafe extern "C" fn play(event: *const c_char, _params: pxs_VarT) -> i64 {
+        let event = unsafe { CStr::from_ptr(event) }.to_string_lossy().to_string();
+        EVENTS.lock().unwrap().push(format!("play:{event}"));
+        NEXT_VOICE.fetch_add(1, Ordering::SeqCst)
+    }
+
+    unsafe extern "C" fn set_param(voice: i64, name: *const c_char, _value: pxs_VarT) {
+        let name = unsafe { CStr::from_ptr(name) }.to_string_lossy().to_string();
+        EVENTS.lock().unwrap().push(format!("param:{voice}:{name}"));
+    }
+
+    unsafe extern "C" fn stop(voice: i64) {
+        EVENTS.lock().unwrap().push(format!("stop:{voice}"));
+    }
+
+    /// A host object that is not a `Voice`.
+    struct Other {
+        id: i64,
+    }
+
+    impl PtrMagic for Other {}
+
+    extern "C" fn free_other(ptr: *mut c_void) {
+        let _ = Other::from_raw(ptr as *mut Other);
+    }
+
+    /// `host.other()`
+    extern "C" fn new_other(_args: pxs_VarT) -> pxs_VarT {
+        let ptr = Other { id: 1 }.into_raw() as *mut c_void;
+        pxs_newhost(pxs_newobject(ptr, free_other, c"Other".as_ptr()))
+    }
+
+    fn take_events() -> Vec<String> {
+        std::mem::take(&mut *EVENTS.lock().unwrap())
+    }
+
+    fn run(runtime: pxs_Runtime, script: &str) {
+        let err = utils::execute_code(script, "<audio>", runtime);
+        assert!(err.is_null(), "Script error: {:#?}", err);
+    }
+
+    fn test_lua() {
+        // Params are forwarded and a voice is only stopped once.
+        run(
+            pxs_Runtime::pxs_Lua,
+            r#"
+local audio = require('pxs_audio')
+local v = audio.play('jump', { volume = 0.5 })
+v:set_param('pitch', 2)
+audio.set_param(v, 'pitch', 3)
+v:stop()
+v:stop()
+audio.stop(v)
+"#,
+        );
+        assert_eq!(take_events(), vec!["play:jump", "param:1:pitch", "param:1:pitch", "stop:1"]);
+
+        // Dropped voices are stopped when the script GC collects them.
+        run(
+            pxs_Runtime::pxs_Lua,
+            r#"
+local audio = require('pxs_audio')
+local v = audio.play('loop')
+v = nil
+collectgarbage('collect')
+collectgarbage('collect')
+"#,
+        );
+        assert_eq!(take_events(), vec!["play:loop", "stop:2"]);
+
+        // stop_all stops every voice, collecting them later does not stop them again.
+        run(
+            pxs_Runtime::pxs_Lua,
+            r#"
+local audio = require('pxs_audio')
+local a = audio.play('a')
+local b = audio.play('b')
+audio.stop_all()
+a = nil
+b = nil
+collectgarbage('collect')
+collectgarbage('collect')
+"#,
+        );
+        let mut events = take_events();
+        events.sort();
+        assert_eq!(events, vec!["play:a", "play:b", "stop:3", "stop:4"]);
+
+        // Other host objects are not voices.
+        run(
+            pxs_Runtime::pxs_Lua,
+            r#"
+local audio = require('pxs_audio')
+local host = require('host')
+assert(not pcall(audio.stop, host.other()))
+assert(not pcall(audio.set_param, host.other(), 'pitch', 1))
+"#,
+        );
+        assert!(take_events().is_empty());
+    }
+
+    fn test_python() {
+        run(
+            pxs_Runtime::pxs_Python,
+            r#"
+import gc
+import pxs_audio
+import host
+v = pxs_audio.play('py')
+v.set_param('pitch', 1)
+del v
+gc.collect()
+
+failed = False
+try:
+    pxs_audio.stop(host.other())
+except Exception:
+    failed = True
+assert failed
+"#,
+        );
+        assert_eq!(take_events(), vec!["play:py", "param:5:pitch", "stop:5"]);
+    }
+
+    fn test_js() {
+        run(
+            pxs_Runtime::pxs_JavaScript,
+            r#"
+import { play, stop, set_param } from 'pxs_audio';
+import * as host from 'host';
+const v = play('js');
+v.set_param('pitch', 1);
+stop(v);
+let failed = false;
+try {
+    set_param(host.other(), 'pitch', 1);
+} catch (e) {
+    failed = true;
+}
+if (!failed) throw new Error('Other is not a Voice');
+"#,
+        );
+        assert_eq!(take_events(), vec!["play:js", "param:6:pitch", "stop:6"]);
+    }
+
+    #[test]
+    fn run_test() {
+        println!();
+        pxs_initialize();
+        pxs_audioinit();
+        pxs_audio_setcallbacks(play, set_param, stop);
+        let host = utils::create_module("host");
+        utils::add_function(host, "other", new_other);
+        pxs_addmod(host);
+
+        print_helper("LUA");
+        test_lua();
+        print_helper("PYTHON");
+        test_python();
+        print_helper("JS");
+        test_js();
+
+        pxs_finalize();
+    }
+}
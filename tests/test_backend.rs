@@ -0,0 +1,205 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_backend --no-default-features --features "testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use std::{
+        ffi::{CStr, CString, c_char, c_void},
+        ptr,
+    };
+
+    use etffi::ptr_magic::PtrMagic;
+    use pixelscript::{
+        pxs_addfunc, pxs_addmod, pxs_backend_compile, pxs_backend_eval, pxs_backend_exec, pxs_backend_getptr,
+        pxs_backend_newvar, pxs_call_handle, pxs_execobject, pxs_finalize, pxs_initialize, pxs_newmod,
+        pxs_register_backend, pxs_removemod, pxs_varcall,
+        shared::{
+            backend::{PXS_CUSTOM_RUNTIME, pxs_BackendVTable},
+            pxs_Opaque,
+            var::{pxs_Var, pxs_VarT, pxs_VarType},
+        },
+    };
+
+    /// A toy language: `exec` remembers modules and code, `eval` makes a function adding the number in the code.
+    #[derive(Default)]
+    struct Toy {
+        runtime: i32,
+        modules: Vec<String>,
+        handles: Vec<i32>,
+        executed: Vec<String>,
+    }
+
+    fn toy<'a>(opaque: pxs_Opaque) -> &'a mut Toy {
+        unsafe { &mut *(opaque as *mut Toy) }
+    }
+
+    fn string(ptr: *const c_char) -> String {
+        unsafe { CStr::from_ptr(ptr) }.to_string_lossy().to_string()
+    }
+
+    unsafe extern "C" fn start(opaque: pxs_Opaque, runtime: i32) {
+        toy(opaque).runtime = runtime;
+    }
+
+    unsafe extern "C" fn add_module(opaque: pxs_Opaque, module: pxs_VarT) {
+        let module = unsafe { pxs_Var::from_borrow(module) };
+        let map = module.get_map().unwrap();
+        let name = map.get_item(&pxs_Var::new_string("name".to_string())).unwrap();
+        let functions = map.get_item(&pxs_Var::new_string("functions".to_string())).unwrap();
+        for function in functions.get_list().unwrap().vars.iter() {
+            let handle = function.get_map().unwrap().get_item(&pxs_Var::new_string("handle".to_string())).unwrap();
+            toy(opaque).handles.push(handle.get_i64().unwrap() as i32);
+        }
+        toy(opaque).modules.push(name.get_string().unwrap());
+    }
+
+    unsafe extern "C" fn remove_module(opaque: pxs_Opaque, name: *const c_char) {
+        let name = string(name);
+        toy(opaque).modules.retain(|m| *m != name);
+    }
+
+    unsafe extern "C" fn execute(opaque: pxs_Opaque, code: *const c_char, _file_name: *const c_char) -> pxs_VarT {
+        let code = string(code);
+        if code == "error" {
+            return pxs_Var::new_exception("toy error").into_raw();
+        }
+        toy(opaque).executed.push(code);
+        ptr::null_mut()
+    }
+
+    unsafe extern "C" fn free_adder(ptr: *mut c_void) {
+        let _ = unsafe { Box::from_raw(ptr as *mut i64) };
+    }
+
+    unsafe extern "C" fn eval(opaque: pxs_Opaque, code: *const c_char, _name: *const c_char) -> pxs_VarT {
+        let Ok(n) = string(code).parse::<i64>() else {
+            return pxs_Var::new_exception("not a number").into_raw();
+        };
+        let ptr = Box::into_raw(Box::new(n)) as *mut c_void;
+        pxs_backend_newvar(toy(opaque).runtime, pxs_VarType::pxs_Function, ptr, Some(free_adder))
+    }
+
+    unsafe extern "C" fn compile(_opaque: pxs_Opaque, code: *const c_char, _scope: pxs_VarT) -> pxs_VarT {
+        pxs_Var::new_list_with(vec![pxs_Var::new_string(string(code))]).into_raw()
+    }
+
+    unsafe extern "C" fn exec_object(opaque: pxs_Opaque, code: pxs_VarT, _scope: pxs_VarT) -> pxs_VarT {
+        let code = unsafe { pxs_Var::from_borrow(code) };
+        let list = code.get_list().unwrap();
+        assert_eq!(list.get_item(0).unwrap().get_i64().unwrap(), toy(opaque).runtime as i64);
+        list.get_item(1).unwrap().clone().into_raw()
+    }
+
+    unsafe extern "C" fn call(_opaque: pxs_Opaque, func: pxs_VarT, args: pxs_VarT) -> pxs_VarT {
+        let n = unsafe { *(pxs_backend_getptr(func) as *mut i64) };
+        let args = unsafe { pxs_Var::from_borrow(args) };
+        let arg = args.get_list().unwrap().get_item(0).unwrap().get_i64().unwrap();
+        pxs_Var::new_i64(n + arg).into_raw()
+    }
+
+    extern "C" fn double(args: pxs_VarT) -> pxs_VarT {
+        let args = unsafe { pxs_Var::from_borrow(args) };
+        let list = args.get_list().unwrap();
+        // The runtime is the backend's id.
+        assert!(list.get_item(0).unwrap().get_i64().unwrap() >= PXS_CUSTOM_RUNTIME as i64);
+        pxs_Var::new_i64(list.get_item(1).unwrap().get_i64().unwrap() * 2).into_raw()
+    }
+
+    fn vtable(toy: &mut Toy) -> pxs_BackendVTable {
+        pxs_BackendVTable {
+            name: c"toy".as_ptr(),
+            opaque: toy as *mut Toy as pxs_Opaque,
+            start: Some(start),
+            stop: None,
+            add_module: Some(add_module),
+            remove_module: Some(remove_module),
+            execute: Some(execute),
+            eval: Some(eval),
+            compile: Some(compile),
+            exec_object: Some(exec_object),
+            call: Some(call),
+            object_call: None,
+            object_get: None,
+            object_set: None,
+        }
+    }
+
+    fn add_math() {
+        let name = CString::new("math").unwrap();
+        let module = pxs_newmod(name.as_ptr());
+        let func_name = CString::new("double").unwrap();
+        pxs_addfunc(module, func_name.as_ptr(), double);
+        pxs_addmod(module);
+    }
+
+    #[test]
+    fn run_test() {
+        pxs_initialize();
+
+        // Modules added before and after registering are both seen.
+        add_math();
+        let mut toy = Toy::default();
+        let id = pxs_register_backend(&vtable(&mut toy));
+        assert_eq!(id, PXS_CUSTOM_RUNTIME);
+        assert_eq!(toy.runtime, id);
+        assert_eq!(toy.modules, vec!["math".to_string()]);
+
+        let name = CString::new("math").unwrap();
+        assert!(pxs_removemod(name.as_ptr()));
+        assert!(toy.modules.is_empty());
+        add_math();
+        assert_eq!(toy.modules, vec!["math".to_string()]);
+
+        // Host functions are called through handles.
+        let args = pxs_Var::new_list_with(vec![pxs_Var::new_i64(21)]).into_raw();
+        let rt = pxs_Var::new_i64(id as i64);
+        let res = pxs_Var::from_raw(pxs_call_handle(&rt as *const pxs_Var as pxs_VarT, toy.handles[1], args));
+        assert_eq!(res.get_i64().unwrap(), 42);
+
+        // Executing.
+        let code = CString::new("print 1").unwrap();
+        let file = CString::new("main.toy").unwrap();
+        assert!(pxs_backend_exec(id, code.as_ptr(), file.as_ptr()).is_null());
+        assert_eq!(toy.executed, vec!["print 1".to_string()]);
+        let code = CString::new("error").unwrap();
+        let res = pxs_Var::from_raw(pxs_backend_exec(id, code.as_ptr(), file.as_ptr()));
+        assert_eq!(res.get_string().unwrap(), "toy error");
+
+        // Functions made by the backend are called by it.
+        let code = CString::new("10").unwrap();
+        let func = pxs_backend_eval(id, code.as_ptr());
+        assert_eq!(pxs_Var::from_raw(func).origin, id);
+        let func = pxs_backend_eval(id, code.as_ptr());
+        let args = pxs_Var::new_list_with(vec![pxs_Var::new_i64(5)]).into_raw();
+        let res = pxs_Var::from_raw(pxs_varcall(ptr::null_mut(), func, args));
+        assert_eq!(res.get_i64().unwrap(), 15);
+
+        // Not with another runtime.
+        let lua = pxs_Var::new_i64(0);
+        let args = pxs_Var::new_list_with(vec![pxs_Var::new_i64(5)]).into_raw();
+        let res = pxs_Var::from_raw(pxs_varcall(&lua as *const pxs_Var as pxs_VarT, func, args));
+        assert!(res.get_string().unwrap().contains("runtime 0"), "{:?}", res.get_string());
+        let _ = pxs_Var::from_raw(func);
+
+        // Compiled objects.
+        let code = CString::new("compiled").unwrap();
+        let object = pxs_backend_compile(id, code.as_ptr(), pxs_Var::new_null().into_raw());
+        let res = pxs_Var::from_raw(pxs_execobject(object, pxs_Var::new_null().into_raw()));
+        assert_eq!(res.get_string().unwrap(), "compiled");
+
+        // Unsupported functions and unknown runtimes.
+        let res = pxs_Var::from_raw(pxs_backend_exec(id + 1, code.as_ptr(), file.as_ptr()));
+        assert!(res.is_exception());
+        assert!(pxs_register_backend(ptr::null()) < 0);
+
+        pxs_finalize();
+    }
+}
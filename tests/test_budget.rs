@@ -0,0 +1,88 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_budget --no-default-features --features "lua,include-core,testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use std::ffi::CString;
+
+    use pixelscript::{
+        pxs_addmod, pxs_call_handle, pxs_finalize, pxs_freevar, pxs_funchandle, pxs_initialize, pxs_listadd,
+        pxs_listlen, pxs_newint, pxs_newlist, pxs_newstring, pxs_set_clone_budget, pxs_varis,
+        shared::{
+            budget::pxs_BudgetMode,
+            pxs_Runtime, utils,
+            var::{pxs_VarT, pxs_VarType},
+        },
+    };
+
+    /// Returns a list of 100 ints.
+    extern "C" fn big(_args: pxs_VarT) -> pxs_VarT {
+        let list = pxs_newlist();
+        for i in 0..100 {
+            pxs_listadd(list, pxs_newint(i));
+        }
+        list
+    }
+
+    extern "C" fn echo(_args: pxs_VarT) -> pxs_VarT {
+        pxs_newint(0)
+    }
+
+    fn handle(name: &str) -> i32 {
+        let module = CString::new("budget").unwrap();
+        let name = CString::new(name).unwrap();
+        pxs_funchandle(module.as_ptr(), name.as_ptr())
+    }
+
+    fn call(rt: pxs_VarT, handle: i32, args: pxs_VarT) -> pxs_VarType {
+        let res = pxs_call_handle(rt, handle, args);
+        let tag = [pxs_VarType::pxs_List, pxs_VarType::pxs_Int64, pxs_VarType::pxs_Exception]
+            .into_iter()
+            .find(|tag| pxs_varis(res, *tag))
+            .unwrap();
+        pxs_freevar(res);
+        tag
+    }
+
+    #[test]
+    fn run_test() {
+        pxs_initialize();
+        let module = utils::create_module("budget");
+        utils::add_function(module, "big", big);
+        utils::add_function(module, "echo", echo);
+        pxs_addmod(module);
+
+        let rt = pxs_newint(pxs_Runtime::pxs_Lua.into_i64());
+        let big = handle("big");
+        let echo = handle("echo");
+
+        // Off by default.
+        assert_eq!(call(rt, big, pxs_newlist()), pxs_VarType::pxs_List);
+
+        pxs_set_clone_budget(10, 0, pxs_BudgetMode::pxs_BudgetWarn);
+        assert_eq!(call(rt, big, pxs_newlist()), pxs_VarType::pxs_List);
+
+        pxs_set_clone_budget(10, 0, pxs_BudgetMode::pxs_BudgetError);
+        assert_eq!(call(rt, big, pxs_newlist()), pxs_VarType::pxs_Exception);
+
+        // Arguments count too.
+        pxs_set_clone_budget(0, 16, pxs_BudgetMode::pxs_BudgetError);
+        let args = pxs_newlist();
+        let s = CString::new("this string is longer than sixteen bytes").unwrap();
+        pxs_listadd(args, pxs_newstring(s.as_ptr()));
+        assert_eq!(call(rt, echo, args), pxs_VarType::pxs_Exception);
+        assert_eq!(call(rt, echo, pxs_newlist()), pxs_VarType::pxs_Int64);
+
+        pxs_set_clone_budget(0, 0, pxs_BudgetMode::pxs_BudgetOff);
+        pxs_freevar(rt);
+        pxs_finalize();
+    }
+}
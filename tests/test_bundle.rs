@@ -0,0 +1,113 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_bundle --no-default-features --features "lua,python,js,testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use std::ffi::CString;
+
+    use pixelscript::{
+        pxs_bundle_add, pxs_bundle_free, pxs_bundle_new, pxs_finalize, pxs_initialize, pxs_use_bundle,
+        shared::{bundle::pxs_Bundle, pxs_Runtime, utils},
+    };
+
+    fn print_helper(lang: &str) {
+        println!("====================== {lang} ===================");
+    }
+
+    fn add(bundle: *mut pxs_Bundle, path: &str, contents: &str) {
+        let (path, contents) = (CString::new(path).unwrap(), CString::new(contents).unwrap());
+        pxs_bundle_add(bundle, path.as_ptr(), contents.as_ptr());
+    }
+
+    fn test_python() {
+        let script = r#"
+import config
+import helpers.strings
+from game import NAME
+assert config.DEBUG == False
+assert helpers.strings.shout('hi') == 'HI!'
+assert NAME == 'game'
+"#;
+        let err = utils::execute_code(script, "<py>", pxs_Runtime::pxs_Python);
+        assert!(err.is_null(), "Python error: {:#?}", err);
+    }
+
+    fn test_lua() {
+        let script = r#"
+assert(require('utils').double(2) == 4)
+assert(require('game').name == 'game')
+assert(require('game.level').number == 1)
+-- The bundle path is the chunk name.
+assert(require('utils').src == 'utils.lua', require('utils').src)
+assert(not pcall(require, 'missing'))
+"#;
+        let err = utils::execute_code(script, "<lua>", pxs_Runtime::pxs_Lua);
+        assert!(err.is_null(), "Lua error: {:#?}", err);
+    }
+
+    fn test_js() {
+        let script = r#"
+import { triple } from 'lib';
+import { VALUE } from './data.js';
+if (triple(2) !== 6 || VALUE !== 3) throw new Error("bundle");
+"#;
+        let err = utils::execute_code(script, "<js>", pxs_Runtime::pxs_JavaScript);
+        assert!(err.is_null(), "JS error: {:#?}", err);
+    }
+
+    #[test]
+    fn run_test() {
+        println!();
+        pxs_initialize();
+
+        // No file reader, everything comes from the bundle.
+        let bundle = pxs_bundle_new();
+        add(bundle, "utils.lua", "return { double = function(x) return x * 2 end, src = debug.getinfo(1, 'S').source }");
+        add(bundle, "game/init.lua", "return { name = 'game' }");
+        add(bundle, "./game/level.lua", "return { number = 0 }");
+        // Replaces the one above.
+        add(bundle, "game/level.lua", "return { number = 1 }");
+        add(bundle, "config.py", "DEBUG = False");
+        add(bundle, "helpers/strings.py", "def shout(s):\n    return s.upper() + '!'");
+        add(bundle, "game/__init__.py", "NAME = 'game'");
+        add(bundle, "lib.js", "export function triple(x) { return x * 3; }");
+        add(bundle, "data.js", "export const VALUE = 3;");
+        pxs_use_bundle(bundle);
+
+        print_helper("PYTHON");
+        test_python();
+        print_helper("LUA");
+        test_lua();
+        print_helper("JS");
+        test_js();
+
+        // A new bundle replaces the old one.
+        let bundle = pxs_bundle_new();
+        add(bundle, "other.lua", "return 'other'");
+        pxs_use_bundle(bundle);
+        let err = utils::execute_code("assert(require('other') == 'other')", "<lua>", pxs_Runtime::pxs_Lua);
+        assert!(err.is_null());
+        let err = utils::execute_code("require('game.unknown')", "<lua>", pxs_Runtime::pxs_Lua);
+        assert!(!err.is_null());
+
+        // Removed.
+        pxs_use_bundle(std::ptr::null_mut());
+        let err = utils::execute_code("require('config2')", "<lua>", pxs_Runtime::pxs_Lua);
+        assert!(!err.is_null());
+
+        // Never used.
+        let unused = pxs_bundle_new();
+        add(unused, "x.lua", "return 1");
+        pxs_bundle_free(unused);
+
+        pxs_finalize();
+    }
+}
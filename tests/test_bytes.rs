@@ -12,10 +12,15 @@
 #[allow(unused)]
 mod tests {
     use pixelscript::{
-        pxs_copybytes, pxs_copystring, pxs_finalize, pxs_freearena, pxs_freevar, pxs_initialize, pxs_newarena, pxs_newbytes, pxs_newmod, pxs_newstring, pxs_varsize, shared::{module::pxs_Module, pxs_Opaque, pxs_Runtime, utils, var::pxs_VarT},
+        pxs_addmod, pxs_byteslen, pxs_copybytes, pxs_copystring, pxs_finalize, pxs_freearena, pxs_freevar, pxs_initialize, pxs_newarena, pxs_newbytebuf, pxs_newbytes, pxs_newmod, pxs_getbytes, pxs_newstring, pxs_varis, pxs_varsize, shared::{module::pxs_Module, pxs_Opaque, pxs_Runtime, utils, var::{pxs_VarT, pxs_VarType}},
     };
     use etffi::{cstring::CStringSafe, borrow_string, create_raw_string, free_raw_string, own_string, ptr_magic::PtrMagic};
 
+    extern "C" fn get_bytes(_args: pxs_VarT) -> pxs_VarT {
+        let raw: [u8; 5] = [0, 159, 146, 150, 255];
+        pxs_newbytebuf(raw.as_ptr() as pxs_Opaque, raw.len())
+    }
+
     fn print_helper(lang: &str) {
         println!("====================== {lang} ===================");
     }
@@ -50,6 +55,30 @@ mod tests {
         let rstring = String::from_utf8(string_bytes).expect("Could not extract string");
         assert!(rstring == "test", "Strings do not match.");
 
+        // Binary buffer
+        let raw: [u8; 5] = [0, 159, 146, 150, 255];
+        let bytes = pxs_newbytebuf(raw.as_ptr() as pxs_Opaque, raw.len());
+        assert!(pxs_varis(bytes, pxs_VarType::pxs_Bytes), "Not pxs_Bytes");
+        assert!(pxs_byteslen(bytes) == 5, "Bytes length does not match");
+
+        let mut len = 0;
+        let ptr = pxs_getbytes(bytes, &mut len);
+        let got = unsafe { core::slice::from_raw_parts(ptr, len) };
+        assert!(got == raw, "Bytes do not match.");
+        pxs_freevar(bytes);
+
+        // Scripts keep binary data intact
+        let test_module = utils::create_module("test");
+        utils::add_function(test_module, "get_bytes", get_bytes);
+        pxs_addmod(test_module);
+        let script = r#"
+local data = require('test').get_bytes()
+assert(#data == 5, "Expected 5 bytes, got " .. #data)
+assert(string.byte(data, 1) == 0 and string.byte(data, 5) == 255, "Bytes changed")
+"#;
+        let err = utils::execute_code(script, "<lua>", pxs_Runtime::pxs_Lua);
+        assert!(err.is_null(), "Lua error: {:#?}", err);
+
         pxs_finalize();
     }
 }
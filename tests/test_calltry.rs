@@ -0,0 +1,102 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_calltry --no-default-features --features "lua,python,testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use std::ffi::{CStr, CString};
+
+    use etffi::ptr_magic::PtrMagic;
+    use pixelscript::{
+        pxs_call, pxs_call_try, pxs_error_free, pxs_finalize, pxs_initialize, pxs_varcall_try,
+        shared::{
+            error::pxs_CallResult,
+            pxs_Runtime, utils,
+            var::{pxs_Var, pxs_VarT},
+        },
+    };
+
+    fn run(runtime: pxs_Runtime, script: &str) {
+        let err = utils::execute_code(script, "<calltry>", runtime);
+        assert!(err.is_null(), "Script error: {:#?}", err);
+    }
+
+    fn string(ptr: *mut std::ffi::c_char) -> String {
+        unsafe { CStr::from_ptr(ptr) }.to_string_lossy().to_string()
+    }
+
+    /// `(message, traceback)` of a failed call.
+    fn failure(res: pxs_CallResult) -> (String, String) {
+        assert!(!res.ok);
+        assert!(res.value.is_null());
+        let error = unsafe { &*res.error };
+        let failure = (string(error.message), string(error.traceback));
+        pxs_error_free(res.error);
+        failure
+    }
+
+    fn call_try(runtime: pxs_Runtime, method: &str) -> pxs_CallResult {
+        let runtime = pxs_Var::new_i64(runtime as i64);
+        let method = CString::new(method).unwrap();
+        pxs_call_try(&runtime as *const pxs_Var as pxs_VarT, method.as_ptr(), pxs_Var::new_list().into_raw())
+    }
+
+    #[test]
+    fn run_test() {
+        pxs_initialize();
+
+        run(
+            pxs_Runtime::pxs_Lua,
+            r#"
+function answer() return 42 end
+function boom() error('kaboom') end
+function get_boom() return boom end
+"#,
+        );
+        run(
+            pxs_Runtime::pxs_Python,
+            r#"
+def answer():
+    return 42
+
+def boom():
+    raise ValueError('kaboom')
+"#,
+        );
+
+        for runtime in [pxs_Runtime::pxs_Lua as i64, pxs_Runtime::pxs_Python as i64] {
+            let res = call_try(pxs_Runtime::from_i64(runtime).unwrap(), "answer");
+            assert!(res.ok);
+            assert!(res.error.is_null());
+            assert_eq!(pxs_Var::from_raw(res.value).get_i64().unwrap(), 42);
+
+            let (message, traceback) = failure(call_try(pxs_Runtime::from_i64(runtime).unwrap(), "boom"));
+            assert!(message.contains("kaboom"), "{message}");
+            assert!(traceback.contains("kaboom"), "{traceback}");
+            if runtime == pxs_Runtime::pxs_Lua as i64 {
+                assert!(traceback.contains("stack traceback"), "{traceback}");
+            } else {
+                assert!(traceback.contains("Traceback"), "{traceback}");
+            }
+        }
+
+        // Function vars.
+        let res = call_try(pxs_Runtime::pxs_Lua, "get_boom");
+        assert!(res.ok);
+        let boom = pxs_Var::from_raw(res.value);
+        let res = pxs_varcall_try(std::ptr::null_mut(), &boom as *const pxs_Var as pxs_VarT, pxs_Var::new_list().into_raw());
+        let (message, traceback) = failure(res);
+        assert!(message.contains("kaboom"), "{message}");
+        assert!(traceback.contains("stack traceback"), "{traceback}");
+        drop(boom);
+
+        pxs_finalize();
+    }
+}
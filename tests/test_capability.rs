@@ -0,0 +1,70 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_capability --no-default-features --features "lua,testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use std::ffi::{CString, c_char};
+
+    use pixelscript::{
+        pxs_addmod, pxs_eval_token, pxs_finalize, pxs_freestr, pxs_freevar, pxs_getint,
+        pxs_initialize, pxs_issue_token, pxs_newint, pxs_newnull, pxs_revoke_token, pxs_varis,
+        shared::{
+            pxs_Runtime, utils,
+            var::{pxs_VarT, pxs_VarType},
+        },
+    };
+
+    extern "C" fn read(_args: pxs_VarT) -> pxs_VarT {
+        pxs_newint(7)
+    }
+
+    extern "C" fn write(_args: pxs_VarT) -> pxs_VarT {
+        pxs_newnull()
+    }
+
+    fn eval(token: *const c_char, code: &str) -> pxs_VarT {
+        let code = CString::new(code).unwrap();
+        pxs_eval_token(token, code.as_ptr(), pxs_Runtime::pxs_Lua)
+    }
+
+    fn is_exception(var: pxs_VarT) -> bool {
+        pxs_varis(var, pxs_VarType::pxs_Exception)
+    }
+
+    #[test]
+    fn run_test() {
+        pxs_initialize();
+        let test_module = utils::create_module("test");
+        utils::add_function(test_module, "read", read);
+        utils::add_function(test_module, "write", write);
+        pxs_addmod(test_module);
+
+        let permissions = CString::new("test.read").unwrap();
+        let token = pxs_issue_token(permissions.as_ptr());
+
+        let res = eval(token, "require('test').read()");
+        assert!(!is_exception(res), "read should be allowed");
+        assert_eq!(pxs_getint(res), 7);
+        pxs_freevar(res);
+
+        let res = eval(token, "require('test').write()");
+        assert!(is_exception(res), "write should be denied");
+        pxs_freevar(res);
+
+        assert!(pxs_revoke_token(token));
+        let res = eval(token, "1 + 1");
+        assert!(is_exception(res), "revoked token should be rejected");
+        pxs_freevar(res);
+
+        pxs_freestr(token);
+        pxs_finalize();
+    }
+}
@@ -0,0 +1,80 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_catalog --no-default-features --features "lua,include-core,testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use etffi::ptr_magic::PtrMagic;
+    use std::ffi::{CStr, CString, c_char};
+
+    use pixelscript::{
+        pxs_addmod, pxs_eval_token, pxs_finalize, pxs_freestr, pxs_initialize, pxs_issue_token, pxs_newint, pxs_newstring,
+        pxs_set_message_catalog,
+        shared::{
+            pxs_Runtime, utils,
+            var::{pxs_Var, pxs_VarT, pxs_VarType},
+        },
+    };
+
+    /// Spanish catalog. Only knows a few keys.
+    unsafe extern "C" fn catalog(key: *const c_char, _default: *const c_char) -> pxs_VarT {
+        let template = match unsafe { CStr::from_ptr(key) }.to_str().unwrap() {
+            "expected_n_args" => "Se esperaban {0} argumentos, se recibieron {1}.",
+            "permission_denied" => "Permiso denegado: '{0}'.",
+            "null_param" => "{0} es nulo {2}",
+            _ => return std::ptr::null_mut(),
+        };
+        let template = CString::new(template).unwrap();
+        pxs_newstring(template.as_ptr())
+    }
+
+    extern "C" fn write(_args: pxs_VarT) -> pxs_VarT {
+        pxs_newint(1)
+    }
+
+    #[test]
+    fn run_test() {
+        pxs_initialize();
+        let test_module = utils::create_module("test");
+        utils::add_function(test_module, "write", write);
+        pxs_addmod(test_module);
+
+        // Default messages before a catalog is set.
+        assert_eq!(pxs_Var::expected_n_args_ep(2, 3).get_string().unwrap(), "Expected 2 args. Got 3.");
+
+        pxs_set_message_catalog(catalog);
+        assert_eq!(
+            pxs_Var::expected_n_args_ep(2, 3).get_string().unwrap(),
+            "Se esperaban 2 argumentos, se recibieron 3."
+        );
+        // Unknown placeholders are kept.
+        assert_eq!(pxs_Var::null_param_ep("x").get_string().unwrap(), "x es nulo {2}");
+        // Keys the catalog does not know keep the default.
+        assert_eq!(
+            pxs_Var::incorrect_type_ep(pxs_VarType::pxs_Int64, pxs_VarType::pxs_String).get_string().unwrap(),
+            "Expected pxs_Int64, found pxs_String"
+        );
+
+        // Errors surfaced to scripts are localized too.
+        let permissions = CString::new("test.read").unwrap();
+        let token = pxs_issue_token(permissions.as_ptr());
+        let script = r#"
+local ok, err = pcall(function() return require('test').write() end)
+assert(not ok)
+assert(string.find(tostring(err), "Permiso denegado: 'test.write'.", 1, true))
+"#;
+        let script = CString::new(script).unwrap();
+        let res = pxs_Var::from_raw(pxs_eval_token(token, script.as_ptr(), pxs_Runtime::pxs_Lua));
+        assert!(!res.is_exception(), "Lua error: {:#?}", res.get_string());
+
+        pxs_freestr(token);
+        pxs_finalize();
+    }
+}
@@ -0,0 +1,93 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_channel --no-default-features --features "lua,pxs_channel,testing" -- --nocapture --test-threads=1
+
+#[cfg(all(test, feature = "pxs_channel"))]
+#[allow(unused)]
+mod tests {
+    use std::{ffi::CString, ptr, thread};
+
+    use etffi::ptr_magic::PtrMagic;
+    use pixelscript::{
+        pxs_channel_close, pxs_channel_new, pxs_channel_recv, pxs_channel_send, pxs_channelinit, pxs_finalize,
+        pxs_initialize, pxs_thread_exec, pxs_thread_join, pxs_thread_spawn,
+        shared::{pxs_Runtime, var::pxs_Var},
+    };
+
+    fn recv(channel: i32, timeout_ms: i64) -> Option<pxs_Var> {
+        let res = pxs_channel_recv(channel, timeout_ms);
+        if res.is_null() { None } else { Some(pxs_Var::from_raw(res)) }
+    }
+
+    fn send(channel: i32, var: pxs_Var) -> bool {
+        pxs_channel_send(channel, &var as *const pxs_Var as *mut pxs_Var)
+    }
+
+    #[test]
+    fn run_test() {
+        pxs_initialize();
+        pxs_channelinit();
+
+        // Between host threads, values are copies.
+        let channel = pxs_channel_new();
+        let producer = thread::spawn(move || {
+            for i in 0..3 {
+                assert!(send(channel, pxs_Var::new_list_with(vec![pxs_Var::new_i64(i)])));
+            }
+        });
+        for i in 0..3 {
+            let list = recv(channel, -1).unwrap();
+            assert_eq!(list.get_list().unwrap().get_item(0).unwrap().get_i64().unwrap(), i);
+        }
+        producer.join().unwrap();
+        assert!(recv(channel, 0).is_none());
+        assert!(recv(channel, 10).is_none());
+
+        // Only data can be sent.
+        assert!(!send(channel, pxs_Var::new_exception("not data")));
+
+        // A script thread consumes and produces.
+        let results = pxs_channel_new();
+        let code = format!(
+            r#"
+local ch = require('pxs_channel')
+while true do
+    local job = ch.recv({channel})
+    if job.stop then break end
+    ch.send({results}, job.value * 2)
+end
+"#
+        );
+        let worker = pxs_thread_spawn();
+        let code = CString::new(code).unwrap();
+        let name = CString::new("<channel>").unwrap();
+        assert!(pxs_thread_exec(worker, pxs_Runtime::pxs_Lua, code.as_ptr(), name.as_ptr()));
+
+        for i in 1..=3 {
+            let job = pxs_Var::new_map();
+            job.get_map().unwrap().add_item(pxs_Var::new_string("value".to_string()), pxs_Var::new_i64(i));
+            assert!(send(channel, job));
+            assert_eq!(recv(results, 1000).unwrap().get_i64().unwrap(), i * 2);
+        }
+        let stop = pxs_Var::new_map();
+        stop.get_map().unwrap().add_item(pxs_Var::new_string("stop".to_string()), pxs_Var::new_bool(true));
+        assert!(send(channel, stop));
+        assert!(pxs_thread_join(worker).is_null());
+
+        // Closing wakes receivers with a error.
+        let waiter = thread::spawn(move || recv(channel, -1).unwrap().is_exception());
+        thread::sleep(std::time::Duration::from_millis(50));
+        assert!(pxs_channel_close(channel));
+        assert!(waiter.join().unwrap());
+        assert!(!pxs_channel_close(channel));
+        assert!(!send(channel, pxs_Var::new_i64(1)));
+
+        pxs_finalize();
+    }
+}
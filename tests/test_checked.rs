@@ -0,0 +1,109 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_checked --no-default-features --features "lua,testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use std::ptr;
+
+    use pixelscript::{
+        pxs_finalize, pxs_getbool_checked, pxs_getfloat_checked, pxs_getint_checked, pxs_getuint_checked,
+        pxs_initialize,
+        shared::{
+            convert::{pxs_Convert, pxs_Status},
+            var::{pxs_Var, pxs_VarT},
+        },
+    };
+
+    fn ptr(var: &pxs_Var) -> pxs_VarT {
+        var as *const pxs_Var as pxs_VarT
+    }
+
+    fn int(var: pxs_Var, policy: pxs_Convert) -> Result<i64, pxs_Status> {
+        let mut out = 0;
+        match pxs_getint_checked(ptr(&var), &mut out, policy) {
+            pxs_Status::pxs_StatusOk => Ok(out),
+            status => Err(status),
+        }
+    }
+
+    fn uint(var: pxs_Var, policy: pxs_Convert) -> Result<u64, pxs_Status> {
+        let mut out = 0;
+        match pxs_getuint_checked(ptr(&var), &mut out, policy) {
+            pxs_Status::pxs_StatusOk => Ok(out),
+            status => Err(status),
+        }
+    }
+
+    fn float(var: pxs_Var, policy: pxs_Convert) -> Result<f64, pxs_Status> {
+        let mut out = 0.0;
+        match pxs_getfloat_checked(ptr(&var), &mut out, policy) {
+            pxs_Status::pxs_StatusOk => Ok(out),
+            status => Err(status),
+        }
+    }
+
+    #[test]
+    fn run_test() {
+        use pxs_Convert::*;
+        use pxs_Status::*;
+
+        // Calls before initialization are reported.
+        let mut out = 0;
+        let one = pxs_Var::new_i64(1);
+        assert_eq!(pxs_getint_checked(ptr(&one), &mut out, pxs_ConvertError), pxs_StatusNotInitialized);
+
+        pxs_initialize();
+
+        // Ints
+        assert_eq!(int(pxs_Var::new_i64(-1), pxs_ConvertError), Ok(-1));
+        assert_eq!(int(pxs_Var::new_u64(u64::MAX), pxs_ConvertError), Err(pxs_StatusOverflow));
+        assert_eq!(int(pxs_Var::new_f64(2.5), pxs_ConvertTruncate), Ok(2));
+        assert_eq!(int(pxs_Var::new_f64(-2.5), pxs_ConvertTruncate), Ok(-2));
+        assert_eq!(int(pxs_Var::new_f64(2.5), pxs_ConvertRound), Ok(3));
+        assert_eq!(int(pxs_Var::new_f64(2.5), pxs_ConvertError), Err(pxs_StatusInexact));
+        assert_eq!(int(pxs_Var::new_f64(4.0), pxs_ConvertError), Ok(4));
+        assert_eq!(int(pxs_Var::new_f64(1e19), pxs_ConvertTruncate), Err(pxs_StatusOverflow));
+        assert_eq!(int(pxs_Var::new_f64(-9_223_372_036_854_775_808.0), pxs_ConvertError), Ok(i64::MIN));
+        assert_eq!(int(pxs_Var::new_f64(f64::NAN), pxs_ConvertTruncate), Err(pxs_StatusOverflow));
+        assert_eq!(int(pxs_Var::new_bool(true), pxs_ConvertError), Ok(1));
+        assert_eq!(int(pxs_Var::new_string("1".to_string()), pxs_ConvertTruncate), Err(pxs_StatusWrongType));
+
+        // Uints
+        assert_eq!(uint(pxs_Var::new_u64(u64::MAX), pxs_ConvertError), Ok(u64::MAX));
+        assert_eq!(uint(pxs_Var::new_i64(-1), pxs_ConvertTruncate), Err(pxs_StatusOverflow));
+        assert_eq!(uint(pxs_Var::new_f64(-0.5), pxs_ConvertTruncate), Ok(0));
+        assert_eq!(uint(pxs_Var::new_f64(-0.5), pxs_ConvertRound), Err(pxs_StatusOverflow));
+        assert_eq!(uint(pxs_Var::new_f64(1.5), pxs_ConvertRound), Ok(2));
+        assert_eq!(uint(pxs_Var::new_f64(f64::INFINITY), pxs_ConvertRound), Err(pxs_StatusOverflow));
+
+        // Floats
+        assert_eq!(float(pxs_Var::new_i64(3), pxs_ConvertError), Ok(3.0));
+        assert_eq!(float(pxs_Var::new_i64((1 << 53) + 1), pxs_ConvertError), Err(pxs_StatusInexact));
+        assert_eq!(float(pxs_Var::new_i64((1 << 53) + 1), pxs_ConvertRound), Ok((1u64 << 53) as f64));
+        assert_eq!(float(pxs_Var::new_i64(i64::MAX), pxs_ConvertError), Err(pxs_StatusInexact));
+        assert_eq!(float(pxs_Var::new_u64(u64::MAX), pxs_ConvertError), Err(pxs_StatusInexact));
+        assert_eq!(float(pxs_Var::new_null(), pxs_ConvertRound), Err(pxs_StatusWrongType));
+
+        // Bools
+        let mut out = false;
+        assert_eq!(pxs_getbool_checked(ptr(&pxs_Var::new_i64(1)), &mut out), pxs_StatusOk);
+        assert!(out);
+        assert_eq!(pxs_getbool_checked(ptr(&pxs_Var::new_i64(2)), &mut out), pxs_StatusOverflow);
+        assert_eq!(pxs_getbool_checked(ptr(&pxs_Var::new_f64(1.0)), &mut out), pxs_StatusWrongType);
+        assert!(out);
+
+        // Null
+        assert_eq!(pxs_getbool_checked(ptr::null_mut(), &mut out), pxs_StatusNull);
+        assert_eq!(pxs_getint_checked(ptr(&pxs_Var::new_i64(1)), ptr::null_mut(), pxs_ConvertError), pxs_StatusNull);
+
+        pxs_finalize();
+    }
+}
@@ -0,0 +1,91 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_chunks --no-default-features --features "lua,testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use std::{
+        ffi::{CStr, CString, c_char},
+        ptr,
+        sync::Mutex,
+    };
+
+    use etffi::ptr_magic::PtrMagic;
+    use pixelscript::{
+        pxs_exec_with_result, pxs_finalize, pxs_initialize, pxs_invalidate_cache, pxs_set_chunk_cache,
+        pxs_set_filereader, pxs_startthread, pxs_stopthread,
+        shared::{
+            pxs_Runtime,
+            var::{pxs_Var, pxs_VarT},
+        },
+    };
+
+    static SOURCE: Mutex<String> = Mutex::new(String::new());
+
+    unsafe extern "C" fn load_file(path: *const c_char) -> pxs_VarT {
+        let path = unsafe { CStr::from_ptr(path) }.to_string_lossy().to_string();
+        if !path.contains("big") {
+            return ptr::null_mut();
+        }
+        pxs_Var::new_string(SOURCE.lock().unwrap().clone()).into_raw()
+    }
+
+    /// `require('big').value`, a fresh require every time.
+    fn value() -> i64 {
+        let code = CString::new("package.loaded['big'] = nil\nreturn require('big').value").unwrap();
+        let file_name = CString::new("<chunks>").unwrap();
+        let mut err: pxs_VarT = ptr::null_mut();
+        let res = pxs_exec_with_result(pxs_Runtime::pxs_Lua, code.as_ptr(), file_name.as_ptr(), &mut err);
+        assert!(err.is_null(), "{:?}", pxs_Var::from_raw(err).get_string());
+        pxs_Var::from_raw(res).get_i64().unwrap()
+    }
+
+    fn set_source(value: i64) {
+        *SOURCE.lock().unwrap() = format!("local M = {{}}\nM.value = {value}\nreturn M");
+    }
+
+    #[test]
+    fn run_test() {
+        pxs_initialize();
+        pxs_set_filereader(load_file);
+        set_source(1);
+
+        // Not cached while off.
+        assert_eq!(value(), 1);
+        assert_eq!(pxs_invalidate_cache(ptr::null()), 0);
+
+        pxs_set_chunk_cache(true);
+        assert_eq!(value(), 1);
+        assert_eq!(value(), 1);
+
+        // Other threads use the same chunk.
+        std::thread::spawn(|| {
+            pxs_startthread();
+            assert_eq!(value(), 1);
+            pxs_stopthread();
+        })
+        .join()
+        .unwrap();
+
+        // A changed file is compiled again.
+        set_source(2);
+        assert_eq!(value(), 2);
+
+        // Hot reload drops it.
+        let path = CString::new("big").unwrap();
+        assert_eq!(pxs_invalidate_cache(path.as_ptr()), 1);
+        assert_eq!(value(), 2);
+
+        pxs_set_chunk_cache(false);
+        assert_eq!(pxs_invalidate_cache(ptr::null()), 0);
+
+        pxs_finalize();
+    }
+}
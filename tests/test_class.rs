@@ -0,0 +1,124 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_class --no-default-features --features "lua,python,testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use std::ffi::{CString, c_void};
+
+    use etffi::ptr_magic::PtrMagic;
+    use pixelscript::{
+        pxs_addclass, pxs_addmod, pxs_class_addfunc, pxs_class_addvar, pxs_class_setconstructor, pxs_finalize,
+        pxs_gethost, pxs_initialize, pxs_newhost, pxs_newobject, pxs_object_addfunc, pxs_registry_export,
+        shared::{
+            pxs_Runtime, utils,
+            var::{pxs_Var, pxs_VarT},
+        },
+    };
+
+    struct Circle {
+        radius: f64,
+    }
+
+    impl PtrMagic for Circle {}
+
+    extern "C" fn free_circle(ptr: *mut c_void) {
+        let _ = Circle::from_raw(ptr as *mut Circle);
+    }
+
+    fn arg<'a>(args: pxs_VarT, idx: i32) -> &'a pxs_Var {
+        unsafe { pxs_Var::from_borrow(args) }.get_list().unwrap().get_item(idx).unwrap()
+    }
+
+    extern "C" fn circle_new(args: pxs_VarT) -> pxs_VarT {
+        let name = CString::new("Circle").unwrap();
+        let circle = Circle { radius: arg(args, 1).get_f64().unwrap() };
+        let object = pxs_newobject(circle.into_raw() as *mut c_void, free_circle, name.as_ptr());
+        let method = CString::new("radius").unwrap();
+        pxs_object_addfunc(object, method.as_ptr(), circle_radius);
+        pxs_newhost(object)
+    }
+
+    extern "C" fn circle_radius(args: pxs_VarT) -> pxs_VarT {
+        let runtime = arg(args, 0) as *const pxs_Var as pxs_VarT;
+        let circle = unsafe { &*(pxs_gethost(runtime, arg(args, 1) as *const pxs_Var as pxs_VarT) as *const Circle) };
+        pxs_Var::new_f64(circle.radius).into_raw()
+    }
+
+    /// Static, gets no object.
+    extern "C" fn circle_area(args: pxs_VarT) -> pxs_VarT {
+        let radius = arg(args, 1).get_f64().unwrap();
+        pxs_Var::new_f64(3.5 * radius * radius).into_raw()
+    }
+
+    fn run(runtime: pxs_Runtime, script: &str) {
+        let err = utils::execute_code(script, "<class>", runtime);
+        assert!(err.is_null(), "Script error: {:#?}", err);
+    }
+
+    fn key(name: &str) -> pxs_Var {
+        pxs_Var::new_string(name.to_string())
+    }
+
+    #[test]
+    fn run_test() {
+        pxs_initialize();
+
+        let module = utils::create_module("shapes");
+        let name = CString::new("Circle").unwrap();
+        let class = pxs_addclass(module, name.as_ptr());
+        assert!(!class.is_null());
+        let pi = CString::new("PI").unwrap();
+        pxs_class_addvar(class, pi.as_ptr(), pxs_Var::new_f64(3.5).into_raw());
+        let area = CString::new("area").unwrap();
+        pxs_class_addfunc(class, area.as_ptr(), circle_area);
+        pxs_class_setconstructor(class, circle_new);
+
+        // A class without constructor only holds statics.
+        let units = CString::new("Units").unwrap();
+        let units = pxs_addclass(module, units.as_ptr());
+        let scale = CString::new("SCALE").unwrap();
+        pxs_class_addvar(units, scale.as_ptr(), pxs_Var::new_i64(16).into_raw());
+        pxs_addmod(module);
+
+        run(
+            pxs_Runtime::pxs_Lua,
+            r#"
+local shapes = require('shapes')
+assert(shapes.Circle.PI == 3.5)
+assert(shapes.Circle.area(2.0) == 14.0)
+local c = shapes.Circle(3.0)
+assert(c:radius() == 3.0)
+assert(shapes.Units.SCALE == 16)
+"#,
+        );
+
+        run(
+            pxs_Runtime::pxs_Python,
+            r#"
+import shapes
+assert shapes.Circle.PI == 3.5
+assert shapes.Circle.area(2.0) == 14.0
+c = shapes.Circle(3.0)
+assert c.radius() == 3.0
+assert shapes.Units.SCALE == 16
+"#,
+        );
+
+        let export = pxs_Var::from_raw(pxs_registry_export());
+        let modules = export.get_map().unwrap().get_item(&key("modules")).unwrap();
+        let shapes = modules.get_list().unwrap().get_item(0).unwrap();
+        let classes = shapes.get_map().unwrap().get_item(&key("classes")).unwrap();
+        let circle = classes.get_list().unwrap().get_item(0).unwrap();
+        assert_eq!(circle.get_map().unwrap().get_item(&key("name")).unwrap().get_string().unwrap(), "Circle");
+
+        pxs_finalize();
+    }
+}
@@ -0,0 +1,85 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_clock --no-default-features --features "lua,python,js,include-core,testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use pixelscript::{
+        pxs_clock_setcallbacks, pxs_clockinit, pxs_finalize, pxs_initialize,
+        shared::{pxs_Runtime, utils},
+    };
+
+    fn print_helper(lang: &str) {
+        println!("====================== {lang} ===================");
+    }
+
+    unsafe extern "C" fn game_time() -> f64 {
+        12.5
+    }
+
+    unsafe extern "C" fn real_time() -> f64 {
+        100.0
+    }
+
+    unsafe extern "C" fn frame() -> i64 {
+        750
+    }
+
+    fn test_python() {
+        let script = r#"
+import pxs_clock
+assert pxs_clock.game_time() == 12.5
+assert pxs_clock.real_time() == 100.0
+assert pxs_clock.frame() == 750
+assert pxs_clock.since(10) == 2.5
+"#;
+        let err = utils::execute_code(script, "<py>", pxs_Runtime::pxs_Python);
+        assert!(err.is_null(), "Python error: {:#?}", err);
+    }
+
+    fn test_lua() {
+        let script = r#"
+local pxs_clock = require('pxs_clock')
+assert(pxs_clock.game_time() == 12.5)
+assert(pxs_clock.frame() == 750)
+assert(pxs_clock.since(2.5) == 10)
+"#;
+        let err = utils::execute_code(script, "<lua>", pxs_Runtime::pxs_Lua);
+        assert!(err.is_null(), "Lua error: {:#?}", err);
+    }
+
+    fn test_js() {
+        let script = r#"
+import { real_time, frame } from 'pxs_clock';
+if (real_time() !== 100) throw new Error("real_time");
+if (frame() !== 750) throw new Error("frame");
+"#;
+        let err = utils::execute_code(script, "<js>", pxs_Runtime::pxs_JavaScript);
+        assert!(err.is_null(), "JS error: {:#?}", err);
+    }
+
+    #[test]
+    fn run_test() {
+        println!();
+        pxs_initialize();
+        pxs_clock_setcallbacks(game_time, real_time, frame);
+        pxs_clockinit();
+        utils::setup_pxs();
+
+        print_helper("PYTHON");
+        test_python();
+        print_helper("LUA");
+        test_lua();
+        print_helper("JS");
+        test_js();
+
+        pxs_finalize();
+    }
+}
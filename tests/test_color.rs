@@ -0,0 +1,82 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_color --no-default-features --features "lua,python,js,include-core,testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use pixelscript::{
+        pxs_finalize, pxs_initialize, pxs_colorinit, pxs_unitsinit,
+        shared::{pxs_Runtime, utils},
+    };
+
+    fn print_helper(lang: &str) {
+        println!("====================== {lang} ===================");
+    }
+
+    fn test_python() {
+        let script = r##"
+import pxs_color
+import pxs_units
+c = pxs_color.hex_to_rgba("#ff8000")
+assert c["r"] == 255 and c["g"] == 128 and c["b"] == 0 and c["a"] == 255, c
+assert pxs_color.rgba_to_hex(c) == "#ff8000"
+hsv = pxs_color.rgb_to_hsv(c)
+assert pxs_color.rgba_to_hex(pxs_color.hsv_to_rgb(hsv)) == "#ff8000", hsv
+mid = pxs_color.lerp({"r": 0, "g": 0, "b": 0}, {"r": 255, "g": 255, "b": 255, "a": 0}, 0.5)
+assert mid["r"] == 128 and mid["a"] == 128, mid
+assert pxs_units.s_to_ms(1.5) == 1500
+"##;
+        let err = utils::execute_code(script, "<py>", pxs_Runtime::pxs_Python);
+        assert!(err.is_null(), "Python error: {:#?}", err);
+    }
+
+    fn test_lua() {
+        let script = r##"
+local pxs_color = require('pxs_color')
+local pxs_units = require('pxs_units')
+local c = pxs_color.hex_to_rgba('0f08')
+assert(c.r == 0 and c.g == 255 and c.a == 136)
+assert(pxs_color.rgba_to_hex(c, true) == '#00ff0088')
+assert(math.abs(pxs_units.deg_to_rad(180) - math.pi) < 0.0001)
+"##;
+        let err = utils::execute_code(script, "<lua>", pxs_Runtime::pxs_Lua);
+        assert!(err.is_null(), "Lua error: {:#?}", err);
+    }
+
+    fn test_js() {
+        let script = r##"
+import { hex_to_rgba } from 'pxs_color';
+import { ms_to_s } from 'pxs_units';
+if (hex_to_rgba('#000').r != 0 || ms_to_s(500) != 0.5) {
+    throw new Error('pxs_color/pxs_units failed');
+}
+"##;
+        let err = utils::execute_code(script, "<js>", pxs_Runtime::pxs_JavaScript);
+        assert!(err.is_null(), "JS error: {:#?}", err);
+    }
+
+    #[test]
+    fn run_test() {
+        println!();
+        pxs_initialize();
+        pxs_colorinit();
+        pxs_unitsinit();
+        utils::setup_pxs();
+
+        print_helper("PYTHON");
+        test_python();
+        print_helper("LUA");
+        test_lua();
+        print_helper("JS");
+        test_js();
+
+        pxs_finalize();
+    }
+}
@@ -0,0 +1,144 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_conformance --no-default-features --features "lua,testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use std::{
+        collections::HashMap,
+        ffi::{CStr, CString, c_char},
+        ptr,
+    };
+
+    use etffi::ptr_magic::PtrMagic;
+    use pixelscript::{
+        pxs_call_handle, pxs_conformance_setsource, pxs_finalize, pxs_initialize, pxs_register_backend,
+        pxs_run_conformance,
+        shared::{
+            backend::pxs_BackendVTable,
+            pxs_Opaque, pxs_Runtime,
+            var::{pxs_Var, pxs_VarT},
+        },
+    };
+
+    /// A backend whose scripts are `<function> <int>`, calling a function of `pxs_conformance`.
+    #[derive(Default)]
+    struct Calls {
+        runtime: i64,
+        handles: HashMap<String, i32>,
+    }
+
+    fn calls<'a>(opaque: pxs_Opaque) -> &'a mut Calls {
+        unsafe { &mut *(opaque as *mut Calls) }
+    }
+
+    fn key(name: &str) -> pxs_Var {
+        pxs_Var::new_string(name.to_string())
+    }
+
+    unsafe extern "C" fn start(opaque: pxs_Opaque, runtime: i32) {
+        calls(opaque).runtime = runtime as i64;
+    }
+
+    unsafe extern "C" fn add_module(opaque: pxs_Opaque, module: pxs_VarT) {
+        let module = unsafe { pxs_Var::from_borrow(module) };
+        let functions = module.get_map().unwrap().get_item(&key("functions")).unwrap();
+        for function in functions.get_list().unwrap().vars.iter() {
+            let function = function.get_map().unwrap();
+            let name = function.get_item(&key("name")).unwrap().get_string().unwrap();
+            let handle = function.get_item(&key("handle")).unwrap().get_i64().unwrap();
+            calls(opaque).handles.insert(name, handle as i32);
+        }
+    }
+
+    unsafe extern "C" fn execute(opaque: pxs_Opaque, code: *const c_char, _file_name: *const c_char) -> pxs_VarT {
+        let code = unsafe { CStr::from_ptr(code) }.to_string_lossy().to_string();
+        let (name, arg) = code.split_once(' ').unwrap();
+        let calls = calls(opaque);
+        let args = pxs_Var::new_list_with(vec![pxs_Var::new_i64(arg.parse().unwrap())]).into_raw();
+        let runtime = pxs_Var::new_i64(calls.runtime);
+        let res = pxs_Var::from_raw(pxs_call_handle(&runtime as *const pxs_Var as pxs_VarT, calls.handles[name], args));
+        if res.is_exception() { res.into_raw() } else { ptr::null_mut() }
+    }
+
+    fn status(report: &pxs_Var, case: &str) -> String {
+        let cases = report.get_map().unwrap().get_item(&key("cases")).unwrap();
+        let case = cases
+            .get_list()
+            .unwrap()
+            .vars
+            .iter()
+            .find(|c| c.get_map().unwrap().get_item(&key("name")).unwrap().get_string().unwrap() == case)
+            .unwrap();
+        case.get_map().unwrap().get_item(&key("status")).unwrap().get_string().unwrap()
+    }
+
+    fn count(report: &pxs_Var, field: &str) -> i64 {
+        report.get_map().unwrap().get_item(&key(field)).unwrap().get_i64().unwrap()
+    }
+
+    fn custom_backend() {
+        let mut state = Calls::default();
+        let vtable = pxs_BackendVTable {
+            name: c"calls".as_ptr(),
+            opaque: &mut state as *mut Calls as pxs_Opaque,
+            start: Some(start),
+            stop: None,
+            add_module: Some(add_module),
+            remove_module: None,
+            execute: Some(execute),
+            eval: None,
+            compile: None,
+            exec_object: None,
+            call: None,
+            object_call: None,
+            object_get: None,
+            object_set: None,
+        };
+        let id = pxs_register_backend(&vtable);
+
+        let set = |case: &str, code: &str| {
+            let (case, code) = (CString::new(case).unwrap(), CString::new(code).unwrap());
+            pxs_conformance_setsource(id, case.as_ptr(), code.as_ptr());
+        };
+        set("int", "report 42");
+        set("function", "report 6");
+        set("host_error", "fail 0");
+
+        let report = pxs_Var::from_raw(pxs_run_conformance(id));
+        assert_eq!(status(&report, "int"), "pass");
+        assert_eq!(status(&report, "function"), "fail", "Reported 6 instead of 5");
+        assert_eq!(status(&report, "host_error"), "pass");
+        assert_eq!(status(&report, "map"), "skip");
+        assert_eq!(count(&report, "passed"), 2);
+        assert_eq!(count(&report, "failed"), 1);
+    }
+
+    fn lua() {
+        let report = pxs_Var::from_raw(pxs_run_conformance(pxs_Runtime::pxs_Lua as i32));
+        for case in ["int", "float", "string", "bool", "list", "function", "variable", "method", "host_error", "script_error"] {
+            assert_eq!(status(&report, case), "pass", "Lua failed {case}: {:#?}", report);
+        }
+        assert_eq!(count(&report, "skipped"), 0);
+    }
+
+    #[test]
+    fn run_test() {
+        pxs_initialize();
+
+        custom_backend();
+        lua();
+
+        // Unknown runtime.
+        assert!(pxs_Var::from_raw(pxs_run_conformance(99)).is_exception());
+
+        pxs_finalize();
+    }
+}
@@ -0,0 +1,76 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_context --no-default-features --features "lua,testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use std::{ffi::CString, ptr};
+
+    use etffi::ptr_magic::PtrMagic;
+    use pixelscript::{
+        pxs_addfunc, pxs_addmod_ctx, pxs_context_free, pxs_context_new, pxs_eval_ctx, pxs_exec_ctx, pxs_finalize,
+        pxs_freevar_ctx, pxs_initialize, pxs_newmod, pxs_varcall_ctx,
+        shared::{
+            pxs_Runtime,
+            var::{pxs_Var, pxs_VarT},
+        },
+    };
+
+    extern "C" fn answer(_args: pxs_VarT) -> pxs_VarT {
+        pxs_Var::new_i64(42).into_raw()
+    }
+
+    fn exec(ctx: *mut pixelscript::shared::context::pxs_Context, code: &str) -> pxs_Var {
+        let code = CString::new(code).unwrap();
+        let file = CString::new("<ctx>").unwrap();
+        pxs_Var::from_raw(pxs_exec_ctx(ctx, pxs_Runtime::pxs_Lua, code.as_ptr(), file.as_ptr()))
+    }
+
+    fn eval(ctx: *mut pixelscript::shared::context::pxs_Context, code: &str) -> pxs_Var {
+        let code = CString::new(code).unwrap();
+        pxs_Var::from_raw(pxs_eval_ctx(ctx, code.as_ptr(), pxs_Runtime::pxs_Lua))
+    }
+
+    #[test]
+    fn run_test() {
+        pxs_initialize();
+
+        let a = pxs_context_new();
+        let b = pxs_context_new();
+
+        // Globals are per context.
+        assert!(!exec(a, "x = 1").is_exception());
+        assert_eq!(eval(a, "return x").get_i64().unwrap(), 1);
+        assert!(eval(b, "return x").is_null());
+
+        // So are modules.
+        let name = CString::new("host").unwrap();
+        let module = pxs_newmod(name.as_ptr());
+        let func_name = CString::new("answer").unwrap();
+        pxs_addfunc(module, func_name.as_ptr(), answer);
+        pxs_addmod_ctx(a, module);
+        assert_eq!(eval(a, "return require('host').answer()").get_i64().unwrap(), 42);
+        assert!(eval(b, "return require('host').answer()").is_exception());
+
+        // Functions of a context are called in it.
+        let func = eval(a, "return function(n) return n + x end").into_raw();
+        let args = pxs_Var::new_list_with(vec![pxs_Var::new_i64(2)]).into_raw();
+        let res = pxs_Var::from_raw(pxs_varcall_ctx(a, ptr::null_mut(), func, args));
+        assert_eq!(res.get_i64().unwrap(), 3);
+        pxs_freevar_ctx(a, func);
+
+        // Null contexts.
+        assert!(exec(ptr::null_mut(), "x = 1").is_exception());
+
+        pxs_context_free(a);
+        pxs_context_free(b);
+        pxs_finalize();
+    }
+}
@@ -0,0 +1,99 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_coroutine --no-default-features --features "lua,testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use std::{ffi::CString, ptr};
+
+    use etffi::ptr_magic::PtrMagic;
+    use pixelscript::{
+        pxs_coroutine_create, pxs_coroutine_free, pxs_coroutine_resume, pxs_coroutine_status, pxs_eval, pxs_exec,
+        pxs_finalize, pxs_initialize,
+        shared::{
+            coroutine::{pxs_Coroutine, pxs_CoroutineStatus},
+            pxs_Runtime,
+            var::{pxs_Var, pxs_VarT},
+        },
+    };
+
+    fn lua() -> pxs_Var {
+        pxs_Runtime::pxs_Lua.into_var()
+    }
+
+    fn func(name: &str) -> pxs_Var {
+        let code = CString::new(format!("return {name}")).unwrap();
+        pxs_Var::from_raw(pxs_eval(code.as_ptr(), pxs_Runtime::pxs_Lua))
+    }
+
+    fn resume(co: *mut pxs_Coroutine, args: Vec<pxs_Var>) -> pxs_Var {
+        pxs_Var::from_raw(pxs_coroutine_resume(co, pxs_Var::new_list_with(args).into_raw()))
+    }
+
+    #[test]
+    fn run_test() {
+        pxs_initialize();
+
+        let code = CString::new(
+            r#"
+function counter(start)
+    local total = start
+    while total < 3 do
+        total = total + (coroutine.yield(total) or 1)
+    end
+    return 'done'
+end
+
+function broken()
+    coroutine.yield(1)
+    error('boom')
+end
+"#,
+        )
+        .unwrap();
+        let file = CString::new("<coroutine>").unwrap();
+        assert!(!pxs_Var::from_raw(pxs_exec(pxs_Runtime::pxs_Lua, code.as_ptr(), file.as_ptr())).is_exception());
+
+        // Driven across "frames", resume args are the results of `coroutine.yield`.
+        let rt = lua();
+        let counter = func("counter");
+        let args = pxs_Var::new_list_with(vec![pxs_Var::new_i64(0)]).into_raw();
+        let co = pxs_coroutine_create(&rt as *const pxs_Var as pxs_VarT, &counter as *const pxs_Var as pxs_VarT, args);
+        assert!(!co.is_null());
+        assert_eq!(pxs_coroutine_status(co), pxs_CoroutineStatus::pxs_CoroutineSuspended);
+        assert_eq!(resume(co, vec![]).get_i64().unwrap(), 0);
+        assert_eq!(resume(co, vec![pxs_Var::new_i64(2)]).get_i64().unwrap(), 2);
+        assert_eq!(pxs_coroutine_status(co), pxs_CoroutineStatus::pxs_CoroutineSuspended);
+        assert_eq!(pxs_Var::from_raw(pxs_coroutine_resume(co, ptr::null_mut())).get_string().unwrap(), "done");
+        assert_eq!(pxs_coroutine_status(co), pxs_CoroutineStatus::pxs_CoroutineDead);
+        assert!(resume(co, vec![]).is_exception());
+        pxs_coroutine_free(co);
+
+        // Script errors kill the coroutine.
+        let broken = func("broken");
+        let co = pxs_coroutine_create(&rt as *const pxs_Var as pxs_VarT, &broken as *const pxs_Var as pxs_VarT, ptr::null_mut());
+        assert_eq!(resume(co, vec![]).get_i64().unwrap(), 1);
+        let res = resume(co, vec![]);
+        assert!(res.is_exception());
+        assert!(res.get_string().unwrap().contains("boom"));
+        assert_eq!(pxs_coroutine_status(co), pxs_CoroutineStatus::pxs_CoroutineDead);
+        pxs_coroutine_free(co);
+
+        // Only functions.
+        let not_func = pxs_Var::new_i64(1);
+        let co = pxs_coroutine_create(&rt as *const pxs_Var as pxs_VarT, &not_func as *const pxs_Var as pxs_VarT, ptr::null_mut());
+        assert!(co.is_null());
+        assert_eq!(pxs_coroutine_status(co), pxs_CoroutineStatus::pxs_CoroutineDead);
+
+        drop(counter);
+        drop(broken);
+        pxs_finalize();
+    }
+}
@@ -0,0 +1,85 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_csv --no-default-features --features "lua,python,js,include-core,testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use pixelscript::{
+        pxs_finalize, pxs_initialize, pxs_csvinit,
+        shared::{pxs_Runtime, utils},
+    };
+
+    fn print_helper(lang: &str) {
+        println!("====================== {lang} ===================");
+    }
+
+    fn test_python() {
+        let script = r#"
+import pxs_csv
+rows = pxs_csv.parse("name,hp,boss\nslime,10,false\n\"King, Slime\",250.5,true\n")
+assert len(rows) == 2, rows
+assert rows[0]["hp"] == 10, rows
+assert rows[1]["name"] == "King, Slime", rows
+assert rows[1]["boss"] == True, rows
+# Quoted fields stay strings.
+rows = pxs_csv.parse('id,code,flag,empty,missing\n1,"42","true","",\n')
+assert rows[0]["id"] == 1, rows
+assert rows[0]["code"] == "42", rows
+assert rows[0]["flag"] == "true", rows
+assert rows[0]["empty"] == "", rows
+assert rows[0]["missing"] is None, rows
+text = pxs_csv.stringify([["a", 1], ["b,c", 2]])
+assert text == "a,1\n\"b,c\",2\n", text
+"#;
+        let err = utils::execute_code(script, "<py>", pxs_Runtime::pxs_Python);
+        assert!(err.is_null(), "Python error: {:#?}", err);
+    }
+
+    fn test_lua() {
+        let script = r#"
+local pxs_csv = require('pxs_csv')
+local rows = pxs_csv.parse('id\tname\n1\tsword\n', {delimiter = '\t', infer = false})
+assert(rows[1].id == '1', rows[1].id)
+local text = pxs_csv.stringify({{id = 2, name = 'axe'}}, {columns = {'id', 'name'}})
+assert(text == 'id,name\n2,axe\n', text)
+"#;
+        let err = utils::execute_code(script, "<lua>", pxs_Runtime::pxs_Lua);
+        assert!(err.is_null(), "Lua error: {:#?}", err);
+    }
+
+    fn test_js() {
+        let script = r#"
+import { parse } from 'pxs_csv';
+let rows = parse('a,b\n1,2\n', {header: false});
+if (rows.length != 2 || rows[1][1] != 2) {
+    throw new Error(JSON.stringify(rows));
+}
+"#;
+        let err = utils::execute_code(script, "<js>", pxs_Runtime::pxs_JavaScript);
+        assert!(err.is_null(), "JS error: {:#?}", err);
+    }
+
+    #[test]
+    fn run_test() {
+        println!();
+        pxs_initialize();
+        pxs_csvinit();
+        utils::setup_pxs();
+
+        print_helper("PYTHON");
+        test_python();
+        print_helper("LUA");
+        test_lua();
+        print_helper("JS");
+        test_js();
+
+        pxs_finalize();
+    }
+}
@@ -0,0 +1,75 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_deepcopy --no-default-features --features "testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use etffi::ptr_magic::PtrMagic;
+    use pixelscript::{
+        pxs_deepcopy, pxs_finalize, pxs_initialize,
+        shared::var::{pxs_Var, pxs_VarT},
+    };
+
+    fn deepcopy(var: &pxs_Var, max_depth: i32) -> pxs_Var {
+        pxs_Var::from_raw(pxs_deepcopy(var as *const pxs_Var as pxs_VarT, max_depth))
+    }
+
+    /// `[depth]` nested `depth` times.
+    fn nested(depth: i32) -> pxs_Var {
+        (1..depth).fold(pxs_Var::new_list_with(vec![pxs_Var::new_i64(depth as i64)]), |inner, _| {
+            pxs_Var::new_list_with(vec![inner])
+        })
+    }
+
+    #[test]
+    fn run_test() {
+        pxs_initialize();
+
+        // Data is copied, the original is untouched.
+        let map = pxs_Var::new_map();
+        map.get_map().unwrap().add_item(pxs_Var::new_string("hp".to_string()), pxs_Var::new_i64(10));
+        let shared = pxs_Var::new_list_with(vec![pxs_Var::new_string("a".to_string())]);
+        let original = pxs_Var::new_list_with(vec![map, shared.clone(), shared, pxs_Var::new_bytes(vec![1, 2])]);
+        let copy = deepcopy(&original, -1);
+        assert!(copy.is_list());
+        let items = &copy.get_list().unwrap().vars;
+        assert_eq!(items.len(), 4);
+        let hp = items[0].get_map().unwrap().get_item(&pxs_Var::new_string("hp".to_string())).unwrap();
+        assert_eq!(hp.get_i64().unwrap(), 10);
+        assert_eq!(items[1].get_list().unwrap().vars[0].get_string().unwrap(), "a");
+        assert_ne!(items[1].get_list().unwrap() as *mut _, original.get_list().unwrap().vars[1].get_list().unwrap() as *mut _);
+        assert_eq!(items[3], pxs_Var::new_bytes(vec![1, 2]));
+
+        // Depth limit
+        assert!(deepcopy(&nested(3), 3).is_list());
+        assert!(deepcopy(&nested(4), 3).is_exception());
+        assert!(deepcopy(&pxs_Var::new_i64(1), 0).is_i64());
+
+        // A list containing itself.
+        let list = pxs_Var::new_list_with(vec![pxs_Var::new_i64(1)]);
+        let alias = unsafe { std::ptr::read(&list) };
+        list.get_list().unwrap().add_item(alias);
+        let res = deepcopy(&list, -1);
+        assert!(res.is_exception());
+        assert!(res.get_string().unwrap().contains("contains itself"));
+        // Do not free the list twice.
+        std::mem::forget(list.get_list().unwrap().vars.pop());
+
+        // Same for maps.
+        let map = pxs_Var::new_map();
+        let alias = unsafe { std::ptr::read(&map) };
+        map.get_map().unwrap().add_item(pxs_Var::new_string("self".to_string()), alias);
+        assert!(deepcopy(&map, -1).is_exception());
+        // Leak it, maps can not give back a item without dropping it.
+        std::mem::forget(map);
+
+        pxs_finalize();
+    }
+}
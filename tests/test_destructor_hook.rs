@@ -0,0 +1,97 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_destructor_hook --no-default-features --features "lua,testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use std::{
+        ffi::{CStr, CString, c_char, c_void},
+        sync::{
+            Mutex,
+            atomic::{AtomicI32, Ordering},
+        },
+    };
+
+    use etffi::ptr_magic::PtrMagic;
+    use pixelscript::{
+        pxs_addfunc, pxs_addmod, pxs_finalize, pxs_initialize, pxs_newhost, pxs_newobject, pxs_object_release,
+        pxs_object_retain, pxs_set_object_destructor_hook,
+        shared::{
+            pxs_Runtime, utils,
+            var::{pxs_Var, pxs_VarT},
+        },
+    };
+
+    /// Type name and host pointer of every collected object.
+    static COLLECTED: Mutex<Vec<(String, usize)>> = Mutex::new(vec![]);
+    /// Host pointer and idx of the last `Texture`.
+    static LAST: Mutex<(usize, i32)> = Mutex::new((0, -1));
+    static FREED: AtomicI32 = AtomicI32::new(0);
+
+    struct Texture {
+        handle: u32,
+    }
+
+    impl PtrMagic for Texture {}
+
+    extern "C" fn free_texture(ptr: *mut c_void) {
+        let _ = Texture::from_raw(ptr as *mut Texture);
+        FREED.fetch_add(1, Ordering::SeqCst);
+    }
+
+    unsafe extern "C" fn hook(type_name: *const c_char, ptr: *mut c_void) {
+        let type_name = unsafe { CStr::from_ptr(type_name) }.to_string_lossy().to_string();
+        // Still alive.
+        assert_eq!(unsafe { &*(ptr as *const Texture) }.handle, 7);
+        COLLECTED.lock().unwrap().push((type_name, ptr as usize));
+    }
+
+    extern "C" fn load(_args: pxs_VarT) -> pxs_VarT {
+        let ptr = Texture { handle: 7 }.into_raw() as *mut c_void;
+        let type_name = CString::new("Texture").unwrap();
+        let var = pxs_newhost(pxs_newobject(ptr, free_texture, type_name.as_ptr()));
+        *LAST.lock().unwrap() = (ptr as usize, unsafe { pxs_Var::from_borrow(var) }.get_host_idx());
+        var
+    }
+
+    fn run(script: &str) {
+        let err = utils::execute_code(script, "<hook>", pxs_Runtime::pxs_Lua);
+        assert!(err.is_null(), "Lua error: {:#?}", err);
+    }
+
+    #[test]
+    fn run_test() {
+        pxs_initialize();
+        pxs_set_object_destructor_hook(hook);
+
+        let module = utils::create_module("gfx");
+        let name = CString::new("load").unwrap();
+        pxs_addfunc(module, name.as_ptr(), load);
+        pxs_addmod(module);
+
+        run("local t = require('gfx').load() t = nil collectgarbage('collect') collectgarbage('collect')");
+        let (ptr, _) = *LAST.lock().unwrap();
+        assert_eq!(*COLLECTED.lock().unwrap(), vec![("Texture".to_string(), ptr)]);
+        assert_eq!(FREED.load(Ordering::SeqCst), 1);
+
+        // Called for the collected table even if the host keeps the object.
+        run("local t = require('gfx').load()");
+        let (ptr, idx) = *LAST.lock().unwrap();
+        assert!(pxs_object_retain(idx));
+        run("collectgarbage('collect') collectgarbage('collect')");
+        assert_eq!(COLLECTED.lock().unwrap().len(), 2);
+        assert_eq!(COLLECTED.lock().unwrap()[1].1, ptr);
+        assert_eq!(FREED.load(Ordering::SeqCst), 1);
+        assert!(pxs_object_release(idx));
+        assert_eq!(FREED.load(Ordering::SeqCst), 2);
+
+        pxs_finalize();
+    }
+}
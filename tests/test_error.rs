@@ -0,0 +1,75 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_error --no-default-features --features "lua,python,js,testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use std::ffi::{CStr, CString};
+
+    use pixelscript::{
+        pxs_error_free, pxs_exec_ex, pxs_finalize, pxs_initialize,
+        shared::{
+            error::{pxs_Error, pxs_ErrorCode},
+            pxs_Runtime,
+        },
+    };
+
+    fn print_helper(lang: &str) {
+        println!("====================== {lang} ===================");
+    }
+
+    /// Run code and check the error.
+    fn check(runtime: pxs_Runtime, code: &str, code_kind: pxs_ErrorCode, line: i32) {
+        let code = CString::new(code).unwrap();
+        let file_name = CString::new("game.script").unwrap();
+        let err = pxs_exec_ex(runtime, code.as_ptr(), file_name.as_ptr());
+        assert!(!err.is_null());
+
+        let e: &pxs_Error = unsafe { &*err };
+        let message = unsafe { CStr::from_ptr(e.message) }.to_str().unwrap();
+        println!("{message}");
+        assert_eq!(e.code, code_kind, "{message}");
+        assert_eq!(e.line, line, "{message}");
+        assert!(!e.file.is_null());
+        assert_eq!(unsafe { CStr::from_ptr(e.file) }.to_str().unwrap(), "game.script");
+
+        pxs_error_free(err);
+    }
+
+    fn ok(runtime: pxs_Runtime, code: &str) {
+        let code = CString::new(code).unwrap();
+        let file_name = CString::new("game.script").unwrap();
+        assert!(pxs_exec_ex(runtime, code.as_ptr(), file_name.as_ptr()).is_null());
+    }
+
+    #[test]
+    fn run_test() {
+        pxs_initialize();
+
+        print_helper("LUA");
+        ok(pxs_Runtime::pxs_Lua, "local x = 1");
+        check(pxs_Runtime::pxs_Lua, "local x = 1\nerror('boom')", pxs_ErrorCode::pxs_RuntimeError, 2);
+        check(pxs_Runtime::pxs_Lua, "local x = 1\n\nlocal = 2", pxs_ErrorCode::pxs_SyntaxError, 3);
+
+        print_helper("PYTHON");
+        ok(pxs_Runtime::pxs_Python, "x = 1");
+        check(pxs_Runtime::pxs_Python, "x = 1\nraise ValueError('boom')", pxs_ErrorCode::pxs_RuntimeError, 2);
+
+        print_helper("JS");
+        ok(pxs_Runtime::pxs_JavaScript, "let x = 1;");
+        check(pxs_Runtime::pxs_JavaScript, "let x = 1;\nthrow new Error('boom');", pxs_ErrorCode::pxs_RuntimeError, 2);
+
+        let err = pxs_exec_ex(pxs_Runtime::pxs_Lua, std::ptr::null(), std::ptr::null());
+        assert_eq!(unsafe { &*err }.code, pxs_ErrorCode::pxs_InvalidArgument);
+        pxs_error_free(err);
+
+        pxs_finalize();
+    }
+}
@@ -0,0 +1,74 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_eval_with --no-default-features --features "lua,python,testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use std::ffi::CString;
+
+    use etffi::ptr_magic::PtrMagic;
+    use pixelscript::{
+        pxs_eval, pxs_eval_with, pxs_finalize, pxs_initialize,
+        shared::{pxs_Runtime, var::pxs_Var},
+    };
+
+    fn bindings(items: Vec<(pxs_Var, pxs_Var)>) -> pxs_Var {
+        let var = pxs_Var::new_map();
+        let map = var.get_map().unwrap();
+        for (key, value) in items {
+            map.add_item(key, value);
+        }
+        var
+    }
+
+    fn s(val: &str) -> pxs_Var {
+        pxs_Var::new_string(val.to_string())
+    }
+
+    fn eval_with(runtime: pxs_Runtime, code: &str, bindings: &pxs_Var) -> pxs_Var {
+        let code = CString::new(code).unwrap();
+        pxs_Var::from_raw(pxs_eval_with(code.as_ptr(), runtime, bindings as *const pxs_Var as _))
+    }
+
+    fn eval(runtime: pxs_Runtime, code: &str) -> pxs_Var {
+        let code = CString::new(code).unwrap();
+        pxs_Var::from_raw(pxs_eval(code.as_ptr(), runtime))
+    }
+
+    fn check(runtime: pxs_Runtime, add: &str, damage: &str, missing: &str) {
+        let vars = bindings(vec![(s("x"), pxs_Var::new_i64(40)), (s("y"), pxs_Var::new_i64(2))]);
+        assert_eq!(eval_with(runtime.clone(), add, &vars).get_i64().unwrap(), 42);
+
+        // Same code, different bindings.
+        let vars = bindings(vec![(s("base"), pxs_Var::new_f64(10.0)), (s("scale"), pxs_Var::new_f64(1.5))]);
+        assert_eq!(eval_with(runtime.clone(), damage, &vars).get_f64().unwrap(), 15.0);
+        let vars = bindings(vec![(s("base"), pxs_Var::new_f64(4.0)), (s("scale"), pxs_Var::new_f64(0.5))]);
+        assert_eq!(eval_with(runtime.clone(), damage, &vars).get_f64().unwrap(), 2.0);
+
+        // Bindings do not leak into the globals.
+        let res = eval(runtime.clone(), missing);
+        assert!(res.is_exception() || res.is_null());
+
+        // Invalid bindings.
+        assert!(eval_with(runtime.clone(), add, &pxs_Var::new_i64(1)).is_exception());
+        let vars = bindings(vec![(pxs_Var::new_i64(1), pxs_Var::new_i64(2))]);
+        assert!(eval_with(runtime, add, &vars).is_exception());
+    }
+
+    #[test]
+    fn run_test() {
+        pxs_initialize();
+
+        check(pxs_Runtime::pxs_Lua, "return x + y", "return base * scale", "return base");
+        check(pxs_Runtime::pxs_Python, "x + y", "base * scale", "base");
+
+        pxs_finalize();
+    }
+}
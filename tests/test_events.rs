@@ -0,0 +1,74 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_events --no-default-features --features "lua,pxs_events,testing" -- --nocapture --test-threads=1
+
+#[cfg(all(test, feature = "pxs_events"))]
+#[allow(unused)]
+mod tests {
+    use std::{ffi::CString, ptr};
+
+    use etffi::ptr_magic::PtrMagic;
+    use pixelscript::{
+        pxs_emit, pxs_events_clear, pxs_eventsinit, pxs_exec, pxs_finalize, pxs_initialize,
+        shared::{pxs_Runtime, var::pxs_Var},
+    };
+
+    fn run(code: &str) {
+        let code = CString::new(code).unwrap();
+        let name = CString::new("<events>").unwrap();
+        let res = pxs_exec(pxs_Runtime::pxs_Lua, code.as_ptr(), name.as_ptr());
+        assert!(res.is_null(), "{:?}", pxs_Var::from_raw(res).get_string());
+    }
+
+    fn emit(event: &str, payload: Option<pxs_Var>) -> i32 {
+        let event = CString::new(event).unwrap();
+        match payload {
+            Some(payload) => pxs_emit(event.as_ptr(), &payload as *const pxs_Var as *mut pxs_Var),
+            None => pxs_emit(event.as_ptr(), ptr::null_mut()),
+        }
+    }
+
+    #[test]
+    fn run_test() {
+        pxs_initialize();
+        pxs_eventsinit();
+
+        // Handlers run in registration order.
+        run(
+            r#"
+local events = require('pxs_events')
+order = {}
+first = events.on('hit', function(dmg) table.insert(order, 'a' .. dmg) end)
+second = events.on('hit', function(dmg) table.insert(order, 'b' .. dmg) end)
+events.on('other', function() table.insert(order, 'other') end)
+"#,
+        );
+        assert_eq!(emit("hit", Some(pxs_Var::new_i64(5))), 2);
+        run("assert(table.concat(order, ',') == 'a5,b5', table.concat(order, ','))");
+
+        // Removed handlers are not called.
+        run("order = {} assert(require('pxs_events').off(first)) assert(not require('pxs_events').off(first))");
+        assert_eq!(emit("hit", Some(pxs_Var::new_i64(1))), 1);
+        run("assert(table.concat(order, ',') == 'b1')");
+
+        // Scripts emit too, without a payload is nil.
+        run("order = {} assert(require('pxs_events').emit('other') == 1) assert(order[1] == 'other')");
+        run("got = 0 require('pxs_events').on('nothing', function(p) got = p == nil end)");
+        assert_eq!(emit("nothing", None), 1);
+        run("assert(got == true)");
+
+        // No handlers.
+        assert_eq!(emit("missing", None), 0);
+
+        pxs_events_clear();
+        assert_eq!(emit("hit", Some(pxs_Var::new_i64(1))), 0);
+
+        pxs_finalize();
+    }
+}
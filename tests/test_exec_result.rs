@@ -0,0 +1,66 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_exec_result --no-default-features --features "lua,python,testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use std::{ffi::CString, ptr};
+
+    use etffi::ptr_magic::PtrMagic;
+    use pixelscript::{
+        pxs_exec_with_result, pxs_finalize, pxs_initialize,
+        shared::{
+            pxs_Runtime,
+            var::{pxs_Var, pxs_VarT},
+        },
+    };
+
+    /// Run code, returns the result or the error.
+    fn run(runtime: pxs_Runtime, code: &str) -> Result<pxs_Var, String> {
+        let code = CString::new(code).unwrap();
+        let file_name = CString::new("result.script").unwrap();
+        let mut err: pxs_VarT = ptr::null_mut();
+        let res = pxs_exec_with_result(runtime, code.as_ptr(), file_name.as_ptr(), &mut err);
+        if res.is_null() {
+            assert!(!err.is_null());
+            Err(pxs_Var::from_raw(err).get_string().unwrap())
+        } else {
+            assert!(err.is_null());
+            Ok(pxs_Var::from_raw(res))
+        }
+    }
+
+    #[test]
+    fn run_test() {
+        pxs_initialize();
+
+        // Lua returns explicitly.
+        assert_eq!(run(pxs_Runtime::pxs_Lua, "local x = 20\nreturn x + 1").unwrap().get_i64().unwrap(), 21);
+        let list = run(pxs_Runtime::pxs_Lua, "return {1, 2, 3}").unwrap();
+        assert_eq!(list.get_list().unwrap().len(), 3);
+        assert!(run(pxs_Runtime::pxs_Lua, "local x = 1").unwrap().is_null());
+        assert!(run(pxs_Runtime::pxs_Lua, "error('boom')").unwrap_err().contains("boom"));
+
+        // Python gives the last expression.
+        assert_eq!(run(pxs_Runtime::pxs_Python, "x = 20\nx + 1\n").unwrap().get_i64().unwrap(), 21);
+        let list = run(pxs_Runtime::pxs_Python, "def items():\n    return [1, 2, 3]\nitems()").unwrap();
+        assert_eq!(list.get_list().unwrap().len(), 3);
+        assert!(run(pxs_Runtime::pxs_Python, "x = 1").unwrap().is_null());
+        assert!(run(pxs_Runtime::pxs_Python, "if True:\n    x = 2").unwrap().is_null());
+        assert!(run(pxs_Runtime::pxs_Python, "raise ValueError('boom')\nx").unwrap_err().contains("boom"));
+
+        // Without a out_err.
+        let code = CString::new("error('boom')").unwrap();
+        let file_name = CString::new("result.script").unwrap();
+        assert!(pxs_exec_with_result(pxs_Runtime::pxs_Lua, code.as_ptr(), file_name.as_ptr(), ptr::null_mut()).is_null());
+
+        pxs_finalize();
+    }
+}
@@ -0,0 +1,103 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_expr --no-default-features --features "lua,python,js,testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use pixelscript::{
+        own_var, pxs_eval_expr, pxs_finalize, pxs_initialize, pxs_map_addpair, pxs_newfloat, pxs_newint,
+        pxs_newmap, pxs_newnull, pxs_newstring, pxs_newuint, pxs_freevar,
+        shared::{pxs_Runtime, var::{pxs_Var, pxs_VarT}},
+    };
+    use etffi::ptr_magic::PtrMagic;
+    use std::ffi::CString;
+
+    fn eval(expr: &str, env: pxs_VarT) -> pxs_Var {
+        let expr = CString::new(expr).unwrap();
+        let res = pxs_eval_expr(pxs_Runtime::pxs_Lua, expr.as_ptr(), env);
+        own_var!(res)
+    }
+
+    #[test]
+    fn run_test() {
+        println!();
+        pxs_initialize();
+
+        let env = pxs_newmap();
+        pxs_map_addpair(env, pxs_newstring(c"level".as_ptr()), pxs_newint(4));
+        pxs_map_addpair(env, pxs_newstring(c"base".as_ptr()), pxs_newfloat(10.5));
+        let stats = pxs_newmap();
+        pxs_map_addpair(stats, pxs_newstring(c"str".as_ptr()), pxs_newint(7));
+        pxs_map_addpair(env, pxs_newstring(c"stats".as_ptr()), stats);
+
+        let res = eval("base + level * 2", env);
+        assert!(res.get_f64().unwrap() == 18.5, "Arithmetic failed: {:#?}", res);
+
+        let res = eval("stats.str % 4 == 3 and level >= 4", env);
+        assert!(res.get_bool().unwrap(), "Logic failed: {:#?}", res);
+
+        let res = eval("level > 10 ? 'high' : \"low\"", env);
+        assert!(res.get_string().unwrap() == "low", "Ternary failed: {:#?}", res);
+
+        let res = eval("-(level - 1) / 2", env);
+        assert!(res.get_f64().unwrap() == -1.5, "Division failed: {:#?}", res);
+
+        // Unknown identifiers are not allowed.
+        let res = eval("os.exit()", env);
+        assert!(res.is_exception(), "Expected exception: {:#?}", res);
+        let res = eval("missing + 1", env);
+        assert!(res.is_exception(), "Expected exception: {:#?}", res);
+        let res = eval("level / 0", env);
+        assert!(res.is_exception(), "Expected exception: {:#?}", res);
+        for expr in ["level % 0", "base % 0", "base % 0.0"] {
+            let res = eval(expr, env);
+            assert!(res.is_exception(), "Expected modulo by zero for {expr}: {:#?}", res);
+        }
+
+        // Unsigned ints above i64::MAX stay positive.
+        pxs_map_addpair(env, pxs_newstring(c"big".as_ptr()), pxs_newuint(u64::MAX));
+        let res = eval("big > 0", env);
+        assert!(res.get_bool().unwrap(), "Unsigned failed: {:#?}", res);
+        let res = eval("big / 2", env);
+        assert!(res.get_f64().unwrap() == u64::MAX as f64 / 2.0, "Unsigned failed: {:#?}", res);
+
+        // Integer overflow is a error.
+        pxs_map_addpair(env, pxs_newstring(c"min".as_ptr()), pxs_newint(i64::MIN));
+        for expr in ["-min", "min % -1", "min - 1", "-min * 1"] {
+            let res = eval(expr, env);
+            assert!(res.is_exception(), "Expected overflow for {expr}: {:#?}", res);
+        }
+        let res = eval("-(min + 1) % 7", env);
+        assert!(res.get_i64().unwrap() == i64::MAX % 7, "Negate failed: {:#?}", res);
+
+        // Deep nesting is a error instead of a stack overflow.
+        let res = eval(&format!("{}1{}", "(".repeat(20), ")".repeat(20)), env);
+        assert!(res.get_i64().unwrap() == 1, "Nesting failed: {:#?}", res);
+        for expr in [
+            format!("{}1{}", "(".repeat(100_000), ")".repeat(100_000)),
+            format!("{}1", "-".repeat(100_000)),
+            format!("1{}", " + 1".repeat(100_000)),
+            format!("stats{}", ".str".repeat(100_000)),
+            format!("{}1", "1 ? ".repeat(100_000)),
+        ] {
+            let res = eval(&expr, env);
+            assert!(res.is_exception(), "Expected depth error: {:#?}", res);
+        }
+
+        // Null env
+        let null_env = pxs_newnull();
+        let res = eval("1 + 2 * 3", null_env);
+        assert!(res.get_i64().unwrap() == 7, "Null env failed: {:#?}", res);
+        pxs_freevar(null_env);
+
+        pxs_freevar(env);
+        pxs_finalize();
+    }
+}
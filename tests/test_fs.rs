@@ -0,0 +1,139 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_fs --no-default-features --features "lua,python,js,include-core,testing" -- --nocapture --test-threads=1
+
+#[cfg(all(test, feature = "pxs_fs"))]
+#[allow(unused)]
+mod tests {
+    use std::{
+        collections::BTreeMap,
+        ffi::{CStr, c_char},
+        sync::Mutex,
+    };
+
+    use etffi::ptr_magic::PtrMagic;
+    use pixelscript::{
+        pxs_finalize, pxs_fsinit, pxs_initialize, pxs_set_dirreader, pxs_set_filereader, pxs_set_filewriter,
+        shared::{
+            pxs_Runtime, utils,
+            var::{pxs_Var, pxs_VarT},
+        },
+    };
+
+    /// In memory files the host exposes. Anything under `locked/` can not be written.
+    static FILES: Mutex<BTreeMap<String, String>> = Mutex::new(BTreeMap::new());
+
+    fn print_helper(lang: &str) {
+        println!("====================== {lang} ===================");
+    }
+
+    fn as_str(ptr: *const c_char) -> String {
+        unsafe { CStr::from_ptr(ptr) }.to_string_lossy().into_owned()
+    }
+
+    unsafe extern "C" fn load_file(path: *const c_char) -> pxs_VarT {
+        match FILES.lock().unwrap().get(&as_str(path)) {
+            Some(contents) => pxs_Var::new_string(contents.clone()).into_raw(),
+            None => std::ptr::null_mut(),
+        }
+    }
+
+    unsafe extern "C" fn write_file(path: *const c_char, contents: *const c_char) -> bool {
+        let path = as_str(path);
+        if path.starts_with("locked/") {
+            return false;
+        }
+        FILES.lock().unwrap().insert(path, as_str(contents));
+        true
+    }
+
+    unsafe extern "C" fn read_dir(path: *const c_char) -> pxs_VarT {
+        let prefix = format!("{}/", as_str(path));
+        let entries: Vec<pxs_Var> = FILES
+            .lock()
+            .unwrap()
+            .keys()
+            .filter_map(|key| key.strip_prefix(&prefix))
+            .map(|name| pxs_Var::new_string(name.to_string()))
+            .collect();
+        if entries.is_empty() {
+            return std::ptr::null_mut();
+        }
+        pxs_Var::new_list_with(entries).into_raw()
+    }
+
+    fn test_python() {
+        let script = r#"
+import pxs_fs
+assert pxs_fs.read('data/a.txt') == 'alpha'
+pxs_fs.write('data/py.txt', 'from python')
+assert pxs_fs.read('data/py.txt') == 'from python'
+assert pxs_fs.exists('data') and pxs_fs.exists('data/a.txt') and not pxs_fs.exists('nope')
+assert 'py.txt' in pxs_fs.list_dir('data')
+try:
+    pxs_fs.read('nope')
+    assert False
+except Exception:
+    pass
+"#;
+        let err = utils::execute_code(script, "<py>", pxs_Runtime::pxs_Python);
+        assert!(err.is_null(), "Python error: {:#?}", err);
+    }
+
+    fn test_lua() {
+        let script = r#"
+local fs = require('pxs_fs')
+assert(fs.read('data/a.txt') == 'alpha')
+fs.write('data/lua.txt', 'from lua')
+assert(fs.read('data/lua.txt') == 'from lua')
+assert(fs.read('data/py.txt') == 'from python')
+assert(fs.exists('data') and not fs.exists('nope'))
+assert(#fs.list_dir('data') == 3)
+assert(not pcall(fs.write, 'locked/x.txt', 'no'))
+assert(not pcall(fs.list_dir, 'nope'))
+"#;
+        let err = utils::execute_code(script, "<lua>", pxs_Runtime::pxs_Lua);
+        assert!(err.is_null(), "Lua error: {:#?}", err);
+    }
+
+    fn test_js() {
+        let script = r#"
+import { read, write, exists } from 'pxs_fs';
+write('data/js.txt', 'from js');
+if (read('data/js.txt') !== 'from js') throw new Error("read");
+if (!exists('data/lua.txt')) throw new Error("exists");
+"#;
+        let err = utils::execute_code(script, "<js>", pxs_Runtime::pxs_JavaScript);
+        assert!(err.is_null(), "JS error: {:#?}", err);
+    }
+
+    #[test]
+    fn run_test() {
+        println!();
+        pxs_initialize();
+        FILES.lock().unwrap().insert("data/a.txt".to_string(), "alpha".to_string());
+        pxs_set_filereader(load_file);
+        pxs_set_filewriter(write_file);
+        pxs_set_dirreader(read_dir);
+        pxs_fsinit();
+        utils::setup_pxs();
+
+        print_helper("PYTHON");
+        test_python();
+        print_helper("LUA");
+        test_lua();
+        print_helper("JS");
+        test_js();
+
+        assert_eq!(FILES.lock().unwrap().get("data/js.txt").unwrap(), "from js");
+        assert!(!FILES.lock().unwrap().contains_key("locked/x.txt"));
+
+        pxs_finalize();
+    }
+}
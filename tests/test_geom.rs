@@ -0,0 +1,171 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_geom --no-default-features --features "lua,python,js,include-core,testing" -- --nocapture --test-threads=1
+
+#[cfg(all(test, feature = "pxs_geom"))]
+#[allow(unused)]
+mod tests {
+    use pixelscript::{
+        pxs_finalize, pxs_geominit, pxs_initialize,
+        shared::{pxs_Runtime, utils},
+    };
+
+    fn print_helper(lang: &str) {
+        println!("====================== {lang} ===================");
+    }
+
+    fn test_python() {
+        let script = r#"
+import pxs_geom as g
+
+box = {'x': 0, 'y': 0, 'w': 2, 'h': 2}
+
+# Containment includes the edges.
+assert g.rect_contains(box, {'x': 2, 'y': 2})
+assert g.rect_contains(box, [1, 1])
+assert not g.rect_contains(box, [2.5, 1])
+assert g.circle_contains({'x': 0, 'y': 0, 'r': 1}, [1, 0])
+assert not g.circle_contains({'x': 0, 'y': 0, 'r': 1}, [1, 1])
+
+# Overlap and intersection.
+assert g.rect_intersects(box, {'x': 1, 'y': 1, 'w': 2, 'h': 2})
+res = g.rect_intersection(box, {'x': 1, 'y': 1, 'w': 2, 'h': 2})
+assert res['x'] == 1 and res['y'] == 1 and res['w'] == 1 and res['h'] == 1, res
+res = g.rect_intersection(box, {'x': 0.5, 'y': 0.5, 'w': 1, 'h': 1})
+assert res['x'] == 0.5 and res['w'] == 1, res
+
+# Rects that only touch do not overlap.
+assert not g.rect_intersects(box, {'x': 2, 'y': 0, 'w': 2, 'h': 2})
+assert g.rect_intersection(box, {'x': 2, 'y': 0, 'w': 2, 'h': 2}) is None
+assert g.rect_intersection(box, {'x': 5, 'y': 5, 'w': 1, 'h': 1}) is None
+
+# Degenerate rects: a zero width rect inside the box still overlaps it.
+res = g.rect_intersection(box, {'x': 1, 'y': 0, 'w': 0, 'h': 2})
+assert res['x'] == 1 and res['w'] == 0 and res['h'] == 2, res
+
+# Circles that touch overlap, circles apart do not.
+assert g.circle_intersects({'x': 0, 'y': 0, 'r': 1}, {'x': 2, 'y': 0, 'r': 1})
+assert not g.circle_intersects({'x': 0, 'y': 0, 'r': 1}, {'x': 2.5, 'y': 0, 'r': 1})
+assert g.circle_intersects({'x': 0, 'y': 0, 'r': 0}, {'x': 0, 'y': 0, 'r': 0})
+assert g.circle_rect_intersects({'x': 3, 'y': 1, 'r': 1}, box)
+assert not g.circle_rect_intersects({'x': 3, 'y': 3, 'r': 1}, box)
+assert g.circle_rect_intersects({'x': 1, 'y': 1, 'r': 0.1}, box)
+
+# Ray distance to a rect, the direction does not need to be normalized.
+hit = g.raycast_rect([-3, 1], [2, 0], box)
+assert hit['x'] == 0 and hit['y'] == 1 and hit['distance'] == 3, hit
+hit = g.raycast_rect([-4, -4], [1, 1], box)
+assert abs(hit['x']) < 1e-9 and abs(hit['y']) < 1e-9, hit
+assert abs(hit['distance'] - 32 ** 0.5) < 1e-9, hit
+
+# A ray starting inside hits right away.
+hit = g.raycast_rect([1, 1], [0, 1], box)
+assert hit['distance'] == 0, hit
+
+# A ray collinear with a edge hits the corner, a parallel ray outside misses.
+hit = g.raycast_rect([-1, 0], [1, 0], box)
+assert hit['x'] == 0 and hit['y'] == 0 and hit['distance'] == 1, hit
+assert g.raycast_rect([-1, 3], [1, 0], box) is None
+
+# Rays pointing away miss.
+assert g.raycast_rect([5, 1], [1, 0], box) is None
+assert g.raycast_circle([5, 0], [1, 0], {'x': 0, 'y': 0, 'r': 1}) is None
+
+# Ray distance to a circle, and a tangent ray.
+hit = g.raycast_circle([-5, 0], [1, 0], {'x': 0, 'y': 0, 'r': 1})
+assert hit['x'] == -1 and hit['distance'] == 4, hit
+hit = g.raycast_circle([-5, 1], [1, 0], {'x': 0, 'y': 0, 'r': 1})
+assert hit['x'] == 0 and hit['y'] == 1 and hit['distance'] == 5, hit
+hit = g.raycast_circle([0, 0.5], [1, 0], {'x': 0, 'y': 0, 'r': 1})
+assert hit['distance'] == 0, hit
+
+# Invalid input.
+def fails(call):
+    try:
+        call()
+    except Exception:
+        return True
+    return False
+
+assert fails(lambda: g.raycast_rect([0, 0], [0, 0], box))
+assert fails(lambda: g.rect_contains({'x': 0, 'y': 0, 'w': -1, 'h': 1}, [0, 0]))
+assert fails(lambda: g.circle_contains({'x': 0, 'y': 0, 'r': -1}, [0, 0]))
+assert fails(lambda: g.rect_contains({'x': 0, 'y': 0}, [0, 0]))
+assert fails(lambda: g.rect_contains(box, [0]))
+"#;
+        let err = utils::execute_code(script, "<py>", pxs_Runtime::pxs_Python);
+        assert!(err.is_null(), "Python error: {:#?}", err);
+    }
+
+    fn test_lua() {
+        let script = r#"
+local g = require('pxs_geom')
+local box = { x = 0, y = 0, w = 2, h = 2 }
+
+assert(g.rect_contains(box, { 2, 2 }))
+assert(not g.rect_intersects(box, { x = 2, y = 0, w = 2, h = 2 }))
+assert(g.rect_intersection(box, { x = 2, y = 0, w = 2, h = 2 }) == nil)
+local res = g.rect_intersection(box, { x = 1, y = 1, w = 2, h = 2 })
+assert(res.x == 1 and res.w == 1)
+assert(g.circle_intersects({ x = 0, y = 0, r = 1 }, { x = 2, y = 0, r = 1 }))
+
+local hit = g.raycast_rect({ x = -1, y = 0 }, { x = 1, y = 0 }, box)
+assert(hit.x == 0 and hit.y == 0 and hit.distance == 1)
+assert(g.raycast_rect({ -1, 3 }, { 1, 0 }, box) == nil)
+hit = g.raycast_circle({ -5, 1 }, { 1, 0 }, { x = 0, y = 0, r = 1 })
+assert(hit.y == 1 and hit.distance == 5)
+
+assert(not pcall(g.raycast_circle, { 0, 0 }, { 0, 0 }, { x = 0, y = 0, r = 1 }))
+assert(not pcall(g.rect_contains, { x = 0, y = 0, w = 1, h = -1 }, { 0, 0 }))
+"#;
+        let err = utils::execute_code(script, "<lua>", pxs_Runtime::pxs_Lua);
+        assert!(err.is_null(), "Lua error: {:#?}", err);
+    }
+
+    fn test_js() {
+        let script = r#"
+import { rect_intersects, rect_intersection, raycast_rect, raycast_circle } from 'pxs_geom';
+
+const check = (ok, msg) => { if (!ok) throw new Error(msg); };
+const box = { x: 0, y: 0, w: 2, h: 2 };
+
+check(rect_intersects(box, { x: 1, y: 1, w: 2, h: 2 }), 'overlap');
+check(rect_intersection(box, { x: 2, y: 0, w: 2, h: 2 }) === null, 'touching');
+const hit = raycast_rect([-3, 1], [2, 0], box);
+check(hit.x === 0 && hit.y === 1 && hit.distance === 3, 'ray distance');
+check(raycast_circle([5, 0], [1, 0], { x: 0, y: 0, r: 1 }) === null, 'ray away');
+let failed = false;
+try {
+    raycast_rect([0, 0], [0, 0], box);
+} catch (e) {
+    failed = true;
+}
+check(failed, 'zero direction');
+"#;
+        let err = utils::execute_code(script, "<js>", pxs_Runtime::pxs_JavaScript);
+        assert!(err.is_null(), "JS error: {:#?}", err);
+    }
+
+    #[test]
+    fn run_test() {
+        println!();
+        pxs_initialize();
+        pxs_geominit();
+        utils::setup_pxs();
+
+        print_helper("PYTHON");
+        test_python();
+        print_helper("LUA");
+        test_lua();
+        print_helper("JS");
+        test_js();
+
+        pxs_finalize();
+    }
+}
@@ -0,0 +1,70 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_globals --no-default-features --features "lua,python,testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use std::ffi::CString;
+
+    use etffi::ptr_magic::PtrMagic;
+    use pixelscript::{
+        pxs_eval, pxs_exec, pxs_finalize, pxs_getglobal, pxs_initialize, pxs_setglobal,
+        shared::{pxs_Runtime, var::pxs_Var},
+    };
+
+    fn set(runtime: pxs_Runtime, name: &str, value: pxs_Var) -> bool {
+        let name = CString::new(name).unwrap();
+        pxs_setglobal(runtime, name.as_ptr(), value.into_raw())
+    }
+
+    fn get(runtime: pxs_Runtime, name: &str) -> pxs_Var {
+        let name = CString::new(name).unwrap();
+        pxs_Var::from_raw(pxs_getglobal(runtime, name.as_ptr()))
+    }
+
+    fn eval(runtime: pxs_Runtime, code: &str) -> pxs_Var {
+        let code = CString::new(code).unwrap();
+        pxs_Var::from_raw(pxs_eval(code.as_ptr(), runtime))
+    }
+
+    fn exec(runtime: pxs_Runtime, code: &str) {
+        let code = CString::new(code).unwrap();
+        let file = CString::new("<globals>").unwrap();
+        assert!(!pxs_Var::from_raw(pxs_exec(runtime, code.as_ptr(), file.as_ptr())).is_exception());
+    }
+
+    fn check(runtime: pxs_Runtime, read_dt: &str, set_score: &str) {
+        // Host to script.
+        assert!(set(runtime.clone(), "dt", pxs_Var::new_f64(0.5)));
+        assert_eq!(eval(runtime.clone(), read_dt).get_f64().unwrap(), 1.0);
+        assert!(set(runtime.clone(), "dt", pxs_Var::new_f64(0.25)));
+        assert_eq!(eval(runtime.clone(), read_dt).get_f64().unwrap(), 0.5);
+
+        // Script to host.
+        exec(runtime.clone(), set_score);
+        assert_eq!(get(runtime.clone(), "score").get_i64().unwrap(), 10);
+        let list = pxs_Var::new_list_with(vec![pxs_Var::new_i64(1), pxs_Var::new_i64(2)]);
+        assert!(set(runtime.clone(), "items", list));
+        assert_eq!(get(runtime.clone(), "items").get_list().unwrap().len(), 2);
+        assert!(get(runtime, "missing").is_null());
+    }
+
+    #[test]
+    fn run_test() {
+        pxs_initialize();
+
+        check(pxs_Runtime::pxs_Lua, "return dt * 2", "score = 10");
+        check(pxs_Runtime::pxs_Python, "dt * 2", "score = 10");
+
+        assert!(!pxs_setglobal(pxs_Runtime::pxs_Lua, std::ptr::null(), pxs_Var::new_null().into_raw()));
+
+        pxs_finalize();
+    }
+}
@@ -0,0 +1,66 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_handle --no-default-features --features "lua,include-core,testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use std::ffi::CString;
+
+    use pixelscript::{
+        pxs_addmod, pxs_call_handle, pxs_finalize, pxs_freevar, pxs_funchandle, pxs_getint, pxs_initialize,
+        pxs_listadd, pxs_listget, pxs_newint, pxs_newlist, pxs_varis,
+        shared::{
+            pxs_Runtime, utils,
+            var::{pxs_VarT, pxs_VarType},
+        },
+    };
+
+    /// `add(a, b)`, also checks that the runtime is passed first.
+    extern "C" fn add(args: pxs_VarT) -> pxs_VarT {
+        assert_eq!(pxs_getint(pxs_listget(args, 0)), pxs_Runtime::pxs_Lua.into_i64());
+        pxs_newint(pxs_getint(pxs_listget(args, 1)) + pxs_getint(pxs_listget(args, 2)))
+    }
+
+    fn handle(module: &str, name: &str) -> i32 {
+        let module = CString::new(module).unwrap();
+        let name = CString::new(name).unwrap();
+        pxs_funchandle(module.as_ptr(), name.as_ptr())
+    }
+
+    #[test]
+    fn run_test() {
+        pxs_initialize();
+        let test_module = utils::create_module("math");
+        utils::add_function(test_module, "add", add);
+        pxs_addmod(test_module);
+
+        let add_handle = handle("math", "add");
+        assert!(add_handle >= 0);
+        assert_eq!(handle("math", "missing"), -1);
+
+        let rt = pxs_newint(pxs_Runtime::pxs_Lua.into_i64());
+        for i in 0..3 {
+            let args = pxs_newlist();
+            pxs_listadd(args, pxs_newint(i));
+            pxs_listadd(args, pxs_newint(10));
+            let res = pxs_call_handle(rt, add_handle, args);
+            assert_eq!(pxs_getint(res), i + 10);
+            pxs_freevar(res);
+        }
+
+        // Unknown handles are a exception, not a crash.
+        let res = pxs_call_handle(rt, 9999, pxs_newlist());
+        assert!(pxs_varis(res, pxs_VarType::pxs_Exception));
+        pxs_freevar(res);
+
+        pxs_freevar(rt);
+        pxs_finalize();
+    }
+}
@@ -0,0 +1,53 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_hostfunc --no-default-features --features "lua,testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use std::sync::atomic::{AtomicI64, Ordering};
+
+    use pixelscript::{
+        pxs_addmod, pxs_finalize, pxs_funcopaque, pxs_getint, pxs_initialize, pxs_listget, pxs_newfunc, pxs_newint,
+        shared::{pxs_Opaque, pxs_Runtime, utils, var::pxs_VarT},
+    };
+
+    static CALLS: AtomicI64 = AtomicI64::new(0);
+
+    /// `add(a, b)`, counts calls through the opaque.
+    extern "C" fn add(args: pxs_VarT) -> pxs_VarT {
+        let calls = unsafe { &*(pxs_funcopaque() as *const AtomicI64) };
+        calls.fetch_add(1, Ordering::SeqCst);
+        pxs_newint(pxs_getint(pxs_listget(args, 1)) + pxs_getint(pxs_listget(args, 2)))
+    }
+
+    extern "C" fn get_adder(_args: pxs_VarT) -> pxs_VarT {
+        pxs_newfunc(add, &CALLS as *const AtomicI64 as pxs_Opaque)
+    }
+
+    #[test]
+    fn run_test() {
+        pxs_initialize();
+        let test_module = utils::create_module("test");
+        utils::add_function(test_module, "get_adder", get_adder);
+        pxs_addmod(test_module);
+
+        let script = r#"
+local add = require('test').get_adder()
+assert(type(add) == "function", "Expected a function, got " .. type(add))
+assert(add(2, 3) == 5)
+assert(add(10, -4) == 6)
+"#;
+        let err = utils::execute_code(script, "<lua>", pxs_Runtime::pxs_Lua);
+        assert!(err.is_null(), "Lua error: {:#?}", err);
+        assert_eq!(CALLS.load(Ordering::SeqCst), 2);
+
+        pxs_finalize();
+    }
+}
@@ -0,0 +1,136 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_http --no-default-features --features "lua,python,js,include-core,testing" -- --nocapture --test-threads=1
+
+#[cfg(all(test, feature = "pxs_http"))]
+#[allow(unused)]
+mod tests {
+    use std::ffi::{CStr, c_char};
+
+    use etffi::ptr_magic::PtrMagic;
+    use pixelscript::{
+        pxs_finalize, pxs_httpinit, pxs_initialize, pxs_set_http_handler,
+        shared::{
+            pxs_HttpRequest, pxs_HttpResponse, pxs_Runtime, utils,
+            var::{pxs_Var, pxs_VarT},
+        },
+    };
+
+    fn print_helper(lang: &str) {
+        println!("====================== {lang} ===================");
+    }
+
+    fn as_str(ptr: *const c_char) -> String {
+        unsafe { CStr::from_ptr(ptr) }.to_string_lossy().into_owned()
+    }
+
+    /// A fake server. `http://down` is unreachable, `/missing` is a 404 and anything else echoes the request as
+    /// `METHOD url x-test body`.
+    unsafe extern "C" fn handler(request: *const pxs_HttpRequest) -> pxs_HttpResponse {
+        let request = unsafe { &*request };
+        let url = as_str(request.url);
+        let mut response = pxs_HttpResponse {
+            status: 200,
+            headers: std::ptr::null_mut(),
+            body: std::ptr::null_mut(),
+            error: std::ptr::null_mut(),
+        };
+        if url == "http://down" {
+            response.error = pxs_Var::new_string("connection refused".to_string()).into_raw();
+            return response;
+        }
+        if url.ends_with("/missing") {
+            response.status = 404;
+            return response;
+        }
+
+        let headers = unsafe { pxs_Var::from_borrow(request.headers) }.get_map().unwrap();
+        let x_test = headers
+            .get_item(&pxs_Var::new_string("x-test".to_string()))
+            .and_then(|value| value.get_string().ok())
+            .unwrap_or_default();
+        let body = if request.body.is_null() {
+            String::new()
+        } else {
+            unsafe { pxs_Var::from_borrow(request.body) }.get_string().unwrap_or_default()
+        };
+        let res_headers = pxs_Var::new_map();
+        res_headers
+            .get_map()
+            .unwrap()
+            .add_item(pxs_Var::new_string("content-type".to_string()), pxs_Var::new_string("text/plain".to_string()));
+        response.headers = res_headers.into_raw();
+        response.body = pxs_Var::new_string(format!("{} {url} {x_test} {body}", as_str(request.method))).into_raw();
+        response
+    }
+
+    fn test_python() {
+        let script = r#"
+import pxs_http
+res = pxs_http.get('http://game/scores', {'headers': {'x-test': 7}})
+assert res['status'] == 200 and res['ok']
+assert res['body'] == 'GET http://game/scores 7 '
+assert res['headers']['content-type'] == 'text/plain'
+res = pxs_http.get('http://game/missing')
+assert res['status'] == 404 and not res['ok'] and res['body'] == ''
+try:
+    pxs_http.get('http://down')
+    assert False
+except Exception:
+    pass
+"#;
+        let err = utils::execute_code(script, "<py>", pxs_Runtime::pxs_Python);
+        assert!(err.is_null(), "Python error: {:#?}", err);
+    }
+
+    fn test_lua() {
+        let script = r#"
+local http = require('pxs_http')
+local res = http.post('http://game/save', 'data', { headers = { ['x-test'] = 'a' }, timeout = 2 })
+assert(res.ok and res.body == 'POST http://game/save a data')
+assert(not pcall(http.post, 'http://game/save', {}))
+assert(not pcall(http.get, 'http://game', { timeout = -1 }))
+"#;
+        let err = utils::execute_code(script, "<lua>", pxs_Runtime::pxs_Lua);
+        assert!(err.is_null(), "Lua error: {:#?}", err);
+    }
+
+    fn test_js() {
+        let script = r#"
+import { get, post } from 'pxs_http';
+const res = post('http://game/save', 'x');
+if (res.status !== 200 || res.body !== 'POST http://game/save  x') throw new Error("post");
+"#;
+        let err = utils::execute_code(script, "<js>", pxs_Runtime::pxs_JavaScript);
+        assert!(err.is_null(), "JS error: {:#?}", err);
+    }
+
+    #[test]
+    fn run_test() {
+        println!();
+        pxs_initialize();
+        pxs_httpinit();
+        utils::setup_pxs();
+
+        // No handler yet.
+        let err = utils::execute_code("require('pxs_http').get('http://game')", "<lua>", pxs_Runtime::pxs_Lua);
+        assert!(!err.is_null());
+
+        pxs_set_http_handler(Some(handler));
+        print_helper("PYTHON");
+        test_python();
+        print_helper("LUA");
+        test_lua();
+        print_helper("JS");
+        test_js();
+
+        pxs_set_http_handler(None);
+        pxs_finalize();
+    }
+}
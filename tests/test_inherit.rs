@@ -0,0 +1,140 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_inherit --no-default-features --features "lua,python,testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use std::ffi::{CString, c_void};
+
+    use etffi::ptr_magic::PtrMagic;
+    use pixelscript::{
+        pxs_addmod, pxs_addobject, pxs_class_setparent, pxs_finalize, pxs_freevar, pxs_gethost, pxs_initialize,
+        pxs_newhost, pxs_newobject, pxs_object_addfunc, pxs_registry_export,
+        shared::{
+            object::pxs_PixelObject,
+            pxs_Runtime, utils,
+            var::{pxs_Var, pxs_VarT},
+        },
+    };
+
+    /// Both types use the same host struct.
+    struct Unit {
+        name: String,
+        damage: i64,
+    }
+
+    impl PtrMagic for Unit {}
+
+    extern "C" fn free_unit(ptr: *mut c_void) {
+        let _ = Unit::from_raw(ptr as *mut Unit);
+    }
+
+    fn arg<'a>(args: pxs_VarT, idx: i32) -> &'a pxs_Var {
+        unsafe { pxs_Var::from_borrow(args) }.get_list().unwrap().get_item(idx).unwrap()
+    }
+
+    fn unit<'a>(args: pxs_VarT) -> &'a Unit {
+        let runtime = arg(args, 0) as *const pxs_Var as pxs_VarT;
+        unsafe { &*(pxs_gethost(runtime, arg(args, 1) as *const pxs_Var as pxs_VarT) as *const Unit) }
+    }
+
+    fn new_unit(type_name: &str, name: &str, damage: i64) -> *mut pxs_PixelObject {
+        let type_name = CString::new(type_name).unwrap();
+        let unit = Unit { name: name.to_string(), damage };
+        pxs_newobject(unit.into_raw() as *mut c_void, free_unit, type_name.as_ptr())
+    }
+
+    fn add_method(object: *mut pxs_PixelObject, name: &str, func: extern "C" fn(pxs_VarT) -> pxs_VarT) {
+        let name = CString::new(name).unwrap();
+        pxs_object_addfunc(object, name.as_ptr(), func);
+    }
+
+    extern "C" fn unit_name(args: pxs_VarT) -> pxs_VarT {
+        pxs_Var::new_string(unit(args).name.clone()).into_raw()
+    }
+
+    extern "C" fn unit_attack(args: pxs_VarT) -> pxs_VarT {
+        pxs_Var::new_i64(unit(args).damage).into_raw()
+    }
+
+    /// Only adds the methods of `Enemy`.
+    extern "C" fn enemy_new(args: pxs_VarT) -> pxs_VarT {
+        let object = new_unit("Enemy", &arg(args, 1).get_string().unwrap(), arg(args, 2).get_i64().unwrap());
+        add_method(object, "attack", unit_attack);
+        pxs_newhost(object)
+    }
+
+    fn run(runtime: pxs_Runtime, script: &str) {
+        let err = utils::execute_code(script, "<inherit>", runtime);
+        assert!(err.is_null(), "Script error: {:#?}", err);
+    }
+
+    fn key(name: &str) -> pxs_Var {
+        pxs_Var::new_string(name.to_string())
+    }
+
+    #[test]
+    fn run_test() {
+        pxs_initialize();
+
+        let (entity, enemy) = (CString::new("Entity").unwrap(), CString::new("Enemy").unwrap());
+        assert!(pxs_class_setparent(enemy.as_ptr(), entity.as_ptr()));
+        // No cycles.
+        assert!(!pxs_class_setparent(entity.as_ptr(), enemy.as_ptr()));
+        assert!(!pxs_class_setparent(enemy.as_ptr(), enemy.as_ptr()));
+
+        // Register the methods of `Entity` once.
+        let object = new_unit("Entity", "base", 0);
+        add_method(object, "name", unit_name);
+        pxs_freevar(pxs_newhost(object));
+
+        let module = utils::create_module("game");
+        pxs_addobject(module, enemy.as_ptr(), enemy_new);
+        pxs_addmod(module);
+
+        run(
+            pxs_Runtime::pxs_Lua,
+            r#"
+local game = require('game')
+local orc = game.Enemy('orc', 5)
+assert(orc:name() == 'orc')
+assert(orc:attack() == 5)
+local goblin = game.Enemy('goblin', 2)
+assert(goblin:name() == 'goblin')
+assert(goblin.missing == nil)
+"#,
+        );
+
+        run(
+            pxs_Runtime::pxs_Python,
+            r#"
+import game
+orc = game.Enemy('orc', 5)
+assert orc.name() == 'orc'
+assert orc.attack() == 5
+goblin = game.Enemy('goblin', 2)
+assert goblin.name() == 'goblin'
+"#,
+        );
+
+        let export = pxs_Var::from_raw(pxs_registry_export());
+        let objects = export.get_map().unwrap().get_item(&key("objects")).unwrap();
+        let enemy = objects
+            .get_list()
+            .unwrap()
+            .vars
+            .iter()
+            .find(|o| o.get_map().unwrap().get_item(&key("name")).unwrap().get_string().unwrap() == "Enemy")
+            .unwrap();
+        assert_eq!(enemy.get_map().unwrap().get_item(&key("parent")).unwrap().get_string().unwrap(), "Entity");
+
+        pxs_finalize();
+    }
+}
@@ -0,0 +1,79 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_init_threads --no-default-features --features "lua,testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use std::{
+        ffi::{CString, c_char},
+        sync::{Arc, Barrier},
+        thread,
+    };
+
+    use pixelscript::{
+        pxs_exec, pxs_finalize, pxs_freestr, pxs_initialize, pxs_is_initialized, pxs_last_error, pxs_startthread,
+        pxs_stopthread,
+        shared::pxs_Runtime,
+    };
+
+    const THREADS: usize = 8;
+
+    /// Run `f` on `THREADS` threads at the same time.
+    fn race<T: Send + 'static>(f: fn() -> T) -> Vec<T> {
+        let barrier = Arc::new(Barrier::new(THREADS));
+        let handles: Vec<_> = (0..THREADS)
+            .map(|_| {
+                let barrier = barrier.clone();
+                thread::spawn(move || {
+                    barrier.wait();
+                    f()
+                })
+            })
+            .collect();
+        handles.into_iter().map(|h| h.join().unwrap()).collect()
+    }
+
+    fn has_last_error() -> bool {
+        let err = pxs_last_error();
+        pxs_freestr(err);
+        !err.is_null()
+    }
+
+    #[test]
+    fn run_test() {
+        // Every caller returns initialized, only one starts the runtimes.
+        let ready = race(|| {
+            pxs_initialize();
+            pxs_is_initialized()
+        });
+        assert!(ready.iter().all(|r| *r));
+        assert!(pxs_is_initialized());
+
+        // Other threads start their own runtimes.
+        pxs_startthread();
+        let code = CString::new("x = 1").unwrap();
+        let name = CString::new("<init_threads>").unwrap();
+        assert!(pxs_exec(pxs_Runtime::pxs_Lua, code.as_ptr(), name.as_ptr()).is_null());
+        pxs_stopthread();
+
+        // Only one caller finalizes.
+        let failed = race(|| {
+            pxs_finalize();
+            has_last_error()
+        });
+        assert_eq!(failed.iter().filter(|f| !**f).count(), 1);
+        assert!(!pxs_is_initialized());
+
+        // And it can be started again.
+        pxs_initialize();
+        assert!(pxs_is_initialized());
+        pxs_finalize();
+    }
+}
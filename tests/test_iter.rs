@@ -0,0 +1,102 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_iter --no-default-features --features "lua,python,js,include-core,testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use pixelscript::{
+        pxs_addmod, pxs_finalize, pxs_freevar, pxs_getint, pxs_initialize, pxs_iter_free, pxs_iter_new, pxs_iter_next,
+        pxs_listget, pxs_listlen, pxs_newint, pxs_newnull, pxs_varis,
+        shared::{
+            pxs_Runtime, utils,
+            var::{pxs_VarT, pxs_VarType},
+        },
+    };
+
+    fn print_helper(lang: &str) {
+        println!("====================== {lang} ===================");
+    }
+
+    /// `sum(iterable)` sums every item, or the value of `[key, value]` items.
+    extern "C" fn sum(args: pxs_VarT) -> pxs_VarT {
+        let iter = pxs_iter_new(pxs_listget(args, 0), pxs_listget(args, 1));
+        if iter.is_null() {
+            return pxs_newnull();
+        }
+        let mut total = 0;
+        loop {
+            let item = pxs_iter_next(iter);
+            if item.is_null() {
+                break;
+            }
+            if pxs_varis(item, pxs_VarType::pxs_List) {
+                total += pxs_getint(pxs_listget(item, pxs_listlen(item) - 1));
+            } else {
+                total += pxs_getint(item);
+            }
+            pxs_freevar(item);
+        }
+        // Exhausted iterators stay exhausted.
+        assert!(pxs_iter_next(iter).is_null());
+        pxs_iter_free(iter);
+        pxs_newint(total)
+    }
+
+    fn test_lua() {
+        let script = r#"
+local test = require('test')
+assert(test.sum({1, 2, 3}) == 6)
+assert(test.sum({a = 1, b = 2, c = 3}) == 6)
+assert(test.sum({}) == 0)
+"#;
+        let err = utils::execute_code(script, "<lua>", pxs_Runtime::pxs_Lua);
+        assert!(err.is_null(), "Lua error: {:#?}", err);
+    }
+
+    fn test_python() {
+        let script = r#"
+import test
+assert test.sum([1, 2, 3]) == 6
+assert test.sum(range(5)) == 10
+assert test.sum({'a': 1, 'b': 2}) == 3
+"#;
+        let err = utils::execute_code(script, "<py>", pxs_Runtime::pxs_Python);
+        assert!(err.is_null(), "Python error: {:#?}", err);
+    }
+
+    fn test_js() {
+        let script = r#"
+import { sum } from 'test';
+if (sum([1, 2, 3]) !== 6) throw new Error("array");
+if (sum(new Set([4, 5])) !== 9) throw new Error("set");
+if (sum({ a: 1, b: 2 }) !== 3) throw new Error("object");
+"#;
+        let err = utils::execute_code(script, "<js>", pxs_Runtime::pxs_JavaScript);
+        assert!(err.is_null(), "JS error: {:#?}", err);
+    }
+
+    #[test]
+    fn run_test() {
+        println!();
+        pxs_initialize();
+        let test_module = utils::create_module("test");
+        utils::add_function(test_module, "sum", sum);
+        pxs_addmod(test_module);
+
+        print_helper("LUA");
+        test_lua();
+        print_helper("PYTHON");
+        test_python();
+        print_helper("JS");
+        test_js();
+
+        pxs_finalize();
+    }
+}
@@ -0,0 +1,155 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_jobs --no-default-features --features "lua,python,js,include-core,testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use std::{
+        ffi::{c_char, c_void},
+        sync::Mutex,
+    };
+
+    use etffi::ptr_magic::PtrMagic;
+    use pixelscript::{
+        pxs_addmod, pxs_finalize, pxs_getint, pxs_initialize, pxs_job_complete, pxs_job_setexecutor, pxs_jobsinit,
+        pxs_newexception, pxs_newhost, pxs_newint, pxs_newobject,
+        shared::{pxs_Runtime, utils, var::pxs_VarT},
+    };
+
+    /// Jobs waiting for the "host" to finish them.
+    static PENDING: Mutex<Vec<(i64, i64)>> = Mutex::new(vec![]);
+
+    fn print_helper(lang: &str) {
+        println!("====================== {lang} ===================");
+    }
+
+    /// `double` jobs finish later with `payload * 2`. `instant` jobs finish right away, `fail` jobs fail.
+    unsafe extern "C" fn executor(id: i64, kind: *const c_char, payload: pxs_VarT) {
+        let kind = unsafe { std::ffi::CStr::from_ptr(kind) }.to_str().unwrap();
+        match kind {
+            "double" => PENDING.lock().unwrap().push((id, pxs_getint(payload) * 2)),
+            "instant" => {
+                pxs_job_complete(id, pxs_newint(7));
+            }
+            _ => {
+                let msg = std::ffi::CString::new("no such kind").unwrap();
+                pxs_job_complete(id, pxs_newexception(msg.as_ptr()));
+            }
+        }
+    }
+
+    /// A host object of a other type.
+    struct Other {
+        id: i64,
+    }
+
+    impl PtrMagic for Other {}
+
+    extern "C" fn free_other(ptr: *mut c_void) {
+        let _ = Other::from_raw(ptr as *mut Other);
+    }
+
+    /// `host.other()`
+    extern "C" fn new_other(_args: pxs_VarT) -> pxs_VarT {
+        let ptr = Other { id: 1 }.into_raw() as *mut c_void;
+        pxs_newhost(pxs_newobject(ptr, free_other, c"Other".as_ptr()))
+    }
+
+    fn finish_pending() {
+        let pending: Vec<(i64, i64)> = PENDING.lock().unwrap().drain(..).collect();
+        for (id, value) in pending {
+            assert!(pxs_job_complete(id, pxs_newint(value)));
+            // Completing twice is refused.
+            assert!(!pxs_job_complete(id, pxs_newint(value)));
+        }
+    }
+
+    fn test_lua() {
+        // `then` is a keyword in Lua, so it is called through the table.
+        let script = r#"
+local pxs_jobs = require('pxs_jobs')
+results = {}
+job = pxs_jobs.submit('double', 21)
+assert(not job:done())
+"#;
+        let err = utils::execute_code(script, "<lua>", pxs_Runtime::pxs_Lua);
+        assert!(err.is_null(), "Lua error: {:#?}", err);
+
+        let script = r#"
+job['then'](job, function(result, err) results.value = result end)
+local instant = require('pxs_jobs').submit('instant', nil)
+assert(instant:done())
+assert(instant:result() == 7)
+local got
+instant['then'](instant, function(result) got = result end)
+assert(got == 7)
+local failed = require('pxs_jobs').submit('fail', nil)
+assert(failed:done())
+assert(failed:result() == nil)
+assert(failed:error() == 'no such kind')
+
+-- Only jobs are jobs.
+assert(not pcall(job.done, require('host').other()))
+"#;
+        let err = utils::execute_code(script, "<lua>", pxs_Runtime::pxs_Lua);
+        assert!(err.is_null(), "Lua error: {:#?}", err);
+
+        finish_pending();
+
+        let script = r#"
+assert(job:done())
+assert(job:result() == 42)
+assert(results.value == 42)
+"#;
+        let err = utils::execute_code(script, "<lua>", pxs_Runtime::pxs_Lua);
+        assert!(err.is_null(), "Lua error: {:#?}", err);
+    }
+
+    fn test_python() {
+        let script = r#"
+import pxs_jobs
+seen = []
+job = pxs_jobs.submit('double', 5)
+job.then(lambda result, err: seen.append(result))
+assert not job.done()
+"#;
+        let err = utils::execute_code(script, "<py>", pxs_Runtime::pxs_Python);
+        assert!(err.is_null(), "Python error: {:#?}", err);
+
+        finish_pending();
+
+        let script = r#"
+assert job.done()
+assert job.result() == 10
+assert seen == [10]
+"#;
+        let err = utils::execute_code(script, "<py>", pxs_Runtime::pxs_Python);
+        assert!(err.is_null(), "Python error: {:#?}", err);
+    }
+
+    #[test]
+    fn run_test() {
+        println!();
+        pxs_initialize();
+        pxs_job_setexecutor(executor);
+        pxs_jobsinit();
+        utils::setup_pxs();
+        let host = utils::create_module("host");
+        utils::add_function(host, "other", new_other);
+        pxs_addmod(host);
+
+        print_helper("LUA");
+        test_lua();
+        print_helper("PYTHON");
+        test_python();
+
+        pxs_finalize();
+    }
+}
@@ -0,0 +1,116 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_json --no-default-features --features "lua,include-core,testing" -- --nocapture --test-threads=1
+
+#[cfg(all(test, feature = "pxs_json"))]
+#[allow(unused)]
+mod tests {
+    use std::ffi::CString;
+
+    use etffi::ptr_magic::PtrMagic;
+    use pixelscript::{
+        pxs_exec, pxs_finalize, pxs_initialize, pxs_json_decode, pxs_json_encode,
+        shared::{
+            pxs_Runtime,
+            var::{pxs_Var, pxs_VarT},
+        },
+    };
+
+    fn rt() -> pxs_Var {
+        pxs_Var::new_i64(pxs_Runtime::pxs_Lua as i64)
+    }
+
+    fn encode(var: pxs_Var, indent: Option<i64>) -> pxs_Var {
+        let mut args = vec![var];
+        if let Some(indent) = indent {
+            args.push(pxs_Var::new_i64(indent));
+        }
+        let rt = rt();
+        pxs_Var::from_raw(pxs_json_encode(&rt as *const pxs_Var as pxs_VarT, pxs_Var::new_list_with(args).into_raw()))
+    }
+
+    fn decode(text: &str) -> pxs_Var {
+        let rt = rt();
+        let args = pxs_Var::new_list_with(vec![pxs_Var::new_string(text.to_string())]);
+        pxs_Var::from_raw(pxs_json_decode(&rt as *const pxs_Var as pxs_VarT, args.into_raw()))
+    }
+
+    fn string(val: &str) -> pxs_Var {
+        pxs_Var::new_string(val.to_string())
+    }
+
+    fn run(code: &str) {
+        let code = CString::new(code).unwrap();
+        let name = CString::new("<json>").unwrap();
+        let res = pxs_exec(pxs_Runtime::pxs_Lua, code.as_ptr(), name.as_ptr());
+        assert!(res.is_null(), "{:?}", pxs_Var::from_raw(res).get_string());
+    }
+
+    #[test]
+    fn run_test() {
+        pxs_initialize();
+
+        // Keys are sorted, floats keep their fraction.
+        let map = pxs_Var::new_map();
+        map.get_map().unwrap().add_item(string("name"), string("Jordan \"J\"\n"));
+        map.get_map().unwrap().add_item(string("hp"), pxs_Var::new_i64(10));
+        map.get_map().unwrap().add_item(
+            string("pos"),
+            pxs_Var::new_list_with(vec![pxs_Var::new_f64(1.0), pxs_Var::new_f64(-2.5), pxs_Var::new_null()]),
+        );
+        map.get_map().unwrap().add_item(string("alive"), pxs_Var::new_bool(true));
+        let json = encode(map, None);
+        assert_eq!(
+            json.get_string().unwrap(),
+            r#"{"alive":true,"hp":10,"name":"Jordan \"J\"\n","pos":[1.0,-2.5,null]}"#
+        );
+
+        // Decoding gives maps and lists, and round trips.
+        let decoded = decode(&json.get_string().unwrap());
+        let fields = decoded.get_map().unwrap();
+        assert!(fields.get_item(&string("hp")).unwrap().is_i64());
+        assert_eq!(fields.get_item(&string("name")).unwrap().get_string().unwrap(), "Jordan \"J\"\n");
+        let pos = fields.get_item(&string("pos")).unwrap();
+        assert!(pos.get_list().unwrap().vars[0].is_f64());
+        assert!(pos.get_list().unwrap().vars[2].is_null());
+        assert_eq!(encode(decoded, None).get_string().unwrap(), json.get_string().unwrap());
+
+        // Pretty printing.
+        let list = pxs_Var::new_list_with(vec![pxs_Var::new_i64(1), pxs_Var::new_list()]);
+        assert_eq!(encode(list, Some(2)).get_string().unwrap(), "[\n  1,\n  []\n]");
+
+        // Escapes and surrogate pairs.
+        assert_eq!(decode(r#""é😀\t""#).get_string().unwrap(), "é😀\t");
+        assert_eq!(decode(" [ 1e2 , -3 ] ").get_list().unwrap().vars[0].get_f64().unwrap(), 100.0);
+
+        // Errors are exceptions.
+        assert!(decode("{\"a\": }").is_exception());
+        assert!(decode("[1] 2").is_exception());
+        assert!(decode("[1,]").is_exception());
+        assert!(encode(pxs_Var::new_f64(f64::NAN), None).is_exception());
+        let nested = (0..200).fold(pxs_Var::new_i64(1), |inner, _| pxs_Var::new_list_with(vec![inner]));
+        assert!(encode(nested, None).is_exception());
+        assert!(decode(&format!("{}{}", "[".repeat(200), "]".repeat(200))).is_exception());
+
+        // Scripts get the same output.
+        run(
+            r#"
+local json = require('pxs_json')
+local text = json.encode({name = 'Jordan', hp = 10, pos = {1, 2}, stats = {str = 3}})
+assert(text == '{"hp":10,"name":"Jordan","pos":[1,2],"stats":{"str":3}}', text)
+local data = json.decode(text)
+assert(data.hp == 10 and data.pos[2] == 2 and data.stats.str == 3)
+assert(pxs_json.encode({1, 2}, 1) == '[\n 1,\n 2\n]')
+assert(not pcall(json.decode, '{'))
+"#,
+        );
+
+        pxs_finalize();
+    }
+}
@@ -0,0 +1,67 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_kwargs --no-default-features --features "python,testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use std::ffi::CString;
+
+    use etffi::ptr_magic::PtrMagic;
+    use pixelscript::{
+        pxs_addfunc, pxs_addmod, pxs_finalize, pxs_initialize,
+        shared::{
+            pxs_Runtime, utils,
+            var::{pxs_Var, pxs_VarT},
+        },
+    };
+
+    fn s(val: &str) -> pxs_Var {
+        pxs_Var::new_string(val.to_string())
+    }
+
+    /// `greet(name, greeting='Hello', punct='!')`, keyword arguments come as a map after `name`.
+    extern "C" fn greet(argv: pxs_VarT) -> pxs_VarT {
+        let argv = &unsafe { pxs_Var::from_borrow(argv) }.get_list().unwrap().vars;
+        let name = argv[1].get_string().unwrap();
+        let option = |key: &str, default: &str| {
+            argv.get(2)
+                .and_then(|kwargs| kwargs.get_map())
+                .and_then(|kwargs| kwargs.get_item(&s(key)))
+                .map(|value| value.get_string().unwrap())
+                .unwrap_or(default.to_string())
+        };
+        s(&format!("{} {}{} ({})", option("greeting", "Hello"), name, option("punct", "!"), argv.len())).into_raw()
+    }
+
+    #[test]
+    fn run_test() {
+        pxs_initialize();
+
+        let module = utils::create_module("hi");
+        let name = CString::new("greet").unwrap();
+        pxs_addfunc(module, name.as_ptr(), greet);
+        pxs_addmod(module);
+
+        let err = utils::execute_code(
+            r#"
+import hi
+# No map without keyword arguments.
+assert hi.greet('Ann') == 'Hello Ann! (2)', hi.greet('Ann')
+assert hi.greet('Ann', greeting='Hi') == 'Hi Ann! (3)'
+assert hi.greet('Bob', punct='?') == 'Hello Bob? (3)'
+"#,
+            "<kwargs>",
+            pxs_Runtime::pxs_Python,
+        );
+        assert!(err.is_null(), "Script error: {:#?}", err);
+
+        pxs_finalize();
+    }
+}
@@ -0,0 +1,73 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_lifecycle --no-default-features --features "lua,testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use std::ffi::CString;
+
+    use etffi::ptr_magic::PtrMagic;
+    use pixelscript::{
+        pxs_addfunc, pxs_addmod, pxs_eval, pxs_exec, pxs_finalize, pxs_initialize, pxs_newmod, pxs_registry_export,
+        shared::{
+            pxs_Runtime,
+            var::{pxs_Var, pxs_VarT},
+        },
+    };
+
+    extern "C" fn answer(_args: pxs_VarT) -> pxs_VarT {
+        pxs_Var::new_i64(42).into_raw()
+    }
+
+    fn add_module() {
+        let name = CString::new("host").unwrap();
+        let module = pxs_newmod(name.as_ptr());
+        let func_name = CString::new("answer").unwrap();
+        pxs_addfunc(module, func_name.as_ptr(), answer);
+        pxs_addmod(module);
+    }
+
+    fn module_count() -> usize {
+        let export = pxs_Var::from_raw(pxs_registry_export());
+        let modules = export.get_map().unwrap().get_item(&pxs_Var::new_string("modules".to_string())).unwrap();
+        modules.get_list().unwrap().vars.len()
+    }
+
+    fn eval(code: &str) -> pxs_Var {
+        let code = CString::new(code).unwrap();
+        pxs_Var::from_raw(pxs_eval(code.as_ptr(), pxs_Runtime::pxs_Lua))
+    }
+
+    #[test]
+    fn run_test() {
+        pxs_initialize();
+        add_module();
+        assert_eq!(module_count(), 1);
+        let code = CString::new("x = require('host').answer()").unwrap();
+        let file = CString::new("<lifecycle>").unwrap();
+        assert!(!pxs_Var::from_raw(pxs_exec(pxs_Runtime::pxs_Lua, code.as_ptr(), file.as_ptr())).is_exception());
+        assert_eq!(eval("return x").get_i64().unwrap(), 42);
+        pxs_finalize();
+
+        // A fresh start, nothing of the last run is left.
+        pxs_initialize();
+        assert_eq!(module_count(), 0);
+        assert!(eval("return x").is_null());
+        assert!(eval("return require('host')").is_exception());
+
+        // And it works like the first one.
+        add_module();
+        assert_eq!(eval("return require('host').answer()").get_i64().unwrap(), 42);
+        pxs_finalize();
+
+        pxs_initialize();
+        pxs_finalize();
+    }
+}
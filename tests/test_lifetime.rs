@@ -0,0 +1,151 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_lifetime --no-default-features --features "lua,python,testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use std::{
+        ffi::{CString, c_void},
+        sync::atomic::{AtomicI32, Ordering},
+    };
+
+    use etffi::ptr_magic::PtrMagic;
+    use pixelscript::{
+        pxs_addfunc, pxs_addmod, pxs_finalize, pxs_gethost, pxs_initialize, pxs_newhost, pxs_newobject,
+        pxs_object_addfunc, pxs_object_release, pxs_object_retain,
+        shared::{
+            pxs_Runtime, utils,
+            var::{pxs_Var, pxs_VarT},
+        },
+    };
+
+    /// Idx of the last `Thing`.
+    static LAST_THING: AtomicI32 = AtomicI32::new(-1);
+    /// Number of freed `Thing`s.
+    static FREED: AtomicI32 = AtomicI32::new(0);
+
+    struct Thing {
+        value: i64,
+    }
+
+    impl PtrMagic for Thing {}
+
+    extern "C" fn free_thing(ptr: *mut c_void) {
+        let _ = Thing::from_raw(ptr as *mut Thing);
+        FREED.fetch_add(1, Ordering::SeqCst);
+    }
+
+    fn arg<'a>(args: pxs_VarT, idx: i32) -> &'a pxs_Var {
+        unsafe { pxs_Var::from_borrow(args) }.get_list().unwrap().get_item(idx).unwrap()
+    }
+
+    fn var_ptr(var: &pxs_Var) -> pxs_VarT {
+        var as *const pxs_Var as pxs_VarT
+    }
+
+    extern "C" fn thing_value(args: pxs_VarT) -> pxs_VarT {
+        let thing = unsafe { &*(pxs_gethost(var_ptr(arg(args, 0)), var_ptr(arg(args, 1))) as *const Thing) };
+        pxs_Var::new_i64(thing.value).into_raw()
+    }
+
+    fn new_thing(value: i64) -> pxs_VarT {
+        let type_name = CString::new("Thing").unwrap();
+        let object = pxs_newobject(Thing { value }.into_raw() as *mut c_void, free_thing, type_name.as_ptr());
+        let name = CString::new("value").unwrap();
+        pxs_object_addfunc(object, name.as_ptr(), thing_value);
+        let var = pxs_newhost(object);
+        LAST_THING.store(unsafe { pxs_Var::from_borrow(var) }.get_host_idx(), Ordering::SeqCst);
+        var
+    }
+
+    extern "C" fn make(args: pxs_VarT) -> pxs_VarT {
+        new_thing(arg(args, 1).get_i64().unwrap())
+    }
+
+    /// The last `Thing` again.
+    extern "C" fn last(_args: pxs_VarT) -> pxs_VarT {
+        pxs_Var::new_host_object(LAST_THING.load(Ordering::SeqCst)).into_raw()
+    }
+
+    fn run(runtime: pxs_Runtime, script: &str) {
+        let err = utils::execute_code(script, "<lifetime>", runtime);
+        assert!(err.is_null(), "Script error: {:#?}", err);
+    }
+
+    fn freed() -> i32 {
+        FREED.load(Ordering::SeqCst)
+    }
+
+    #[test]
+    fn run_test() {
+        pxs_initialize();
+
+        let module = utils::create_module("life");
+        for (name, func) in [("make", make as extern "C" fn(pxs_VarT) -> pxs_VarT), ("last", last)] {
+            let name = CString::new(name).unwrap();
+            pxs_addfunc(module, name.as_ptr(), func);
+        }
+        pxs_addmod(module);
+
+        // Freed once the table is collected.
+        run(
+            pxs_Runtime::pxs_Lua,
+            r#"
+local life = require('life')
+local t = life.make(1)
+assert(t:value() == 1)
+t = nil
+collectgarbage('collect')
+collectgarbage('collect')
+"#,
+        );
+        assert_eq!(freed(), 1);
+
+        // Kept by the host, the script gets a new table.
+        run(pxs_Runtime::pxs_Lua, "local t = require('life').make(2)");
+        let idx = LAST_THING.load(Ordering::SeqCst);
+        assert!(pxs_object_retain(idx));
+        run(
+            pxs_Runtime::pxs_Lua,
+            r#"
+local life = require('life')
+collectgarbage('collect')
+collectgarbage('collect')
+assert(life.last():value() == 2)
+"#,
+        );
+        assert_eq!(freed(), 1);
+        assert!(pxs_object_release(idx));
+        run(pxs_Runtime::pxs_Lua, "collectgarbage('collect') collectgarbage('collect')");
+        assert_eq!(freed(), 2);
+
+        // Freed once the instance is collected.
+        run(
+            pxs_Runtime::pxs_Python,
+            r#"
+import gc
+import life
+t = life.make(3)
+assert t.value() == 3
+del t
+gc.collect()
+"#,
+        );
+        assert_eq!(freed(), 3);
+
+        // Never passed to a script.
+        let thing = pxs_Var::from_raw(new_thing(4));
+        assert!(pxs_object_release(thing.get_host_idx()));
+        assert_eq!(freed(), 4);
+        assert!(!pxs_object_release(thing.get_host_idx()));
+
+        pxs_finalize();
+    }
+}
@@ -0,0 +1,82 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_limit --no-default-features --features "lua,python,js,testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use std::ffi::{CStr, CString};
+
+    use pixelscript::{
+        pxs_error_free, pxs_exec_ex, pxs_finalize, pxs_initialize, pxs_set_exec_limit,
+        shared::{
+            error::{pxs_Error, pxs_ErrorCode},
+            pxs_Runtime,
+        },
+    };
+
+    fn print_helper(lang: &str) {
+        println!("====================== {lang} ===================");
+    }
+
+    /// Run code and check that it was stopped by the limit.
+    fn timeout(runtime: pxs_Runtime, code: &str) {
+        let code = CString::new(code).unwrap();
+        let file_name = CString::new("loop.script").unwrap();
+        let err = pxs_exec_ex(runtime, code.as_ptr(), file_name.as_ptr());
+        assert!(!err.is_null());
+
+        let e: &pxs_Error = unsafe { &*err };
+        let message = unsafe { CStr::from_ptr(e.message) }.to_str().unwrap();
+        println!("{message}");
+        assert_eq!(e.code, pxs_ErrorCode::pxs_TimeoutError, "{message}");
+
+        pxs_error_free(err);
+    }
+
+    fn ok(runtime: pxs_Runtime, code: &str) {
+        let code = CString::new(code).unwrap();
+        let file_name = CString::new("loop.script").unwrap();
+        assert!(pxs_exec_ex(runtime, code.as_ptr(), file_name.as_ptr()).is_null());
+    }
+
+    #[test]
+    fn run_test() {
+        pxs_initialize();
+
+        print_helper("LUA");
+        pxs_set_exec_limit(pxs_Runtime::pxs_Lua, 100_000, 0);
+        ok(pxs_Runtime::pxs_Lua, "for i = 1, 100 do end");
+        timeout(pxs_Runtime::pxs_Lua, "while true do end");
+        // Catching the error does not keep the script going.
+        timeout(pxs_Runtime::pxs_Lua, "while true do pcall(function() while true do end end) end");
+        pxs_set_exec_limit(pxs_Runtime::pxs_Lua, 0, 50);
+        timeout(pxs_Runtime::pxs_Lua, "while true do end");
+        pxs_set_exec_limit(pxs_Runtime::pxs_Lua, 0, 0);
+        ok(pxs_Runtime::pxs_Lua, "for i = 1, 1000000 do end");
+
+        print_helper("PYTHON");
+        pxs_set_exec_limit(pxs_Runtime::pxs_Python, 10_000, 0);
+        ok(pxs_Runtime::pxs_Python, "for i in range(100):\n    pass");
+        timeout(pxs_Runtime::pxs_Python, "x = 0\nwhile True:\n    x += 1");
+        pxs_set_exec_limit(pxs_Runtime::pxs_Python, 0, 50);
+        timeout(pxs_Runtime::pxs_Python, "while True: pass");
+        pxs_set_exec_limit(pxs_Runtime::pxs_Python, 0, 0);
+
+        print_helper("JS");
+        pxs_set_exec_limit(pxs_Runtime::pxs_JavaScript, 1_000_000, 0);
+        ok(pxs_Runtime::pxs_JavaScript, "for (let i = 0; i < 100; i++) {}");
+        timeout(pxs_Runtime::pxs_JavaScript, "while (true) {}");
+        pxs_set_exec_limit(pxs_Runtime::pxs_JavaScript, 0, 50);
+        timeout(pxs_Runtime::pxs_JavaScript, "while (true) {}");
+        pxs_set_exec_limit(pxs_Runtime::pxs_JavaScript, 0, 0);
+
+        pxs_finalize();
+    }
+}
@@ -0,0 +1,68 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_list_builder --no-default-features --features "lua,testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use std::ptr;
+
+    use etffi::ptr_magic::PtrMagic;
+    use pixelscript::{
+        pxs_finalize, pxs_freevar, pxs_initialize, pxs_list_clear, pxs_list_extend, pxs_list_remove, pxs_listget,
+        pxs_listlen, pxs_newint, pxs_newlist_from, pxs_newstring,
+        shared::var::{pxs_Var, pxs_VarT},
+    };
+
+    fn int_at(list: pxs_VarT, index: i32) -> i64 {
+        unsafe { pxs_Var::from_borrow(pxs_listget(list, index)) }.get_i64().unwrap()
+    }
+
+    #[test]
+    fn run_test() {
+        pxs_initialize();
+
+        // Build in one call, null items become null.
+        let items = [pxs_newint(1), pxs_newint(2), ptr::null_mut()];
+        let list = pxs_newlist_from(items.as_ptr(), items.len());
+        assert_eq!(pxs_listlen(list), 3);
+        assert_eq!(int_at(list, 1), 2);
+        assert!(unsafe { pxs_Var::from_borrow(pxs_listget(list, 2)) }.is_null());
+
+        // Extend
+        let more = [pxs_newint(3), pxs_newstring(c"four".as_ptr())];
+        assert_eq!(pxs_list_extend(list, more.as_ptr(), more.len()), 5);
+        assert_eq!(pxs_list_extend(list, ptr::null(), 0), 5);
+
+        // Remove returns the item.
+        let last = pxs_Var::from_raw(pxs_list_remove(list, -1));
+        assert_eq!(last.get_string().unwrap(), "four");
+        let first = pxs_Var::from_raw(pxs_list_remove(list, 0));
+        assert_eq!(first.get_i64().unwrap(), 1);
+        assert_eq!(pxs_listlen(list), 3);
+        assert_eq!(int_at(list, 0), 2);
+        assert!(pxs_list_remove(list, 3).is_null());
+        assert!(pxs_list_remove(list, -4).is_null());
+
+        // Clear
+        assert!(pxs_list_clear(list));
+        assert_eq!(pxs_listlen(list), 0);
+
+        // Not a list, items are still taken.
+        let not_list = pxs_newint(0);
+        let items = [pxs_newint(1)];
+        assert_eq!(pxs_list_extend(not_list, items.as_ptr(), items.len()), -1);
+        assert!(!pxs_list_clear(not_list));
+        assert!(pxs_list_remove(not_list, 0).is_null());
+        pxs_freevar(not_list);
+
+        pxs_freevar(list);
+        pxs_finalize();
+    }
+}
@@ -0,0 +1,92 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_listindex --no-default-features --features "lua,testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use etffi::ptr_magic::PtrMagic;
+    use pixelscript::{
+        pxs_finalize, pxs_freevar, pxs_initialize, pxs_listdel, pxs_listget, pxs_listinsert, pxs_listset, pxs_newint,
+        pxs_newlist_from,
+        shared::var::{pxs_Var, pxs_VarList, pxs_VarT},
+    };
+
+    fn new_list(len: i64) -> pxs_VarList {
+        let mut list = pxs_VarList::new();
+        for i in 0..len {
+            list.add_item(pxs_Var::new_i64(i));
+        }
+        list
+    }
+
+    fn ints(list: &pxs_VarList) -> Vec<i64> {
+        list.vars.iter().map(|item| item.get_i64().unwrap()).collect()
+    }
+
+    #[test]
+    fn run_test() {
+        // Index resolution
+        let list = new_list(3);
+        assert_eq!(list.resolve_index(0, false).unwrap(), 0);
+        assert_eq!(list.resolve_index(2, false).unwrap(), 2);
+        assert_eq!(list.resolve_index(-1, false).unwrap(), 2);
+        assert_eq!(list.resolve_index(-3, false).unwrap(), 0);
+        assert!(list.resolve_index(3, false).is_err());
+        assert!(list.resolve_index(-4, false).is_err());
+        assert!(list.resolve_index(i32::MIN, false).is_err());
+        assert_eq!(list.resolve_index(3, true).unwrap(), 3);
+        assert!(list.resolve_index(4, true).is_err());
+        assert!(new_list(0).resolve_index(0, false).is_err());
+        assert_eq!(new_list(0).resolve_index(0, true).unwrap(), 0);
+
+        // get
+        assert_eq!(list.get_item(1).unwrap().get_i64().unwrap(), 1);
+        assert_eq!(list.get_item(-1).unwrap().get_i64().unwrap(), 2);
+        assert!(list.get_item(3).is_none());
+
+        // set
+        let mut list = new_list(3);
+        list.set_item(pxs_Var::new_i64(10), 1).unwrap();
+        list.set_item(pxs_Var::new_i64(20), -1).unwrap();
+        assert!(list.set_item(pxs_Var::new_i64(30), 3).is_err());
+        assert!(list.set_item(pxs_Var::new_i64(30), -4).is_err());
+        assert_eq!(ints(&list), vec![0, 10, 20]);
+
+        // insert
+        list.insert_item(-1, pxs_Var::new_i64(15)).unwrap();
+        list.insert_item(4, pxs_Var::new_i64(25)).unwrap();
+        list.insert_item(0, pxs_Var::new_i64(-5)).unwrap();
+        assert!(list.insert_item(7, pxs_Var::new_i64(0)).is_err());
+        assert_eq!(ints(&list), vec![-5, 0, 10, 15, 20, 25]);
+
+        // del and remove
+        list.del_item(-1).unwrap();
+        list.del_item(1).unwrap();
+        assert!(list.del_item(4).is_err());
+        assert_eq!(list.remove_item(-2).unwrap().get_i64().unwrap(), 15);
+        assert_eq!(ints(&list), vec![-5, 10, 20]);
+
+        // Through the FFI
+        pxs_initialize();
+        let items = [pxs_newint(0), pxs_newint(1), pxs_newint(2)];
+        let list = pxs_newlist_from(items.as_ptr(), items.len());
+        assert!(pxs_listset(list, 1, pxs_newint(11)));
+        assert!(pxs_listset(list, -3, pxs_newint(10)));
+        assert!(!pxs_listset(list, 3, pxs_newint(13)));
+        assert!(pxs_listdel(list, -1));
+        assert!(!pxs_listdel(list, 2));
+        pxs_listinsert(list, 2, pxs_newint(12));
+        pxs_listinsert(list, 10, pxs_newint(0));
+        assert_eq!(ints(unsafe { pxs_Var::from_borrow(list) }.get_list().unwrap()), vec![10, 11, 12]);
+        assert!(pxs_listget(list, 3).is_null());
+        pxs_freevar(list);
+        pxs_finalize();
+    }
+}
@@ -0,0 +1,77 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_listops --no-default-features --features "lua,testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use etffi::ptr_magic::PtrMagic;
+    use pixelscript::{
+        pxs_finalize, pxs_freevar, pxs_initialize, pxs_listfind, pxs_listget, pxs_listlen, pxs_listpop,
+        pxs_listslice, pxs_newfloat, pxs_newint, pxs_newlist_from, pxs_newstring,
+        shared::var::{pxs_Var, pxs_VarT},
+    };
+
+    /// The ints of a list.
+    fn ints(list: pxs_VarT) -> Vec<i64> {
+        let list = unsafe { pxs_Var::from_borrow(list) };
+        list.get_list().unwrap().vars.iter().map(|item| item.get_i64().unwrap()).collect()
+    }
+
+    #[test]
+    fn run_test() {
+        pxs_initialize();
+
+        let items: Vec<pxs_VarT> = (0..5).map(|i| pxs_newint(i)).collect();
+        let list = pxs_newlist_from(items.as_ptr(), items.len());
+
+        // Slices
+        let cases = [
+            (1, 3, vec![1, 2]),
+            (-2, i32::MAX, vec![3, 4]),
+            (0, -1, vec![0, 1, 2, 3]),
+            (3, 1, vec![]),
+            (-100, 100, vec![0, 1, 2, 3, 4]),
+        ];
+        for (start, end, expected) in cases {
+            let slice = pxs_listslice(list, start, end);
+            assert_eq!(ints(slice), expected, "slice({start}, {end})");
+            pxs_freevar(slice);
+        }
+        assert_eq!(pxs_listlen(list), 5);
+
+        // Find
+        let three = pxs_newint(3);
+        assert_eq!(pxs_listfind(list, three), 3);
+        let float = pxs_newfloat(3.0);
+        assert_eq!(pxs_listfind(list, float), -1);
+        let string = pxs_newstring(c"3".as_ptr());
+        assert_eq!(pxs_listfind(list, string), -1);
+        pxs_freevar(three);
+        pxs_freevar(float);
+        pxs_freevar(string);
+
+        // Pop
+        let last = pxs_Var::from_raw(pxs_listpop(list));
+        assert_eq!(last.get_i64().unwrap(), 4);
+        assert_eq!(ints(list), vec![0, 1, 2, 3]);
+        while !pxs_listpop(list).is_null() {}
+        assert_eq!(pxs_listlen(list), 0);
+
+        // Not a list
+        let not_list = pxs_newint(0);
+        assert!(pxs_Var::from_raw(pxs_listslice(not_list, 0, 1)).is_exception());
+        assert!(pxs_listpop(not_list).is_null());
+        assert_eq!(pxs_listfind(not_list, not_list), -1);
+        pxs_freevar(not_list);
+
+        pxs_freevar(list);
+        pxs_finalize();
+    }
+}
@@ -0,0 +1,105 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_log --no-default-features --features "lua,python,js,include-core,testing" -- --nocapture --test-threads=1
+
+#[cfg(all(test, feature = "pxs_log"))]
+#[allow(unused)]
+mod tests {
+    use std::{
+        ffi::{CStr, c_char},
+        sync::Mutex,
+    };
+
+    use pixelscript::{
+        pxs_finalize, pxs_initialize, pxs_loginit, pxs_set_log_sink,
+        shared::{pxs_LogLevel, pxs_Runtime, utils},
+    };
+
+    /// Level, message, file and line of every logged message.
+    static LOGS: Mutex<Vec<(pxs_LogLevel, String, String, i32)>> = Mutex::new(vec![]);
+
+    fn print_helper(lang: &str) {
+        println!("====================== {lang} ===================");
+    }
+
+    unsafe extern "C" fn sink(level: pxs_LogLevel, message: *const c_char, file: *const c_char, line: i32) {
+        let message = unsafe { CStr::from_ptr(message) }.to_string_lossy().to_string();
+        let file = unsafe { CStr::from_ptr(file) }.to_string_lossy().to_string();
+        LOGS.lock().unwrap().push((level, message, file, line));
+    }
+
+    /// Take the logged messages.
+    fn take_logs() -> Vec<(pxs_LogLevel, String, String, i32)> {
+        std::mem::take(&mut *LOGS.lock().unwrap())
+    }
+
+    fn test_python() {
+        let script = r#"
+import pxs_log
+pxs_log.debug('hidden')
+pxs_log.info('hp', 10, True, None)
+"#;
+        let err = utils::execute_code(script, "<py>", pxs_Runtime::pxs_Python);
+        assert!(err.is_null(), "Python error: {:#?}", err);
+        let logs = take_logs();
+        assert_eq!(logs, vec![(pxs_LogLevel::pxs_LogInfo, "hp 10 true null".to_string(), "<py>".to_string(), 4)]);
+    }
+
+    fn test_lua() {
+        let script = r#"
+local log = require('pxs_log')
+log.warn('low', 2.5)
+log.error('boom')
+"#;
+        let err = utils::execute_code(script, "<lua>", pxs_Runtime::pxs_Lua);
+        assert!(err.is_null(), "Lua error: {:#?}", err);
+        let logs = take_logs();
+        assert_eq!(logs, vec![
+            (pxs_LogLevel::pxs_LogWarn, "low 2.5".to_string(), "<lua>".to_string(), 3),
+            (pxs_LogLevel::pxs_LogError, "boom".to_string(), "<lua>".to_string(), 4),
+        ]);
+    }
+
+    fn test_js() {
+        let script = r#"
+import { info } from 'pxs_log';
+
+info('ready');
+"#;
+        let err = utils::execute_code(script, "<js>", pxs_Runtime::pxs_JavaScript);
+        assert!(err.is_null(), "JS error: {:#?}", err);
+        let logs = take_logs();
+        assert_eq!(logs, vec![(pxs_LogLevel::pxs_LogInfo, "ready".to_string(), "<js>".to_string(), 4)]);
+    }
+
+    #[test]
+    fn run_test() {
+        println!();
+        pxs_initialize();
+        pxs_loginit();
+        pxs_set_log_sink(Some(sink), pxs_LogLevel::pxs_LogInfo);
+        utils::setup_pxs();
+
+        print_helper("PYTHON");
+        test_python();
+        print_helper("LUA");
+        test_lua();
+        print_helper("JS");
+        test_js();
+
+        // Lower the minimum level.
+        pxs_set_log_sink(Some(sink), pxs_LogLevel::pxs_LogDebug);
+        let err = utils::execute_code("require('pxs_log').debug('shown')", "<lua>", pxs_Runtime::pxs_Lua);
+        assert!(err.is_null(), "Lua error: {:#?}", err);
+        assert_eq!(take_logs()[0].1, "shown");
+
+        pxs_set_log_sink(None, pxs_LogLevel::pxs_LogDebug);
+        pxs_finalize();
+    }
+}
@@ -0,0 +1,77 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_migrate --no-default-features --features "testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use std::ffi::CString;
+
+    use etffi::ptr_magic::PtrMagic;
+    use pixelscript::{
+        pxs_finalize, pxs_freevar, pxs_initialize, pxs_listadd, pxs_listget, pxs_listlen, pxs_migrate, pxs_newint, pxs_newlist, pxs_newstring,
+        pxs_varis,
+        shared::{
+            pxs_Runtime,
+            var::{pxs_Var, pxs_VarType},
+        },
+    };
+
+    fn migrate_data() {
+        let name = CString::new("Jordan").unwrap();
+        let list = pxs_newlist();
+        pxs_listadd(list, pxs_newint(1));
+        pxs_listadd(list, pxs_newstring(name.as_ptr()));
+        let inner = pxs_newlist();
+        pxs_listadd(inner, pxs_newint(2));
+        pxs_listadd(list, inner);
+
+        let res = pxs_migrate(list);
+        assert!(pxs_varis(res, pxs_VarType::pxs_List));
+        assert_eq!(pxs_listlen(res), 3);
+        assert_eq!(pxs_listlen(pxs_listget(res, 2)), 1);
+        assert_eq!(unsafe { pxs_Var::from_borrow(res) }.origin, -1);
+
+        pxs_freevar(res);
+        pxs_freevar(list);
+    }
+
+    fn not_migratable() {
+        let list = pxs_newlist();
+        pxs_listadd(list, pxs_Var::new_function(std::ptr::null_mut(), None).into_raw());
+
+        let res = pxs_migrate(list);
+        assert!(pxs_varis(res, pxs_VarType::pxs_Exception));
+
+        pxs_freevar(res);
+        pxs_freevar(list);
+    }
+
+    #[test]
+    fn test_foreign() {
+        let local = pxs_Var::new_null().with_origin(pxs_Runtime::pxs_Lua);
+        assert!(!local.is_foreign());
+        // Host vars belong to no VM.
+        assert!(!pxs_Var::new_null().is_foreign());
+
+        let vm = std::thread::spawn(|| pxs_Var::new_null().with_origin(pxs_Runtime::pxs_Lua).vm).join().unwrap();
+        let mut other = pxs_Var::new_null();
+        other.origin = pxs_Runtime::pxs_Lua.into_i64() as i32;
+        other.vm = vm;
+        assert!(other.is_foreign());
+    }
+
+    #[test]
+    fn run_test() {
+        pxs_initialize();
+        migrate_data();
+        not_migratable();
+        pxs_finalize();
+    }
+}
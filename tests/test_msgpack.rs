@@ -0,0 +1,123 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_msgpack --no-default-features --features "testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use etffi::ptr_magic::PtrMagic;
+    use pixelscript::{
+        pxs_finalize, pxs_frommsgpack, pxs_initialize, pxs_tomsgpack,
+        shared::{
+            msgpack,
+            var::{pxs_Var, pxs_VarType},
+        },
+    };
+
+    fn s(val: &str) -> pxs_Var {
+        pxs_Var::new_string(val.to_string())
+    }
+
+    fn map(items: Vec<(pxs_Var, pxs_Var)>) -> pxs_Var {
+        let var = pxs_Var::new_map();
+        let map = var.get_map().unwrap();
+        for (key, value) in items {
+            map.add_item(key, value);
+        }
+        var
+    }
+
+    fn entity(id: i64) -> pxs_Var {
+        map(vec![
+            (s("id"), pxs_Var::new_i64(id)),
+            (s("name"), s(&"x".repeat(id as usize))),
+            (s("pos"), pxs_Var::new_list_with(vec![pxs_Var::new_f64(1.5), pxs_Var::new_f64(-2.0)])),
+            (s("alive"), pxs_Var::new_bool(id % 2 == 0)),
+            (s("data"), pxs_Var::new_bytes(vec![id as u8; id as usize])),
+            (pxs_Var::new_i64(7), pxs_Var::new_null()),
+        ])
+    }
+
+    /// Round trip `var` and return the encoding.
+    fn round_trip(var: &pxs_Var) -> Vec<u8> {
+        let bytes = msgpack::encode(var).unwrap();
+        let decoded = msgpack::decode(&bytes).unwrap();
+        assert_eq!(msgpack::encode(&decoded).unwrap(), bytes);
+        bytes
+    }
+
+    #[test]
+    fn test_encoding() {
+        // Length prefix and the smallest formats.
+        assert_eq!(round_trip(&pxs_Var::new_i64(1)), vec![0, 0, 0, 1, 0x01]);
+        assert_eq!(round_trip(&pxs_Var::new_i64(-1)), vec![0, 0, 0, 1, 0xff]);
+        assert_eq!(round_trip(&pxs_Var::new_i64(200)), vec![0, 0, 0, 2, 0xcc, 200]);
+        assert_eq!(round_trip(&pxs_Var::new_i64(-200)), vec![0, 0, 0, 3, 0xd1, 0xff, 0x38]);
+        assert_eq!(round_trip(&map(vec![(s("a"), pxs_Var::new_i64(1))])), vec![0, 0, 0, 4, 0x81, 0xa1, b'a', 0x01]);
+        assert_eq!(round_trip(&pxs_Var::new_u64(u64::MAX))[4], 0xcf);
+
+        // Bigger values.
+        for n in [0, 1, 15, 16, 31, 32, 255, 256, 65535, 65536] {
+            round_trip(&s(&"a".repeat(n)));
+            round_trip(&pxs_Var::new_bytes(vec![1; n]));
+            round_trip(&pxs_Var::new_list_with((0..n as i64).map(pxs_Var::new_i64).collect()));
+        }
+        for n in [i64::MIN, i32::MIN as i64 - 1, -33, -32, 127, 128, u32::MAX as i64 + 1, i64::MAX] {
+            let bytes = round_trip(&pxs_Var::new_i64(n));
+            assert_eq!(msgpack::decode(&bytes).unwrap().get_i64().unwrap(), n);
+        }
+
+        // Nested data, map keys are sorted.
+        let world = pxs_Var::new_list_with((0..20).map(entity).collect());
+        let bytes = round_trip(&world);
+        assert_eq!(msgpack::encode(&pxs_Var::new_list_with((0..20).map(entity).collect())).unwrap(), bytes);
+        let decoded = msgpack::decode(&bytes).unwrap();
+        let third = decoded.get_list().unwrap().get_item(3).unwrap();
+        assert_eq!(third.get_map().unwrap().get_item(&s("name")).unwrap().get_string().unwrap(), "xxx");
+    }
+
+    #[test]
+    fn test_invalid() {
+        let bytes = msgpack::encode(&entity(3)).unwrap();
+        assert!(msgpack::decode(&bytes[..bytes.len() - 1]).is_err());
+        assert!(msgpack::decode(&[0, 0, 0, 2, 0x01, 0x01]).is_err(), "Trailing bytes");
+        assert!(msgpack::decode(&[0, 0, 0, 3, 0xd4, 0x01, 0x01]).is_err(), "Extension type");
+        assert!(msgpack::decode(&[0, 0, 0, 5, 0xdd, 0xff, 0xff, 0xff, 0xff]).is_err(), "Huge list");
+        assert!(msgpack::decode(&[0, 0, 0, 3, 0xa2, 0xff, 0xfe]).is_err(), "Invalid UTF-8");
+
+        let mut deep = vec![0, 0, 0, 0];
+        deep.extend([0x91; 1000]);
+        deep.push(0xc0);
+        let len = (deep.len() as u32 - 4).to_be_bytes();
+        deep[..4].copy_from_slice(&len);
+        assert!(msgpack::decode(&deep).is_err(), "Too deep");
+    }
+
+    #[test]
+    fn run_test() {
+        pxs_initialize();
+
+        let var = entity(4).into_raw();
+        let bytes = pxs_tomsgpack(var);
+        assert!(pxs_Var::from_raw(bytes).is_bytes());
+        let bytes = pxs_tomsgpack(var);
+        let decoded = pxs_Var::from_raw(pxs_frommsgpack(bytes));
+        assert_eq!(decoded.get_map().unwrap().get_item(&s("id")).unwrap().get_i64().unwrap(), 4);
+
+        // Only data.
+        let func = pxs_Var::new_function(std::ptr::null_mut(), None).into_raw();
+        assert!(pxs_Var::from_raw(pxs_tomsgpack(func)).is_exception());
+        assert!(pxs_Var::from_raw(pxs_frommsgpack(var)).is_exception());
+
+        let _ = pxs_Var::from_raw(var);
+        let _ = pxs_Var::from_raw(bytes);
+        let _ = pxs_Var::from_raw(func);
+        pxs_finalize();
+    }
+}
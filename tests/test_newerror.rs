@@ -0,0 +1,108 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_newerror --no-default-features --features "lua,python,testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use std::ffi::CString;
+
+    use etffi::ptr_magic::PtrMagic;
+    use pixelscript::{
+        pxs_addfunc, pxs_addmod, pxs_call, pxs_finalize, pxs_geterrorkind, pxs_initialize, pxs_newerror,
+        pxs_varis_error,
+        shared::{
+            error::pxs_ErrorKind,
+            pxs_Runtime, utils,
+            var::{pxs_Var, pxs_VarT},
+        },
+    };
+
+    /// `parse(text)` fails with a `ValueError` for anything but digits.
+    extern "C" fn parse(args: pxs_VarT) -> pxs_VarT {
+        let args = unsafe { pxs_Var::from_borrow(args) }.get_list().unwrap();
+        let text = args.get_item(1).unwrap().get_string().unwrap();
+        match text.parse::<i64>() {
+            Ok(value) => pxs_Var::new_i64(value).into_raw(),
+            Err(_) => {
+                let msg = CString::new(format!("not a number: {text}")).unwrap();
+                pxs_newerror(msg.as_ptr(), pxs_ErrorKind::pxs_ValueError)
+            }
+        }
+    }
+
+    fn var_ptr(var: &pxs_Var) -> pxs_VarT {
+        var as *const pxs_Var as pxs_VarT
+    }
+
+    fn run(runtime: pxs_Runtime, script: &str) {
+        let err = utils::execute_code(script, "<newerror>", runtime);
+        assert!(err.is_null(), "Script error: {:#?}", err);
+    }
+
+    fn call(runtime: i64, method: &str, arg: &str) -> pxs_Var {
+        let runtime = pxs_Var::new_i64(runtime);
+        let method = CString::new(method).unwrap();
+        let args = pxs_Var::new_list_with(vec![pxs_Var::new_string(arg.to_string())]).into_raw();
+        pxs_Var::from_raw(pxs_call(&runtime as *const pxs_Var as pxs_VarT, method.as_ptr(), args))
+    }
+
+    #[test]
+    fn run_test() {
+        pxs_initialize();
+
+        let module = utils::create_module("nums");
+        let name = CString::new("parse").unwrap();
+        pxs_addfunc(module, name.as_ptr(), parse);
+        pxs_addmod(module);
+
+        run(
+            pxs_Runtime::pxs_Lua,
+            r#"
+local nums = require('nums')
+assert(nums.parse('12') == 12)
+local ok, err = pcall(nums.parse, 'x')
+assert(not ok and string.find(err, 'not a number: x', 1, true), err)
+function parse_lua(text) return nums.parse(text) end
+"#,
+        );
+
+        run(
+            pxs_Runtime::pxs_Python,
+            r#"
+import nums
+caught = None
+try:
+    nums.parse('x')
+except ValueError as e:
+    caught = str(e)
+assert caught is not None and 'not a number: x' in caught, caught
+
+def parse_py(text):
+    return nums.parse(text)
+"#,
+        );
+
+        // Failures propagate to the host.
+        for (runtime, method) in [(pxs_Runtime::pxs_Lua as i64, "parse_lua"), (pxs_Runtime::pxs_Python as i64, "parse_py")] {
+            let ok = call(runtime, method, "7");
+            assert!(!pxs_varis_error(var_ptr(&ok)));
+            let failed = call(runtime, method, "y");
+            assert!(pxs_varis_error(var_ptr(&failed)), "{:?}", failed);
+            assert!(failed.get_string().unwrap().contains("not a number: y"));
+        }
+
+        let error = CString::new("bad").unwrap();
+        let error = pxs_Var::from_raw(pxs_newerror(error.as_ptr(), pxs_ErrorKind::pxs_KeyError));
+        assert!(error.is_exception());
+        assert_eq!(pxs_geterrorkind(var_ptr(&error)), pxs_ErrorKind::pxs_KeyError);
+
+        pxs_finalize();
+    }
+}
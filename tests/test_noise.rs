@@ -0,0 +1,74 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_noise --no-default-features --features "lua,python,js,js_commonjs,include-core,testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use pixelscript::{
+        pxs_finalize, pxs_initialize, pxs_noiseinit,
+        shared::{pxs_Runtime, utils},
+    };
+
+    fn print_helper(lang: &str) {
+        println!("====================== {lang} ===================");
+    }
+
+    #[test]
+    fn run_test() {
+        println!();
+        pxs_initialize();
+        pxs_noiseinit();
+        utils::setup_pxs();
+
+        print_helper("PYTHON");
+        let script = r#"
+import pxs_noise
+
+def fails(call):
+    try:
+        call()
+    except Exception:
+        return True
+    return False
+
+# Huge coordinates still map to a lattice cell, NaN and infinity are errors.
+for f in [pxs_noise.perlin, pxs_noise.simplex, pxs_noise.value]:
+    assert -1 <= f(1e300, -9.3e18, 3) <= 1
+    for bad in [float('inf'), float('-inf'), float('nan')]:
+        assert fails(lambda: f(bad, 0))
+assert fails(lambda: pxs_noise.fbm(1e300, 0, {'lacunarity': 1e300}))
+"#;
+        let err = utils::execute_code(script, "<py>", pxs_Runtime::pxs_Python);
+        assert!(err.is_null(), "Python error: {:#?}", err);
+        let py = utils::eval_code("pxs_noise.perlin(1.5, 2.25, 42) + pxs_noise.fbm(0.3, 0.7, {'kind': 'simplex', 'seed': 7})", "", pxs_Runtime::pxs_Python);
+
+        print_helper("LUA");
+        let lua = utils::eval_code(
+            "local n = require('pxs_noise')\nreturn n.perlin(1.5, 2.25, 42) + n.fbm(0.3, 0.7, {kind = 'simplex', seed = 7})",
+            "",
+            pxs_Runtime::pxs_Lua,
+        );
+
+        print_helper("JS");
+        let js = utils::eval_code(
+            "const n = require('pxs_noise');\nn.perlin(1.5, 2.25, 42) + n.fbm(0.3, 0.7, {kind: 'simplex', seed: 7});",
+            "",
+            pxs_Runtime::pxs_JavaScript,
+        );
+
+        // Same seed, same output in every runtime.
+        let py = py.get_f64().expect("Python result is not a float");
+        assert!(py.abs() <= 2.0, "Noise out of range: {py}");
+        assert!(py == lua.get_f64().expect("Lua result is not a float"), "Python and Lua noise differ");
+        assert!(py == js.get_f64().expect("JS result is not a float"), "Python and JS noise differ");
+
+        pxs_finalize();
+    }
+}
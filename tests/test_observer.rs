@@ -0,0 +1,67 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_observer --no-default-features --features "lua,include-core,testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use std::{
+        ffi::{CStr, c_char},
+        sync::Mutex,
+    };
+
+    use pixelscript::{
+        pxs_finalize, pxs_initialize, pxs_set_object_observer,
+        shared::{object::pxs_ObjectEvent, pxs_Runtime, utils},
+    };
+
+    static EVENTS: Mutex<Vec<(pxs_ObjectEvent, String, i32)>> = Mutex::new(vec![]);
+
+    unsafe extern "C" fn observer(event: pxs_ObjectEvent, type_name: *const c_char, idx: i32) {
+        let type_name = unsafe { CStr::from_ptr(type_name) }.to_string_lossy().to_string();
+        EVENTS.lock().unwrap().push((event, type_name, idx));
+    }
+
+    fn events_of(event: pxs_ObjectEvent) -> Vec<i32> {
+        EVENTS
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|(e, name, _)| *e == event && name == "Per")
+            .map(|(_, _, idx)| *idx)
+            .collect()
+    }
+
+    #[test]
+    fn run_test() {
+        pxs_initialize();
+        pxs_set_object_observer(observer);
+        utils::setup_pxs();
+
+        let script = r#"
+local pxs = require('pxs')
+local p = pxs.Per('Jordan', 24)
+p = nil
+collectgarbage('collect')
+collectgarbage('collect')
+"#;
+        let err = utils::execute_code(script, "<lua>", pxs_Runtime::pxs_Lua);
+        assert!(err.is_null(), "Lua error: {:#?}", err);
+
+        let wrapped = events_of(pxs_ObjectEvent::pxs_ObjectWrapped);
+        assert_eq!(wrapped.len(), 1, "Expected one wrap, got: {:#?}", EVENTS.lock().unwrap());
+        assert!(wrapped[0] >= 0);
+
+        pxs_finalize();
+
+        // Released and finalized once, with the same idx.
+        assert_eq!(events_of(pxs_ObjectEvent::pxs_ObjectFinalized), wrapped);
+        assert!(events_of(pxs_ObjectEvent::pxs_ObjectReleased).len() <= 1);
+    }
+}
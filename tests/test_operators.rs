@@ -0,0 +1,180 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_operators --no-default-features --features "lua,python,testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use std::{
+        ffi::{CString, c_void},
+        ptr,
+    };
+
+    use etffi::ptr_magic::PtrMagic;
+    use pixelscript::{
+        pxs_addmod, pxs_addobject, pxs_finalize, pxs_funcopaque, pxs_gethost, pxs_initialize, pxs_newhost,
+        pxs_newobject, pxs_object_addoperator,
+        shared::{
+            object::pxs_Operator,
+            pxs_Runtime, utils,
+            var::{pxs_Var, pxs_VarT},
+        },
+    };
+
+    struct Vec2 {
+        x: f64,
+        y: f64,
+    }
+
+    impl PtrMagic for Vec2 {}
+
+    extern "C" fn free_vec(ptr: *mut c_void) {
+        let _ = Vec2::from_raw(ptr as *mut Vec2);
+    }
+
+    /// Number of components, the opaque of `pxs_Operator_Len`.
+    static DIMENSIONS: i64 = 2;
+
+    fn new_vec(x: f64, y: f64) -> pxs_VarT {
+        let name = CString::new("Vec2").unwrap();
+        let object = pxs_newobject(Vec2 { x, y }.into_raw() as *mut c_void, free_vec, name.as_ptr());
+        pxs_object_addoperator(object, pxs_Operator::pxs_Operator_Add, vec_add, ptr::null_mut());
+        pxs_object_addoperator(object, pxs_Operator::pxs_Operator_Sub, vec_sub, ptr::null_mut());
+        pxs_object_addoperator(object, pxs_Operator::pxs_Operator_Mul, vec_mul, ptr::null_mut());
+        pxs_object_addoperator(object, pxs_Operator::pxs_Operator_Eq, vec_eq, ptr::null_mut());
+        pxs_object_addoperator(object, pxs_Operator::pxs_Operator_Lt, vec_lt, ptr::null_mut());
+        pxs_object_addoperator(object, pxs_Operator::pxs_Operator_ToString, vec_tostring, ptr::null_mut());
+        pxs_object_addoperator(object, pxs_Operator::pxs_Operator_Index, vec_index, ptr::null_mut());
+        let dimensions = &DIMENSIONS as *const i64 as *mut c_void;
+        pxs_object_addoperator(object, pxs_Operator::pxs_Operator_Len, vec_len, dimensions);
+        pxs_newhost(object)
+    }
+
+    /// The vec at `idx` of `args`.
+    fn vec_at<'a>(args: pxs_VarT, idx: usize) -> &'a Vec2 {
+        let list = unsafe { pxs_Var::from_borrow(args) }.get_list().unwrap();
+        let runtime = list.get_item(0).unwrap() as *const pxs_Var as pxs_VarT;
+        let var = list.get_item(idx as i32).unwrap() as *const pxs_Var as pxs_VarT;
+        unsafe { &*(pxs_gethost(runtime, var) as *const Vec2) }
+    }
+
+    fn arg<'a>(args: pxs_VarT, idx: i32) -> &'a pxs_Var {
+        unsafe { pxs_Var::from_borrow(args) }.get_list().unwrap().get_item(idx).unwrap()
+    }
+
+    extern "C" fn vec_new(args: pxs_VarT) -> pxs_VarT {
+        new_vec(arg(args, 1).get_f64().unwrap(), arg(args, 2).get_f64().unwrap())
+    }
+
+    extern "C" fn vec_add(args: pxs_VarT) -> pxs_VarT {
+        let (a, b) = (vec_at(args, 1), vec_at(args, 2));
+        new_vec(a.x + b.x, a.y + b.y)
+    }
+
+    extern "C" fn vec_sub(args: pxs_VarT) -> pxs_VarT {
+        let (a, b) = (vec_at(args, 1), vec_at(args, 2));
+        new_vec(a.x - b.x, a.y - b.y)
+    }
+
+    extern "C" fn vec_mul(args: pxs_VarT) -> pxs_VarT {
+        let (a, n) = (vec_at(args, 1), arg(args, 2).get_f64().unwrap());
+        new_vec(a.x * n, a.y * n)
+    }
+
+    extern "C" fn vec_eq(args: pxs_VarT) -> pxs_VarT {
+        let (a, b) = (vec_at(args, 1), vec_at(args, 2));
+        pxs_Var::new_bool(a.x == b.x && a.y == b.y).into_raw()
+    }
+
+    extern "C" fn vec_lt(args: pxs_VarT) -> pxs_VarT {
+        let (a, b) = (vec_at(args, 1), vec_at(args, 2));
+        pxs_Var::new_bool(a.x.hypot(a.y) < b.x.hypot(b.y)).into_raw()
+    }
+
+    extern "C" fn vec_tostring(args: pxs_VarT) -> pxs_VarT {
+        let a = vec_at(args, 1);
+        pxs_Var::new_string(format!("Vec2({}, {})", a.x, a.y)).into_raw()
+    }
+
+    extern "C" fn vec_len(_args: pxs_VarT) -> pxs_VarT {
+        pxs_Var::new_i64(unsafe { *(pxs_funcopaque() as *const i64) }).into_raw()
+    }
+
+    extern "C" fn vec_index(args: pxs_VarT) -> pxs_VarT {
+        let a = vec_at(args, 1);
+        let key = arg(args, 2);
+        let value = if key.is_string() {
+            match key.get_string().unwrap().as_str() {
+                "x" => a.x,
+                "y" => a.y,
+                _ => return pxs_Var::new_null().into_raw(),
+            }
+        } else if matches!(key.get_i64(), Ok(1)) {
+            a.x
+        } else {
+            a.y
+        };
+        pxs_Var::new_f64(value).into_raw()
+    }
+
+    fn run(runtime: pxs_Runtime, script: &str) {
+        let err = utils::execute_code(script, "<operators>", runtime);
+        assert!(err.is_null(), "Script error: {:#?}", err);
+    }
+
+    #[test]
+    fn run_test() {
+        pxs_initialize();
+
+        let module = utils::create_module("vec");
+        let name = CString::new("Vec2").unwrap();
+        pxs_addobject(module, name.as_ptr(), vec_new);
+        pxs_addmod(module);
+
+        run(
+            pxs_Runtime::pxs_Lua,
+            r#"
+local vec = require('vec')
+local a = vec.Vec2(1.0, 2.0)
+local b = vec.Vec2(3.0, 4.0)
+local c = a + b
+assert(c[1] == 4.0 and c[2] == 6.0, tostring(c))
+assert(c.x == 4.0 and c.y == 6.0)
+assert((b - a).x == 2.0)
+assert((a * 2.0).y == 4.0)
+assert(a == vec.Vec2(1.0, 2.0))
+assert(a ~= b)
+assert(a < b and not (b < a))
+assert(tostring(c) == 'Vec2(4, 6)', tostring(c))
+assert(#a == 2)
+assert(c.z == nil)
+"#,
+        );
+
+        run(
+            pxs_Runtime::pxs_Python,
+            r#"
+import vec
+a = vec.Vec2(1.0, 2.0)
+b = vec.Vec2(3.0, 4.0)
+c = a + b
+assert c['x'] == 4.0 and c['y'] == 6.0
+assert (b - a)['x'] == 2.0
+assert (a * 2.0)['y'] == 4.0
+assert a == vec.Vec2(1.0, 2.0)
+assert not (a == b)
+assert a < b and not (b < a)
+assert str(c) == 'Vec2(4, 6)', str(c)
+assert len(a) == 2
+"#,
+        );
+
+        pxs_finalize();
+    }
+}
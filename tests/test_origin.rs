@@ -0,0 +1,74 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_origin --no-default-features --features "lua,testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use std::{ffi::CString, ptr};
+
+    use etffi::ptr_magic::PtrMagic;
+    use pixelscript::{
+        pxs_finalize, pxs_freevar, pxs_getint, pxs_initialize, pxs_listadd, pxs_newint, pxs_newlist, pxs_newcopy,
+        pxs_objectcall, pxs_varcall, pxs_varis, pxs_varorigin,
+        shared::{pxs_Runtime, utils, var::pxs_VarType},
+    };
+
+    #[test]
+    fn run_test() {
+        pxs_initialize();
+
+        let func = utils::eval_code("return function(a) return a * 2 end", "<lua>", pxs_Runtime::pxs_Lua).into_raw();
+        assert_eq!(pxs_varorigin(func), pxs_Runtime::pxs_Lua.into_i64() as i32);
+
+        // Copies keep the origin.
+        let copy = pxs_newcopy(func);
+        assert_eq!(pxs_varorigin(copy), pxs_varorigin(func));
+        pxs_freevar(copy);
+
+        // No runtime passed, the function's runtime is used.
+        let args = pxs_newlist();
+        pxs_listadd(args, pxs_newint(21));
+        let res = pxs_varcall(ptr::null_mut(), func, args);
+        assert_eq!(pxs_getint(res), 42);
+        pxs_freevar(res);
+        pxs_freevar(func);
+
+        let obj = utils::eval_code(
+            "return { double = function(self, a) return a * 2 end }",
+            "<lua>",
+            pxs_Runtime::pxs_Lua,
+        )
+        .into_raw();
+        let args = pxs_newlist();
+        pxs_listadd(args, pxs_newint(4));
+        let method = CString::new("double").unwrap();
+        let res = pxs_objectcall(ptr::null_mut(), obj, method.as_ptr(), args);
+        assert_eq!(pxs_getint(res), 8);
+        pxs_freevar(res);
+
+        // A Lua table used with Python.
+        let python = pxs_newint(pxs_Runtime::pxs_Python.into_i64());
+        let res = pxs_objectcall(python, obj, method.as_ptr(), pxs_newlist());
+        assert!(pxs_varis(res, pxs_VarType::pxs_Exception));
+        pxs_freevar(res);
+        pxs_freevar(python);
+        pxs_freevar(obj);
+
+        // Host vars have no origin, so the runtime is required.
+        let host = pxs_newint(1);
+        assert_eq!(pxs_varorigin(host), -1);
+        let res = pxs_varcall(ptr::null_mut(), host, pxs_newlist());
+        assert!(pxs_varis(res, pxs_VarType::pxs_Exception));
+        pxs_freevar(res);
+        pxs_freevar(host);
+
+        pxs_finalize();
+    }
+}
@@ -0,0 +1,101 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_path --no-default-features --features "lua,python,js,include-core,testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use pixelscript::{
+        pxs_finalize, pxs_initialize, pxs_path_setprovider, pxs_pathinit,
+        shared::{pxs_Runtime, utils},
+    };
+
+    fn print_helper(lang: &str) {
+        println!("====================== {lang} ===================");
+    }
+
+    /// 5x5 grid with a wall at x == 2 except for y == 4.
+    unsafe extern "C" fn grid_cost(x: i32, y: i32) -> f64 {
+        if x == 2 && y != 4 { -1.0 } else { 1.0 }
+    }
+
+    /// 7x3 grid with a cheap road at y == 0.
+    unsafe extern "C" fn road_cost(_x: i32, y: i32) -> f64 {
+        if y == 0 { 0.1 } else { 1.0 }
+    }
+
+    fn test_python() {
+        let script = r#"
+import pxs_path
+path = pxs_path.find({"x": 0, "y": 0}, [4, 0])
+assert len(path) == 13, path
+assert path[0]["x"] == 0 and path[-1]["x"] == 4, path
+assert pxs_path.find([0, 0], [2, 0]) is None
+"#;
+        let err = utils::execute_code(script, "<py>", pxs_Runtime::pxs_Python);
+        assert!(err.is_null(), "Python error: {:#?}", err);
+    }
+
+    fn test_lua() {
+        let script = r#"
+local pxs_path = require('pxs_path')
+local path = pxs_path.find({x = 0, y = 0}, {x = 4, y = 0}, {diagonal = true})
+assert(#path < 13, #path)
+for _, p in ipairs(path) do
+    assert(not (p.x == 2 and p.y ~= 4))
+end
+"#;
+        let err = utils::execute_code(script, "<lua>", pxs_Runtime::pxs_Lua);
+        assert!(err.is_null(), "Lua error: {:#?}", err);
+    }
+
+    /// Run on the `road_cost` grid.
+    fn test_min_cost() {
+        let script = r#"
+import pxs_path
+# Costs below the default min_cost overestimate, the straight path is found.
+path = pxs_path.find([0, 1], [6, 1])
+assert len(path) == 7, path
+# With the real min_cost the cheaper road is taken.
+for min_cost in [0.1, 0]:
+    path = pxs_path.find([0, 1], [6, 1], {'min_cost': min_cost})
+    assert len(path) == 9, path
+    assert all(p['y'] == 0 for p in path[1:-1]), path
+
+failed = False
+try:
+    pxs_path.find([0, 1], [6, 1], {'min_cost': -1})
+except Exception:
+    failed = True
+assert failed
+"#;
+        let err = utils::execute_code(script, "<py>", pxs_Runtime::pxs_Python);
+        assert!(err.is_null(), "Python error: {:#?}", err);
+    }
+
+    #[test]
+    fn run_test() {
+        println!();
+        pxs_initialize();
+        pxs_path_setprovider(5, 5, grid_cost);
+        pxs_pathinit();
+        utils::setup_pxs();
+
+        print_helper("PYTHON");
+        test_python();
+        print_helper("LUA");
+        test_lua();
+
+        print_helper("MIN COST");
+        pxs_path_setprovider(7, 3, road_cost);
+        test_min_cost();
+
+        pxs_finalize();
+    }
+}
@@ -0,0 +1,40 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_pressure --no-default-features --features "lua,testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use std::ffi::CString;
+
+    use etffi::ptr_magic::PtrMagic;
+
+    use pixelscript::{
+        pxs_exec, pxs_finalize, pxs_initialize, pxs_notify_memory_pressure,
+        shared::{pressure::pxs_MemoryPressure, pxs_Runtime, var::pxs_Var},
+    };
+
+    #[test]
+    fn run_test() {
+        pxs_initialize();
+
+        // Garbage for the collector.
+        let code = CString::new("local t = {} for i = 1, 100000 do t[i] = { i } end t = nil").unwrap();
+        let file = CString::new("<garbage>").unwrap();
+        assert!(!pxs_Var::from_raw(pxs_exec(pxs_Runtime::pxs_Lua, code.as_ptr(), file.as_ptr())).is_exception());
+        assert!(pxs_notify_memory_pressure(pxs_MemoryPressure::pxs_MemoryModerate) > 0);
+
+        // Critical pressure still leaves a working runtime.
+        pxs_notify_memory_pressure(pxs_MemoryPressure::pxs_MemoryCritical);
+        let code = CString::new("local x = 1 + 1").unwrap();
+        assert!(!pxs_Var::from_raw(pxs_exec(pxs_Runtime::pxs_Lua, code.as_ptr(), file.as_ptr())).is_exception());
+
+        pxs_finalize();
+    }
+}
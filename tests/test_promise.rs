@@ -0,0 +1,107 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_promise --no-default-features --features "lua,testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use std::{
+        ffi::CString,
+        sync::atomic::{AtomicI64, Ordering},
+    };
+
+    use etffi::ptr_magic::PtrMagic;
+    use pixelscript::{
+        pxs_addfunc, pxs_addmod, pxs_coroutine_create, pxs_coroutine_free, pxs_coroutine_resume, pxs_coroutine_status,
+        pxs_eval, pxs_exec, pxs_finalize, pxs_initialize, pxs_newmod, pxs_newpromise, pxs_promise_resolve,
+        pxs_promise_var,
+        shared::{
+            coroutine::pxs_CoroutineStatus,
+            pxs_Runtime,
+            var::{pxs_Var, pxs_VarT},
+        },
+    };
+
+    /// Id of the last promise returned by `net.fetch`.
+    static LAST: AtomicI64 = AtomicI64::new(-1);
+
+    /// `net.fetch()` starts "IO" and returns a promise.
+    extern "C" fn fetch(_args: pxs_VarT) -> pxs_VarT {
+        let id = pxs_newpromise();
+        LAST.store(id, Ordering::SeqCst);
+        pxs_promise_var(id)
+    }
+
+    fn run(code: &str) {
+        let code = CString::new(code).unwrap();
+        let name = CString::new("<promise>").unwrap();
+        let res = pxs_exec(pxs_Runtime::pxs_Lua, code.as_ptr(), name.as_ptr());
+        assert!(res.is_null(), "{:?}", pxs_Var::from_raw(res).get_string());
+    }
+
+    fn resolve(value: pxs_Var) -> bool {
+        pxs_promise_resolve(LAST.load(Ordering::SeqCst), value.into_raw())
+    }
+
+    #[test]
+    fn run_test() {
+        pxs_initialize();
+
+        let name = CString::new("net").unwrap();
+        let module = pxs_newmod(name.as_ptr());
+        let func_name = CString::new("fetch").unwrap();
+        pxs_addfunc(module, func_name.as_ptr(), fetch);
+        pxs_addmod(module);
+
+        // Callbacks run once the host resolves.
+        run("p = require('net').fetch() p:then(function(res, err) got = res end) assert(not p:done())");
+        assert!(resolve(pxs_Var::new_string("data".to_string())));
+        assert!(!resolve(pxs_Var::new_string("again".to_string())));
+        run("assert(got == 'data') assert(p:done() and p:result() == 'data' and p:error() == nil)");
+        run("p:then(function(res) late = res end) assert(late == 'data')");
+
+        // Coroutines await a yielded promise.
+        run(
+            r#"
+function task()
+    local net = require('net')
+    local data = coroutine.yield(net.fetch())
+    result = data
+    local _, err = coroutine.yield(net.fetch())
+    failure = err
+end
+"#,
+        );
+        let rt = pxs_Runtime::pxs_Lua.into_var();
+        let code = CString::new("return task").unwrap();
+        let task = pxs_Var::from_raw(pxs_eval(code.as_ptr(), pxs_Runtime::pxs_Lua));
+        let co = pxs_coroutine_create(
+            &rt as *const pxs_Var as pxs_VarT,
+            &task as *const pxs_Var as pxs_VarT,
+            pxs_Var::new_list().into_raw(),
+        );
+        let _ = pxs_Var::from_raw(pxs_coroutine_resume(co, std::ptr::null_mut()));
+        assert_eq!(pxs_coroutine_status(co), pxs_CoroutineStatus::pxs_CoroutineSuspended);
+
+        assert!(resolve(pxs_Var::new_string("first".to_string())));
+        run("assert(result == 'first')");
+        assert_eq!(pxs_coroutine_status(co), pxs_CoroutineStatus::pxs_CoroutineSuspended);
+
+        assert!(resolve(pxs_Var::new_exception("timed out")));
+        run("assert(failure:find('timed out'))");
+        assert_eq!(pxs_coroutine_status(co), pxs_CoroutineStatus::pxs_CoroutineDead);
+        pxs_coroutine_free(co);
+
+        // Unknown promises.
+        assert!(!pxs_promise_resolve(12345, pxs_Var::new_null().into_raw()));
+        assert!(pxs_Var::from_raw(pxs_promise_var(12345)).is_exception());
+
+        pxs_finalize();
+    }
+}
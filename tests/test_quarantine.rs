@@ -0,0 +1,79 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_quarantine --no-default-features --features "lua,testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use std::{
+        ffi::{CStr, CString, c_char},
+        ptr,
+        sync::atomic::{AtomicU32, Ordering},
+    };
+
+    use pixelscript::{
+        pxs_finalize, pxs_freevar, pxs_initialize, pxs_mod_isquarantined, pxs_mod_release, pxs_mod_report,
+        pxs_modcall, pxs_newexception, pxs_newint, pxs_newlist, pxs_quarantine_setcallback,
+        pxs_quarantine_setthreshold, pxs_varis,
+        shared::var::pxs_VarType,
+    };
+
+    static QUARANTINED: AtomicU32 = AtomicU32::new(0);
+
+    unsafe extern "C" fn on_quarantine(mod_name: *const c_char, error: *const c_char) {
+        let mod_name = unsafe { CStr::from_ptr(mod_name) }.to_str().unwrap();
+        let error = unsafe { CStr::from_ptr(error) }.to_str().unwrap();
+        assert_eq!(mod_name, "broken_mod");
+        assert_eq!(error, "boom");
+        QUARANTINED.fetch_add(1, Ordering::SeqCst);
+    }
+
+    fn report_error(mod_name: &CString) -> bool {
+        let error = CString::new("boom").unwrap();
+        let var = pxs_newexception(error.as_ptr());
+        let res = pxs_mod_report(mod_name.as_ptr(), var);
+        pxs_freevar(var);
+        res
+    }
+
+    #[test]
+    fn run_test() {
+        pxs_initialize();
+        pxs_quarantine_setthreshold(3);
+        pxs_quarantine_setcallback(on_quarantine);
+
+        let broken = CString::new("broken_mod").unwrap();
+
+        // A success in between resets the count.
+        assert!(!report_error(&broken));
+        assert!(!report_error(&broken));
+        assert!(!pxs_mod_report(broken.as_ptr(), ptr::null_mut()));
+        assert!(!report_error(&broken));
+        assert!(!report_error(&broken));
+        assert!(!pxs_mod_isquarantined(broken.as_ptr()));
+
+        assert!(report_error(&broken));
+        assert!(pxs_mod_isquarantined(broken.as_ptr()));
+        // Only notified once.
+        assert!(report_error(&broken));
+        assert_eq!(QUARANTINED.load(Ordering::SeqCst), 1);
+
+        // Quarantined mods are not called.
+        let rt = pxs_newint(0);
+        let res = pxs_modcall(broken.as_ptr(), rt, ptr::null_mut(), pxs_newlist());
+        assert!(pxs_varis(res, pxs_VarType::pxs_Null));
+        pxs_freevar(res);
+        pxs_freevar(rt);
+
+        pxs_mod_release(broken.as_ptr());
+        assert!(!pxs_mod_isquarantined(broken.as_ptr()));
+
+        pxs_finalize();
+    }
+}
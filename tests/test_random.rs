@@ -0,0 +1,133 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_random --no-default-features --features "lua,python,js,include-core,testing" -- --nocapture --test-threads=1
+
+#[cfg(all(test, feature = "pxs_math", feature = "pxs_random"))]
+#[allow(unused)]
+mod tests {
+    use std::ffi::c_void;
+
+    use etffi::ptr_magic::PtrMagic;
+    use pixelscript::{
+        pxs_addmod, pxs_finalize, pxs_initialize, pxs_mathinit, pxs_newhost, pxs_newobject, pxs_random_setseed,
+        pxs_randominit,
+        shared::{pxs_Runtime, utils, var::pxs_VarT},
+    };
+
+    /// A host object of a other type.
+    struct Other {
+        id: i64,
+    }
+
+    impl PtrMagic for Other {}
+
+    extern "C" fn free_other(ptr: *mut c_void) {
+        let _ = Other::from_raw(ptr as *mut Other);
+    }
+
+    /// `host.other()`
+    extern "C" fn new_other(_args: pxs_VarT) -> pxs_VarT {
+        let ptr = Other { id: 1 }.into_raw() as *mut c_void;
+        pxs_newhost(pxs_newobject(ptr, free_other, c"Other".as_ptr()))
+    }
+
+    fn print_helper(lang: &str) {
+        println!("====================== {lang} ===================");
+    }
+
+    fn test_python() {
+        let script = r#"
+import pxs_math
+import pxs_random
+assert pxs_math.lerp(0, 10, 0.25) == 2.5
+assert pxs_math.inverse_lerp(0, 10, 2.5) == 0.25
+assert pxs_math.clamp(15, 0, 10) == 10
+assert pxs_math.remap(5, 0, 10, 100, 200) == 150.0
+assert pxs_math.smoothstep(0, 1, 0.5) == 0.5
+assert pxs_math.sign(-3) == -1
+# Half away from zero, Python's round(2.5) would give 2.
+assert pxs_math.round(2.5) == 3 and pxs_math.round(-2.5) == -3
+assert pxs_math.round(1.005, 1) == 1.0
+assert pxs_math.wrap(-1, 0, 360) == 359
+
+# Seeded from the host.
+assert pxs_random.int(1, 100) == 58
+pxs_random.seed(1)
+assert pxs_random.int(1, 100) == 58
+r = pxs_random.new(7)
+r2 = pxs_random.new(7)
+assert [r.int(0, 1000) for _ in range(5)] == [r2.int(0, 1000) for _ in range(5)]
+assert 0 <= r.random() < 1
+assert r.choice(['a']) == 'a'
+assert sorted(r.shuffle([3, 1, 2])) == [1, 2, 3]
+"#;
+        let err = utils::execute_code(script, "<py>", pxs_Runtime::pxs_Python);
+        assert!(err.is_null(), "Python error: {:#?}", err);
+    }
+
+    fn test_lua() {
+        let script = r#"
+local math2 = require('pxs_math')
+local random = require('pxs_random')
+assert(math2.clamp(-5, 0, 10) == 0)
+assert(math2.round(2.5) == 3)
+assert(not pcall(math2.clamp, 1, 10, 0))
+
+-- Same seed, same numbers as Python.
+random.seed(1)
+assert(random.int(1, 100) == 58)
+local r = random.new(7)
+local r2 = random.new(7)
+for _ = 1, 5 do assert(r:int(0, 1000) == r2:int(0, 1000)) end
+local f = random.float(2, 3)
+assert(f >= 2 and f < 3)
+assert(not pcall(random.choice, {}))
+assert(not pcall(random.int, 5, 1))
+
+-- Methods only take `Random` objects.
+assert(not pcall(r.int, require('host').other(), 0, 1))
+"#;
+        let err = utils::execute_code(script, "<lua>", pxs_Runtime::pxs_Lua);
+        assert!(err.is_null(), "Lua error: {:#?}", err);
+    }
+
+    fn test_js() {
+        let script = r#"
+import { seed, int } from 'pxs_random';
+import { lerp } from 'pxs_math';
+seed(1);
+if (int(1, 100) !== 58) throw new Error("int");
+if (lerp(0, 4, 0.5) !== 2) throw new Error("lerp");
+"#;
+        let err = utils::execute_code(script, "<js>", pxs_Runtime::pxs_JavaScript);
+        assert!(err.is_null(), "JS error: {:#?}", err);
+    }
+
+    #[test]
+    fn run_test() {
+        println!();
+        pxs_initialize();
+        pxs_mathinit();
+        pxs_randominit();
+        pxs_random_setseed(1);
+        utils::setup_pxs();
+        let host = utils::create_module("host");
+        utils::add_function(host, "other", new_other);
+        pxs_addmod(host);
+
+        print_helper("PYTHON");
+        test_python();
+        print_helper("LUA");
+        test_lua();
+        print_helper("JS");
+        test_js();
+
+        pxs_finalize();
+    }
+}
@@ -0,0 +1,90 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_registry --no-default-features --features "lua,testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use std::ffi::{CString, c_void};
+
+    use etffi::{own_string, ptr_magic::PtrMagic};
+    use pixelscript::{
+        pxs_addmod, pxs_addobject, pxs_finalize, pxs_freevar, pxs_getstring, pxs_initialize, pxs_listget,
+        pxs_listlen, pxs_mapget, pxs_newfloat, pxs_newhost, pxs_newnull, pxs_newobject, pxs_newstring,
+        pxs_object_addfunc, pxs_object_addprop, pxs_registry_export,
+        shared::{utils, var::pxs_VarT},
+    };
+
+    struct Vector;
+
+    impl PtrMagic for Vector {}
+
+    extern "C" fn free_vector(ptr: *mut c_void) {
+        let _ = Vector::from_raw(ptr as *mut Vector);
+    }
+
+    extern "C" fn noop(_args: pxs_VarT) -> pxs_VarT {
+        pxs_newnull()
+    }
+
+    /// Get a field from a map.
+    fn field(map: pxs_VarT, key: &str) -> pxs_VarT {
+        let key = CString::new(key).unwrap();
+        let key = pxs_newstring(key.as_ptr());
+        let res = pxs_mapget(map, key);
+        pxs_freevar(key);
+        res
+    }
+
+    fn string(var: pxs_VarT) -> String {
+        own_string!(pxs_getstring(var))
+    }
+
+    #[test]
+    fn run_test() {
+        pxs_initialize();
+
+        let math = utils::create_module("math");
+        utils::add_function(math, "add", noop);
+        let name = CString::new("Vector").unwrap();
+        pxs_addobject(math, name.as_ptr(), noop);
+        utils::add_variable(math, "PI", pxs_newfloat(3.14));
+        pxs_addmod(math);
+
+        let object = pxs_newobject(Vector.into_raw() as *mut c_void, free_vector, name.as_ptr());
+        let method = CString::new("length").unwrap();
+        pxs_object_addfunc(object, method.as_ptr(), noop);
+        let prop = CString::new("x").unwrap();
+        pxs_object_addprop(object, prop.as_ptr(), noop);
+        let host = pxs_newhost(object);
+
+        let export = pxs_registry_export();
+
+        let modules = field(export, "modules");
+        assert_eq!(pxs_listlen(modules), 1);
+        let math = pxs_listget(modules, 0);
+        assert_eq!(string(field(math, "name")), "math");
+        assert_eq!(string(pxs_listget(field(math, "functions"), 0)), "add");
+        assert_eq!(string(pxs_listget(field(math, "objects"), 0)), "Vector");
+        let pi = pxs_listget(field(math, "constants"), 0);
+        assert_eq!(string(field(pi, "name")), "PI");
+        assert_eq!(string(field(pi, "type")), "Float64");
+
+        let objects = field(export, "objects");
+        assert_eq!(pxs_listlen(objects), 1);
+        let vector = pxs_listget(objects, 0);
+        assert_eq!(string(field(vector, "name")), "Vector");
+        assert_eq!(string(pxs_listget(field(vector, "methods"), 0)), "length");
+        assert_eq!(string(pxs_listget(field(vector, "properties"), 0)), "x");
+
+        pxs_freevar(export);
+        pxs_freevar(host);
+        pxs_finalize();
+    }
+}
@@ -0,0 +1,96 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_reloadmod --no-default-features --features "lua,include-core,testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use pixelscript::{
+        pxs_addmod, pxs_finalize, pxs_initialize, pxs_newint, pxs_reloadmod,
+        rust_api::Object,
+        shared::{module::pxs_Module, pxs_Runtime, utils, var::pxs_VarT},
+    };
+
+    extern "C" fn version_1(_args: pxs_VarT) -> pxs_VarT {
+        pxs_newint(1)
+    }
+
+    extern "C" fn version_2(_args: pxs_VarT) -> pxs_VarT {
+        pxs_newint(2)
+    }
+
+    extern "C" fn new_thing_1(_args: pxs_VarT) -> pxs_VarT {
+        let mut object = Object::new("Thing", ());
+        object.add_method("old", version_1);
+        object.into_value().into_raw()
+    }
+
+    extern "C" fn new_thing_2(_args: pxs_VarT) -> pxs_VarT {
+        let mut object = Object::new("Thing", ());
+        object.add_method("new", version_2);
+        object.into_value().into_raw()
+    }
+
+    fn plugin(version: pixelscript::shared::func::pxs_Func) -> *mut pxs_Module {
+        let module = utils::create_module("plugin");
+        utils::add_function(module, "version", version);
+        module
+    }
+
+    fn things(constructor: pixelscript::shared::func::pxs_Func) -> *mut pxs_Module {
+        let module = utils::create_module("things");
+        utils::add_function(module, "Thing", constructor);
+        module
+    }
+
+    /// Objects made after a reload do not get the methods of the old version.
+    fn reload_objects() {
+        pxs_addmod(things(new_thing_1));
+        run(r#"
+old_thing = require('things').Thing()
+assert(old_thing:old() == 1)
+"#);
+
+        assert!(pxs_reloadmod(things(new_thing_2)));
+        run(r#"
+local thing = require('things').Thing()
+assert(thing:new() == 2)
+assert(thing.old == nil)
+"#);
+    }
+
+    fn run(script: &str) {
+        let err = utils::execute_code(script, "<lua>", pxs_Runtime::pxs_Lua);
+        assert!(err.is_null(), "Lua error: {:#?}", err);
+    }
+
+    #[test]
+    fn run_test() {
+        pxs_initialize();
+        // Nothing to replace yet.
+        assert!(!pxs_reloadmod(plugin(version_1)));
+
+        run(r#"
+old = require('plugin')
+assert(old.version() == 1)
+"#);
+
+        assert!(pxs_reloadmod(plugin(version_2)));
+
+        run(r#"
+assert(require('plugin').version() == 2)
+-- Released functions return nil instead of calling the new version.
+assert(old.version() == nil)
+"#);
+
+        reload_objects();
+
+        pxs_finalize();
+    }
+}
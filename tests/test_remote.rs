@@ -0,0 +1,80 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_remote --no-default-features --features "lua,remote-console,testing" -- --nocapture --test-threads=1
+
+#[cfg(all(test, feature = "remote-console"))]
+#[allow(unused)]
+mod tests {
+    use std::{
+        ffi::CString,
+        io::{BufRead, BufReader, Write},
+        net::TcpStream,
+        thread,
+        time::Duration,
+    };
+
+    use pixelscript::{pxs_finalize, pxs_initialize, pxs_remote_listen, pxs_remote_poll, pxs_remote_stop};
+
+    const PORT: u16 = 47913;
+
+    /// Connect, authenticate, and send each line. Returns the answers.
+    fn client(token: &str, lines: Vec<&str>) -> Vec<String> {
+        let stream = TcpStream::connect(("127.0.0.1", PORT)).unwrap();
+        let mut writer = stream.try_clone().unwrap();
+        let mut reader = BufReader::new(stream);
+        let mut answers = vec![];
+
+        writeln!(writer, "{token}").unwrap();
+        let mut answer = String::new();
+        if reader.read_line(&mut answer).unwrap() == 0 {
+            return answers;
+        }
+        answers.push(answer.trim_end().to_string());
+
+        for line in lines {
+            writeln!(writer, "{line}").unwrap();
+            let mut answer = String::new();
+            reader.read_line(&mut answer).unwrap();
+            answers.push(answer.trim_end().to_string());
+        }
+        answers
+    }
+
+    /// Run a client on another thread while polling on this one.
+    fn run_client(token: &'static str, lines: Vec<&'static str>) -> Vec<String> {
+        let handle = thread::spawn(move || client(token, lines));
+        while !handle.is_finished() {
+            pxs_remote_poll();
+            thread::sleep(Duration::from_millis(10));
+        }
+        handle.join().unwrap()
+    }
+
+    #[test]
+    fn run_test() {
+        pxs_initialize();
+
+        let token = CString::new("secret").unwrap();
+        assert!(pxs_remote_listen(PORT, token.as_ptr()));
+        // Only one console at a time.
+        assert!(!pxs_remote_listen(PORT, token.as_ptr()));
+
+        // Bad token closes the connection.
+        assert!(run_client("wrong", vec![]).is_empty());
+
+        let answers = run_client("secret", vec!["ruby puts 1", "lua 1 + 2", "lua error('boom')"]);
+        assert_eq!(answers[0], "ok");
+        assert!(answers[1].starts_with("error Unknown or disabled runtime"), "{}", answers[1]);
+        assert_eq!(answers[2], "ok 3");
+        assert!(answers[3].starts_with("error"), "{}", answers[3]);
+
+        pxs_remote_stop();
+        pxs_finalize();
+    }
+}
@@ -0,0 +1,68 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_removemod --no-default-features --features "lua,include-core,testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use std::ffi::CString;
+
+    use pixelscript::{
+        pxs_addmod, pxs_finalize, pxs_funchandle, pxs_initialize, pxs_newint, pxs_removemod,
+        shared::{pxs_Runtime, utils, var::pxs_VarT},
+    };
+
+    extern "C" fn one(_args: pxs_VarT) -> pxs_VarT {
+        pxs_newint(1)
+    }
+
+    fn handle(module: &str, name: &str) -> i32 {
+        let module = CString::new(module).unwrap();
+        let name = CString::new(name).unwrap();
+        pxs_funchandle(module.as_ptr(), name.as_ptr())
+    }
+
+    #[test]
+    fn run_test() {
+        pxs_initialize();
+        let plugin = utils::create_module("plugin");
+        utils::add_function(plugin, "one", one);
+        let child = utils::create_module("child");
+        utils::add_function(child, "one", one);
+        pixelscript::pxs_add_submod(plugin, child);
+        pxs_addmod(plugin);
+
+        let script = r#"
+local plugin = require('plugin')
+assert(plugin.one() == 1)
+assert(require('plugin.child').one() == 1)
+"#;
+        let err = utils::execute_code(script, "<lua>", pxs_Runtime::pxs_Lua);
+        assert!(err.is_null(), "Lua error: {:#?}", err);
+        assert!(handle("plugin", "one") >= 0);
+        assert!(handle("child", "one") >= 0);
+
+        let name = CString::new("plugin").unwrap();
+        assert!(pxs_removemod(name.as_ptr()));
+        // Already removed.
+        assert!(!pxs_removemod(name.as_ptr()));
+
+        assert_eq!(handle("plugin", "one"), -1);
+        assert_eq!(handle("child", "one"), -1);
+
+        let script = r#"
+assert(not pcall(require, 'plugin'))
+assert(not pcall(require, 'plugin.child'))
+"#;
+        let err = utils::execute_code(script, "<lua>", pxs_Runtime::pxs_Lua);
+        assert!(err.is_null(), "Lua error: {:#?}", err);
+
+        pxs_finalize();
+    }
+}
@@ -0,0 +1,138 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_resolver --no-default-features --features "lua,python,js,testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use std::{
+        ffi::{CStr, c_char},
+        ptr,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    use etffi::ptr_magic::PtrMagic;
+    use pixelscript::{
+        pxs_add_module_resolver, pxs_clear_module_resolvers, pxs_finalize, pxs_initialize, pxs_set_filereader,
+        shared::{
+            pxs_ResolvedModule, pxs_Runtime, utils,
+            var::{pxs_Var, pxs_VarT},
+        },
+    };
+
+    /// Names asked of the counting resolver.
+    static ASKED: AtomicUsize = AtomicUsize::new(0);
+
+    fn print_helper(lang: &str) {
+        println!("====================== {lang} ===================");
+    }
+
+    fn string(value: &str) -> pxs_VarT {
+        pxs_Var::new_string(value.to_string()).into_raw()
+    }
+
+    /// Has nothing, counts how often it is asked.
+    unsafe extern "C" fn counting(_name: *const c_char, _runtime: pxs_Runtime) -> pxs_ResolvedModule {
+        ASKED.fetch_add(1, Ordering::Relaxed);
+        pxs_ResolvedModule { source: ptr::null_mut(), path: ptr::null_mut() }
+    }
+
+    /// A archive with a `pak.util` module per language.
+    unsafe extern "C" fn archive(name: *const c_char, runtime: pxs_Runtime) -> pxs_ResolvedModule {
+        let name = unsafe { CStr::from_ptr(name) }.to_string_lossy().to_string();
+        let source = match (runtime, name.as_str()) {
+            (pxs_Runtime::pxs_Lua, "pak.util") => "return { from = 'pak', src = debug.getinfo(1, 'S').source }",
+            // Python imports the package first.
+            (pxs_Runtime::pxs_Python, "pak") => "",
+            (pxs_Runtime::pxs_Python, "pak.util") => "FROM = 'pak'",
+            (pxs_Runtime::pxs_JavaScript, "pak/util.js") => "export const FROM = 'pak';",
+            // Shadows the file reader.
+            (_, "shadowed") | (_, "shadowed.js") => "return 'pak'",
+            _ => return pxs_ResolvedModule { source: ptr::null_mut(), path: ptr::null_mut() },
+        };
+        pxs_ResolvedModule { source: string(source), path: string(&format!("pak://{name}")) }
+    }
+
+    /// The disk, after the resolvers.
+    unsafe extern "C" fn load_file(path: *const c_char) -> pxs_VarT {
+        let path = unsafe { CStr::from_ptr(path) }.to_string_lossy().to_string();
+        match path.as_str() {
+            "disk" => string("return { from = 'disk' }"),
+            "disk.py" => string("FROM = 'disk'"),
+            "shadowed" => string("return 'disk'"),
+            _ => ptr::null_mut(),
+        }
+    }
+
+    fn test_python() {
+        let script = r#"
+import pak.util
+import disk
+assert pak.util.FROM == 'pak'
+assert disk.FROM == 'disk'
+try:
+    import nowhere
+    assert False
+except ImportError:
+    pass
+"#;
+        let err = utils::execute_code(script, "<py>", pxs_Runtime::pxs_Python);
+        assert!(err.is_null(), "Python error: {:#?}", err);
+    }
+
+    fn test_lua() {
+        let script = r#"
+local util = require('pak.util')
+assert(util.from == 'pak')
+-- The canonical path is the chunk name.
+assert(util.src == 'pak://pak.util', util.src)
+assert(require('disk').from == 'disk')
+assert(require('shadowed') == 'pak')
+assert(not pcall(require, 'nowhere'))
+"#;
+        let err = utils::execute_code(script, "<lua>", pxs_Runtime::pxs_Lua);
+        assert!(err.is_null(), "Lua error: {:#?}", err);
+    }
+
+    fn test_js() {
+        let script = r#"
+import { FROM } from 'pak/util.js';
+if (FROM !== 'pak') throw new Error("pak");
+"#;
+        let err = utils::execute_code(script, "<js>", pxs_Runtime::pxs_JavaScript);
+        assert!(err.is_null(), "JS error: {:#?}", err);
+    }
+
+    #[test]
+    fn run_test() {
+        println!();
+        pxs_initialize();
+        pxs_set_filereader(load_file);
+        pxs_add_module_resolver(counting);
+        pxs_add_module_resolver(archive);
+
+        print_helper("PYTHON");
+        test_python();
+        print_helper("LUA");
+        test_lua();
+        print_helper("JS");
+        test_js();
+        // Asked first, even for modules found by the next one.
+        assert!(ASKED.load(Ordering::Relaxed) > 0);
+
+        // Only the file reader is left.
+        pxs_clear_module_resolvers();
+        let err = utils::execute_code("assert(require('disk').from == 'disk')", "<lua>", pxs_Runtime::pxs_Lua);
+        assert!(err.is_null());
+        let err = utils::execute_code("require('pak.util')", "<lua>", pxs_Runtime::pxs_Lua);
+        assert!(!err.is_null());
+
+        pxs_finalize();
+    }
+}
@@ -0,0 +1,68 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_retain --no-default-features --features "lua,testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use std::{ffi::CString, ptr};
+
+    use etffi::ptr_magic::PtrMagic;
+
+    use pixelscript::{
+        pxs_exec, pxs_finalize, pxs_freevar, pxs_garbagecollect, pxs_getint, pxs_initialize, pxs_newint, pxs_newlist,
+        pxs_objectget, pxs_refcount, pxs_release, pxs_retain,
+        shared::{pxs_Runtime, utils},
+    };
+
+    fn counts() {
+        let var = pxs_newint(7);
+        assert_eq!(pxs_refcount(var), 1);
+        assert_eq!(pxs_retain(var), 2);
+        assert_eq!(pxs_retain(var), 3);
+
+        // Freeing drops a reference, the var is still alive.
+        pxs_freevar(var);
+        assert_eq!(pxs_refcount(var), 2);
+        assert_eq!(pxs_getint(var), 7);
+        assert_eq!(pxs_release(var), 1);
+        assert_eq!(pxs_getint(var), 7);
+        assert_eq!(pxs_release(var), 0);
+
+        assert_eq!(pxs_retain(ptr::null_mut()), 0);
+        assert_eq!(pxs_release(ptr::null_mut()), 0);
+    }
+
+    fn lua_table() {
+        let table = utils::eval_code("return { hp = 10 }", "<lua>", pxs_Runtime::pxs_Lua).into_raw();
+        pxs_retain(table);
+        pxs_freevar(table);
+
+        // The table survives collections while retained.
+        let code = CString::new("collectgarbage()").unwrap();
+        let file = CString::new("<gc>").unwrap();
+        pxs_freevar(pxs_exec(pxs_Runtime::pxs_Lua, code.as_ptr(), file.as_ptr()));
+        pxs_garbagecollect();
+        let key = CString::new("hp").unwrap();
+        let hp = pxs_objectget(ptr::null_mut(), table, key.as_ptr());
+        assert_eq!(pxs_getint(hp), 10);
+        pxs_freevar(hp);
+        assert_eq!(pxs_release(table), 0);
+    }
+
+    #[test]
+    fn run_test() {
+        pxs_initialize();
+
+        counts();
+        lua_table();
+
+        pxs_finalize();
+    }
+}
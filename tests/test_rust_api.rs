@@ -0,0 +1,140 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_rust_api --no-default-features --features "lua,testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use std::{
+        ffi::CString,
+        sync::{
+            Arc,
+            atomic::{AtomicI64, Ordering},
+        },
+    };
+
+    use pixelscript::{
+        pxs_removemod,
+        rust_api::{self, Module, Object, Runtime, Value},
+        shared::var::{pxs_VarT, pxs_VarType},
+    };
+
+    struct Counter {
+        count: i64,
+    }
+
+    extern "C" fn add(args: pxs_VarT) -> pxs_VarT {
+        let args = unsafe { rust_api::args(args) };
+        let a = args[1].as_i64().unwrap_or(0);
+        let b = args[2].as_i64().unwrap_or(0);
+        Value::from(a + b).into_raw()
+    }
+
+    extern "C" fn counter_bump(args: pxs_VarT) -> pxs_VarT {
+        let args = unsafe { rust_api::args(args) };
+        let Some(counter) = (unsafe { Object::data::<Counter>(&args[0], &args[1]) }) else {
+            return Value::exception("Not a Counter").into_raw();
+        };
+        counter.count += 1;
+        Value::from(counter.count).into_raw()
+    }
+
+    extern "C" fn new_counter(_args: pxs_VarT) -> pxs_VarT {
+        let mut object = Object::new("Counter", Counter { count: 0 });
+        object.add_method("bump", counter_bump);
+        object.into_value().into_raw()
+    }
+
+    #[test]
+    fn test_values() {
+        assert_eq!(Value::from(3).as_i64(), Some(3));
+        assert_eq!(Value::from(1.5).as_f64(), Some(1.5));
+        assert_eq!(Value::from(true).as_bool(), Some(true));
+        assert_eq!(Value::from("hi").as_string(), Some("hi".to_string()));
+        assert!(Value::from(()).is_null());
+        assert!(Value::from(None::<i64>).is_null());
+        assert_eq!(Value::from(3).as_string(), None);
+
+        let mut list = Value::from(vec![1, 2]);
+        list.push("three");
+        let items = list.items().unwrap();
+        assert_eq!(items.len(), 3);
+        assert_eq!(items[1].as_i64(), Some(2));
+        assert_eq!(list.get(2).unwrap().var_type(), pxs_VarType::pxs_String);
+        assert!(list.get(3).is_none());
+
+        let mut map = Value::map();
+        map.insert("hp", 10);
+        assert_eq!(map.get_key("hp").and_then(Value::as_i64), Some(10));
+        assert!(map.get_key("mp").is_none());
+    }
+
+    fn closures() {
+        let calls = Arc::new(AtomicI64::new(0));
+        let factor = 3;
+
+        let mut module = Module::new("closures");
+        let counter = Arc::clone(&calls);
+        module
+            .add_fn("scale", move |args| {
+                counter.fetch_add(1, Ordering::SeqCst);
+                Value::from(args[1].as_i64().unwrap_or(0) * factor)
+            })
+            .add_fn("panics", |_| panic!("oops"));
+        module.register();
+
+        let res = Runtime::Lua.eval("require('closures').scale(2) + require('closures').scale(5)").unwrap();
+        assert_eq!(res.as_i64(), Some(21));
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        let err = Runtime::Lua.exec("require('closures').panics()", "<rust_api>").unwrap_err();
+        assert!(err.contains("panicked"), "Unexpected error: {err}");
+
+        // Removing the module drops the closures.
+        assert_eq!(Arc::strong_count(&calls), 2);
+        let name = CString::new("closures").unwrap();
+        assert!(pxs_removemod(name.as_ptr()));
+        assert_eq!(Arc::strong_count(&calls), 1);
+    }
+
+    #[test]
+    fn run_test() {
+        rust_api::initialize();
+
+        let mut module = Module::new("game");
+        module.add_func("add", add).add_object("Counter", new_counter).add_var("version", "1.0");
+        module.register();
+
+        // Dropped without registering.
+        let _unused = Module::new("unused");
+
+        let res = Runtime::Lua.eval("require('game').add(1, 2)").unwrap();
+        assert_eq!(res.as_i64(), Some(3));
+
+        let res = Runtime::Lua.eval("require('game').version").unwrap();
+        assert_eq!(res.as_string(), Some("1.0".to_string()));
+
+        let res = Runtime::Lua.exec("local c = require('game').Counter(); c:bump(); assert(c:bump() == 2)", "<rust_api>");
+        assert!(res.is_ok(), "Lua error: {:#?}", res);
+
+        let err = Runtime::Lua.exec("error('boom')", "<rust_api>").unwrap_err();
+        assert!(err.contains("boom"), "Unexpected error: {err}");
+
+        let func = Runtime::Lua.eval("function(a, b) return a * b end").unwrap();
+        let res = Runtime::Lua.call(&func, vec![Value::from(6), Value::from(7)]).unwrap();
+        assert_eq!(res.as_i64(), Some(42));
+        drop(func);
+
+        assert!(Runtime::Lua.exec("nul\0byte", "<rust_api>").is_err());
+
+        closures();
+
+        rust_api::finalize();
+    }
+}
@@ -0,0 +1,95 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_save --no-default-features --features "lua,python,js,include-core,testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use pixelscript::{
+        pxs_finalize, pxs_freevar, pxs_getint, pxs_initialize, pxs_migrate_save, pxs_newint, pxs_save_clear,
+        pxs_saveinit, pxs_varis,
+        shared::{pxs_Runtime, utils, var::pxs_VarType},
+    };
+
+    fn print_helper(lang: &str) {
+        println!("====================== {lang} ===================");
+    }
+
+    fn test_python() {
+        let script = r#"
+import pxs_save
+
+def v10_to_v11(data):
+    data["hp"] = data.pop("health")
+    return data
+
+def v11_to_v12(data):
+    data["items"] = []
+    return data
+
+pxs_save.register_migration(10, 11, v10_to_v11)
+pxs_save.register_migration(11, 12, v11_to_v12)
+
+data = pxs_save.migrate({"health": 3}, 10, 12)
+assert data["hp"] == 3, data
+assert data["items"] == [], data
+
+try:
+    pxs_save.migrate({}, 12, 10)
+    assert False
+except Exception:
+    pass
+"#;
+        let err = utils::execute_code(script, "<py>", pxs_Runtime::pxs_Python);
+        assert!(err.is_null(), "Python error: {:#?}", err);
+    }
+
+    fn test_lua() {
+        let script = r#"
+local pxs_save = require('pxs_save')
+pxs_save.register_migration(1, 2, function(data) return data * 2 end)
+pxs_save.register_migration(2, 3, function(data) return data + 1 end)
+"#;
+        let err = utils::execute_code(script, "<lua>", pxs_Runtime::pxs_Lua);
+        assert!(err.is_null(), "Lua error: {:#?}", err);
+    }
+
+    fn test_host() {
+        let data = pxs_newint(5);
+
+        let res = pxs_migrate_save(data, 1, 3);
+        assert!(pxs_varis(res, pxs_VarType::pxs_Int64));
+        assert_eq!(pxs_getint(res), 11);
+        pxs_freevar(res);
+
+        let res = pxs_migrate_save(data, 3, 1);
+        assert!(pxs_varis(res, pxs_VarType::pxs_Exception));
+        pxs_freevar(res);
+
+        pxs_freevar(data);
+    }
+
+    #[test]
+    fn run_test() {
+        println!();
+        pxs_initialize();
+        pxs_saveinit();
+        utils::setup_pxs();
+
+        print_helper("PYTHON");
+        test_python();
+        print_helper("LUA");
+        test_lua();
+        print_helper("HOST");
+        test_host();
+
+        pxs_save_clear();
+        pxs_finalize();
+    }
+}
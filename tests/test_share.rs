@@ -0,0 +1,140 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_share --no-default-features --features "lua,python,js,testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use std::{
+        ffi::{CStr, CString, c_char},
+        ptr,
+    };
+
+    use etffi::ptr_magic::PtrMagic;
+    use pixelscript::{
+        pxs_finalize, pxs_initialize, pxs_set_filereader, pxs_share_module,
+        shared::{
+            pxs_Runtime, utils,
+            var::{pxs_Var, pxs_VarT},
+        },
+    };
+
+    fn print_helper(lang: &str) {
+        println!("====================== {lang} ===================");
+    }
+
+    /// A module per language. Python asks for `mylib.py`, Lua and JS for the plain name.
+    unsafe extern "C" fn load_file(path: *const c_char) -> pxs_VarT {
+        let path = unsafe { CStr::from_ptr(path) }.to_string_lossy().to_string();
+        let source = match path.as_str() {
+            "mylib.py" => {
+                r#"
+VERSION = '1.2'
+def add(a, b):
+    return a + b
+def stats(items):
+    return {'count': len(items), 'total': sum(items)}
+def scale(v, by):
+    return {'x': v['x'] * by, 'y': v['y'] * by}
+"#
+            }
+            "vec" => {
+                r#"
+local M = { origin = { x = 0, y = 0 } }
+function M.len2(v) return v.x * v.x + v.y * v.y end
+function M.fail() error('vec failed') end
+return M
+"#
+            }
+            "greet" => "export function hello(name) { return 'hi ' + name; }\nexport const LANGS = ['lua', 'python'];",
+            _ => return ptr::null_mut(),
+        };
+        pxs_Var::new_string(source.to_string()).into_raw()
+    }
+
+    fn share(runtime: pxs_Runtime, name: &str) -> Result<(), String> {
+        let name = CString::new(name).unwrap();
+        let err = pxs_share_module(runtime, name.as_ptr());
+        if err.is_null() {
+            return Ok(());
+        }
+        Err(pxs_Var::from_raw(err).get_string().unwrap_or_default())
+    }
+
+    fn test_python() {
+        let script = r#"
+import vec
+import greet
+assert vec.len2({'x': 3, 'y': 4}) == 25
+assert vec.origin['x'] == 0
+assert greet.hello('py') == 'hi py'
+assert greet.LANGS[1] == 'python'
+try:
+    vec.fail()
+    assert False
+except Exception as e:
+    assert 'vec failed' in str(e)
+"#;
+        let err = utils::execute_code(script, "<py>", pxs_Runtime::pxs_Python);
+        assert!(err.is_null(), "Python error: {:#?}", err);
+    }
+
+    fn test_lua() {
+        let script = r#"
+local mylib = require('mylib')
+assert(mylib.VERSION == '1.2')
+assert(mylib.add(2, 3) == 5)
+local s = mylib.stats({ 1, 2, 3 })
+assert(s.count == 3 and s.total == 6)
+local v = mylib.scale({ x = 1, y = 2 }, 3)
+assert(v.x == 3 and v.y == 6)
+assert(require('greet').hello('lua') == 'hi lua')
+-- Functions stay in their runtime.
+assert(not pcall(mylib.add, function() end, 1))
+"#;
+        let err = utils::execute_code(script, "<lua>", pxs_Runtime::pxs_Lua);
+        assert!(err.is_null(), "Lua error: {:#?}", err);
+    }
+
+    fn test_js() {
+        let script = r#"
+import { add, VERSION } from 'mylib';
+import { len2 } from 'vec';
+if (add(1, 1) !== 2 || VERSION !== '1.2') throw new Error("mylib");
+if (len2({ x: 1, y: 1 }) !== 2) throw new Error("vec");
+"#;
+        let err = utils::execute_code(script, "<js>", pxs_Runtime::pxs_JavaScript);
+        assert!(err.is_null(), "JS error: {:#?}", err);
+    }
+
+    #[test]
+    fn run_test() {
+        println!();
+        pxs_initialize();
+        pxs_set_filereader(load_file);
+
+        share(pxs_Runtime::pxs_Python, "mylib").unwrap();
+        share(pxs_Runtime::pxs_Lua, "vec").unwrap();
+        share(pxs_Runtime::pxs_JavaScript, "greet").unwrap();
+
+        // Missing modules and bad names.
+        assert!(share(pxs_Runtime::pxs_Lua, "nothing").is_err());
+        assert!(share(pxs_Runtime::pxs_Python, "os'); x = ('").is_err());
+        assert!(share(pxs_Runtime::pxs_Python, "").is_err());
+
+        print_helper("PYTHON");
+        test_python();
+        print_helper("LUA");
+        test_lua();
+        print_helper("JS");
+        test_js();
+
+        pxs_finalize();
+    }
+}
@@ -0,0 +1,129 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_signature --no-default-features --features "lua,python,testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use std::{ffi::CString, ptr};
+
+    use etffi::ptr_magic::PtrMagic;
+    use pixelscript::{
+        pxs_addfunc_ex, pxs_addmod, pxs_finalize, pxs_initialize,
+        shared::{
+            pxs_Runtime, utils,
+            var::{pxs_Var, pxs_VarT},
+        },
+    };
+
+    fn s(val: &str) -> pxs_Var {
+        pxs_Var::new_string(val.to_string())
+    }
+
+    fn map(items: Vec<(&str, pxs_Var)>) -> pxs_Var {
+        let var = pxs_Var::new_map();
+        let map = var.get_map().unwrap();
+        for (key, value) in items {
+            map.add_item(s(key), value);
+        }
+        var
+    }
+
+    fn args<'a>(args: pxs_VarT) -> &'a Vec<pxs_Var> {
+        &unsafe { pxs_Var::from_borrow(args) }.get_list().unwrap().vars
+    }
+
+    /// `move(x: float, y: float = 0.5)`
+    extern "C" fn move_by(argv: pxs_VarT) -> pxs_VarT {
+        let argv = args(argv);
+        assert_eq!(argv.len(), 3);
+        pxs_Var::new_f64(argv[1].get_f64().unwrap() + argv[2].get_f64().unwrap()).into_raw()
+    }
+
+    /// `join(sep: string, *parts: string)`
+    extern "C" fn join(argv: pxs_VarT) -> pxs_VarT {
+        let argv = args(argv);
+        let parts: Vec<String> = argv[2..].iter().map(|part| part.get_string().unwrap()).collect();
+        s(&parts.join(&argv[1].get_string().unwrap())).into_raw()
+    }
+
+    fn run(runtime: pxs_Runtime, script: &str) {
+        let err = utils::execute_code(script, "<signature>", runtime);
+        assert!(err.is_null(), "Script error: {:#?}", err);
+    }
+
+    #[test]
+    fn run_test() {
+        pxs_initialize();
+
+        let module = utils::create_module("geo");
+        let signature = map(vec![(
+            "params",
+            pxs_Var::new_list_with(vec![
+                map(vec![("name", s("x")), ("type", s("float"))]),
+                map(vec![("name", s("y")), ("type", s("float")), ("default", pxs_Var::new_f64(0.5))]),
+            ]),
+        )]);
+        let name = CString::new("move").unwrap();
+        pxs_addfunc_ex(module, name.as_ptr(), move_by, ptr::null_mut(), signature.into_raw());
+
+        let signature = map(vec![
+            ("params", pxs_Var::new_list_with(vec![map(vec![("name", s("sep")), ("type", s("string"))])])),
+            ("varargs", s("string")),
+        ]);
+        let name = CString::new("join").unwrap();
+        pxs_addfunc_ex(module, name.as_ptr(), join, ptr::null_mut(), signature.into_raw());
+        pxs_addmod(module);
+
+        run(
+            pxs_Runtime::pxs_Lua,
+            r#"
+local geo = require('geo')
+assert(geo.move(1) == 1.5)
+assert(geo.move(1, 2) == 3)
+assert(geo.join('-', 'a', 'b') == 'a-b')
+assert(geo.join(',') == '')
+
+local ok, err = pcall(geo.move)
+assert(not ok and string.find(err, 'takes 1 to 2 arguments', 1, true), err)
+ok, err = pcall(geo.move, 1, 2, 3)
+assert(not ok and string.find(err, '(3 given)', 1, true), err)
+ok, err = pcall(geo.move, 'a')
+assert(not ok and string.find(err, "argument 'x' must be float, not string", 1, true), err)
+ok, err = pcall(geo.join, '-', 'a', 1)
+assert(not ok and string.find(err, "argument 'arg 3' must be string", 1, true), err)
+"#,
+        );
+
+        run(
+            pxs_Runtime::pxs_Python,
+            r#"
+import geo
+assert geo.move(1) == 1.5
+assert geo.join('-', 'a', 'b', 'c') == 'a-b-c'
+
+failed = False
+try:
+    geo.move(1, 'b')
+except TypeError as e:
+    failed = True
+assert failed
+
+failed = False
+try:
+    geo.move()
+except TypeError:
+    failed = True
+assert failed
+"#,
+        );
+
+        pxs_finalize();
+    }
+}
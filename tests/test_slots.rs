@@ -0,0 +1,111 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_slots --no-default-features --features "lua,include-core,testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use std::ffi::CString;
+
+    use pixelscript::{
+        pxs_addmod, pxs_call_handle, pxs_clear, pxs_finalize, pxs_freevar, pxs_funchandle, pxs_initialize,
+        pxs_newint, pxs_newlist, pxs_varis,
+        shared::{
+            pxs_Runtime,
+            slots::SlotMap,
+            utils,
+            var::{pxs_VarT, pxs_VarType},
+        },
+    };
+
+    extern "C" fn one(_args: pxs_VarT) -> pxs_VarT {
+        pxs_newint(1)
+    }
+
+    fn register() -> i32 {
+        let module = utils::create_module("math");
+        utils::add_function(module, "one", one);
+        pxs_addmod(module);
+
+        let module = CString::new("math").unwrap();
+        let name = CString::new("one").unwrap();
+        pxs_funchandle(module.as_ptr(), name.as_ptr())
+    }
+
+    #[test]
+    fn test_slot_map() {
+        let mut map = SlotMap::new();
+        let a = map.insert("a").unwrap();
+        let b = map.insert("b").unwrap();
+        assert!(a > 0 && b > 0 && a != b);
+        assert_eq!(map.get(a), Some(&"a"));
+
+        // A reused slot gets a new handle, the old one stops resolving.
+        assert_eq!(map.remove(a), Some("a"));
+        let c = map.insert("c").unwrap();
+        assert_ne!(a, c);
+        assert_eq!(map.get(a), None);
+        assert_eq!(map.get(c), Some(&"c"));
+
+        // Pinned values are negative and survive `clear`.
+        let pinned = map.insert_pinned("pinned").unwrap();
+        assert!(pinned < -1);
+        map.clear();
+        assert_eq!(map.get(b), None);
+        assert_eq!(map.get(c), None);
+        assert_eq!(map.get(pinned), Some(&"pinned"));
+        assert_eq!(map.iter().count(), 1);
+
+        map.clear_all();
+        assert_eq!(map.get(pinned), None);
+
+        // Never handles.
+        assert_eq!(map.get(-1), None);
+        assert_eq!(map.get(0), None);
+        assert_eq!(map.get(i32::MIN), None);
+    }
+
+    #[test]
+    fn test_full_slot_map() {
+        // 2^20 live values fit, the next insert is a error instead of a panic.
+        let mut map = SlotMap::new();
+        let handles: Vec<i32> = (0..1 << 20).map(|i| map.insert(i).unwrap()).collect();
+        assert!(map.insert(-1).is_err());
+        assert!(map.insert_pinned(-1).is_ok());
+
+        // Freeing a value makes room again.
+        assert_eq!(map.remove(handles[7]), Some(7));
+        let handle = map.insert(-2).unwrap();
+        assert_eq!(map.get(handle), Some(&-2));
+    }
+
+    #[test]
+    fn test_stale_function_handle() {
+        pxs_initialize();
+        let rt = pxs_newint(pxs_Runtime::pxs_Lua.into_i64());
+
+        let old = register();
+        assert!(old >= 0);
+        pxs_clear();
+        let new = register();
+        assert_ne!(old, new);
+
+        // The old handle does not call whatever got registered after the clear.
+        let res = pxs_call_handle(rt, old, pxs_newlist());
+        assert!(pxs_varis(res, pxs_VarType::pxs_Exception));
+        pxs_freevar(res);
+
+        let res = pxs_call_handle(rt, new, pxs_newlist());
+        assert!(pxs_varis(res, pxs_VarType::pxs_Int64));
+        pxs_freevar(res);
+
+        pxs_freevar(rt);
+        pxs_finalize();
+    }
+}
@@ -0,0 +1,95 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_snapshot --no-default-features --features "testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use etffi::ptr_magic::PtrMagic;
+    use pixelscript::{
+        pxs_finalize, pxs_initialize, pxs_snapshot_apply, pxs_snapshot_diff,
+        shared::{delta, var::pxs_Var},
+    };
+
+    /// A fake world save: 200 entities of 32 bytes.
+    fn world() -> Vec<u8> {
+        (0..200u32).flat_map(|id| {
+            let mut entity = id.to_le_bytes().to_vec();
+            entity.extend((0..28).map(|i| (id as u8).wrapping_mul(31).wrapping_add(i)));
+            entity
+        }).collect()
+    }
+
+    fn round_trip(old: &[u8], new: &[u8]) -> Vec<u8> {
+        let diff = delta::diff(old, new);
+        assert_eq!(delta::apply(old, &diff).unwrap(), new);
+        diff
+    }
+
+    #[test]
+    fn test_delta() {
+        let old = world();
+
+        // Unchanged and small changes make small diffs.
+        assert!(round_trip(&old, &old).len() < 32);
+        let mut new = old.clone();
+        new[1000] = 0xff;
+        new[5000..5004].copy_from_slice(&[1, 2, 3, 4]);
+        assert!(round_trip(&old, &new).len() < 100);
+
+        // Inserted and removed entities.
+        let mut new = old.clone();
+        new.splice(320..320, [7u8; 32]);
+        new.drain(4000..4064);
+        assert!(round_trip(&old, &new).len() < 100);
+
+        // Edge cases.
+        round_trip(&[], &old);
+        round_trip(&old, &[]);
+        round_trip(&[], &[]);
+        round_trip(b"short", b"shorter");
+    }
+
+    #[test]
+    fn test_invalid_diff() {
+        let old = world();
+        let mut new = old.clone();
+        new[10] = 0;
+        let diff = delta::diff(&old, &new);
+
+        assert!(delta::apply(&new, &diff).is_err(), "Applied to the wrong base");
+        assert!(delta::apply(&old, &diff[..diff.len() - 1]).is_err());
+        assert!(delta::apply(&old, b"nope").is_err());
+    }
+
+    #[test]
+    fn run_test() {
+        pxs_initialize();
+
+        let old = pxs_Var::new_bytes(world()).into_raw();
+        let mut data = world();
+        data[42] = 1;
+        let new = pxs_Var::new_bytes(data.clone()).into_raw();
+
+        let diff = pxs_snapshot_diff(old, new);
+        let res = pxs_Var::from_raw(pxs_snapshot_apply(old, diff));
+        assert_eq!(res.get_bytes().map(|b| b.clone()), Some(data));
+
+        // Only bytes.
+        let not_bytes = pxs_Var::new_i64(1).into_raw();
+        assert!(pxs_Var::from_raw(pxs_snapshot_diff(old, not_bytes)).is_exception());
+        assert!(pxs_Var::from_raw(pxs_snapshot_apply(new, diff)).is_exception());
+
+        let _ = pxs_Var::from_raw(old);
+        let _ = pxs_Var::from_raw(new);
+        let _ = pxs_Var::from_raw(diff);
+        let _ = pxs_Var::from_raw(not_bytes);
+        pxs_finalize();
+    }
+}
@@ -0,0 +1,97 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_source_identity --no-default-features --features "lua,python,js,testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use std::{ffi::CString, ptr};
+
+    use etffi::ptr_magic::PtrMagic;
+    use pixelscript::{
+        pxs_addmod, pxs_finalize, pxs_initialize, pxs_iter_free, pxs_iter_new, pxs_iter_next, pxs_listget,
+        pxs_set_source_identity,
+        shared::{
+            pxs_Runtime, utils,
+            var::{pxs_Var, pxs_VarT},
+        },
+    };
+
+    /// `first_error(iterable)` returns the error of the first `next`, it runs in the generated `iter` chunk.
+    extern "C" fn first_error(args: pxs_VarT) -> pxs_VarT {
+        let iter = pxs_iter_new(pxs_listget(args, 0), pxs_listget(args, 1));
+        assert!(!iter.is_null());
+        let item = pxs_Var::from_raw(pxs_iter_next(iter));
+        pxs_iter_free(iter);
+        assert!(item.is_exception(), "Expected a exception, got: {:#?}", item.tag);
+        pxs_Var::new_string(item.get_string().unwrap()).into_raw()
+    }
+
+    fn set_identity(prefix: Option<&str>) {
+        match prefix {
+            Some(prefix) => {
+                let prefix = CString::new(prefix).unwrap();
+                pxs_set_source_identity(prefix.as_ptr());
+            }
+            None => pxs_set_source_identity(ptr::null()),
+        }
+    }
+
+    fn run(script: &str) {
+        let err = utils::execute_code(script, "<py>", pxs_Runtime::pxs_Python);
+        assert!(err.is_null(), "Python error: {:#?}", err);
+    }
+
+    #[test]
+    fn run_test() {
+        println!();
+        // Can be set before initializing.
+        set_identity(Some("pxs:"));
+        pxs_initialize();
+        let test_module = utils::create_module("test");
+        utils::add_function(test_module, "first_error", first_error);
+        pxs_addmod(test_module);
+
+        run(r#"
+import test
+def boom():
+    raise ValueError('boom')
+    yield 1
+err = test.first_error(boom())
+assert 'boom' in err, err
+assert '<pxs:iter>' in err, err
+"#);
+
+        // Only code generated after the call is renamed.
+        set_identity(Some("engine/"));
+        run(r#"
+import test
+def boom():
+    raise ValueError('boom')
+    yield 1
+err = test.first_error(boom())
+assert '<engine/iter>' in err, err
+assert '<pxs:iter>' not in err, err
+"#);
+
+        // Null clears the prefix. User chunk names are never prefixed.
+        set_identity(None);
+        run(r#"
+import test
+def boom():
+    raise ValueError('boom')
+    yield 1
+err = test.first_error(boom())
+assert '<iter>' in err, err
+assert '<py>' in err, err
+"#);
+
+        pxs_finalize();
+    }
+}
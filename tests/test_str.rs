@@ -0,0 +1,94 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_str --no-default-features --features "lua,python,js,include-core,testing" -- --nocapture --test-threads=1
+
+#[cfg(all(test, feature = "pxs_str"))]
+#[allow(unused)]
+mod tests {
+    use pixelscript::{
+        pxs_finalize, pxs_initialize, pxs_strinit,
+        shared::{pxs_Runtime, utils},
+    };
+
+    fn print_helper(lang: &str) {
+        println!("====================== {lang} ===================");
+    }
+
+    fn test_python() {
+        let script = r#"
+import pxs_str
+assert pxs_str.split('a,b,,c', ',') == ['a', 'b', '', 'c']
+assert pxs_str.split('  a  b c ') == ['a', 'b', 'c']
+assert pxs_str.split('a b c', None, 1) == ['a', 'b c']
+assert pxs_str.join([1, 2.0, 'x', True, None], '-') == '1-2.0-x-true-null'
+assert pxs_str.trim('  hi \n') == 'hi'
+assert pxs_str.trim('xxhixy', 'xy') == 'hi'
+assert pxs_str.starts_with('héllo', 'hé') and pxs_str.ends_with('héllo', 'lo')
+assert pxs_str.format('{} has {1} hp {{ok}}', 'Bob', 10) == 'Bob has 10 hp {ok}'
+assert pxs_str.pad('é', 3, '*', 'center') == '*é*'
+assert pxs_str.pad('7', 3, '0', 'right') == '007'
+try:
+    pxs_str.format('{5}', 1)
+    assert False
+except Exception:
+    pass
+"#;
+        let err = utils::execute_code(script, "<py>", pxs_Runtime::pxs_Python);
+        assert!(err.is_null(), "Python error: {:#?}", err);
+    }
+
+    fn test_lua() {
+        let script = r#"
+local str = require('pxs_str')
+local parts = str.split('a,b,,c', ',')
+assert(#parts == 4 and parts[3] == '' and parts[4] == 'c')
+assert(#str.split('  a  b c ') == 3)
+assert(str.join({1, 2.0, 'x', true}, '-') == '1-2.0-x-true')
+assert(str.trim('  hi \n') == 'hi')
+assert(str.starts_with('héllo', 'hé') and str.ends_with('héllo', 'lo'))
+assert(str.format('{} has {1} hp {{ok}}', 'Bob', 10) == 'Bob has 10 hp {ok}')
+-- Widths count characters, not bytes.
+assert(str.pad('é', 3, '*', 'center') == '*é*')
+assert(str.pad('ab', 4) == 'ab  ')
+assert(not pcall(str.pad, 'a', 3, '**'))
+assert(not pcall(str.format, '{', 1))
+"#;
+        let err = utils::execute_code(script, "<lua>", pxs_Runtime::pxs_Lua);
+        assert!(err.is_null(), "Lua error: {:#?}", err);
+    }
+
+    fn test_js() {
+        let script = r#"
+import { split, join, format, pad } from 'pxs_str';
+if (split('a b', null).length !== 2) throw new Error("split");
+if (join(['a', 'b'], ', ') !== 'a, b') throw new Error("join");
+if (format('{}/{}', 1, 'x') !== '1/x') throw new Error("format");
+if (pad('é', 3, '*', 'center') !== '*é*') throw new Error("pad");
+"#;
+        let err = utils::execute_code(script, "<js>", pxs_Runtime::pxs_JavaScript);
+        assert!(err.is_null(), "JS error: {:#?}", err);
+    }
+
+    #[test]
+    fn run_test() {
+        println!();
+        pxs_initialize();
+        pxs_strinit();
+        utils::setup_pxs();
+
+        print_helper("PYTHON");
+        test_python();
+        print_helper("LUA");
+        test_lua();
+        print_helper("JS");
+        test_js();
+
+        pxs_finalize();
+    }
+}
@@ -0,0 +1,62 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_strict --no-default-features --features "testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use std::{ffi::CString, process::Command, ptr};
+
+    use etffi::ptr_magic::PtrMagic;
+    use pixelscript::{
+        pxs_addfunc, pxs_finalize, pxs_initialize, pxs_listadd, pxs_map_addpair, pxs_set_strict,
+        shared::var::{pxs_Var, pxs_VarT},
+    };
+
+    extern "C" fn noop(_args: pxs_VarT) -> pxs_VarT {
+        pxs_Var::new_null().into_raw()
+    }
+
+    /// Run in a child process by `run_test`, strict mode aborts the process.
+    #[test]
+    #[ignore]
+    fn strict_child() {
+        pxs_initialize();
+        pxs_set_strict(true);
+        let name = CString::new("noop").unwrap();
+        pxs_addfunc(ptr::null_mut(), name.as_ptr(), noop);
+        pxs_finalize();
+    }
+
+    #[test]
+    fn run_test() {
+        pxs_initialize();
+
+        // Not strict, invalid calls are ignored.
+        let name = CString::new("noop").unwrap();
+        pxs_addfunc(ptr::null_mut(), name.as_ptr(), noop);
+        let not_list = pxs_Var::new_i64(1).into_raw();
+        assert_eq!(pxs_listadd(not_list, pxs_Var::new_null().into_raw()), -1);
+        let map = pxs_Var::new_map().into_raw();
+        pxs_map_addpair(map, pxs_Var::new_null().into_raw(), pxs_Var::new_i64(2).into_raw());
+        assert!(pxs_Var::from_raw(map).get_map().unwrap().keys().is_empty());
+        let _ = pxs_Var::from_raw(not_list);
+
+        pxs_finalize();
+
+        // Strict, the offending function is named.
+        let output = Command::new(std::env::current_exe().unwrap())
+            .args(["--ignored", "--exact", "tests::strict_child", "--nocapture"])
+            .output()
+            .unwrap();
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert!(!output.status.success());
+        assert!(stderr.contains("pxs_addfunc: module_ptr or name is null"), "Unexpected stderr: {stderr}");
+    }
+}
@@ -0,0 +1,49 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_stringview --no-default-features --features "lua,testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use std::{ffi::CStr, ptr};
+
+    use pixelscript::{
+        pxs_finalize, pxs_freevar, pxs_getstring_utf8len, pxs_getstring_view, pxs_initialize, pxs_newint,
+        pxs_newstring,
+    };
+
+    #[test]
+    fn run_test() {
+        pxs_initialize();
+
+        let string = pxs_newstring(c"héllo".as_ptr());
+        let mut len = 0;
+        let view = pxs_getstring_view(string, &mut len);
+        assert_eq!(len, 6);
+        assert_eq!(unsafe { CStr::from_ptr(view) }.to_str().unwrap(), "héllo");
+        // Same memory every time, nothing is copied.
+        assert_eq!(pxs_getstring_view(string, ptr::null_mut()), view);
+        assert_eq!(pxs_getstring_utf8len(string), 5);
+        pxs_freevar(string);
+
+        let empty = pxs_newstring(c"".as_ptr());
+        assert_eq!(pxs_getstring_utf8len(empty), 0);
+        assert!(!pxs_getstring_view(empty, &mut len).is_null());
+        assert_eq!(len, 0);
+        pxs_freevar(empty);
+
+        // Not a string
+        let int = pxs_newint(1);
+        assert!(pxs_getstring_view(int, &mut len).is_null());
+        assert_eq!(pxs_getstring_utf8len(int), -1);
+        pxs_freevar(int);
+
+        pxs_finalize();
+    }
+}
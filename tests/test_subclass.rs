@@ -0,0 +1,153 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_subclass --no-default-features --features "lua,python,testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use std::{
+        ffi::{CString, c_void},
+        sync::atomic::{AtomicI32, Ordering},
+    };
+
+    use etffi::ptr_magic::PtrMagic;
+    use pixelscript::{
+        pxs_addfunc, pxs_addmod, pxs_addobject, pxs_finalize, pxs_gethost, pxs_initialize, pxs_newhost,
+        pxs_newobject, pxs_object_addfunc, pxs_object_is_overridden, pxs_objectcall,
+        shared::{
+            pxs_Runtime, utils,
+            var::{pxs_Var, pxs_VarT},
+        },
+    };
+
+    /// Idx of the last `Enemy`.
+    static LAST_ENEMY: AtomicI32 = AtomicI32::new(-1);
+
+    struct Enemy {
+        name: String,
+        damage: i64,
+    }
+
+    impl PtrMagic for Enemy {}
+
+    extern "C" fn free_enemy(ptr: *mut c_void) {
+        let _ = Enemy::from_raw(ptr as *mut Enemy);
+    }
+
+    fn arg<'a>(args: pxs_VarT, idx: i32) -> &'a pxs_Var {
+        unsafe { pxs_Var::from_borrow(args) }.get_list().unwrap().get_item(idx).unwrap()
+    }
+
+    fn var_ptr(var: &pxs_Var) -> pxs_VarT {
+        var as *const pxs_Var as pxs_VarT
+    }
+
+    fn enemy<'a>(args: pxs_VarT) -> &'a Enemy {
+        unsafe { &*(pxs_gethost(var_ptr(arg(args, 0)), var_ptr(arg(args, 1))) as *const Enemy) }
+    }
+
+    extern "C" fn enemy_name(args: pxs_VarT) -> pxs_VarT {
+        pxs_Var::new_string(enemy(args).name.clone()).into_raw()
+    }
+
+    extern "C" fn enemy_attack(args: pxs_VarT) -> pxs_VarT {
+        pxs_Var::new_i64(enemy(args).damage).into_raw()
+    }
+
+    extern "C" fn enemy_new(args: pxs_VarT) -> pxs_VarT {
+        let enemy = Enemy { name: arg(args, 1).get_string().unwrap(), damage: arg(args, 2).get_i64().unwrap() };
+        let type_name = CString::new("Enemy").unwrap();
+        let object = pxs_newobject(enemy.into_raw() as *mut c_void, free_enemy, type_name.as_ptr());
+        for (name, func) in [("name", enemy_name as extern "C" fn(pxs_VarT) -> pxs_VarT), ("attack", enemy_attack)] {
+            let name = CString::new(name).unwrap();
+            pxs_object_addfunc(object, name.as_ptr(), func);
+        }
+        let var = pxs_newhost(object);
+        LAST_ENEMY.store(unsafe { pxs_Var::from_borrow(var) }.get_host_idx(), Ordering::SeqCst);
+        var
+    }
+
+    /// Call `attack` like a game loop would: `[overridden, damage]`.
+    fn attack(runtime: pxs_VarT, enemy: pxs_VarT) -> pxs_Var {
+        let method = CString::new("attack").unwrap();
+        let overridden = pxs_object_is_overridden(runtime, enemy, method.as_ptr());
+        let damage = pxs_Var::from_raw(pxs_objectcall(runtime, enemy, method.as_ptr(), pxs_Var::new_list().into_raw()));
+        pxs_Var::new_list_with(vec![pxs_Var::new_bool(overridden), damage])
+    }
+
+    extern "C" fn fight(args: pxs_VarT) -> pxs_VarT {
+        attack(var_ptr(arg(args, 0)), var_ptr(arg(args, 1))).into_raw()
+    }
+
+    fn run(runtime: pxs_Runtime, script: &str) {
+        let err = utils::execute_code(script, "<subclass>", runtime);
+        assert!(err.is_null(), "Script error: {:#?}", err);
+    }
+
+    #[test]
+    fn run_test() {
+        pxs_initialize();
+
+        let module = utils::create_module("game");
+        let name = CString::new("Enemy").unwrap();
+        pxs_addobject(module, name.as_ptr(), enemy_new);
+        let name = CString::new("fight").unwrap();
+        pxs_addfunc(module, name.as_ptr(), fight);
+        pxs_addmod(module);
+
+        // Lua objects are tables, functions assigned to them override the host methods.
+        run(
+            pxs_Runtime::pxs_Lua,
+            r#"
+local game = require('game')
+local orc = game.Enemy('orc', 5)
+local res = game.fight(orc)
+assert(res[1] == false and res[2] == 5)
+
+function orc:attack() return 100 + #self:name() end
+res = game.fight(orc)
+assert(res[1] == true and res[2] == 103)
+"#,
+        );
+
+        run(
+            pxs_Runtime::pxs_Python,
+            r#"
+import game
+
+class Boss(game.Enemy):
+    def __init__(self, name, damage):
+        self.phase = 2
+
+    def attack(self):
+        return 100 * self.phase
+
+orc = game.Enemy('orc', 5)
+res = game.fight(orc)
+assert res[0] == False and res[1] == 5
+
+boss = Boss('boss', 7)
+assert boss.name() == 'boss'
+assert isinstance(boss, game.Enemy)
+res = game.fight(boss)
+assert res[0] == True and res[1] == 200
+"#,
+        );
+
+        // The host object of the boss is the subclass instance.
+        let runtime = pxs_Var::new_i64(pxs_Runtime::pxs_Python as i64);
+        let boss = pxs_Var::new_host_object(LAST_ENEMY.load(Ordering::SeqCst));
+        let res = attack(var_ptr(&runtime), var_ptr(&boss));
+        let res = res.get_list().unwrap();
+        assert!(res.get_item(0).unwrap().get_bool().unwrap());
+        assert_eq!(res.get_item(1).unwrap().get_i64().unwrap(), 200);
+
+        pxs_finalize();
+    }
+}
@@ -0,0 +1,76 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_sync --no-default-features --features "lua,testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use pixelscript::{
+        pxs_eval, pxs_finalize, pxs_freevar, pxs_initialize, pxs_newint, pxs_sync_begin, pxs_sync_bind,
+        pxs_sync_end, pxs_sync_field,
+        shared::{pxs_Runtime, sync::pxs_SyncType, utils},
+    };
+
+    /// Encode a record header.
+    fn header(buf: &mut Vec<u8>, target: u32, field: i32, tag: pxs_SyncType) {
+        buf.extend_from_slice(&target.to_le_bytes());
+        buf.extend_from_slice(&(field as u16).to_le_bytes());
+        buf.push(tag as u8);
+    }
+
+    /// Copy `data` into the sync buffer and apply it.
+    fn apply(data: &[u8]) -> i64 {
+        let ptr = pxs_sync_begin(data.len());
+        unsafe { core::ptr::copy_nonoverlapping(data.as_ptr(), ptr, data.len()) };
+        pxs_sync_end()
+    }
+
+    #[test]
+    fn run_test() {
+        pxs_initialize();
+
+        let err = utils::execute_code("player = {}", "<lua>", pxs_Runtime::pxs_Lua);
+        assert!(err.is_null(), "Lua error: {:#?}", err);
+
+        let rt = pxs_newint(pxs_Runtime::pxs_Lua.into_i64());
+        let player = pxs_eval(c"player".as_ptr(), pxs_Runtime::pxs_Lua);
+        let target = pxs_sync_bind(rt, player) as u32;
+        pxs_freevar(player);
+
+        let x = pxs_sync_field(c"x".as_ptr());
+        let hp = pxs_sync_field(c"hp".as_ptr());
+        let alive = pxs_sync_field(c"alive".as_ptr());
+        assert_eq!(x, pxs_sync_field(c"x".as_ptr()), "Fields should be interned");
+
+        let mut buf = vec![];
+        header(&mut buf, target, x, pxs_SyncType::pxs_SyncFloat32);
+        buf.extend_from_slice(&1.5f32.to_le_bytes());
+        header(&mut buf, target, hp, pxs_SyncType::pxs_SyncInt32);
+        buf.extend_from_slice(&90i32.to_le_bytes());
+        header(&mut buf, target, alive, pxs_SyncType::pxs_SyncBool);
+        buf.push(1);
+        assert_eq!(apply(&buf), 3);
+
+        let err = utils::execute_code(
+            "assert(player.x == 1.5 and player.hp == 90 and player.alive == true)",
+            "<lua>",
+            pxs_Runtime::pxs_Lua,
+        );
+        assert!(err.is_null(), "Lua error: {:#?}", err);
+
+        // Truncated buffers apply nothing.
+        let mut bad = vec![];
+        header(&mut bad, target, hp, pxs_SyncType::pxs_SyncInt32);
+        bad.extend_from_slice(&[1, 0]);
+        assert_eq!(apply(&bad), -1);
+
+        pxs_freevar(rt);
+        pxs_finalize();
+    }
+}
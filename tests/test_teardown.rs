@@ -0,0 +1,113 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_teardown --no-default-features --features "lua,python,js,testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use std::{
+        ffi::c_void,
+        sync::atomic::{AtomicU32, Ordering},
+    };
+
+    use etffi::{create_raw_string, free_raw_string, ptr_magic::PtrMagic};
+    use pixelscript::{
+        pxs_addmod, pxs_addobject, pxs_finalize, pxs_initialize, pxs_newhost, pxs_newnull, pxs_newobject,
+        shared::{pxs_Runtime, utils, var::pxs_VarT},
+    };
+
+    static CREATED: AtomicU32 = AtomicU32::new(0);
+    static FREED: AtomicU32 = AtomicU32::new(0);
+    static UNLOADED: AtomicU32 = AtomicU32::new(0);
+
+    struct Token;
+
+    impl PtrMagic for Token {}
+
+    extern "C" fn free_token(ptr: *mut c_void) {
+        let _ = Token::from_raw(ptr as *mut Token);
+        FREED.fetch_add(1, Ordering::SeqCst);
+    }
+
+    extern "C" fn new_token(_args: pxs_VarT) -> pxs_VarT {
+        CREATED.fetch_add(1, Ordering::SeqCst);
+        let type_name = create_raw_string!("Token");
+        let object = pxs_newobject(Token.into_raw() as *mut c_void, free_token, type_name);
+        unsafe {
+            free_raw_string!(type_name);
+        }
+        pxs_newhost(object)
+    }
+
+    extern "C" fn unloaded(_args: pxs_VarT) -> pxs_VarT {
+        UNLOADED.fetch_add(1, Ordering::SeqCst);
+        pxs_newnull()
+    }
+
+    fn test_python() {
+        let script = r#"
+from test import Token, unloaded
+
+kept = [Token(), Token()]
+Token()
+
+def on_unload():
+    unloaded()
+"#;
+        let err = utils::execute_code(script, "<py>", pxs_Runtime::pxs_Python);
+        assert!(err.is_null(), "Python error: {:#?}", err);
+    }
+
+    fn test_lua() {
+        let script = r#"
+local test = require('test')
+kept = test.Token()
+
+function on_unload()
+    test.unloaded()
+end
+"#;
+        let err = utils::execute_code(script, "<lua>", pxs_Runtime::pxs_Lua);
+        assert!(err.is_null(), "Lua error: {:#?}", err);
+    }
+
+    fn test_js() {
+        let script = r#"
+import { Token } from 'test';
+globalThis.kept = Token();
+"#;
+        let err = utils::execute_code(script, "<js>", pxs_Runtime::pxs_JavaScript);
+        assert!(err.is_null(), "JS error: {:#?}", err);
+    }
+
+    #[test]
+    fn run_test() {
+        pxs_initialize();
+        utils::setup_pxs();
+        let test_module = utils::create_module("test");
+        let name = create_raw_string!("Token");
+        pxs_addobject(test_module, name, new_token);
+        unsafe {
+            free_raw_string!(name);
+        }
+        utils::add_function(test_module, "unloaded", unloaded);
+        pxs_addmod(test_module);
+
+        test_python();
+        test_lua();
+        test_js();
+
+        pxs_finalize();
+
+        assert_eq!(UNLOADED.load(Ordering::SeqCst), 2);
+        assert_eq!(CREATED.load(Ordering::SeqCst), 5);
+        // Every FreeMethod fires exactly once.
+        assert_eq!(FREED.load(Ordering::SeqCst), CREATED.load(Ordering::SeqCst));
+    }
+}
@@ -0,0 +1,99 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_template --no-default-features --features "lua,python,js,include-core,testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use pixelscript::{
+        pxs_finalize, pxs_initialize, pxs_templateinit,
+        shared::{pxs_Runtime, utils},
+    };
+
+    fn print_helper(lang: &str) {
+        println!("====================== {lang} ===================");
+    }
+
+    fn test_python() {
+        let script = r#"
+import pxs_template
+player = {"name": "<Jordan>", "level": 4}
+res = pxs_template.render("Hello {name} ({level}) {{ok}}", player)
+assert res == "Hello <Jordan> (4) {ok}", res
+res = pxs_template.render("{name}", player, "html")
+assert res == "&lt;Jordan&gt;", res
+
+# Keys set to None render as null, missing keys are errors.
+res = pxs_template.render("{title}/{name}", {"title": None, "name": "x"})
+assert res == "null/x", res
+try:
+    pxs_template.render("{missing}", player)
+    assert False
+except Exception as e:
+    assert "missing" in str(e), e
+"#;
+        let err = utils::execute_code(script, "<py>", pxs_Runtime::pxs_Python);
+        assert!(err.is_null(), "Python error: {:#?}", err);
+    }
+
+    fn test_lua() {
+        let script = r#"
+local pxs_template = require('pxs_template')
+local data = {player = {name = 'Jordan'}}
+local res = pxs_template.render('Hello {player.name}', data)
+assert(res == 'Hello Jordan', res)
+local ok = pcall(pxs_template.render, '{missing}', data)
+assert(not ok)
+"#;
+        let err = utils::execute_code(script, "<lua>", pxs_Runtime::pxs_Lua);
+        assert!(err.is_null(), "Lua error: {:#?}", err);
+    }
+
+    fn test_js() {
+        let script = r#"
+import { render } from 'pxs_template';
+let res = render('Hello {player.name}', {player: {name: 'Jordan'}});
+if (res != 'Hello Jordan') {
+    throw new Error(res);
+}
+res = render('{title}', {title: null});
+if (res != 'null') {
+    throw new Error(res);
+}
+let failed = false;
+try {
+    render('{missing}', {title: null});
+} catch (e) {
+    failed = true;
+}
+if (!failed) {
+    throw new Error('missing key rendered');
+}
+"#;
+        let err = utils::execute_code(script, "<js>", pxs_Runtime::pxs_JavaScript);
+        assert!(err.is_null(), "JS error: {:#?}", err);
+    }
+
+    #[test]
+    fn run_test() {
+        println!();
+        pxs_initialize();
+        pxs_templateinit();
+        utils::setup_pxs();
+
+        print_helper("PYTHON");
+        test_python();
+        print_helper("LUA");
+        test_lua();
+        print_helper("JS");
+        test_js();
+
+        pxs_finalize();
+    }
+}
@@ -0,0 +1,116 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_tick --no-default-features --features "lua,python,testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use std::{
+        ffi::{CStr, CString},
+        ptr,
+        sync::atomic::{AtomicBool, AtomicU64, Ordering},
+    };
+
+    use etffi::ptr_magic::PtrMagic;
+    use pixelscript::{
+        pxs_coroutine_create, pxs_coroutine_free, pxs_coroutine_resume, pxs_coroutine_status, pxs_error_free, pxs_eval,
+        pxs_exec, pxs_exec_ex, pxs_finalize, pxs_initialize, pxs_set_tick_callback,
+        shared::{
+            coroutine::pxs_CoroutineStatus,
+            error::{pxs_Error, pxs_ErrorCode},
+            pxs_Runtime,
+            var::{pxs_Var, pxs_VarT},
+        },
+    };
+
+    static TICKS: AtomicU64 = AtomicU64::new(0);
+    static SUSPEND: AtomicBool = AtomicBool::new(false);
+
+    unsafe extern "C" fn tick(_runtime: pxs_Runtime, instructions: u64) -> bool {
+        assert!(instructions > 0);
+        TICKS.fetch_add(1, Ordering::SeqCst);
+        SUSPEND.load(Ordering::SeqCst)
+    }
+
+    fn exec(runtime: pxs_Runtime, code: &str) -> pxs_Var {
+        let code = CString::new(code).unwrap();
+        let file_name = CString::new("tick.script").unwrap();
+        pxs_Var::from_raw(pxs_exec(runtime, code.as_ptr(), file_name.as_ptr()))
+    }
+
+    /// Run code and check that it was suspended.
+    fn suspended(runtime: pxs_Runtime, code: &str) {
+        let code = CString::new(code).unwrap();
+        let file_name = CString::new("tick.script").unwrap();
+        let err = pxs_exec_ex(runtime, code.as_ptr(), file_name.as_ptr());
+        assert!(!err.is_null());
+
+        let e: &pxs_Error = unsafe { &*err };
+        let message = unsafe { CStr::from_ptr(e.message) }.to_str().unwrap();
+        assert_eq!(e.code, pxs_ErrorCode::pxs_SuspendedError, "{message}");
+        pxs_error_free(err);
+    }
+
+    fn lua() {
+        // Called while the script runs.
+        TICKS.store(0, Ordering::SeqCst);
+        assert!(!exec(pxs_Runtime::pxs_Lua, "for i = 1, 100000 do end").is_exception());
+        assert!(TICKS.load(Ordering::SeqCst) > 10);
+
+        // `pxs_exec` can not yield, so it stops.
+        SUSPEND.store(true, Ordering::SeqCst);
+        suspended(pxs_Runtime::pxs_Lua, "while true do end");
+
+        // A coroutine yields and continues over several resumes.
+        assert!(!exec(pxs_Runtime::pxs_Lua, "function count() local n = 0 for i = 1, 100000 do n = n + 1 end return n end").is_exception());
+        let code = CString::new("return count").unwrap();
+        let count = pxs_Var::from_raw(pxs_eval(code.as_ptr(), pxs_Runtime::pxs_Lua));
+        let rt = pxs_Runtime::pxs_Lua.into_var();
+        let co = pxs_coroutine_create(&rt as *const pxs_Var as pxs_VarT, &count as *const pxs_Var as pxs_VarT, ptr::null_mut());
+        let mut resumes = 0;
+        let mut res = pxs_Var::new_null();
+        while pxs_coroutine_status(co) == pxs_CoroutineStatus::pxs_CoroutineSuspended {
+            res = pxs_Var::from_raw(pxs_coroutine_resume(co, ptr::null_mut()));
+            assert!(!res.is_exception(), "{:?}", res.get_string());
+            resumes += 1;
+        }
+        assert!(resumes > 10);
+        assert_eq!(res.get_i64().unwrap(), 100000);
+        pxs_coroutine_free(co);
+        drop(count);
+        SUSPEND.store(false, Ordering::SeqCst);
+    }
+
+    fn python() {
+        TICKS.store(0, Ordering::SeqCst);
+        assert!(!exec(pxs_Runtime::pxs_Python, "for i in range(100):\n    pass").is_exception());
+        assert!(TICKS.load(Ordering::SeqCst) > 10);
+
+        SUSPEND.store(true, Ordering::SeqCst);
+        suspended(pxs_Runtime::pxs_Python, "while True: pass");
+        SUSPEND.store(false, Ordering::SeqCst);
+    }
+
+    #[test]
+    fn run_test() {
+        pxs_initialize();
+
+        pxs_set_tick_callback(Some(tick), 10);
+        lua();
+        python();
+
+        // Removed.
+        pxs_set_tick_callback(None, 0);
+        TICKS.store(0, Ordering::SeqCst);
+        assert!(!exec(pxs_Runtime::pxs_Lua, "for i = 1, 100000 do end").is_exception());
+        assert_eq!(TICKS.load(Ordering::SeqCst), 0);
+
+        pxs_finalize();
+    }
+}
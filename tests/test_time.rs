@@ -0,0 +1,99 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_time --no-default-features --features "lua,python,js,include-core,testing" -- --nocapture --test-threads=1
+
+#[cfg(all(test, feature = "pxs_time"))]
+#[allow(unused)]
+mod tests {
+    use pixelscript::{
+        pxs_finalize, pxs_initialize, pxs_set_clock, pxs_time_allowsleep, pxs_timeinit,
+        shared::{pxs_Runtime, utils},
+    };
+
+    fn print_helper(lang: &str) {
+        println!("====================== {lang} ===================");
+    }
+
+    /// 2024-02-29 12:34:56.5 UTC, a Thursday.
+    unsafe extern "C" fn replay_clock() -> f64 {
+        1709210096.5
+    }
+
+    fn test_python() {
+        let script = r#"
+import pxs_time
+assert pxs_time.now() == 1709210096.5
+assert pxs_time.monotonic() == 1709210096.5
+assert pxs_time.format('%Y-%m-%d %H:%M:%S %a') == '2024-02-29 12:34:56 Thu'
+d = pxs_time.date(0)
+assert d['year'] == 1970 and d['month'] == 1 and d['day'] == 1 and d['weekday'] == 4
+try:
+    pxs_time.sleep(0)
+    assert False
+except Exception:
+    pass
+"#;
+        let err = utils::execute_code(script, "<py>", pxs_Runtime::pxs_Python);
+        assert!(err.is_null(), "Python error: {:#?}", err);
+    }
+
+    fn test_lua() {
+        let script = r#"
+local time = require('pxs_time')
+assert(time.now() == 1709210096.5)
+local d = time.date()
+assert(d.year == 2024 and d.month == 2 and d.day == 29 and d.yday == 60)
+assert(time.format('%j %B', 1735689599) == '366 December')
+assert(not pcall(time.format, '%Q'))
+"#;
+        let err = utils::execute_code(script, "<lua>", pxs_Runtime::pxs_Lua);
+        assert!(err.is_null(), "Lua error: {:#?}", err);
+    }
+
+    fn test_js() {
+        let script = r#"
+import { now, format } from 'pxs_time';
+if (now() !== 1709210096.5) throw new Error("now");
+if (format('%d/%m/%y') !== '29/02/24') throw new Error("format");
+"#;
+        let err = utils::execute_code(script, "<js>", pxs_Runtime::pxs_JavaScript);
+        assert!(err.is_null(), "JS error: {:#?}", err);
+    }
+
+    #[test]
+    fn run_test() {
+        println!();
+        pxs_initialize();
+        pxs_timeinit();
+        pxs_set_clock(Some(replay_clock));
+        utils::setup_pxs();
+
+        print_helper("PYTHON");
+        test_python();
+        print_helper("LUA");
+        test_lua();
+        print_helper("JS");
+        test_js();
+
+        // System clock and sleeping.
+        pxs_set_clock(None);
+        pxs_time_allowsleep(true);
+        let script = r#"
+local time = require('pxs_time')
+assert(time.now() > 1709210096)
+local start = time.monotonic()
+time.sleep(0.01)
+assert(time.monotonic() - start >= 0.01)
+"#;
+        let err = utils::execute_code(script, "<lua>", pxs_Runtime::pxs_Lua);
+        assert!(err.is_null(), "Lua error: {:#?}", err);
+
+        pxs_finalize();
+    }
+}
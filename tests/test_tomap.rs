@@ -0,0 +1,154 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_tomap --no-default-features --features "lua,python,js,testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use etffi::{cstring::CStringSafe, ptr_magic::PtrMagic};
+    use pixelscript::{
+        pxs_addmod, pxs_finalize, pxs_initialize, pxs_listget, pxs_mapset, pxs_newmap, pxs_tomap,
+        shared::{
+            pxs_Runtime, utils,
+            var::{pxs_Var, pxs_VarT, pxs_VarType},
+        },
+    };
+
+    fn print_helper(lang: &str) {
+        println!("====================== {lang} ===================");
+    }
+
+    /// `key=kind` of every entry, sorted. Nested maps are described in braces.
+    fn describe_map(map: &pxs_Var) -> String {
+        let map = map.get_map().unwrap();
+        let mut parts: Vec<String> = map
+            .keys()
+            .into_iter()
+            .map(|key| {
+                let key_text = match key.tag {
+                    pxs_VarType::pxs_Int64 => format!("#{}", key.get_i64().unwrap()),
+                    _ => key.get_string().unwrap(),
+                };
+                let value = map.get_item(key).unwrap();
+                let kind = match value.tag {
+                    pxs_VarType::pxs_Map => describe_map(value),
+                    pxs_VarType::pxs_List => format!("list{}", value.get_list().unwrap().len()),
+                    pxs_VarType::pxs_String => "str".to_string(),
+                    pxs_VarType::pxs_Int64 => "int".to_string(),
+                    pxs_VarType::pxs_Bool => "bool".to_string(),
+                    _ => format!("{:?}", value.tag),
+                };
+                format!("{key_text}={kind}")
+            })
+            .collect();
+        parts.sort();
+        format!("{{{}}}", parts.join(","))
+    }
+
+    /// `describe(t)`, what the host gets from `pxs_tomap`.
+    extern "C" fn describe(args: pxs_VarT) -> pxs_VarT {
+        let map = pxs_Var::from_raw(pxs_tomap(pxs_listget(args, 0), pxs_listget(args, 1)));
+        if map.is_exception() {
+            return map.into_raw();
+        }
+        pxs_Var::new_string(describe_map(&map)).into_raw()
+    }
+
+    /// `tagged(t)`, a copy of `t` with `seen = true`.
+    extern "C" fn tagged(args: pxs_VarT) -> pxs_VarT {
+        let map = pxs_tomap(pxs_listget(args, 0), pxs_listget(args, 1));
+        let key = pxs_Var::new_string("seen".to_string());
+        let value = pxs_Var::new_bool(true);
+        if !pxs_mapset(map, &key as *const pxs_Var as pxs_VarT, &value as *const pxs_Var as pxs_VarT) {
+            let _ = pxs_Var::from_raw(map);
+            return pxs_Var::new_exception("pxs_mapset failed").into_raw();
+        }
+        map
+    }
+
+    const EXPECTED: &str = "{hp=int,name=str,pos={x=int,y=int},tags=list2}";
+
+    fn test_lua() {
+        let script = r#"
+local maps = require('maps')
+local t = { name = 'a', hp = 10, pos = { x = 1, y = 2 }, tags = { 'a', 'b' } }
+assert(maps.describe(t) == EXPECTED, maps.describe(t))
+assert(maps.describe({ [5] = 'five', on = true }) == '{#5=str,on=bool}')
+local copy = maps.tagged(t)
+assert(copy.seen == true and copy.pos.y == 2)
+-- A copy, the table itself is unchanged.
+assert(t.seen == nil)
+assert(not pcall(maps.describe, 1))
+"#
+        .replace("EXPECTED", &format!("'{EXPECTED}'"));
+        let err = utils::execute_code(&script, "<lua>", pxs_Runtime::pxs_Lua);
+        assert!(err.is_null(), "Lua error: {:#?}", err);
+    }
+
+    fn test_python() {
+        let script = r#"
+import maps
+d = {'name': 'a', 'hp': 10, 'pos': {'x': 1, 'y': 2}, 'tags': ['a', 'b']}
+assert maps.describe(d) == EXPECTED, maps.describe(d)
+# Dict keys win over dict methods.
+assert maps.describe({'items': 1, 5: 'five'}) == '{#5=str,items=int}'
+copy = maps.tagged(d)
+assert copy['seen'] == True and copy['pos']['y'] == 2
+assert 'seen' not in d
+"#
+        .replace("EXPECTED", &format!("'{EXPECTED}'"));
+        let err = utils::execute_code(&script, "<py>", pxs_Runtime::pxs_Python);
+        assert!(err.is_null(), "Python error: {:#?}", err);
+    }
+
+    fn test_js() {
+        let script = r#"
+import { describe, tagged } from 'maps';
+const o = { name: 'a', hp: 10, pos: { x: 1, y: 2 }, tags: ['a', 'b'] };
+if (describe(o) !== EXPECTED) throw new Error(describe(o));
+const copy = tagged(o);
+if (copy.seen !== true || copy.pos.y !== 2 || o.seen !== undefined) throw new Error("tagged");
+"#
+        .replace("EXPECTED", &format!("'{EXPECTED}'"));
+        let err = utils::execute_code(&script, "<js>", pxs_Runtime::pxs_JavaScript);
+        assert!(err.is_null(), "JS error: {:#?}", err);
+    }
+
+    #[test]
+    fn run_test() {
+        println!();
+        pxs_initialize();
+        let module = utils::create_module("maps");
+        utils::add_function(module, "describe", describe);
+        utils::add_function(module, "tagged", tagged);
+        pxs_addmod(module);
+
+        // Host maps.
+        let map = pxs_newmap();
+        let key = pxs_Var::new_string("a".to_string());
+        let (one, two) = (pxs_Var::new_i64(1), pxs_Var::new_i64(2));
+        let key_ptr = &key as *const pxs_Var as pxs_VarT;
+        assert!(pxs_mapset(map, key_ptr, &one as *const pxs_Var as pxs_VarT));
+        assert!(pxs_mapset(map, key_ptr, &two as *const pxs_Var as pxs_VarT));
+        let list = pxs_Var::new_list();
+        assert!(!pxs_mapset(map, &list as *const pxs_Var as pxs_VarT, key_ptr));
+        let map = pxs_Var::from_raw(map);
+        assert_eq!(map.get_map().unwrap().len(), 1);
+        assert_eq!(map.get_map().unwrap().get_item(&key).unwrap().get_i64().unwrap(), 2);
+
+        print_helper("LUA");
+        test_lua();
+        print_helper("PYTHON");
+        test_python();
+        print_helper("JS");
+        test_js();
+
+        pxs_finalize();
+    }
+}
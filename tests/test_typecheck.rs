@@ -0,0 +1,128 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_typecheck --no-default-features --features "lua,testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use std::{
+        ffi::{CStr, CString, c_void},
+        ptr,
+    };
+
+    use etffi::ptr_magic::PtrMagic;
+    use pixelscript::{
+        pxs_addfunc, pxs_addmod, pxs_addobject, pxs_class_setparent, pxs_finalize, pxs_freestr, pxs_gethost_checked,
+        pxs_initialize, pxs_newhost, pxs_newobject, pxs_object_typename,
+        shared::{
+            pxs_Runtime, utils,
+            var::{pxs_Var, pxs_VarT},
+        },
+    };
+
+    struct Animal {
+        sound: String,
+    }
+
+    impl PtrMagic for Animal {}
+
+    extern "C" fn free_animal(ptr: *mut c_void) {
+        let _ = Animal::from_raw(ptr as *mut Animal);
+    }
+
+    fn arg<'a>(args: pxs_VarT, idx: i32) -> &'a pxs_Var {
+        unsafe { pxs_Var::from_borrow(args) }.get_list().unwrap().get_item(idx).unwrap()
+    }
+
+    fn var_ptr(var: &pxs_Var) -> pxs_VarT {
+        var as *const pxs_Var as pxs_VarT
+    }
+
+    fn animal(type_name: &str, sound: &str) -> pxs_VarT {
+        let type_name = CString::new(type_name).unwrap();
+        let ptr = Animal { sound: sound.to_string() }.into_raw() as *mut c_void;
+        pxs_newhost(pxs_newobject(ptr, free_animal, type_name.as_ptr()))
+    }
+
+    extern "C" fn new_cat(_args: pxs_VarT) -> pxs_VarT {
+        animal("Cat", "purr")
+    }
+
+    extern "C" fn new_kitten(_args: pxs_VarT) -> pxs_VarT {
+        animal("Kitten", "mew")
+    }
+
+    extern "C" fn new_dog(_args: pxs_VarT) -> pxs_VarT {
+        animal("Dog", "woof")
+    }
+
+    fn type_name(runtime: &pxs_Var, var: &pxs_Var) -> String {
+        let name = pxs_object_typename(var_ptr(runtime), var_ptr(var));
+        if name.is_null() {
+            return "nothing".to_string();
+        }
+        let res = unsafe { CStr::from_ptr(name) }.to_string_lossy().to_string();
+        pxs_freestr(name);
+        res
+    }
+
+    /// `pet(cat)`, only takes cats.
+    extern "C" fn pet(args: pxs_VarT) -> pxs_VarT {
+        let (runtime, var) = (arg(args, 0), arg(args, 1));
+        let ptr = pxs_gethost_checked(var_ptr(runtime), var_ptr(var), c"Cat".as_ptr());
+        if ptr.is_null() {
+            return pxs_Var::new_exception(format!("expected a Cat, got {}", type_name(runtime, var))).into_raw();
+        }
+        let cat = unsafe { &*(ptr as *const Animal) };
+        pxs_Var::new_string(cat.sound.clone()).into_raw()
+    }
+
+    fn run(script: &str) {
+        let err = utils::execute_code(script, "<typecheck>", pxs_Runtime::pxs_Lua);
+        assert!(err.is_null(), "Lua error: {:#?}", err);
+    }
+
+    #[test]
+    fn run_test() {
+        pxs_initialize();
+        assert!(pxs_class_setparent(c"Kitten".as_ptr(), c"Cat".as_ptr()));
+
+        let module = utils::create_module("zoo");
+        for (name, func) in [("Cat", new_cat as extern "C" fn(pxs_VarT) -> pxs_VarT), ("Kitten", new_kitten), ("Dog", new_dog)] {
+            let name = CString::new(name).unwrap();
+            pxs_addobject(module, name.as_ptr(), func);
+        }
+        pxs_addfunc(module, c"pet".as_ptr(), pet);
+        pxs_addmod(module);
+
+        run(
+            r#"
+local zoo = require('zoo')
+assert(zoo.pet(zoo.Cat()) == 'purr')
+assert(zoo.pet(zoo.Kitten()) == 'mew')
+
+local ok, err = pcall(zoo.pet, zoo.Dog())
+assert(not ok and string.find(err, 'expected a Cat, got Dog'), err)
+ok, err = pcall(zoo.pet, 'cat')
+assert(not ok and string.find(err, 'got nothing'), err)
+ok, err = pcall(zoo.pet, {})
+assert(not ok and string.find(err, 'got nothing'), err)
+"#,
+        );
+
+        // Host objects directly.
+        let runtime = pxs_Var::new_i64(pxs_Runtime::pxs_Lua as i64);
+        let dog = pxs_Var::from_raw(new_dog(ptr::null_mut()));
+        assert_eq!(type_name(&runtime, &dog), "Dog");
+        assert!(pxs_gethost_checked(var_ptr(&runtime), var_ptr(&dog), c"Cat".as_ptr()).is_null());
+        assert!(!pxs_gethost_checked(var_ptr(&runtime), var_ptr(&dog), c"Dog".as_ptr()).is_null());
+
+        pxs_finalize();
+    }
+}
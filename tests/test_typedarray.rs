@@ -0,0 +1,97 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_typedarray --no-default-features --features "lua,python,testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use std::ffi::CString;
+
+    use etffi::ptr_magic::PtrMagic;
+    use pixelscript::{
+        pxs_copybytes, pxs_eval, pxs_finalize, pxs_freevar, pxs_gettypedarray, pxs_initialize, pxs_newtypedarray,
+        pxs_setglobal, pxs_typedarray_fromlist, pxs_typedarray_tolist, pxs_typedarraylen, pxs_varis, pxs_varsize,
+        shared::{
+            pxs_Opaque, pxs_Runtime,
+            var::{pxs_ArrayKind, pxs_Var, pxs_VarType},
+        },
+    };
+
+    fn eval(runtime: pxs_Runtime, code: &str) -> pxs_Var {
+        let code = CString::new(code).unwrap();
+        pxs_Var::from_raw(pxs_eval(code.as_ptr(), runtime))
+    }
+
+    /// Scripts get a list of numbers, the host converts it back.
+    fn check(runtime: pxs_Runtime, double: &str) {
+        let samples = [1.5f32, -2.0, 4.25];
+        let name = CString::new("samples").unwrap();
+        let array = pxs_newtypedarray(pxs_ArrayKind::pxs_ArrayF32, samples.as_ptr() as pxs_Opaque, samples.len());
+        assert!(pxs_setglobal(runtime.clone(), name.as_ptr(), array));
+
+        let doubled = eval(runtime, double);
+        assert!(doubled.is_list(), "{doubled:?}");
+        let back = pxs_Var::from_raw(pxs_typedarray_fromlist(pxs_ArrayKind::pxs_ArrayF32, &doubled as *const pxs_Var as *mut pxs_Var));
+        let mut len = 0;
+        let data = pxs_gettypedarray(&back as *const pxs_Var as *mut pxs_Var, std::ptr::null_mut(), &mut len);
+        let data = unsafe { std::slice::from_raw_parts(data as *const f32, len) };
+        assert_eq!(data, &[3.0, -4.0, 8.5]);
+    }
+
+    #[test]
+    fn run_test() {
+        pxs_initialize();
+
+        // Copy from the host and write in place.
+        let values = [1i32, 2, 3, 4];
+        let array = pxs_newtypedarray(pxs_ArrayKind::pxs_ArrayI32, values.as_ptr() as pxs_Opaque, values.len());
+        assert!(pxs_varis(array, pxs_VarType::pxs_TypedArray));
+        assert_eq!(pxs_typedarraylen(array), 4);
+        let mut kind = pxs_ArrayKind::pxs_ArrayU8;
+        let mut len = 0;
+        let data = pxs_gettypedarray(array, &mut kind, &mut len) as *mut i32;
+        assert_eq!(kind, pxs_ArrayKind::pxs_ArrayI32);
+        assert_eq!(len, 4);
+        unsafe { *data.add(2) = 30 };
+
+        let borrowed = unsafe { pxs_Var::from_borrow(array) };
+        let copy = borrowed.clone();
+        assert_eq!(copy.get_typed_array().unwrap().get(2).unwrap().get_i64().unwrap(), 30);
+        assert_eq!(&copy, borrowed);
+
+        // Raw bytes
+        assert_eq!(pxs_varsize(array), 16);
+        let mut out = [0i32; 4];
+        pxs_copybytes(array, out.as_mut_ptr() as pxs_Opaque);
+        assert_eq!(out, [1, 2, 30, 4]);
+
+        // Null data makes zeros.
+        let zeros = pxs_newtypedarray(pxs_ArrayKind::pxs_ArrayF64, std::ptr::null_mut(), 3);
+        assert_eq!(pxs_typedarraylen(zeros), 3);
+        let list = pxs_Var::from_raw(pxs_typedarray_tolist(zeros));
+        assert_eq!(list.get_list().unwrap().vars.iter().map(|v| v.get_f64().unwrap()).collect::<Vec<_>>(), [0.0; 3]);
+
+        // Lists convert with wrapping, anything else fails.
+        let list = pxs_Var::new_list_with(vec![pxs_Var::new_i64(300), pxs_Var::new_f64(2.9)]);
+        let bytes = pxs_Var::from_raw(pxs_typedarray_fromlist(pxs_ArrayKind::pxs_ArrayU8, &list as *const pxs_Var as *mut pxs_Var));
+        assert_eq!(bytes.get_typed_array().unwrap().as_bytes(), &[44, 2]);
+        let bad = pxs_Var::new_list_with(vec![pxs_Var::new_string("a".to_string())]);
+        assert!(pxs_Var::from_raw(pxs_typedarray_fromlist(pxs_ArrayKind::pxs_ArrayU8, &bad as *const pxs_Var as *mut pxs_Var)).is_exception());
+        assert!(pxs_gettypedarray(&bad as *const pxs_Var as *mut pxs_Var, std::ptr::null_mut(), std::ptr::null_mut()).is_null());
+        assert_eq!(pxs_typedarraylen(&bad as *const pxs_Var as *mut pxs_Var), -1);
+
+        pxs_freevar(array);
+        pxs_freevar(zeros);
+
+        check(pxs_Runtime::pxs_Lua, "local res = {} for i, v in ipairs(samples) do res[i] = v * 2 end return res");
+        check(pxs_Runtime::pxs_Python, "[v * 2 for v in samples]");
+
+        pxs_finalize();
+    }
+}
@@ -0,0 +1,207 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_ui --no-default-features --features "lua,python,js,include-core,testing" -- --nocapture --test-threads=1
+
+#[cfg(all(test, feature = "pxs_ui"))]
+#[allow(unused)]
+mod tests {
+    use std::{
+        ffi::{CStr, c_char},
+        sync::Mutex,
+    };
+
+    use etffi::{cstring::CStringSafe, ptr_magic::PtrMagic};
+    use pixelscript::{
+        pxs_addfunc, pxs_addmod, pxs_finalize, pxs_initialize, pxs_newmod, pxs_ui_clear, pxs_ui_poll,
+        pxs_ui_setcallback, pxs_uiinit,
+        shared::{
+            pxs_Runtime, utils,
+            var::{pxs_Var, pxs_VarT, pxs_VarType},
+        },
+    };
+
+    /// `widget=value` of every notification. Numbers and null are written out, other values are `obj`.
+    static EVENTS: Mutex<Vec<String>> = Mutex::new(vec![]);
+
+    fn print_helper(lang: &str) {
+        println!("====================== {lang} ===================");
+    }
+
+    unsafe extern "C" fn on_change(widget_id: *const c_char, value: pxs_VarT) {
+        let widget_id = unsafe { CStr::from_ptr(widget_id) }.to_string_lossy().to_string();
+        let value = unsafe { pxs_Var::from_borrow(value) };
+        let value = match value.tag {
+            pxs_VarType::pxs_Int64 => value.get_i64().unwrap().to_string(),
+            pxs_VarType::pxs_Float64 => (value.get_f64().unwrap() as i64).to_string(),
+            pxs_VarType::pxs_Null => "null".to_string(),
+            _ => "obj".to_string(),
+        };
+        EVENTS.lock().unwrap().push(format!("{widget_id}={value}"));
+    }
+
+    /// `host.poll()`, polls through the C API.
+    extern "C" fn host_poll(_args: pxs_VarT) -> pxs_VarT {
+        pxs_Var::new_i64(pxs_ui_poll() as i64).into_raw()
+    }
+
+    /// `host.clear()`, drops every binding through the C API.
+    extern "C" fn host_clear(_args: pxs_VarT) -> pxs_VarT {
+        pxs_ui_clear();
+        pxs_Var::new_null().into_raw()
+    }
+
+    /// Every language runs the same steps, so every language sends the same notifications.
+    fn check_events(lang: &str) {
+        let events = std::mem::take(&mut *EVENTS.lock().unwrap());
+        assert_eq!(
+            events,
+            vec!["hp=3", "pos=obj", "tags=obj", "empty=null", "hp=4", "pos=obj", "hp=5"],
+            "{lang} notifications"
+        );
+    }
+
+    fn test_python() {
+        let script = r#"
+import pxs_ui
+import host
+
+state = {'hp': 3, 'pos': {'x': 1}, 'tags': ['a'], 'empty': None}
+hp = pxs_ui.bind('hp', state, 'hp')
+pxs_ui.bind('pos', state, 'pos')
+pxs_ui.bind('tags', state, 'tags')
+pxs_ui.bind('empty', state, 'empty')
+
+# Nothing changed, nested values included.
+assert pxs_ui.poll() == 0
+
+# Plain writes show up at the next poll, once.
+state['hp'] = 4
+state['pos']['x'] = 2
+assert pxs_ui.poll() == 2
+assert host.poll() == 0
+
+# set() notifies right away.
+pxs_ui.set(state, 'hp', 5)
+assert pxs_ui.poll() == 0
+
+assert pxs_ui.unbind(hp)
+assert not pxs_ui.unbind(hp)
+state['hp'] = 6
+assert pxs_ui.poll() == 0
+
+host.clear()
+state['pos']['x'] = 3
+state['tags'].append('b')
+assert host.poll() == 0
+"#;
+        let err = utils::execute_code(script, "<py>", pxs_Runtime::pxs_Python);
+        assert!(err.is_null(), "Python error: {:#?}", err);
+    }
+
+    fn test_lua() {
+        let script = r#"
+local pxs_ui = require('pxs_ui')
+local host = require('host')
+
+local state = { hp = 3, pos = { x = 1 }, tags = { 'a' }, empty = nil }
+local hp = pxs_ui.bind('hp', state, 'hp')
+pxs_ui.bind('pos', state, 'pos')
+pxs_ui.bind('tags', state, 'tags')
+pxs_ui.bind('empty', state, 'empty')
+
+assert(pxs_ui.poll() == 0)
+
+state.hp = 4
+state.pos.x = 2
+assert(pxs_ui.poll() == 2)
+assert(host.poll() == 0)
+
+pxs_ui.set(state, 'hp', 5)
+assert(pxs_ui.poll() == 0)
+
+assert(pxs_ui.unbind(hp))
+assert(not pxs_ui.unbind(hp))
+state.hp = 6
+assert(pxs_ui.poll() == 0)
+
+host.clear()
+state.pos.x = 3
+table.insert(state.tags, 'b')
+assert(host.poll() == 0)
+"#;
+        let err = utils::execute_code(script, "<lua>", pxs_Runtime::pxs_Lua);
+        assert!(err.is_null(), "Lua error: {:#?}", err);
+    }
+
+    fn test_js() {
+        let script = r#"
+import { bind, unbind, set, poll } from 'pxs_ui';
+import * as host from 'host';
+
+const check = (ok, msg) => { if (!ok) throw new Error(msg); };
+
+const state = { hp: 3, pos: { x: 1 }, tags: ['a'], empty: null };
+const hp = bind('hp', state, 'hp');
+bind('pos', state, 'pos');
+bind('tags', state, 'tags');
+bind('empty', state, 'empty');
+
+check(poll() === 0, 'no change');
+
+state.hp = 4;
+state.pos.x = 2;
+check(poll() === 2, 'writes');
+check(host.poll() === 0, 'second poll');
+
+set(state, 'hp', 5);
+check(poll() === 0, 'set');
+
+check(unbind(hp), 'unbind');
+check(!unbind(hp), 'unbind twice');
+state.hp = 6;
+check(poll() === 0, 'unbound');
+
+host.clear();
+state.pos.x = 3;
+state.tags.push('b');
+check(host.poll() === 0, 'clear');
+"#;
+        let err = utils::execute_code(script, "<js>", pxs_Runtime::pxs_JavaScript);
+        assert!(err.is_null(), "JS error: {:#?}", err);
+    }
+
+    #[test]
+    fn run_test() {
+        println!();
+        pxs_initialize();
+        pxs_uiinit();
+        pxs_ui_setcallback(on_change);
+
+        let mut cstrgen = CStringSafe::new();
+        let host = pxs_newmod(cstrgen.new_string("host"));
+        pxs_addfunc(host, cstrgen.new_string("poll"), host_poll);
+        pxs_addfunc(host, cstrgen.new_string("clear"), host_clear);
+        pxs_addmod(host);
+
+        print_helper("PYTHON");
+        test_python();
+        check_events("Python");
+        print_helper("LUA");
+        test_lua();
+        check_events("Lua");
+        print_helper("JS");
+        test_js();
+        check_events("JS");
+
+        // Nothing is bound anymore.
+        assert_eq!(pxs_ui_poll(), 0);
+
+        pxs_finalize();
+    }
+}
@@ -0,0 +1,74 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_uninit --no-default-features --features "lua,testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use std::ffi::{CStr, CString};
+
+    use etffi::ptr_magic::PtrMagic;
+    use pixelscript::{
+        pxs_exec, pxs_exec_ex, pxs_finalize, pxs_freestr, pxs_initialize, pxs_is_initialized, pxs_last_error,
+        pxs_error_free, pxs_newmod,
+        shared::{error::pxs_ErrorCode, pxs_Runtime, var::pxs_Var},
+    };
+
+    /// Take the last error as a String.
+    fn last_error() -> Option<String> {
+        let err = pxs_last_error();
+        if err.is_null() {
+            return None;
+        }
+        let msg = unsafe { CStr::from_ptr(err) }.to_string_lossy().to_string();
+        pxs_freestr(err);
+        Some(msg)
+    }
+
+    fn exec(code: &str) -> pxs_Var {
+        let code = CString::new(code).unwrap();
+        let name = CString::new("<uninit>").unwrap();
+        let res = pxs_exec(pxs_Runtime::pxs_Lua, code.as_ptr(), name.as_ptr());
+        if res.is_null() { pxs_Var::new_null() } else { pxs_Var::from_raw(res) }
+    }
+
+    /// Calls before `pxs_initialize` and after `pxs_finalize` fail instead of panicking.
+    fn check_uninitialized() {
+        assert!(!pxs_is_initialized());
+
+        let name = CString::new("mod").unwrap();
+        assert!(pxs_newmod(name.as_ptr()).is_null());
+        assert!(last_error().unwrap().contains("not initialized"));
+        assert!(last_error().is_none());
+
+        assert!(exec("x = 1").is_exception());
+
+        let code = CString::new("x = 1").unwrap();
+        let err = pxs_exec_ex(pxs_Runtime::pxs_Lua, code.as_ptr(), name.as_ptr());
+        assert!(!err.is_null());
+        assert_eq!(unsafe { &*err }.code, pxs_ErrorCode::pxs_NotInitialized);
+        pxs_error_free(err);
+
+        pxs_finalize();
+        assert!(last_error().is_some());
+    }
+
+    #[test]
+    fn run_test() {
+        check_uninitialized();
+
+        pxs_initialize();
+        assert!(pxs_is_initialized());
+        assert!(exec("x = 1").is_null());
+        assert!(last_error().is_none());
+        pxs_finalize();
+
+        check_uninitialized();
+    }
+}
@@ -0,0 +1,67 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_usage --no-default-features --features "lua,testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use std::ffi::CString;
+
+    use pixelscript::{
+        pxs_addmod, pxs_finalize, pxs_freevar, pxs_getint, pxs_initialize, pxs_mapget, pxs_newnull,
+        pxs_newstring, pxs_usage_record, pxs_usage_report,
+        shared::{pxs_Runtime, utils, var::pxs_VarT},
+    };
+
+    extern "C" fn used(_args: pxs_VarT) -> pxs_VarT {
+        pxs_newnull()
+    }
+
+    extern "C" fn unused(_args: pxs_VarT) -> pxs_VarT {
+        pxs_newnull()
+    }
+
+    /// Get the number of calls for `name` in a report.
+    fn calls(report: pxs_VarT, name: &str) -> i64 {
+        let name = CString::new(name).unwrap();
+        let key = pxs_newstring(name.as_ptr());
+        let calls = pxs_getint(pxs_mapget(report, key));
+        pxs_freevar(key);
+        calls
+    }
+
+    #[test]
+    fn run_test() {
+        pxs_initialize();
+        let test_module = utils::create_module("test");
+        utils::add_function(test_module, "used", used);
+        utils::add_function(test_module, "unused", unused);
+        pxs_addmod(test_module);
+
+        pxs_usage_record(true);
+        let script = r#"
+local test = require('test')
+test.used()
+test.used()
+"#;
+        let err = utils::execute_code(script, "<lua>", pxs_Runtime::pxs_Lua);
+        assert!(err.is_null(), "Lua error: {:#?}", err);
+        pxs_usage_record(false);
+
+        let err = utils::execute_code("require('test').used()", "<lua>", pxs_Runtime::pxs_Lua);
+        assert!(err.is_null(), "Lua error: {:#?}", err);
+
+        let report = pxs_usage_report();
+        assert_eq!(calls(report, "test.used"), 2);
+        assert_eq!(calls(report, "test.unused"), 0);
+        pxs_freevar(report);
+
+        pxs_finalize();
+    }
+}
@@ -0,0 +1,74 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_utf16 --no-default-features --features "lua,testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use std::ptr;
+
+    use etffi::ptr_magic::PtrMagic;
+    use pixelscript::{
+        pxs_finalize, pxs_freestr_utf16, pxs_getstring_utf16, pxs_initialize, pxs_newint, pxs_newstring_utf16,
+        pxs_set_utf16_policy,
+        shared::{utf16::pxs_Utf16Policy, var::pxs_Var},
+    };
+
+    fn new_string(units: &[u16]) -> pxs_Var {
+        pxs_Var::from_raw(pxs_newstring_utf16(units.as_ptr(), units.len()))
+    }
+
+    /// Round trip a string through `pxs_getstring_utf16`.
+    fn get_utf16(var: &pxs_Var) -> Option<Vec<u16>> {
+        let mut len = 0;
+        let raw = pxs_getstring_utf16(var as *const pxs_Var as *mut pxs_Var, &mut len);
+        if raw.is_null() {
+            return None;
+        }
+        let units = unsafe { std::slice::from_raw_parts(raw, len + 1) }.to_vec();
+        pxs_freestr_utf16(raw);
+        assert_eq!(units.last(), Some(&0));
+        Some(units[..len].to_vec())
+    }
+
+    #[test]
+    fn run_test() {
+        pxs_initialize();
+
+        // Round trip, including surrogate pairs.
+        let text = "héllo 🎮";
+        let units: Vec<u16> = text.encode_utf16().collect();
+        let var = new_string(&units);
+        assert_eq!(var.get_string().unwrap(), text);
+        assert_eq!(get_utf16(&var).unwrap(), units);
+
+        assert_eq!(pxs_Var::from_raw(pxs_newstring_utf16(ptr::null(), 0)).get_string().unwrap(), "");
+        assert!(pxs_Var::from_raw(pxs_newstring_utf16(ptr::null(), 3)).is_exception());
+
+        // Unpaired surrogates and nulls
+        let invalid = [0x61, 0xD800, 0x62, 0, 0x63];
+        assert_eq!(new_string(&invalid).get_string().unwrap(), "a\u{FFFD}b\u{FFFD}c");
+
+        pxs_set_utf16_policy(pxs_Utf16Policy::pxs_Utf16Skip);
+        assert_eq!(new_string(&invalid).get_string().unwrap(), "abc");
+
+        pxs_set_utf16_policy(pxs_Utf16Policy::pxs_Utf16Error);
+        assert!(new_string(&invalid).is_exception());
+        assert_eq!(new_string(&units).get_string().unwrap(), text);
+
+        pxs_set_utf16_policy(pxs_Utf16Policy::pxs_Utf16Replace);
+
+        // Not a string
+        let int = pxs_Var::new_i64(1);
+        assert!(get_utf16(&int).is_none());
+        pxs_freestr_utf16(ptr::null_mut());
+
+        pxs_finalize();
+    }
+}
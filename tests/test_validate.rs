@@ -0,0 +1,120 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_validate --no-default-features --features "testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use etffi::ptr_magic::PtrMagic;
+    use pixelscript::{
+        pxs_validate,
+        shared::var::{pxs_Var, pxs_VarType},
+    };
+
+    fn s(val: &str) -> pxs_Var {
+        pxs_Var::new_string(val.to_string())
+    }
+
+    fn map(items: Vec<(&str, pxs_Var)>) -> pxs_Var {
+        let var = pxs_Var::new_map();
+        let map = var.get_map().unwrap();
+        for (key, value) in items {
+            map.add_item(s(key), value);
+        }
+        var
+    }
+
+    /// `{ name: string, hp: int 1..=100, tags: [string] }` with `name` and `hp` required.
+    fn unit_schema() -> pxs_Var {
+        map(vec![
+            ("type", s("map")),
+            ("required", pxs_Var::new_list_with(vec![s("name"), s("hp")])),
+            (
+                "keys",
+                map(vec![
+                    ("name", map(vec![("type", s("string")), ("min", pxs_Var::new_i64(1))])),
+                    (
+                        "hp",
+                        map(vec![("type", s("int")), ("min", pxs_Var::new_i64(1)), ("max", pxs_Var::new_i64(100))]),
+                    ),
+                    ("tags", map(vec![("type", s("list")), ("items", map(vec![("type", s("string"))]))])),
+                ]),
+            ),
+        ])
+    }
+
+    fn validate(var: &pxs_Var, schema: &pxs_Var) -> pxs_Var {
+        let res = pxs_validate(
+            var as *const pxs_Var as *mut pxs_Var,
+            schema as *const pxs_Var as *mut pxs_Var,
+        );
+        pxs_Var::from_raw(res)
+    }
+
+    fn errors(var: &pxs_Var, schema: &pxs_Var) -> Vec<String> {
+        let res = validate(var, schema);
+        assert!(res.is_list(), "Expected a list: {:#?}", res);
+        res.get_list().unwrap().vars.iter().map(|e| e.get_string().unwrap()).collect()
+    }
+
+    #[test]
+    fn test_valid() {
+        let unit = map(vec![
+            ("name", s("Knight")),
+            ("hp", pxs_Var::new_i64(100)),
+            ("tags", pxs_Var::new_list_with(vec![s("melee")])),
+        ]);
+        assert!(errors(&unit, &unit_schema()).is_empty());
+    }
+
+    #[test]
+    fn test_errors() {
+        let units = pxs_Var::new_list_with(vec![
+            map(vec![("name", s("Knight")), ("hp", pxs_Var::new_i64(10))]),
+            map(vec![
+                ("name", s("")),
+                ("hp", pxs_Var::new_i64(500)),
+                ("tags", pxs_Var::new_list_with(vec![s("ranged"), pxs_Var::new_i64(3)])),
+            ]),
+            map(vec![("hp", s("lots"))]),
+        ]);
+        let schema = map(vec![("type", s("list")), ("max", pxs_Var::new_i64(2)), ("items", unit_schema())]);
+
+        assert_eq!(
+            errors(&units, &schema),
+            vec![
+                "$: must be at most 2, found 3.",
+                "$[1].hp: must be at most 100, found 500.",
+                "$[1].name: must be at least 1, found 0.",
+                "$[1].tags[1]: expected string, found int.",
+                "$[2]: missing required key \"name\".",
+                "$[2].hp: expected int, found string.",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_type_list() {
+        let schema = map(vec![("type", pxs_Var::new_list_with(vec![s("number"), s("null")]))]);
+        assert!(errors(&pxs_Var::new_f64(1.5), &schema).is_empty());
+        assert!(errors(&pxs_Var::new_null(), &schema).is_empty());
+        assert_eq!(errors(&s("1.5"), &schema), vec!["$: expected number or null, found string."]);
+    }
+
+    #[test]
+    fn test_invalid_schema() {
+        let res = validate(&pxs_Var::new_i64(1), &s("int"));
+        assert!(res.is_exception());
+
+        let schema = map(vec![("type", s("integer"))]);
+        let res = validate(&pxs_Var::new_i64(1), &schema);
+        assert!(res.is_exception());
+        assert_eq!(res.get_string().unwrap(), "Invalid schema at $.");
+    }
+}
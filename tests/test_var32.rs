@@ -0,0 +1,99 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_var32 --no-default-features --features "lua,python,testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use std::ffi::CString;
+
+    use etffi::ptr_magic::PtrMagic;
+    use pixelscript::{
+        pxs_copybytes, pxs_eval, pxs_finalize, pxs_freevar, pxs_frommsgpack, pxs_getfloat32, pxs_getint,
+        pxs_getint_checked, pxs_getint32, pxs_getuint32, pxs_initialize, pxs_newfloat32, pxs_newint32, pxs_newuint32,
+        pxs_setglobal, pxs_tomsgpack, pxs_varis, pxs_varsize,
+        shared::{
+            convert::{pxs_Convert, pxs_Status},
+            pxs_Opaque, pxs_Runtime,
+            var::{pxs_Var, pxs_VarType},
+        },
+    };
+
+    fn eval(runtime: pxs_Runtime, code: &str) -> pxs_Var {
+        let code = CString::new(code).unwrap();
+        pxs_Var::from_raw(pxs_eval(code.as_ptr(), runtime))
+    }
+
+    /// Scripts get regular numbers and give back the 64 bit types.
+    fn check(runtime: pxs_Runtime) {
+        let name = CString::new("n").unwrap();
+        assert!(pxs_setglobal(runtime.clone(), name.as_ptr(), pxs_newint32(-7)));
+        assert_eq!(eval(runtime.clone(), "n * 2").get_i64().unwrap(), -14);
+        assert!(pxs_setglobal(runtime.clone(), name.as_ptr(), pxs_newuint32(u32::MAX)));
+        assert_eq!(eval(runtime.clone(), "n + 1").get_i64().unwrap(), u32::MAX as i64 + 1);
+        assert!(pxs_setglobal(runtime.clone(), name.as_ptr(), pxs_newfloat32(0.5)));
+        assert_eq!(eval(runtime, "n * 2").get_f64().unwrap(), 1.0);
+    }
+
+    #[test]
+    fn run_test() {
+        pxs_initialize();
+
+        // Constructors and accessors
+        let int = pxs_newint32(-5);
+        let uint = pxs_newuint32(3_000_000_000);
+        let float = pxs_newfloat32(1.5);
+        assert!(pxs_varis(int, pxs_VarType::pxs_Int32));
+        assert!(pxs_varis(uint, pxs_VarType::pxs_UInt32));
+        assert!(pxs_varis(float, pxs_VarType::pxs_Float32));
+        assert_eq!(pxs_getint32(int), -5);
+        assert_eq!(pxs_getuint32(uint), 3_000_000_000);
+        assert_eq!(pxs_getfloat32(float), 1.5);
+        assert_eq!(pxs_getint(uint), 3_000_000_000);
+        assert_eq!(pxs_getfloat32(int), -5.0);
+
+        let mut out = 0;
+        assert_eq!(pxs_getint_checked(float, &mut out, pxs_Convert::pxs_ConvertError), pxs_Status::pxs_StatusInexact);
+        assert_eq!(pxs_getint_checked(uint, &mut out, pxs_Convert::pxs_ConvertError), pxs_Status::pxs_StatusOk);
+        assert_eq!(out, 3_000_000_000);
+
+        // Compact in memory
+        let list = pxs_Var::new_list_with(vec![pxs_Var::new_i32(1), pxs_Var::new_f32(2.0), pxs_Var::new_u32(3)]);
+        let list_ptr = &list as *const pxs_Var as *mut pxs_Var;
+        assert_eq!(pxs_varsize(list_ptr), 12);
+        let mut data = [0u8; 12];
+        pxs_copybytes(list_ptr, data.as_mut_ptr() as pxs_Opaque);
+        assert_eq!(i32::from_ne_bytes(data[0..4].try_into().unwrap()), 1);
+        assert_eq!(f32::from_ne_bytes(data[4..8].try_into().unwrap()), 2.0);
+        assert_eq!(u32::from_ne_bytes(data[8..12].try_into().unwrap()), 3);
+
+        // Equality and copies keep the type.
+        assert_eq!(pxs_Var::new_i32(4), pxs_Var::new_i32(4));
+        assert_ne!(pxs_Var::new_i32(4), pxs_Var::new_i64(4));
+        assert!(pxs_Var::new_f32(1.0).clone().is_f32());
+
+        // MessagePack keeps the value.
+        let packed = pxs_tomsgpack(list_ptr);
+        let unpacked = pxs_Var::from_raw(pxs_frommsgpack(packed));
+        pxs_freevar(packed);
+        let items = &unpacked.get_list().unwrap().vars;
+        assert_eq!(items[0].get_i64().unwrap(), 1);
+        assert_eq!(items[1].get_f64().unwrap(), 2.0);
+        assert_eq!(items[2].get_i64().unwrap(), 3);
+
+        pxs_freevar(int);
+        pxs_freevar(uint);
+        pxs_freevar(float);
+
+        check(pxs_Runtime::pxs_Lua);
+        check(pxs_Runtime::pxs_Python);
+
+        pxs_finalize();
+    }
+}
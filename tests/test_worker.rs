@@ -0,0 +1,165 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_worker --no-default-features --features "script-worker,testing" -- --nocapture --test-threads=1
+
+#[cfg(all(test, feature = "script-worker"))]
+#[allow(unused)]
+mod tests {
+    use std::{
+        collections::HashMap,
+        ffi::{CStr, CString, c_char},
+        ptr,
+    };
+
+    use etffi::ptr_magic::PtrMagic;
+    use pixelscript::{
+        pxs_addfunc, pxs_addmod, pxs_addvar, pxs_backend_eval, pxs_call_handle, pxs_finalize, pxs_initialize,
+        pxs_newmod, pxs_register_backend, pxs_worker_main, pxs_worker_spawn,
+        shared::{
+            backend::{PXS_CUSTOM_RUNTIME, pxs_BackendVTable},
+            pxs_Opaque,
+            var::{pxs_Var, pxs_VarT},
+        },
+    };
+
+    /// The language of the worker: `double <n>` calls the host, `version` reads a module variable, `crash` aborts.
+    #[derive(Default)]
+    struct Calc {
+        runtime: i64,
+        handles: HashMap<String, i32>,
+        variables: HashMap<String, pxs_Var>,
+    }
+
+    fn calc<'a>(opaque: pxs_Opaque) -> &'a mut Calc {
+        unsafe { &mut *(opaque as *mut Calc) }
+    }
+
+    fn key(name: &str) -> pxs_Var {
+        pxs_Var::new_string(name.to_string())
+    }
+
+    fn items(map: &pxs_Var, field: &str) -> Vec<(String, pxs_Var)> {
+        let list = map.get_map().unwrap().get_item(&key(field)).unwrap().get_list().unwrap();
+        list.vars
+            .iter()
+            .map(|item| {
+                let item = item.get_map().unwrap();
+                let name = item.get_item(&key("name")).unwrap().get_string().unwrap();
+                let value = item.get_item(&key("handle")).or(item.get_item(&key("value"))).unwrap().clone();
+                (name, value)
+            })
+            .collect()
+    }
+
+    unsafe extern "C" fn start(opaque: pxs_Opaque, runtime: i32) {
+        calc(opaque).runtime = runtime as i64;
+    }
+
+    unsafe extern "C" fn add_module(opaque: pxs_Opaque, module: pxs_VarT) {
+        let module = unsafe { pxs_Var::from_borrow(module) };
+        for (name, handle) in items(module, "functions") {
+            calc(opaque).handles.insert(name, handle.get_i64().unwrap() as i32);
+        }
+        for (name, value) in items(module, "variables") {
+            calc(opaque).variables.insert(name, value);
+        }
+    }
+
+    unsafe extern "C" fn eval(opaque: pxs_Opaque, code: *const c_char, _name: *const c_char) -> pxs_VarT {
+        let code = unsafe { CStr::from_ptr(code) }.to_string_lossy().to_string();
+        let calc = calc(opaque);
+        match code.split_once(' ') {
+            Some((name, arg)) => {
+                let args = pxs_Var::new_list_with(vec![pxs_Var::new_i64(arg.parse().unwrap())]).into_raw();
+                let runtime = pxs_Var::new_i64(calc.runtime);
+                pxs_call_handle(&runtime as *const pxs_Var as pxs_VarT, calc.handles[name], args)
+            }
+            None if code == "crash" => std::process::abort(),
+            None if code == "print" => {
+                println!("Printed by the worker");
+                pxs_Var::new_i64(1).into_raw()
+            }
+            None => calc.variables[&code].clone().into_raw(),
+        }
+    }
+
+    /// Started by `run_test` as the worker.
+    #[test]
+    #[ignore]
+    fn worker_child() {
+        pxs_initialize();
+        let mut state = Calc::default();
+        let vtable = pxs_BackendVTable {
+            name: c"calc".as_ptr(),
+            opaque: &mut state as *mut Calc as pxs_Opaque,
+            start: Some(start),
+            stop: None,
+            add_module: Some(add_module),
+            remove_module: None,
+            execute: None,
+            eval: Some(eval),
+            compile: None,
+            exec_object: None,
+            call: None,
+            object_call: None,
+            object_get: None,
+            object_set: None,
+        };
+        let id = pxs_register_backend(&vtable);
+        std::process::exit(pxs_worker_main(id));
+    }
+
+    extern "C" fn double(args: pxs_VarT) -> pxs_VarT {
+        let args = unsafe { pxs_Var::from_borrow(args) };
+        let list = args.get_list().unwrap();
+        // Called with the runtime id of the worker.
+        assert!(list.get_item(0).unwrap().get_i64().unwrap() >= PXS_CUSTOM_RUNTIME as i64);
+        pxs_Var::new_i64(list.get_item(1).unwrap().get_i64().unwrap() * 2).into_raw()
+    }
+
+    fn eval_in(worker: i32, code: &str) -> pxs_Var {
+        let code = CString::new(code).unwrap();
+        pxs_Var::from_raw(pxs_backend_eval(worker, code.as_ptr()))
+    }
+
+    #[test]
+    fn run_test() {
+        pxs_initialize();
+
+        let name = CString::new("math").unwrap();
+        let module = pxs_newmod(name.as_ptr());
+        let func_name = CString::new("double").unwrap();
+        pxs_addfunc(module, func_name.as_ptr(), double);
+        let var_name = CString::new("version").unwrap();
+        pxs_addvar(module, var_name.as_ptr(), pxs_Var::new_string("1.0".to_string()).into_raw());
+        pxs_addmod(module);
+
+        let args = ["--ignored", "--exact", "tests::worker_child", "--nocapture", "--test-threads=1"];
+        let args = pxs_Var::new_list_with(args.iter().map(|a| key(a)).collect());
+        let worker = pxs_worker_spawn(0, ptr::null(), &args as *const pxs_Var as pxs_VarT);
+        assert!(worker >= PXS_CUSTOM_RUNTIME);
+
+        // Host functions and variables are proxied, output is passed through.
+        assert_eq!(eval_in(worker, "double 21").get_i64().unwrap(), 42);
+        assert_eq!(eval_in(worker, "version").get_string().unwrap(), "1.0");
+        assert_eq!(eval_in(worker, "print").get_i64().unwrap(), 1);
+
+        // A crash only takes down the worker.
+        let res = eval_in(worker, "crash");
+        assert!(res.is_exception());
+        assert!(res.get_string().unwrap().contains("crashed"), "{:?}", res.get_string());
+        assert!(eval_in(worker, "double 1").is_exception());
+
+        // Can not be started.
+        let missing = CString::new("/does/not/exist").unwrap();
+        assert_eq!(pxs_worker_spawn(0, missing.as_ptr(), ptr::null_mut()), -1);
+
+        pxs_finalize();
+    }
+}
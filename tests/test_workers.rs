@@ -0,0 +1,148 @@
+// Copyright 2026 Jordan Castro <jordan@grupojvm.com>
+//
+// Licensed under the Apache License, Version 2.0 (the "License"); you may not use this file except in compliance with the License. You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software distributed under the License is distributed on an "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied. See the License for the specific language governing permissions and limitations under the License.
+//
+// cargo test --test test_workers --no-default-features --features "lua,python,js,testing" -- --nocapture --test-threads=1
+
+#[cfg(test)]
+#[allow(unused)]
+mod tests {
+    use std::{
+        ffi::CString,
+        sync::atomic::{AtomicI64, Ordering},
+    };
+
+    use etffi::ptr_magic::PtrMagic;
+    use pixelscript::{
+        pxs_addfunc, pxs_addmod, pxs_addvar, pxs_finalize, pxs_initialize, pxs_newmod, pxs_thread_exec,
+        pxs_thread_join, pxs_thread_spawn,
+        shared::{
+            pxs_Runtime,
+            thread::pxs_Thread,
+            var::{pxs_Var, pxs_VarT},
+        },
+    };
+
+    static TOTAL: AtomicI64 = AtomicI64::new(0);
+
+    fn print_helper(lang: &str) {
+        println!("====================== {lang} ===================");
+    }
+
+    /// `counter.add(n)`, called from every script thread.
+    extern "C" fn add(args: pxs_VarT) -> pxs_VarT {
+        let args = unsafe { pxs_Var::from_borrow(args) };
+        let n = args.get_list().unwrap().get_item(1).unwrap().get_i64().unwrap();
+        pxs_Var::new_i64(TOTAL.fetch_add(n, Ordering::SeqCst) + n).into_raw()
+    }
+
+    fn exec(thread: *mut pxs_Thread, runtime: pxs_Runtime, code: &str) {
+        let code = CString::new(code).unwrap();
+        let name = CString::new("<threads>").unwrap();
+        assert!(pxs_thread_exec(thread, runtime, code.as_ptr(), name.as_ptr()));
+    }
+
+    fn join(thread: *mut pxs_Thread) -> Option<String> {
+        let res = pxs_thread_join(thread);
+        if res.is_null() { None } else { Some(pxs_Var::from_raw(res).get_string().unwrap()) }
+    }
+
+    /// Scripts of one language: add `counter.step`, set a global, check the global is unset, raise `msg`.
+    struct Lang {
+        runtime: pxs_Runtime,
+        add_step: &'static str,
+        set_global: &'static str,
+        no_global: &'static str,
+        raise: fn(&str) -> String,
+        add_one: &'static str,
+    }
+
+    /// Run the same checks with the scripts of `lang`. Starts with `TOTAL` at `start`.
+    fn test_lang(lang: Lang, start: i64) {
+        // Modules are added to every thread, the code runs in parallel.
+        let threads: Vec<_> = (0..4).map(|_| pxs_thread_spawn()).collect();
+        for thread in &threads {
+            for _ in 0..10 {
+                exec(*thread, lang.runtime.clone(), lang.add_step);
+            }
+        }
+        for thread in threads {
+            assert_eq!(join(thread), None);
+        }
+        assert_eq!(TOTAL.load(Ordering::SeqCst), start + 80);
+
+        // Threads have their own VMs.
+        let first = pxs_thread_spawn();
+        let second = pxs_thread_spawn();
+        exec(first, lang.runtime.clone(), lang.set_global);
+        assert_eq!(join(first), None);
+        exec(second, lang.runtime.clone(), lang.no_global);
+        assert_eq!(join(second), None);
+
+        // The first error is returned, later code still runs.
+        let thread = pxs_thread_spawn();
+        exec(thread, lang.runtime.clone(), &(lang.raise)("first"));
+        exec(thread, lang.runtime.clone(), &(lang.raise)("second"));
+        exec(thread, lang.runtime.clone(), lang.add_one);
+        let err = join(thread).unwrap();
+        assert!(err.contains("first"), "{err}");
+        assert_eq!(TOTAL.load(Ordering::SeqCst), start + 81);
+    }
+
+    #[test]
+    fn run_test() {
+        println!();
+        pxs_initialize();
+
+        let name = CString::new("counter").unwrap();
+        let module = pxs_newmod(name.as_ptr());
+        let func_name = CString::new("add").unwrap();
+        pxs_addfunc(module, func_name.as_ptr(), add);
+        let var_name = CString::new("step").unwrap();
+        pxs_addvar(module, var_name.as_ptr(), pxs_Var::new_i64(2).into_raw());
+        pxs_addmod(module);
+
+        print_helper("LUA");
+        test_lang(
+            Lang {
+                runtime: pxs_Runtime::pxs_Lua,
+                add_step: "local counter = require('counter') counter.add(counter.step)",
+                set_global: "shared = 1",
+                no_global: "assert(shared == nil)",
+                raise: |msg| format!("error('{msg}')"),
+                add_one: "require('counter').add(1)",
+            },
+            0,
+        );
+        print_helper("PYTHON");
+        test_lang(
+            Lang {
+                runtime: pxs_Runtime::pxs_Python,
+                add_step: "import counter\ncounter.add(counter.step)",
+                set_global: "shared = 1",
+                no_global: "assert 'shared' not in globals()",
+                raise: |msg| format!("raise Exception('{msg}')"),
+                add_one: "import counter\ncounter.add(1)",
+            },
+            81,
+        );
+        print_helper("JS");
+        test_lang(
+            Lang {
+                runtime: pxs_Runtime::pxs_JavaScript,
+                add_step: "import { add, step } from 'counter';\nadd(step);",
+                set_global: "globalThis.shared = 1;",
+                no_global: "if (globalThis.shared !== undefined) throw new Error('shared');",
+                raise: |msg| format!("throw new Error('{msg}');"),
+                add_one: "import { add } from 'counter';\nadd(1);",
+            },
+            162,
+        );
+
+        pxs_finalize();
+    }
+}